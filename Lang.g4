@@ -5,32 +5,64 @@ LET: 'let';
 FN: 'fn';
 IMPL: 'impl';
 FOR: 'for';
+IN: 'in';
 IF: 'if';
 ELSE: 'else';
 RETURN: 'return';
 WHILE: 'while';
+LOOP: 'loop';
+BREAK: 'break';
+CONTINUE: 'continue';
 TRAIT: 'trait';
 STRUCT: 'struct';
 ENUM: 'enum';
 IMPORT: 'import';
-OR: 'or';
-AND: 'and';
+MATCH: 'match';
+OR: 'or' | '||';
+AND: 'and' | '&&';
 TRUE: 'true';
 FALSE: 'false';
+AS: 'as';
 
 // TODO: how the hell do I lex user defined types??
 TYPE:
-	'i32'
+	'i8'
+	| 'i16'
+	| 'i32'
 	| 'i64'
+	| 'u8'
+	| 'u16'
+	| 'u32'
+	| 'u64'
+	| 'usize'
 	| 'f64'
 	| 'f32'
 	;
-NUMBER: DIGIT+ (DOT DIGIT+)?;
+NUMBER:
+	(
+		'0x' [0-9a-fA-F_]+
+		| '0o' [0-7_]+
+		| '0b' [01_]+
+		| DIGIT (DIGIT | '_')* (DOT DIGIT (DIGIT | '_')*)? ([eE] [+-]? DIGIT (DIGIT | '_')*)?
+	) (INTEGER_SUFFIX | FLOAT_SUFFIX)?;
+FLOAT_SUFFIX: 'f32' | 'f64';
+INTEGER_SUFFIX:
+	'i8'
+	| 'i16'
+	| 'i32'
+	| 'i64'
+	| 'u8'
+	| 'u16'
+	| 'u32'
+	| 'u64'
+	| 'usize';
 DIGIT: [0-9];
 IDENTIFIER: [A-Za-z0-9_]+;
+LABEL: '\'' IDENTIFIER;
 
 // Other Symbols
 RETURN_TYPE: '->';
+FAT_ARROW: '=>';
 COLON: ':';
 RBRACE: '}';
 LBRACE: '{';
@@ -46,7 +78,10 @@ SEMICOLON: ';';
 PATH_SEPARATOR: '::';
 STAR: '*';
 EQUAL: '=';
+DOT_DOT: '..';
+DOT_DOT_EQUAL: '..=';
 DOT: '.';
+QUESTION: '?';
 EQUAL_EQUAL: '==';
 BANG_EQUAL: '!=';
 GREATER: '>';
@@ -56,13 +91,36 @@ LESS_EQUAL: '<=';
 SUB: '-';
 PLUS: '+';
 DIV: '/';
+PERCENT: '%';
 BANG: '!';
+AMPERSAND: '&';
+PIPE: '|';
+CARET: '^';
+TILDE: '~';
+SHIFT_LEFT: '<<';
+SHIFT_RIGHT: '>>';
+COMPOUND_EQUAL:
+	'+='
+	| '-='
+	| '*='
+	| '/='
+	| '%='
+	| '&='
+	| '|='
+	| '^='
+	| '<<='
+	| '>>=';
 RBRACKET: ']';
 LBRACKET: '[';
 
 DUB_QUOTE: '"';
 
-STRING: '"' '.*'? '"';
+STRING: '"' (ESCAPE | ~["\\])* '"' | 'r"' ~["]* '"';
+// Format strings are split into text and arguments by the scanner
+FORMAT_START: 'f"';
+FORMAT_TEXT: ('{{' | '}}' | ESCAPE | ~["\\{}])+;
+FORMAT_SPEC: ':' (~[}] [<^>] | [<^>])? '+'? '#'? '0'? [0-9]* ('.' [0-9]+)? [?xXob]?;
+fragment ESCAPE: '\\' ([nrt0\\'"\n] | 'u{' [0-9a-fA-F]+ '}');
 WS: [ \t\n\r]+ -> skip;
 
 program: declaration* EOF;
@@ -81,19 +139,25 @@ declaration:
 statement:
 	returnStatement
 	| expressionStatement
-	| forStatement
+	| breakStatement
+	| continueStatement
+	| (LABEL COLON)? forStatement
 	| ifStatement
-	| whileStatement
+	| (LABEL COLON)? whileStatement
+	| (LABEL COLON)? loopExpr SEMICOLON?
 	| block
-	| importStatement;
+	| importStatement
+	| ifExpr SEMICOLON?
+	| matchExpr SEMICOLON?;
 
 comment: blockComment | lineComment;
 
-enumDecl: ENUM IDENTIFIER RBRACE enumItemList? LBRACE;
+enumDecl: ENUM IDENTIFIER LBRACE enumItemList? COMMA? RBRACE;
 
 enumItemList: enumItem (COMMA enumItem)*;
 
-enumItem: IDENTIFIER;
+enumItem:
+	IDENTIFIER (LPAREN typeAnnotation (COMMA typeAnnotation)* RPAREN)? (EQUAL expression)?;
 
 blockComment:
 	BLOCK_COMMENT_BEGIN (blockComment | ANY) BLOCK_COMMENT_END;
@@ -119,50 +183,93 @@ implTrait:
 	IMPL IDENTIFIER FOR IDENTIFIER LBRACE functionDecl* RBRACE;
 forStatement:
 	FOR LPAREN (letDecl | expressionStatement | SEMICOLON) expression? SEMICOLON expression? RPAREN
-		statement;
+		statement
+	| FOR IDENTIFIER IN expression block;
 ifStatement:
 	IF LPAREN expression? RPAREN statement (ELSE statement)?;
 returnStatement: RETURN expression? SEMICOLON;
 whileStatement: WHILE LPAREN expression RPAREN statement;
-block: LBRACE declaration? RBRACE;
+loopExpr: LOOP block;
+breakStatement: BREAK LABEL? expression? SEMICOLON;
+continueStatement: CONTINUE LABEL? SEMICOLON;
+block: LBRACE declaration* expression? RBRACE;
 traitDecl: TRAIT IDENTIFIER LBRACE traitFunctionDecl* RBRACE;
 traitFunctionDecl:
-	FN IDENTIFIER LPAREN parameters? RPAREN RETURN_TYPE TYPE SEMICOLON;
+	FN IDENTIFIER LPAREN parameters? RPAREN RETURN_TYPE typeAnnotation SEMICOLON;
 structDecl: STRUCT IDENTIFIER LBRACE structFields* RBRACE;
-structFields: IDENTIFIER COLON TYPE COMMA?;
+structFields: IDENTIFIER COLON typeAnnotation (EQUAL expression)? COMMA?;
 letDecl:
-	LET IDENTIFIER COLON TYPE (EQUAL expression)? SEMICOLON;
+	LET IDENTIFIER (COLON typeAnnotation)? (EQUAL expression)? SEMICOLON
+	| LET LPAREN IDENTIFIER (COMMA IDENTIFIER)* RPAREN EQUAL expression SEMICOLON;
 implDecl: IMPL IDENTIFIER LBRACE functionDecl* RBRACE;
 expression: assignment;
-assignment: (call DOT)? IDENTIFIER EQUAL assignment | logicOr;
+assignment:
+	(call DOT)? IDENTIFIER (EQUAL | COMPOUND_EQUAL) assignment
+	| range;
+range: logicOr ((DOT_DOT | DOT_DOT_EQUAL) logicOr)?;
 logicOr: logicAnd (OR logicAnd)*;
 logicAnd: equality (AND equality)*;
 equality: comparison ((BANG_EQUAL | EQUAL_EQUAL) comparison)*;
 comparison:
-	addition (
-		(GREATER | GREATER_EQUAL | LESS | LESS_EQUAL) addition
-	)*;
+	bitOr ((GREATER | GREATER_EQUAL | LESS | LESS_EQUAL) bitOr)*;
+bitOr: bitXor (PIPE bitXor)*;
+bitXor: bitAnd (CARET bitAnd)*;
+bitAnd: shift (AMPERSAND shift)*;
+shift: addition ((SHIFT_LEFT | SHIFT_RIGHT) addition)*;
 addition: multiplication ((SUB | PLUS) multiplication)*;
-multiplication: unary ((DIV | STAR) unary)*;
-unary: (BANG | SUB) unary | call;
-call: primary (LPAREN arguments? RPAREN | DOT IDENTIFIER)*;
+multiplication: cast ((DIV | STAR | PERCENT) cast)*;
+cast: unary (AS typeAnnotation)*;
+unary: (BANG | SUB | TILDE) unary | call;
+call: primary (LPAREN arguments? RPAREN | DOT IDENTIFIER | DOT NUMBER | QUESTION)*;
 arguments: expression (COMMA expression)*;
 primary:
 	TRUE
 	| FALSE
 	| NUMBER
+	| formatString
 	| STRING 
 	| LPAREN expression RPAREN
+	| LPAREN expression (COMMA expression)+ RPAREN
 	| IDENTIFIER indexExpression
 	| arrayExpr
+	| structLiteral
 	| IDENTIFIER
-	| enumPrimary;
+	| enumPrimary
+	| ifExpr
+	| block
+	| (LABEL COLON)? loopExpr
+	| matchExpr;
+formatString:
+	FORMAT_START (FORMAT_TEXT | LBRACE expression FORMAT_SPEC? RBRACE)* DUB_QUOTE;
+ifExpr: IF LPAREN expression RPAREN block (ELSE (ifExpr | block))?;
+matchExpr: MATCH expression LBRACE (matchArm COMMA?)* RBRACE;
+matchArm: pattern FAT_ARROW (block | expression);
+pattern:
+	IDENTIFIER PATH_SEPARATOR IDENTIFIER (
+		LPAREN pattern (COMMA pattern)* RPAREN
+	)?
+	| TRUE
+	| FALSE
+	| NUMBER
+	| STRING
+	| IDENTIFIER;
+structLiteral:
+	IDENTIFIER LBRACE (
+		IDENTIFIER COLON expression (COMMA IDENTIFIER COLON expression)* COMMA?
+	)? RBRACE;
 arrayExpr: LBRACKET arrayElements? RBRACKET;
 arrayElements: (expression (COMMA expression)*);
 indexExpression: LBRACKET expression RBRACKET;
 enumPrimary: IDENTIFIER (PATH_SEPARATOR IDENTIFIER)*;
 functionDecl: FN function;
 function:
-	IDENTIFIER LPAREN parameters? RPAREN RETURN_TYPE TYPE block;
+	IDENTIFIER LPAREN parameters? RPAREN RETURN_TYPE typeAnnotation block;
 parameters:
-	IDENTIFIER COLON TYPE (COMMA IDENTIFIER COLON TYPE)*;
+	IDENTIFIER COLON typeAnnotation (COMMA IDENTIFIER COLON typeAnnotation)*;
+typeAnnotation:
+	TYPE
+	| IDENTIFIER
+	| LPAREN typeAnnotation (COMMA typeAnnotation)+ RPAREN
+	| FN LPAREN (typeAnnotation (COMMA typeAnnotation)*)? RPAREN (
+		RETURN_TYPE typeAnnotation
+	)?;
@@ -7,347 +7,4244 @@ extern crate lang;
 use self::lang::lang::Lang;
 use criterion::{black_box, Criterion};
 
+fn array_index_with_unsigned_types(c: &mut Criterion) {
+    c.bench_function("Array index with unsigned types", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let values = [10, 20, 30];
+        let i: usize = 2;
+        let j: u8 = 1;
+        assert(values[i] == 30);
+        assert(values[j] == 20);
+        values[0usize] = 5;
+        assert(values[0] == 5);
+        ",
+            )))
+        })
+    });
+}
+fn array_literal_element_type_failure(c: &mut Criterion) {
+    c.bench_function("Array literal element type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: Array<i64> = [\"x\"];
+        ",
+            )))
+        })
+    });
+}
+fn array_negative_index_assignment_failure(c: &mut Criterion) {
+    c.bench_function("Array negative index assignment failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = [1, 2];
+        let i = -1;
+        a[i] = 3;
+        ",
+            )))
+        })
+    });
+}
+fn array_negative_index_failure(c: &mut Criterion) {
+    c.bench_function("Array negative index failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = [1, 2];
+        let i = -1;
+        print a[i];
+        ",
+            )))
+        })
+    });
+}
 fn array_i64_variable_declaration(c: &mut Criterion) {
     c.bench_function("Array<i64> Variable declaration", |b| {
         b.iter(|| Lang::new(black_box(Some("let i: Array<i64>;"))))
     });
 }
-fn array_i64_variable_declaration_and_assignment(c: &mut Criterion) {
-    c.bench_function("Array<i64> Variable declaration and assignment", |b| {
+fn array_i64_variable_declaration_and_assignment(c: &mut Criterion) {
+    c.bench_function("Array<i64> Variable declaration and assignment", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let i: Array<i64> = [0, 1, 2];
+        assert(i[0] == 0);
+        assert(i[1] == 1);
+        assert(i[2] == 2);
+        ",
+            )))
+        })
+    });
+}
+fn array_i64_variable_declaration_empty(c: &mut Criterion) {
+    c.bench_function("Array<i64> Variable declaration empty", |b| {
+        b.iter(|| Lang::new(black_box(Some("let i: Array<i64> = [];"))))
+    });
+}
+fn array_i64_variable_re_assignment(c: &mut Criterion) {
+    c.bench_function("Array<i64> Variable re-assignment", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let i: Array<i64> = [];
+        i = [0, 1, 2];
+        assert(i[0] == 0);
+        assert(i[1] == 1);
+        assert(i[2] == 2);
+        ",
+            )))
+        })
+    });
+}
+fn array_i64_variable_re_assignment_failure(c: &mut Criterion) {
+    c.bench_function("Array<i64> Variable re-assignment failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "let i: Array<i64> = [];
+            i = [0.00, 1.00, 2.00];
+        assert(i[0] == 0.00);
+        assert(i[1] == 1.00);
+        assert(i[2] == 2.00);
+            ",
+            )))
+        })
+    });
+}
+fn assertion(c: &mut Criterion) {
+    c.bench_function("Assertion", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        assert(100 == 100);
+        assert(true == true);
+        assert(1.05 == 1.05);
+        ",
+            )))
+        })
+    });
+}
+fn assertion_failure(c: &mut Criterion) {
+    c.bench_function("Assertion failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        assert(0 == 100);
+        ",
+            )))
+        })
+    });
+}
+fn assertion_inside_a_function_failure(c: &mut Criterion) {
+    c.bench_function("Assertion inside a function failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn check(n: i32) -> () {
+            assert(n > 0);
+        }
+        check(-1);
+        ",
+            )))
+        })
+    });
+}
+fn bitwise_not_on_floats_failure(c: &mut Criterion) {
+    c.bench_function("Bitwise not on floats failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = ~1.5;
+        ",
+            )))
+        })
+    });
+}
+fn bitwise_operator_precedence(c: &mut Criterion) {
+    c.bench_function("Bitwise operator precedence", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        assert(1 + 2 << 1 == 6);
+        assert(6 & 3 == 2);
+        assert(1 | 2 ^ 3 & 4 == 3);
+        assert(2 * 3 % 4 == 2);
+        assert(true && false || true);
+        ",
+            )))
+        })
+    });
+}
+fn bitwise_operators(c: &mut Criterion) {
+    c.bench_function("Bitwise operators", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        assert((6 & 3) == 2);
+        assert((6 | 3) == 7);
+        assert((6 ^ 3) == 5);
+        assert(~0 == -1);
+        let a: u8 = 5;
+        assert(~a == 250u8);
+        let b: u8 = 12;
+        let c: i64 = 10;
+        assert((b | c) == 14);
+        ",
+            )))
+        })
+    });
+}
+fn bitwise_operators_on_floats_failure(c: &mut Criterion) {
+    c.bench_function("Bitwise operators on floats failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 1.0 & 2.0;
+        ",
+            )))
+        })
+    });
+}
+fn block_expression(c: &mut Criterion) {
+    c.bench_function("Block expression", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 2;
+        let b: i32 = {
+            let a: i32 = 10;
+            a * 3
+        };
+        assert(a == 2);
+        assert(b == 30);
+        let c: i32 = { a } + { b };
+        assert(c == 32);
+        ",
+            )))
+        })
+    });
+}
+fn break_outside_of_a_loop_failure(c: &mut Criterion) {
+    c.bench_function("Break outside of a loop failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 1;
+        break;
+        ",
+            )))
+        })
+    });
+}
+fn break_to_an_unknown_label_failure(c: &mut Criterion) {
+    c.bench_function("Break to an unknown label failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        while (true) {
+            break 'missing;
+        }
+        ",
+            )))
+        })
+    });
+}
+fn break_with_a_value_out_of_a_while_failure(c: &mut Criterion) {
+    c.bench_function("Break with a value out of a while failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        while (true) {
+            break 1;
+        }
+        ",
+            )))
+        })
+    });
+}
+fn closure_outlives_its_block(c: &mut Criterion) {
+    c.bench_function("Closure outlives its block", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn zero() -> i32 {
+            return 0;
+        }
+        let f: fn() -> i32 = zero;
+        {
+            let hidden: i32 = 7;
+            fn reveal() -> i32 {
+                return hidden;
+            }
+            f = reveal;
+        }
+        assert(f() == 7);
+        ",
+            )))
+        })
+    });
+}
+fn closure_returned_from_a_function(c: &mut Criterion) {
+    c.bench_function("Closure returned from a function", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn make_adder(n: i32) -> fn(i32) -> i32 {
+            fn add(x: i32) -> i32 {
+                return x + n;
+            }
+            return add;
+        }
+        fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+            return f(x);
+        }
+        let add5 = make_adder(5);
+        let add10: fn(i32) -> i32 = make_adder(10);
+        assert(add5(3) == 8);
+        assert(add10(3) == 13);
+        assert(apply(add5, 1) == 6);
+        assert(make_adder(1)(1) == 2);
+        ",
+            )))
+        })
+    });
+}
+fn closure_sees_the_binding_in_scope_at_its_declaration(c: &mut Criterion) {
+    c.bench_function(
+        "Closure sees the binding in scope at its declaration",
+        |b| {
+            b.iter(|| {
+                Lang::new(black_box(Some(
+                    "
+        let a: i32 = 1;
+        {
+            fn show() -> i32 {
+                return a;
+            }
+            assert(show() == 1);
+            let a: i32 = 2;
+            assert(show() == 1);
+            assert(a == 2);
+        }
+        ",
+                )))
+            })
+        },
+    );
+}
+fn closures_created_in_a_long_loop(c: &mut Criterion) {
+    c.bench_function("Closures created in a long loop", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn make_adder(n: i32) -> fn(i32) -> i32 {
+            fn add(x: i32) -> i32 {
+                return x + n;
+            }
+            return add;
+        }
+        let first = make_adder(100);
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 500) {
+            let adder = make_adder(i);
+            total = total + adder(1);
+            i = i + 1;
+        }
+        assert(total == 125250);
+        assert(first(1) == 101);
+        ",
+            )))
+        })
+    });
+}
+fn comments(c: &mut Criterion) {
+    c.bench_function("Comments", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        // A line comment
+        /// A doc comment
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b; // trailing comment
+        }
+        /* A block comment
+           spanning lines /* with a nested comment */
+        */
+        /** A block doc comment */
+        let sum: i32 = add(1, /* inline */ 2);
+        assert(sum == 3);
+        ",
+            )))
+        })
+    });
+}
+fn compound_assignment(c: &mut Criterion) {
+    c.bench_function("Compound assignment", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let i = 0;
+        i += 5;
+        i -= 1;
+        i *= 3;
+        i /= 2;
+        i %= 4;
+        assert(i == 2);
+        i <<= 3;
+        i |= 1;
+        i ^= 3;
+        i &= 30;
+        i >>= 1;
+        assert(i == 9);
+        let s = \"a\";
+        s += \"b\";
+        assert(s == \"ab\");
+        ",
+            )))
+        })
+    });
+}
+fn compound_assignment_element_type_mismatch_failure(c: &mut Criterion) {
+    c.bench_function("Compound assignment element type mismatch failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let values = [1, 2];
+        values[0] += 1.5;
+        ",
+            )))
+        })
+    });
+}
+fn compound_assignment_evaluates_its_target_once(c: &mut Criterion) {
+    c.bench_function("Compound assignment evaluates its target once", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Counter { count: i32 }
+        struct Calls { count: i32 }
+        let calls = Calls { count: 0 };
+        let counter = Counter { count: 1 };
+        fn target(calls: Calls, counter: Counter) -> Counter {
+            calls.count += 1;
+            return counter;
+        }
+        fn next(calls: Calls) -> i32 {
+            calls.count += 1;
+            return calls.count - 1;
+        }
+        target(calls, counter).count += 5;
+        assert(counter.count == 6);
+        assert(calls.count == 1);
+        let values = [10, 20, 30];
+        values[next(calls)] *= 2;
+        assert(values[1] == 40);
+        assert(calls.count == 2);
+        let totals: Map<String, i32> = [\"a\": 1];
+        totals[\"a\"] += 2;
+        assert(totals[\"a\"] == 3);
+        ",
+            )))
+        })
+    });
+}
+fn compound_assignment_invalid_target_failure(c: &mut Criterion) {
+    c.bench_function("Compound assignment invalid target failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 1;
+        a + 1 += 2;
+        ",
+            )))
+        })
+    });
+}
+fn compound_assignment_to_a_field_of_an_overloaded_type(c: &mut Criterion) {
+    c.bench_function(
+        "Compound assignment to a field of an overloaded type",
+        |b| {
+            b.iter(|| {
+                Lang::new(black_box(Some(
+                    "
+        struct Vec2 { x: i32 }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x };
+            }
+        }
+        struct Body { position: Vec2 }
+        let body = Body { position: Vec2 { x: 1 } };
+        body.position += Vec2 { x: 2 };
+        let position = body.position;
+        assert(position.x == 3);
+        ",
+                )))
+            })
+        },
+    );
+}
+fn compound_assignment_to_fields_and_elements(c: &mut Criterion) {
+    c.bench_function("Compound assignment to fields and elements", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Counter { count: i32 }
+        let counter = Counter { count: 1 };
+        counter.count += 10;
+        counter.count <<= 1;
+        assert(counter.count == 22);
+        let values = [1, 2, 3];
+        values[1] += 40;
+        values[2] *= values[1];
+        assert(values[1] == 42);
+        assert(values[2] == 126);
+        ",
+            )))
+        })
+    });
+}
+fn compound_assignment_type_mismatch_failure(c: &mut Criterion) {
+    c.bench_function("Compound assignment type mismatch failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 1;
+        a += \"x\";
+        ",
+            )))
+        })
+    });
+}
+fn continue_in_while_and_for_loops(c: &mut Criterion) {
+    c.bench_function("Continue in while and for loops", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let i: i32 = 0;
+        let odd_sum: i32 = 0;
+        while (i < 10) {
+            i += 1;
+            if (i % 2 == 0) {
+                continue;
+            }
+            odd_sum += i;
+        }
+        assert(odd_sum == 25);
+        let even_sum: i32 = 0;
+        for (let j: i32 = 0; j < 10; j += 1) {
+            if (j % 2 == 1) {
+                continue;
+            }
+            even_sum += j;
+        }
+        assert(even_sum == 20);
+        ",
+            )))
+        })
+    });
+}
+fn continue_outside_of_a_loop_failure(c: &mut Criterion) {
+    c.bench_function("Continue outside of a loop failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn skip() -> () {
+            continue;
+        }
+        while (true) {
+            skip();
+            break;
+        }
+        ",
+            )))
+        })
+    });
+}
+fn digit_separators_and_exponents(c: &mut Criterion) {
+    c.bench_function("Digit separators and exponents", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let big: i64 = 1_000_000_000_000;
+        assert(big == 1000000000000i64);
+        assert(1.5e-3 == 0.0015);
+        assert(2E3 == 2000.0);
+        assert(1_0.2_5 == 10.25);
+        ",
+            )))
+        })
+    });
+}
+fn division_by_zero_failure(c: &mut Criterion) {
+    c.bench_function("Division by zero failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 1;
+        let b = a / 0;
+        ",
+            )))
+        })
+    });
+}
+fn empty_arrays_of_function_values(c: &mut Criterion) {
+    c.bench_function("Empty arrays of function values", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn one() -> i32 {
+            return 1;
+        }
+        fn two() -> i32 {
+            return 2;
+        }
+        let handlers: Array<fn() -> i32> = [];
+        let sum: i32 = 0;
+        for handler in handlers {
+            sum += handler();
+        }
+        assert(sum == 0);
+        handlers = [one, two];
+        for handler in handlers {
+            sum += handler();
+        }
+        assert(sum == 3);
+        ",
+            )))
+        })
+    });
+}
+fn empty_arrays_of_tuples(c: &mut Criterion) {
+    c.bench_function("Empty arrays of tuples", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let pairs: Array<(i32, i32)> = [];
+        let names: Array<(String, Array<i32>)> = [];
+        let count: i32 = 0;
+        for pair in pairs {
+            count += 1;
+        }
+        for name in names {
+            count += 1;
+        }
+        assert(count == 0);
+        pairs = [(1, 2), (3, 4)];
+        assert(pairs[1].0 == 3);
+        ",
+            )))
+        })
+    });
+}
+fn enum_as_function_argument_and_return_type(c: &mut Criterion) {
+    c.bench_function("Enum as function argument and return type", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Color {
+            Red,
+            Blue
+        }
+        fn is_red(color: Color) -> bool {
+            return color == Color::Red;
+        }
+        fn favourite() -> Color {
+            return Color::Blue;
+        }
+        assert(is_red(Color::Red));
+        assert(!is_red(favourite()));
+        ",
+            )))
+        })
+    });
+}
+fn enum_declaration(c: &mut Criterion) {
+    c.bench_function("Enum declaration", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+        ",
+            )))
+        })
+    });
+}
+fn enum_duplicate_variant_failure(c: &mut Criterion) {
+    c.bench_function("Enum duplicate variant failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Color {
+            Red,
+            Red
+        }
+        ",
+            )))
+        })
+    });
+}
+fn enum_type_mismatch_failure(c: &mut Criterion) {
+    c.bench_function("Enum type mismatch failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Color {
+            Red
+        }
+        let color: Color = 1;
+        ",
+            )))
+        })
+    });
+}
+fn enum_undefined_variant_failure(c: &mut Criterion) {
+    c.bench_function("Enum undefined variant failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Color {
+            Red
+        }
+        let color: Color = Color::Purple;
+        ",
+            )))
+        })
+    });
+}
+fn enum_variant_comparison(c: &mut Criterion) {
+    c.bench_function("Enum variant comparison", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Color {
+            Red,
+            Green,
+            Blue
+        }
+        let color: Color = Color::Red;
+        assert(color == Color::Red);
+        assert(color != Color::Green);
+        color = Color::Blue;
+        assert(color == Color::Blue);
+        print color;
+        ",
+            )))
+        })
+    });
+}
+fn enum_variant_with_initializer(c: &mut Criterion) {
+    c.bench_function("Enum variant with initializer", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Status {
+            Ok = 200,
+            NotFound = 404,
+            Teapot
+        }
+        assert(Status::Ok < Status::NotFound);
+        assert(Status::NotFound < Status::Teapot);
+        ",
+            )))
+        })
+    });
+}
+fn enum_variant_with_values(c: &mut Criterion) {
+    c.bench_function("Enum variant with values", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Shape {
+            Circle(f64),
+            Rect(i32, i32),
+            Empty
+        }
+        let rect: Shape = Shape::Rect(2, 3);
+        assert(rect == Shape::Rect(2, 3));
+        assert(rect != Shape::Rect(3, 2));
+        assert(rect != Shape::Empty);
+        print rect;
+        ",
+            )))
+        })
+    });
+}
+fn enum_variant_with_values_arity_failure(c: &mut Criterion) {
+    c.bench_function("Enum variant with values arity failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Shape {
+            Rect(i32, i32)
+        }
+        let rect: Shape = Shape::Rect(1);
+        ",
+            )))
+        })
+    });
+}
+fn enum_variant_with_values_wrong_type_failure(c: &mut Criterion) {
+    c.bench_function("Enum variant with values wrong type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Shape {
+            Circle(f64)
+        }
+        let circle: Shape = Shape::Circle(\"round\");
+        ",
+            )))
+        })
+    });
+}
+fn float_arithmetic(c: &mut Criterion) {
+    c.bench_function("Float arithmetic", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: f32 = 1.5;
+        let b: f32 = a * 2.0 as f32 - 0.5 as f32;
+        assert(b == 2.5);
+        let c: f64 = 9.0;
+        let d: f64 = c / 4.0;
+        assert(d == 2.25);
+        ",
+            )))
+        })
+    });
+}
+fn float_suffixes(c: &mut Criterion) {
+    c.bench_function("Float suffixes", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 2.0f32;
+        let b: f32 = a;
+        let c = 5f64;
+        let d: f64 = c;
+        let e = 1e2f32;
+        assert(e == 100.0f32);
+        ",
+            )))
+        })
+    });
+}
+fn for_in_break_and_continue(c: &mut Criterion) {
+    c.bench_function("For in break and continue", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let sum: i32 = 0;
+        for i in 0..100 {
+            if (i % 2 == 0) {
+                continue;
+            }
+            if (i > 9) {
+                break;
+            }
+            sum += i;
+        }
+        assert(sum == 25);
+        ",
+            )))
+        })
+    });
+}
+fn for_in_over_a_map(c: &mut Criterion) {
+    c.bench_function("For in over a map", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let stock: Map<String, i32> = [\"apples\": 3, \"pears\": 5];
+        let total: i32 = 0;
+        let names = \"\";
+        for name in stock {
+            names += name;
+            total += stock[name];
+            stock[name] = 0;
+        }
+        assert(names == \"applespears\");
+        assert(total == 8);
+        assert(stock[\"pears\"] == 0);
+        ",
+            )))
+        })
+    });
+}
+fn for_in_over_a_map_key_type_failure(c: &mut Criterion) {
+    c.bench_function("For in over a map key type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let stock: Map<String, i32> = [\"apples\": 3];
+        for name in stock {
+            let count: i32 = name;
+        }
+        ",
+            )))
+        })
+    });
+}
+fn for_in_over_a_string(c: &mut Criterion) {
+    c.bench_function("For in over a string", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let count: i32 = 0;
+        let last: char = 'a';
+        for c in \"hello\" {
+            count += 1;
+            last = c;
+        }
+        assert(count == 5);
+        assert(last == 'o');
+        ",
+            )))
+        })
+    });
+}
+fn for_in_over_an_array(c: &mut Criterion) {
+    c.bench_function("For in over an array", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let xs = [1, 2, 3, 4];
+        let sum: i32 = 0;
+        for x in xs {
+            sum += x;
+        }
+        assert(sum == 10);
+        ",
+            )))
+        })
+    });
+}
+fn for_in_over_an_iterator(c: &mut Criterion) {
+    c.bench_function("For in over an iterator", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Countdown {
+            from: i32,
+        }
+        impl Iterator<i32> for Countdown {
+            fn next(self: Countdown) -> Option<i32> {
+                if (self.from == 0) {
+                    return Option::None;
+                }
+                self.from -= 1;
+                return Option::Some(self.from + 1);
+            }
+        }
+        let seen: i32 = 0;
+        let sum: i32 = 0;
+        for n in Countdown { from: 3 } {
+            seen += 1;
+            sum += n;
+        }
+        assert(seen == 3);
+        assert(sum == 6);
+        ",
+            )))
+        })
+    });
+}
+fn for_in_over_ranges(c: &mut Criterion) {
+    c.bench_function("For in over ranges", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let sum: i32 = 0;
+        for i in 0..5 {
+            sum += i;
+        }
+        assert(sum == 10);
+        let inclusive: i32 = 0;
+        for i in 1..=5 {
+            inclusive += i;
+        }
+        assert(inclusive == 15);
+        let empty: i32 = 0;
+        for i in 5..5 {
+            empty += 1;
+        }
+        assert(empty == 0);
+        ",
+            )))
+        })
+    });
+}
+fn for_in_variable_is_scoped_to_its_body(c: &mut Criterion) {
+    c.bench_function("For in variable is scoped to its body", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let x: i32 = 10;
+        for x in 0..3 {
+            assert(x < 3);
+        }
+        assert(x == 10);
+        ",
+            )))
+        })
+    });
+}
+fn for_in_variable_type_failure(c: &mut Criterion) {
+    c.bench_function("For in variable type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        for c in \"abc\" {
+            let i: i32 = c;
+        }
+        ",
+            )))
+        })
+    });
+}
+fn for_loop(c: &mut Criterion) {
+    c.bench_function("For loop", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        for (let i: i32 = 0; i < 10; i = i + 1) {
+            print i;
+        }
+        for (let b: bool = false; b == true; b = false) {
+            print b;
+        }
+        ",
+            )))
+        })
+    });
+}
+fn format_string_debug(c: &mut Criterion) {
+    c.bench_function("Format string debug", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+            y: f64,
+            label: String,
+        }
+        enum Shape { Circle(f64), Empty }
+        let p: Point = Point { x: 1, y: 2.0, label: \"origin\" };
+        assert(f\"{p:?}\" == \"Point { x: 1, y: 2.0, label: \\\"origin\\\" }\");
+        assert(f\"{p}\" == \"Point\");
+        assert(f\"{[1, 2, 3]:?} {[1, 2, 3]}\" == \"[1, 2, 3] 1 2 3\");
+        assert(f\"{(1, \"a\"):?} {(1, \"a\")}\" == \"(1, \\\"a\\\") (1, a)\");
+        assert(f\"{Shape::Circle(1.5):?} {Shape::Empty:?}\" == \"Shape::Circle(1.5) Shape::Empty\");
+        assert(f\"{'c':?} {'c'} {1.0:?} {1.0} {true:?}\" == \"'c' c 1.0 1 true\");
+        assert(f\"{[1: \"a\"]:?}\" == \"[1: \\\"a\\\"]\");
+        ",
+            )))
+        })
+    });
+}
+fn format_string_display_trait(c: &mut Criterion) {
+    c.bench_function("Format string display trait", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        impl Display for Point {
+            fn fmt(self: Point) -> String {
+                return f\"({self.x}, {self.y})\";
+            }
+        }
+        let p: Point = Point { x: 1, y: 2 };
+        assert(f\"p = {p}\" == \"p = (1, 2)\");
+        assert(f\"[{p:>8}]\" == \"[  (1, 2)]\");
+        assert(f\"{p:?}\" == \"Point { x: 1, y: 2 }\");
+        ",
+            )))
+        })
+    });
+}
+fn format_string_interpolation(c: &mut Criterion) {
+    c.bench_function("Format string interpolation", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let x: i32 = 3;
+        let name: String = \"lang\";
+        assert(f\"x = {x}, name = {name}\" == \"x = 3, name = lang\");
+        assert(f\"{x + 1} {x * x}\" == \"4 9\");
+        assert(f\"{{x}} {{{x}}}\" == \"{x} {3}\");
+        assert(f\"{f\"{x}{x}\"}!\" == \"33!\");
+        assert(f\"\" == \"\");
+        ",
+            )))
+        })
+    });
+}
+fn format_string_radix(c: &mut Criterion) {
+    c.bench_function("Format string radix", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let x: i32 = 255;
+        assert(f\"{x:x} {x:X} {x:#x}\" == \"ff FF 0xff\");
+        assert(f\"{x:b} {8:o} {5:#06b}\" == \"11111111 10 0b0101\");
+        assert(f\"{-1i8:x} {-2:X}\" == \"ff FFFFFFFE\");
+        ",
+            )))
+        })
+    });
+}
+fn format_string_radix_of_a_float_failure(c: &mut Criterion) {
+    c.bench_function("Format string radix of a float failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let y: f64 = 1.5;
+        print f\"{y:x}\";
+        ",
+            )))
+        })
+    });
+}
+fn format_string_unknown_spec_failure(c: &mut Criterion) {
+    c.bench_function("Format string unknown spec failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        print f\"{1:q}\";
+        ",
+            )))
+        })
+    });
+}
+fn format_string_unmatched_brace_failure(c: &mut Criterion) {
+    c.bench_function("Format string unmatched brace failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        print f\"a } b\";
+        ",
+            )))
+        })
+    });
+}
+fn format_string_width_precision_and_alignment(c: &mut Criterion) {
+    c.bench_function("Format string width precision and alignment", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let x: i32 = 42;
+        let y: f64 = 3.14159;
+        assert(f\"{y:.2}\" == \"3.14\");
+        assert(f\"[{x:6}]\" == \"[    42]\");
+        assert(f\"[{x:<6}]\" == \"[42    ]\");
+        assert(f\"[{x:^6}]\" == \"[  42  ]\");
+        assert(f\"[{x:*^7}]\" == \"[**42***]\");
+        assert(f\"[{x:+}] [{x:06}] [{-x:06}]\" == \"[+42] [000042] [-00042]\");
+        assert(f\"[{y:8.3}]\" == \"[   3.142]\");
+        assert(f\"[{\"ab\":5}] [{\"abc\":.2}] [{\"ab\":>4}]\" == \"[ab   ] [ab] [  ab]\");
+        ",
+            )))
+        })
+    });
+}
+fn function_assert_failure(c: &mut Criterion) {
+    c.bench_function("Function assert failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn test(a: i32, b: f64) -> bool {
+            if (!(a > 1000)) {
+                assert(false);
+            }
+            return false;
+        }
+        test(1, 0.0);
+        ",
+            )))
+        })
+    });
+}
+fn function_early_return_before_trailing_expression(c: &mut Criterion) {
+    c.bench_function("Function early return before trailing expression", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn clamp(n: i32) -> i32 {
+            if (n > 10) {
+                return 10;
+            }
+            n
+        }
+        fn pick(first: bool) -> i32 {
+            if (first) {
+                return 1;
+            } else {
+                return 2;
+            }
+        }
+        assert(clamp(42) == 10);
+        assert(clamp(3) == 3);
+        assert(pick(false) == 2);
+        ",
+            )))
+        })
+    });
+}
+fn function_falling_off_its_end_failure(c: &mut Criterion) {
+    c.bench_function("Function falling off its end failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn sign(x: i32) -> i32 {
+            if (x > 0) {
+                return 1;
+            }
+        }
+        let s: i32 = sign(-5);
+        ",
+            )))
+        })
+    });
+}
+fn function_leaving_a_loop_without_returning_failure(c: &mut Criterion) {
+    c.bench_function("Function leaving a loop without returning failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn first_even(a: Array<i32>) -> i32 {
+            while (true) {
+                for x in a {
+                    if (x % 2 == 0) {
+                        return x;
+                    }
+                }
+                break;
+            }
+        }
+        ",
+            )))
+        })
+    });
+}
+fn function_nested_return(c: &mut Criterion) {
+    c.bench_function("Function nested return", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn test() -> bool {
+            {
+                {
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+        assert(false == test());
+        ",
+            )))
+        })
+    });
+}
+fn function_returns_bool(c: &mut Criterion) {
+    c.bench_function("Function returns bool", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn test() -> bool {
+            return false;
+        }
+        assert(false == test());
+        ",
+            )))
+        })
+    });
+}
+fn function_returns_bool_with_args(c: &mut Criterion) {
+    c.bench_function("Function returns bool with args", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn test(a: i32, b: f64) -> bool {
+            return false;
+        }
+        assert(false == test(100, 100.00));
+        ",
+            )))
+        })
+    });
+}
+fn function_trailing_expression_return(c: &mut Criterion) {
+    c.bench_function("Function trailing expression return", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn square(n: i32) -> i32 {
+            n * n
+        }
+        fn sign(n: i32) -> i32 {
+            if (n > 0) {
+                1
+            } else if (n < 0) {
+                -1
+            } else {
+                0
+            }
+        }
+        fn describe(n: i32) -> String {
+            let prefix: String = \"n is \";
+            match sign(n) {
+                1 => prefix + \"positive\",
+                0 => prefix + \"zero\",
+                _ => prefix + \"negative\",
+            }
+        }
+        assert(square(5) == 25);
+        assert(sign(-4) == -1);
+        assert(sign(0) == 0);
+        assert(describe(3) == \"n is positive\");
+        ",
+            )))
+        })
+    });
+}
+fn function_type_argument_failure(c: &mut Criterion) {
+    c.bench_function("Function type argument failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+            return f(x);
+        }
+        fn is_positive(x: i32) -> bool {
+            return x > 0;
+        }
+        apply(is_positive, 1);
+        ",
+            )))
+        })
+    });
+}
+fn function_type_call_argument_failure(c: &mut Criterion) {
+    c.bench_function("Function type call argument failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn apply(f: fn(i32) -> i32) -> i32 {
+            return f(true);
+        }
+        ",
+            )))
+        })
+    });
+}
+fn function_type_mismatch_failure(c: &mut Criterion) {
+    c.bench_function("Function type mismatch failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn zero() -> i32 {
+            return 0;
+        }
+        let f: fn(i32) -> bool = zero;
+        ",
+            )))
+        })
+    });
+}
+fn functions_returning_on_every_path(c: &mut Criterion) {
+    c.bench_function("Functions returning on every path", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Sign {
+            Negative,
+            Zero,
+            Positive
+        }
+        fn sign(x: i32) -> Sign {
+            if (x > 0) {
+                return Sign::Positive;
+            } else if (x < 0) {
+                return Sign::Negative;
+            } else {
+                return Sign::Zero;
+            }
+        }
+        fn value(s: Sign) -> i32 {
+            match s {
+                Sign::Negative => {
+                    return -1;
+                }
+                Sign::Zero => {
+                    return 0;
+                }
+                Sign::Positive => {
+                    return 1;
+                }
+            }
+        }
+        fn first_over(a: Array<i32>, limit: i32) -> i32 {
+            loop {
+                for x in a {
+                    if (x > limit) {
+                        return x;
+                    }
+                    break;
+                }
+            }
+        }
+        fn countdown(n: i32) -> i32 {
+            while (true) {
+                if (n == 0) {
+                    return n;
+                }
+                n -= 1;
+            }
+        }
+        assert(value(sign(-5)) == -1);
+        assert(value(sign(0)) == 0);
+        assert(first_over([7, 2], 5) == 7);
+        assert(countdown(3) == 0);
+        ",
+            )))
+        })
+    });
+}
+fn garbage_collection_in_a_long_loop(c: &mut Criterion) {
+    c.bench_function("Garbage collection in a long loop", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Counter {
+            count: i32,
+        }
+        fn bump(c: Counter) -> i32 {
+            c.count = c.count + 1;
+            return c.count;
+        }
+        fn double(n: i32) -> i32 {
+            return n + n;
+        }
+        let counter: Counter = Counter();
+        counter.count = 0;
+        let names: Array<String> = [\"a\", \"b\", \"c\"];
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 1000) {
+            let doubled: i32 = double(i);
+            total = total + doubled;
+            bump(counter);
+            i = i + 1;
+        }
+        assert(total == 999000);
+        assert(counter.count == 1000);
+        assert(names[2] == \"c\");
+        ",
+            )))
+        })
+    });
+}
+fn generic_function_argument_failure(c: &mut Criterion) {
+    c.bench_function("Generic function argument failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn pick<T>(a: T, b: T) -> T {
+            return a;
+        }
+        pick(1, \"a\");
+        ",
+            )))
+        })
+    });
+}
+fn generic_function_return_type_failure(c: &mut Criterion) {
+    c.bench_function("Generic function return type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn first<T>(a: Array<T>) -> T {
+            return a[0];
+        }
+        let s: String = first([1, 2]);
+        ",
+            )))
+        })
+    });
+}
+fn generic_functions(c: &mut Criterion) {
+    c.bench_function("Generic functions", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn first<T>(a: Array<T>) -> T {
+            return a[0];
+        }
+        fn identity<T>(value: T) -> T {
+            return value;
+        }
+        let i: i32 = first([1, 2, 3]);
+        assert(i == 1);
+        let s: String = identity(\"a\");
+        assert(s == \"a\");
+        assert(identity(first([true])));
+        ",
+            )))
+        })
+    });
+}
+fn generic_struct_field_type_failure(c: &mut Criterion) {
+    c.bench_function("Generic struct field type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Wrapper<T> {
+            value: T,
+        }
+        let w: Wrapper<i32> = Wrapper { value: 1 };
+        w.value = true;
+        ",
+            )))
+        })
+    });
+}
+fn generic_struct_literal(c: &mut Criterion) {
+    c.bench_function("Generic struct literal", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Pair<T> {
+            first: T,
+            second: T,
+        }
+        let pair: Pair<String> = Pair { first: \"a\", second: \"b\" };
+        assert(pair.first == \"a\");
+        assert(pair.second == \"b\");
+        ",
+            )))
+        })
+    });
+}
+fn generic_struct_literal_empty_array_field(c: &mut Criterion) {
+    c.bench_function("Generic struct literal empty array field", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Stack<T> {
+            items: Array<T>,
+        }
+        let stack: Stack<i32> = Stack { items: [] };
+        stack.items = [1, 2];
+        let items = stack.items;
+        assert(items[1] == 2);
+        ",
+            )))
+        })
+    });
+}
+fn generic_struct_type_argument_count_failure(c: &mut Criterion) {
+    c.bench_function("Generic struct type argument count failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Wrapper<T> {
+            value: T,
+        }
+        let w: Wrapper<i32, bool> = Wrapper { value: 1 };
+        ",
+            )))
+        })
+    });
+}
+fn generic_struct_with_a_generic_impl(c: &mut Criterion) {
+    c.bench_function("Generic struct with a generic impl", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Wrapper<T> {
+            fn get(self: Wrapper<T>) -> T {
+                return self.value;
+            }
+            fn set(self: Wrapper<T>, value: T) -> () {
+                self.value = value;
+            }
+        }
+        let number: Wrapper<i32> = Wrapper { value: 0 };
+        number.set(5);
+        assert(number.get() == 5);
+        let name: Wrapper<String> = Wrapper { value: \"\" };
+        name.set(\"lang\");
+        assert(name.get() == \"lang\");
+        ",
+            )))
+        })
+    });
+}
+fn generic_type_parameter_used_as_a_concrete_type_failure(c: &mut Criterion) {
+    c.bench_function(
+        "Generic type parameter used as a concrete type failure",
+        |b| {
+            b.iter(|| {
+                Lang::new(black_box(Some(
+                    "
+        fn to_int<T>(a: T) -> i32 {
+            return a;
+        }
+        ",
+                )))
+            })
+        },
+    );
+}
+fn generic_variants_with_annotations(c: &mut Criterion) {
+    c.bench_function("Generic variants with annotations", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: Option<String> = None;
+        a = Some(\"x\");
+        let b: Result<i32, i32> = Err(3);
+        let c = Some(1);
+        assert(a == Some(\"x\"));
+        assert(b == Err(3));
+        assert(c == Some(1));
+        ",
+            )))
+        })
+    });
+}
+fn hex_octal_and_binary_literals(c: &mut Criterion) {
+    c.bench_function("Hex octal and binary literals", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: u8 = 0xFF;
+        assert(a == 255u8);
+        assert(0xdead_beefu32 == 3735928559u32);
+        assert(0o777 == 511);
+        assert(0b1010_1010 == 170);
+        assert(0b1000_0000u8 == 128u8);
+        ",
+            )))
+        })
+    });
+}
+fn if_expression(c: &mut Criterion) {
+    c.bench_function("If expression", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: bool = true;
+        let x: i32 = if (a) { 1 } else { 2 };
+        assert(x == 1);
+        let y: i32 = if (!a) { 1 } else { 2 };
+        assert(y == 2);
+        let z: i32 = if (x > y) { x } else if (x == y) { 0 } else { y * 10 };
+        assert(z == 20);
+        ",
+            )))
+        })
+    });
+}
+fn if_expression_branches_with_statements(c: &mut Criterion) {
+    c.bench_function("If expression branches with statements", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let n: i32 = 4;
+        let description: String = if (n % 2 == 0) {
+            let half: i32 = n / 2;
+            assert(half == 2);
+            \"even\"
+        } else {
+            \"odd\"
+        };
+        assert(description == \"even\");
+        ",
+            )))
+        })
+    });
+}
+fn if_expression_incompatible_branch_types_failure(c: &mut Criterion) {
+    c.bench_function("If expression incompatible branch types failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: bool = true;
+        let x: i32 = if (a) { 1 } else { \"two\" };
+        ",
+            )))
+        })
+    });
+}
+fn if_expression_without_else_failure(c: &mut Criterion) {
+    c.bench_function("If expression without else failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: bool = true;
+        let x: i32 = if (a) { 1 };
+        ",
+            )))
+        })
+    });
+}
+fn if_statement_with_block_branches(c: &mut Criterion) {
+    c.bench_function("If statement with block branches", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let counter: i32 = 0;
+        if (counter == 0) {
+            counter = 1;
+        } else {
+            counter = 2;
+        }
+        if (counter == 1) {
+            counter += 10;
+        }
+        assert(counter == 11);
+        ",
+            )))
+        })
+    });
+}
+fn infer_a_variable_from_err_failure(c: &mut Criterion) {
+    c.bench_function("Infer a variable from Err failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = Err(3);
+        ",
+            )))
+        })
+    });
+}
+fn infer_a_variable_from_none_failure(c: &mut Criterion) {
+    c.bench_function("Infer a variable from None failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = None;
+        ",
+            )))
+        })
+    });
+}
+fn integer_arithmetic(c: &mut Criterion) {
+    c.bench_function("Integer arithmetic", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let q: i32 = 7 / 2;
+        assert(q == 3);
+        let r: i32 = 7 - 10;
+        assert(r == -3);
+        let p: i32 = 6 * 7;
+        assert(p == 42);
+        let m: i64 = 4294967296 / 2;
+        assert(m == 2147483648);
+        ",
+            )))
+        })
+    });
+}
+fn integer_literal_suffixes(c: &mut Criterion) {
+    c.bench_function("Integer literal suffixes", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 255u8;
+        let b = 100i8 + 27i8;
+        let c: u64 = 1u64 + 2u8;
+        assert(a == 255);
+        assert(b == 127);
+        assert(c == 3);
+        let d: i16 = 200u8 + 1i16;
+        assert(d == 201);
+        ",
+            )))
+        })
+    });
+}
+fn integer_overflow_failure(c: &mut Criterion) {
+    c.bench_function("Integer overflow failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 2147483647;
+        let b = a + 1;
+        ",
+            )))
+        })
+    });
+}
+fn integer_suffix_on_float_failure(c: &mut Criterion) {
+    c.bench_function("Integer suffix on float failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 1.5i32;
+        ",
+            )))
+        })
+    });
+}
+fn integer_width_casts(c: &mut Criterion) {
+    c.bench_function("Integer width casts", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        assert(300 as u8 == 44);
+        assert(-1 as u8 == 255);
+        assert(255u8 as i8 == -1);
+        assert(-1i8 as u64 == 18446744073709551615u64);
+        assert(70000 as i16 == 4464);
+        assert(-5.7 as u8 == 0);
+        assert(300.0 as u8 == 255);
+        assert('A' as u8 == 65);
+        ",
+            )))
+        })
+    });
+}
+fn integer_widths(c: &mut Criterion) {
+    c.bench_function("Integer widths", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: u8 = 255;
+        let b: i8 = -128;
+        let c: i16 = -32768;
+        let d: u16 = 65535;
+        let e: u32 = 4294967295;
+        let f: u64 = 18446744073709551615;
+        let g: usize = 7;
+        assert(a == 255);
+        assert(b == -128);
+        assert(c == -32768);
+        assert(d == 65535);
+        assert(e == 4294967295);
+        assert(f == 18446744073709551615u64);
+        assert(g == 7);
+        ",
+            )))
+        })
+    });
+}
+fn invalid_digit_for_radix_failure(c: &mut Criterion) {
+    c.bench_function("Invalid digit for radix failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 0b102;
+        ",
+            )))
+        })
+    });
+}
+fn iterate_over_a_non_iterable_failure(c: &mut Criterion) {
+    c.bench_function("Iterate over a non-iterable failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        for x in 5 {
+            print x;
+        }
+        ",
+            )))
+        })
+    });
+}
+fn labeled_break_and_continue(c: &mut Criterion) {
+    c.bench_function("Labeled break and continue", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let pairs: i32 = 0;
+        'outer: for (let i: i32 = 0; i < 5; i += 1) {
+            for (let j: i32 = 0; j < 5; j += 1) {
+                if (j > i) {
+                    continue 'outer;
+                }
+                if (i == 3) {
+                    break 'outer;
+                }
+                pairs += 1;
+            }
+        }
+        assert(pairs == 6);
+        let count: i32 = 0;
+        'rows: while (true) {
+            while (true) {
+                count += 1;
+                if (count == 3) {
+                    break 'rows;
+                }
+            }
+        }
+        assert(count == 3);
+        ",
+            )))
+        })
+    });
+}
+fn labeled_for_in(c: &mut Criterion) {
+    c.bench_function("Labeled for in", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let pairs: i32 = 0;
+        'outer: for i in 0..5 {
+            for j in [0, 1, 2, 3, 4] {
+                if (j > i) {
+                    continue 'outer;
+                }
+                if (i == 3) {
+                    break 'outer;
+                }
+                pairs += 1;
+            }
+        }
+        assert(pairs == 6);
+        ",
+            )))
+        })
+    });
+}
+fn let_type_inference(c: &mut Criterion) {
+    c.bench_function("Let type inference", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+        }
+        fn double(a: i32) -> i32 {
+            return a + a;
+        }
+        let a = 1;
+        let b = 2.5;
+        let s = \"hi\";
+        let c = 'c';
+        let t = true;
+        let d = double(a);
+        let p = Point();
+        p.x = 3;
+        let arr = [1, 2, 3];
+        a = 5;
+        assert(a == 5);
+        assert(b == 2.5);
+        assert(s == \"hi\");
+        assert(c == 'c');
+        assert(t);
+        assert(d == 2);
+        assert(p.x == 3);
+        assert(arr[2] == 3);
+        let q: Point = p;
+        let copy: Array<i32> = arr;
+        ",
+            )))
+        })
+    });
+}
+fn let_type_inference_empty_array_failure(c: &mut Criterion) {
+    c.bench_function("Let type inference empty array failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = [];
+        ",
+            )))
+        })
+    });
+}
+fn let_type_inference_reassignment_failure(c: &mut Criterion) {
+    c.bench_function("Let type inference reassignment failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 1;
+        a = \"not a number\";
+        ",
+            )))
+        })
+    });
+}
+fn let_without_type_or_initializer_failure(c: &mut Criterion) {
+    c.bench_function("Let without type or initializer failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a;
+        ",
+            )))
+        })
+    });
+}
+fn literal_out_of_range_failure(c: &mut Criterion) {
+    c.bench_function("Literal out of range failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: u8 = 256;
+        ",
+            )))
+        })
+    });
+}
+fn long_overflow_failure(c: &mut Criterion) {
+    c.bench_function("Long overflow failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i64 = 9223372036854775807;
+        let b = a * 2;
+        ",
+            )))
+        })
+    });
+}
+fn loop_breaks_with_incompatible_values_failure(c: &mut Criterion) {
+    c.bench_function("Loop breaks with incompatible values failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let flag: bool = true;
+        let a: i32 = loop {
+            if (flag) {
+                break 1;
+            }
+            break \"one\";
+        };
+        ",
+            )))
+        })
+    });
+}
+fn loop_with_break_value(c: &mut Criterion) {
+    c.bench_function("Loop with break value", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let n: i32 = 0;
+        let square: i32 = loop {
+            n += 1;
+            if (n * n > 50) {
+                break n * n;
+            }
+        };
+        assert(square == 64);
+        fn find(target: i32) -> i32 {
+            let attempts: i32 = 0;
+            'search: loop {
+                let guess: i32 = 0;
+                loop {
+                    attempts += 1;
+                    if (guess == target) {
+                        break 'search attempts;
+                    }
+                    if (guess > target) {
+                        break;
+                    }
+                    guess += 1;
+                }
+            }
+        }
+        assert(find(4) == 5);
+        ",
+            )))
+        })
+    });
+}
+fn map_empty_literal_without_a_type_failure(c: &mut Criterion) {
+    c.bench_function("Map empty literal without a type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let m = [:];
+        ",
+            )))
+        })
+    });
+}
+fn map_entry_type_failure(c: &mut Criterion) {
+    c.bench_function("Map entry type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let m = [\"a\": 1, \"b\": true];
+        ",
+            )))
+        })
+    });
+}
+fn map_float_key_failure(c: &mut Criterion) {
+    c.bench_function("Map float key failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let m = [1.5: 2];
+        ",
+            )))
+        })
+    });
+}
+fn map_index_key_type_failure(c: &mut Criterion) {
+    c.bench_function("Map index key type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let m = [\"a\": 1];
+        print m[1];
+        ",
+            )))
+        })
+    });
+}
+fn map_insert_value_type_failure(c: &mut Criterion) {
+    c.bench_function("Map insert value type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let m = [\"a\": 1];
+        m.insert(\"b\", \"c\");
+        ",
+            )))
+        })
+    });
+}
+fn map_iteration(c: &mut Criterion) {
+    c.bench_function("Map iteration", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let scores = [\"a\": 1, \"b\": 2, \"c\": 3];
+        scores.remove(\"b\");
+        let keys = scores.keys();
+        let values = scores.values();
+        let total = 0;
+        let i = 0;
+        while (i < scores.len()) {
+            assert(scores[keys[i]] == values[i]);
+            total = total + values[i];
+            i = i + 1;
+        }
+        assert(total == 4);
+        assert(keys[1] == \"c\");
+        ",
+            )))
+        })
+    });
+}
+fn map_keys_of_each_hashable_type(c: &mut Criterion) {
+    c.bench_function("Map keys of each hashable type", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        enum Color { Red, Green }
+        let ids = [1: \"one\", 2: \"two\"];
+        assert(ids[2] == \"two\");
+        let letters = ['a': 1, 'b': 2];
+        assert(letters['b'] == 2);
+        let flags = [true: \"yes\", false: \"no\"];
+        assert(flags[1 == 1] == \"yes\");
+        let p = Point { x: 1, y: 2 };
+        let q = Point { x: 2, y: 1 };
+        let names: Map<Point, String> = [p: \"p\"];
+        names[q] = \"q\";
+        let r = p;
+        assert(names[r] == \"p\");
+        assert(names.len() == 2);
+        let colors = [Color::Red: 1];
+        colors[Color::Green] = 2;
+        assert(colors[Color::Green] == 2);
+        ",
+            )))
+        })
+    });
+}
+fn map_literals_indexing_and_methods(c: &mut Criterion) {
+    c.bench_function("Map literals, indexing and methods", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let scores: Map<String, i32> = [\"alice\": 3, \"bob\": 5];
+        assert(scores[\"bob\"] == 5);
+        scores[\"carol\"] = 7;
+        scores.insert(\"alice\", 4);
+        assert(scores[\"alice\"] == 4);
+        assert(scores.len() == 3);
+        assert(scores.contains(\"bob\"));
+        assert(scores.remove(\"bob\"));
+        assert(!scores.remove(\"bob\"));
+        assert(!scores.contains(\"bob\"));
+        let empty: Map<i64, bool> = [:];
+        assert(empty.len() == 0);
+        let copy: Map<String, i32> = [\"carol\": 7, \"alice\": 4];
+        assert(scores == copy);
+        ",
+            )))
+        })
+    });
+}
+fn map_missing_key_failure(c: &mut Criterion) {
+    c.bench_function("Map missing key failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let m = [\"a\": 1];
+        print m[\"b\"];
+        ",
+            )))
+        })
+    });
+}
+fn map_missing_struct_key_failure(c: &mut Criterion) {
+    c.bench_function("Map missing struct key failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        let m: Map<Point, i32> = [Point { x: 1, y: 2 }: 5];
+        print m[Point { x: 2, y: 1 }];
+        ",
+            )))
+        })
+    });
+}
+fn map_struct_keys_compare_by_their_fields(c: &mut Criterion) {
+    c.bench_function("Map struct keys compare by their fields", |b| {
+b.iter(|| Lang::new(black_box(Some("
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        struct Segment {
+            from: Point,
+            to: Point
+        }
+        let m: Map<Point, i32> = [Point { x: 1, y: 2 }: 5];
+        assert(m[Point { x: 1, y: 2 }] == 5);
+        assert(m.contains(Point { x: 1, y: 2 }));
+        assert(!m.contains(Point { x: 2, y: 1 }));
+        m[Point { x: 1, y: 2 }] = 6;
+        assert(m.len() == 1);
+        assert(m[Point { x: 1, y: 2 }] == 6);
+        let p = Point { x: 3, y: 4 };
+        m[p] = 7;
+        p.x = 1;
+        assert(m[Point { x: 3, y: 4 }] == 7);
+        let lengths: Map<Segment, i32> = [Segment { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }: 5];
+        assert(lengths[Segment { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }] == 5);
+        let pairs: Map<(Point, i32), bool> = [(Point { x: 1, y: 1 }, 1): true];
+        assert(pairs[(Point { x: 1, y: 1 }, 1)]);
+        assert(m.remove(Point { x: 1, y: 2 }));
+        assert(m.len() == 1);
+        "))))
+});
+}
+fn match_arms_of_incompatible_types_failure(c: &mut Criterion) {
+    c.bench_function("Match arms of incompatible types failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Shape { Circle, Square }
+        let shape = Shape::Circle;
+        let sides: i32 = match shape {
+            Shape::Circle => \"none\",
+            Shape::Square => 4
+        };
+        ",
+            )))
+        })
+    });
+}
+fn match_expression_on_enum_variants(c: &mut Criterion) {
+    c.bench_function("Match expression on enum variants", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32),
+            Empty
+        }
+        fn size(shape: Shape) -> i32 {
+            return match shape {
+                Shape::Circle(r) => r + r,
+                Shape::Rect(w, h) => w + h,
+                Shape::Empty => 0,
+            };
+        }
+        assert(size(Shape::Circle(2)) == 4);
+        assert(size(Shape::Rect(2, 5)) == 7);
+        assert(size(Shape::Empty) == 0);
+        ",
+            )))
+        })
+    });
+}
+fn match_literal_without_wildcard_failure(c: &mut Criterion) {
+    c.bench_function("Match literal without wildcard failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let n: i32 = 3;
+        let name: String = match n {
+            1 => \"one\",
+            3 => \"three\",
+        };
+        ",
+            )))
+        })
+    });
+}
+fn match_missing_variant_failure(c: &mut Criterion) {
+    c.bench_function("Match missing variant failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        match shape {
+            Shape::Circle(r) => {
+                print r;
+            }
+        }
+        ",
+            )))
+        })
+    });
+}
+fn match_nested_and_binding_patterns(c: &mut Criterion) {
+    c.bench_function("Match nested and binding patterns", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Inner {
+            A,
+            B(i32)
+        }
+        enum Outer {
+            Wrap(Inner),
+            Nothing
+        }
+        let value: Outer = Outer::Wrap(Inner::B(7));
+        let result: i32 = match value {
+            Outer::Wrap(Inner::B(x)) => x,
+            Outer::Wrap(_) => 1,
+            other => 0,
+        };
+        assert(result == 7);
+        ",
+            )))
+        })
+    });
+}
+fn match_pattern_arity_failure(c: &mut Criterion) {
+    c.bench_function("Match pattern arity failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Shape {
+            Circle(i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        let n: i32 = match shape {
+            Shape::Circle(a, b) => a,
+        };
+        ",
+            )))
+        })
+    });
+}
+fn match_refutable_field_pattern_failure(c: &mut Criterion) {
+    c.bench_function("Match refutable field pattern failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        let area: i32 = match shape {
+            Shape::Circle(r) => r,
+            Shape::Rect(1, h) => h,
+        };
+        ",
+            )))
+        })
+    });
+}
+fn match_statement_with_block_arms(c: &mut Criterion) {
+    c.bench_function("Match statement with block arms", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Color {
+            Red,
+            Green,
+            Blue
+        }
+        let color: Color = Color::Green;
+        let seen: i32 = 0;
+        match color {
+            Color::Red => {
+                seen = 1;
+            }
+            Color::Green => {
+                seen = 2;
+            }
+            Color::Blue => {
+                seen = 3;
+            }
+        }
+        assert(seen == 2);
+        ",
+            )))
+        })
+    });
+}
+fn match_undefined_variant_failure(c: &mut Criterion) {
+    c.bench_function("Match undefined variant failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        enum Color {
+            Red
+        }
+        let color: Color = Color::Red;
+        let n: i32 = match color {
+            Color::Red => 1,
+            Color::Purple => 2,
+        };
+        ",
+            )))
+        })
+    });
+}
+fn match_wildcard_and_literal_patterns(c: &mut Criterion) {
+    c.bench_function("Match wildcard and literal patterns", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let n: i32 = 3;
+        let name: String = match n {
+            1 => \"one\",
+            3 => \"three\",
+            _ => \"many\",
+        };
+        assert(name == \"three\");
+        let flag: bool = false;
+        let as_int: i32 = match flag {
+            true => 1,
+            false => 0,
+        };
+        assert(as_int == 0);
+        ",
+            )))
+        })
+    });
+}
+fn negation_overflow_failure(c: &mut Criterion) {
+    c.bench_function("Negation overflow failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = -2147483647 - 1;
+        let b = -a;
+        ",
+            )))
+        })
+    });
+}
+fn negative_literals_take_their_suffix_type(c: &mut Criterion) {
+    c.bench_function("Negative literals take their suffix type", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = -128i8;
+        let b: i8 = a;
+        let c: i8 = -0x80;
+        assert(b == c);
+        let d = -2.5e1f32;
+        let e: f32 = d;
+        assert(e == -25.0f32);
+        let f = -9223372036854775808;
+        let g: i64 = f;
+        ",
+            )))
+        })
+    });
+}
+fn nested_generic_type_arguments(c: &mut Criterion) {
+    c.bench_function("Nested generic type arguments", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: Option<Option<i32>> = Some(Some(1));
+        match a {
+            Some(Some(value)) => { assert(value == 1); }
+            _ => { assert(false); }
+        }
+        ",
+            )))
+        })
+    });
+}
+fn numeric_casts(c: &mut Criterion) {
+    c.bench_function("Numeric casts", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let x: f64 = 7 as f64;
+        assert(x == 7.0);
+        let t: i32 = 3.9 as i32;
+        assert(t == 3);
+        let n: i32 = -3.9 as i32;
+        assert(n == -3);
+        let big: i64 = 4294967297;
+        assert(big as i32 == 1);
+        assert(3000000000.0 as i32 == 2147483647);
+        assert('a' as i32 == 97);
+        assert(true as i64 == 1);
+        let half: f32 = 1 as f32 / 2 as f32;
+        assert(half == 0.5);
+        ",
+            )))
+        })
+    });
+}
+fn numeric_promotion(c: &mut Criterion) {
+    c.bench_function("Numeric promotion", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 2147483647;
+        let b: i64 = 1;
+        let c: i64 = a + b;
+        assert(c == 2147483648);
+        let x: f32 = 1.5;
+        let y: f64 = 2.25;
+        let z: f64 = x + y;
+        assert(z == 3.75);
+        ",
+            )))
+        })
+    });
+}
+fn option_and_result_constructors(c: &mut Criterion) {
+    c.bench_function("Option and Result constructors", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let some: Option<i32> = Some(3);
+        let none: Option<i32> = None;
+        assert(some == Option::Some(3));
+        assert(some != none);
+        let ok: Result<i32, String> = Ok(1);
+        let err: Result<i32, String> = Err(\"failed\");
+        assert(ok != err);
+        let found = match some {
+            Some(n) => n + 1,
+            None => 0,
+        };
+        assert(found == 4);
+        let message = match err {
+            Ok(_) => \"fine\",
+            Err(reason) => reason,
+        };
+        assert(message == \"failed\");
+        ",
+            )))
+        })
+    });
+}
+fn option_payload_type_failure(c: &mut Criterion) {
+    c.bench_function("Option payload type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: Option<i32> = Some(\"x\");
+        ",
+            )))
+        })
+    });
+}
+fn option_propagation(c: &mut Criterion) {
+    c.bench_function("Option propagation", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn first_even(a: i32, b: i32) -> Option<i32> {
+            if (a == 2) {
+                return Some(a);
+            }
+            if (b == 4) {
+                return Some(b);
+            }
+            return None;
+        }
+        fn plus_one(a: i32, b: i32) -> Option<i32> {
+            let n = first_even(a, b)?;
+            return Some(n + 1);
+        }
+        assert(plus_one(2, 1) == Some(3));
+        assert(plus_one(1, 1) == None);
+        ",
+            )))
+        })
+    });
+}
+fn overloaded_arithmetic_operators(c: &mut Criterion) {
+    c.bench_function("Overloaded arithmetic operators", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Vec2 {
+            x: i32,
+            y: i32,
+        }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x, y: self.y + other.y };
+            }
+        }
+        impl Sub for Vec2 {
+            fn sub(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x + 1, y: self.y + other.y + 1 };
+            }
+        }
+        let a = Vec2 { x: 1, y: 2 };
+        let b = Vec2 { x: 3, y: 4 };
+        let c: Vec2 = a + b;
+        assert(c.x == 4);
+        assert(c.y == 6);
+        let d: Vec2 = a + b + c;
+        assert(d.x == 8);
+        let e: Vec2 = a - b;
+        assert(e.y == 7);
+        ",
+            )))
+        })
+    });
+}
+fn overloaded_comparison_operators(c: &mut Criterion) {
+    c.bench_function("Overloaded comparison operators", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Version {
+            major: i32,
+            minor: i32,
+        }
+        impl Eq for Version {
+            fn eq(self: Version, other: Version) -> bool {
+                return self.major == other.major and self.minor == other.minor;
+            }
+        }
+        impl Ord for Version {
+            fn cmp(self: Version, other: Version) -> i32 {
+                if (self.major != other.major) {
+                    if (self.major < other.major) {
+                        return -1;
+                    }
+                    return 1;
+                }
+                if (self.minor < other.minor) {
+                    return -1;
+                }
+                if (self.minor > other.minor) {
+                    return 1;
+                }
+                return 0;
+            }
+        }
+        let old = Version { major: 1, minor: 4 };
+        let new = Version { major: 2, minor: 0 };
+        assert(old == Version { major: 1, minor: 4 });
+        assert(old != new);
+        assert(old < new);
+        assert(old <= old);
+        assert(new > old);
+        assert(new >= old);
+        assert(false == (new < old));
+        ",
+            )))
+        })
+    });
+}
+fn overloaded_index_operator(c: &mut Criterion) {
+    c.bench_function("Overloaded index operator", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Polynomial {
+            degree: i32,
+            coefficients: Array<i32>,
+        }
+        impl Index<i32, i32> for Polynomial {
+            fn index(self: Polynomial, power: i32) -> i32 {
+                if (power > self.degree) {
+                    return 0;
+                }
+                let coefficients = self.coefficients;
+                return coefficients[power];
+            }
+        }
+        let p = Polynomial { degree: 2, coefficients: [3, 0, 2] };
+        let constant: i32 = p[0];
+        assert(constant == 3);
+        assert(p[2] == 2);
+        assert(p[7] == 0);
+        ",
+            )))
+        })
+    });
+}
+fn overloaded_operators_on_generic_structs(c: &mut Criterion) {
+    c.bench_function("Overloaded operators on generic structs", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Eq for Wrapper<T> {
+            fn eq(self: Wrapper<T>, other: Wrapper<T>) -> bool {
+                return self.value == other.value;
+            }
+        }
+        impl<T> Index<i32, T> for Wrapper<T> {
+            fn index(self: Wrapper<T>, index: i32) -> T {
+                return self.value;
+            }
+        }
+        let a: Wrapper<String> = Wrapper { value: \"a\" };
+        let b: Wrapper<String> = Wrapper { value: \"a\" };
+        assert(a == b);
+        let value: String = a[0];
+        assert(value == \"a\");
+        ",
+            )))
+        })
+    });
+}
+fn overloaded_remainder_and_bitwise_operators(c: &mut Criterion) {
+    c.bench_function("Overloaded remainder and bitwise operators", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Bits { value: i32 }
+        impl Rem for Bits {
+            fn rem(self: Bits, other: Bits) -> Bits {
+                return Bits { value: self.value % other.value };
+            }
+        }
+        impl BitOr for Bits {
+            fn bitor(self: Bits, other: Bits) -> Bits {
+                return Bits { value: self.value | other.value };
+            }
+        }
+        let a = Bits { value: 12 };
+        let b = Bits { value: 5 };
+        assert((a % b).value == 2);
+        a |= b;
+        assert(a.value == 13);
+        ",
+            )))
+        })
+    });
+}
+fn range_values(c: &mut Criterion) {
+    c.bench_function("Range values", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let r: Range<i64> = 2..4i64;
+        let total: i64 = 0;
+        for i in r {
+            total += i;
+        }
+        assert(total == 5);
+        for i in r {
+            total += i;
+        }
+        assert(total == 10);
+        assert(r == (2..4i64));
+        print r;
+        ",
+            )))
+        })
+    });
+}
+fn range_with_float_bounds_failure(c: &mut Criterion) {
+    c.bench_function("Range with float bounds failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let r = 0.5..2.5;
+        ",
+            )))
+        })
+    });
+}
+fn raw_and_multi_line_strings(c: &mut Criterion) {
+    c.bench_function("Raw and multi-line strings", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let raw = r\"a\\n\";
+        let count: i32 = 0;
+        for c in raw {
+            count += 1;
+        }
+        assert(count == 3);
+        assert(r#\"say \"hi\"\"# == \"say \\\"hi\\\"\");
+        let lines = \"one
+two\";
+        assert(lines == \"one\\ntwo\");
+        let joined = \"one \\
+                      two\";
+        assert(joined == \"one two\");
+        ",
+            )))
+        })
+    });
+}
+fn redeclaring_a_variable_in_the_same_scope_makes_a_new_one(c: &mut Criterion) {
+    c.bench_function(
+        "Redeclaring a variable in the same scope makes a new one",
+        |b| {
+            b.iter(|| {
+                Lang::new(black_box(Some(
+                    "
+        fn twice_plus_one(x: i32) -> i32 {
+            let x: i32 = x * 2;
+            let x: i32 = x + 1;
+            return x;
+        }
+        assert(twice_plus_one(3) == 7);
+        {
+            let a: i32 = 1;
+            fn show() -> i32 {
+                return a;
+            }
+            let a: String = \"a\";
+            assert(show() == 1);
+            assert(a == \"a\");
+        }
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 3) {
+            let step: i32 = i;
+            let step: i32 = step + 10;
+            total = total + step;
+            i = i + 1;
+        }
+        assert(total == 33);
+        ",
+                )))
+            })
+        },
+    );
+}
+fn remainder(c: &mut Criterion) {
+    c.bench_function("Remainder", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        assert(17 % 5 == 2);
+        assert(-7 % 3 == -1);
+        assert(7.5 % 2.0 == 1.5);
+        let a: u8 = 200;
+        assert(a % 7u8 == 4);
+        ",
+            )))
+        })
+    });
+}
+fn remainder_by_zero_failure(c: &mut Criterion) {
+    c.bench_function("Remainder by zero failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 5;
+        let b = 0;
+        let c = a % b;
+        ",
+            )))
+        })
+    });
+}
+fn result_propagation(c: &mut Criterion) {
+    c.bench_function("Result propagation", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn parse(text: String) -> Result<i32, String> {
+            if (text == \"one\") {
+                return Ok(1);
+            }
+            return Err(\"not a number\");
+        }
+        fn sum(a: String, b: String) -> Result<i32, String> {
+            return Ok(parse(a)? + parse(b)?);
+        }
+        assert(sum(\"one\", \"one\") == Ok(2));
+        assert(sum(\"one\", \"two\") == Err(\"not a number\"));
+        ",
+            )))
+        })
+    });
+}
+fn return_from_block(c: &mut Criterion) {
+    c.bench_function("Return from block", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn test() -> i32 {
+            {
+                return 100;
+            }
+        }
+        let value: i32 = test();
+        assert(value == 100);
+        ",
+            )))
+        })
+    });
+}
+fn return_from_inside_a_while_loop(c: &mut Criterion) {
+    c.bench_function("Return from inside a while loop", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn first_multiple(of: i32, above: i32) -> i32 {
+            let candidate: i32 = above;
+            while (true) {
+                if (candidate % of == 0) {
+                    return candidate;
+                }
+                candidate += 1;
+            }
+            return -1;
+        }
+        assert(first_multiple(7, 20) == 21);
+        fn nested(limit: i32) -> i32 {
+            for (let i: i32 = 0; i < limit; i += 1) {
+                for (let j: i32 = 0; j < limit; j += 1) {
+                    if (i * j == 6) return i * 10 + j;
+                }
+            }
+            return 0;
+        }
+        assert(nested(5) == 23);
+        ",
+            )))
+        })
+    });
+}
+fn self_in_impls(c: &mut Criterion) {
+    c.bench_function("Self in impls", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        trait Double {
+            fn double(self: Self) -> Self;
+        }
+        struct Point {
+            x: i32,
+        }
+        impl Double for Point {
+            fn double(self: Self) -> Self {
+                let doubled: Self = Point { x: self.x * 2 };
+                return doubled;
+            }
+        }
+        impl Point {
+            fn plus(self: Self, other: Self) -> Self {
+                return Point { x: self.x + other.x };
+            }
+        }
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Wrapper<T> {
+            fn same(self: Self) -> Self {
+                return self;
+            }
+        }
+        let point = Point { x: 3 };
+        let doubled = point.double();
+        assert(doubled.x == 6);
+        let sum = doubled.plus(Point { x: 1 });
+        assert(sum.x == 7);
+        let wrapper: Wrapper<i32> = Wrapper { value: 4 };
+        let same = wrapper.same();
+        assert(same.value == 4);
+        ",
+            )))
+        })
+    });
+}
+fn self_in_impls_type_failure(c: &mut Criterion) {
+    c.bench_function("Self in impls type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+        }
+        struct Other {
+            x: i32,
+        }
+        impl Point {
+            fn other(self: Self) -> Self {
+                return Other { x: self.x };
+            }
+        }
+        ",
+            )))
+        })
+    });
+}
+fn shadowed_variables_in_nested_blocks(c: &mut Criterion) {
+    c.bench_function("Shadowed variables in nested blocks", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 1;
+        let b: i32 = 0;
+        {
+            b = 7;
+            let a: i32 = 2;
+            let b: i32 = 9;
+            {
+                let a: i32 = 3;
+                assert(a == 3);
+                b = 10;
+            }
+            assert(a == 2);
+            assert(b == 10);
+        }
+        assert(a == 1);
+        assert(b == 7);
+        ",
+            )))
+        })
+    });
+}
+fn shift_operators(c: &mut Criterion) {
+    c.bench_function("Shift operators", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        assert(1 << 4 == 16);
+        assert(-16 >> 2 == -4);
+        let a: u8 = 3;
+        assert(a << 7 == 128u8);
+        let b: u64 = 18446744073709551615;
+        assert(b >> 60u8 == 15u64);
+        let c: i64 = 1;
+        assert(c << 40u8 == 1099511627776);
+        ",
+            )))
+        })
+    });
+}
+fn shift_overflow_failure(c: &mut Criterion) {
+    c.bench_function("Shift overflow failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 32;
+        let b = 1 << a;
+        ",
+            )))
+        })
+    });
+}
+fn signed_literal_past_its_maximum_failure(c: &mut Criterion) {
+    c.bench_function("Signed literal past its maximum failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 128i8;
+        ",
+            )))
+        })
+    });
+}
+fn small_integer_overflow_failure(c: &mut Criterion) {
+    c.bench_function("Small integer overflow failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i8 = 100;
+        let b = a * 2i8;
+        ",
+            )))
+        })
+    });
+}
+fn string_invalid_escape_failure(c: &mut Criterion) {
+    c.bench_function("String invalid escape failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = \"\\q\";
+        ",
+            )))
+        })
+    });
+}
+fn string_literal_escapes(c: &mut Criterion) {
+    c.bench_function("String literal escapes", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let count: i32 = 0;
+        for c in \"\\t\\r\\n\\0\\\\\\\"\\'\" {
+            count += 1;
+        }
+        assert(count == 7);
+        assert(\"\\u{48}i\" == \"Hi\");
+        assert('\\u{41}' == 'A');
+        assert('\\'' != '\\\"');
+        let tab: char = 'a';
+        for c in \"a\\tb\" {
+            if (c != 'a' and c != 'b') {
+                tab = c;
+            }
+        }
+        assert(tab == '\\t');
+        ",
+            )))
+        })
+    });
+}
+fn string_literal_punctuation_and_unicode(c: &mut Criterion) {
+    c.bench_function("String literal punctuation and unicode", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = \"Hi, world! ¿Qué tal? ☃\";
+        let count: i32 = 0;
+        for c in a {
+            count += 1;
+        }
+        assert(count == 22);
+        assert(a != \"Hi world\");
+        assert(\"  padded \" != \"padded\");
+        ",
+            )))
+        })
+    });
+}
+fn string_unterminated_failure(c: &mut Criterion) {
+    c.bench_function("String unterminated failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = \"abc;
+        ",
+            )))
+        })
+    });
+}
+fn struct_constructor_call_arguments_failure(c: &mut Criterion) {
+    c.bench_function("Struct constructor call arguments failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+        }
+        let p = Point(1);
+        ",
+            )))
+        })
+    });
+}
+fn struct_constructor_call_field_defaults(c: &mut Criterion) {
+    c.bench_function("Struct constructor call field defaults", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32 = 7,
+            y: i32,
+            label: String,
+        }
+        let p = Point();
+        assert(p.x == 7);
+        assert(p.y == 0);
+        assert(p.label == \"\");
+        let q = Point();
+        q.x = 1;
+        assert(p.x == 7);
+        ",
+            )))
+        })
+    });
+}
+fn struct_constructor_call_missing_field_failure(c: &mut Criterion) {
+    c.bench_function("Struct constructor call missing field failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Inner {
+            x: i32,
+        }
+        struct Outer {
+            inner: Inner,
+        }
+        let o = Outer();
+        ",
+            )))
+        })
+    });
+}
+fn struct_declaration(c: &mut Criterion) {
+    c.bench_function("Struct declaration", |b| {
+        b.iter(|| Lang::new(black_box(Some("struct TestStruct {}"))))
+    });
+}
+fn struct_declaration_failure(c: &mut Criterion) {
+    c.bench_function("Struct declaration failure", |b| {
+        b.iter(|| Lang::new(black_box(Some("struct TestStruct {{}"))))
+    });
+}
+fn struct_declaration_with_fields(c: &mut Criterion) {
+    c.bench_function("Struct declaration with fields", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct TestStruct {
+            field0: i64,
+            field1: f64,
+            field2: bool
+        }
+        ",
+            )))
+        })
+    });
+}
+fn struct_field_default_type_failure(c: &mut Criterion) {
+    c.bench_function("Struct field default type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32 = \"zero\",
+        }
+        ",
+            )))
+        })
+    });
+}
+fn struct_formats_as_its_name(c: &mut Criterion) {
+    c.bench_function("Struct formats as its name", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        let p = Point { x: 1, y: 2 };
+        assert(f\"{p}\" == \"Point\");
+        assert(f\"{p:?}\" == \"Point { x: 1, y: 2 }\");
+        ",
+            )))
+        })
+    });
+}
+fn struct_literal_duplicate_field_failure(c: &mut Criterion) {
+    c.bench_function("Struct literal duplicate field failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: 1, x: 2 };
+        ",
+            )))
+        })
+    });
+}
+fn struct_literal_empty_collection_fields(c: &mut Criterion) {
+    c.bench_function("Struct literal empty collection fields", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn empty() -> Inventory {
+            return Inventory { items: [], counts: [:] };
+        }
+        struct Inventory {
+            items: Array<String>,
+            counts: Map<String, i32>,
+        }
+        let inventory = Inventory { items: [], counts: [:] };
+        inventory.items = [\"apple\"];
+        let items = inventory.items;
+        assert(items[0] == \"apple\");
+        let counts = empty().counts;
+        counts[\"apple\"] = 2;
+        assert(counts[\"apple\"] == 2);
+        ",
+            )))
+        })
+    });
+}
+fn struct_literal_field_defaults(c: &mut Criterion) {
+    c.bench_function("Struct literal field defaults", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Config {
+            name: String,
+            retries: i32 = 3,
+            verbose: bool = false,
+        }
+        let config = Config { name: \"default\" };
+        assert(config.retries == 3);
+        assert(config.verbose == false);
+        let custom = Config { verbose: true, name: \"custom\", retries: 5 };
+        assert(custom.retries == 5);
+        assert(custom.verbose);
+        ",
+            )))
+        })
+    });
+}
+fn struct_literal_field_type_failure(c: &mut Criterion) {
+    c.bench_function("Struct literal field type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: true };
+        ",
+            )))
+        })
+    });
+}
+fn struct_literal_missing_field_failure(c: &mut Criterion) {
+    c.bench_function("Struct literal missing field failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+            y: i32 = 0,
+        }
+        let p = Point { y: 1 };
+        ",
+            )))
+        })
+    });
+}
+fn struct_literal_unknown_field_failure(c: &mut Criterion) {
+    c.bench_function("Struct literal unknown field failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: 1, z: 2 };
+        ",
+            )))
+        })
+    });
+}
+fn struct_literals(c: &mut Criterion) {
+    c.bench_function("Struct literals", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        fn origin() -> Point {
+            return Point { x: 0, y: 0 };
+        }
+        let x = 3;
+        let p = Point { y: 4, x: x };
+        assert(p.x == 3);
+        assert(p.y == 4);
+        let o: Point = origin();
+        assert(o.x == 0);
+        o.x = 1;
+        assert(o.x == 1);
+        ",
+            )))
+        })
+    });
+}
+fn struct_with_field_access_failure(c: &mut Criterion) {
+    c.bench_function("Struct with field access failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct TestStruct {}
+
+        let instance: TestStruct = TestStruct();
+        instance.field_doesnt_exist;
+        ",
+            )))
+        })
+    });
+}
+fn struct_with_fields_instatiation(c: &mut Criterion) {
+    c.bench_function("Struct with fields instatiation", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct TestStruct {
+            field0: i64,
+            field1: f64,
+            field2: bool
+        }
+
+        let instance: TestStruct = TestStruct();
+        ",
+            )))
+        })
+    });
+}
+fn struct_with_fields_instatiation_and_field_assignment(c: &mut Criterion) {
+    c.bench_function(
+        "Struct with fields instatiation and field assignment",
+        |b| {
+            b.iter(|| {
+                Lang::new(black_box(Some(
+                    "
+        struct TestStruct {
+            field0: i64,
+            field1: f64,
+            field2: bool
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.field0 = 0;
+        instance.field1 = 1.00;
+        instance.field2 = false;
+        assert(instance.field0 == 0);
+        assert(instance.field1 == 1.00);
+        assert(instance.field2 == false);
+        ",
+                )))
+            })
+        },
+    );
+}
+fn struct_with_impl(c: &mut Criterion) {
+    c.bench_function("Struct with impl", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct TestStruct {
+        }
+
+        impl TestStruct {
+            fn hello() -> () {
+                print \"Hello world\";
+            }
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.hello();
+        ",
+            )))
+        })
+    });
+}
+fn struct_with_impl_using_mutable_self(c: &mut Criterion) {
+    c.bench_function("Struct with impl using mutable self", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct TestStruct {
+            i: i32,
+        }
+
+        impl TestStruct {
+            fn hello(self: TestStruct, other: TestStruct) -> () {
+                self.i = self.i + other.i;
+            }
+        }
+
+        let instance: TestStruct = TestStruct();
+        let other: TestStruct = TestStruct();
+        other.i = 100;
+        instance.i = 100;
+        instance.hello(other);
+        assert(instance.i == 200);
+        ",
+            )))
+        })
+    });
+}
+fn struct_with_impl_using_self(c: &mut Criterion) {
+    c.bench_function("Struct with impl using self", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct TestStruct {
+            i: i32,
+        }
+
+        impl TestStruct {
+            fn hello() -> () {
+                print self.i;
+            }
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.i = 100;
+        assert(instance.i == 100);
+        instance.hello();
+        ",
+            )))
+        })
+    });
+}
+fn struct_with_method_call_failure(c: &mut Criterion) {
+    c.bench_function("Struct with method call failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct TestStruct {
+        }
+
+        impl TestStruct {
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.hello();
+        ",
+            )))
+        })
+    });
+}
+fn suffixed_literal_out_of_range_failure(c: &mut Criterion) {
+    c.bench_function("Suffixed literal out of range failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 128i8;
+        ",
+            )))
+        })
+    });
+}
+fn trait_implementation(c: &mut Criterion) {
+    c.bench_function("Trait implementation", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Point {
+            x: i32,
+        }
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+            fn double(a: i32) -> i32 {
+                return a + a;
+            }
+        }
+        let point: Point = Point();
+        let doubled: i32 = point.double(3);
+        assert(doubled == 6);
+        ",
+            )))
+        })
+    });
+}
+fn try_in_a_function_not_returning_an_option_failure(c: &mut Criterion) {
+    c.bench_function("Try in a function not returning an option failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn f(a: Option<i32>) -> i32 {
+            return a?;
+        }
+        ",
+            )))
+        })
+    });
+}
+fn try_on_a_non_option_failure(c: &mut Criterion) {
+    c.bench_function("Try on a non-option failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn f(n: i32) -> Option<i32> {
+            return Some(n?);
+        }
+        ",
+            )))
+        })
+    });
+}
+fn try_on_a_variant_of_a_different_enum_failure(c: &mut Criterion) {
+    c.bench_function("Try on a variant of a different enum failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn first() -> Option<i32> {
+            let value: i32 = Err(1)?;
+            return Some(value);
+        }
+        ",
+            )))
+        })
+    });
+}
+fn try_outside_a_function_failure(c: &mut Criterion) {
+    c.bench_function("Try outside a function failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: Option<i32> = Some(1);
+        let b = a?;
+        ",
+            )))
+        })
+    });
+}
+fn try_with_a_different_error_type_failure(c: &mut Criterion) {
+    c.bench_function("Try with a different error type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn f(a: Result<i32, String>) -> Result<i32, bool> {
+            return Ok(a?);
+        }
+        ",
+            )))
+        })
+    });
+}
+fn tuple_destructure_arity_failure(c: &mut Criterion) {
+    c.bench_function("Tuple destructure arity failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let (a, b, c) = (1, 2);
+        ",
+            )))
+        })
+    });
+}
+fn tuple_destructuring(c: &mut Criterion) {
+    c.bench_function("Tuple destructuring", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn pair(name: String) -> (String, i32) {
+            return (name, 2);
+        }
+        let (name, count) = pair(\"apples\");
+        assert(name == \"apples\");
+        assert(count == 2);
+        fn swap(p: (i32, String)) -> (String, i32) {
+            let (number, text) = p;
+            return (text, number);
+        }
+        let swapped = swap((1, \"one\"));
+        assert(swapped.0 == \"one\");
+        assert(swapped.1 == 1);
+        ",
+            )))
+        })
+    });
+}
+fn tuple_field_assignment_failure(c: &mut Criterion) {
+    c.bench_function("Tuple field assignment failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let t = (1, 2);
+        t.0 = 3;
+        ",
+            )))
+        })
+    });
+}
+fn tuple_field_out_of_range_failure(c: &mut Criterion) {
+    c.bench_function("Tuple field out of range failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let t = (1, 2);
+        print t.2;
+        ",
+            )))
+        })
+    });
+}
+fn tuple_literals_and_fields(c: &mut Criterion) {
+    c.bench_function("Tuple literals and fields", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn min_max(a: i32, b: i32) -> (i32, i32) {
+            if (a < b) {
+                return (a, b);
+            }
+            return (b, a);
+        }
+        let bounds = min_max(7, 3);
+        assert(bounds.0 == 3);
+        assert(bounds.1 == 7);
+        let nested = ((1, 2), \"three\");
+        assert(nested.0.1 == 2);
+        assert(nested.1 == \"three\");
+        assert((1, \"a\") == (1, \"a\"));
+        assert((1, \"a\") != (1, \"b\"));
+        let empty: (i32, bool);
+        assert(empty == (0, false));
+        ",
+            )))
+        })
+    });
+}
+fn tuple_literals_widen_to_the_declared_type(c: &mut Criterion) {
+    c.bench_function("Tuple literals widen to the declared type", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn f() -> (i64, String) {
+            return (1, \"a\");
+        }
+        fn g() -> (i64, (f64, u32)) {
+            (2, (1.5, 3u8))
+        }
+        let t: (i64, String) = (1, \"a\");
+        assert(f() == t);
+        assert(g().1.1 == 3u32);
+        let (big, text) = f();
+        let wide: i64 = big + 5000000000;
+        assert(wide == 5000000001);
+        ",
+            )))
+        })
+    });
+}
+fn tuple_map_keys(c: &mut Criterion) {
+    c.bench_function("Tuple map keys", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let grid = [(0, 0): \"origin\", (1, 2): \"point\"];
+        assert(grid[(1, 2)] == \"point\");
+        ",
+            )))
+        })
+    });
+}
+fn tuple_return_type_failure(c: &mut Criterion) {
+    c.bench_function("Tuple return type failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn f() -> (i32, String) {
+            return (\"a\", 1);
+        }
+        ",
+            )))
+        })
+    });
+}
+fn type_check_binary_operator_failure(c: &mut Criterion) {
+    c.bench_function("Type check binary operator failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 1 + true;
+        ",
+            )))
+        })
+    });
+}
+fn type_check_cast_from_string_failure(c: &mut Criterion) {
+    c.bench_function("Type check cast from string failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = \"1\" as i32;
+        ",
+            )))
+        })
+    });
+}
+fn type_check_cast_to_non_numeric_failure(c: &mut Criterion) {
+    c.bench_function("Type check cast to non-numeric failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a = 1 as bool;
+        ",
+            )))
+        })
+    });
+}
+fn type_check_mixed_int_and_float_failure(c: &mut Criterion) {
+    c.bench_function("Type check mixed int and float failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 1;
+        let b: f64 = 2.0;
+        let c = a + b;
+        ",
+            )))
+        })
+    });
+}
+fn type_check_narrowing_assignment_failure(c: &mut Criterion) {
+    c.bench_function("Type check narrowing assignment failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: u16 = 1;
+        let b: u8 = a;
+        ",
+            )))
+        })
+    });
+}
+fn type_check_operator_without_trait_impl_failure(c: &mut Criterion) {
+    c.bench_function("Type check operator without trait impl failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Vec2 {
+            x: i32,
+        }
+        let a = Vec2 { x: 1 };
+        let b: Vec2 = a + a;
+        ",
+            )))
+        })
+    });
+}
+fn type_check_overloaded_index_result_failure(c: &mut Criterion) {
+    c.bench_function("Type check overloaded index result failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Grid {
+            x: i32,
+        }
+        impl Index<i32, bool> for Grid {
+            fn index(self: Grid, index: i32) -> bool {
+                return true;
+            }
+        }
+        let g = Grid { x: 1 };
+        let a: i32 = g[0];
+        ",
+            )))
+        })
+    });
+}
+fn type_check_overloaded_operator_operand_failure(c: &mut Criterion) {
+    c.bench_function("Type check overloaded operator operand failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Vec2 {
+            x: i32,
+        }
+        impl Mul for Vec2 {
+            fn mul(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x };
+            }
+        }
+        let a = Vec2 { x: 1 };
+        let b: Vec2 = a * 2;
+        ",
+            )))
+        })
+    });
+}
+fn type_check_overloaded_operator_result_failure(c: &mut Criterion) {
+    c.bench_function("Type check overloaded operator result failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        struct Vec2 {
+            x: i32,
+        }
+        impl Div for Vec2 {
+            fn div(self: Vec2, other: Vec2) -> Vec2 {
+                return self;
+            }
+        }
+        let a = Vec2 { x: 1 };
+        let b: i32 = a / a;
+        ",
+            )))
+        })
+    });
+}
+fn type_check_promoted_result_failure(c: &mut Criterion) {
+    c.bench_function("Type check promoted result failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        let i: Array<i64> = [0, 1, 2];
-        assert(i[0] == 0);
-        assert(i[1] == 1);
-        assert(i[2] == 2);
+        let a: i32 = 1;
+        let b: i64 = 2;
+        let c: i32 = a + b;
         ",
             )))
         })
     });
 }
-fn array_i64_variable_declaration_empty(c: &mut Criterion) {
-    c.bench_function("Array<i64> Variable declaration empty", |b| {
-        b.iter(|| Lang::new(black_box(Some("let i: Array<i64> = [];"))))
+fn type_check_signed_and_unsigned_mix_failure(c: &mut Criterion) {
+    c.bench_function("Type check signed and unsigned mix failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 1;
+        let b: u32 = 2;
+        let c = a + b;
+        ",
+            )))
+        })
     });
 }
-fn array_i64_variable_re_assignment(c: &mut Criterion) {
-    c.bench_function("Array<i64> Variable re-assignment", |b| {
+fn type_check_trait_impl_missing_function_failure(c: &mut Criterion) {
+    c.bench_function("Type check trait impl missing function failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        let i: Array<i64> = [];
-        i = [0, 1, 2];
-        assert(i[0] == 0);
-        assert(i[1] == 1);
-        assert(i[2] == 2);
+        struct Point {}
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+        }
         ",
             )))
         })
     });
 }
-fn array_i64_variable_re_assignment_failure(c: &mut Criterion) {
-    c.bench_function("Array<i64> Variable re-assignment failure", |b| {
+fn type_check_trait_impl_return_type_failure(c: &mut Criterion) {
+    c.bench_function("Type check trait impl return type failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
-                "let i: Array<i64> = [];
-            i = [0.00, 1.00, 2.00];
-        assert(i[0] == 0.00);
-        assert(i[1] == 1.00);
-        assert(i[2] == 2.00);
-            ",
+                "
+        struct Point {}
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+            fn double(a: i32) -> bool {
+                return true;
+            }
+        }
+        ",
             )))
         })
     });
 }
-fn assertion(c: &mut Criterion) {
-    c.bench_function("Assertion", |b| {
+fn type_check_trait_impl_self_type_failure(c: &mut Criterion) {
+    c.bench_function("Type check trait impl self type failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        assert(100 == 100);
-        assert(true == true);
-        assert(1.05 == 1.05);
+        struct Vec2 {
+            x: i32,
+        }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: i32) -> Vec2 {
+                return self;
+            }
+        }
         ",
             )))
         })
     });
 }
-fn assertion_failure(c: &mut Criterion) {
-    c.bench_function("Assertion failure", |b| {
+fn type_check_trait_type_argument_count_failure(c: &mut Criterion) {
+    c.bench_function("Type check trait type argument count failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        assert(0 == 100);
+        struct Grid {
+            x: i32,
+        }
+        impl Index<i32> for Grid {
+            fn index(self: Grid, index: i32) -> i32 {
+                return index;
+            }
+        }
         ",
             )))
         })
     });
 }
-fn for_loop(c: &mut Criterion) {
-    c.bench_function("For loop", |b| {
+fn type_check_unexecuted_branch_failure(c: &mut Criterion) {
+    c.bench_function("Type check unexecuted branch failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        for (let i: i32 = 0; i < 10; i = i + 1) {
-            print i;
-        }
-        for (let b: bool = false; b == true; b = false) {
-            print b;
+        let a: i32 = 0;
+        if (false) {
+            a = \"unreachable\";
         }
         ",
             )))
         })
     });
 }
-fn function_assert_failure(c: &mut Criterion) {
-    c.bench_function("Function assert failure", |b| {
+fn type_check_unexecuted_call_argument_failure(c: &mut Criterion) {
+    c.bench_function("Type check unexecuted call argument failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        fn test(a: i32, b: f64) -> bool {
-            if (!(a > 1000)) {
-                assert(false);
-            }
-            return false;
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b;
+        }
+        fn never_called() -> i32 {
+            return add(1, 2.0);
         }
-        test(1, 0.0);
         ",
             )))
         })
     });
 }
-fn function_nested_return(c: &mut Criterion) {
-    c.bench_function("Function nested return", |b| {
+fn type_check_unexecuted_call_arity_failure(c: &mut Criterion) {
+    c.bench_function("Type check unexecuted call arity failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        fn test() -> bool {
-            {
-                {
-                    {
-                        return false;
-                    }
-                }
-            }
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b;
+        }
+        fn never_called() -> i32 {
+            return add(1);
         }
-        assert(false == test());
         ",
             )))
         })
     });
 }
-fn function_returns_bool(c: &mut Criterion) {
-    c.bench_function("Function returns bool", |b| {
+fn type_check_unexecuted_field_access_failure(c: &mut Criterion) {
+    c.bench_function("Type check unexecuted field access failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        fn test() -> bool {
-            return false;
+        struct Point {
+            x: i32,
+        }
+        fn never_called(point: Point) -> i32 {
+            return point.y;
         }
-        assert(false == test());
         ",
             )))
         })
     });
 }
-fn function_returns_bool_with_args(c: &mut Criterion) {
-    c.bench_function("Function returns bool with args", |b| {
+fn type_check_unexecuted_return_failure(c: &mut Criterion) {
+    c.bench_function("Type check unexecuted return failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        fn test(a: i32, b: f64) -> bool {
-            return false;
+        fn never_called() -> i32 {
+            return \"not a number\";
         }
-        assert(false == test(100, 100.00));
         ",
             )))
         })
     });
 }
-fn return_from_block(c: &mut Criterion) {
-    c.bench_function("Return from block", |b| {
+fn type_check_unsigned_negation_failure(c: &mut Criterion) {
+    c.bench_function("Type check unsigned negation failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        fn test() -> i32 {
-            {
-                return 100;
-            }
+        let a: u8 = 1;
+        let b = -a;
+        ",
+            )))
+        })
+    });
+}
+fn undefined_function_failure(c: &mut Criterion) {
+    c.bench_function("Undefined function failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        fn defined() -> i32 {
+            return missing(1);
         }
-        let value: i32 = test();
-        assert(value == 100);
         ",
             )))
         })
     });
 }
-fn struct_declaration(c: &mut Criterion) {
-    c.bench_function("Struct declaration", |b| {
-        b.iter(|| Lang::new(black_box(Some("struct TestStruct {}"))))
+fn undefined_variable_failure(c: &mut Criterion) {
+    c.bench_function("Undefined variable failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 1;
+        print a;
+        print missing;
+        ",
+            )))
+        })
     });
 }
-fn struct_declaration_failure(c: &mut Criterion) {
-    c.bench_function("Struct declaration failure", |b| {
-        b.iter(|| Lang::new(black_box(Some("struct TestStruct {{}"))))
+fn uninitialized_char_variable_failure(c: &mut Criterion) {
+    c.bench_function("Uninitialized char variable failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let c: char;
+        ",
+            )))
+        })
     });
 }
-fn struct_declaration_with_fields(c: &mut Criterion) {
-    c.bench_function("Struct declaration with fields", |b| {
+fn uninitialized_enum_variable_failure(c: &mut Criterion) {
+    c.bench_function("Uninitialized enum variable failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        struct TestStruct {
-            field0: i64,
-            field1: f64,
-            field2: bool
-        }
+        let c: Option<i32>;
         ",
             )))
         })
     });
 }
-fn struct_with_field_access_failure(c: &mut Criterion) {
-    c.bench_function("Struct with field access failure", |b| {
+fn uninitialized_function_variable_failure(c: &mut Criterion) {
+    c.bench_function("Uninitialized function variable failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        struct TestStruct {}
-
-        let instance: TestStruct = TestStruct();
-        instance.field_doesnt_exist;
+        let f: fn(i32) -> i32;
         ",
             )))
         })
     });
 }
-fn struct_with_fields_instatiation(c: &mut Criterion) {
-    c.bench_function("Struct with fields instatiation", |b| {
+fn uninitialized_struct_variable_failure(c: &mut Criterion) {
+    c.bench_function("Uninitialized struct variable failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        struct TestStruct {
-            field0: i64,
-            field1: f64,
-            field2: bool
+        struct Point {
+            x: i32
         }
-
-        let instance: TestStruct = TestStruct();
+        let p: Point;
         ",
             )))
         })
     });
 }
-fn struct_with_fields_instatiation_and_field_assignment(c: &mut Criterion) {
-    c.bench_function(
-        "Struct with fields instatiation and field assignment",
-        |b| {
-            b.iter(|| {
-                Lang::new(black_box(Some(
-                    "
-        struct TestStruct {
-            field0: i64,
-            field1: f64,
-            field2: bool
-        }
-
-        let instance: TestStruct = TestStruct();
-        instance.field0 = 0;
-        instance.field1 = 1.00;
-        instance.field2 = false;
-        assert(instance.field0 == 0);
-        assert(instance.field1 == 1.00);
-        assert(instance.field2 == false);
+fn unsigned_arithmetic(c: &mut Criterion) {
+    c.bench_function("Unsigned arithmetic", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let count: u32 = 10;
+        let step: u32 = 3;
+        assert(count - step == 7u32);
+        assert(count * step == 30u32);
+        assert(count / step == 3u32);
+        let bytes: u8 = 200u8;
+        let wide: u16 = bytes + 100u16;
+        assert(wide == 300);
         ",
-                )))
-            })
-        },
-    );
+            )))
+        })
+    });
 }
-fn struct_with_impl(c: &mut Criterion) {
-    c.bench_function("Struct with impl", |b| {
+fn unsigned_map_keys(c: &mut Criterion) {
+    c.bench_function("Unsigned map keys", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        struct TestStruct {
-        }
-
-        impl TestStruct {
-            fn hello() -> () {
-                print \"Hello world\";
-            }
-        }
-
-        let instance: TestStruct = TestStruct();
-        instance.hello();
+        let counts: Map<u8, i32> = [:];
+        counts[1u8] = 3;
+        assert(counts[1u8] == 3);
         ",
             )))
         })
     });
 }
-fn struct_with_impl_using_mutable_self(c: &mut Criterion) {
-    c.bench_function("Struct with impl using mutable self", |b| {
+fn unsigned_overflow_failure(c: &mut Criterion) {
+    c.bench_function("Unsigned overflow failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        struct TestStruct {
-            i: i32,
-        }
-
-        impl TestStruct {
-            fn hello(self: TestStruct, other: TestStruct) -> () {
-                self.i = self.i + other.i;
-            }
-        }
-
-        let instance: TestStruct = TestStruct();
-        let other: TestStruct = TestStruct();
-        other.i = 100;
-        instance.i = 100;
-        instance.hello(other);
-        assert(instance.i == 200);
+        let a: u8 = 255;
+        let b = a + 1u8;
         ",
             )))
         })
     });
 }
-fn struct_with_impl_using_self(c: &mut Criterion) {
-    c.bench_function("Struct with impl using self", |b| {
+fn unsigned_underflow_failure(c: &mut Criterion) {
+    c.bench_function("Unsigned underflow failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        struct TestStruct {
-            i: i32,
-        }
-
-        impl TestStruct {
-            fn hello() -> () {
-                print self.i;
-            }
-        }
-
-        let instance: TestStruct = TestStruct();
-        instance.i = 100;
-        assert(instance.i == 100);
-        instance.hello();
+        let a: u32 = 0;
+        let b = a - 1u32;
         ",
             )))
         })
     });
 }
-fn struct_with_method_call_failure(c: &mut Criterion) {
-    c.bench_function("Struct with method call failure", |b| {
+fn unterminated_block_comment_failure(c: &mut Criterion) {
+    c.bench_function("Unterminated block comment failure", |b| {
         b.iter(|| {
             Lang::new(black_box(Some(
                 "
-        struct TestStruct {
-        }
-
-        impl TestStruct {
-        }
-
-        let instance: TestStruct = TestStruct();
-        instance.hello();
+        /* never /* closed */
+        let a: i32 = 1;
         ",
             )))
         })
@@ -424,6 +4321,17 @@ fn bool_not_equal(c: &mut Criterion) {
         })
     });
 }
+fn char_empty_literal_failure(c: &mut Criterion) {
+    c.bench_function("char empty literal failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: char = '';
+        ",
+            )))
+        })
+    });
+}
 fn char_equal(c: &mut Criterion) {
     c.bench_function("char equal", |b| {
         b.iter(|| {
@@ -489,6 +4397,17 @@ fn char_less_or_equal(c: &mut Criterion) {
         })
     });
 }
+fn char_literal_of_several_characters_failure(c: &mut Criterion) {
+    c.bench_function("char literal of several characters failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: char = 'ab';
+        ",
+            )))
+        })
+    });
+}
 fn char_not_equal(c: &mut Criterion) {
     c.bench_function("char not equal", |b| {
         b.iter(|| {
@@ -502,6 +4421,17 @@ fn char_not_equal(c: &mut Criterion) {
         })
     });
 }
+fn expression_missing_at_the_end_of_the_source_failure(c: &mut Criterion) {
+    c.bench_function("expression missing at the end of the source failure", |b| {
+        b.iter(|| {
+            Lang::new(black_box(Some(
+                "
+        let a: i32 = 1 +
+        ",
+            )))
+        })
+    });
+}
 fn f64_variable_declaration(c: &mut Criterion) {
     c.bench_function("f64 Variable declaration", |b| {
         b.iter(|| Lang::new(black_box(Some("let i: f64;"))))
@@ -771,6 +4701,10 @@ fn struct_vars_not_equal(c: &mut Criterion) {
 }
 criterion_group!(
     lang_benches,
+    array_index_with_unsigned_types,
+    array_literal_element_type_failure,
+    array_negative_index_assignment_failure,
+    array_negative_index_failure,
     array_i64_variable_declaration,
     array_i64_variable_declaration_and_assignment,
     array_i64_variable_declaration_empty,
@@ -778,15 +4712,181 @@ criterion_group!(
     array_i64_variable_re_assignment_failure,
     assertion,
     assertion_failure,
+    assertion_inside_a_function_failure,
+    bitwise_not_on_floats_failure,
+    bitwise_operator_precedence,
+    bitwise_operators,
+    bitwise_operators_on_floats_failure,
+    block_expression,
+    break_outside_of_a_loop_failure,
+    break_to_an_unknown_label_failure,
+    break_with_a_value_out_of_a_while_failure,
+    closure_outlives_its_block,
+    closure_returned_from_a_function,
+    closure_sees_the_binding_in_scope_at_its_declaration,
+    closures_created_in_a_long_loop,
+    comments,
+    compound_assignment,
+    compound_assignment_element_type_mismatch_failure,
+    compound_assignment_evaluates_its_target_once,
+    compound_assignment_invalid_target_failure,
+    compound_assignment_to_a_field_of_an_overloaded_type,
+    compound_assignment_to_fields_and_elements,
+    compound_assignment_type_mismatch_failure,
+    continue_in_while_and_for_loops,
+    continue_outside_of_a_loop_failure,
+    digit_separators_and_exponents,
+    division_by_zero_failure,
+    empty_arrays_of_function_values,
+    empty_arrays_of_tuples,
+    enum_as_function_argument_and_return_type,
+    enum_declaration,
+    enum_duplicate_variant_failure,
+    enum_type_mismatch_failure,
+    enum_undefined_variant_failure,
+    enum_variant_comparison,
+    enum_variant_with_initializer,
+    enum_variant_with_values,
+    enum_variant_with_values_arity_failure,
+    enum_variant_with_values_wrong_type_failure,
+    float_arithmetic,
+    float_suffixes,
+    for_in_break_and_continue,
+    for_in_over_a_map,
+    for_in_over_a_map_key_type_failure,
+    for_in_over_a_string,
+    for_in_over_an_array,
+    for_in_over_an_iterator,
+    for_in_over_ranges,
+    for_in_variable_is_scoped_to_its_body,
+    for_in_variable_type_failure,
     for_loop,
+    format_string_debug,
+    format_string_display_trait,
+    format_string_interpolation,
+    format_string_radix,
+    format_string_radix_of_a_float_failure,
+    format_string_unknown_spec_failure,
+    format_string_unmatched_brace_failure,
+    format_string_width_precision_and_alignment,
     function_assert_failure,
+    function_early_return_before_trailing_expression,
+    function_falling_off_its_end_failure,
+    function_leaving_a_loop_without_returning_failure,
     function_nested_return,
     function_returns_bool,
     function_returns_bool_with_args,
+    function_trailing_expression_return,
+    function_type_argument_failure,
+    function_type_call_argument_failure,
+    function_type_mismatch_failure,
+    functions_returning_on_every_path,
+    garbage_collection_in_a_long_loop,
+    generic_function_argument_failure,
+    generic_function_return_type_failure,
+    generic_functions,
+    generic_struct_field_type_failure,
+    generic_struct_literal,
+    generic_struct_literal_empty_array_field,
+    generic_struct_type_argument_count_failure,
+    generic_struct_with_a_generic_impl,
+    generic_type_parameter_used_as_a_concrete_type_failure,
+    generic_variants_with_annotations,
+    hex_octal_and_binary_literals,
+    if_expression,
+    if_expression_branches_with_statements,
+    if_expression_incompatible_branch_types_failure,
+    if_expression_without_else_failure,
+    if_statement_with_block_branches,
+    infer_a_variable_from_err_failure,
+    infer_a_variable_from_none_failure,
+    integer_arithmetic,
+    integer_literal_suffixes,
+    integer_overflow_failure,
+    integer_suffix_on_float_failure,
+    integer_width_casts,
+    integer_widths,
+    invalid_digit_for_radix_failure,
+    iterate_over_a_non_iterable_failure,
+    labeled_break_and_continue,
+    labeled_for_in,
+    let_type_inference,
+    let_type_inference_empty_array_failure,
+    let_type_inference_reassignment_failure,
+    let_without_type_or_initializer_failure,
+    literal_out_of_range_failure,
+    long_overflow_failure,
+    loop_breaks_with_incompatible_values_failure,
+    loop_with_break_value,
+    map_empty_literal_without_a_type_failure,
+    map_entry_type_failure,
+    map_float_key_failure,
+    map_index_key_type_failure,
+    map_insert_value_type_failure,
+    map_iteration,
+    map_keys_of_each_hashable_type,
+    map_literals_indexing_and_methods,
+    map_missing_key_failure,
+    map_missing_struct_key_failure,
+    map_struct_keys_compare_by_their_fields,
+    match_arms_of_incompatible_types_failure,
+    match_expression_on_enum_variants,
+    match_literal_without_wildcard_failure,
+    match_missing_variant_failure,
+    match_nested_and_binding_patterns,
+    match_pattern_arity_failure,
+    match_refutable_field_pattern_failure,
+    match_statement_with_block_arms,
+    match_undefined_variant_failure,
+    match_wildcard_and_literal_patterns,
+    negation_overflow_failure,
+    negative_literals_take_their_suffix_type,
+    nested_generic_type_arguments,
+    numeric_casts,
+    numeric_promotion,
+    option_and_result_constructors,
+    option_payload_type_failure,
+    option_propagation,
+    overloaded_arithmetic_operators,
+    overloaded_comparison_operators,
+    overloaded_index_operator,
+    overloaded_operators_on_generic_structs,
+    overloaded_remainder_and_bitwise_operators,
+    range_values,
+    range_with_float_bounds_failure,
+    raw_and_multi_line_strings,
+    redeclaring_a_variable_in_the_same_scope_makes_a_new_one,
+    remainder,
+    remainder_by_zero_failure,
+    result_propagation,
     return_from_block,
+    return_from_inside_a_while_loop,
+    self_in_impls,
+    self_in_impls_type_failure,
+    shadowed_variables_in_nested_blocks,
+    shift_operators,
+    shift_overflow_failure,
+    signed_literal_past_its_maximum_failure,
+    small_integer_overflow_failure,
+    string_invalid_escape_failure,
+    string_literal_escapes,
+    string_literal_punctuation_and_unicode,
+    string_unterminated_failure,
+    struct_constructor_call_arguments_failure,
+    struct_constructor_call_field_defaults,
+    struct_constructor_call_missing_field_failure,
     struct_declaration,
     struct_declaration_failure,
     struct_declaration_with_fields,
+    struct_field_default_type_failure,
+    struct_formats_as_its_name,
+    struct_literal_duplicate_field_failure,
+    struct_literal_empty_collection_fields,
+    struct_literal_field_defaults,
+    struct_literal_field_type_failure,
+    struct_literal_missing_field_failure,
+    struct_literal_unknown_field_failure,
+    struct_literals,
     struct_with_field_access_failure,
     struct_with_fields_instatiation,
     struct_with_fields_instatiation_and_field_assignment,
@@ -794,17 +4894,67 @@ criterion_group!(
     struct_with_impl_using_mutable_self,
     struct_with_impl_using_self,
     struct_with_method_call_failure,
+    suffixed_literal_out_of_range_failure,
+    trait_implementation,
+    try_in_a_function_not_returning_an_option_failure,
+    try_on_a_non_option_failure,
+    try_on_a_variant_of_a_different_enum_failure,
+    try_outside_a_function_failure,
+    try_with_a_different_error_type_failure,
+    tuple_destructure_arity_failure,
+    tuple_destructuring,
+    tuple_field_assignment_failure,
+    tuple_field_out_of_range_failure,
+    tuple_literals_and_fields,
+    tuple_literals_widen_to_the_declared_type,
+    tuple_map_keys,
+    tuple_return_type_failure,
+    type_check_binary_operator_failure,
+    type_check_cast_from_string_failure,
+    type_check_cast_to_non_numeric_failure,
+    type_check_mixed_int_and_float_failure,
+    type_check_narrowing_assignment_failure,
+    type_check_operator_without_trait_impl_failure,
+    type_check_overloaded_index_result_failure,
+    type_check_overloaded_operator_operand_failure,
+    type_check_overloaded_operator_result_failure,
+    type_check_promoted_result_failure,
+    type_check_signed_and_unsigned_mix_failure,
+    type_check_trait_impl_missing_function_failure,
+    type_check_trait_impl_return_type_failure,
+    type_check_trait_impl_self_type_failure,
+    type_check_trait_type_argument_count_failure,
+    type_check_unexecuted_branch_failure,
+    type_check_unexecuted_call_argument_failure,
+    type_check_unexecuted_call_arity_failure,
+    type_check_unexecuted_field_access_failure,
+    type_check_unexecuted_return_failure,
+    type_check_unsigned_negation_failure,
+    undefined_function_failure,
+    undefined_variable_failure,
+    uninitialized_char_variable_failure,
+    uninitialized_enum_variable_failure,
+    uninitialized_function_variable_failure,
+    uninitialized_struct_variable_failure,
+    unsigned_arithmetic,
+    unsigned_map_keys,
+    unsigned_overflow_failure,
+    unsigned_underflow_failure,
+    unterminated_block_comment_failure,
     while_loop,
     array_equal,
     array_not_equal,
     bool_equal,
     bool_not_equal,
+    char_empty_literal_failure,
     char_equal,
     char_greater,
     char_greater_or_equal,
     char_less,
     char_less_or_equal,
+    char_literal_of_several_characters_failure,
     char_not_equal,
+    expression_missing_at_the_end_of_the_source_failure,
     f64_variable_declaration,
     f64_variable_declaration_and_assignment,
     f64_variable_re_assignment,
@@ -1,8 +1,32 @@
+use crate::ast::stmt::Stmt;
 use crate::error::*;
-use crate::token::TokenType;
+use crate::format::FormatSpec;
+use crate::syntax::span::SourceLocation;
+use crate::token::{TokenType, TypeAnnotation};
 use crate::value::TypedValue;
 use std::convert::Into;
 use std::convert::TryInto;
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Uniquely identifies an expression node, so later passes can attach data to it through side
+/// tables instead of mutating the tree
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Ids are unique across parsers, imported files are parsed separately from the main one
+    pub fn next() -> NodeId {
+        static NEXT_NODE_ID: AtomicUsize = AtomicUsize::new(0);
+        NodeId(NEXT_NODE_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Expr {
@@ -11,12 +35,23 @@ pub enum Expr {
     Call(Box<CallExpr>),
     Get(Box<GetExpr>),
     EnumPath(Box<EnumPathExpr>),
+    Match(Box<MatchExpr>),
     Grouping(Box<GroupingExpr>),
     Literal(Box<LiteralExpr>),
     Logical(Box<LogicalExpr>),
     Set(Box<SetExpr>),
     Unary(Box<UnaryExpr>),
     Array(Box<ArrayExpr>),
+    Map(Box<MapExpr>),
+    Tuple(Box<TupleExpr>),
+    StructLiteral(Box<StructLiteralExpr>),
+    Try(Box<TryExpr>),
+    Cast(Box<CastExpr>),
+    Block(Box<BlockExpr>),
+    If(Box<IfExpr>),
+    Loop(Box<LoopExpr>),
+    Range(Box<RangeExpr>),
+    FormatString(Box<FormatStringExpr>),
     Index(Box<IndexExpr>),
     SetArrayElement(Box<SetArrayElementExpr>),
     Variable(Box<VariableExpr>),
@@ -25,6 +60,7 @@ pub enum Expr {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SelfIdentExpr {
+    pub id: NodeId,
     pub keyword: String,
 }
 
@@ -33,30 +69,181 @@ pub struct SetArrayElementExpr {
     pub index: Expr,
     pub name: String,
     pub value: Expr,
+    /// The operator of a compound assignment such as `a[i] += 1`, which combines the element with
+    /// `value`
+    pub operator: Option<TokenType>,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct EnumPathExpr {
     pub name: String,
     pub path_items: Vec<String>,
+    pub location: SourceLocation,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchExpr {
+    /// The value being matched against
+    pub scrutinee: Expr,
+    pub arms: Vec<MatchArm>,
+    pub location: SourceLocation,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    /// Either an expression statement, whose value becomes the value of the match, or a block
+    pub body: Stmt,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    /// `_`, matches anything without binding it
+    Wildcard,
+    /// An identifier, matches anything and binds it to the identifier
+    Binding(String),
+    Literal(TypedValue),
+    EnumVariant(Box<EnumVariantPattern>),
+}
+
+impl Pattern {
+    /// Whether the pattern matches every value it could be given
+    pub fn is_irrefutable(&self) -> bool {
+        matches!(self, Pattern::Wildcard | Pattern::Binding(_))
+    }
+}
+
+/// A pattern of the form `Enum::Variant` or `Enum::Variant(field_patterns...)`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnumVariantPattern {
+    pub enum_name: String,
+    pub variant: String,
+    pub fields: Vec<Pattern>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct AssignExpr {
+    pub id: NodeId,
     pub expr: Expr,
     pub name: String,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct IndexExpr {
     pub index: Expr,
     pub from: String,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ArrayExpr {
-    pub type_annotation: Option<TokenType>,
+    /// The type of the variable or field the array initializes, which gives an empty array its
+    /// type
+    pub type_annotation: Option<TypeAnnotation>,
     pub elements: Vec<Expr>,
+    pub location: SourceLocation,
+}
+
+/// A map literal, e.g. `["a": 1, "b": 2]`, or `[:]` for an empty map
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MapExpr {
+    /// The type of the variable the map initializes, which gives an empty map its type
+    pub type_annotation: Option<TypeAnnotation>,
+    pub entries: Vec<(Expr, Expr)>,
+    pub location: SourceLocation,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TupleExpr {
+    pub elements: Vec<Expr>,
+    pub location: SourceLocation,
+}
+
+/// A struct literal, e.g. `Point { x: 1, y: 2 }`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StructLiteralExpr {
+    pub name: String,
+    /// The fields given a value, in the order they're written in
+    pub fields: Vec<(String, Expr)>,
+    pub location: SourceLocation,
+}
+
+/// The `?` operator, e.g. `parse(input)?`, which unwraps a `Some` or `Ok` and returns a `None` or
+/// `Err` from the enclosing function
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TryExpr {
+    pub expression: Expr,
+    pub location: SourceLocation,
+}
+
+/// A conversion of a number, bool or char to a numeric type, e.g. `count as f64`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CastExpr {
+    pub expression: Expr,
+    pub target: TypeAnnotation,
+    pub location: SourceLocation,
+}
+
+/// A block evaluating to the expression it ends with, e.g. `{ let a = 1; a + 1 }`. A block that
+/// doesn't end with one evaluates to unit
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BlockExpr {
+    pub statements: Vec<Stmt>,
+    pub value: Option<Expr>,
+    pub location: SourceLocation,
+}
+
+/// An `if` evaluating to the value of the branch taken, e.g. `if (a) { 1 } else { 2 }`. The else
+/// branch is either a block or another `if`, without one the `if` evaluates to unit
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IfExpr {
+    pub condition: Expr,
+    pub then_branch: BlockExpr,
+    pub else_branch: Option<Expr>,
+    pub location: SourceLocation,
+}
+
+/// A loop only left through a `break`, evaluating to the value it's broken with, e.g.
+/// `loop { if (done) { break result; } }`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LoopExpr {
+    /// The label naming the loop for a `break` or `continue` in a nested loop, e.g. `'outer`
+    pub label: Option<String>,
+    pub body: Vec<Stmt>,
+    pub location: SourceLocation,
+}
+
+/// The integers from `start` up to `end`, which is left out of it unless the range is inclusive,
+/// e.g. `0..n` or `1..=10`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RangeExpr {
+    pub start: Expr,
+    pub end: Expr,
+    pub inclusive: bool,
+    pub location: SourceLocation,
+}
+
+/// A format string, e.g. `f"x = {x}, y = {y:.2}"`, which makes a string out of its text and the
+/// values of its arguments written the way their specs say
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FormatStringExpr {
+    pub pieces: Vec<FormatPiece>,
+    pub location: SourceLocation,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FormatPiece {
+    Text(String),
+    Argument(FormatArgument),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FormatArgument {
+    pub value: Expr,
+    pub spec: FormatSpec,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -64,18 +251,21 @@ pub struct BinaryExpr {
     pub left: Expr,
     pub operator: TokenType,
     pub right: Expr,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct CallExpr {
     pub arguments: Vec<Expr>,
     pub callee: Expr,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct GetExpr {
     pub name: String,
     pub object: Expr,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -93,6 +283,7 @@ pub struct LogicalExpr {
     pub left: Expr,
     pub operator: TokenType,
     pub right: Expr,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -103,17 +294,24 @@ pub struct SetExpr {
     pub object: Expr,
     /// The value the field should be set to
     pub value: Expr,
+    /// The operator of a compound assignment such as `p.x += 1`, which combines the field with
+    /// `value`
+    pub operator: Option<TokenType>,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct UnaryExpr {
     pub operator: TokenType,
     pub right: Expr,
+    pub location: SourceLocation,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct VariableExpr {
+    pub id: NodeId,
     pub name: String,
+    pub location: SourceLocation,
 }
 
 impl LiteralExpr {
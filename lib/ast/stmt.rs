@@ -1,11 +1,15 @@
 use crate::ast::expr::*;
 use crate::error::*;
+use crate::syntax::span::SourceLocation;
 use crate::token::{TokenType, TypeAnnotation};
+use crate::value::{TypedValue, Value};
+use std::collections::HashMap;
 use std::convert::TryInto;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Stmt {
-    Break,
+    Break(Box<BreakStmt>),
+    Continue(Box<ContinueStmt>),
     Assert(Box<AssertStmt>),
     Enum(Box<EnumStmt>),
     Impl(Box<ImplStmt>),
@@ -20,11 +24,13 @@ pub enum Stmt {
     Print(Box<PrintStmt>),
     Return(Box<ReturnStmt>),
     Var(Box<VarStmt>),
+    Destructure(Box<DestructureStmt>),
     While(Box<WhileStmt>),
+    ForIn(Box<ForInStmt>),
     Import(Box<ImportStmt>),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct VariableData {
     pub identifier: String,
     pub type_annotation: TypeAnnotation,
@@ -39,109 +45,252 @@ impl VariableData {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct EnumItem {
     pub identifier: String,
+    /// Types of the values carried by the variant, empty for C-style variants
+    pub fields: Vec<TypeAnnotation>,
     pub initializer: Option<Expr>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct EnumStmt {
     pub name: String,
+    /// Type parameters of a generic enum, e.g. the `T` of `enum Option<T>`
+    pub type_params: Vec<String>,
     pub item_list: Vec<EnumItem>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ImplStmt {
     pub name: String,
+    /// Type parameters of a generic impl, e.g. the `T` of `impl<T> Stack<T>`
+    pub type_params: Vec<String>,
     pub fn_declarations: Vec<Stmt>,
 }
 
 /// Implementation of a trait by a instance of a type with identifier 'impl_name'
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ImplTraitStmt {
     /// Name of trait to be implemented
     pub trait_name: String,
+    /// Type arguments given to a generic trait, e.g. the `i32` and `f64` of
+    /// `impl Index<i32, f64> for Polynomial`
+    pub trait_type_args: Vec<TypeAnnotation>,
     /// Identifier of type that will implement trait_name
     pub impl_name: String,
+    /// Type parameters of a generic impl, e.g. the `T` of `impl<T> Trait for Stack<T>`
+    pub type_params: Vec<String>,
     /// Function declarations for trait
     pub fn_declarations: Vec<Stmt>,
+    pub location: SourceLocation,
 }
 
-#[derive(Clone, Debug)]
+impl ImplTraitStmt {
+    /// Binds the type parameters `trait_type_params` of the implemented trait, and its `Self`, to
+    /// the types this impl gives them
+    pub fn trait_bindings(&self, trait_type_params: &[String]) -> HashMap<String, TypeAnnotation> {
+        let self_type = self_type(&self.impl_name, &self.type_params);
+        let mut bindings: HashMap<String, TypeAnnotation> = trait_type_params
+            .iter()
+            .cloned()
+            .zip(self.trait_type_args.iter().cloned())
+            .collect();
+        bindings.insert(SELF_TYPE.to_string(), self_type);
+        bindings
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct BlockStmt {
     pub statements: Vec<Stmt>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StructField {
+    pub identifier: String,
+    pub type_annotation: TypeAnnotation,
+    /// The value a struct literal gives the field when it doesn't, e.g. the `0` of `x: i32 = 0`
+    pub default: Option<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct StructStmt {
-    pub fields: Vec<VariableData>,
+    pub fields: Vec<StructField>,
     pub name: String,
+    pub type_params: Vec<String>,
+    pub location: SourceLocation,
 }
 
-#[derive(Clone, Debug)]
+impl StructStmt {
+    /// The fields `Name()` gives a value, which is their default or else the default value of
+    /// their type. The literal it stands for is missing the fields that have neither
+    pub fn constructor_fields(fields: &[StructField]) -> Vec<(String, Expr)> {
+        fields
+            .iter()
+            .filter_map(|field| {
+                let value = match field.default {
+                    Some(ref default) => default.clone(),
+                    None => Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
+                        Value::default_value(&field.type_annotation)?,
+                        field.type_annotation.clone(),
+                    )))),
+                };
+                Some((field.identifier.clone(), value))
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ExpressionStmt {
     pub expression: Expr,
 }
 
-#[derive(Clone, Debug)]
+/// The type parameter standing for the type implementing a trait in its function signatures
+pub const SELF_TYPE: &str = "Self";
+
+/// The type `Self` stands for in an impl for the struct `name` with the type parameters
+/// `type_params`, e.g. `Stack<T>` in `impl<T> Stack<T>`
+pub fn self_type(name: &str, type_params: &[String]) -> TypeAnnotation {
+    if type_params.is_empty() {
+        TypeAnnotation::User(name.to_string())
+    } else {
+        TypeAnnotation::Generic {
+            name: name.to_string(),
+            type_args: type_params
+                .iter()
+                .map(|type_param| TypeAnnotation::TypeParam(type_param.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct TraitStmt {
     pub name: String,
+    /// Type parameters of a generic trait, e.g. the `I` and `T` of `trait Index<I, T>`. Every
+    /// trait also has the implicit type parameter `Self`, the type implementing it
+    pub type_params: Vec<String>,
     pub trait_fn_declarations: Vec<Stmt>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct TraitFunctionStmt {
     pub name: String,
     pub return_type: TypeAnnotation,
     pub params: Vec<VariableData>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FunctionStmt {
     pub name: String,
+    pub type_params: Vec<String>,
     pub return_type: TokenType,
     pub params: Vec<VariableData>,
     pub body: Vec<Stmt>,
+    pub location: SourceLocation,
 }
 
-#[derive(Clone, Debug)]
+impl FunctionStmt {
+    /// The type of the function when used as a value
+    pub fn function_type(&self) -> TypeAnnotation {
+        TypeAnnotation::Function {
+            params: self
+                .params
+                .iter()
+                .map(|param| param.type_annotation.clone())
+                .collect(),
+            return_type: Box::new(
+                self.return_type
+                    .to_type_annotation()
+                    .unwrap_or(TypeAnnotation::Unit),
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct AssertStmt {
     pub condition: Expr,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct IfStmt {
     pub condition: Expr,
     pub else_branch: Option<Stmt>,
     pub then_branch: Stmt,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct PrintStmt {
     pub expression: Expr,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ReturnStmt {
     pub keyword: String,
     pub value: Expr,
+    pub location: SourceLocation,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct VarStmt {
+    pub id: NodeId,
     pub initializer: Option<Expr>,
-    pub type_annotation: TypeAnnotation,
+    /// `None` when the type is to be inferred from the initializer
+    pub type_annotation: Option<TypeAnnotation>,
     pub name: String,
+    pub location: SourceLocation,
+}
+
+/// Declares a variable for each element of a tuple, e.g. `let (a, b) = f();`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DestructureStmt {
+    pub names: Vec<String>,
+    pub initializer: Expr,
+    pub location: SourceLocation,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct WhileStmt {
     pub body: Stmt,
     pub condition: Expr,
+    /// The label naming the loop for a `break` or `continue` in a nested loop, e.g. `'outer`
+    pub label: Option<String>,
+    /// The increment of a `for` loop, run after the body and after a `continue`
+    pub increment: Option<Expr>,
+}
+
+/// Runs `body` once for each item of an array, a string's characters, a range or an iterator,
+/// with the item bound to `variable`, e.g. `for i in 0..n { ... }`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ForInStmt {
+    /// The label naming the loop for a `break` or `continue` in a nested loop, e.g. `'outer`
+    pub label: Option<String>,
+    pub variable: String,
+    pub iterable: Expr,
+    pub body: Vec<Stmt>,
+    pub location: SourceLocation,
+}
+
+/// Leaves the innermost loop, or the loop named by `label`. Only a `loop` can be left with a
+/// value, e.g. `break 'search index;`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BreakStmt {
+    pub label: Option<String>,
+    pub value: Option<Expr>,
+    pub location: SourceLocation,
+}
+
+/// Skips to the next iteration of the innermost loop, or the loop named by `label`
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ContinueStmt {
+    pub label: Option<String>,
+    pub location: SourceLocation,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ImportStmt {
     pub module_path: String,
 }
@@ -51,6 +51,9 @@ impl VisitorMut<Vec<Stmt>> for DependencyResolver {
     fn visit_enum_path(&mut self, _: &EnumPathExpr) -> Result<Vec<Stmt>, LangError> {
         unreachable!()
     }
+    fn visit_match(&mut self, _: &MatchExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
     fn visit_grouping(&mut self, _: &GroupingExpr) -> Result<Vec<Stmt>, LangError> {
         unreachable!()
     }
@@ -69,6 +72,36 @@ impl VisitorMut<Vec<Stmt>> for DependencyResolver {
     fn visit_array(&mut self, _: &ArrayExpr) -> Result<Vec<Stmt>, LangError> {
         unreachable!()
     }
+    fn visit_map(&mut self, _: &MapExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_tuple(&mut self, _: &TupleExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_struct_literal(&mut self, _: &StructLiteralExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_try(&mut self, _: &TryExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_cast(&mut self, _: &CastExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_block_expr(&mut self, _: &BlockExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_if_expr(&mut self, _: &IfExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_loop(&mut self, _: &LoopExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_range(&mut self, _: &RangeExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_format_string(&mut self, _: &FormatStringExpr) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
     fn visit_index(&mut self, _: &IndexExpr) -> Result<Vec<Stmt>, LangError> {
         unreachable!()
     }
@@ -83,7 +116,10 @@ impl VisitorMut<Vec<Stmt>> for DependencyResolver {
     }
 
     // stmt
-    fn visit_break(&mut self) -> Result<Vec<Stmt>, LangError> {
+    fn visit_break(&mut self, _: &BreakStmt) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
+    fn visit_continue(&mut self, _: &ContinueStmt) -> Result<Vec<Stmt>, LangError> {
         unreachable!()
     }
     fn visit_assert(&mut self, _: &AssertStmt) -> Result<Vec<Stmt>, LangError> {
@@ -128,9 +164,15 @@ impl VisitorMut<Vec<Stmt>> for DependencyResolver {
     fn visit_var(&mut self, _: &VarStmt) -> Result<Vec<Stmt>, LangError> {
         unreachable!()
     }
+    fn visit_destructure(&mut self, _: &DestructureStmt) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
     fn visit_while(&mut self, _: &WhileStmt) -> Result<Vec<Stmt>, LangError> {
         unreachable!()
     }
+    fn visit_for_in(&mut self, _: &ForInStmt) -> Result<Vec<Stmt>, LangError> {
+        unreachable!()
+    }
     fn visit_import(&mut self, import_stmt: &ImportStmt) -> Result<Vec<Stmt>, LangError> {
         // TODO!!!
         // This is naive as fuck but works as a PoC that all our stuff can work
@@ -8,29 +8,50 @@ use crate::value::*;
 use std::convert::*;
 use std::{
     collections::HashMap,
-    fmt::{self, Debug},
-    ops::{Index, IndexMut},
+    fmt::{self, Debug, Display},
 };
 
-pub type EnvironmentEntryIndex = usize;
+/// A handle to an [`EnvironmentEntry`]. Entries are reused once removed, so each handle carries
+/// the generation of the entry it was created for and handles to removed entries are rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EnvironmentEntryIndex {
+    index: usize,
+    generation: u64,
+}
+
+impl Display for EnvironmentEntryIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{}", self.index, self.generation)
+    }
+}
 
-// TODO: Generationl indices, memory management. Memory management will likely come along with
-// a VM
 #[derive(Clone, Debug)]
 pub struct EnvironmentEntry {
     pub values: HashMap<String, ArenaEntryIndex>,
     pub enclosing: Option<EnvironmentEntryIndex>,
 }
 
+#[derive(Debug)]
+struct EnvironmentSlot {
+    generation: u64,
+    entry: Option<EnvironmentEntry>,
+    /// Whether a function closes over the entry, which then outlives the scope it was made for
+    captured: bool,
+    /// Whether the scope the entry was made for has ended. Closed entries are only kept while a
+    /// function closing over them is reachable
+    closed: bool,
+}
+
 pub struct Environment {
     pub root_entry_id: EnvironmentEntryIndex,
     pub current_index: EnvironmentEntryIndex,
-    pub entries: Vec<EnvironmentEntry>,
+    entries: Vec<EnvironmentSlot>,
+    free: Vec<usize>,
 }
 
 impl Debug for Environment {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for entry in self.entries.iter() {
+        for entry in self.entries.iter().filter_map(|slot| slot.entry.as_ref()) {
             write!(f, "[")?;
             write!(
                 f,
@@ -50,75 +71,128 @@ impl Debug for Environment {
 
 impl Default for Environment {
     fn default() -> Environment {
-        Environment {
-            root_entry_id: 0,
-            current_index: 0,
-            entries: Vec::new(),
-        }
-    }
-}
-
-impl Index<EnvironmentEntryIndex> for Environment {
-    type Output = EnvironmentEntry;
-
-    fn index(&self, env_id: EnvironmentEntryIndex) -> &Self::Output {
-        &self.entries[env_id]
-    }
-}
-
-impl IndexMut<EnvironmentEntryIndex> for Environment {
-    fn index_mut(&mut self, env_id: EnvironmentEntryIndex) -> &mut EnvironmentEntry {
-        &mut self.entries[env_id]
+        Environment::new()
     }
 }
 
 impl Environment {
     pub fn new() -> Environment {
+        let root_entry_id = EnvironmentEntryIndex {
+            index: 0,
+            generation: 0,
+        };
         let mut env = Environment {
-            root_entry_id: 0,
-            current_index: 0,
+            root_entry_id,
+            current_index: root_entry_id,
             entries: Vec::new(),
+            free: Vec::new(),
         };
-        env.root_entry_id = env.entries.len();
-        env.entries.push(EnvironmentEntry {
-            values: HashMap::new(),
-            enclosing: None,
-        });
+        env.root_entry_id = env.new_entry();
+        env.current_index = env.root_entry_id;
         env
     }
+
     pub fn new_entry(&mut self) -> EnvironmentEntryIndex {
-        let env_id = self.entries.len();
-        self.entries.push(EnvironmentEntry {
+        let entry = EnvironmentEntry {
             values: HashMap::new(),
             enclosing: None,
-        });
-        env_id
+        };
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.entries[index];
+                slot.entry = Some(entry);
+                slot.captured = false;
+                slot.closed = false;
+                EnvironmentEntryIndex {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.entries.push(EnvironmentSlot {
+                    generation: 0,
+                    entry: Some(entry),
+                    captured: false,
+                    closed: false,
+                });
+                EnvironmentEntryIndex {
+                    index: self.entries.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Returns the entry `env_id` refers to, or an internal error if it has since been removed
+    pub fn entry(&self, env_id: EnvironmentEntryIndex) -> Result<&EnvironmentEntry, LangError> {
+        match self.entries.get(env_id.index) {
+            Some(EnvironmentSlot {
+                generation,
+                entry: Some(entry),
+                ..
+            }) if *generation == env_id.generation => Ok(entry),
+            _ => Err(Environment::stale_entry_error(env_id)),
+        }
+    }
+
+    /// Mutable counterpart of [`Environment::entry`]
+    pub fn entry_mut(
+        &mut self,
+        env_id: EnvironmentEntryIndex,
+    ) -> Result<&mut EnvironmentEntry, LangError> {
+        match self.entries.get_mut(env_id.index) {
+            Some(EnvironmentSlot {
+                generation,
+                entry: Some(entry),
+                ..
+            }) if *generation == env_id.generation => Ok(entry),
+            _ => Err(Environment::stale_entry_error(env_id)),
+        }
+    }
+
+    fn stale_entry_error(env_id: EnvironmentEntryIndex) -> LangError {
+        LangErrorType::new_iie_error(format!(
+            "tried to use the environment entry {}, which has been removed",
+            env_id
+        ))
+    }
+
+    /// Walks `distance` enclosing links up from `env_id`
+    pub fn ancestor(
+        &self,
+        env_id: EnvironmentEntryIndex,
+        distance: usize,
+    ) -> Result<EnvironmentEntryIndex, LangError> {
+        let mut current = env_id;
+        for _ in 0..distance {
+            current = self.entry(current)?.enclosing.ok_or_else(|| {
+                LangErrorType::new_iie_error(format!(
+                    "the environment entry {} has fewer than {} enclosing entries",
+                    env_id, distance
+                ))
+            })?;
+        }
+        Ok(current)
     }
 
-    pub fn get_at(&mut self, index: usize, name: &str) -> Result<ArenaEntryIndex, LangError> {
-        self.entries.get(index).map_or(
+    /// Gets `name` from the entry `distance` links up from `env_id`, as computed by the resolver
+    pub fn get_at(
+        &self,
+        env_id: EnvironmentEntryIndex,
+        distance: usize,
+        name: &str,
+    ) -> Result<ArenaEntryIndex, LangError> {
+        let ancestor = self.ancestor(env_id, distance)?;
+        self.entry(ancestor)?.values.get(name).map_or(
             Err(LangErrorType::new_runtime_error(
-                RuntimeErrorType::GenericError {
+                RuntimeErrorType::UndefinedVariable {
                     reason: format!(
-                        "tried to index an environment with lenght {} at index {}",
-                        self.entries.len(),
-                        index
+                        "tried to get an undefined variable: '{}' at index {}",
+                        name, ancestor
                     ),
                 },
             )),
-            |env_entry| {
-                env_entry.values.get(name).map_or(
-                    Err(LangErrorType::new_runtime_error(
-                        RuntimeErrorType::UndefinedVariable {
-                            reason: format!(
-                                "tried to get an undefined variable: '{}' at index {}",
-                                name, index
-                            ),
-                        },
-                    )),
-                    |value| Ok(*value),
-                )
-            },
+            |value| Ok(*value),
         )
     }
 
@@ -127,9 +201,10 @@ impl Environment {
         env_id: EnvironmentEntryIndex,
         name: &str,
     ) -> Result<ArenaEntryIndex, LangError> {
-        if self[env_id].values.contains_key(name) {
-            return Ok(self[env_id].values[name]);
-        } else if let Some(enclosing) = self[env_id].enclosing {
+        let entry = self.entry(env_id)?;
+        if let Some(index) = entry.values.get(name) {
+            return Ok(*index);
+        } else if let Some(enclosing) = entry.enclosing {
             return Ok(self.get(enclosing, name)?);
         }
         // We error when an assignment is attempted on a variable that hasn't been instantiated
@@ -140,8 +215,16 @@ impl Environment {
         ))
     }
 
-    pub fn define(&mut self, env_id: EnvironmentEntryIndex, name: &str, index: ArenaEntryIndex) {
-        self[env_id].values.insert(name.to_string(), index);
+    pub fn define(
+        &mut self,
+        env_id: EnvironmentEntryIndex,
+        name: &str,
+        index: ArenaEntryIndex,
+    ) -> Result<(), LangError> {
+        self.entry_mut(env_id)?
+            .values
+            .insert(name.to_string(), index);
+        Ok(())
     }
 
     pub fn define_and_insert(
@@ -150,7 +233,7 @@ impl Environment {
         arena: &mut Arena<TypedValue>,
         name: &str,
         value: TypedValue,
-    ) -> ArenaEntryIndex {
+    ) -> Result<ArenaEntryIndex, LangError> {
         debug!(
             "{}:{} Defining '{}' with value '{:?}' at index '{}'",
             file!(),
@@ -159,9 +242,10 @@ impl Environment {
             value,
             env_id
         );
+        let entry = self.entry_mut(env_id)?;
         let index = arena.insert(value);
-        self[env_id].values.insert(name.to_string(), index);
-        index
+        entry.values.insert(name.to_string(), index);
+        Ok(index)
     }
 
     pub fn assign(
@@ -179,17 +263,10 @@ impl Environment {
             value,
             env_id
         );
-        if self[env_id].values.contains_key(name) {
-            if let Some(existing_value_index) = self[env_id].values.get(name) {
-                let existing_value_entry = &mut arena[*existing_value_index];
-                let existing_value: &mut TypedValue = existing_value_entry.try_into()?;
-                if !TypeChecker::can_convert_implicitly(existing_value, &value) {
-                    TypeChecker::check_type(existing_value, &value)?;
-                }
-                *existing_value = value;
-            }
-            return Ok(());
-        } else if let Some(enclosing) = self[env_id].enclosing {
+        let entry = self.entry(env_id)?;
+        if let Some(existing_value_index) = entry.values.get(name) {
+            return Environment::assign_value(*existing_value_index, value, arena);
+        } else if let Some(enclosing) = entry.enclosing {
             self.assign(enclosing, name, value, arena)?;
             return Ok(());
         }
@@ -201,29 +278,51 @@ impl Environment {
         ))
     }
 
+    /// Assigns `name` in the entry `distance` links up from `env_id`, as computed by the resolver
+    pub fn assign_at(
+        &mut self,
+        env_id: EnvironmentEntryIndex,
+        distance: usize,
+        name: &str,
+        value: TypedValue,
+        arena: &mut Arena<TypedValue>,
+    ) -> Result<(), LangError> {
+        let existing_value_index = self.get_at(env_id, distance, name).map_err(|_| {
+            LangErrorType::new_runtime_error(RuntimeErrorType::UndefinedVariable {
+                reason: format!("tried to assign an undefined variable: '{}'", name),
+            })
+        })?;
+        Environment::assign_value(existing_value_index, value, arena)
+    }
+
+    fn assign_value(
+        index: ArenaEntryIndex,
+        value: TypedValue,
+        arena: &mut Arena<TypedValue>,
+    ) -> Result<(), LangError> {
+        let existing_value_entry = &mut arena[index];
+        let existing_value: &mut TypedValue = existing_value_entry.try_into()?;
+        if !TypeChecker::can_convert_implicitly(existing_value, &value) {
+            TypeChecker::check_type(existing_value, &value)?;
+        }
+        *existing_value = value;
+        Ok(())
+    }
+
     pub fn assign_index_entry(
         &self,
         env_id: EnvironmentEntryIndex,
         name: &str,
         value: &TypedValue,
         arena: &mut Arena<TypedValue>,
-        index: usize,
+        index: &TypedValue,
     ) -> Result<(), LangError> {
-        if let Some(arr_value_index) = self[env_id].values.get(name) {
+        if let Some(arr_value_index) = self.entry(env_id)?.values.get(name) {
             let arr_value_entry = &mut arena[*arr_value_index];
             let arr_value: &mut TypedValue = arr_value_entry.try_into()?;
             match arr_value.value {
                 Value::Array(ref mut arr) => {
-                    if index >= arr.len() {
-                        return Err(Lang::error_s(
-                            name,
-                            &format!(
-                                "Index out of bounds. Tried to index at {} for an array of length {}",
-                                index,
-                                arr.len()
-                            ),
-                        ));
-                    }
+                    let index = index.as_array_index(arr.len())?;
                     arr[index].assign_checked(value)?;
                     return Ok(());
                 }
@@ -245,24 +344,117 @@ impl Environment {
         ))
     }
 
-    pub fn entry_from(&mut self, enclosing: EnvironmentEntryIndex) -> EnvironmentEntryIndex {
+    /// Creates a new entry enclosed by `enclosing`
+    pub fn entry_from(
+        &mut self,
+        enclosing: EnvironmentEntryIndex,
+    ) -> Result<EnvironmentEntryIndex, LangError> {
+        self.entry(enclosing)?;
         let new_entry = self.new_entry();
-        self[new_entry].enclosing = Some(enclosing);
-        new_entry
+        self.entry_mut(new_entry)?.enclosing = Some(enclosing);
+        Ok(new_entry)
     }
 
-    pub fn remove_entry(&mut self, env_id: EnvironmentEntryIndex) {
-        self.entries.remove(env_id);
+    /// Ends the scope of the entry `env_id`. Entries captured by a function are kept for as long
+    /// as the function is reachable, any other entry is removed straight away, invalidating every
+    /// handle to it
+    pub fn remove_entry(&mut self, env_id: EnvironmentEntryIndex) -> Result<(), LangError> {
+        self.entry(env_id)?;
+        let slot = &mut self.entries[env_id.index];
+        if slot.captured {
+            slot.closed = true;
+        } else {
+            self.free_entry(env_id.index);
+        }
+        Ok(())
     }
 
-    pub fn is_defined(&self, env_id: EnvironmentEntryIndex, name: String) -> bool {
-        if self[env_id].values.contains_key(&name) {
-            return true;
+    /// Ends the scope of the entry `env_id`, which is either the current entry or encloses it.
+    /// The entries between them were opened by declarations shadowing a variable of the scope
+    pub fn remove_scope(&mut self, env_id: EnvironmentEntryIndex) -> Result<(), LangError> {
+        let mut current = self.current_index;
+        while current != env_id {
+            let enclosing = self.entry(current)?.enclosing.ok_or_else(|| {
+                LangErrorType::new_iie_error(format!(
+                    "the environment entry {} isn't enclosed by {}",
+                    self.current_index, env_id
+                ))
+            })?;
+            self.remove_entry(current)?;
+            current = enclosing;
         }
-        if let Some(enclosing) = self[env_id].enclosing {
-            return self.is_defined(enclosing, name);
+        self.remove_entry(env_id)
+    }
+
+    fn free_entry(&mut self, index: usize) {
+        let slot = &mut self.entries[index];
+        slot.entry = None;
+        slot.generation += 1;
+        self.free.push(index);
+    }
+
+    /// Keeps `env_id` and the entries enclosing it alive after their scopes end, for a function
+    /// closing over them
+    pub fn capture(&mut self, env_id: EnvironmentEntryIndex) -> Result<(), LangError> {
+        let mut next = Some(env_id);
+        while let Some(env_id) = next {
+            next = self.entry(env_id)?.enclosing;
+            self.entries[env_id.index].captured = true;
+        }
+        Ok(())
+    }
+
+    /// Frees the values in `arena`, and the closed entries, that can't be reached from an entry
+    /// whose scope hasn't ended or from a temporary value. Returns the number of values freed
+    pub fn collect_garbage(&mut self, arena: &mut Arena<TypedValue>) -> usize {
+        let mut tracer = Tracer::default();
+        for (index, slot) in self.entries.iter().enumerate() {
+            if slot.entry.is_some() && !slot.closed {
+                tracer.scope(EnvironmentEntryIndex {
+                    index,
+                    generation: slot.generation,
+                });
+            }
+        }
+        let mut marked = vec![false; self.entries.len()];
+        let freed_values = arena.collect_traced(tracer, |env_id, tracer| {
+            if let Ok(entry) = self.entry(env_id) {
+                if !marked[env_id.index] {
+                    marked[env_id.index] = true;
+                    for index in entry.values.values() {
+                        tracer.value(*index);
+                    }
+                    if let Some(enclosing) = entry.enclosing {
+                        tracer.scope(enclosing);
+                    }
+                }
+            }
+        });
+        let mut freed_entries = 0;
+        for (index, is_marked) in marked.into_iter().enumerate() {
+            if !is_marked && self.entries[index].closed {
+                self.free_entry(index);
+                freed_entries += 1;
+            }
+        }
+        debug!(
+            "{}:{} Collected {} closed environment entries",
+            file!(),
+            line!(),
+            freed_entries
+        );
+        freed_values
+    }
+
+    pub fn is_defined(&self, env_id: EnvironmentEntryIndex, name: String) -> bool {
+        match self.entry(env_id) {
+            Ok(entry) if entry.values.contains_key(&name) => true,
+            Ok(EnvironmentEntry {
+                enclosing: Some(enclosing),
+                ..
+            }) => self.is_defined(*enclosing, name),
+            _ => false,
         }
-        false
     }
 
     pub fn update_value<Closure>(
@@ -275,12 +467,9 @@ impl Environment {
     where
         Closure: FnOnce(&mut TypedValue) -> Result<(), LangError>,
     {
-        if let Some(value) = self[env_id].values.get(name) {
+        if let Some(value) = self.entry(env_id)?.values.get(name) {
             let value_entry = &mut arena[*value];
-            let value = match value_entry {
-                ArenaEntry::Occupied(v) => v,
-                ArenaEntry::Emtpy => panic!(),
-            };
+            let value: &mut TypedValue = value_entry.try_into()?;
             debug!(
                 "{}:{} Updating value in env with identifier '{}'",
                 file!(),
@@ -297,3 +486,81 @@ impl Environment {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::env::*;
+
+    #[test]
+    fn removed_entries_are_stale() {
+        let mut env = Environment::new();
+        let mut arena: Arena<TypedValue> = Arena::new();
+        let block = env.entry_from(env.root_entry_id).unwrap();
+        env.define_and_insert(block, &mut arena, "a", TypedValue::default())
+            .unwrap();
+        env.remove_entry(block).unwrap();
+        let reused = env.entry_from(env.root_entry_id).unwrap();
+
+        assert_ne!(block, reused);
+        assert!(env.entry(block).is_err());
+        assert!(env.get(block, "a").is_err());
+        assert!(env.entry_from(block).is_err());
+        assert!(env.remove_entry(block).is_err());
+        assert!(env.get(reused, "a").is_err());
+    }
+
+    #[test]
+    fn captured_entries_outlive_their_scope() {
+        let mut env = Environment::new();
+        let mut arena: Arena<TypedValue> = Arena::new();
+        let block = env.entry_from(env.root_entry_id).unwrap();
+        let index = env
+            .define_and_insert(block, &mut arena, "a", TypedValue::default())
+            .unwrap();
+        let nested = env.entry_from(block).unwrap();
+        env.capture(nested).unwrap();
+        env.remove_entry(nested).unwrap();
+        env.remove_entry(block).unwrap();
+        arena.release_temporaries(0);
+
+        assert_eq!(env.get(nested, "a").unwrap(), index);
+        // Nothing refers to the closed entries, so they're freed along with their values
+        assert_eq!(env.collect_garbage(&mut arena), 1);
+        assert!(env.entry(nested).is_err());
+        assert!(env.entry(block).is_err());
+    }
+
+    #[test]
+    fn get_walks_enclosing_entries() {
+        let mut env = Environment::new();
+        let mut arena: Arena<TypedValue> = Arena::new();
+        let root = env.root_entry_id;
+        let index = env
+            .define_and_insert(root, &mut arena, "a", TypedValue::default())
+            .unwrap();
+        let block = env.entry_from(root).unwrap();
+
+        assert_eq!(env.get(block, "a").unwrap(), index);
+        assert!(env.is_defined(block, "a".to_string()));
+        assert!(!env.is_defined(root, "b".to_string()));
+    }
+
+    #[test]
+    fn get_at_skips_shadowing_entries() {
+        let mut env = Environment::new();
+        let mut arena: Arena<TypedValue> = Arena::new();
+        let root = env.root_entry_id;
+        let outer = env
+            .define_and_insert(root, &mut arena, "a", TypedValue::default())
+            .unwrap();
+        let block = env.entry_from(root).unwrap();
+        let inner = env
+            .define_and_insert(block, &mut arena, "a", TypedValue::default())
+            .unwrap();
+
+        assert_eq!(env.ancestor(block, 1).unwrap(), root);
+        assert_eq!(env.get_at(block, 0, "a").unwrap(), inner);
+        assert_eq!(env.get_at(block, 1, "a").unwrap(), outer);
+        assert!(env.get_at(block, 2, "a").is_err());
+    }
+}
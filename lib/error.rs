@@ -17,6 +17,10 @@ pub enum ErrMessage {
     ExpectExpr(String),
     ExpectStmt(String),
     IncorrectIndexType(String),
+    /// An index, as it's written, and the length of the array it's out of bounds for
+    IndexOutOfBounds(String, usize),
+    /// A key missing from a map, as `{:?}` writes it
+    MissingKey(String),
 }
 
 #[inline]
@@ -30,6 +34,11 @@ pub fn error_message(msg_type: &ErrMessage) -> String {
             "tried to index an array with an incorrect type '{}'",
             type_string
         ),
+        IndexOutOfBounds(index, len) => format!(
+            "index {} is out of bounds for an array of length {}",
+            index, len
+        ),
+        MissingKey(key) => format!("key {} is not in the map", key),
     }
 }
 
@@ -53,18 +62,86 @@ pub enum RuntimeErrorType {
     InvalidFunctionArgumentType { reason: String },
     #[fail(display = "Invalid function return type: {}", reason)]
     InvalidFunctionReturnType { reason: String },
+    /// Integer overflow or division by zero
+    #[fail(display = "Arithmetic error: {}", reason)]
+    ArithmeticError { reason: String },
+    /// An `assert` whose condition was false
+    #[fail(display = "Assertion error: {}", reason)]
+    AssertionError { reason: String },
     #[fail(display = "{}", reason)]
     GenericError { reason: String },
 }
 
-#[derive(Fail, Debug, Clone, PartialEq, PartialOrd)]
+/// A jump out of the statements being run to somewhere other than the next one. Unlike a
+/// [`LangError`] it's expected, and is caught by the loop or function call it jumps to
+#[derive(Debug, Clone, PartialEq)]
 pub enum ControlFlow {
-    #[fail(display = "Break")]
-    Break,
-    #[fail(display = "Assert")]
-    Assert,
-    #[fail(display = "Assert")]
-    Return { index: ArenaEntryIndex },
+    Break {
+        label: Option<String>,
+        value: Option<ArenaEntryIndex>,
+    },
+    Continue {
+        label: Option<String>,
+    },
+    Return {
+        index: ArenaEntryIndex,
+    },
+}
+
+impl ControlFlow {
+    /// Whether the `break` or `continue` is aimed at the loop labelled `label`. One without a
+    /// label is aimed at the innermost loop
+    pub fn targets(&self, label: &Option<String>) -> bool {
+        match self {
+            ControlFlow::Break {
+                label: Some(target),
+                ..
+            }
+            | ControlFlow::Continue {
+                label: Some(target),
+            } => label.as_ref() == Some(target),
+            ControlFlow::Break { label: None, .. } | ControlFlow::Continue { label: None } => true,
+            ControlFlow::Return { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for ControlFlow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ControlFlow::Break { .. } => write!(f, "break"),
+            ControlFlow::Continue { .. } => write!(f, "continue"),
+            ControlFlow::Return { .. } => write!(f, "return"),
+        }
+    }
+}
+
+/// Why running a statement or evaluating an expression stopped early, either to jump elsewhere or
+/// because of an error
+#[derive(Debug)]
+pub enum Interrupt {
+    ControlFlow(ControlFlow),
+    Error(LangError),
+}
+
+impl Interrupt {
+    /// The error this interrupt stands for once nothing is left to catch it. The resolver rejects
+    /// a `break`, `continue` or `return` outside of a loop or function, so only errors remain
+    pub fn into_error(self) -> LangError {
+        match self {
+            Interrupt::Error(error) => error,
+            Interrupt::ControlFlow(control_flow) => LangErrorType::new_iie_error(format!(
+                "'{}' escaped the loop or function it was used in",
+                control_flow
+            )),
+        }
+    }
+}
+
+impl From<LangError> for Interrupt {
+    fn from(error: LangError) -> Interrupt {
+        Interrupt::Error(error)
+    }
 }
 
 impl Debug for RuntimeErrorType {
@@ -79,6 +156,8 @@ impl Debug for RuntimeErrorType {
             | RuntimeErrorType::InvalidTypeAssignmentError { reason }
             | RuntimeErrorType::InvalidFunctionArgumentType { reason }
             | RuntimeErrorType::InvalidFunctionReturnType { reason }
+            | RuntimeErrorType::ArithmeticError { reason }
+            | RuntimeErrorType::AssertionError { reason }
             | RuntimeErrorType::GenericError { reason } => write!(f, "{}", reason),
         }
     }
@@ -88,12 +167,13 @@ impl Debug for RuntimeErrorType {
 pub enum LangErrorType {
     #[fail(display = "Parser error: {}", reason)]
     ParserError { reason: String },
+    /// Raised by the static type checker, `reason` holds every error found in the program
+    #[fail(display = "Type error: {}", reason)]
+    TypeError { reason: String },
     #[fail(display = "IIE: {}", reason)]
     InternalError { reason: String },
     #[fail(display = "Runtime error: {}", subtype)]
     RuntimeError { subtype: RuntimeErrorType },
-    #[fail(display = "Control flow: {}", subtype)]
-    ControlFlow { subtype: ControlFlow },
 }
 
 impl Debug for LangErrorType {
@@ -106,9 +186,7 @@ impl Debug for LangErrorType {
                 reason, ISSUES_URL
             ),
             LangErrorType::ParserError { reason } => write!(f, "parser error {:?}", reason),
-            LangErrorType::ControlFlow { subtype } => {
-                write!(f, "{:?} must be used within a loop", subtype)
-            }
+            LangErrorType::TypeError { reason } => write!(f, "type error:\n{}", reason),
         }
     }
 }
@@ -119,6 +197,10 @@ impl LangErrorType {
         LangError::from(LangErrorType::ParserError { reason })
     }
 
+    pub fn new_type_error(reason: String) -> LangError {
+        LangError::from(LangErrorType::TypeError { reason })
+    }
+
     pub fn new_iie_error(reason: String) -> LangError {
         LangError::from(LangErrorType::InternalError { reason })
     }
@@ -0,0 +1,390 @@
+use crate::ast::expr::*;
+use crate::ast::stmt::*;
+use crate::error::*;
+use crate::syntax::span::SourceLocation;
+use crate::value::Value;
+use crate::visitor::*;
+
+use std::collections::HashMap;
+
+/// Checks that every `match` covers all of the values it can be given before the program is
+/// executed. Enum declarations and the patterns of each match are gathered in a walk over the
+/// whole program, so a match may refer to an enum declared after it
+#[derive(Default)]
+pub struct ExhaustivenessChecker {
+    /// Maps each enum's name to its variants and the number of values each variant carries
+    enums: HashMap<String, Vec<(String, usize)>>,
+    /// The location and the patterns of the arms of every match expression in the program
+    matches: Vec<(SourceLocation, Vec<Pattern>)>,
+}
+
+impl ExhaustivenessChecker {
+    pub fn check(&mut self, stmts: &[Stmt]) -> Result<(), LangError> {
+        self.check_stmts(stmts)?;
+        // Like type errors, the errors of every match are reported together
+        let errors: Vec<String> = self
+            .matches
+            .iter()
+            .filter_map(|(location, patterns)| {
+                self.check_match(patterns)
+                    .err()
+                    .map(|reason| format!("{}: {}", location, reason))
+            })
+            .collect();
+        if errors.is_empty() {
+            return Ok(());
+        }
+        Err(LangErrorType::new_type_error(errors.join("\n")))
+    }
+
+    /// Checks the arms of a match, giving the reason it's rejected for
+    fn check_match(&self, patterns: &[Pattern]) -> Result<(), String> {
+        for pattern in patterns.iter() {
+            self.check_pattern(pattern)?;
+        }
+        if patterns.iter().any(Pattern::is_irrefutable) {
+            return Ok(());
+        }
+        let mut enum_names: Vec<&String> = Vec::new();
+        for pattern in patterns.iter() {
+            if let Pattern::EnumVariant(variant_pattern) = pattern {
+                if !enum_names.contains(&&variant_pattern.enum_name) {
+                    enum_names.push(&variant_pattern.enum_name);
+                }
+            }
+        }
+        match enum_names.as_slice() {
+            [] => self.check_literal_match(patterns),
+            [enum_name] => self.check_enum_match(enum_name, patterns),
+            _ => Err(format!(
+                "match arms mix variants of the enums {}",
+                enum_names
+                    .iter()
+                    .map(|name| format!("'{}'", name))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// A match over enum variants must have an arm taking every value of each variant
+    fn check_enum_match(&self, enum_name: &str, patterns: &[Pattern]) -> Result<(), String> {
+        let missing: Vec<String> = self.enums[enum_name]
+            .iter()
+            .filter(|(variant, _)| {
+                !patterns.iter().any(|pattern| match pattern {
+                    Pattern::EnumVariant(variant_pattern) => {
+                        variant_pattern.variant == *variant
+                            && variant_pattern.fields.iter().all(Pattern::is_irrefutable)
+                    }
+                    _ => false,
+                })
+            })
+            .map(|(variant, _)| format!("{}::{}", enum_name, variant))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(format!(
+            "non-exhaustive match on enum '{}', missing variant(s): {}",
+            enum_name,
+            missing.join(", ")
+        ))
+    }
+
+    /// Literals only cover every value of a type for booleans, anything else needs a `_` arm
+    fn check_literal_match(&self, patterns: &[Pattern]) -> Result<(), String> {
+        let covers = |boolean: bool| {
+            patterns.iter().any(|pattern| match pattern {
+                Pattern::Literal(literal) => literal.value == Value::Boolean(boolean),
+                _ => false,
+            })
+        };
+        if covers(true) && covers(false) {
+            return Ok(());
+        }
+        Err("non-exhaustive match, add a '_' arm to match the remaining values".to_string())
+    }
+
+    /// Checks that the variants named by `pattern` exist and are given the right number of
+    /// patterns for their values
+    fn check_pattern(&self, pattern: &Pattern) -> Result<(), String> {
+        if let Pattern::EnumVariant(variant_pattern) = pattern {
+            let variants = match self.enums.get(&variant_pattern.enum_name) {
+                Some(variants) => variants,
+                None => {
+                    return Err(format!(
+                        "match pattern refers to the undeclared enum '{}'",
+                        variant_pattern.enum_name
+                    ))
+                }
+            };
+            let field_count = match variants
+                .iter()
+                .find(|(variant, _)| *variant == variant_pattern.variant)
+            {
+                Some((_, field_count)) => *field_count,
+                None => {
+                    return Err(format!(
+                        "enum '{}' has no variant '{}'",
+                        variant_pattern.enum_name, variant_pattern.variant
+                    ))
+                }
+            };
+            if field_count != variant_pattern.fields.len() {
+                return Err(format!(
+                    "the variant {}::{} holds {} value(s) but its pattern has {}",
+                    variant_pattern.enum_name,
+                    variant_pattern.variant,
+                    field_count,
+                    variant_pattern.fields.len()
+                ));
+            }
+            for field in variant_pattern.fields.iter() {
+                self.check_pattern(field)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_stmts(&mut self, stmts: &[Stmt]) -> Result<(), LangError> {
+        for stmt in stmts {
+            self.visit_stmt_mut(stmt)?;
+        }
+        Ok(())
+    }
+}
+
+impl VisitorMut<()> for ExhaustivenessChecker {
+    fn visit_expr_mut(&mut self, expr: &Expr) -> Result<(), LangError> {
+        visit_expr_mut(self, expr)
+    }
+    fn visit_stmt_mut(&mut self, stmt: &Stmt) -> Result<(), LangError> {
+        visit_stmt_mut(self, stmt)
+    }
+
+    fn visit_assign(&mut self, assign: &AssignExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&assign.expr)
+    }
+    fn visit_binary(&mut self, binary: &BinaryExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&binary.left)?;
+        self.visit_expr_mut(&binary.right)
+    }
+    fn visit_call(&mut self, call: &CallExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&call.callee)?;
+        for arg in call.arguments.iter() {
+            self.visit_expr_mut(arg)?;
+        }
+        Ok(())
+    }
+    fn visit_get(&mut self, get: &GetExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&get.object)
+    }
+    fn visit_enum_path(&mut self, _: &EnumPathExpr) -> Result<(), LangError> {
+        Ok(())
+    }
+    fn visit_match(&mut self, match_expr: &MatchExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&match_expr.scrutinee)?;
+        for arm in match_expr.arms.iter() {
+            self.visit_stmt_mut(&arm.body)?;
+        }
+        self.matches.push((
+            match_expr.location,
+            match_expr
+                .arms
+                .iter()
+                .map(|arm| arm.pattern.clone())
+                .collect(),
+        ));
+        Ok(())
+    }
+    fn visit_grouping(&mut self, grouping: &GroupingExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&grouping.expression)
+    }
+    fn visit_literal(&mut self, _: &LiteralExpr) -> Result<(), LangError> {
+        Ok(())
+    }
+    fn visit_logical(&mut self, logical: &LogicalExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&logical.left)?;
+        self.visit_expr_mut(&logical.right)
+    }
+    fn visit_set(&mut self, set: &SetExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&set.object)?;
+        self.visit_expr_mut(&set.value)
+    }
+    fn visit_unary(&mut self, unary: &UnaryExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&unary.right)
+    }
+    fn visit_array(&mut self, array: &ArrayExpr) -> Result<(), LangError> {
+        for element in array.elements.iter() {
+            self.visit_expr_mut(element)?;
+        }
+        Ok(())
+    }
+    fn visit_struct_literal(
+        &mut self,
+        struct_literal: &StructLiteralExpr,
+    ) -> Result<(), LangError> {
+        for (_, value) in struct_literal.fields.iter() {
+            self.visit_expr_mut(value)?;
+        }
+        Ok(())
+    }
+    fn visit_try(&mut self, try_expr: &TryExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&try_expr.expression)
+    }
+    fn visit_cast(&mut self, cast: &CastExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&cast.expression)
+    }
+    fn visit_block_expr(&mut self, block: &BlockExpr) -> Result<(), LangError> {
+        self.check_stmts(&block.statements)?;
+        if let Some(ref value) = block.value {
+            self.visit_expr_mut(value)?;
+        }
+        Ok(())
+    }
+    fn visit_loop(&mut self, loop_expr: &LoopExpr) -> Result<(), LangError> {
+        self.check_stmts(&loop_expr.body)
+    }
+    fn visit_range(&mut self, range: &RangeExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&range.start)?;
+        self.visit_expr_mut(&range.end)
+    }
+    fn visit_format_string(&mut self, format_string: &FormatStringExpr) -> Result<(), LangError> {
+        for piece in &format_string.pieces {
+            if let FormatPiece::Argument(argument) = piece {
+                self.visit_expr_mut(&argument.value)?;
+            }
+        }
+        Ok(())
+    }
+    fn visit_if_expr(&mut self, if_expr: &IfExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&if_expr.condition)?;
+        self.visit_block_expr(&if_expr.then_branch)?;
+        if let Some(ref else_branch) = if_expr.else_branch {
+            self.visit_expr_mut(else_branch)?;
+        }
+        Ok(())
+    }
+    fn visit_map(&mut self, map: &MapExpr) -> Result<(), LangError> {
+        for (key, value) in map.entries.iter() {
+            self.visit_expr_mut(key)?;
+            self.visit_expr_mut(value)?;
+        }
+        Ok(())
+    }
+    fn visit_tuple(&mut self, tuple: &TupleExpr) -> Result<(), LangError> {
+        for element in tuple.elements.iter() {
+            self.visit_expr_mut(element)?;
+        }
+        Ok(())
+    }
+    fn visit_index(&mut self, index: &IndexExpr) -> Result<(), LangError> {
+        self.visit_expr_mut(&index.index)
+    }
+    fn visit_set_array_element(
+        &mut self,
+        set_array_element: &SetArrayElementExpr,
+    ) -> Result<(), LangError> {
+        self.visit_expr_mut(&set_array_element.index)?;
+        self.visit_expr_mut(&set_array_element.value)
+    }
+    fn visit_variable(&mut self, _: &VariableExpr) -> Result<(), LangError> {
+        Ok(())
+    }
+    fn visit_self_ident(&mut self, _: &SelfIdentExpr) -> Result<(), LangError> {
+        Ok(())
+    }
+
+    // stmt
+    fn visit_break(&mut self, break_stmt: &BreakStmt) -> Result<(), LangError> {
+        if let Some(ref value) = break_stmt.value {
+            self.visit_expr_mut(value)?;
+        }
+        Ok(())
+    }
+    fn visit_continue(&mut self, _: &ContinueStmt) -> Result<(), LangError> {
+        Ok(())
+    }
+    fn visit_assert(&mut self, assert_stmt: &AssertStmt) -> Result<(), LangError> {
+        self.visit_expr_mut(&assert_stmt.condition)
+    }
+    fn visit_enum(&mut self, enum_stmt: &EnumStmt) -> Result<(), LangError> {
+        let variants = enum_stmt
+            .item_list
+            .iter()
+            .map(|item| (item.identifier.clone(), item.fields.len()))
+            .collect();
+        self.enums.insert(enum_stmt.name.clone(), variants);
+        Ok(())
+    }
+    fn visit_impl(&mut self, impl_stmt: &ImplStmt) -> Result<(), LangError> {
+        self.check_stmts(&impl_stmt.fn_declarations)
+    }
+    fn visit_impl_trait(&mut self, impl_trait: &ImplTraitStmt) -> Result<(), LangError> {
+        self.check_stmts(&impl_trait.fn_declarations)
+    }
+    fn visit_block(&mut self, block: &BlockStmt) -> Result<(), LangError> {
+        self.check_stmts(&block.statements)
+    }
+    fn visit_struct(&mut self, struct_stmt: &StructStmt) -> Result<(), LangError> {
+        for default in struct_stmt
+            .fields
+            .iter()
+            .filter_map(|field| field.default.as_ref())
+        {
+            self.visit_expr_mut(default)?;
+        }
+        Ok(())
+    }
+    fn visit_expression(&mut self, expr: &ExpressionStmt) -> Result<(), LangError> {
+        self.visit_expr_mut(&expr.expression)
+    }
+    fn visit_trait(&mut self, _: &TraitStmt) -> Result<(), LangError> {
+        Ok(())
+    }
+    fn visit_trait_function(&mut self, _: &TraitFunctionStmt) -> Result<(), LangError> {
+        Ok(())
+    }
+    fn visit_function(&mut self, function_stmt: &FunctionStmt) -> Result<(), LangError> {
+        self.check_stmts(&function_stmt.body)
+    }
+    fn visit_if(&mut self, if_stmt: &IfStmt) -> Result<(), LangError> {
+        self.visit_expr_mut(&if_stmt.condition)?;
+        self.visit_stmt_mut(&if_stmt.then_branch)?;
+        if let Some(ref else_branch) = if_stmt.else_branch {
+            self.visit_stmt_mut(else_branch)?;
+        }
+        Ok(())
+    }
+    fn visit_print(&mut self, print_stmt: &PrintStmt) -> Result<(), LangError> {
+        self.visit_expr_mut(&print_stmt.expression)
+    }
+    fn visit_return(&mut self, return_stmt: &ReturnStmt) -> Result<(), LangError> {
+        self.visit_expr_mut(&return_stmt.value)
+    }
+    fn visit_var(&mut self, var_stmt: &VarStmt) -> Result<(), LangError> {
+        if let Some(ref initializer) = var_stmt.initializer {
+            self.visit_expr_mut(initializer)?;
+        }
+        Ok(())
+    }
+    fn visit_destructure(&mut self, destructure_stmt: &DestructureStmt) -> Result<(), LangError> {
+        self.visit_expr_mut(&destructure_stmt.initializer)
+    }
+    fn visit_while(&mut self, while_stmt: &WhileStmt) -> Result<(), LangError> {
+        self.visit_expr_mut(&while_stmt.condition)?;
+        self.visit_stmt_mut(&while_stmt.body)?;
+        if let Some(ref increment) = while_stmt.increment {
+            self.visit_expr_mut(increment)?;
+        }
+        Ok(())
+    }
+    fn visit_for_in(&mut self, for_in: &ForInStmt) -> Result<(), LangError> {
+        self.visit_expr_mut(&for_in.iterable)?;
+        self.check_stmts(&for_in.body)
+    }
+    fn visit_import(&mut self, _: &ImportStmt) -> Result<(), LangError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,414 @@
+use crate::error::LangError;
+use crate::token::NumericType;
+use std::fmt::{self, Display};
+
+/// Where a formatted value goes within its width when it's shorter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+/// How a value is written out by an argument of a format string
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FormatKind {
+    /// `{}`, the way `print` writes values, or through their `Display` implementation
+    Display,
+    /// `{:?}`, strings and chars are quoted and structs show their fields
+    Debug,
+    /// `{:x}`
+    LowerHex,
+    /// `{:X}`
+    UpperHex,
+    /// `{:o}`
+    Octal,
+    /// `{:b}`
+    Binary,
+}
+
+/// The part of a format string argument after its `:`, e.g. the `>8.2` of `{x:>8.2}`. It's
+/// written `[[fill]align][+][#][0][width][.precision][type]` as in Rust
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FormatSpec {
+    pub fill: char,
+    pub alignment: Option<Alignment>,
+    /// Whether positive numbers are written with a `+`
+    pub sign: bool,
+    /// Whether hex, octal and binary numbers are prefixed with `0x`, `0o` or `0b`
+    pub alternate: bool,
+    /// Whether numbers are padded with zeros after their sign and prefix
+    pub zero_pad: bool,
+    pub width: Option<usize>,
+    /// The digits after the point of a float, or the number of characters of a string kept
+    pub precision: Option<usize>,
+    pub kind: FormatKind,
+}
+
+/// A value to format, as the backends hand it over
+pub enum FormatArg {
+    Integer(i128, NumericType),
+    /// A float along with the way it's written without a precision
+    Float(f64, String),
+    /// Any other value, already written the way the spec's kind asks for
+    Text(String),
+}
+
+/// What `{:?}` shows of a value of a backend. `V` are the values within it, and `I` tells
+/// struct instances apart
+pub enum DebugParts<V, I> {
+    /// A value with no values within it, already written out
+    Leaf(String),
+    Array(Vec<V>),
+    Tuple(Vec<V>),
+    Map(Vec<(V, V)>),
+    Variant {
+        enum_name: String,
+        variant: String,
+        fields: Vec<V>,
+    },
+    Instance {
+        name: String,
+        identity: I,
+        fields: Vec<(String, V)>,
+    },
+}
+
+/// A value `debug_string` can write out
+pub trait DebugValue: Sized {
+    type Identity: PartialEq;
+
+    fn debug_parts(&self) -> Result<DebugParts<Self, Self::Identity>, LangError>;
+}
+
+/// How `{:?}` writes `value`: strings and chars are quoted, floats keep their point and struct
+/// instances show their fields. An instance found within itself is written `Name { .. }`
+pub fn debug_string<V: DebugValue>(value: &V) -> Result<String, LangError> {
+    debug_string_within(value, &mut Vec::new())
+}
+
+/// `ancestors` identifies the instances the value is found in
+fn debug_string_within<V: DebugValue>(
+    value: &V,
+    ancestors: &mut Vec<V::Identity>,
+) -> Result<String, LangError> {
+    let debug_string = match value.debug_parts()? {
+        DebugParts::Leaf(text) => text,
+        DebugParts::Array(elements) => format!("[{}]", debug_list(&elements, ancestors)?),
+        DebugParts::Tuple(elements) => format!("({})", debug_list(&elements, ancestors)?),
+        DebugParts::Map(entries) if entries.is_empty() => "[:]".to_string(),
+        DebugParts::Map(entries) => {
+            let mut written = Vec::new();
+            for (key, value) in entries.iter() {
+                written.push(format!(
+                    "{}: {}",
+                    debug_string_within(key, ancestors)?,
+                    debug_string_within(value, ancestors)?
+                ));
+            }
+            format!("[{}]", written.join(", "))
+        }
+        DebugParts::Variant {
+            enum_name,
+            variant,
+            fields,
+        } if fields.is_empty() => format!("{}::{}", enum_name, variant),
+        DebugParts::Variant {
+            enum_name,
+            variant,
+            fields,
+        } => format!(
+            "{}::{}({})",
+            enum_name,
+            variant,
+            debug_list(&fields, ancestors)?
+        ),
+        DebugParts::Instance {
+            name,
+            identity,
+            fields,
+        } => {
+            if ancestors.contains(&identity) {
+                return Ok(format!("{} {{ .. }}", name));
+            }
+            ancestors.push(identity);
+            let mut written = Vec::new();
+            for (field, value) in fields.iter() {
+                written.push(format!(
+                    "{}: {}",
+                    field,
+                    debug_string_within(value, ancestors)?
+                ));
+            }
+            ancestors.pop();
+            if written.is_empty() {
+                name
+            } else {
+                format!("{} {{ {} }}", name, written.join(", "))
+            }
+        }
+    };
+    Ok(debug_string)
+}
+
+fn debug_list<V: DebugValue>(
+    values: &[V],
+    ancestors: &mut Vec<V::Identity>,
+) -> Result<String, LangError> {
+    let written = values
+        .iter()
+        .map(|value| debug_string_within(value, ancestors))
+        .collect::<Result<Vec<String>, LangError>>()?;
+    Ok(written.join(", "))
+}
+
+impl Default for FormatSpec {
+    fn default() -> FormatSpec {
+        FormatSpec {
+            fill: ' ',
+            alignment: None,
+            sign: false,
+            alternate: false,
+            zero_pad: false,
+            width: None,
+            precision: None,
+            kind: FormatKind::Display,
+        }
+    }
+}
+
+fn alignment(ch: char) -> Option<Alignment> {
+    match ch {
+        '<' => Some(Alignment::Left),
+        '^' => Some(Alignment::Center),
+        '>' => Some(Alignment::Right),
+        _ => None,
+    }
+}
+
+/// Takes the decimal number at the start of `spec`, if there's one
+fn count(spec: &str) -> (Option<usize>, &str) {
+    let end = spec
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(spec.len());
+    (spec[..end].parse().ok(), &spec[end..])
+}
+
+impl FormatSpec {
+    /// Parses the text after the `:` of an argument, or says why it isn't a valid spec
+    pub fn parse(text: &str) -> Result<FormatSpec, String> {
+        let mut spec = FormatSpec::default();
+        let mut chars = text.chars();
+        let mut rest = text;
+        match (chars.next(), chars.next().and_then(alignment)) {
+            (Some(fill), Some(align)) => {
+                spec.fill = fill;
+                spec.alignment = Some(align);
+                rest = &text[fill.len_utf8() + 1..];
+            }
+            (Some(ch), _) if alignment(ch).is_some() => {
+                spec.alignment = alignment(ch);
+                rest = &text[1..];
+            }
+            _ => {}
+        }
+        if let Some(after) = rest.strip_prefix('+') {
+            spec.sign = true;
+            rest = after;
+        }
+        if let Some(after) = rest.strip_prefix('#') {
+            spec.alternate = true;
+            rest = after;
+        }
+        if let Some(after) = rest.strip_prefix('0') {
+            spec.zero_pad = true;
+            rest = after;
+        }
+        let (width, after) = count(rest);
+        spec.width = width;
+        rest = after;
+        if let Some(after) = rest.strip_prefix('.') {
+            let (precision, after) = count(after);
+            if precision.is_none() {
+                return Err(format!("expected a precision after '.' in '{}'", text));
+            }
+            spec.precision = precision;
+            rest = after;
+        }
+        spec.kind = match rest {
+            "" => FormatKind::Display,
+            "?" => FormatKind::Debug,
+            "x" => FormatKind::LowerHex,
+            "X" => FormatKind::UpperHex,
+            "o" => FormatKind::Octal,
+            "b" => FormatKind::Binary,
+            _ => return Err(format!("unknown format spec '{}'", text)),
+        };
+        Ok(spec)
+    }
+
+    /// Whether the spec writes integers in another base, which other values can't be
+    pub fn needs_integer(&self) -> bool {
+        !matches!(self.kind, FormatKind::Display | FormatKind::Debug)
+    }
+
+    /// Writes `arg` out, or returns `None` when the spec needs an integer and `arg` isn't one
+    pub fn format(&self, arg: FormatArg) -> Option<String> {
+        let (text, numeric) = match arg {
+            FormatArg::Integer(integer, numeric_type) if self.needs_integer() => {
+                (self.radix_string(integer, numeric_type), true)
+            }
+            _ if self.needs_integer() => return None,
+            FormatArg::Integer(integer, _) => (integer.to_string(), true),
+            FormatArg::Float(float, text) => match self.precision {
+                Some(precision) => (format!("{:.*}", precision, float), true),
+                None => (text, true),
+            },
+            FormatArg::Text(text) => match self.precision {
+                Some(precision) if self.kind == FormatKind::Display => {
+                    (text.chars().take(precision).collect(), false)
+                }
+                _ => (text, false),
+            },
+        };
+        Some(self.pad(text, numeric))
+    }
+
+    /// `integer` in the spec's base. Negative integers are written as the two's complement of
+    /// their type, like Rust does
+    fn radix_string(&self, integer: i128, numeric_type: NumericType) -> String {
+        let bits = match numeric_type {
+            NumericType::I8 | NumericType::U8 => 8,
+            NumericType::I16 | NumericType::U16 => 16,
+            NumericType::I32 | NumericType::U32 | NumericType::F32 => 32,
+            _ => 64,
+        };
+        let unsigned = (integer as u128) & ((1u128 << bits) - 1);
+        let (digits, prefix) = match self.kind {
+            FormatKind::UpperHex => (format!("{:X}", unsigned), "0x"),
+            FormatKind::Octal => (format!("{:o}", unsigned), "0o"),
+            FormatKind::Binary => (format!("{:b}", unsigned), "0b"),
+            _ => (format!("{:x}", unsigned), "0x"),
+        };
+        if self.alternate {
+            format!("{}{}", prefix, digits)
+        } else {
+            digits
+        }
+    }
+
+    /// Pads `text` out to the spec's width. Numbers are aligned right unless the spec says
+    /// otherwise, and zero padding goes between their sign or prefix and their digits
+    fn pad(&self, text: String, numeric: bool) -> String {
+        let text = if numeric && self.sign && !text.starts_with('-') {
+            format!("+{}", text)
+        } else {
+            text
+        };
+        let length = text.chars().count();
+        let padding = match self.width {
+            Some(width) if width > length => width - length,
+            _ => return text,
+        };
+        if numeric && self.zero_pad {
+            let sign = text
+                .find(|c: char| c != '+' && c != '-')
+                .unwrap_or(text.len());
+            let prefix = if self.alternate && self.needs_integer() {
+                sign + 2
+            } else {
+                sign
+            };
+            return format!(
+                "{}{}{}",
+                &text[..prefix],
+                "0".repeat(padding),
+                &text[prefix..]
+            );
+        }
+        let default = if numeric {
+            Alignment::Right
+        } else {
+            Alignment::Left
+        };
+        let (before, after) = match self.alignment.unwrap_or(default) {
+            Alignment::Left => (0, padding),
+            Alignment::Center => (padding / 2, padding - padding / 2),
+            Alignment::Right => (padding, 0),
+        };
+        let fill = |count: usize| self.fill.to_string().repeat(count);
+        format!("{}{}{}", fill(before), text, fill(after))
+    }
+}
+
+impl Display for FormatKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let spec = match self {
+            FormatKind::Display => "",
+            FormatKind::Debug => "?",
+            FormatKind::LowerHex => "x",
+            FormatKind::UpperHex => "X",
+            FormatKind::Octal => "o",
+            FormatKind::Binary => "b",
+        };
+        write!(f, "{{:{}}}", spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::*;
+
+    fn format(spec: &str, arg: FormatArg) -> Option<String> {
+        FormatSpec::parse(spec).unwrap().format(arg)
+    }
+
+    #[test]
+    fn parses_specs() {
+        let spec = FormatSpec::parse("*^+#08.3x").unwrap();
+        assert_eq!(spec.fill, '*');
+        assert_eq!(spec.alignment, Some(Alignment::Center));
+        assert!(spec.sign && spec.alternate && spec.zero_pad);
+        assert_eq!(spec.width, Some(8));
+        assert_eq!(spec.precision, Some(3));
+        assert_eq!(spec.kind, FormatKind::LowerHex);
+        assert_eq!(FormatSpec::parse("").unwrap(), FormatSpec::default());
+        assert!(FormatSpec::parse("5.").is_err());
+        assert!(FormatSpec::parse("q").is_err());
+    }
+
+    #[test]
+    fn formats_numbers() {
+        let float = || FormatArg::Float(2.5, "2.5".to_string());
+        assert_eq!(format("", float()), Some("2.5".to_string()));
+        assert_eq!(format(".2", float()), Some("2.50".to_string()));
+        assert_eq!(format("8.2", float()), Some("    2.50".to_string()));
+        assert_eq!(format("+08.2", float()), Some("+0002.50".to_string()));
+        assert_eq!(
+            format("x", FormatArg::Integer(255, NumericType::I32)),
+            Some("ff".to_string())
+        );
+        assert_eq!(
+            format("#06X", FormatArg::Integer(255, NumericType::I32)),
+            Some("0x00FF".to_string())
+        );
+        assert_eq!(
+            format("b", FormatArg::Integer(-1, NumericType::I8)),
+            Some("11111111".to_string())
+        );
+        assert_eq!(format("x", FormatArg::Text("a".to_string())), None);
+    }
+
+    #[test]
+    fn aligns_text() {
+        let text = || FormatArg::Text("ab".to_string());
+        assert_eq!(format("5", text()), Some("ab   ".to_string()));
+        assert_eq!(format(">5", text()), Some("   ab".to_string()));
+        assert_eq!(format("-^6", text()), Some("--ab--".to_string()));
+        assert_eq!(format(".1", text()), Some("a".to_string()));
+        assert_eq!(
+            format("<3", FormatArg::Integer(7, NumericType::I32)),
+            Some("7  ".to_string())
+        );
+    }
+}
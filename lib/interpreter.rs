@@ -4,27 +4,81 @@ use crate::ast::expr::*;
 use crate::ast::stmt::*;
 use crate::env::*;
 use crate::error::*;
+use crate::format::*;
 use crate::mem::*;
 use crate::token::*;
 use crate::type_checker::*;
 use crate::value::*;
 use crate::value_traits::callable::*;
 use crate::visitor::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 
+/// What a loop does once an iteration of its body is over
+enum Iteration {
+    Continue,
+    /// Leave the loop, evaluating to the value given to the `break` if any
+    Break(Option<ArenaEntryIndex>),
+}
+
+/// The items a `for` loop has yet to go through
+enum Items {
+    /// The elements of an array or the characters of a string
+    Values(std::vec::IntoIter<TypedValue>),
+    /// The integers of a range from `next` up to, but not including, `end`
+    Range {
+        next: i128,
+        end: i128,
+        numeric_type: NumericType,
+    },
+    /// The items returned by the `next` method of the struct instance at the index, which
+    /// implements `Iterator`
+    Iterator(ArenaEntryIndex),
+}
+
 #[derive(Debug)]
-pub struct Interpreter {}
+pub struct Interpreter {
+    /// Scope distances computed by the resolver, names missing from it are looked up dynamically
+    locals: HashMap<NodeId, usize>,
+    /// Declarations of a variable already declared in their scope, each of which opens a scope
+    /// of its own for the rest of the enclosing one
+    shadowing: HashSet<NodeId>,
+}
 
 impl Default for Interpreter {
     fn default() -> Interpreter {
-        Interpreter {}
+        Interpreter::new()
     }
 }
 
 impl Interpreter {
     pub fn new() -> Interpreter {
-        Interpreter {}
+        Interpreter {
+            locals: HashMap::new(),
+            shadowing: HashSet::new(),
+        }
+    }
+
+    /// Records that the name used by the node `id` is defined `depth` scopes up from its use
+    pub fn resolve(&mut self, id: NodeId, depth: usize) {
+        self.locals.insert(id, depth);
+    }
+
+    /// Records that the declaration `id` shadows a variable declared in the same scope
+    pub fn shadow(&mut self, id: NodeId) {
+        self.shadowing.insert(id);
+    }
+
+    /// Opens the scope the declaration `var_stmt` makes its variable in, if it shadows another
+    fn declaration_scope(
+        &self,
+        var_stmt: &VarStmt,
+        env: &mut Environment,
+    ) -> Result<EnvironmentEntryIndex, LangError> {
+        if self.shadowing.contains(&var_stmt.id) {
+            env.current_index = env.entry_from(env.current_index)?;
+        }
+        Ok(env.current_index)
     }
 
     fn evaluate(
@@ -32,7 +86,7 @@ impl Interpreter {
         expr: &Expr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_expr(expr, arena, env)?)
     }
 
@@ -52,11 +106,17 @@ impl Interpreter {
         assign: &AssignExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         if let Some(arena_entry_index) = self.evaluate(&assign.expr, arena, env)? {
             let arena_entry = &arena[arena_entry_index];
             let value: &TypedValue = arena_entry.try_into()?;
-            env.assign(env.current_index, &assign.name, value.clone(), arena)?;
+            let value = value.clone();
+            match self.locals.get(&assign.id) {
+                Some(distance) => {
+                    env.assign_at(env.current_index, *distance, &assign.name, value, arena)?
+                }
+                None => env.assign(env.current_index, &assign.name, value, arena)?,
+            }
         }
         Ok(None)
     }
@@ -67,7 +127,7 @@ impl Interpreter {
         call: &CallExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         let mut args = Vec::new();
         for arg in &call.arguments {
             if let Some(arg_entry_index) = self.evaluate(&arg, arena, env)? {
@@ -80,16 +140,24 @@ impl Interpreter {
             match &callee.value {
                 Value::Callable(callable) => {
                     let mut actual_args = Vec::new();
-                    if let Some(first) = callable.get_params().first() {
-                        // TODO: This is a string comparison, fuckin' gross
-                        if let TypeAnnotation::User(_) = first.type_annotation {
-                            if let Expr::Get(get_expr) = &call.callee {
-                                if let Expr::Variable(var) = &get_expr.object {
-                                    if let Some(arena_index) =
-                                        env[env.current_index].values.get(&var.name)
-                                    {
-                                        actual_args.push(*arena_index);
-                                    }
+                    if let Expr::Get(get_expr) = &call.callee {
+                        if let Expr::Variable(var) = &get_expr.object {
+                            let receiver = self.look_up_variable(var.id, &var.name, env)?;
+                            // TODO: This is a string comparison, fuckin' gross
+                            let takes_self = callable
+                                .get_params()
+                                .first()
+                                .is_some_and(|first| first.type_annotation.is_user_type());
+                            if takes_self {
+                                actual_args.push(receiver);
+                            } else {
+                                // A method without a `self` parameter still refers to the
+                                // instance it's called on as `self`
+                                let receiver: TypedValue = (&arena[receiver]).try_into()?;
+                                if let Value::Struct(_) = receiver.value {
+                                    let instance: &dyn StructInstanceTrait =
+                                        (&receiver.value).try_into()?;
+                                    callable.bind(instance, env, arena)?;
                                 }
                             }
                         }
@@ -103,11 +171,12 @@ impl Interpreter {
                     return Ok(Some(arena.insert(value)));
                 }
                 _ => {
-                    return Err(LangErrorType::new_runtime_error(
-                        RuntimeErrorType::CallError {
+                    return Err(
+                        LangErrorType::new_runtime_error(RuntimeErrorType::CallError {
                             reason: "Can only call functions and structs".to_string(),
-                        },
-                    ))
+                        })
+                        .into(),
+                    )
                 }
             };
         }
@@ -119,12 +188,38 @@ impl Interpreter {
         get_expr: &GetExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         if let Some(arena_entry_index) = self.evaluate(&get_expr.object, arena, env)? {
             let arena_entry = &arena[arena_entry_index];
-            let value: TypedValue = arena_entry.try_into()?;
+            let value: &TypedValue = arena_entry.try_into()?;
             let mut index = None;
             match &value.value {
+                Value::Map(_) => {
+                    let method = MapMethod::from_name(&get_expr.name).ok_or_else(|| {
+                        LangErrorType::new_runtime_error(RuntimeErrorType::UndefinedVariable {
+                            reason: format!("map has no method '{}'", get_expr.name),
+                        })
+                    })?;
+                    let method = BoundMapMethod::new(arena_entry_index, method);
+                    index = Some(arena.insert(TypedValue::new(
+                        Value::Callable(Box::new(method)),
+                        TypeAnnotation::Fn,
+                    )));
+                }
+                Value::Tuple(elements) => {
+                    let element = get_expr
+                        .name
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|field| elements.get(field))
+                        .cloned()
+                        .ok_or_else(|| {
+                            LangErrorType::new_runtime_error(RuntimeErrorType::UndefinedVariable {
+                                reason: format!("tuple has no field '{}'", get_expr.name),
+                            })
+                        })?;
+                    index = Some(arena.insert(element));
+                }
                 Value::Struct(_) => {
                     let struct_value: &dyn StructInstanceTrait = (&value.value).try_into()?;
                     index = Some(struct_value.get_field(&get_expr.name)?);
@@ -148,23 +243,24 @@ impl Interpreter {
         left: &TypedValue,
         right: &TypedValue,
     ) -> Result<TypedValue, LangError> {
+        // Arithmetic evaluates to the type of the wider operand, or to a string for `+`
+        let arithmetic_result = |value: Value| {
+            let value_type = value
+                .numeric_type()
+                .map_or(TypeAnnotation::String, TypeAnnotation::from);
+            TypedValue::new(value, value_type)
+        };
         match op {
-            TokenType::Plus => Ok(TypedValue::new(
-                &left.value + &right.value,
-                left.value_type.clone(),
-            )),
-            TokenType::Minus => Ok(TypedValue::new(
-                &left.value - &right.value,
-                left.value_type.clone(),
-            )),
-            TokenType::Star => Ok(TypedValue::new(
-                &left.value * &right.value,
-                left.value_type.clone(),
-            )),
-            TokenType::Slash => Ok(TypedValue::new(
-                &left.value / &right.value,
-                left.value_type.clone(),
-            )),
+            TokenType::Plus => Ok(arithmetic_result((&left.value + &right.value)?)),
+            TokenType::Minus => Ok(arithmetic_result((&left.value - &right.value)?)),
+            TokenType::Star => Ok(arithmetic_result((&left.value * &right.value)?)),
+            TokenType::Slash => Ok(arithmetic_result((&left.value / &right.value)?)),
+            TokenType::Percent => Ok(arithmetic_result((&left.value % &right.value)?)),
+            TokenType::Ampersand => Ok(arithmetic_result((&left.value & &right.value)?)),
+            TokenType::Pipe => Ok(arithmetic_result((&left.value | &right.value)?)),
+            TokenType::Caret => Ok(arithmetic_result((&left.value ^ &right.value)?)),
+            TokenType::LessLess => Ok(arithmetic_result((&left.value << &right.value)?)),
+            TokenType::GreaterGreater => Ok(arithmetic_result((&left.value >> &right.value)?)),
             TokenType::Greater => Ok(TypedValue::new(
                 Value::Boolean(left.value > right.value),
                 TypeAnnotation::Bool,
@@ -195,12 +291,72 @@ impl Interpreter {
         }
     }
 
+    /// Evaluates the binary operation `op` on a struct instance that overloads it by implementing
+    /// the operator's built-in trait, such as `Add` for `+`. Returns `None` when the left operand
+    /// isn't such an instance
+    fn execute_overloaded_op(
+        &self,
+        op: &TokenType,
+        left: ArenaEntryIndex,
+        right: ArenaEntryIndex,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<TypedValue>, LangError> {
+        let method_name = match op.operator_trait() {
+            Some((_, method_name)) => method_name,
+            None => return Ok(None),
+        };
+        let value = match self.call_method(left, method_name, vec![left, right], arena, env)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        // `eq` answers `==` and `cmp` orders the operands by returning a negative number, zero or
+        // a positive number
+        let result = match (op, &value.value) {
+            (TokenType::BangEqual, Value::Boolean(equal)) => !equal,
+            (TokenType::Greater, Value::Int32(ordering)) => *ordering > 0,
+            (TokenType::GreaterEqual, Value::Int32(ordering)) => *ordering >= 0,
+            (TokenType::Less, Value::Int32(ordering)) => *ordering < 0,
+            (TokenType::LessEqual, Value::Int32(ordering)) => *ordering <= 0,
+            _ => return Ok(Some(value)),
+        };
+        Ok(Some(TypedValue::new(
+            Value::Boolean(result),
+            TypeAnnotation::Bool,
+        )))
+    }
+
+    /// Calls the method `name` of the struct instance at `receiver` with `args`. Returns `None`
+    /// when the value there isn't an instance with such a method
+    fn call_method(
+        &self,
+        receiver: ArenaEntryIndex,
+        name: &str,
+        args: Vec<ArenaEntryIndex>,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<TypedValue>, LangError> {
+        let receiver: &TypedValue = (&arena[receiver]).try_into()?;
+        let method_index = match receiver.value {
+            Value::Struct(ref struct_value) => match struct_value.get_method(name) {
+                Ok(method_index) => method_index,
+                Err(_) => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        let method: TypedValue = (&arena[method_index]).try_into()?;
+        let callable: &dyn CallableTrait = (&method.value).try_into()?;
+        Ok(Some(callable.call(arena, env, self, args)?))
+    }
+
     fn visit_impl_trait_stmt(
         &self,
         impl_trait_stmt: &ImplTraitStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        // The methods can be called after the scope the impl is in ends
+        env.capture(env.current_index)?;
         for fn_impl in impl_trait_stmt.fn_declarations.iter() {
             if let Stmt::Function(function_statement) = fn_impl {
                 let function = Value::Callable(Box::new(Callable::new(
@@ -212,7 +368,7 @@ impl Interpreter {
                     &function,
                     env,
                     &arena,
-                    &impl_trait_stmt.trait_name,
+                    impl_trait_stmt,
                 )?;
                 let fn_index = arena.insert(TypedValue::new(function.clone(), TypeAnnotation::Fn));
                 let update_struct_decl_closure =
@@ -238,13 +394,13 @@ impl Interpreter {
         trait_stmt: &TraitStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         let trait_value_index = env.define_and_insert(
             env.current_index,
             arena,
             &trait_stmt.name,
             TypedValue::new(Value::Unit, TypeAnnotation::Unit),
-        );
+        )?;
         let mut trait_value = TraitValue {
             trait_stmt: trait_stmt.clone(),
             fn_declarations: HashMap::new(),
@@ -274,7 +430,9 @@ impl Interpreter {
         impl_stmt: &ImplStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        // The methods can be called after the scope the impl is in ends
+        env.capture(env.current_index)?;
         for fn_decl in &impl_stmt.fn_declarations {
             if let Stmt::Function(function_statement) = fn_decl {
                 let function = Value::Callable(Box::new(Callable::new(
@@ -294,25 +452,450 @@ impl Interpreter {
         Ok(None)
     }
 
+    fn visit_enum_stmt(
+        &self,
+        enum_stmt: &EnumStmt,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let mut variants = HashMap::new();
+        // Variants without an initializer take the previous discriminant plus one
+        let mut discriminant: i64 = 0;
+        for item in enum_stmt.item_list.iter() {
+            if let Some(ref initializer) = item.initializer {
+                if let Some(initializer_index) = self.evaluate(initializer, arena, env)? {
+                    let initializer_entry = &arena[initializer_index];
+                    let initializer_value: &TypedValue = initializer_entry.try_into()?;
+                    discriminant = match initializer_value.value {
+                        Value::Int32(i) => i64::from(i),
+                        Value::Int64(i) => i,
+                        _ => {
+                            return Err(LangErrorType::new_runtime_error(
+                                RuntimeErrorType::InvalidTypeAssignmentError {
+                                    reason: format!(
+                                    "enum discriminant for '{}::{}' must be an integer, found {}",
+                                    enum_stmt.name, item.identifier, initializer_value.value_type
+                                ),
+                                },
+                            )
+                            .into())
+                        }
+                    };
+                }
+            }
+            variants.insert(
+                item.identifier.clone(),
+                EnumVariantDecl {
+                    discriminant: Value::Int64(discriminant),
+                    fields: item.fields.clone(),
+                },
+            );
+            discriminant += 1;
+        }
+        let enum_value = Value::Enum(Box::new(Enum::new(enum_stmt.name.clone(), variants)));
+        let enum_value_index = env.define_and_insert(
+            env.current_index,
+            arena,
+            &enum_stmt.name,
+            TypedValue::new(enum_value, TypeAnnotation::User(enum_stmt.name.clone())),
+        )?;
+        Ok(Some(enum_value_index))
+    }
+
+    fn visit_enum_path_expr(
+        &self,
+        enum_path: &EnumPathExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let variant = match enum_path.path_items.as_slice() {
+            [variant] => variant,
+            _ => {
+                return Err(
+                    LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                        reason: format!(
+                            "expected a path of the form '{}::Variant'",
+                            enum_path.name
+                        ),
+                    })
+                    .into(),
+                )
+            }
+        };
+        let enum_index = env.get(env.current_index, &enum_path.name)?;
+        let enum_entry = &arena[enum_index];
+        let enum_typed_value: &TypedValue = enum_entry.try_into()?;
+        let enum_value: &Enum = (&enum_typed_value.value).try_into()?;
+        let value = enum_value.get_variant(variant)?;
+        Ok(Some(arena.insert(value)))
+    }
+
+    fn visit_match_expr(
+        &self,
+        match_expr: &MatchExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let scrutinee = match self.evaluate(&match_expr.scrutinee, arena, env)? {
+            Some(index) => {
+                let entry = &arena[index];
+                let value: TypedValue = entry.try_into()?;
+                value
+            }
+            None => TypedValue::new(Value::Unit, TypeAnnotation::Unit),
+        };
+        for arm in match_expr.arms.iter() {
+            let mut bindings = Vec::new();
+            if Interpreter::match_pattern(&arm.pattern, &scrutinee, &mut bindings) {
+                return self.execute_match_arm(arm, bindings, arena, env);
+            }
+        }
+        Err(
+            LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                reason: format!("no match arm matched the value '{}'", scrutinee.value),
+            })
+            .into(),
+        )
+    }
+
+    /// Checks `value` against `pattern`, collecting the values bound by the pattern
+    fn match_pattern(
+        pattern: &Pattern,
+        value: &TypedValue,
+        bindings: &mut Vec<(String, TypedValue)>,
+    ) -> bool {
+        match pattern {
+            Pattern::Wildcard => true,
+            Pattern::Binding(name) => {
+                bindings.push((name.clone(), value.clone()));
+                true
+            }
+            Pattern::Literal(literal) => value.value == literal.value,
+            Pattern::EnumVariant(variant_pattern) => match value.value {
+                Value::EnumVariant(ref variant)
+                    if variant.enum_name == variant_pattern.enum_name
+                        && variant.variant == variant_pattern.variant
+                        && variant.fields.len() == variant_pattern.fields.len() =>
+                {
+                    variant_pattern
+                        .fields
+                        .iter()
+                        .zip(variant.fields.iter())
+                        .all(|(field_pattern, field)| {
+                            Interpreter::match_pattern(field_pattern, field, bindings)
+                        })
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Executes the body of `arm` in a new scope holding `bindings`. The value of an expression
+    /// arm is the value of the match, block arms evaluate to unit
+    fn execute_match_arm(
+        &self,
+        arm: &MatchArm,
+        bindings: Vec<(String, TypedValue)>,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let previous = env.current_index;
+        let env_id = env.entry_from(previous)?;
+        for (name, value) in bindings {
+            env.define_and_insert(env_id, arena, &name, value)?;
+        }
+        env.current_index = env_id;
+        let result = match arm.body {
+            Stmt::Expression(ref expression_stmt) => {
+                self.evaluate(&expression_stmt.expression, arena, env)
+            }
+            ref body => self
+                .execute(body, arena, env)
+                .map(|_| Some(arena.insert(TypedValue::new(Value::Unit, TypeAnnotation::Unit)))),
+        };
+        env.remove_scope(env_id)?;
+        env.current_index = previous;
+        result
+    }
+
+    /// Executes the statements of `block` in a new scope, evaluating to the value it ends with or
+    /// unit
+    fn visit_block_expression(
+        &self,
+        block: &BlockExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let previous = env.current_index;
+        let env_id = env.entry_from(previous)?;
+        env.current_index = env_id;
+        let result = self
+            .execute_block_statements(&block.statements, arena, env)
+            .and_then(|_| match block.value {
+                Some(ref value) => self.evaluate(value, arena, env),
+                None => Ok(Some(
+                    arena.insert(TypedValue::new(Value::Unit, TypeAnnotation::Unit)),
+                )),
+            });
+        env.remove_scope(env_id)?;
+        env.current_index = previous;
+        result
+    }
+
+    fn visit_if_expression(
+        &self,
+        if_expr: &IfExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let condition = match self.evaluate(&if_expr.condition, arena, env)? {
+            Some(index) => {
+                let value: &TypedValue = (&arena[index]).try_into()?;
+                self.is_truthy(&value.value)
+            }
+            None => false,
+        };
+        if condition {
+            return self.visit_block_expression(&if_expr.then_branch, arena, env);
+        }
+        match if_expr.else_branch {
+            Some(ref else_branch) => self.evaluate(else_branch, arena, env),
+            None => Ok(Some(
+                arena.insert(TypedValue::new(Value::Unit, TypeAnnotation::Unit)),
+            )),
+        }
+    }
+
+    fn visit_loop_expr(
+        &self,
+        loop_expr: &LoopExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        loop {
+            let mut env_id = env.entry_from(env.current_index)?;
+            let result = self.execute_block(&loop_expr.body, &mut env_id, arena, env);
+            if let Iteration::Break(value) = Interpreter::end_iteration(&loop_expr.label, result)? {
+                let unit = TypedValue::new(Value::Unit, TypeAnnotation::Unit);
+                return Ok(Some(value.unwrap_or_else(|| arena.insert(unit))));
+            }
+        }
+    }
+
+    fn visit_range_expr(
+        &self,
+        range: &RangeExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let start: TypedValue = match self.evaluate(&range.start, arena, env)? {
+            Some(index) => (&arena[index]).try_into()?,
+            None => return Ok(None),
+        };
+        let end: TypedValue = match self.evaluate(&range.end, arena, env)? {
+            Some(index) => (&arena[index]).try_into()?,
+            None => return Ok(None),
+        };
+        let bound = |value: &Value| value.integer().zip(value.numeric_type());
+        let range_value = match (bound(&start.value), bound(&end.value)) {
+            (Some(start), Some(end)) => RangeValue::new(start, end, range.inclusive),
+            _ => None,
+        };
+        let range_value = range_value.ok_or_else(|| {
+            LangErrorType::new_runtime_error(RuntimeErrorType::InvalidTypeAssignmentError {
+                reason: format!(
+                    "the bounds of a range must be integers of compatible types, found {} and {}",
+                    start.value_type, end.value_type
+                ),
+            })
+        })?;
+        let range_type = TypeAnnotation::Range(Box::new(range_value.numeric_type.into()));
+        Ok(Some(arena.insert(TypedValue::new(
+            Value::Range(Box::new(range_value)),
+            range_type,
+        ))))
+    }
+
+    fn visit_format_string_expr(
+        &self,
+        format_string: &FormatStringExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let mut text = String::new();
+        for piece in &format_string.pieces {
+            let argument = match piece {
+                FormatPiece::Text(literal) => {
+                    text.push_str(literal);
+                    continue;
+                }
+                FormatPiece::Argument(argument) => argument,
+            };
+            let index = match self.evaluate(&argument.value, arena, env)? {
+                Some(index) => index,
+                None => return Ok(None),
+            };
+            let format_arg = self.format_arg(index, &argument.spec, arena, env)?;
+            let formatted = argument.spec.format(format_arg).ok_or_else(|| {
+                LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                    reason: format!("the {} format needs an integer", argument.spec.kind),
+                })
+            })?;
+            text.push_str(&formatted);
+        }
+        Ok(Some(arena.insert(TypedValue::new(
+            Value::String(text),
+            TypeAnnotation::String,
+        ))))
+    }
+
+    /// The value at `index` as `spec` formats it. Struct instances implementing `Display` are
+    /// written by their `fmt` method, other instances by the name of their struct
+    fn format_arg(
+        &self,
+        index: ArenaEntryIndex,
+        spec: &FormatSpec,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<FormatArg, LangError> {
+        let value: TypedValue = (&arena[index]).try_into()?;
+        if let Some(number) = value.value.number() {
+            return Ok(number.format_arg(spec.kind));
+        }
+        let format_arg = match value.value {
+            ref other if spec.kind == FormatKind::Debug => {
+                FormatArg::Text(other.debug_string(arena)?)
+            }
+            Value::Struct(ref struct_value) => {
+                match self.call_method(index, "fmt", vec![index], arena, env)? {
+                    Some(text) => FormatArg::Text(text.value.to_string()),
+                    None => FormatArg::Text(struct_value.callable_trait().get_name()),
+                }
+            }
+            ref other => FormatArg::Text(other.to_string()),
+        };
+        Ok(format_arg)
+    }
+
+    /// The items of the value at `iterable` for a `for` loop to go through, which are the keys of
+    /// a map. Arrays and maps are iterated over as they were when the loop started
+    fn items(
+        &self,
+        iterable: ArenaEntryIndex,
+        arena: &Arena<TypedValue>,
+    ) -> Result<Items, LangError> {
+        let value: &TypedValue = (&arena[iterable]).try_into()?;
+        match value.value {
+            Value::Array(ref elements) => Ok(Items::Values(elements.clone().into_iter())),
+            Value::Map(ref map) => Ok(Items::Values(
+                map.iter()
+                    .map(|(key, _)| key.clone())
+                    .collect::<Vec<TypedValue>>()
+                    .into_iter(),
+            )),
+            Value::String(ref string) => Ok(Items::Values(
+                string
+                    .chars()
+                    .map(|ch| TypedValue::new(Value::Char(ch), TypeAnnotation::Char))
+                    .collect::<Vec<TypedValue>>()
+                    .into_iter(),
+            )),
+            Value::Range(ref range) => Ok(Items::Range {
+                next: range.start,
+                end: range.end_exclusive(),
+                numeric_type: range.numeric_type,
+            }),
+            Value::Struct(_) => Ok(Items::Iterator(iterable)),
+            _ => Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::GenericError {
+                    reason: format!("values of type {} can't be iterated over", value.value_type),
+                },
+            )),
+        }
+    }
+
+    /// Inserts the next of `items` into the arena, returning `None` once they've run out
+    fn next_item(
+        &self,
+        items: &mut Items,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+        let item = match items {
+            Items::Values(values) => values.next(),
+            Items::Range {
+                next,
+                end,
+                numeric_type,
+            } => {
+                if *next >= *end {
+                    return Ok(None);
+                }
+                let value = Value::convert_integer(*next, *numeric_type);
+                *next += 1;
+                Some(TypedValue::new(value, TypeAnnotation::from(*numeric_type)))
+            }
+            Items::Iterator(iterator) => {
+                let iterator = *iterator;
+                let option = self
+                    .call_method(iterator, "next", vec![iterator], arena, env)?
+                    .ok_or_else(|| {
+                        LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                            reason: "a struct iterated over must implement Iterator".to_string(),
+                        })
+                    })?;
+                match option.value {
+                    Value::EnumVariant(ref variant) if variant.enum_name == "Option" => {
+                        variant.fields.first().cloned()
+                    }
+                    _ => {
+                        return Err(LangErrorType::new_runtime_error(
+                            RuntimeErrorType::InvalidFunctionReturnType {
+                                reason: format!(
+                                    "'next' must return an Option, found {}",
+                                    option.value_type
+                                ),
+                            },
+                        ))
+                    }
+                }
+            }
+        };
+        Ok(item.map(|item| arena.insert(item)))
+    }
+
+    /// Decides whether the loop labelled `label` goes on after an iteration of its body finished
+    /// with `result`. A `break` or `continue` aimed at another loop, or a `return`, leaves it
+    fn end_iteration(
+        label: &Option<String>,
+        result: Result<Option<ArenaEntryIndex>, Interrupt>,
+    ) -> Result<Iteration, Interrupt> {
+        match result {
+            Ok(_) => Ok(Iteration::Continue),
+            Err(Interrupt::ControlFlow(control_flow)) if control_flow.targets(label) => {
+                match control_flow {
+                    ControlFlow::Break { value, .. } => Ok(Iteration::Break(value)),
+                    _ => Ok(Iteration::Continue),
+                }
+            }
+            Err(interrupt) => Err(interrupt),
+        }
+    }
+
     fn visit_struct_stmt(
         &self,
         struct_stmt: &StructStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         let struct_value_index = env.define_and_insert(
             env.current_index,
             arena,
             &struct_stmt.name,
             TypedValue::new(Value::Unit, TypeAnnotation::Unit),
-        );
-        let mut fields = HashMap::new();
-        for field in struct_stmt.fields.iter() {
-            fields.insert(field.identifier.clone(), 0);
-        }
+        )?;
         let struct_value = Value::Struct(Box::new(StructValue::new(
             struct_stmt,
-            fields,
             struct_stmt.name.clone(),
         )));
         env.assign(
@@ -331,14 +914,16 @@ impl Interpreter {
         expr: &BinaryExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         if let Some(left_arena_entry_index) = self.evaluate(&expr.left, arena, env)? {
             if let Some(right_arena_entry_index) = self.evaluate(&expr.right, arena, env)? {
-                let left_arena_entry = &arena[left_arena_entry_index];
-                let left: &TypedValue = left_arena_entry.try_into()?;
-                let right_arena_entry = &arena[right_arena_entry_index];
-                let right: &TypedValue = right_arena_entry.try_into()?;
-                let value = self.execute_binary_op(&expr.operator, left, right)?;
+                let value = self.binary_op(
+                    &expr.operator,
+                    left_arena_entry_index,
+                    right_arena_entry_index,
+                    arena,
+                    env,
+                )?;
                 let index = arena.insert(value);
                 return Ok(Some(index));
             }
@@ -346,38 +931,46 @@ impl Interpreter {
         Ok(None)
     }
 
+    /// Applies the binary `operator` to the values at `left` and `right`, through the operator's
+    /// trait when `left` is a struct instance implementing it
+    fn binary_op(
+        &self,
+        operator: &TokenType,
+        left: ArenaEntryIndex,
+        right: ArenaEntryIndex,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<TypedValue, Interrupt> {
+        if let Some(value) = self.execute_overloaded_op(operator, left, right, arena, env)? {
+            return Ok(value);
+        }
+        let left_arena_entry = &arena[left];
+        let left: &TypedValue = left_arena_entry.try_into()?;
+        let right_arena_entry = &arena[right];
+        let right: &TypedValue = right_arena_entry.try_into()?;
+        Ok(self.execute_binary_op(operator, left, right)?)
+    }
+
     fn visit_unary_expr(
         &self,
         unary_expr: &UnaryExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         if let Some(right_arena_index) = self.evaluate(&unary_expr.right, arena, env)? {
             let right_arena_entry = &arena[right_arena_index];
             let right: &TypedValue = right_arena_entry.try_into()?;
             match unary_expr.operator {
-                TokenType::Minus => match right.value {
-                    Value::Int32(i) => {
-                        return Ok(Some(
-                            arena.insert(TypedValue::new(Value::Int32(-i), TypeAnnotation::I32)),
-                        ))
-                    }
-                    Value::Int64(i) => {
-                        return Ok(Some(
-                            arena.insert(TypedValue::new(Value::Int64(-i), TypeAnnotation::I64)),
-                        ))
-                    }
-                    Value::Float64(f) => {
-                        return Ok(Some(
-                            arena.insert(TypedValue::new(Value::Float64(-f), TypeAnnotation::F64)),
-                        ))
-                    }
-                    _ => {
-                        return Ok(Some(
-                            arena.insert(TypedValue::new(Value::Unit, TypeAnnotation::Unit)),
-                        ))
-                    }
-                },
+                TokenType::Minus => {
+                    let value = (-&right.value)?;
+                    let value_type = right.value_type.clone();
+                    return Ok(Some(arena.insert(TypedValue::new(value, value_type))));
+                }
+                TokenType::Tilde => {
+                    let value = (!&right.value)?;
+                    let value_type = right.value_type.clone();
+                    return Ok(Some(arena.insert(TypedValue::new(value, value_type))));
+                }
                 TokenType::Bang => {
                     let value = !self.is_truthy(&right.value);
                     return Ok(Some(arena.insert(TypedValue::new(
@@ -400,7 +993,7 @@ impl Interpreter {
         logical_expr: &LogicalExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         if let Some(arena_entry_index) = self.evaluate(&logical_expr.left, arena, env)? {
             let arena_entry = &arena[arena_entry_index];
             let left: TypedValue = arena_entry.try_into()?;
@@ -408,7 +1001,7 @@ impl Interpreter {
                 let index = arena.insert(left);
                 return Ok(Some(index));
             }
-            if !self.is_truthy(&left.value) {
+            if logical_expr.operator == TokenType::And && !self.is_truthy(&left.value) {
                 let index = arena.insert(left);
                 return Ok(Some(index));
             }
@@ -417,134 +1010,361 @@ impl Interpreter {
         Ok(None)
     }
 
-    // TODO: this shit is _still_ a mess
     fn visit_set_expr(
         &self,
         set_expr: &SetExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        let value: TypedValue;
-        if let Some(value_entry_index) = self.evaluate(&set_expr.value, arena, env)? {
-            let value_arena_entry = &arena[value_entry_index];
-            value = value_arena_entry.try_into()?;
-        } else {
-            return Err(LangErrorType::new_iie_error(
-                "Set expr failed to retrieve the value to set".into(),
-            ));
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let object_entry_index = match self.evaluate(&set_expr.object, arena, env)? {
+            Some(object_entry_index) => object_entry_index,
+            None => {
+                return Err(LangErrorType::new_iie_error(
+                    "Set expr failed to retrieve the object to set".into(),
+                )
+                .into())
+            }
+        };
+        let field_index = self.field_to_set(object_entry_index, &set_expr.name, arena, env)?;
+        // The field's value is read before `value` is evaluated, like it would be by `p.x + value`
+        let current_index = match set_expr.operator {
+            Some(_) => {
+                let field_entry = &arena[field_index];
+                let field: TypedValue = field_entry.try_into()?;
+                Some(arena.insert(field))
+            }
+            None => None,
+        };
+        let value_entry_index = match self.evaluate(&set_expr.value, arena, env)? {
+            Some(value_entry_index) => value_entry_index,
+            None => {
+                return Err(LangErrorType::new_iie_error(
+                    "Set expr failed to retrieve the value to set".into(),
+                )
+                .into())
+            }
+        };
+        let value = match (&set_expr.operator, current_index) {
+            (Some(operator), Some(current_index)) => {
+                self.binary_op(operator, current_index, value_entry_index, arena, env)?
+            }
+            _ => {
+                let value_arena_entry = &arena[value_entry_index];
+                value_arena_entry.try_into()?
+            }
+        };
+        arena.replace(field_index, value)?;
+        Ok(None)
+    }
+
+    /// The arena entry of the field `name` of the struct instance at `object_index`, which a set
+    /// replaces the value of
+    fn field_to_set(
+        &self,
+        object_index: ArenaEntryIndex,
+        name: &str,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<ArenaEntryIndex, Interrupt> {
+        let object_arena_entry = &arena[object_index];
+        let object: &TypedValue = object_arena_entry.try_into()?;
+        // `self` refers to the instance through the environment
+        let struct_index = match object.value {
+            Value::SelfIndex(ref s) => env.get(s.env_id, &s.name)?,
+            _ => object_index,
+        };
+        let struct_arena_entry = &arena[struct_index];
+        let object: &TypedValue = struct_arena_entry.try_into()?;
+        match object.value {
+            Value::Struct(ref struct_value) => {
+                if !struct_value.field_exists(name) {
+                    return Err(LangErrorType::new_runtime_error(
+                        RuntimeErrorType::UndefinedVariable {
+                            reason: "Tried to set an undefined struct field".to_string(),
+                        },
+                    )
+                    .into());
+                }
+                Ok(struct_value.get_field(name)?)
+            }
+            _ => Err(
+                LangErrorType::new_runtime_error(RuntimeErrorType::UndefinedVariable {
+                    reason: "Tried to do a set on an invalid value type".to_string(),
+                })
+                .into(),
+            ),
         }
-        if let Some(object_entry_index) = self.evaluate(&set_expr.object, arena, env)? {
-            {
-                let object_arena_entry = &mut arena[object_entry_index];
-                let object: &mut TypedValue = object_arena_entry.try_into()?;
-                if let Value::SelfIndex(ref s) = object.value {
-                    let prev_object_index = env.get(s.env_id, &s.name)?;
-                    let prev_object_entry = &mut arena[prev_object_index];
-                    let prev_object: &mut TypedValue = prev_object_entry.try_into()?;
-                    if let Value::Struct(ref mut struct_value) = prev_object.value {
-                        if !struct_value.field_exists(&set_expr.name) {
-                            return Err(LangErrorType::new_runtime_error(
-                                RuntimeErrorType::UndefinedVariable {
-                                    reason: "Tried to set an undefined struct field".to_string(),
-                                },
-                            ));
-                        }
-                        let field_index = struct_value.get_field(&set_expr.name)?;
-                        arena[field_index] = ArenaEntry::Occupied(value);
-                        return Ok(None);
-                    }
+    }
+
+    fn visit_set_array_element_expr(
+        &self,
+        set_array_element_expr: &SetArrayElementExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let index_entry_index = match self.evaluate(&set_array_element_expr.index, arena, env)? {
+            Some(index_entry_index) => index_entry_index,
+            None => {
+                return Err(
+                    LangErrorType::new_iie_error("could not set array element".into()).into(),
+                )
+            }
+        };
+        let index_arena_entry = &arena[index_entry_index];
+        let index_value: TypedValue = index_arena_entry.try_into()?;
+        // The element is read before `value` is evaluated, like it would be by `a[i] + value`
+        let current_index = match set_array_element_expr.operator {
+            Some(_) => Some(self.element_at(
+                &set_array_element_expr.name,
+                index_entry_index,
+                arena,
+                env,
+            )?),
+            None => None,
+        };
+        if let Some(value_entry_index) = self.evaluate(&set_array_element_expr.value, arena, env)? {
+            let value = match (&set_array_element_expr.operator, current_index) {
+                (Some(operator), Some(current_index)) => {
+                    self.binary_op(operator, current_index, value_entry_index, arena, env)?
+                }
+                _ => {
+                    let value_arena_entry = &arena[value_entry_index];
+                    value_arena_entry.try_into()?
                 }
+            };
+            // Assigning to a key of a map inserts it
+            let container_index = env.get(env.current_index, &set_array_element_expr.name)?;
+            let map_key = MapKey::new(&index_value.value, arena)?;
+            let container_entry = &mut arena[container_index];
+            let container: &mut TypedValue = container_entry.try_into()?;
+            if let Value::Map(_) = container.value {
+                container.insert_checked(index_value, map_key, value)?;
+                return Ok(None);
+            }
+            env.assign_index_entry(
+                env.current_index,
+                &set_array_element_expr.name,
+                &value,
+                arena,
+                &index_value,
+            )?;
+        }
+        Ok(None)
+    }
+
+    fn visit_array_expr(
+        &self,
+        array_expr: &ArrayExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let mut elements = Vec::new();
+        let mut type_annotation = TypeAnnotation::Unit;
+        let mut array_element_type = TypeAnnotation::Unit;
+        // Invariant used in order evaluate the initial array element only once
+        let mut first_element = true;
+        for item in array_expr.elements.iter() {
+            if let Some(item_index) = self.evaluate(&item, arena, env)? {
+                let item_arena_entry = &arena[item_index];
+                let element: &TypedValue = item_arena_entry.try_into()?;
+                if first_element {
+                    array_element_type = element.value_type.clone();
+                    first_element = false;
+                } else {
+                    TypeChecker::check_type(&array_element_type, &element.value_type)?;
+                }
+                elements.push(element.clone());
+            }
+        }
+        if let Some(ref type_annotation_set) = array_expr.type_annotation {
+            type_annotation = type_annotation_set.clone();
+        }
+        if type_annotation == TypeAnnotation::Unit {
+            type_annotation = TypeAnnotation::Array(Box::new(array_element_type));
+        }
+        Ok(Some(arena.insert(TypedValue::new(
+            Value::Array(elements),
+            type_annotation,
+        ))))
+    }
+
+    fn visit_map_expr(
+        &self,
+        map_expr: &MapExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let mut entries = Vec::new();
+        for (key, value) in map_expr.entries.iter() {
+            let key = self.evaluate(key, arena, env)?;
+            let value = self.evaluate(value, arena, env)?;
+            if let (Some(key_index), Some(value_index)) = (key, value) {
+                let key_arena_entry = &arena[key_index];
+                let key: TypedValue = key_arena_entry.try_into()?;
+                let value_arena_entry = &arena[value_index];
+                let value: TypedValue = value_arena_entry.try_into()?;
+                entries.push((key, value));
+            }
+        }
+        // Without the type of the variable it initializes, the map takes the type of its first
+        // entry
+        let type_annotation = match (&map_expr.type_annotation, entries.first()) {
+            (Some(type_annotation), _) => type_annotation.clone(),
+            (None, Some((key, value))) => TypeAnnotation::Map(
+                Box::new(key.value_type.clone()),
+                Box::new(value.value_type.clone()),
+            ),
+            (None, None) => TypeAnnotation::Map(
+                Box::new(TypeAnnotation::Unit),
+                Box::new(TypeAnnotation::Unit),
+            ),
+        };
+        let mut map = TypedValue::new(Value::Map(Box::default()), type_annotation);
+        for (key, value) in entries {
+            let map_key = MapKey::new(&key.value, arena)?;
+            map.insert_checked(key, map_key, value)?;
+        }
+        Ok(Some(arena.insert(map)))
+    }
+
+    fn visit_tuple_expr(
+        &self,
+        tuple_expr: &TupleExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let mut elements = Vec::new();
+        for element in tuple_expr.elements.iter() {
+            if let Some(element_index) = self.evaluate(element, arena, env)? {
+                let element_arena_entry = &arena[element_index];
+                let element: TypedValue = element_arena_entry.try_into()?;
+                elements.push(element);
+            }
+        }
+        let type_annotation = TypeAnnotation::Tuple(
+            elements
+                .iter()
+                .map(|element| element.value_type.clone())
+                .collect(),
+        );
+        Ok(Some(arena.insert(TypedValue::new(
+            Value::Tuple(elements),
+            type_annotation,
+        ))))
+    }
+
+    fn visit_struct_literal_expr(
+        &self,
+        struct_literal: &StructLiteralExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let struct_index = env.get(env.current_index, &struct_literal.name)?;
+        let struct_entry = &arena[struct_index];
+        let struct_value: TypedValue = struct_entry.try_into()?;
+        let struct_value = match struct_value.value {
+            Value::Struct(struct_value) => struct_value,
+            _ => {
+                return Err(
+                    LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                        reason: format!("'{}' is not a struct", struct_literal.name),
+                    })
+                    .into(),
+                )
+            }
+        };
+        let instance =
+            self.instantiate_struct(&*struct_value, &struct_literal.fields, arena, env)?;
+        Ok(Some(arena.insert(instance)))
+    }
+
+    /// Builds an instance of `struct_value` out of the values of `fields` and the defaults of the
+    /// fields missing from them
+    pub fn instantiate_struct(
+        &self,
+        struct_value: &dyn StructInstanceTrait,
+        fields: &[(String, Expr)],
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<TypedValue, Interrupt> {
+        let mut values = HashMap::new();
+        for (name, value) in fields.iter() {
+            if let Some(value_index) = self.evaluate(value, arena, env)? {
+                let value_entry = &arena[value_index];
+                let value: TypedValue = value_entry.try_into()?;
+                values.insert(name.clone(), value);
+            }
+        }
+        // Defaults are evaluated anew for every instance, so instances never share them
+        for (name, default) in struct_value.defaults().iter() {
+            if values.contains_key(name) {
+                continue;
             }
-            let object_arena_entry = &mut arena[object_entry_index];
-            let object: &mut TypedValue = object_arena_entry.try_into()?;
-            match object.value {
-                Value::Struct(ref mut struct_value) => {
-                    if !struct_value.field_exists(&set_expr.name) {
-                        return Err(LangErrorType::new_runtime_error(
-                            RuntimeErrorType::UndefinedVariable {
-                                reason: "Tried to set an undefined struct field".to_string(),
-                            },
-                        ));
-                    }
-                    let field_value = struct_value.get_field(&set_expr.name)?;
-                    arena[field_value] = ArenaEntry::Occupied(value);
-                }
-                _ => {
-                    return Err(LangErrorType::new_runtime_error(
-                        RuntimeErrorType::UndefinedVariable {
-                            reason: "Tried to do a set on an invalid value type".to_string(),
-                        },
-                    ));
-                }
+            if let Some(default_index) = self.evaluate(default, arena, env)? {
+                let default_entry = &arena[default_index];
+                let default: TypedValue = default_entry.try_into()?;
+                values.insert(name.clone(), default);
             }
         }
-        Ok(None)
+        Ok(struct_value.instantiate(arena, values)?)
     }
 
-    fn visit_set_array_element_expr(
+    fn visit_cast_expr(
         &self,
-        set_array_element_expr: &SetArrayElementExpr,
+        cast: &CastExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        let index: usize;
-        if let Some(index_entry_index) = self.evaluate(&set_array_element_expr.index, arena, env)? {
-            let index_arena_entry = &arena[index_entry_index];
-            let index_value: &TypedValue = index_arena_entry.try_into()?;
-            index = index_value.as_array_index()?;
-        } else {
-            return Err(LangErrorType::new_iie_error(
-                "could not set array element".into(),
-            ));
-        }
-        if let Some(value_entry_index) = self.evaluate(&set_array_element_expr.value, arena, env)? {
-            let value_arena_entry = &arena[value_entry_index];
-            let value: TypedValue = value_arena_entry.try_into()?;
-            env.assign_index_entry(
-                env.current_index,
-                &set_array_element_expr.name,
-                &value,
-                arena,
-                index,
-            )?;
-        }
-        Ok(None)
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let index = match self.evaluate(&cast.expression, arena, env)? {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let value: TypedValue = (&arena[index]).try_into()?;
+        let target = cast.target.numeric_type().ok_or_else(|| {
+            LangErrorType::new_runtime_error(RuntimeErrorType::InvalidTypeAssignmentError {
+                reason: format!("cannot cast to non-numeric type {}", cast.target),
+            })
+        })?;
+        let result = value.value.cast(target)?;
+        Ok(Some(arena.insert(TypedValue::new(
+            result,
+            TypeAnnotation::from(target),
+        ))))
     }
 
-    fn visit_array_expr(
+    fn visit_try_expr(
         &self,
-        array_expr: &ArrayExpr,
+        try_expr: &TryExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        let mut elements = Vec::new();
-        let mut type_annotation = TypeAnnotation::Unit;
-        let mut array_element_type = TypeAnnotation::Unit;
-        // Invariant used in order evaluate the initial array element only once
-        let mut first_element = true;
-        for item in array_expr.elements.iter() {
-            if let Some(item_index) = self.evaluate(&item, arena, env)? {
-                let item_arena_entry = &arena[item_index];
-                let element: &TypedValue = item_arena_entry.try_into()?;
-                if first_element {
-                    array_element_type = element.value_type.clone();
-                    first_element = false;
-                } else {
-                    TypeChecker::check_type(&array_element_type, &element.value_type)?;
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let index = match self.evaluate(&try_expr.expression, arena, env)? {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let entry = &arena[index];
+        let value: TypedValue = entry.try_into()?;
+        if let Value::EnumVariant(ref variant) = value.value {
+            match TryOutcome::of(&variant.enum_name, &variant.variant) {
+                Some(TryOutcome::Unwrap) => {
+                    let payload = variant.fields[0].clone();
+                    return Ok(Some(arena.insert(payload)));
                 }
-                elements.push(element.clone());
+                Some(TryOutcome::Return) => {
+                    return Err(Interrupt::ControlFlow(ControlFlow::Return { index }))
+                }
+                None => {}
             }
         }
-        if let Some(ref type_annotation_set) = array_expr.type_annotation {
-            type_annotation = type_annotation_set.to_type_annotation()?;
-        }
-        if type_annotation == TypeAnnotation::Unit {
-            type_annotation = TypeAnnotation::Array(Box::new(array_element_type));
-        }
-        Ok(Some(arena.insert(TypedValue::new(
-            Value::Array(elements),
-            type_annotation,
-        ))))
+        Err(
+            LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                reason: format!(
+                    "the '?' operator can only be applied to an Option or a Result, found {}",
+                    value.value_type
+                ),
+            })
+            .into(),
+        )
     }
 
     fn visit_index_expr(
@@ -552,86 +1372,131 @@ impl Interpreter {
         index_expr: &IndexExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         if let Some(index_entry_index) = self.evaluate(&index_expr.index, arena, env)? {
-            let index_arena_entry = &arena[index_entry_index];
-            let index_value: &TypedValue = index_arena_entry.try_into()?;
-            let index = index_value.as_array_index()?;
-            let value_entry_index = env.get(env.current_index, &index_expr.from)?;
-            let value_arena_entry = &mut arena[value_entry_index];
-            let value: &mut TypedValue = value_arena_entry.try_into()?;
-            let value_at_index = match value.value {
-                Value::Array(ref mut arr) => {
-                    if index < arr.len() {
-                        arr[index].clone()
-                    } else {
-                        return Err(LangErrorType::new_runtime_error(
-                                RuntimeErrorType::GenericError {
-                                    reason: format!("Index out of bounds. Tried to index at {} for an array of length {}", index, arr.len()),
-                                },
-                            ));
-                    }
-                }
-                _ => {
-                    return Err(LangErrorType::new_runtime_error(
-                        RuntimeErrorType::GenericError {
-                            reason: "Tried to index a non-array value. This should never happen"
-                                .to_string(),
-                        },
-                    ))
-                }
-            };
-            return Ok(Some(arena.insert(value_at_index)));
+            let value = self.element_at(&index_expr.from, index_entry_index, arena, env)?;
+            return Ok(Some(value));
         }
         Ok(None)
     }
 
+    /// The element of the array, map or indexable struct instance in the variable `from` at the
+    /// index at `index_entry_index`
+    fn element_at(
+        &self,
+        from: &str,
+        index_entry_index: ArenaEntryIndex,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<ArenaEntryIndex, Interrupt> {
+        let index_arena_entry = &arena[index_entry_index];
+        let index_value: TypedValue = index_arena_entry.try_into()?;
+        let value_entry_index = env.get(env.current_index, from)?;
+        // Struct instances are indexed by their impl of the built-in trait `Index`
+        if let Some(value) = self.call_method(
+            value_entry_index,
+            "index",
+            vec![value_entry_index, index_entry_index],
+            arena,
+            env,
+        )? {
+            return Ok(arena.insert(value));
+        }
+        let map_key = MapKey::new(&index_value.value, arena)?;
+        let value_arena_entry = &mut arena[value_entry_index];
+        let value: &mut TypedValue = value_arena_entry.try_into()?;
+        let value_at_index = match value.value {
+            Value::Map(ref map) => match map.get(&map_key) {
+                Some(value) => value.clone(),
+                None => {
+                    return Err(
+                        LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                            reason: error_message(&ErrMessage::MissingKey(
+                                index_value.value.debug_string(arena)?,
+                            )),
+                        })
+                        .into(),
+                    )
+                }
+            },
+            Value::Array(ref mut arr) => arr[index_value.as_array_index(arr.len())?].clone(),
+            _ => {
+                return Err(
+                    LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                        reason: "Tried to index a non-array value. This should never happen"
+                            .to_string(),
+                    })
+                    .into(),
+                )
+            }
+        };
+        Ok(arena.insert(value_at_index))
+    }
+
     #[inline(always)]
     pub fn interpret(&self, stmts: Vec<Stmt>) -> Result<(), LangError> {
         let mut env = Environment::new();
         let mut arena: Arena<TypedValue> = Arena::with_capacity(256);
         for stmt in stmts {
-            self.execute(&stmt, &mut arena, &mut env)?;
+            self.execute(&stmt, &mut arena, &mut env)
+                .map_err(Interrupt::into_error)?;
         }
+        debug!("{}:{} Arena: {}", file!(), line!(), arena.stats());
         Ok(())
     }
 
+    /// Executes `stmt`, then releases the temporaries it created and collects garbage if enough
+    /// has been allocated. The value the statement evaluates to, or returns, stays rooted
     #[inline(always)]
     fn execute(
         &self,
         stmt: &Stmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        Ok(self.visit_stmt(stmt, arena, env)?)
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let temporaries = arena.temporaries_mark();
+        let result = self.visit_stmt(stmt, arena, env);
+        arena.release_temporaries(temporaries);
+        if let Some(index) = Interpreter::in_flight_index(&result) {
+            arena.root_temporary(index);
+        }
+        if arena.should_collect() {
+            env.collect_garbage(arena);
+        }
+        result
+    }
+
+    /// The arena index carried out of a statement, either as its value or as a value returned or
+    /// broken out of a loop with
+    fn in_flight_index(
+        result: &Result<Option<ArenaEntryIndex>, Interrupt>,
+    ) -> Option<ArenaEntryIndex> {
+        match result {
+            Ok(index) => *index,
+            Err(Interrupt::ControlFlow(ControlFlow::Return { index })) => Some(*index),
+            Err(Interrupt::ControlFlow(ControlFlow::Break { value, .. })) => *value,
+            Err(_) => None,
+        }
     }
 
     fn look_up_variable(
         &self,
+        id: NodeId,
         token: &str,
-        arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<ArenaEntryIndex, LangError> {
         debug!(
-            "{}:{} Looking for token '{:?}' within env '{:?}', and arena:",
+            "{}:{} Looking for token '{:?}' ({}) within env '{:?}'",
             file!(),
             line!(),
             token,
+            id,
             env,
         );
-        for entry in arena.entries().iter() {
-            debug!("{:?}", entry);
+        match self.locals.get(&id) {
+            Some(distance) => env.get_at(env.current_index, *distance, token),
+            None => env.get(env.current_index, token),
         }
-        for entry in (0..env.entries.len()).rev() {
-            if env[entry].values.contains_key(token) {
-                return Ok(Some(env[entry].values[token]));
-            }
-        }
-        Err(LangErrorType::new_runtime_error(
-            RuntimeErrorType::UndefinedVariable {
-                reason: format!("could not find variable {}", token),
-            },
-        ))
     }
 
     pub fn execute_block(
@@ -640,29 +1505,25 @@ impl Interpreter {
         env_id: &mut EnvironmentEntryIndex,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         let previous = env.current_index;
         env.current_index = *env_id;
+        let result = self.execute_block_statements(stmts, arena, env);
+        // The block's scope ends however the block is left, so that no handles to it outlive it
+        env.remove_scope(*env_id)?;
+        env.current_index = previous;
+        result
+    }
+
+    fn execute_block_statements(
+        &self,
+        stmts: &[Stmt],
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         for stmt in stmts {
-            match stmt {
-                Stmt::Return(_) => {
-                    // Set value and break early on a return
-                    if let Some(index) = self.execute(&stmt, arena, env)? {
-                        debug!("return value: idx {} value {:?}", index, arena[index]);
-                        env.remove_entry(env.current_index);
-                        env.current_index = previous;
-                        return Err(LangError::from(LangErrorType::ControlFlow {
-                            subtype: ControlFlow::Return { index },
-                        }));
-                    }
-                }
-                _ => {
-                    self.execute(&stmt, arena, env)?;
-                }
-            }
+            self.execute(stmt, arena, env)?;
         }
-        env.remove_entry(env.current_index);
-        env.current_index = previous;
         Ok(None)
     }
 
@@ -670,14 +1531,16 @@ impl Interpreter {
         &self,
         callable: &dyn CallableTrait,
         trait_function: &TraitFunctionValue,
+        bindings: &HashMap<String, TypeAnnotation>,
     ) -> Result<(), LangError> {
         if let Some(return_type) = callable.get_return_type() {
-            if trait_function.function.return_type != return_type {
+            let trait_return_type = trait_function.function.return_type.substitute(bindings);
+            if trait_return_type != return_type {
                 return Err(LangErrorType::new_runtime_error(
                     RuntimeErrorType::InvalidTypeAssignmentError {
                         reason: format!(
                             "trait impl {} doesn't match trait return type {}",
-                            return_type, trait_function.function.return_type
+                            return_type, trait_return_type
                         ),
                     },
                 ));
@@ -709,6 +1572,7 @@ impl Interpreter {
         &self,
         callable: &dyn CallableTrait,
         trait_function: &TraitFunctionValue,
+        bindings: &HashMap<String, TypeAnnotation>,
     ) -> Result<(), LangError> {
         for params in trait_function
             .function
@@ -716,12 +1580,13 @@ impl Interpreter {
             .iter()
             .zip(callable.get_params().iter())
         {
-            if params.0.type_annotation != params.1.type_annotation {
+            let trait_param_type = params.0.type_annotation.substitute(bindings);
+            if trait_param_type != params.1.type_annotation {
                 return Err(LangErrorType::new_runtime_error(
                     RuntimeErrorType::InvalidTypeAssignmentError {
                         reason: format!(
                             "trait impl expected parameter of type {}, found type {}",
-                            params.0.type_annotation, params.1.type_annotation
+                            trait_param_type, params.1.type_annotation
                         ),
                     },
                 ));
@@ -736,18 +1601,19 @@ impl Interpreter {
         fn_value: &Value,
         env: &Environment,
         arena: &Arena<TypedValue>,
-        trait_token: &str,
+        impl_trait_stmt: &ImplTraitStmt,
     ) -> Result<bool, LangError> {
-        let typed_trait_value_idx = env.get(env.current_index, trait_token)?;
+        let typed_trait_value_idx = env.get(env.current_index, &impl_trait_stmt.trait_name)?;
         let entry = &arena[typed_trait_value_idx];
         let typed_trait_value: &TypedValue = entry.try_into()?;
         let trait_value_type: &TraitValue = (&typed_trait_value.value).try_into()?;
+        let bindings = impl_trait_stmt.trait_bindings(&trait_value_type.trait_stmt.type_params);
         if let Some(trait_fn_decl) = trait_value_type.fn_declarations.get(impl_trait) {
             if let Value::TraitFunction(ref trait_function) = trait_fn_decl.value {
                 let callable_value: &dyn CallableTrait = fn_value.try_into()?;
-                self.check_impl_trait_return_type(callable_value, trait_function)?;
+                self.check_impl_trait_return_type(callable_value, trait_function, &bindings)?;
                 self.check_impl_trait_arity(callable_value, trait_function)?;
-                self.check_impl_trait_param_types(callable_value, trait_function)?;
+                self.check_impl_trait_param_types(callable_value, trait_function, &bindings)?;
             }
         }
         Ok(false)
@@ -760,7 +1626,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         expr: &Expr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(visit_expr(self, expr, arena, env)?)
     }
     fn visit_stmt(
@@ -768,7 +1634,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         stmt: &Stmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(visit_stmt(self, stmt, arena, env)?)
     }
 
@@ -777,7 +1643,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         assign: &AssignExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_assign_expr(assign, arena, env)?)
     }
     fn visit_binary(
@@ -785,7 +1651,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         binary: &BinaryExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_binary_expr(binary, arena, env)?)
     }
     fn visit_call(
@@ -793,7 +1659,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         call: &CallExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_call_expr(call, arena, env)?)
     }
     fn visit_get(
@@ -801,47 +1667,48 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         get: &GetExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_get_expr(get, arena, env)?)
     }
     fn visit_enum_path(
         &self,
-        _: &EnumPathExpr,
-        _: &mut Arena<TypedValue>,
-        _: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        Ok(None)
+        enum_path: &EnumPathExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        Ok(self.visit_enum_path_expr(enum_path, arena, env)?)
+    }
+
+    fn visit_match(
+        &self,
+        match_expr: &MatchExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        Ok(self.visit_match_expr(match_expr, arena, env)?)
     }
     fn visit_grouping(
         &self,
         grouping_expr: &GroupingExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.evaluate(&grouping_expr.expression, arena, env)?)
     }
     fn visit_literal(
         &self,
         literal: &LiteralExpr,
         arena: &mut Arena<TypedValue>,
-        env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        match literal.value.value_type {
-            TypeAnnotation::User(ref user_type) => {
-                let value_index = env.get(env.current_index, &user_type)?;
-                let value_entry = &arena[value_index];
-                let value: TypedValue = value_entry.try_into()?;
-                Ok(Some(arena.insert(value)))
-            }
-            _ => Ok(Some(arena.insert(literal.value.clone()))),
-        }
+        _: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        Ok(Some(arena.insert(literal.value.clone())))
     }
     fn visit_logical(
         &self,
         logical: &LogicalExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_logical_expr(logical, arena, env)?)
     }
     fn visit_set(
@@ -849,7 +1716,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         set: &SetExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_set_expr(set, arena, env)?)
     }
     fn visit_unary(
@@ -857,7 +1724,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         unary: &UnaryExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_unary_expr(unary, arena, env)?)
     }
     fn visit_array(
@@ -865,15 +1732,95 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         array: &ArrayExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_array_expr(array, arena, env)?)
     }
+    fn visit_map(
+        &self,
+        map: &MapExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_map_expr(map, arena, env)
+    }
+    fn visit_tuple(
+        &self,
+        tuple: &TupleExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_tuple_expr(tuple, arena, env)
+    }
+    fn visit_struct_literal(
+        &self,
+        struct_literal: &StructLiteralExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_struct_literal_expr(struct_literal, arena, env)
+    }
+    fn visit_try(
+        &self,
+        try_expr: &TryExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_try_expr(try_expr, arena, env)
+    }
+    fn visit_cast(
+        &self,
+        cast: &CastExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_cast_expr(cast, arena, env)
+    }
+    fn visit_block_expr(
+        &self,
+        block: &BlockExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_block_expression(block, arena, env)
+    }
+    fn visit_if_expr(
+        &self,
+        if_expr: &IfExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_if_expression(if_expr, arena, env)
+    }
+    fn visit_loop(
+        &self,
+        loop_expr: &LoopExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_loop_expr(loop_expr, arena, env)
+    }
+    fn visit_range(
+        &self,
+        range: &RangeExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_range_expr(range, arena, env)
+    }
+    fn visit_format_string(
+        &self,
+        format_string: &FormatStringExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        self.visit_format_string_expr(format_string, arena, env)
+    }
     fn visit_index(
         &self,
         index: &IndexExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_index_expr(index, arena, env)?)
     }
     fn visit_set_array_element(
@@ -881,29 +1828,55 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         set_array_element: &SetArrayElementExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_set_array_element_expr(set_array_element, arena, env)?)
     }
     fn visit_variable(
         &self,
         variable: &VariableExpr,
-        arena: &mut Arena<TypedValue>,
+        _: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        Ok(self.look_up_variable(&variable.name, arena, env)?)
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        Ok(Some(self.look_up_variable(
+            variable.id,
+            &variable.name,
+            env,
+        )?))
     }
     fn visit_self_ident(
         &self,
         self_ident: &SelfIdentExpr,
-        arena: &mut Arena<TypedValue>,
+        _: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        Ok(self.look_up_variable(&self_ident.keyword, arena, env)?)
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        Ok(Some(self.look_up_variable(
+            self_ident.id,
+            &self_ident.keyword,
+            env,
+        )?))
     }
 
-    fn visit_break(&self) -> Result<Option<ArenaEntryIndex>, LangError> {
-        Err(LangError::from(LangErrorType::ControlFlow {
-            subtype: ControlFlow::Break,
+    fn visit_break(
+        &self,
+        break_stmt: &BreakStmt,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let value = match break_stmt.value {
+            Some(ref value) => self.evaluate(value, arena, env)?,
+            None => None,
+        };
+        Err(Interrupt::ControlFlow(ControlFlow::Break {
+            label: break_stmt.label.clone(),
+            value,
+        }))
+    }
+    fn visit_continue(
+        &self,
+        continue_stmt: &ContinueStmt,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        Err(Interrupt::ControlFlow(ControlFlow::Continue {
+            label: continue_stmt.label.clone(),
         }))
     }
 
@@ -912,38 +1885,38 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         assert_stmt: &AssertStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         if let Some(assert_stmt_index) = self.evaluate(&assert_stmt.condition, arena, env)? {
             let arena_entry = &arena[assert_stmt_index];
-            let eval = match arena_entry {
-                ArenaEntry::Occupied(v) => v,
-                ArenaEntry::Emtpy => panic!(),
-            };
+            let eval: &TypedValue = arena_entry.try_into()?;
             if self.is_truthy(&eval.value) {
                 return Ok(Some(assert_stmt_index));
             } else {
                 println!("assert failed");
-                return Err(LangError::from(LangErrorType::ControlFlow {
-                    subtype: ControlFlow::Assert,
-                }));
+                return Err(
+                    LangErrorType::new_runtime_error(RuntimeErrorType::AssertionError {
+                        reason: "the asserted condition was false".to_string(),
+                    })
+                    .into(),
+                );
             }
         }
         Ok(None)
     }
     fn visit_enum(
         &self,
-        _: &EnumStmt,
-        _: &mut Arena<TypedValue>,
-        _: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        unimplemented!()
+        enum_stmt: &EnumStmt,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        Ok(self.visit_enum_stmt(enum_stmt, arena, env)?)
     }
     fn visit_impl(
         &self,
         impl_stmt: &ImplStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_impl_stmt(impl_stmt, arena, env)?)
     }
     fn visit_impl_trait(
@@ -951,7 +1924,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         impl_trait: &ImplTraitStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_impl_trait_stmt(impl_trait, arena, env)?)
     }
     fn visit_block(
@@ -959,8 +1932,8 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         block: &BlockStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        let mut env_id = env.entry_from(env.current_index.clone());
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let mut env_id = env.entry_from(env.current_index)?;
         Ok(self.execute_block(&block.statements, &mut env_id, arena, env)?)
     }
     fn visit_struct(
@@ -968,7 +1941,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         block: &StructStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_struct_stmt(&block, arena, env)?)
     }
     fn visit_expression(
@@ -976,7 +1949,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         block: &ExpressionStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_expr(&block.expression, arena, env)?)
     }
     fn visit_trait(
@@ -984,7 +1957,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         block: &TraitStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(self.visit_trait_stmt(block, arena, env)?)
     }
     fn visit_trait_function(
@@ -992,7 +1965,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         trait_fn_stmt: &TraitFunctionStmt,
         arena: &mut Arena<TypedValue>,
         _: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         let trait_fn = Value::TraitFunction(Box::new(TraitFunctionValue {
             function: trait_fn_stmt.clone(),
         }));
@@ -1005,17 +1978,19 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         function_stmt: &FunctionStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         let function = Value::Callable(Box::new(Callable::new(
             function_stmt.clone(),
             env.current_index,
         )));
+        // The function can be called after the scope it's declared in ends
+        env.capture(env.current_index)?;
         let function_value_index = env.define_and_insert(
             env.current_index,
             arena,
             &function_stmt.name,
-            TypedValue::new(function, TypeAnnotation::Fn),
-        );
+            TypedValue::new(function, function_stmt.function_type()),
+        )?;
         Ok(Some(function_value_index))
     }
     fn visit_if(
@@ -1023,14 +1998,11 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         if_stmt: &IfStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         debug!("if_stmt condition {:#?}", if_stmt.condition);
         if let Some(if_stmt_index) = self.evaluate(&if_stmt.condition, arena, env)? {
             let arena_entry = &arena[if_stmt_index];
-            let eval = match arena_entry {
-                ArenaEntry::Occupied(v) => v,
-                ArenaEntry::Emtpy => panic!(),
-            };
+            let eval: &TypedValue = arena_entry.try_into()?;
             if self.is_truthy(&eval.value) {
                 return Ok(self.execute(&if_stmt.then_branch, arena, env)?);
             }
@@ -1045,7 +2017,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         print_stmt: &PrintStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         if let Some(expr_index) = self.evaluate(&print_stmt.expression, arena, env)? {
             let arena_entry = &arena[expr_index];
             let value: &TypedValue = arena_entry.try_into()?;
@@ -1058,7 +2030,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         return_stmt: &ReturnStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         let value = Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
             Value::Unit,
             TypeAnnotation::Unit,
@@ -1073,44 +2045,63 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         } else {
             TypedValue::new(Value::Unit, TypeAnnotation::Unit)
         };
-        Ok(Some(arena.insert(return_value)))
+        let index = arena.insert(return_value);
+        debug!("return value: idx {} value {:?}", index, arena[index]);
+        Err(Interrupt::ControlFlow(ControlFlow::Return { index }))
     }
     fn visit_var(
         &self,
         var_stmt: &VarStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         // A variable can be instantiated without being initialized
         if let Some(ref initializer) = var_stmt.initializer {
             if let Some(value_index) = self.evaluate(&initializer, arena, env)? {
                 let value_entry = &mut arena[value_index];
                 let value: &mut TypedValue = value_entry.try_into()?;
-                let var_type_annotation = var_stmt.type_annotation.clone();
-                if var_type_annotation != value.value_type {
-                    return Err(LangErrorType::new_runtime_error(
-                        RuntimeErrorType::InvalidTypeAssignmentError {
-                            reason: format!(
+                match var_stmt.type_annotation {
+                    Some(ref var_type_annotation) => {
+                        if !var_type_annotation.admits(&value.value_type) {
+                            return Err(LangErrorType::new_runtime_error(
+                                RuntimeErrorType::InvalidTypeAssignmentError {
+                                    reason: format!(
                         "Tried to assign a variable of type {} with an initializer of type {}",
                         var_type_annotation.to_string(),
                         value.value_type.to_string()
                     ),
-                        },
-                    ));
+                                },
+                            )
+                            .into());
+                        }
+                    }
+                    None => {
+                        if let Some(reason) =
+                            TypeChecker::inference_error(&var_stmt.name, &value.value_type)
+                        {
+                            return Err(LangErrorType::new_runtime_error(
+                                RuntimeErrorType::InvalidTypeAssignmentError { reason },
+                            )
+                            .into());
+                        }
+                    }
                 }
                 if let Value::Struct(ref mut struct_value) = value.value {
                     struct_value.set_instance_name(var_stmt.name.clone());
                 }
                 // Var vaue has already been put into the arena, so we just have to do an insert into the env
-                let env_id = env.current_index;
-                env[env_id]
+                let env_id = self.declaration_scope(var_stmt, env)?;
+                env.entry_mut(env_id)?
                     .values
                     .insert(var_stmt.name.clone(), value_index);
                 return Ok(Some(value_index));
             }
         } else {
             let value = TypedValue::new(Value::Unit, TypeAnnotation::Unit);
-            let var_type_annotation = var_stmt.type_annotation.clone();
+            let var_type_annotation = var_stmt
+                .type_annotation
+                .clone()
+                .unwrap_or(TypeAnnotation::Unit);
             // TODO: this is basically a bogus check, since all Unit value types can be reassigned to a non-Unit value type
             if var_type_annotation != value.value_type {
                 return Err(LangErrorType::new_runtime_error(
@@ -1121,41 +2112,95 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
                             value.value_type.to_string()
                         ),
                     },
-                ));
+                )
+                .into());
             }
-            let value_index =
-                env.define_and_insert(env.current_index, arena, &var_stmt.name, value);
+            let env_id = self.declaration_scope(var_stmt, env)?;
+            let value_index = env.define_and_insert(env_id, arena, &var_stmt.name, value)?;
             return Ok(Some(value_index));
         }
         Ok(None)
     }
+    fn visit_destructure(
+        &self,
+        destructure_stmt: &DestructureStmt,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let value_index = match self.evaluate(&destructure_stmt.initializer, arena, env)? {
+            Some(value_index) => value_index,
+            None => return Ok(None),
+        };
+        let value_arena_entry = &arena[value_index];
+        let value: TypedValue = value_arena_entry.try_into()?;
+        let elements = match value.value {
+            Value::Tuple(elements) if elements.len() == destructure_stmt.names.len() => elements,
+            _ => {
+                return Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::InvalidTypeAssignmentError {
+                        reason: format!(
+                            "Tried to destructure a value of type {} into {} variables",
+                            value.value_type,
+                            destructure_stmt.names.len()
+                        ),
+                    },
+                )
+                .into())
+            }
+        };
+        for (name, element) in destructure_stmt.names.iter().zip(elements) {
+            env.define_and_insert(env.current_index, arena, name, element)?;
+        }
+        Ok(None)
+    }
     fn visit_while(
         &self,
         while_stmt: &WhileStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
-        if let Some(condition_index) = self.evaluate(&while_stmt.condition, arena, env)? {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        // Each evaluation of the condition is released before the body runs, so long loops don't
+        // keep every condition value alive
+        let temporaries = arena.temporaries_mark();
+        while let Some(condition_index) = self.evaluate(&while_stmt.condition, arena, env)? {
             let condition_entry = &arena[condition_index];
-            let mut while_condition: &TypedValue = condition_entry.try_into()?;
-            while self.is_truthy(&while_condition.value) {
-                if let Err(error) = self.execute(&while_stmt.body, arena, env) {
-                    match error.context.get_context() {
-                        LangErrorType::ControlFlow { .. } => {
-                            break;
-                        }
-                        other => {
-                            return Err(LangError::from((*other).clone()));
-                        }
-                    }
-                }
-                let while_condition_index =
-                    self.evaluate(&while_stmt.condition, arena, env)?.unwrap();
-                let while_condition_entry = &arena[while_condition_index];
-                while_condition = match while_condition_entry {
-                    ArenaEntry::Occupied(v) => v,
-                    ArenaEntry::Emtpy => panic!(),
-                };
+            let while_condition: &TypedValue = condition_entry.try_into()?;
+            let is_truthy = self.is_truthy(&while_condition.value);
+            arena.release_temporaries(temporaries);
+            if !is_truthy {
+                break;
+            }
+            let result = self.execute(&while_stmt.body, arena, env);
+            if let Iteration::Break(_) = Interpreter::end_iteration(&while_stmt.label, result)? {
+                break;
+            }
+            if let Some(ref increment) = while_stmt.increment {
+                self.evaluate(increment, arena, env)?;
+            }
+        }
+        Ok(None)
+    }
+    fn visit_for_in(
+        &self,
+        for_in: &ForInStmt,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
+        let iterable = match self.evaluate(&for_in.iterable, arena, env)? {
+            Some(iterable) => iterable,
+            None => return Ok(None),
+        };
+        let mut items = self.items(iterable, arena)?;
+        // The iterable stays rooted while each item, and whatever its iteration allocated, is
+        // released before the next one
+        let temporaries = arena.temporaries_mark();
+        while let Some(item) = self.next_item(&mut items, arena, env)? {
+            let mut env_id = env.entry_from(env.current_index)?;
+            env.define(env_id, &for_in.variable, item)?;
+            let result = self.execute_block(&for_in.body, &mut env_id, arena, env);
+            arena.release_temporaries(temporaries);
+            if let Iteration::Break(_) = Interpreter::end_iteration(&for_in.label, result)? {
+                break;
             }
         }
         Ok(None)
@@ -1165,7 +2210,7 @@ impl Visitor<Option<ArenaEntryIndex>> for Interpreter {
         _: &ImportStmt,
         _: &mut Arena<TypedValue>,
         _: &mut Environment,
-    ) -> Result<Option<ArenaEntryIndex>, LangError> {
+    ) -> Result<Option<ArenaEntryIndex>, Interrupt> {
         Ok(None)
     }
 }
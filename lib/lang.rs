@@ -1,20 +1,56 @@
 use crate::ast::stmt::*;
 use crate::depresolver::*;
 use crate::error::*;
+use crate::exhaustiveness::ExhaustivenessChecker;
 use crate::interpreter::Interpreter;
 use crate::resolver::*;
 use crate::syntax::parser::Parser;
 use crate::syntax::scanner::*;
 use crate::syntax::token::*;
+use crate::type_checker::TypeChecker;
+use crate::vm::compiler::Compiler;
+use crate::vm::machine::Vm;
 
 use std::{
     fs::File,
     io::{self, prelude::*},
 };
 
+/// Declarations every program starts with
+const PRELUDE: &str = "
+enum Option<T> { None, Some(T) }
+enum Result<T, E> { Ok(T), Err(E) }
+trait Add { fn add(self: Self, other: Self) -> Self; }
+trait Sub { fn sub(self: Self, other: Self) -> Self; }
+trait Mul { fn mul(self: Self, other: Self) -> Self; }
+trait Div { fn div(self: Self, other: Self) -> Self; }
+trait Rem { fn rem(self: Self, other: Self) -> Self; }
+trait BitAnd { fn bitand(self: Self, other: Self) -> Self; }
+trait BitOr { fn bitor(self: Self, other: Self) -> Self; }
+trait BitXor { fn bitxor(self: Self, other: Self) -> Self; }
+trait Shl { fn shl(self: Self, other: Self) -> Self; }
+trait Shr { fn shr(self: Self, other: Self) -> Self; }
+trait Eq { fn eq(self: Self, other: Self) -> bool; }
+trait Ord { fn cmp(self: Self, other: Self) -> i32; }
+trait Index<I, T> { fn index(self: Self, index: I) -> T; }
+trait Iterator<T> { fn next(self: Self) -> Option<T>; }
+trait Display { fn fmt(self: Self) -> String; }
+";
+
+/// What executes a program once it has been checked
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Walks the syntax tree directly
+    #[default]
+    Interpreter,
+    /// Compiles the program to bytecode and runs it on a stack machine
+    Vm,
+}
+
 pub struct Lang<'a> {
     interpreter: Interpreter,
     scanner: Option<Scanner<'a>>,
+    backend: Backend,
 }
 
 impl<'a> Lang<'a> {
@@ -27,6 +63,7 @@ impl<'a> Lang<'a> {
         Lang {
             interpreter: Interpreter::new(),
             scanner,
+            backend: Backend::default(),
         }
     }
 }
@@ -37,6 +74,10 @@ impl<'a> Lang<'a> {
         Ok(())
     }
 
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = backend;
+    }
+
     pub fn print_tokens(&mut self) -> Result<(), LangError> {
         if let Some(ref mut scanner) = self.scanner {
             let tokens: Vec<Token> = scanner.scan_tokens()?;
@@ -72,16 +113,32 @@ impl<'a> Lang<'a> {
         }
     }
 
+    /// Parses the declarations of the built-in `Option` and `Result` enums, of the traits
+    /// overloading operators, of the `Iterator` trait `for` loops use and of the `Display` trait
+    /// format strings use
+    fn prelude() -> Result<Vec<Stmt>, LangError> {
+        let tokens: Vec<Token> = Scanner::new(PRELUDE).scan_tokens()?;
+        Parser::new(PRELUDE, tokens).parse()
+    }
+
     pub fn run(&mut self) -> Result<(), LangError> {
         let statements = self.build_statements();
         let mut dep_resolver = DependencyResolver::default();
         let mut resolver = Resolver::new(&mut self.interpreter);
         match statements {
             Ok(mut s) => {
-                let mut import_statements = dep_resolver.resolve(&s)?;
+                let mut import_statements = Lang::prelude()?;
+                import_statements.append(&mut dep_resolver.resolve(&s)?);
                 import_statements.append(&mut s);
                 resolver.resolve(&import_statements)?;
-                resolver.interpreter.interpret(import_statements)?;
+                ExhaustivenessChecker::default().check(&import_statements)?;
+                TypeChecker::default().check(&import_statements)?;
+                match self.backend {
+                    Backend::Interpreter => resolver.interpreter.interpret(import_statements)?,
+                    Backend::Vm => {
+                        Vm::new().run(Compiler::default().compile(&import_statements)?)?
+                    }
+                }
             }
             Err(e) => {
                 return Err(e);
@@ -96,10 +153,16 @@ impl<'a> Lang<'a> {
         let mut resolver = Resolver::new(&mut self.interpreter);
         let mut dep_resolver = DependencyResolver::default();
         let mut parser = Parser::new(script, tokens);
-        let statements = parser.parse()?;
+        let mut statements = Lang::prelude()?;
+        statements.append(&mut parser.parse()?);
         dep_resolver.resolve(&statements)?;
         resolver.resolve(&statements)?;
-        resolver.interpreter.interpret(statements)?;
+        ExhaustivenessChecker::default().check(&statements)?;
+        TypeChecker::default().check(&statements)?;
+        match self.backend {
+            Backend::Interpreter => resolver.interpreter.interpret(statements)?,
+            Backend::Vm => Vm::new().run(Compiler::default().compile(&statements)?)?,
+        }
         Ok(())
     }
 
@@ -9,9 +9,13 @@ pub mod ast_printer;
 pub mod depresolver;
 pub mod env;
 pub mod error;
+pub mod exhaustiveness;
+pub mod format;
 pub mod interpreter;
 pub mod lang;
 pub mod mem;
+pub mod number;
+pub mod ordered_map;
 pub mod resolver;
 pub mod syntax;
 pub mod token;
@@ -19,3 +23,4 @@ pub mod type_checker;
 pub mod value;
 pub mod value_traits;
 pub mod visitor;
+pub mod vm;
@@ -1,14 +1,38 @@
+extern crate log;
+
+use crate::env::EnvironmentEntryIndex;
 use crate::error::*;
 use crate::value::*;
 use std::convert::TryInto;
+use std::fmt::{self, Display};
 use std::ops::{Index, IndexMut};
 
-pub type ArenaEntryIndex = usize;
+/// A handle to a value in an [`Arena`]. Slots are reused once freed, so each handle carries the
+/// generation of the slot it was created for and handles to freed values are rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArenaEntryIndex {
+    index: usize,
+    generation: u64,
+}
+
+impl Display for ArenaEntryIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{}", self.index, self.generation)
+    }
+}
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum ArenaEntry<T> {
     Emtpy,
     Occupied(T),
+    /// What a handle to a freed value resolves to
+    Stale,
+}
+
+fn stale_entry_error() -> LangError {
+    LangErrorType::new_iie_error(
+        "tried to use a stale arena index, its value has been freed".into(),
+    )
 }
 
 impl TryInto<TypedValue> for &ArenaEntry<TypedValue> {
@@ -19,6 +43,7 @@ impl TryInto<TypedValue> for &ArenaEntry<TypedValue> {
             ArenaEntry::Emtpy => Err(LangErrorType::new_iie_error(
                 "tried to index an empty arena entry".into(),
             )),
+            ArenaEntry::Stale => Err(stale_entry_error()),
         }
     }
 }
@@ -31,6 +56,7 @@ impl TryInto<TypedValue> for ArenaEntry<TypedValue> {
             ArenaEntry::Emtpy => Err(LangErrorType::new_iie_error(
                 "tried to index an empty arena entry".into(),
             )),
+            ArenaEntry::Stale => Err(stale_entry_error()),
         }
     }
 }
@@ -43,6 +69,7 @@ impl<'a> TryInto<&'a TypedValue> for &'a ArenaEntry<TypedValue> {
             ArenaEntry::Emtpy => Err(LangErrorType::new_iie_error(
                 "tried to index an empty arena entry".into(),
             )),
+            ArenaEntry::Stale => Err(stale_entry_error()),
         }
     }
 }
@@ -55,47 +82,187 @@ impl<'a> TryInto<&'a mut TypedValue> for &'a mut ArenaEntry<TypedValue> {
             ArenaEntry::Emtpy => Err(LangErrorType::new_iie_error(
                 "tried to index an empty arena entry".into(),
             )),
+            ArenaEntry::Stale => Err(stale_entry_error()),
         }
     }
 }
 
+/// Implemented by values stored in an [`Arena`] so the collector can find the entries they refer to
+pub trait Trace {
+    /// Hands every arena entry and environment entry directly referenced by `self` to `tracer`
+    fn trace(&self, tracer: &mut Tracer);
+}
+
+/// The arena entries and environment entries found reachable during a collection that are yet to
+/// be traced themselves
+#[derive(Debug, Default)]
+pub struct Tracer {
+    values: Vec<ArenaEntryIndex>,
+    scopes: Vec<EnvironmentEntryIndex>,
+}
+
+impl Tracer {
+    /// Marks the arena entry `index` as reachable
+    pub fn value(&mut self, index: ArenaEntryIndex) {
+        self.values.push(index);
+    }
+
+    /// Marks the environment entry `env_id` as reachable
+    pub fn scope(&mut self, env_id: EnvironmentEntryIndex) {
+        self.scopes.push(env_id);
+    }
+}
+
+/// Number of allocations made before the first collection is attempted
+const INITIAL_COLLECTION_THRESHOLD: usize = 256;
+
+/// Counters describing the work done by an [`Arena`] and its collector
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArenaStats {
+    /// Total number of values inserted
+    pub allocations: usize,
+    /// Number of insertions that reused a slot from the free list
+    pub reused: usize,
+    /// Number of collections run
+    pub collections: usize,
+    /// Total number of entries freed by collections
+    pub freed: usize,
+}
+
+impl Display for ArenaStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations ({} reused slots), {} collections freeing {} entries",
+            self.allocations, self.reused, self.collections, self.freed
+        )
+    }
+}
+
+/// Storage for values, reclaimed by a tracing mark-and-sweep collector.
+///
+/// Every inserted value is held as a temporary until released with
+/// [`Arena::release_temporaries`], which keeps in-flight values alive while an expression is being
+/// evaluated. Anything else must be reachable from the roots passed to [`Arena::collect`] to
+/// survive a collection. Freed slots are kept on a free list and reused by later insertions
 #[derive(Debug)]
 pub struct Arena<T> {
     entries: Vec<ArenaEntry<T>>,
+    generations: Vec<u64>,
+    free: Vec<usize>,
+    temporaries: Vec<ArenaEntryIndex>,
     len: usize,
-    offset: usize,
+    allocated_since_collection: usize,
+    next_collection: usize,
+    stats: ArenaStats,
+    stale: ArenaEntry<T>,
 }
 
 impl<T> Index<ArenaEntryIndex> for Arena<T> {
     type Output = ArenaEntry<T>;
 
     fn index(&self, arena_id: ArenaEntryIndex) -> &Self::Output {
-        &self.entries[arena_id]
+        if self.is_current(arena_id) {
+            &self.entries[arena_id.index]
+        } else {
+            &self.stale
+        }
     }
 }
 
 impl<T> IndexMut<ArenaEntryIndex> for Arena<T> {
     fn index_mut(&mut self, arena_id: ArenaEntryIndex) -> &mut ArenaEntry<T> {
-        &mut self.entries[arena_id]
+        if self.is_current(arena_id) {
+            &mut self.entries[arena_id.index]
+        } else {
+            self.stale = ArenaEntry::Stale;
+            &mut self.stale
+        }
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Arena<T> {
+        Arena::with_capacity(0)
     }
 }
 
-impl<T: std::fmt::Debug> Arena<T> {
+impl<T> Arena<T> {
     pub fn insert(&mut self, element: T) -> ArenaEntryIndex {
-        let arena_index = self.offset;
-        self.offset += 1;
-        self.entries
-            .insert(arena_index, ArenaEntry::Occupied(element));
+        let index = match self.free.pop() {
+            Some(free_index) => {
+                self.entries[free_index] = ArenaEntry::Occupied(element);
+                self.stats.reused += 1;
+                free_index
+            }
+            None => {
+                self.entries.push(ArenaEntry::Occupied(element));
+                self.generations.push(0);
+                self.entries.len() - 1
+            }
+        };
+        let arena_index = ArenaEntryIndex {
+            index,
+            generation: self.generations[index],
+        };
+        self.temporaries.push(arena_index);
         self.len += 1;
+        self.allocated_since_collection += 1;
+        self.stats.allocations += 1;
         arena_index
     }
 
-    pub fn get(&mut self, index: ArenaEntryIndex) -> ArenaEntry<T> {
-        self.entries.remove(index)
+    /// Whether `index` refers to the value currently held in its slot
+    fn is_current(&self, index: ArenaEntryIndex) -> bool {
+        self.generations.get(index.index) == Some(&index.generation)
+    }
+
+    /// Returns the value at `index`, if it hasn't been freed
+    pub fn get(&self, index: ArenaEntryIndex) -> Option<&T> {
+        match self[index] {
+            ArenaEntry::Occupied(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Frees the value at `index`, making its slot available to later insertions. Every handle
+    /// to the value becomes stale
+    pub fn remove(&mut self, index: ArenaEntryIndex) -> Option<T> {
+        if !self.is_current(index) {
+            return None;
+        }
+        match std::mem::replace(&mut self.entries[index.index], ArenaEntry::Emtpy) {
+            ArenaEntry::Occupied(value) => {
+                self.generations[index.index] += 1;
+                self.free.push(index.index);
+                self.len -= 1;
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Replaces the value at `index`, failing if it has been freed
+    pub fn replace(&mut self, index: ArenaEntryIndex, element: T) -> Result<(), LangError> {
+        match self[index] {
+            ArenaEntry::Occupied(_) => {
+                self.entries[index.index] = ArenaEntry::Occupied(element);
+                Ok(())
+            }
+            ArenaEntry::Stale => Err(stale_entry_error()),
+            ArenaEntry::Emtpy => Err(LangErrorType::new_iie_error(
+                "tried to replace an empty arena entry".into(),
+            )),
+        }
+    }
+
+    /// Number of occupied entries
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    pub fn remove(&mut self, index: ArenaEntryIndex) {
-        self.entries.insert(index, ArenaEntry::Emtpy)
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
     pub fn capacity(&self) -> usize {
@@ -105,8 +272,14 @@ impl<T: std::fmt::Debug> Arena<T> {
     pub fn with_capacity(capacity: usize) -> Arena<T> {
         Arena {
             entries: Vec::with_capacity(capacity),
-            len: capacity,
-            offset: 0,
+            generations: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            temporaries: Vec::new(),
+            len: 0,
+            allocated_since_collection: 0,
+            next_collection: INITIAL_COLLECTION_THRESHOLD,
+            stats: ArenaStats::default(),
+            stale: ArenaEntry::Stale,
         }
     }
 
@@ -119,11 +292,32 @@ impl<T: std::fmt::Debug> Arena<T> {
     }
 
     pub fn new() -> Arena<T> {
-        Arena {
-            entries: Vec::new(),
-            len: 0,
-            offset: 0,
-        }
+        Arena::with_capacity(0)
+    }
+
+    pub fn stats(&self) -> &ArenaStats {
+        &self.stats
+    }
+
+    /// Returns a marker for the current set of temporaries, to be passed to
+    /// [`Arena::release_temporaries`]
+    pub fn temporaries_mark(&self) -> usize {
+        self.temporaries.len()
+    }
+
+    /// Stops treating the values inserted since `mark` was taken as roots
+    pub fn release_temporaries(&mut self, mark: usize) {
+        self.temporaries.truncate(mark);
+    }
+
+    /// Keeps the value at `index` alive until the enclosing temporaries are released
+    pub fn root_temporary(&mut self, index: ArenaEntryIndex) {
+        self.temporaries.push(index);
+    }
+
+    /// Whether enough values have been inserted since the last collection to warrant another
+    pub fn should_collect(&self) -> bool {
+        self.allocated_since_collection >= self.next_collection
     }
 
     pub fn update_entry<Closure>(
@@ -134,16 +328,86 @@ impl<T: std::fmt::Debug> Arena<T> {
     where
         Closure: FnOnce(&mut T) -> Result<(), LangError>,
     {
-        let value_entry = &mut self.entries[index];
-        let value: &mut T = match value_entry {
+        let value: &mut T = match self[index] {
             ArenaEntry::Occupied(ref mut v) => v,
-            _ => panic!(),
+            ArenaEntry::Stale => return Err(stale_entry_error()),
+            ArenaEntry::Emtpy => panic!(),
         };
         closure(value)?;
         Ok(())
     }
 }
 
+impl<T: Trace> Arena<T> {
+    /// Frees every entry that isn't reachable from `roots` or the live temporaries, returning the
+    /// number of entries freed
+    pub fn collect<Roots>(&mut self, roots: Roots) -> usize
+    where
+        Roots: IntoIterator<Item = ArenaEntryIndex>,
+    {
+        let mut tracer = Tracer::default();
+        for root in roots {
+            tracer.value(root);
+        }
+        self.collect_traced(tracer, |_, _| {})
+    }
+
+    /// Frees every entry that isn't reachable from the roots given to `tracer` or the live
+    /// temporaries, returning the number of entries freed. Environment entries are traced by
+    /// `trace_scope` as they're reached
+    pub fn collect_traced<TraceScope>(
+        &mut self,
+        mut tracer: Tracer,
+        mut trace_scope: TraceScope,
+    ) -> usize
+    where
+        TraceScope: FnMut(EnvironmentEntryIndex, &mut Tracer),
+    {
+        let mut marked = vec![false; self.entries.len()];
+        tracer.values.extend(self.temporaries.iter().copied());
+        loop {
+            if let Some(env_id) = tracer.scopes.pop() {
+                trace_scope(env_id, &mut tracer);
+                continue;
+            }
+            let index = match tracer.values.pop() {
+                Some(index) => index,
+                None => break,
+            };
+            if let ArenaEntry::Occupied(ref value) = self[index] {
+                if !marked[index.index] {
+                    marked[index.index] = true;
+                    value.trace(&mut tracer);
+                }
+            }
+        }
+
+        let mut freed = 0;
+        for (index, is_marked) in marked.into_iter().enumerate() {
+            let arena_index = ArenaEntryIndex {
+                index,
+                generation: self.generations[index],
+            };
+            if !is_marked && self.remove(arena_index).is_some() {
+                freed += 1;
+            }
+        }
+        self.allocated_since_collection = 0;
+        self.next_collection = std::cmp::max(INITIAL_COLLECTION_THRESHOLD, self.len);
+        self.stats.collections += 1;
+        self.stats.freed += freed;
+        debug!(
+            "{}:{} Collected {} arena entries, {} live. {}",
+            file!(),
+            line!(),
+            freed,
+            self.len,
+            self.stats
+        );
+        freed
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::mem::*;
@@ -167,4 +431,124 @@ mod tests {
         }
         println!("{:?}", arena);
     }
+
+    /// A node referring to other arena entries
+    #[derive(Debug, PartialEq)]
+    struct Node(Vec<ArenaEntryIndex>);
+
+    impl Trace for Node {
+        fn trace(&self, tracer: &mut Tracer) {
+            for index in self.0.iter() {
+                tracer.value(*index);
+            }
+        }
+    }
+
+    #[test]
+    fn collect_frees_unreachable_entries() {
+        let mut arena: Arena<Node> = Arena::new();
+        let mark = arena.temporaries_mark();
+        let leaf = arena.insert(Node(vec![]));
+        let root = arena.insert(Node(vec![leaf]));
+        let garbage = arena.insert(Node(vec![]));
+        arena.release_temporaries(mark);
+
+        assert_eq!(arena.collect(vec![root]), 1);
+        assert_eq!(arena[garbage], ArenaEntry::Stale);
+        assert_eq!(arena[leaf], ArenaEntry::Occupied(Node(vec![])));
+        assert_eq!(arena.len(), 2);
+        assert_eq!(arena.stats().collections, 1);
+        assert_eq!(arena.stats().freed, 1);
+    }
+
+    #[test]
+    fn collect_handles_cycles() {
+        let mut arena: Arena<Node> = Arena::new();
+        let first = arena.insert(Node(vec![]));
+        let second = arena.insert(Node(vec![first]));
+        arena.replace(first, Node(vec![second])).unwrap();
+        arena.release_temporaries(0);
+
+        assert_eq!(arena.collect(vec![second]), 0);
+        assert_eq!(arena.collect(vec![]), 2);
+        assert!(arena.is_empty());
+    }
+
+    #[test]
+    fn collect_keeps_temporaries() {
+        let mut arena: Arena<Node> = Arena::new();
+        let temporary = arena.insert(Node(vec![]));
+
+        assert_eq!(arena.collect(vec![]), 0);
+        arena.release_temporaries(0);
+        arena.root_temporary(temporary);
+        assert_eq!(arena.collect(vec![]), 0);
+        arena.release_temporaries(0);
+        assert_eq!(arena.collect(vec![]), 1);
+    }
+
+    #[test]
+    fn insert_reuses_freed_slots() {
+        let mut arena: Arena<Node> = Arena::new();
+        for _ in 0..4 {
+            arena.insert(Node(vec![]));
+        }
+        arena.release_temporaries(0);
+        arena.collect(vec![]);
+
+        for _ in 0..4 {
+            arena.insert(Node(vec![]));
+        }
+        assert_eq!(arena.entries().len(), 4);
+        assert_eq!(arena.stats().allocations, 8);
+        assert_eq!(arena.stats().reused, 4);
+    }
+
+    #[test]
+    fn remove_does_not_shift_entries() {
+        let mut arena: Arena<()> = Arena::new();
+        let first = arena.insert(());
+        let second = arena.insert(());
+
+        assert_eq!(arena.remove(first), Some(()));
+        assert_eq!(arena.remove(first), None);
+        assert_eq!(arena.get(first), None);
+        assert_eq!(arena.get(second), Some(&()));
+        arena.insert(());
+        assert_eq!(arena.entries().len(), 2);
+    }
+
+    #[test]
+    fn freed_indices_are_stale() {
+        let mut arena: Arena<Node> = Arena::new();
+        let freed = arena.insert(Node(vec![]));
+        arena.remove(freed);
+        let reused = arena.insert(Node(vec![freed]));
+
+        assert_ne!(freed, reused);
+        assert_eq!(arena[freed], ArenaEntry::Stale);
+        assert_eq!(arena.get(freed), None);
+        assert_eq!(arena.remove(freed), None);
+        assert!(arena.replace(freed, Node(vec![])).is_err());
+        assert_eq!(arena[reused], ArenaEntry::Occupied(Node(vec![freed])));
+
+        let mut values: Arena<TypedValue> = Arena::new();
+        let value = values.insert(TypedValue::default());
+        values.remove(value);
+        let entry: Result<&TypedValue, LangError> = (&values[value]).try_into();
+        assert!(entry.is_err());
+    }
+
+    #[test]
+    fn should_collect_after_threshold() {
+        let mut arena: Arena<Node> = Arena::new();
+        for _ in 0..INITIAL_COLLECTION_THRESHOLD - 1 {
+            arena.insert(Node(vec![]));
+        }
+        assert!(!arena.should_collect());
+        arena.insert(Node(vec![]));
+        assert!(arena.should_collect());
+        arena.collect(vec![]);
+        assert!(!arena.should_collect());
+    }
 }
@@ -0,0 +1,391 @@
+use crate::error::*;
+use crate::format::{FormatArg, FormatKind};
+use crate::token::NumericType;
+
+/// A number of one of the numeric types. The interpreter and the VM each have values of their own,
+/// which they turn into numbers to do arithmetic on and convert them, so that both backends
+/// compute the same results and report the same errors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Number {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    USize(usize),
+    F32(f32),
+    F64(f64),
+}
+
+/// The operators taking two numbers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+}
+
+impl BinaryOperator {
+    fn symbol(self) -> &'static str {
+        match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Remainder => "%",
+            BinaryOperator::BitAnd => "&",
+            BinaryOperator::BitOr => "|",
+            BinaryOperator::BitXor => "^",
+            BinaryOperator::ShiftLeft => "<<",
+            BinaryOperator::ShiftRight => ">>",
+        }
+    }
+
+    /// What an arithmetic operator does, as its errors put it
+    fn operation(self) -> Option<&'static str> {
+        match self {
+            BinaryOperator::Add => Some("add"),
+            BinaryOperator::Subtract => Some("subtract"),
+            BinaryOperator::Multiply => Some("multiply"),
+            BinaryOperator::Divide => Some("divide"),
+            BinaryOperator::Remainder => Some("take the remainder of"),
+            _ => None,
+        }
+    }
+
+    /// The error for operands of types `lhs_type` and `rhs_type` the operator can't be applied to
+    fn invalid_operands(self, lhs_type: &str, rhs_type: &str) -> LangError {
+        let reason = match self.operation() {
+            Some(operation) => format!(
+                "cannot {} values of type {} and {}",
+                operation, lhs_type, rhs_type
+            ),
+            None => format!(
+                "cannot apply '{}' to values of type {} and {}",
+                self.symbol(),
+                lhs_type,
+                rhs_type
+            ),
+        };
+        LangErrorType::new_runtime_error(RuntimeErrorType::InvalidTypeAssignmentError { reason })
+    }
+}
+
+impl Number {
+    pub fn numeric_type(self) -> NumericType {
+        match self {
+            Number::I8(_) => NumericType::I8,
+            Number::I16(_) => NumericType::I16,
+            Number::I32(_) => NumericType::I32,
+            Number::I64(_) => NumericType::I64,
+            Number::U8(_) => NumericType::U8,
+            Number::U16(_) => NumericType::U16,
+            Number::U32(_) => NumericType::U32,
+            Number::U64(_) => NumericType::U64,
+            Number::USize(_) => NumericType::USize,
+            Number::F32(_) => NumericType::F32,
+            Number::F64(_) => NumericType::F64,
+        }
+    }
+
+    /// The value of an integer of any width, which all fit in an i128
+    pub fn integer(self) -> Option<i128> {
+        match self {
+            Number::I8(value) => Some(i128::from(value)),
+            Number::I16(value) => Some(i128::from(value)),
+            Number::I32(value) => Some(i128::from(value)),
+            Number::I64(value) => Some(i128::from(value)),
+            Number::U8(value) => Some(i128::from(value)),
+            Number::U16(value) => Some(i128::from(value)),
+            Number::U32(value) => Some(i128::from(value)),
+            Number::U64(value) => Some(i128::from(value)),
+            Number::USize(value) => Some(value as i128),
+            Number::F32(_) | Number::F64(_) => None,
+        }
+    }
+
+    pub fn float(self) -> Option<f64> {
+        match self {
+            Number::F32(value) => Some(f64::from(value)),
+            Number::F64(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// `value` as an integer of type `numeric_type`, or `None` when it's out of the type's range
+    pub fn from_integer(value: i128, numeric_type: NumericType) -> Option<Number> {
+        let (min, max) = numeric_type.integer_range()?;
+        if value < min || value > max {
+            return None;
+        }
+        Some(Number::convert_integer(value, numeric_type))
+    }
+
+    /// Converts an integer to `numeric_type` the way `as` does, truncating it to narrower integers
+    pub fn convert_integer(value: i128, numeric_type: NumericType) -> Number {
+        match numeric_type {
+            NumericType::I8 => Number::I8(value as i8),
+            NumericType::I16 => Number::I16(value as i16),
+            NumericType::I32 => Number::I32(value as i32),
+            NumericType::I64 => Number::I64(value as i64),
+            NumericType::U8 => Number::U8(value as u8),
+            NumericType::U16 => Number::U16(value as u16),
+            NumericType::U32 => Number::U32(value as u32),
+            NumericType::U64 => Number::U64(value as u64),
+            NumericType::USize => Number::USize(value as usize),
+            NumericType::F32 => Number::F32(value as f32),
+            NumericType::F64 => Number::F64(value as f64),
+        }
+    }
+
+    /// Converts a float to `numeric_type` the way `as` does, rounding it towards zero and
+    /// saturating when converting it to an integer
+    pub fn from_float(value: f64, numeric_type: NumericType) -> Number {
+        match numeric_type {
+            NumericType::I8 => Number::I8(value as i8),
+            NumericType::I16 => Number::I16(value as i16),
+            NumericType::I32 => Number::I32(value as i32),
+            NumericType::I64 => Number::I64(value as i64),
+            NumericType::U8 => Number::U8(value as u8),
+            NumericType::U16 => Number::U16(value as u16),
+            NumericType::U32 => Number::U32(value as u32),
+            NumericType::U64 => Number::U64(value as u64),
+            NumericType::USize => Number::USize(value as usize),
+            NumericType::F32 => Number::F32(value as f32),
+            NumericType::F64 => Number::F64(value),
+        }
+    }
+
+    /// Converts the number to `target` the way `as` does
+    pub fn cast(self, target: NumericType) -> Number {
+        match (self.integer(), self.float()) {
+            (Some(integer), _) => Number::convert_integer(integer, target),
+            (_, float) => Number::from_float(float.unwrap_or_default(), target),
+        }
+    }
+
+    /// Applies `operator` to two operands, either of which may not be a number. The narrower
+    /// number is widened to the type of the wider one, see `NumericType::widens_to`, but integers
+    /// and floats don't mix. A shift gives a number of the type of the shifted one instead, whose
+    /// bits shifted out of it are lost. Integer overflow and division by zero are errors, floats
+    /// follow IEEE 754. `types` names the types of the operands in errors
+    pub fn apply(
+        operator: BinaryOperator,
+        lhs: Option<Number>,
+        rhs: Option<Number>,
+        types: (&str, &str),
+    ) -> Result<Number, LangError> {
+        let (lhs, rhs) = match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => (lhs, rhs),
+            _ => return Err(operator.invalid_operands(types.0, types.1)),
+        };
+        let result = match operator {
+            BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight => {
+                Number::shift(operator, lhs, rhs)
+            }
+            BinaryOperator::BitAnd | BinaryOperator::BitOr | BinaryOperator::BitXor => {
+                Number::bitwise(operator, lhs, rhs)
+            }
+            _ => Number::arithmetic(operator, lhs, rhs),
+        };
+        result.ok_or_else(|| operator.invalid_operands(types.0, types.1))?
+    }
+
+    /// `None` when the operands can't be added, subtracted and so on
+    fn arithmetic(
+        operator: BinaryOperator,
+        lhs: Number,
+        rhs: Number,
+    ) -> Option<Result<Number, LangError>> {
+        let promoted = lhs.numeric_type().promote(rhs.numeric_type())?;
+        // An f32 result is computed as an f64 and rounded, which gives the same result
+        let value = match (lhs.integer(), rhs.integer()) {
+            (Some(lhs), Some(rhs)) => match operator {
+                BinaryOperator::Add => lhs.checked_add(rhs),
+                BinaryOperator::Subtract => lhs.checked_sub(rhs),
+                BinaryOperator::Multiply => lhs.checked_mul(rhs),
+                BinaryOperator::Divide => lhs.checked_div(rhs),
+                _ => lhs.checked_rem(rhs),
+            }
+            .and_then(|value| Number::from_integer(value, promoted)),
+            _ => {
+                let (lhs, rhs) = (lhs.float()?, rhs.float()?);
+                let value = match operator {
+                    BinaryOperator::Add => lhs + rhs,
+                    BinaryOperator::Subtract => lhs - rhs,
+                    BinaryOperator::Multiply => lhs * rhs,
+                    BinaryOperator::Divide => lhs / rhs,
+                    _ => lhs % rhs,
+                };
+                Some(Number::from_float(value, promoted))
+            }
+        };
+        Some(value.ok_or_else(|| {
+            let reason = match (operator, rhs.integer()) {
+                (BinaryOperator::Divide, Some(0)) => "attempt to divide by zero".to_string(),
+                (BinaryOperator::Remainder, Some(0)) => {
+                    "attempt to calculate the remainder with a divisor of zero".to_string()
+                }
+                _ => format!(
+                    "attempt to {} with overflow",
+                    operator.operation().unwrap_or_default()
+                ),
+            };
+            LangErrorType::new_runtime_error(RuntimeErrorType::ArithmeticError { reason })
+        }))
+    }
+
+    /// `None` unless both operands are integers, the narrower one is widened like `arithmetic`
+    fn bitwise(
+        operator: BinaryOperator,
+        lhs: Number,
+        rhs: Number,
+    ) -> Option<Result<Number, LangError>> {
+        let promoted = lhs
+            .numeric_type()
+            .promote(rhs.numeric_type())
+            .filter(|promoted| promoted.is_integer())?;
+        let (lhs, rhs) = (lhs.integer()?, rhs.integer()?);
+        let value = match operator {
+            BinaryOperator::BitAnd => lhs & rhs,
+            BinaryOperator::BitOr => lhs | rhs,
+            _ => lhs ^ rhs,
+        };
+        // Both operands fit the promoted type, and so does any of their bitwise combinations
+        Some(Ok(Number::convert_integer(value, promoted)))
+    }
+
+    /// `None` unless an integer is shifted by an integer of any type
+    fn shift(
+        operator: BinaryOperator,
+        lhs: Number,
+        rhs: Number,
+    ) -> Option<Result<Number, LangError>> {
+        let (value, amount) = (lhs.integer()?, rhs.integer()?);
+        let numeric_type = lhs.numeric_type();
+        let left = operator == BinaryOperator::ShiftLeft;
+        let amount = match numeric_type.shift_amount(amount) {
+            Some(amount) => amount,
+            None => {
+                return Some(Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::ArithmeticError {
+                        reason: format!(
+                            "attempt to shift {} with overflow",
+                            if left { "left" } else { "right" }
+                        ),
+                    },
+                )))
+            }
+        };
+        let value = if left {
+            value << amount
+        } else {
+            value >> amount
+        };
+        Some(Ok(Number::convert_integer(value, numeric_type)))
+    }
+
+    /// The `-` operator on a value of the type `value_type`, which may not be a number
+    pub fn negate(number: Option<Number>, value_type: &str) -> Result<Number, LangError> {
+        let number = number.ok_or_else(|| {
+            LangErrorType::new_runtime_error(RuntimeErrorType::InvalidTypeAssignmentError {
+                reason: format!("cannot negate a value of type {}", value_type),
+            })
+        })?;
+        let negated = match (number.integer(), number.float()) {
+            (Some(integer), _) => Number::from_integer(-integer, number.numeric_type()),
+            (_, float) => Some(Number::from_float(
+                -float.unwrap_or_default(),
+                number.numeric_type(),
+            )),
+        };
+        negated.ok_or_else(|| {
+            LangErrorType::new_runtime_error(RuntimeErrorType::ArithmeticError {
+                reason: "attempt to negate with overflow".to_string(),
+            })
+        })
+    }
+
+    /// Bitwise not, the `~` operator, on a value of the type `value_type`
+    pub fn bit_not(number: Option<Number>, value_type: &str) -> Result<Number, LangError> {
+        number
+            .and_then(|number| {
+                Some(Number::convert_integer(
+                    !number.integer()?,
+                    number.numeric_type(),
+                ))
+            })
+            .ok_or_else(|| {
+                LangErrorType::new_runtime_error(RuntimeErrorType::InvalidTypeAssignmentError {
+                    reason: format!("cannot apply '~' to a value of type {}", value_type),
+                })
+            })
+    }
+
+    /// The number as an argument of a format string, whose spec is of the kind `kind`
+    pub fn format_arg(self, kind: FormatKind) -> FormatArg {
+        match self {
+            Number::F32(float) if kind == FormatKind::Debug => {
+                FormatArg::Float(f64::from(float), format!("{:?}", float))
+            }
+            Number::F64(float) if kind == FormatKind::Debug => {
+                FormatArg::Float(float, format!("{:?}", float))
+            }
+            Number::F32(float) => FormatArg::Float(f64::from(float), float.to_string()),
+            Number::F64(float) => FormatArg::Float(float, float.to_string()),
+            integer => FormatArg::Integer(
+                integer.integer().unwrap_or_default(),
+                integer.numeric_type(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(operator: BinaryOperator, lhs: Number, rhs: Number) -> Result<Number, LangError> {
+        Number::apply(operator, Some(lhs), Some(rhs), ("", ""))
+    }
+
+    #[test]
+    fn widens_the_narrower_operand() {
+        let sum = apply(BinaryOperator::Add, Number::I8(1), Number::I32(2));
+        assert_eq!(sum.ok(), Some(Number::I32(3)));
+        let product = apply(BinaryOperator::Multiply, Number::F32(1.5), Number::F64(2.0));
+        assert_eq!(product.ok(), Some(Number::F64(3.0)));
+        assert!(apply(BinaryOperator::Add, Number::I32(1), Number::F64(2.0)).is_err());
+    }
+
+    #[test]
+    fn checks_integer_overflow() {
+        assert!(apply(BinaryOperator::Add, Number::U8(255), Number::U8(1)).is_err());
+        assert!(apply(BinaryOperator::Divide, Number::I32(1), Number::I32(0)).is_err());
+        assert!(apply(BinaryOperator::ShiftLeft, Number::I8(1), Number::I64(8)).is_err());
+        let shifted = apply(BinaryOperator::ShiftLeft, Number::I8(1), Number::I64(7));
+        assert_eq!(shifted.ok(), Some(Number::I8(i8::MIN)));
+        assert!(Number::negate(Some(Number::I8(i8::MIN)), "i8").is_err());
+    }
+
+    #[test]
+    fn casts_like_as() {
+        assert_eq!(Number::I32(300).cast(NumericType::U8), Number::U8(44));
+        assert_eq!(Number::F64(-1.5).cast(NumericType::U8), Number::U8(0));
+        assert_eq!(
+            Number::F64(1e10).cast(NumericType::I32),
+            Number::I32(i32::MAX)
+        );
+        assert_eq!(Number::U8(7).cast(NumericType::F32), Number::F32(7.0));
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The entries of a map, kept in the order their keys were first inserted so that maps print and
+/// iterate the same way on every run. Each backend looks its entries up by a `K` made from their
+/// keys, which compares the way the keys of a map are compared
+#[derive(Clone, Debug)]
+pub struct OrderedMap<K, E, V> {
+    entries: Vec<(E, V)>,
+    /// The position of each key's entry in `entries`
+    positions: HashMap<K, usize>,
+}
+
+impl<K, E, V> Default for OrderedMap<K, E, V> {
+    fn default() -> OrderedMap<K, E, V> {
+        OrderedMap {
+            entries: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, E, V> OrderedMap<K, E, V> {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.positions
+            .get(key)
+            .map(|position| &self.entries[*position].1)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    /// Sets the value of `key`, which is looked up by `lookup_key`. A key that's already in the
+    /// map keeps its position
+    pub fn insert(&mut self, key: E, lookup_key: K, value: V) {
+        match self.positions.get(&lookup_key) {
+            Some(position) => self.entries[*position].1 = value,
+            None => {
+                self.positions.insert(lookup_key, self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let removed = self.positions.remove(key)?;
+        for position in self.positions.values_mut() {
+            if *position > removed {
+                *position -= 1;
+            }
+        }
+        Some(self.entries.remove(removed).1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(E, V)> {
+        self.entries.iter()
+    }
+
+    /// The value of each entry along with the key it's looked up by, in no particular order
+    pub fn lookup_iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.positions
+            .iter()
+            .map(move |(key, position)| (key, &self.entries[*position].1))
+    }
+}
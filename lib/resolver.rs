@@ -15,13 +15,21 @@ use std::collections::HashMap;
 enum FunctionType {
     None,
     Function,
+    Method,
 }
 
 #[derive(Debug)]
 pub struct Resolver<'a> {
     pub interpreter: &'a mut Interpreter,
     scopes: Vec<HashMap<String, bool>>,
+    /// For each scope begun, the number of scopes declarations shadowing a variable of it opened
+    shadowing_scopes: Vec<usize>,
     current_function_type: FunctionType,
+    /// The scope of the innermost impl, methods get `self` bound in it when they're called
+    self_scope: Option<usize>,
+    /// Labels of the loops around the statement being resolved, innermost last. A function body
+    /// starts outside of any loop
+    loops: Vec<Option<String>>,
 }
 
 impl<'a> Resolver<'a> {
@@ -31,7 +39,10 @@ impl<'a> Resolver<'a> {
         Resolver {
             interpreter,
             scopes,
+            shadowing_scopes: vec![0],
             current_function_type: FunctionType::None,
+            self_scope: None,
+            loops: Vec::new(),
         }
     }
 
@@ -52,11 +63,14 @@ impl<'a> Resolver<'a> {
 
     fn begin_scope(&mut self) {
         self.scopes.push(HashMap::new());
+        self.shadowing_scopes.push(0);
         debug!("{}:{} Begin_scope: {:?}", file!(), line!(), self.scopes);
     }
 
     fn end_scope(&mut self) {
-        self.scopes.pop();
+        let shadowing_scopes = self.shadowing_scopes.pop().unwrap_or_default();
+        let len = self.scopes.len() - shadowing_scopes - 1;
+        self.scopes.truncate(len);
         debug!("{}:{} End_scope: {:?}", file!(), line!(), self.scopes);
     }
 
@@ -94,6 +108,49 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    /// Declares and defines every identifier bound by `pattern` in the current scope
+    fn define_pattern(&mut self, pattern: &Pattern) -> Result<(), LangError> {
+        match pattern {
+            Pattern::Binding(name) => {
+                self.declare(name)?;
+                self.define(name);
+            }
+            Pattern::EnumVariant(variant) => {
+                for field in variant.fields.iter() {
+                    self.define_pattern(field)?;
+                }
+            }
+            Pattern::Wildcard | Pattern::Literal(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Tells the interpreter how many scopes up from its use `name` is defined. Names that aren't
+    /// in any scope yet, like functions declared further down, are left for a dynamic lookup
+    fn resolve_local(&mut self, id: NodeId, name: &str) {
+        if let Some(position) = self
+            .scopes
+            .iter()
+            .rev()
+            .position(|scope| scope.contains_key(name))
+        {
+            self.interpreter.resolve(id, position);
+        }
+    }
+
+    /// Resolves the methods of an impl, they aren't bound by name in the enclosing scope
+    fn resolve_methods(&mut self, fn_declarations: &[Stmt]) -> Result<(), LangError> {
+        let enclosing = self.self_scope;
+        self.self_scope = Some(self.scopes.len() - 1);
+        for fn_declaration in fn_declarations {
+            if let Stmt::Function(function_stmt) = fn_declaration {
+                self.resolve_function(function_stmt, FunctionType::Method)?;
+            }
+        }
+        self.self_scope = enclosing;
+        Ok(())
+    }
+
     fn resolve_function(
         &mut self,
         function: &FunctionStmt,
@@ -101,6 +158,7 @@ impl<'a> Resolver<'a> {
     ) -> Result<(), LangError> {
         let enclosing = self.current_function_type.clone();
         self.current_function_type = fn_type;
+        let enclosing_loops = std::mem::take(&mut self.loops);
         self.begin_scope();
         for param in &function.params {
             self.declare(&param.identifier)?;
@@ -110,8 +168,36 @@ impl<'a> Resolver<'a> {
         self.resolve(&function.body)?;
         self.end_scope();
         self.current_function_type = enclosing;
+        self.loops = enclosing_loops;
         Ok(())
     }
+
+    /// Resolves the body of a loop labelled `label`
+    fn resolve_loop_body(
+        &mut self,
+        label: &Option<String>,
+        body: &[Stmt],
+    ) -> Result<(), LangError> {
+        self.loops.push(label.clone());
+        let result = self.resolve(body);
+        self.loops.pop();
+        result
+    }
+
+    /// Checks that a `break` or `continue` is in a loop, and in one named `label` if it's given
+    fn check_loop_target(&self, keyword: &str, label: &Option<String>) -> Result<(), LangError> {
+        let reason = match label {
+            None if self.loops.is_empty() => format!("tried to {} outside of a loop", keyword),
+            Some(label) if !self.loops.contains(&Some(label.clone())) => format!(
+                "tried to {} to the loop labeled {}, which doesn't enclose it",
+                keyword, label
+            ),
+            _ => return Ok(()),
+        };
+        Err(LangErrorType::new_runtime_error(
+            RuntimeErrorType::ResolutionError { reason },
+        ))
+    }
 }
 
 impl<'a> VisitorMut<()> for Resolver<'a> {
@@ -124,6 +210,7 @@ impl<'a> VisitorMut<()> for Resolver<'a> {
 
     fn visit_assign(&mut self, assign: &AssignExpr) -> Result<(), LangError> {
         self.resolve_expr(&assign.expr)?;
+        self.resolve_local(assign.id, &assign.name);
         Ok(())
     }
     fn visit_binary(&mut self, binary: &BinaryExpr) -> Result<(), LangError> {
@@ -143,6 +230,16 @@ impl<'a> VisitorMut<()> for Resolver<'a> {
     fn visit_enum_path(&mut self, _: &EnumPathExpr) -> Result<(), LangError> {
         Ok(())
     }
+    fn visit_match(&mut self, match_expr: &MatchExpr) -> Result<(), LangError> {
+        self.resolve_expr(&match_expr.scrutinee)?;
+        for arm in match_expr.arms.iter() {
+            self.begin_scope();
+            self.define_pattern(&arm.pattern)?;
+            self.resolve_statement(&arm.body)?;
+            self.end_scope();
+        }
+        Ok(())
+    }
     fn visit_grouping(&mut self, grouping: &GroupingExpr) -> Result<(), LangError> {
         Ok(self.resolve_expr(&grouping.expression)?)
     }
@@ -166,6 +263,72 @@ impl<'a> VisitorMut<()> for Resolver<'a> {
         }
         Ok(())
     }
+    fn visit_map(&mut self, map: &MapExpr) -> Result<(), LangError> {
+        for (key, value) in map.entries.iter() {
+            self.resolve_expr(key)?;
+            self.resolve_expr(value)?;
+        }
+        Ok(())
+    }
+    fn visit_tuple(&mut self, tuple: &TupleExpr) -> Result<(), LangError> {
+        for element in tuple.elements.iter() {
+            self.resolve_expr(element)?;
+        }
+        Ok(())
+    }
+    fn visit_struct_literal(
+        &mut self,
+        struct_literal: &StructLiteralExpr,
+    ) -> Result<(), LangError> {
+        for (_, value) in struct_literal.fields.iter() {
+            self.resolve_expr(value)?;
+        }
+        Ok(())
+    }
+    fn visit_try(&mut self, try_expr: &TryExpr) -> Result<(), LangError> {
+        if self.current_function_type == FunctionType::None {
+            return Err(Lang::error_s("?", "Cannot use '?' in top-level code"));
+        }
+        self.resolve_expr(&try_expr.expression)
+    }
+    fn visit_cast(&mut self, cast: &CastExpr) -> Result<(), LangError> {
+        self.resolve_expr(&cast.expression)
+    }
+    fn visit_block_expr(&mut self, block: &BlockExpr) -> Result<(), LangError> {
+        self.begin_scope();
+        self.resolve(&block.statements)?;
+        if let Some(ref value) = block.value {
+            self.resolve_expr(value)?;
+        }
+        self.end_scope();
+        Ok(())
+    }
+    fn visit_loop(&mut self, loop_expr: &LoopExpr) -> Result<(), LangError> {
+        self.begin_scope();
+        let result = self.resolve_loop_body(&loop_expr.label, &loop_expr.body);
+        self.end_scope();
+        result
+    }
+    fn visit_range(&mut self, range: &RangeExpr) -> Result<(), LangError> {
+        self.resolve_expr(&range.start)?;
+        self.resolve_expr(&range.end)
+    }
+    fn visit_format_string(&mut self, format_string: &FormatStringExpr) -> Result<(), LangError> {
+        for piece in &format_string.pieces {
+            if let FormatPiece::Argument(argument) = piece {
+                self.resolve_expr(&argument.value)?;
+            }
+        }
+        Ok(())
+    }
+    fn visit_if_expr(&mut self, if_expr: &IfExpr) -> Result<(), LangError> {
+        self.resolve_expr(&if_expr.condition)?;
+        self.visit_block_expr(&if_expr.then_branch)?;
+        if let Some(ref else_branch) = if_expr.else_branch {
+            self.resolve_expr(else_branch)?;
+        }
+        Ok(())
+    }
     fn visit_index(&mut self, index: &IndexExpr) -> Result<(), LangError> {
         Ok(self.resolve_expr(&index.index)?)
     }
@@ -183,31 +346,68 @@ impl<'a> VisitorMut<()> for Resolver<'a> {
                 }
             }
         }
+        self.resolve_local(variable.id, &variable.name);
         Ok(())
     }
-    fn visit_self_ident(&mut self, _: &SelfIdentExpr) -> Result<(), LangError> {
+    fn visit_self_ident(&mut self, self_ident: &SelfIdentExpr) -> Result<(), LangError> {
+        // Methods taking `self` as a parameter shadow the one bound in the impl's scope
+        let parameter = self
+            .scopes
+            .iter()
+            .rev()
+            .position(|scope| scope.contains_key(&self_ident.keyword));
+        match (parameter, self.self_scope) {
+            (Some(distance), _) => self.interpreter.resolve(self_ident.id, distance),
+            (None, Some(self_scope)) => self
+                .interpreter
+                .resolve(self_ident.id, self.scopes.len() - 1 - self_scope),
+            (None, None) => {}
+        }
         Ok(())
     }
 
     // stmt
-    fn visit_break(&mut self) -> Result<(), LangError> {
+    fn visit_break(&mut self, break_stmt: &BreakStmt) -> Result<(), LangError> {
+        self.check_loop_target("break", &break_stmt.label)?;
+        if let Some(ref value) = break_stmt.value {
+            self.resolve_expr(value)?;
+        }
         Ok(())
     }
+    fn visit_continue(&mut self, continue_stmt: &ContinueStmt) -> Result<(), LangError> {
+        self.check_loop_target("continue", &continue_stmt.label)
+    }
     fn visit_assert(&mut self, assert_stmt: &AssertStmt) -> Result<(), LangError> {
         self.resolve_expr(&assert_stmt.condition)?;
         Ok(())
     }
-    fn visit_enum(&mut self, _: &EnumStmt) -> Result<(), LangError> {
-        unimplemented!()
-    }
-    fn visit_impl(&mut self, impl_stmt: &ImplStmt) -> Result<(), LangError> {
-        for fn_decl_statement in &impl_stmt.fn_declarations {
-            self.resolve_statement(&fn_decl_statement)?;
+    fn visit_enum(&mut self, enum_stmt: &EnumStmt) -> Result<(), LangError> {
+        self.declare(&enum_stmt.name)?;
+        let mut variants = Vec::new();
+        for item in enum_stmt.item_list.iter() {
+            if variants.contains(&&item.identifier) {
+                return Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::ResolutionError {
+                        reason: format!(
+                            "enum '{}' declares the variant '{}' more than once",
+                            enum_stmt.name, item.identifier
+                        ),
+                    },
+                ));
+            }
+            variants.push(&item.identifier);
+            if let Some(ref initializer) = item.initializer {
+                self.resolve_expr(initializer)?;
+            }
         }
+        self.define(&enum_stmt.name);
         Ok(())
     }
-    fn visit_impl_trait(&mut self, _: &ImplTraitStmt) -> Result<(), LangError> {
-        Ok(())
+    fn visit_impl(&mut self, impl_stmt: &ImplStmt) -> Result<(), LangError> {
+        self.resolve_methods(&impl_stmt.fn_declarations)
+    }
+    fn visit_impl_trait(&mut self, impl_trait: &ImplTraitStmt) -> Result<(), LangError> {
+        self.resolve_methods(&impl_trait.fn_declarations)
     }
     fn visit_block(&mut self, block: &BlockStmt) -> Result<(), LangError> {
         self.begin_scope();
@@ -229,14 +429,12 @@ impl<'a> VisitorMut<()> for Resolver<'a> {
         Ok(self.resolve_expr(&expr.expression)?)
     }
     fn visit_trait(&mut self, trait_stmt: &TraitStmt) -> Result<(), LangError> {
-        for fn_declarations in &trait_stmt.trait_fn_declarations {
-            self.resolve_statement(&fn_declarations)?;
-        }
+        self.declare(&trait_stmt.name)?;
+        self.define(&trait_stmt.name);
         Ok(())
     }
-    fn visit_trait_function(&mut self, trait_fn_stmt: &TraitFunctionStmt) -> Result<(), LangError> {
-        self.declare(&trait_fn_stmt.name)?;
-        self.define(&trait_fn_stmt.name);
+    // Trait functions are only signatures, they're not bound by name anywhere
+    fn visit_trait_function(&mut self, _: &TraitFunctionStmt) -> Result<(), LangError> {
         Ok(())
     }
     fn visit_function(&mut self, function_stmt: &FunctionStmt) -> Result<(), LangError> {
@@ -273,6 +471,27 @@ impl<'a> VisitorMut<()> for Resolver<'a> {
         Ok(())
     }
     fn visit_var(&mut self, var_stmt: &VarStmt) -> Result<(), LangError> {
+        // A variable declared again in the scope of a function or block is a new variable, which
+        // functions closing over the first one don't see. The rest of the scope is resolved in a
+        // scope of its own, where the initializer can't refer to the new variable yet
+        let shadows = self.scopes.len() > 1
+            && self
+                .scopes
+                .last()
+                .is_some_and(|scope| scope.contains_key(&var_stmt.name));
+        if shadows {
+            if let Some(ref initializer) = var_stmt.initializer {
+                self.resolve_expr(initializer)?;
+            }
+            self.scopes.push(HashMap::new());
+            if let Some(shadowing_scopes) = self.shadowing_scopes.last_mut() {
+                *shadowing_scopes += 1;
+            }
+            self.interpreter.shadow(var_stmt.id);
+            self.declare(&var_stmt.name)?;
+            self.define(&var_stmt.name);
+            return Ok(());
+        }
         self.declare(&var_stmt.name)?;
         if let Some(ref initializer) = var_stmt.initializer {
             self.resolve_expr(initializer)?;
@@ -280,11 +499,34 @@ impl<'a> VisitorMut<()> for Resolver<'a> {
         self.define(&var_stmt.name);
         Ok(())
     }
+    fn visit_destructure(&mut self, destructure_stmt: &DestructureStmt) -> Result<(), LangError> {
+        for name in destructure_stmt.names.iter() {
+            self.declare(name)?;
+        }
+        self.resolve_expr(&destructure_stmt.initializer)?;
+        for name in destructure_stmt.names.iter() {
+            self.define(name);
+        }
+        Ok(())
+    }
     fn visit_while(&mut self, while_stmt: &WhileStmt) -> Result<(), LangError> {
         self.resolve_expr(&while_stmt.condition)?;
-        self.resolve_statement(&while_stmt.body)?;
+        self.resolve_loop_body(&while_stmt.label, std::slice::from_ref(&while_stmt.body))?;
+        if let Some(ref increment) = while_stmt.increment {
+            self.resolve_expr(increment)?;
+        }
         Ok(())
     }
+    fn visit_for_in(&mut self, for_in: &ForInStmt) -> Result<(), LangError> {
+        self.resolve_expr(&for_in.iterable)?;
+        // Each iteration binds the loop variable in the scope of the body
+        self.begin_scope();
+        self.declare(&for_in.variable)?;
+        self.define(&for_in.variable);
+        let result = self.resolve_loop_body(&for_in.label, &for_in.body);
+        self.end_scope();
+        result
+    }
     fn visit_import(&mut self, _: &ImportStmt) -> Result<(), LangError> {
         Ok(())
     }
@@ -1,15 +1,29 @@
 use crate::ast::expr::*;
 use crate::ast::stmt::*;
 use crate::error::*;
+use crate::format::FormatSpec;
 use crate::lang::Lang;
+use crate::syntax::scanner::literal_suffix;
+use crate::syntax::span::SourceLocation;
 use crate::syntax::token::Token;
-use crate::token::{TokenType, TypeAnnotation};
+use crate::token::{NumericType, TokenType, TypeAnnotation};
 use crate::value::{TypedValue, Value};
+use std::collections::HashMap;
 
 pub struct Parser<'a> {
     source_lines: Vec<&'a str>,
     tokens: Vec<Token<'a>>,
     cursor_position: usize,
+    /// Type parameters of the generic declarations being parsed, the innermost declaration's
+    /// are last
+    type_params: Vec<String>,
+    /// Return types of the functions being parsed, the innermost function's is last
+    return_types: Vec<TypeAnnotation>,
+    /// The structs declared anywhere in the source, whose field types give the values of struct
+    /// literals their types
+    structs: HashMap<String, StructStmt>,
+    /// The type `Self` stands for in the impl being parsed
+    self_type: Option<TypeAnnotation>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -18,6 +32,7 @@ pub struct TokenIR {
     pub lexeme: String,
     pub value: Value,
     pub line: u32,
+    pub column: u32,
     pub offset: usize,
 }
 
@@ -28,9 +43,24 @@ impl TokenIR {
             lexeme: token.span.content.input.to_string(),
             value: token.value.clone(),
             line: token.span.begin.line,
+            column: token.span.begin.column,
             offset: token.span.begin.offset,
         }
     }
+
+    fn location(&self) -> SourceLocation {
+        SourceLocation::new(self.line, self.column + 1)
+    }
+}
+
+/// The built-in enum whose variant `variant` can be written without a path, as in `Some(1)` for
+/// `Option::Some(1)`
+fn prelude_enum(variant: &str) -> Option<&'static str> {
+    match variant {
+        "Some" | "None" => Some("Option"),
+        "Ok" | "Err" => Some("Result"),
+        _ => None,
+    }
 }
 
 impl<'a> Parser<'a> {
@@ -39,6 +69,10 @@ impl<'a> Parser<'a> {
             source_lines: source.split('\n').collect(),
             tokens,
             cursor_position: 0,
+            type_params: Vec::new(),
+            return_types: Vec::new(),
+            structs: HashMap::new(),
+            self_type: None,
         }
     }
 
@@ -53,6 +87,7 @@ impl<'a> Parser<'a> {
             let right = self.equality()?;
             expr = Expr::Logical(Box::new(LogicalExpr {
                 left: expr,
+                location: operator.location(),
                 operator: operator.token_type,
                 right,
             }));
@@ -67,6 +102,7 @@ impl<'a> Parser<'a> {
             let right = self.and()?;
             expr = Expr::Logical(Box::new(LogicalExpr {
                 left: expr,
+                location: operator.location(),
                 operator: operator.token_type,
                 right,
             }));
@@ -75,44 +111,91 @@ impl<'a> Parser<'a> {
     }
 
     fn assignment(&mut self) -> Result<Expr, LangError> {
-        let expr = self.or()?;
+        let expr = self.range()?;
         if self.matches(&[TokenType::Equal]) {
             // Use equals for errors!
             let equals = self.previous();
             let value = self.assignment()?;
-            match expr {
-                Expr::Variable(variable_expr) => {
-                    return Ok(Expr::Assign(Box::new(AssignExpr {
-                        name: variable_expr.name,
-                        expr: value,
-                    })));
-                }
-                Expr::Get(get_expr) => {
-                    return Ok(Expr::Set(Box::new(SetExpr {
-                        name: get_expr.name.clone(),
-                        object: get_expr.object,
-                        value,
-                    })));
-                }
-                Expr::Index(index_expr) => {
-                    return Ok(Expr::SetArrayElement(Box::new(SetArrayElementExpr {
-                        name: index_expr.from.clone(),
-                        index: index_expr.index,
-                        value,
-                    })));
-                }
-                _ => {
-                    return Err(Lang::error_ir(
-                        equals.line,
-                        &equals.lexeme,
-                        "Invalid assignment target",
-                    ));
-                }
-            };
+            return self.assignment_target(expr, value, None, &equals);
+        }
+        if let Some(operator) = self.peek().token_type.compound_operator() {
+            let equals = self.advance();
+            let value = self.assignment()?;
+            return self.assignment_target(expr, value, Some(operator), &equals);
         }
         Ok(expr)
     }
 
+    /// A range binds looser than the other operators, so `0..n + 1` ends at `n + 1`. Ranges don't
+    /// chain, `a..b..c` isn't an expression
+    fn range(&mut self) -> Result<Expr, LangError> {
+        let start = self.or()?;
+        if !self.matches(&[TokenType::DotDot, TokenType::DotDotEqual]) {
+            return Ok(start);
+        }
+        let operator = self.previous();
+        let end = self.or()?;
+        Ok(Expr::Range(Box::new(RangeExpr {
+            start,
+            end,
+            inclusive: operator.token_type == TokenType::DotDotEqual,
+            location: operator.location(),
+        })))
+    }
+
+    /// Assigns `value` to `target`, which must be a variable, a struct field or an array element
+    /// Builds the assignment of `value` to `target`, combining the two with `operator` for a
+    /// compound assignment such as `+=`
+    fn assignment_target(
+        &self,
+        target: Expr,
+        value: Expr,
+        operator: Option<TokenType>,
+        equals: &TokenIR,
+    ) -> Result<Expr, LangError> {
+        match target {
+            Expr::Variable(variable_expr) => {
+                // `a += b` is sugar for `a = a + b`, reading a variable twice has no side effects.
+                // Fields and elements are combined in place, so that their object and index are
+                // only evaluated once
+                let value = match operator {
+                    Some(operator) => Expr::Binary(Box::new(BinaryExpr {
+                        left: Expr::Variable(variable_expr.clone()),
+                        location: equals.location(),
+                        operator,
+                        right: value,
+                    })),
+                    None => value,
+                };
+                Ok(Expr::Assign(Box::new(AssignExpr {
+                    id: NodeId::next(),
+                    name: variable_expr.name,
+                    expr: value,
+                    location: variable_expr.location,
+                })))
+            }
+            Expr::Get(get_expr) => Ok(Expr::Set(Box::new(SetExpr {
+                name: get_expr.name.clone(),
+                object: get_expr.object,
+                value,
+                operator,
+                location: get_expr.location,
+            }))),
+            Expr::Index(index_expr) => Ok(Expr::SetArrayElement(Box::new(SetArrayElementExpr {
+                name: index_expr.from.clone(),
+                index: index_expr.index,
+                value,
+                operator,
+                location: index_expr.location,
+            }))),
+            _ => Err(Lang::error_ir(
+                equals.line,
+                &equals.lexeme,
+                "Invalid assignment target",
+            )),
+        }
+    }
+
     fn equality(&mut self) -> Result<Expr, LangError> {
         let mut expression = self.comparison()?;
 
@@ -121,6 +204,7 @@ impl<'a> Parser<'a> {
             let right = self.comparison()?;
             expression = Expr::Binary(Box::new(BinaryExpr {
                 left: expression,
+                location: operator.location(),
                 operator: operator.token_type,
                 right,
             }));
@@ -129,17 +213,80 @@ impl<'a> Parser<'a> {
     }
 
     fn comparison(&mut self) -> Result<Expr, LangError> {
-        let mut expr = self.addition()?;
+        let mut expr = self.bit_or()?;
         while self.matches(&[
             TokenType::Greater,
             TokenType::GreaterEqual,
             TokenType::Less,
             TokenType::LessEqual,
         ]) {
+            let operator = self.previous();
+            let right = self.bit_or()?;
+            expr = Expr::Binary(Box::new(BinaryExpr {
+                left: expr,
+                location: operator.location(),
+                operator: operator.token_type,
+                right,
+            }));
+        }
+        Ok(expr)
+    }
+
+    /// The bitwise operators bind tighter than the comparisons, like in Rust, so
+    /// `flags & MASK == 0` tests the masked bits
+    fn bit_or(&mut self) -> Result<Expr, LangError> {
+        let mut expr = self.bit_xor()?;
+        while self.matches(&[TokenType::Pipe]) {
+            let operator = self.previous();
+            let right = self.bit_xor()?;
+            expr = Expr::Binary(Box::new(BinaryExpr {
+                left: expr,
+                location: operator.location(),
+                operator: operator.token_type,
+                right,
+            }));
+        }
+        Ok(expr)
+    }
+
+    fn bit_xor(&mut self) -> Result<Expr, LangError> {
+        let mut expr = self.bit_and()?;
+        while self.matches(&[TokenType::Caret]) {
+            let operator = self.previous();
+            let right = self.bit_and()?;
+            expr = Expr::Binary(Box::new(BinaryExpr {
+                left: expr,
+                location: operator.location(),
+                operator: operator.token_type,
+                right,
+            }));
+        }
+        Ok(expr)
+    }
+
+    fn bit_and(&mut self) -> Result<Expr, LangError> {
+        let mut expr = self.shift()?;
+        while self.matches(&[TokenType::Ampersand]) {
+            let operator = self.previous();
+            let right = self.shift()?;
+            expr = Expr::Binary(Box::new(BinaryExpr {
+                left: expr,
+                location: operator.location(),
+                operator: operator.token_type,
+                right,
+            }));
+        }
+        Ok(expr)
+    }
+
+    fn shift(&mut self) -> Result<Expr, LangError> {
+        let mut expr = self.addition()?;
+        while self.matches(&[TokenType::LessLess, TokenType::GreaterGreater]) {
             let operator = self.previous();
             let right = self.addition()?;
             expr = Expr::Binary(Box::new(BinaryExpr {
                 left: expr,
+                location: operator.location(),
                 operator: operator.token_type,
                 right,
             }));
@@ -154,6 +301,7 @@ impl<'a> Parser<'a> {
             let right = self.multiplication()?;
             expr = Expr::Binary(Box::new(BinaryExpr {
                 left: expr,
+                location: operator.location(),
                 operator: operator.token_type,
                 right,
             }));
@@ -162,12 +310,13 @@ impl<'a> Parser<'a> {
     }
 
     fn multiplication(&mut self) -> Result<Expr, LangError> {
-        let mut expr = self.unary()?;
-        while self.matches(&[TokenType::Slash, TokenType::Star]) {
+        let mut expr = self.cast()?;
+        while self.matches(&[TokenType::Slash, TokenType::Star, TokenType::Percent]) {
             let operator = self.previous();
-            let right = self.unary()?;
+            let right = self.cast()?;
             expr = Expr::Binary(Box::new(BinaryExpr {
                 left: expr,
+                location: operator.location(),
                 operator: operator.token_type,
                 right,
             }));
@@ -175,11 +324,40 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
+    /// Casts bind tighter than the binary operators but looser than the unary ones, so
+    /// `-a as i64` casts `-a`
+    fn cast(&mut self) -> Result<Expr, LangError> {
+        let mut expr = self.unary()?;
+        while self.matches(&[TokenType::As]) {
+            let location = self.previous().location();
+            let target = self.type_annotation()?;
+            expr = Expr::Cast(Box::new(CastExpr {
+                expression: expr,
+                target,
+                location,
+            }));
+        }
+        Ok(expr)
+    }
+
     fn unary(&mut self) -> Result<Expr, LangError> {
-        if self.matches(&[TokenType::Bang, TokenType::Minus]) {
+        // A minus right before a number is part of its literal, so `-128` can initialize an i8
+        let negative_number = self.check(&TokenType::Minus)
+            && self
+                .token_at(self.cursor_position + 1)
+                .is_some_and(|token| {
+                    matches!(token.token_type, TokenType::Integer | TokenType::Float)
+                });
+        if negative_number {
+            self.advance();
+            self.advance();
+            return self.numeric_literal(true);
+        }
+        if self.matches(&[TokenType::Bang, TokenType::Minus, TokenType::Tilde]) {
             let operator = self.previous();
             let right = self.unary()?;
             return Ok(Expr::Unary(Box::new(UnaryExpr {
+                location: operator.location(),
                 operator: operator.token_type,
                 right,
             })));
@@ -193,12 +371,22 @@ impl<'a> Parser<'a> {
             if self.matches(&[TokenType::LeftParen]) {
                 expr = self.finish_call(&expr)?;
             } else if self.matches(&[TokenType::Dot]) {
+                if self.matches(&[TokenType::Integer, TokenType::Float]) {
+                    expr = self.tuple_fields(expr)?;
+                    continue;
+                }
                 let name =
                     self.pop_expect(&TokenType::Identifier, "Expected property name after '.'")?;
                 expr = Expr::Get(Box::new(GetExpr {
+                    location: name.location(),
                     name: name.lexeme,
                     object: expr,
                 }));
+            } else if self.matches(&[TokenType::Ternary]) {
+                expr = Expr::Try(Box::new(TryExpr {
+                    location: self.previous().location(),
+                    expression: expr,
+                }));
             } else {
                 break;
             }
@@ -206,7 +394,66 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
+    /// Parses the pieces of a format string after its `FormatStart`. The scanner has split it into
+    /// text and arguments, each the tokens of an expression between braces with an optional spec
+    fn format_string(&mut self) -> Result<Expr, LangError> {
+        let start = self.previous();
+        let mut pieces = Vec::new();
+        loop {
+            if self.matches(&[TokenType::FormatLiteral]) {
+                pieces.push(FormatPiece::Text(self.previous().value.to_string()));
+                continue;
+            }
+            if self.matches(&[TokenType::FormatEnd]) {
+                break;
+            }
+            let brace = self.pop_expect(&TokenType::LeftBrace, "Expect an argument or text")?;
+            let value = self.expression()?;
+            let mut spec = FormatSpec::default();
+            if self.check(&TokenType::FormatSpec) {
+                let token = self.peek();
+                spec = FormatSpec::parse(token.span.content.input)
+                    .map_err(|reason| self.parse_error(&token, &reason))?;
+                self.advance();
+            }
+            self.pop_expect(
+                &TokenType::RightBrace,
+                "Expect '}' after a format string argument",
+            )?;
+            pieces.push(FormatPiece::Argument(FormatArgument {
+                value,
+                spec,
+                location: brace.location(),
+            }));
+        }
+        Ok(Expr::FormatString(Box::new(FormatStringExpr {
+            pieces,
+            location: start.location(),
+        })))
+    }
+
+    /// Parses the tuple field access `object.0`. The scanner lexes the fields of a nested access
+    /// such as `pair.0.1` as the float `0.1`, which accesses each field in turn
+    fn tuple_fields(&mut self, object: Expr) -> Result<Expr, LangError> {
+        let fields = self.previous();
+        let mut expr = object;
+        for field in fields.lexeme.split('.') {
+            if field.parse::<usize>().is_err() {
+                return Err(
+                    self.parse_error(&self.peek(), &format!("invalid tuple field '{}'", field))
+                );
+            }
+            expr = Expr::Get(Box::new(GetExpr {
+                location: fields.location(),
+                name: field.to_string(),
+                object: expr,
+            }));
+        }
+        Ok(expr)
+    }
+
     fn finish_call(&mut self, expr: &Expr) -> Result<Expr, LangError> {
+        let paren = self.previous();
         let mut arguments = Vec::new();
         if !self.check(&TokenType::RightParen) {
             loop {
@@ -224,12 +471,34 @@ impl<'a> Parser<'a> {
         Ok(Expr::Call(Box::new(CallExpr {
             callee: expr.clone(),
             arguments,
+            location: paren.location(),
         })))
     }
 
     fn primary(&mut self) -> Result<Expr, LangError> {
+        if self.matches(&[TokenType::Match]) {
+            return self.match_expression();
+        }
+        if self.matches(&[TokenType::If]) {
+            let location = self.previous().location();
+            let condition = self.if_condition()?;
+            return self.if_expression(condition, location);
+        }
+        if self.matches(&[TokenType::LeftBrace]) {
+            return Ok(Expr::Block(Box::new(self.block_expression()?)));
+        }
+        if self.matches(&[TokenType::Loop]) {
+            return self.loop_expression(None);
+        }
+        if self.matches(&[TokenType::Label]) {
+            let label = Some(self.previous().lexeme);
+            self.pop_expect(&TokenType::Colon, "Expect ':' after a loop label")?;
+            self.pop_expect(&TokenType::Loop, "Expect 'loop' after a label")?;
+            return self.loop_expression(label);
+        }
         if self.matches(&[TokenType::SelfIdent]) {
             return Ok(Expr::SelfIdent(Box::new(SelfIdentExpr {
+                id: NodeId::next(),
                 keyword: self.previous().lexeme,
             })));
         }
@@ -256,67 +525,10 @@ impl<'a> Parser<'a> {
                 Value::Unit,
                 TypeAnnotation::Unit,
             )))));
-        } else if self.matches(&[TokenType::Integer]) {
-            let value = self.previous().value;
-            // Go back a 3 positions as we're cursor_positionly at the SemiColon
-            if self.check(&TokenType::SemiColon) && self.get_previous_index() > 2 {
-                if let Some(type_annotation_token) = self.token_at(self.get_previous_index() - 2) {
-                    if let TokenType::Type(type_annotation) = type_annotation_token.token_type {
-                        match type_annotation {
-                            TypeAnnotation::I32 => {
-                                return Ok(Expr::Literal(Box::new(LiteralExpr::new(
-                                    TypedValue::new(value, TypeAnnotation::I32),
-                                ))));
-                            }
-                            TypeAnnotation::I64 => {
-                                return Ok(Expr::Literal(Box::new(LiteralExpr::new(
-                                    TypedValue::new(value, TypeAnnotation::I64),
-                                ))));
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-            }
-            match value {
-                Value::Int32(_) => {
-                    return Ok(Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
-                        value,
-                        TypeAnnotation::I32,
-                    )))));
-                }
-                Value::Int64(_) => {
-                    return Ok(Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
-                        value,
-                        TypeAnnotation::I64,
-                    )))));
-                }
-                _ => {}
-            }
-        } else if self.matches(&[TokenType::Float]) {
-            if self.check(&TokenType::SemiColon) && self.get_previous_index() > 2 {
-                if let Some(type_annotation_token) = self.token_at(self.get_previous_index() - 2) {
-                    if let TokenType::Type(type_annotation) = type_annotation_token.token_type {
-                        match type_annotation {
-                            TypeAnnotation::F32 => {
-                                return Ok(Expr::Literal(Box::new(LiteralExpr::new(
-                                    TypedValue::new(self.previous().value, TypeAnnotation::F32),
-                                ))));
-                            }
-                            TypeAnnotation::F64 => {
-                                return Ok(Expr::Literal(Box::new(LiteralExpr::new(
-                                    TypedValue::new(self.previous().value, TypeAnnotation::F64),
-                                ))));
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-            }
-            return Ok(Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
-                self.previous().value,
-                TypeAnnotation::F64,
-            )))));
+        } else if self.matches(&[TokenType::Integer, TokenType::Float]) {
+            return self.numeric_literal(false);
+        } else if self.matches(&[TokenType::FormatStart]) {
+            return self.format_string();
         } else if self.matches(&[TokenType::String]) {
             return Ok(Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
                 self.previous().value,
@@ -332,44 +544,80 @@ impl<'a> Parser<'a> {
                     "Expected ']' after index expression",
                 )?;
                 return Ok(Expr::Index(Box::new(IndexExpr {
+                    location: from.location(),
                     from: from.lexeme,
                     index,
                 })));
-            } else if self.matches(&[TokenType::PathSeparator]) {
+            } else if self.check(&TokenType::PathSeparator) {
                 let enum_name = self.previous();
                 let mut path_elements = Vec::new();
-                loop {
-                    if self.check(&TokenType::SemiColon) || self.is_at_end() {
-                        break;
-                    }
-                    let path_item = self.pop_expect(&TokenType::Identifier, "expected ident")?;
+                while self.matches(&[TokenType::PathSeparator]) {
+                    let path_item =
+                        self.pop_expect(&TokenType::Identifier, "Expected identifier after '::'")?;
                     path_elements.push(path_item.lexeme);
                 }
                 path_elements.shrink_to_fit();
                 return Ok(Expr::EnumPath(Box::new(EnumPathExpr {
+                    location: enum_name.location(),
                     name: enum_name.lexeme,
                     path_items: path_elements,
                 })));
+            } else if self.is_struct_literal() {
+                return self.struct_literal();
+            } else if let Some(enum_name) = prelude_enum(&self.previous().lexeme) {
+                let variant = self.previous();
+                return Ok(Expr::EnumPath(Box::new(EnumPathExpr {
+                    location: variant.location(),
+                    name: enum_name.to_string(),
+                    path_items: vec![variant.lexeme],
+                })));
             } else {
+                let name = self.previous();
                 return Ok(Expr::Variable(Box::new(VariableExpr {
-                    name: self.previous().lexeme,
+                    id: NodeId::next(),
+                    location: name.location(),
+                    name: name.lexeme,
                 })));
             }
         } else if self.matches(&[TokenType::LeftParen]) {
+            let paren = self.previous();
             let expr = self.expression()?;
+            // A ',' after the first element makes this a tuple literal
+            if self.matches(&[TokenType::Comma]) {
+                let mut elements = vec![expr];
+                loop {
+                    elements.push(self.expression()?);
+                    if !self.matches(&[TokenType::Comma]) {
+                        break;
+                    }
+                }
+                self.pop_expect(&TokenType::RightParen, "Expect ')' after tuple expression")?;
+                elements.shrink_to_fit();
+                return Ok(Expr::Tuple(Box::new(TupleExpr {
+                    elements,
+                    location: paren.location(),
+                })));
+            }
             self.pop_expect(&TokenType::RightParen, "Expect ')' after and expression")?;
             return Ok(Expr::Grouping(Box::new(GroupingExpr { expression: expr })));
         } else if self.matches(&[TokenType::LeftBracket]) {
-            let mut array_type_annotation: Option<TokenType> = None;
-            if let Some(array_type_token) = self.token_at(self.cursor_position - 3) {
-                if let TokenType::Type(_) = array_type_token.token_type {
-                    array_type_annotation = Some(array_type_token.clone().token_type);
-                }
+            let bracket = self.previous();
+            if self.matches(&[TokenType::Colon]) {
+                self.pop_expect(&TokenType::RightBracket, "Expect ']' after '[:'")?;
+                return Ok(Expr::Map(Box::new(MapExpr {
+                    type_annotation: None,
+                    entries: Vec::new(),
+                    location: bracket.location(),
+                })));
             }
             let mut elements = Vec::new();
             if !self.check(&TokenType::RightBracket) {
                 loop {
                     elements.push(self.expression()?);
+                    // A ':' after the first element makes this a map literal
+                    if elements.len() == 1 && self.check(&TokenType::Colon) {
+                        return self.map_literal(elements.remove(0), bracket.location());
+                    }
                     if !self.matches(&[TokenType::Comma]) {
                         break;
                     }
@@ -381,13 +629,117 @@ impl<'a> Parser<'a> {
             )?;
             elements.shrink_to_fit();
             return Ok(Expr::Array(Box::new(ArrayExpr {
-                type_annotation: array_type_annotation,
+                type_annotation: None,
                 elements,
+                location: bracket.location(),
             })));
         }
         Err(self.parse_error(&self.peek(), "Expected expression"))
     }
 
+    /// Parses the value following a ':' in a literal. The scanner lexes an identifier following a
+    /// ':' as a type, here it starts the value instead
+    fn value_after_colon(&mut self) -> Result<Expr, LangError> {
+        if let TokenType::Type(TypeAnnotation::User(_)) = self.peek().token_type {
+            self.tokens[self.cursor_position].token_type = TokenType::Identifier;
+        }
+        self.expression()
+    }
+
+    /// Whether the identifier just parsed starts a struct literal, which is when it's followed by
+    /// a '{' and either a '}' or a field name and ':'. This tells it apart from an identifier
+    /// followed by a block, such as the value of `match value { ... }`
+    fn is_struct_literal(&self) -> bool {
+        let token_type = |offset| {
+            self.token_at(self.cursor_position + offset)
+                .map(|token| token.token_type)
+        };
+        if !self.check(&TokenType::LeftBrace) {
+            return false;
+        }
+        match token_type(1) {
+            Some(TokenType::RightBrace) => true,
+            Some(TokenType::Identifier) => token_type(2) == Some(TokenType::Colon),
+            _ => false,
+        }
+    }
+
+    /// Parses the fields of a struct literal, e.g. the `{ x: 1, y: 2 }` of `Point { x: 1, y: 2 }`
+    fn struct_literal(&mut self) -> Result<Expr, LangError> {
+        let name = self.previous();
+        self.pop_expect(&TokenType::LeftBrace, "Expected '{' after struct name")?;
+        let mut fields: Vec<(String, Expr)> = Vec::new();
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            let field = self.pop_expect(&TokenType::Identifier, "Expected field name")?;
+            if fields.iter().any(|(name, _)| *name == field.lexeme) {
+                return Err(self.parse_error(
+                    &self.peek(),
+                    &format!("field '{}' is given more than once", field.lexeme),
+                ));
+            }
+            self.pop_expect(&TokenType::Colon, "Expected ':' after field name")?;
+            fields.push((field.lexeme, self.value_after_colon()?));
+            if !self.matches(&[TokenType::Comma]) {
+                break;
+            }
+        }
+        self.pop_expect(&TokenType::RightBrace, "Expected '}' after struct fields")?;
+        // Fields of a type that's known without the struct's type arguments take it here
+        if let Some(struct_stmt) = self.structs.get(&name.lexeme) {
+            fields = fields
+                .into_iter()
+                .map(|(name, value)| {
+                    match struct_stmt
+                        .fields
+                        .iter()
+                        .find(|field| field.identifier == name)
+                    {
+                        Some(field)
+                            if !field
+                                .type_annotation
+                                .mentions_type_params(&struct_stmt.type_params) =>
+                        {
+                            let value = self.typed_literal(value, &field.type_annotation);
+                            (name, value)
+                        }
+                        _ => (name, value),
+                    }
+                })
+                .collect();
+        }
+        fields.shrink_to_fit();
+        Ok(Expr::StructLiteral(Box::new(StructLiteralExpr {
+            location: name.location(),
+            name: name.lexeme,
+            fields,
+        })))
+    }
+
+    /// Parses the rest of a map literal whose first key, `first_key`, has been parsed
+    fn map_literal(
+        &mut self,
+        first_key: Expr,
+        location: SourceLocation,
+    ) -> Result<Expr, LangError> {
+        let mut entries = Vec::new();
+        let mut key = first_key;
+        loop {
+            self.pop_expect(&TokenType::Colon, "Expected ':' after a map key")?;
+            entries.push((key, self.value_after_colon()?));
+            if !self.matches(&[TokenType::Comma]) || self.check(&TokenType::RightBracket) {
+                break;
+            }
+            key = self.expression()?;
+        }
+        self.pop_expect(&TokenType::RightBracket, "Expect ']' after map expression")?;
+        entries.shrink_to_fit();
+        Ok(Expr::Map(Box::new(MapExpr {
+            type_annotation: None,
+            entries,
+            location,
+        })))
+    }
+
     /// Checks if the cursor_position token in source matches `token_type`, errors using the string `string`
     /// on failure.
     fn pop_expect(&mut self, token_type: &TokenType, string: &str) -> Result<TokenIR, LangError> {
@@ -413,6 +765,18 @@ impl<'a> Parser<'a> {
         )
     }
 
+    /// Pops the `>` closing a list of type arguments or parameters. The scanner lexes the `>>`
+    /// closing nested ones, as in `Option<Option<i32>>`, as a shift, which is split in two here
+    fn pop_closing_angle(&mut self, string: &str) -> Result<(), LangError> {
+        let rest = match self.peek().token_type {
+            TokenType::GreaterGreater => TokenType::Greater,
+            TokenType::GreaterGreaterEqual => TokenType::GreaterEqual,
+            _ => return self.pop_expect(&TokenType::Greater, string).map(|_| ()),
+        };
+        self.tokens[self.cursor_position].token_type = rest;
+        Ok(())
+    }
+
     /// Checks if next sequence of tokens matches those of the slice `tokens`, in respective order,
     /// advancing the cursor_position position in source on first match
     fn matches(&mut self, tokens: &[TokenType]) -> bool {
@@ -456,6 +820,73 @@ impl<'a> Parser<'a> {
         self.cursor_position - 1
     }
 
+    /// The integer or float literal just consumed, negated if a minus came right before it. Its
+    /// type is that of the variable it initializes when it's annotated with a type of the same
+    /// kind, as in `let x: i64 = 1;`, unless it has a suffix of its own, as in `255u8`. Otherwise
+    /// it's the type of its value
+    fn numeric_literal(&self, negative: bool) -> Result<Expr, LangError> {
+        let literal = self.previous();
+        let literal_type = literal.value.numeric_type().ok_or_else(|| {
+            LangErrorType::new_parser_error(format!("expected a number, found {}", literal.value))
+        })?;
+        // Go back past the `=` and any minus, as we're currently at the SemiColon
+        let type_offset = if negative { 3 } else { 2 };
+        let mut annotation = None;
+        if self.check(&TokenType::SemiColon) && self.get_previous_index() > type_offset {
+            if let Some(token) = self.token_at(self.get_previous_index() - type_offset) {
+                if let TokenType::Type(type_annotation) = token.token_type {
+                    annotation = type_annotation.numeric_type();
+                }
+            }
+        }
+        let suffixed = literal_suffix(&literal.lexeme).is_some();
+        let integer = match literal.value.integer() {
+            // The scanner wraps a literal one past its signed type's maximum around to the
+            // type's minimum, which it's only allowed to be when negated, as in `-128i8`
+            Some(integer) if integer < 0 && !negative => {
+                return Err(LangErrorType::new_parser_error(format!(
+                    "literal {} is out of range for {}",
+                    -integer,
+                    TypeAnnotation::from(literal_type)
+                )))
+            }
+            Some(integer) if integer < 0 => Some(integer),
+            Some(integer) if negative => Some(-integer),
+            integer => integer,
+        };
+        let numeric_type = match (annotation, integer) {
+            (Some(annotation), _)
+                if !suffixed && annotation.is_integer() == literal_type.is_integer() =>
+            {
+                annotation
+            }
+            // Like positive ones, negative unsuffixed integers are the narrowest type that fits
+            (_, Some(integer)) if negative && !suffixed => {
+                if integer >= i128::from(i32::MIN) {
+                    NumericType::I32
+                } else {
+                    NumericType::I64
+                }
+            }
+            _ => literal_type,
+        };
+        let value = match integer {
+            Some(integer) => Value::from_integer(integer, numeric_type).ok_or_else(|| {
+                LangErrorType::new_parser_error(format!(
+                    "literal {} is out of range for {}",
+                    integer,
+                    TypeAnnotation::from(numeric_type)
+                ))
+            })?,
+            None if negative => (-&literal.value.cast(numeric_type)?)?,
+            None => literal.value.cast(numeric_type)?,
+        };
+        Ok(Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
+            value,
+            TypeAnnotation::from(numeric_type),
+        )))))
+    }
+
     fn token_at(&self, pos: usize) -> Option<Token> {
         if let Some(token) = self.tokens.get(pos) {
             return Some(token.clone());
@@ -498,7 +929,19 @@ impl<'a> Parser<'a> {
 
     fn expression_statement(&mut self) -> Result<Stmt, LangError> {
         let expr = self.expression()?;
-        self.pop_expect(&TokenType::SemiColon, "Expect ';' after expression.")?;
+        // The expression ending a block is its value, which needs no ';'
+        if !self.check(&TokenType::RightBrace) {
+            self.pop_expect(&TokenType::SemiColon, "Expect ';' after expression.")?;
+        }
+        Ok(Stmt::Expression(Box::new(ExpressionStmt {
+            expression: expr,
+        })))
+    }
+
+    /// A match in statement position, like a block, doesn't need a trailing ';'
+    fn match_statement(&mut self) -> Result<Stmt, LangError> {
+        let expr = self.expression()?;
+        self.matches(&[TokenType::SemiColon]);
         Ok(Stmt::Expression(Box::new(ExpressionStmt {
             expression: expr,
         })))
@@ -531,11 +974,15 @@ impl<'a> Parser<'a> {
         Ok(Stmt::Import(Box::new(ImportStmt { module_path: path })))
     }
 
+    /// An `if` whose branches are blocks is an expression, like a match it needs no trailing ';'
+    /// in statement position. One whose branches are single statements is a statement
     fn if_statement(&mut self) -> Result<Stmt, LangError> {
-        self.pop_expect(&TokenType::LeftParen, "Expect '(' after an 'if'.")?;
-        let condition = self.expression()?;
-        self.pop_expect(&TokenType::RightParen, "Expect ')' after 'if' condition.")?;
-
+        let location = self.previous().location();
+        let condition = self.if_condition()?;
+        if self.check(&TokenType::LeftBrace) {
+            let expression = self.if_expression(condition, location)?;
+            return Ok(Stmt::Expression(Box::new(ExpressionStmt { expression })));
+        }
         let then_branch = self.statement()?;
         let else_branch = if self.matches(&[TokenType::Else]) {
             Some(self.statement()?)
@@ -549,15 +996,51 @@ impl<'a> Parser<'a> {
         })))
     }
 
-    fn while_statement(&mut self) -> Result<Stmt, LangError> {
+    fn while_statement(&mut self, label: Option<String>) -> Result<Stmt, LangError> {
         self.pop_expect(&TokenType::LeftParen, "Expect '(' after 'while'")?;
         let condition = self.expression()?;
         self.pop_expect(&TokenType::RightParen, "Expect ')' after condition")?;
         let body = self.statement()?;
-        Ok(Stmt::While(Box::new(WhileStmt { body, condition })))
+        Ok(Stmt::While(Box::new(WhileStmt {
+            body,
+            condition,
+            label,
+            increment: None,
+        })))
+    }
+
+    /// Parses a `loop` following its keyword
+    fn loop_expression(&mut self, label: Option<String>) -> Result<Expr, LangError> {
+        let location = self.previous().location();
+        self.pop_expect(&TokenType::LeftBrace, "Expect '{' after 'loop'")?;
+        let body = self.block()?;
+        Ok(Expr::Loop(Box::new(LoopExpr {
+            label,
+            body,
+            location,
+        })))
+    }
+
+    /// Parses the loop named by the label preceding it, e.g. `'outer: while (true) { ... }`
+    fn labeled_loop(&mut self) -> Result<Stmt, LangError> {
+        let label = Some(self.previous().lexeme);
+        self.pop_expect(&TokenType::Colon, "Expect ':' after a loop label")?;
+        if self.matches(&[TokenType::While]) {
+            return self.while_statement(label);
+        }
+        if self.matches(&[TokenType::For]) {
+            return self.for_statement(label);
+        }
+        self.pop_expect(&TokenType::Loop, "Expect a loop after a label")?;
+        let expression = self.loop_expression(label)?;
+        self.matches(&[TokenType::SemiColon]);
+        Ok(Stmt::Expression(Box::new(ExpressionStmt { expression })))
     }
 
-    fn for_statement(&mut self) -> Result<Stmt, LangError> {
+    fn for_statement(&mut self, label: Option<String>) -> Result<Stmt, LangError> {
+        if !self.check(&TokenType::LeftParen) {
+            return self.for_in_statement(label);
+        }
         self.pop_expect(&TokenType::LeftParen, "Expect '(' after 'for'")?;
         let initializer;
         if self.matches(&[TokenType::SemiColon]) {
@@ -582,16 +1065,6 @@ impl<'a> Parser<'a> {
         self.pop_expect(&TokenType::RightParen, "Expect ')' after for clauses")?;
 
         let mut body = self.statement()?;
-        if let Some(increment) = increment {
-            body = Stmt::Block(Box::new(BlockStmt {
-                statements: vec![
-                    body,
-                    Stmt::Expression(Box::new(ExpressionStmt {
-                        expression: increment,
-                    })),
-                ],
-            }));
-        }
         if condition.is_none() {
             condition = Some(Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
                 Value::Boolean(true),
@@ -602,6 +1075,8 @@ impl<'a> Parser<'a> {
         body = Stmt::While(Box::new(WhileStmt {
             condition: condition.unwrap(),
             body,
+            label,
+            increment,
         }));
         if let Some(initializer) = initializer {
             body = Stmt::Block(Box::new(BlockStmt {
@@ -611,9 +1086,34 @@ impl<'a> Parser<'a> {
         Ok(body)
     }
 
+    /// Parses a `for` over the items of an iterable, e.g. `for item in items { ... }`
+    fn for_in_statement(&mut self, label: Option<String>) -> Result<Stmt, LangError> {
+        let location = self.previous().location();
+        let variable = self.pop_expect(
+            &TokenType::Identifier,
+            "Expect '(' or a loop variable after 'for'",
+        )?;
+        self.pop_expect(&TokenType::In, "Expect 'in' after the loop variable")?;
+        let iterable = self.expression()?;
+        self.pop_expect(&TokenType::LeftBrace, "Expect '{' after the iterable")?;
+        let body = self.block()?;
+        Ok(Stmt::ForIn(Box::new(ForInStmt {
+            label,
+            variable: variable.lexeme,
+            iterable,
+            body,
+            location,
+        })))
+    }
+
     fn return_statement(&mut self) -> Result<Stmt, LangError> {
+        let keyword = self.previous();
         let value = if !self.check(&TokenType::SemiColon) {
-            self.expression()?
+            let value = self.expression()?;
+            match self.return_types.last() {
+                Some(return_type) => self.typed_literal(value, return_type),
+                None => value,
+            }
         } else {
             Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
                 Value::Unit,
@@ -624,6 +1124,7 @@ impl<'a> Parser<'a> {
         Ok(Stmt::Return(Box::new(ReturnStmt {
             keyword: "return".into(),
             value,
+            location: keyword.location(),
         })))
     }
 
@@ -631,11 +1132,22 @@ impl<'a> Parser<'a> {
         if self.matches(&[TokenType::Break]) {
             return Ok(self.break_statement()?);
         }
+        if self.matches(&[TokenType::Continue]) {
+            return self.continue_statement();
+        }
         if self.matches(&[TokenType::Assert]) {
             return Ok(self.assert_statement()?);
         }
         if self.matches(&[TokenType::For]) {
-            return Ok(self.for_statement()?);
+            return Ok(self.for_statement(None)?);
+        }
+        if self.matches(&[TokenType::Label]) {
+            return self.labeled_loop();
+        }
+        if self.matches(&[TokenType::Loop]) {
+            let expression = self.loop_expression(None)?;
+            self.matches(&[TokenType::SemiColon]);
+            return Ok(Stmt::Expression(Box::new(ExpressionStmt { expression })));
         }
         if self.matches(&[TokenType::Import]) {
             return Ok(self.import_statement()?);
@@ -650,19 +1162,54 @@ impl<'a> Parser<'a> {
             return Ok(self.return_statement()?);
         }
         if self.matches(&[TokenType::While]) {
-            return Ok(self.while_statement()?);
+            return Ok(self.while_statement(None)?);
         }
         if self.matches(&[TokenType::LeftBrace]) {
+            let block = self.block_expression()?;
+            if block.value.is_some() {
+                return Ok(Stmt::Expression(Box::new(ExpressionStmt {
+                    expression: Expr::Block(Box::new(block)),
+                })));
+            }
             return Ok(Stmt::Block(Box::new(BlockStmt {
-                statements: self.block()?,
+                statements: block.statements,
             })));
         }
+        if self.check(&TokenType::Match) {
+            return Ok(self.match_statement()?);
+        }
         Ok(self.expression_statement()?)
     }
 
     fn break_statement(&mut self) -> Result<Stmt, LangError> {
+        let location = self.previous().location();
+        let label = self.loop_label();
+        let value = if !self.check(&TokenType::SemiColon) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
         self.pop_expect(&TokenType::SemiColon, "expected ';' after 'break'")?;
-        Ok(Stmt::Break)
+        Ok(Stmt::Break(Box::new(BreakStmt {
+            label,
+            value,
+            location,
+        })))
+    }
+
+    fn continue_statement(&mut self) -> Result<Stmt, LangError> {
+        let location = self.previous().location();
+        let label = self.loop_label();
+        self.pop_expect(&TokenType::SemiColon, "expected ';' after 'continue'")?;
+        Ok(Stmt::Continue(Box::new(ContinueStmt { label, location })))
+    }
+
+    /// The label of the loop a `break` or `continue` is aimed at, if it names one
+    fn loop_label(&mut self) -> Option<String> {
+        if self.matches(&[TokenType::Label]) {
+            return Some(self.previous().lexeme);
+        }
+        None
     }
 
     fn assert_statement(&mut self) -> Result<Stmt, LangError> {
@@ -677,50 +1224,447 @@ impl<'a> Parser<'a> {
         Ok(Stmt::Assert(Box::new(AssertStmt { condition })))
     }
 
+    /// Parses a block in statement position, where the value it ends with is discarded
     fn block(&mut self) -> Result<Vec<Stmt>, LangError> {
+        let block = self.block_expression()?;
+        let mut statements = block.statements;
+        if let Some(value) = block.value {
+            statements.push(Stmt::Expression(Box::new(ExpressionStmt {
+                expression: value,
+            })));
+        }
+        Ok(statements)
+    }
+
+    /// Parses the statements of a block following its '{'. An expression statement ending the
+    /// block without a ';' is its value, be it an expression like `a + 1` or an `if`, block or
+    /// match. An `if` without an else always evaluates to unit, so it's left a statement
+    fn block_expression(&mut self) -> Result<BlockExpr, LangError> {
+        let location = self.previous().location();
         let mut statements = Vec::new();
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
             statements.push(self.declaration()?);
         }
-        statements.shrink_to_fit();
         self.pop_expect(&TokenType::RightBrace, "Expect '}' after a block.")?;
-        Ok(statements)
+        let ends_with_semicolon = self
+            .token_at(self.cursor_position - 2)
+            .is_some_and(|token| token.token_type == TokenType::SemiColon);
+        let value = match statements.last() {
+            Some(Stmt::Expression(expression_stmt))
+                if !ends_with_semicolon
+                    && !matches!(
+                        expression_stmt.expression,
+                        Expr::If(ref if_expr) if if_expr.else_branch.is_none()
+                    ) =>
+            {
+                match statements.pop() {
+                    Some(Stmt::Expression(expression_stmt)) => Some(expression_stmt.expression),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        statements.shrink_to_fit();
+        Ok(BlockExpr {
+            statements,
+            value,
+            location,
+        })
     }
 
-    fn let_declaration(&mut self) -> Result<Stmt, LangError> {
-        let name = self.pop_expect(&TokenType::Identifier, "Expected variable name")?;
-        self.pop_expect(&TokenType::Colon, "Expected colon after variable name")?;
+    /// Parses an `if` whose branches are blocks, following its condition. `else if` chains
+    /// nest, the else branch of each `if` but the last being the next `if`
+    fn if_expression(
+        &mut self,
+        condition: Expr,
+        location: SourceLocation,
+    ) -> Result<Expr, LangError> {
+        self.pop_expect(&TokenType::LeftBrace, "Expect '{' before 'if' branch.")?;
+        let then_branch = self.block_expression()?;
+        let else_branch = if !self.matches(&[TokenType::Else]) {
+            None
+        } else if self.matches(&[TokenType::If]) {
+            let location = self.previous().location();
+            let condition = self.if_condition()?;
+            Some(self.if_expression(condition, location)?)
+        } else {
+            self.pop_expect(&TokenType::LeftBrace, "Expect '{' after 'else'.")?;
+            Some(Expr::Block(Box::new(self.block_expression()?)))
+        };
+        Ok(Expr::If(Box::new(IfExpr {
+            condition,
+            then_branch,
+            else_branch,
+            location,
+        })))
+    }
+
+    fn if_condition(&mut self) -> Result<Expr, LangError> {
+        self.pop_expect(&TokenType::LeftParen, "Expect '(' after an 'if'.")?;
+        let condition = self.expression()?;
+        self.pop_expect(&TokenType::RightParen, "Expect ')' after 'if' condition.")?;
+        Ok(condition)
+    }
+
+    /// Parses a type annotation, including function types such as `fn(i32) -> bool` and generic
+    /// types such as `Stack<i32>`
+    fn type_annotation(&mut self) -> Result<TypeAnnotation, LangError> {
         let type_annotation_token = self.advance();
-        if TypeAnnotation::check_token_type2(&type_annotation_token).is_err() {
-            return Err(self.parse_error(
+        match type_annotation_token.token_type {
+            TokenType::Type(TypeAnnotation::User(name)) => self.user_type(name),
+            TokenType::Type(type_annotation) => Ok(type_annotation),
+            TokenType::Identifier => self.user_type(type_annotation_token.lexeme),
+            TokenType::Fn => self.function_type(),
+            TokenType::LeftParen => self.tuple_type(),
+            other => Err(self.parse_error(
                 &self.peek(),
                 &format!(
                     "invalid type annotation, expected a type annotation but found {}",
-                    &type_annotation_token.token_type.to_string()
+                    other
                 ),
+            )),
+        }
+    }
+
+    /// Parses the type arguments following the user type `name`, if any. `name` is a type
+    /// parameter when it's one of the type parameters of the declarations being parsed
+    fn user_type(&mut self, name: String) -> Result<TypeAnnotation, LangError> {
+        if self.type_params.contains(&name) {
+            return Ok(TypeAnnotation::TypeParam(name));
+        }
+        if let (SELF_TYPE, Some(self_type)) = (name.as_str(), &self.self_type) {
+            return Ok(self_type.clone());
+        }
+        if !self.matches(&[TokenType::Less]) {
+            return Ok(TypeAnnotation::User(name));
+        }
+        let mut type_args = Vec::new();
+        loop {
+            type_args.push(self.type_annotation()?);
+            if !self.matches(&[TokenType::Comma]) {
+                break;
+            }
+        }
+        self.pop_closing_angle(&format!(
+            "Expected '>' after the type arguments of '{}'",
+            name
+        ))?;
+        // Arrays of element types the scanner doesn't lex as part of `Array<...>`, such as
+        // `Array<T>` or `Array<Point>`
+        if name == "Array" && type_args.len() == 1 {
+            return Ok(TypeAnnotation::Array(Box::new(type_args.remove(0))));
+        }
+        if name == "Range" && type_args.len() == 1 {
+            return Ok(TypeAnnotation::Range(Box::new(type_args.remove(0))));
+        }
+        if name == "Map" {
+            if type_args.len() != 2 {
+                return Err(self.parse_error(
+                    &self.peek(),
+                    &format!(
+                        "Map takes a key and a value type argument, found {}",
+                        type_args.len()
+                    ),
+                ));
+            }
+            let value_type = type_args.remove(1);
+            let key_type = type_args.remove(0);
+            return Ok(TypeAnnotation::Map(
+                Box::new(key_type),
+                Box::new(value_type),
             ));
         }
+        type_args.shrink_to_fit();
+        Ok(TypeAnnotation::Generic { name, type_args })
+    }
+
+    /// Parses the type parameters of a generic declaration, e.g. the `<T, U>` of
+    /// `fn pair<T, U>(...)`, and brings them into scope. Returns no type parameters for a
+    /// declaration that isn't generic
+    fn type_parameters(&mut self) -> Result<Vec<String>, LangError> {
+        let mut type_params = Vec::new();
+        if !self.matches(&[TokenType::Less]) {
+            return Ok(type_params);
+        }
+        loop {
+            let type_param =
+                self.pop_expect(&TokenType::Identifier, "Expected a type parameter name")?;
+            if type_params.contains(&type_param.lexeme) {
+                return Err(self.parse_error(
+                    &self.peek(),
+                    &format!("duplicate type parameter '{}'", type_param.lexeme),
+                ));
+            }
+            type_params.push(type_param.lexeme);
+            if !self.matches(&[TokenType::Comma]) {
+                break;
+            }
+        }
+        self.pop_closing_angle("Expected '>' after type parameters")?;
+        self.type_params.extend(type_params.iter().cloned());
+        type_params.shrink_to_fit();
+        Ok(type_params)
+    }
+
+    /// Takes the type parameters brought into scope by [`Parser::type_parameters`] out of scope
+    fn end_type_parameters(&mut self, type_params: &[String]) {
+        let len = self.type_params.len() - type_params.len();
+        self.type_params.truncate(len);
+    }
+
+    /// Parses the type arguments a generic impl gives the struct it implements, which have to be
+    /// the impl's own type parameters, as in `impl<T> Stack<T>`
+    fn impl_type_arguments(&mut self, name: &str, type_params: &[String]) -> Result<(), LangError> {
+        let found = if self.check(&TokenType::Less) {
+            self.user_type(name.to_string())?
+        } else {
+            TypeAnnotation::User(name.to_string())
+        };
+        self.check_impl_type(name, found, type_params)
+    }
+
+    /// Checks that `found`, the type an impl is for, is `name` given the impl's type parameters
+    fn check_impl_type(
+        &self,
+        name: &str,
+        found: TypeAnnotation,
+        type_params: &[String],
+    ) -> Result<(), LangError> {
+        let expected = TypeAnnotation::Generic {
+            name: name.to_string(),
+            type_args: type_params
+                .iter()
+                .map(|type_param| TypeAnnotation::TypeParam(type_param.clone()))
+                .collect(),
+        };
+        if type_params.is_empty() && found == TypeAnnotation::User(name.to_string()) {
+            return Ok(());
+        }
+        if found != expected {
+            return Err(self.parse_error(
+                &self.peek(),
+                &format!(
+                    "expected the impl to be for '{}', found '{}'",
+                    expected, found
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parses the element types of a tuple type following `(`, e.g. `(i64, String)`. Tuples have
+    /// at least two elements, `()` is the unit type
+    fn tuple_type(&mut self) -> Result<TypeAnnotation, LangError> {
+        let mut element_types = vec![self.type_annotation()?];
+        while self.matches(&[TokenType::Comma]) {
+            element_types.push(self.type_annotation()?);
+        }
+        self.pop_expect(
+            &TokenType::RightParen,
+            "Expected ')' after the element types of a tuple type",
+        )?;
+        if element_types.len() < 2 {
+            return Err(self.parse_error(
+                &self.peek(),
+                "a tuple type needs at least two element types",
+            ));
+        }
+        element_types.shrink_to_fit();
+        Ok(TypeAnnotation::Tuple(element_types))
+    }
+
+    /// Parses the parameter and return types of a function type following `fn`. Function types
+    /// without a return type return unit
+    fn function_type(&mut self) -> Result<TypeAnnotation, LangError> {
+        self.pop_expect(
+            &TokenType::LeftParen,
+            "Expected '(' after 'fn' in a function type",
+        )?;
+        let mut params = Vec::new();
+        if !self.check(&TokenType::RightParen) {
+            loop {
+                params.push(self.type_annotation()?);
+                if !self.matches(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.pop_expect(
+            &TokenType::RightParen,
+            "Expected ')' after the parameter types of a function type",
+        )?;
+        let return_type = if self.matches(&[TokenType::ReturnType]) {
+            self.type_annotation()?
+        } else {
+            TypeAnnotation::Unit
+        };
+        Ok(TypeAnnotation::Function {
+            params,
+            return_type: Box::new(return_type),
+        })
+    }
+
+    /// Gives a literal the type of the variable, field or return value it initializes where it
+    /// can take it. `[]` and `[:]` don't have a type otherwise, and numbers, including those in a
+    /// tuple such as the `1` of `(1, "a")`, widen to a wider numeric type
+    fn typed_literal(&self, mut literal: Expr, type_annotation: &TypeAnnotation) -> Expr {
+        match (&mut literal, type_annotation) {
+            (Expr::Array(ref mut array), TypeAnnotation::Array(_)) => {
+                array.type_annotation = Some(type_annotation.clone());
+            }
+            (Expr::Map(ref mut map), TypeAnnotation::Map(..)) => {
+                map.type_annotation = Some(type_annotation.clone());
+            }
+            (Expr::Tuple(ref mut tuple), TypeAnnotation::Tuple(element_types))
+                if tuple.elements.len() == element_types.len() =>
+            {
+                tuple.elements = std::mem::take(&mut tuple.elements)
+                    .into_iter()
+                    .zip(element_types.iter())
+                    .map(|(element, element_type)| self.typed_literal(element, element_type))
+                    .collect();
+            }
+            // The fields of a generic struct take their types from its type arguments, e.g. the
+            // `[]` of `let s: Stack<i32> = Stack { items: [] }` is an `Array<i32>`
+            (
+                Expr::StructLiteral(ref mut struct_literal),
+                TypeAnnotation::Generic { name, type_args },
+            ) if *name == struct_literal.name => {
+                if let Some(struct_stmt) = self.structs.get(name) {
+                    let bindings: HashMap<String, TypeAnnotation> = struct_stmt
+                        .type_params
+                        .iter()
+                        .cloned()
+                        .zip(type_args.iter().cloned())
+                        .collect();
+                    struct_literal.fields = std::mem::take(&mut struct_literal.fields)
+                        .into_iter()
+                        .map(|(name, value)| {
+                            match struct_stmt
+                                .fields
+                                .iter()
+                                .find(|field| field.identifier == name)
+                            {
+                                Some(field) => {
+                                    let field_type = field.type_annotation.substitute(&bindings);
+                                    let value = self.typed_literal(value, &field_type);
+                                    (name, value)
+                                }
+                                None => (name, value),
+                            }
+                        })
+                        .collect();
+                }
+            }
+            (Expr::Literal(ref mut literal), _) => {
+                let numeric_types = literal
+                    .value
+                    .value
+                    .numeric_type()
+                    .zip(type_annotation.numeric_type());
+                if let Some((literal_type, numeric_type)) = numeric_types {
+                    if literal_type.widens_to(numeric_type) {
+                        if let Ok(value) = literal.value.value.cast(numeric_type) {
+                            literal.value = TypedValue::new(value, type_annotation.clone());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        literal
+    }
+
+    fn let_declaration(&mut self) -> Result<Stmt, LangError> {
+        if self.matches(&[TokenType::LeftParen]) {
+            return self.destructure_declaration();
+        }
+        let name = self.pop_expect(&TokenType::Identifier, "Expected variable name")?;
+        // Without a type annotation the variable takes the type of its initializer
+        let type_annotation = if self.matches(&[TokenType::Colon]) {
+            Some(self.type_annotation()?)
+        } else {
+            None
+        };
+        // A variable of a type without a default value is left uninitialized, which the type
+        // checker reports
         let initializer = if self.matches(&[TokenType::Equal]) {
-            self.expression()?
+            let initializer = self.expression()?;
+            Some(match type_annotation {
+                Some(ref type_annotation) => self.typed_literal(initializer, type_annotation),
+                None => initializer,
+            })
+        } else if let Some(ref type_annotation) = type_annotation {
+            Value::default_value(type_annotation).map(|value| {
+                Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
+                    value,
+                    type_annotation.clone(),
+                ))))
+            })
         } else {
-            Expr::Literal(Box::new(LiteralExpr::new(TypedValue::new(
-                Value::default_value(&type_annotation_token.token_type.to_type_annotation()?),
-                type_annotation_token.token_type.to_type_annotation()?,
-            ))))
+            return Err(self.parse_error(
+                &self.peek(),
+                &format!(
+                    "expected a type annotation or an initializer for '{}'",
+                    name.lexeme
+                ),
+            ));
         };
         self.pop_expect(
             &TokenType::SemiColon,
             "Expect ';' after variable declaration",
         )?;
         Ok(Stmt::Var(Box::new(VarStmt {
-            initializer: Some(initializer),
-            type_annotation: type_annotation_token.token_type.to_type_annotation()?,
+            id: NodeId::next(),
+            initializer,
+            type_annotation,
+            location: name.location(),
             name: name.lexeme,
         })))
     }
 
+    /// Parses the rest of `let (a, b) = pair;`, which declares a variable for each element of the
+    /// tuple `pair`
+    fn destructure_declaration(&mut self) -> Result<Stmt, LangError> {
+        let paren = self.previous();
+        let mut names = Vec::new();
+        loop {
+            let name = self.pop_expect(&TokenType::Identifier, "Expected variable name")?;
+            if names.contains(&name.lexeme) {
+                return Err(self.parse_error(
+                    &self.peek(),
+                    &format!("'{}' is bound more than once", name.lexeme),
+                ));
+            }
+            names.push(name.lexeme);
+            if !self.matches(&[TokenType::Comma]) {
+                break;
+            }
+        }
+        self.pop_expect(&TokenType::RightParen, "Expected ')' after variable names")?;
+        self.pop_expect(&TokenType::Equal, "Expected '=' after variable names")?;
+        let initializer = self.expression()?;
+        self.pop_expect(
+            &TokenType::SemiColon,
+            "Expect ';' after variable declaration",
+        )?;
+        names.shrink_to_fit();
+        Ok(Stmt::Destructure(Box::new(DestructureStmt {
+            names,
+            initializer,
+            location: paren.location(),
+        })))
+    }
+
     fn enum_declaration(&mut self) -> Result<Stmt, LangError> {
         let name = self.pop_expect(&TokenType::Identifier, "expected identifier")?;
+        let type_params = self.type_parameters()?;
+        let result = self.enum_body(name, type_params.clone());
+        self.end_type_parameters(&type_params);
+        result
+    }
+
+    fn enum_body(&mut self, name: TokenIR, type_params: Vec<String>) -> Result<Stmt, LangError> {
         self.pop_expect(&TokenType::LeftBrace, "expected left brace")?;
         let mut item_list = Vec::new();
         let mut comma_count = 0;
@@ -732,8 +1676,12 @@ impl<'a> Parser<'a> {
                 identifier: self
                     .pop_expect(&TokenType::Identifier, "expected identifier")?
                     .lexeme,
+                fields: Vec::new(),
                 initializer: None,
             };
+            if self.matches(&[TokenType::LeftParen]) {
+                item.fields = self.enum_variant_fields()?;
+            }
             if self.matches(&[TokenType::Equal]) {
                 item.initializer = Some(self.expression()?);
             }
@@ -751,13 +1699,139 @@ impl<'a> Parser<'a> {
         item_list.shrink_to_fit();
         Ok(Stmt::Enum(Box::new(EnumStmt {
             name: name.lexeme,
+            type_params,
             item_list,
         })))
     }
 
-    fn trait_impl_declaration(&mut self, trait_name: TokenIR) -> Result<Stmt, LangError> {
+    /// Parses the payload types of an enum variant, e.g. the `f64, f64` of `Rect(f64, f64)`
+    fn enum_variant_fields(&mut self) -> Result<Vec<TypeAnnotation>, LangError> {
+        let mut fields = Vec::new();
+        loop {
+            fields.push(self.type_annotation()?);
+            if !self.matches(&[TokenType::Comma]) {
+                break;
+            }
+        }
+        self.pop_expect(
+            &TokenType::RightParen,
+            "expected ')' after enum variant types",
+        )?;
+        fields.shrink_to_fit();
+        Ok(fields)
+    }
+
+    fn match_expression(&mut self) -> Result<Expr, LangError> {
+        let location = self.previous().location();
+        let scrutinee = self.expression()?;
+        self.pop_expect(&TokenType::LeftBrace, "Expected '{' after match value")?;
+        let mut arms = Vec::new();
+        while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
+            let pattern = self.pattern()?;
+            self.pop_expect(&TokenType::FatArrow, "Expected '=>' after match pattern")?;
+            let body = if self.matches(&[TokenType::LeftBrace]) {
+                Stmt::Block(Box::new(BlockStmt {
+                    statements: self.block()?,
+                }))
+            } else {
+                Stmt::Expression(Box::new(ExpressionStmt {
+                    expression: self.expression()?,
+                }))
+            };
+            arms.push(MatchArm { pattern, body });
+            if !self.matches(&[TokenType::Comma]) && !self.check(&TokenType::RightBrace) {
+                if let Some(MatchArm {
+                    body: Stmt::Expression(_),
+                    ..
+                }) = arms.last()
+                {
+                    return Err(LangErrorType::new_parser_error(
+                        "need comma after match arm".to_string(),
+                    ));
+                }
+            }
+        }
+        self.pop_expect(&TokenType::RightBrace, "Expected '}' after match arms")?;
+        arms.shrink_to_fit();
+        Ok(Expr::Match(Box::new(MatchExpr {
+            scrutinee,
+            arms,
+            location,
+        })))
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, LangError> {
+        if self.matches(&[TokenType::Identifier]) {
+            let identifier = self.previous();
+            if self.matches(&[TokenType::PathSeparator]) {
+                let variant = self.pop_expect(
+                    &TokenType::Identifier,
+                    "Expected enum variant after '::' in pattern",
+                )?;
+                return self.enum_variant_pattern(identifier.lexeme, variant.lexeme);
+            }
+            if let Some(enum_name) = prelude_enum(&identifier.lexeme) {
+                return self.enum_variant_pattern(enum_name.to_string(), identifier.lexeme);
+            }
+            if identifier.lexeme == "_" {
+                return Ok(Pattern::Wildcard);
+            }
+            return Ok(Pattern::Binding(identifier.lexeme));
+        }
+        if self.check(&TokenType::Integer)
+            || self.check(&TokenType::Float)
+            || self.check(&TokenType::String)
+            || self.check(&TokenType::Char)
+            || self.check(&TokenType::True)
+            || self.check(&TokenType::False)
+        {
+            if let Expr::Literal(literal) = self.primary()? {
+                return Ok(Pattern::Literal(literal.value));
+            }
+        }
+        Err(self.parse_error(
+            &self.peek(),
+            &format!("Expected a pattern but found {}", self.peek().token_type),
+        ))
+    }
+
+    /// Parses the field patterns, if any, following the variant of an enum variant pattern
+    fn enum_variant_pattern(
+        &mut self,
+        enum_name: String,
+        variant: String,
+    ) -> Result<Pattern, LangError> {
+        let mut fields = Vec::new();
+        if self.matches(&[TokenType::LeftParen]) {
+            loop {
+                fields.push(self.pattern()?);
+                if !self.matches(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+            self.pop_expect(
+                &TokenType::RightParen,
+                "Expected ')' after enum variant patterns",
+            )?;
+        }
+        fields.shrink_to_fit();
+        Ok(Pattern::EnumVariant(Box::new(EnumVariantPattern {
+            enum_name,
+            variant,
+            fields,
+        })))
+    }
+
+    fn trait_impl_declaration(
+        &mut self,
+        trait_name: TokenIR,
+        trait_type_args: Vec<TypeAnnotation>,
+        type_params: Vec<String>,
+    ) -> Result<Stmt, LangError> {
         let impl_trait_name =
             self.pop_expect(&TokenType::Identifier, "expected identifier after for")?;
+        self.impl_type_arguments(&impl_trait_name.lexeme, &type_params)?;
+        self.self_type = Some(self_type(&impl_trait_name.lexeme, &type_params));
         let mut trait_fn_declarations = Vec::new();
         self.pop_expect(
             &TokenType::LeftBrace,
@@ -779,7 +1853,10 @@ impl<'a> Parser<'a> {
         trait_fn_declarations.shrink_to_fit();
         Ok(Stmt::ImplTrait(Box::new(ImplTraitStmt {
             impl_name: impl_trait_name.lexeme,
+            type_params,
+            location: trait_name.location(),
             trait_name: trait_name.lexeme,
+            trait_type_args,
             fn_declarations: trait_fn_declarations,
         })))
     }
@@ -787,6 +1864,19 @@ impl<'a> Parser<'a> {
     fn trait_declaration(&mut self) -> Result<Stmt, LangError> {
         let trait_name =
             self.pop_expect(&TokenType::Identifier, "expected identifier for trait")?;
+        let type_params = self.type_parameters()?;
+        self.type_params.push(SELF_TYPE.to_string());
+        let result = self.trait_body(trait_name, type_params.clone());
+        self.type_params.pop();
+        self.end_type_parameters(&type_params);
+        result
+    }
+
+    fn trait_body(
+        &mut self,
+        trait_name: TokenIR,
+        type_params: Vec<String>,
+    ) -> Result<Stmt, LangError> {
         let mut trait_fn_declarations = Vec::new();
         self.pop_expect(
             &TokenType::LeftBrace,
@@ -812,20 +1902,54 @@ impl<'a> Parser<'a> {
         trait_fn_declarations.shrink_to_fit();
         Ok(Stmt::Trait(Box::new(TraitStmt {
             name: trait_name.lexeme,
+            type_params,
             trait_fn_declarations,
         })))
     }
 
     fn impl_declaration(&mut self) -> Result<Stmt, LangError> {
-        let name = self.pop_expect(&TokenType::Identifier, "expected identifier")?;
-        if self.matches(&[TokenType::For]) {
-            Ok(self.trait_impl_declaration(name)?)
-        } else {
-            Ok(self.method_impl_declaration(name)?)
-        }
+        let type_params = self.type_parameters()?;
+        let name = self.pop_expect(&TokenType::Identifier, "expected identifier");
+        let result = match name {
+            Ok(name) if self.matches(&[TokenType::For]) => {
+                self.trait_impl_declaration(name, vec![], type_params.clone())
+            }
+            // Either the type arguments of a generic trait, as in
+            // `impl Index<i32, f64> for Polynomial`, or those of the generic struct the impl is for
+            Ok(name) if self.check(&TokenType::Less) => match self.user_type(name.lexeme.clone()) {
+                Ok(TypeAnnotation::Generic { type_args, .. })
+                    if self.matches(&[TokenType::For]) =>
+                {
+                    self.trait_impl_declaration(name, type_args, type_params.clone())
+                }
+                Ok(found) => self
+                    .check_impl_type(&name.lexeme, found, &type_params)
+                    .and_then(|_| self.method_impl_body(name, type_params.clone())),
+                Err(err) => Err(err),
+            },
+            Ok(name) => self.method_impl_declaration(name, type_params.clone()),
+            Err(err) => Err(err),
+        };
+        self.self_type = None;
+        self.end_type_parameters(&type_params);
+        result
+    }
+
+    fn method_impl_declaration(
+        &mut self,
+        name: TokenIR,
+        type_params: Vec<String>,
+    ) -> Result<Stmt, LangError> {
+        self.impl_type_arguments(&name.lexeme, &type_params)?;
+        self.method_impl_body(name, type_params)
     }
 
-    fn method_impl_declaration(&mut self, name: TokenIR) -> Result<Stmt, LangError> {
+    fn method_impl_body(
+        &mut self,
+        name: TokenIR,
+        type_params: Vec<String>,
+    ) -> Result<Stmt, LangError> {
+        self.self_type = Some(self_type(&name.lexeme, &type_params));
         let mut fn_declarations = Vec::new();
         self.pop_expect(
             &TokenType::LeftBrace,
@@ -847,6 +1971,7 @@ impl<'a> Parser<'a> {
         fn_declarations.shrink_to_fit();
         Ok(Stmt::Impl(Box::new(ImplStmt {
             name: name.lexeme,
+            type_params,
             fn_declarations,
         })))
     }
@@ -861,20 +1986,10 @@ impl<'a> Parser<'a> {
                 if self.matches(&[TokenType::SelfIdent, TokenType::Identifier]) {
                     let identifier = self.previous();
                     self.pop_expect(&TokenType::Colon, "Expected colon after paramter name")?;
-                    let type_annotation_token = self.advance();
-                    if TypeAnnotation::check_token_type2(&type_annotation_token).is_err() {
-                        return Err(self.parse_error(
-                            &self.peek(),
-                            &format!(
-                                "invalid type annotation, expected a type annotation but found {}",
-                                &type_annotation_token.token_type.to_string()
-                            ),
-                        ));
-                    }
                     // We only pass down the type annotation
                     parameters.push(VariableData::new(
                         identifier.lexeme,
-                        type_annotation_token.token_type.to_type_annotation()?,
+                        self.type_annotation()?,
                     ));
                 }
                 if !self.matches(&[TokenType::Comma]) {
@@ -884,22 +1999,11 @@ impl<'a> Parser<'a> {
         }
         self.pop_expect(&TokenType::RightParen, "Expect ')' after parameter list.")?;
         self.pop_expect(&TokenType::ReturnType, "Expected '->' after ')'")?;
-        let return_type_annotation_token = self.advance();
-        if TypeAnnotation::check_token_type2(&return_type_annotation_token).is_err() {
-            return Err(self.parse_error(
-                &self.peek(),
-                &format!(
-                    "invalid type annotation, expected a type annotation but found {}",
-                    &return_type_annotation_token.token_type.to_string()
-                ),
-            ));
-        }
+        let return_type = self.type_annotation()?;
         parameters.shrink_to_fit();
         Ok(Stmt::TraitFunction(Box::new(TraitFunctionStmt {
             name: name.lexeme,
-            return_type: return_type_annotation_token
-                .token_type
-                .to_type_annotation()?,
+            return_type,
             params: parameters,
         })))
     }
@@ -909,7 +2013,18 @@ impl<'a> Parser<'a> {
             &TokenType::Identifier,
             &format!("function: Expect {} name", kind),
         )?;
+        let type_params = self.type_parameters()?;
+        let result = self.function_signature_and_body(kind, name, type_params.clone());
+        self.end_type_parameters(&type_params);
+        result
+    }
 
+    fn function_signature_and_body(
+        &mut self,
+        kind: &str,
+        name: TokenIR,
+        type_params: Vec<String>,
+    ) -> Result<Stmt, LangError> {
         self.pop_expect(
             &TokenType::LeftParen,
             &format!("Expect '(' after {} name.", kind),
@@ -920,20 +2035,10 @@ impl<'a> Parser<'a> {
                 if self.matches(&[TokenType::SelfIdent, TokenType::Identifier]) {
                     let identifier = self.previous();
                     self.pop_expect(&TokenType::Colon, "Expected colon after paramter name")?;
-                    let type_annotation_token = self.advance();
-                    if TypeAnnotation::check_token_type2(&type_annotation_token).is_err() {
-                        return Err(self.parse_error(
-                            &self.peek(),
-                            &format!(
-                                "invalid type annotation, expected a type annotation but found {}",
-                                &type_annotation_token.token_type.to_string()
-                            ),
-                        ));
-                    }
                     // We only pass down the type annotation
                     parameters.push(VariableData::new(
                         identifier.lexeme,
-                        type_annotation_token.token_type.to_type_annotation()?,
+                        self.type_annotation()?,
                     ));
                 }
                 if !self.matches(&[TokenType::Comma]) {
@@ -943,25 +2048,37 @@ impl<'a> Parser<'a> {
         }
         self.pop_expect(&TokenType::RightParen, "Expect ')' after parameter list.")?;
         self.pop_expect(&TokenType::ReturnType, "Expected '->' after ')'")?;
-        let return_type_annotation_token = self.advance();
-        if TypeAnnotation::check_token_type2(&return_type_annotation_token).is_err() {
-            return Err(self.parse_error(
-                &self.peek(),
-                &format!(
-                    "invalid type annotation, expected a type annotation but found {}",
-                    &return_type_annotation_token.token_type.to_string()
-                ),
-            ));
-        }
+        let return_type = self.type_annotation()?;
         self.pop_expect(
             &TokenType::LeftBrace,
             &format!("Expect '{{' before {} body.", kind),
         )?;
-        let body = self.block()?;
+        self.return_types.push(return_type.clone());
+        let block = self.block_expression();
+        self.return_types.pop();
+        let block = block?;
+        let mut body = block.statements;
+        // A function returns the value its body ends with, unless it returns unit
+        if let Some(value) = block.value {
+            let value = self.typed_literal(value, &return_type);
+            if return_type == TypeAnnotation::Unit {
+                body.push(Stmt::Expression(Box::new(ExpressionStmt {
+                    expression: value,
+                })));
+            } else {
+                body.push(Stmt::Return(Box::new(ReturnStmt {
+                    keyword: "return".into(),
+                    value,
+                    location: self.previous().location(),
+                })));
+            }
+        }
         parameters.shrink_to_fit();
         Ok(Stmt::Function(Box::new(FunctionStmt {
+            location: name.location(),
             name: name.lexeme,
-            return_type: return_type_annotation_token.token_type,
+            type_params,
+            return_type: TokenType::Type(return_type),
             params: parameters,
             body,
         })))
@@ -969,6 +2086,13 @@ impl<'a> Parser<'a> {
 
     fn struct_declaration(&mut self) -> Result<Stmt, LangError> {
         let name = self.pop_expect(&TokenType::Identifier, "Expected struct name")?;
+        let type_params = self.type_parameters()?;
+        let result = self.struct_body(name, type_params.clone());
+        self.end_type_parameters(&type_params);
+        result
+    }
+
+    fn struct_body(&mut self, name: TokenIR, type_params: Vec<String>) -> Result<Stmt, LangError> {
         self.pop_expect(&TokenType::LeftBrace, "Expected '{' before struct body")?;
 
         let mut fields = Vec::new();
@@ -979,23 +2103,21 @@ impl<'a> Parser<'a> {
             }
             let field = self.pop_expect(&TokenType::Identifier, "Expected identifier")?;
             self.pop_expect(&TokenType::Colon, "Expected ':' after field identifier")?;
-            let type_annotation = self.advance();
-            if TypeAnnotation::check_token_type2(&type_annotation).is_err() {
-                return Err(self.parse_error(
-                    &self.peek(),
-                    &format!(
-                        "invalid type annotation, expected a type annotation but found {}",
-                        &type_annotation.token_type.to_string()
-                    ),
-                ));
-            }
+            let type_annotation = self.type_annotation()?;
+            let default = if self.matches(&[TokenType::Equal]) {
+                let default = self.expression()?;
+                Some(self.typed_literal(default, &type_annotation))
+            } else {
+                None
+            };
             if self.matches(&[TokenType::Comma]) {
                 comma_count += 1;
             }
-            fields.push(VariableData::new(
-                field.lexeme,
-                type_annotation.token_type.to_type_annotation()?,
-            ));
+            fields.push(StructField {
+                identifier: field.lexeme,
+                type_annotation,
+                default,
+            });
             if comma_count < fields.len() - 1 && !fields.is_empty() {
                 return Err(LangErrorType::new_parser_error(
                     "need comma after field declaration".to_string(),
@@ -1007,6 +2129,8 @@ impl<'a> Parser<'a> {
         fields.shrink_to_fit();
         Ok(Stmt::Struct(Box::new(StructStmt {
             fields,
+            type_params,
+            location: name.location(),
             name: name.lexeme,
         })))
     }
@@ -1024,7 +2148,7 @@ impl<'a> Parser<'a> {
             }
         }
         if self.matches(&[TokenType::Enum]) {
-            self.enum_declaration()?;
+            return self.enum_declaration();
         }
         if self.matches(&[TokenType::Trait]) {
             return self.trait_declaration();
@@ -1055,7 +2179,29 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parses the declarations of the structs ahead of the rest of the source, so that struct
+    /// literals know the types of the fields wherever the struct is declared. Errors are left for
+    /// the parse proper to report
+    fn declare_structs(&mut self) {
+        let positions: Vec<usize> = self
+            .tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| token.token_type == TokenType::Struct)
+            .map(|(position, _)| position)
+            .collect();
+        for position in positions {
+            self.cursor_position = position + 1;
+            if let Ok(Stmt::Struct(struct_stmt)) = self.struct_declaration() {
+                self.structs.insert(struct_stmt.name.clone(), *struct_stmt);
+            }
+        }
+        self.cursor_position = 0;
+        self.type_params.clear();
+    }
+
     pub fn parse(&mut self) -> Result<Vec<Stmt>, LangError> {
+        self.declare_structs();
         let mut statements = Vec::new();
         while !self.is_at_end() {
             statements.push(self.declaration()?)
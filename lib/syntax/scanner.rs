@@ -4,17 +4,17 @@ extern crate nom;
 use crate::error::*;
 use crate::syntax::span::*;
 use crate::syntax::token::*;
-use crate::token::{TokenType, TypeAnnotation};
+use crate::token::{NumericType, TokenType, TypeAnnotation};
 use crate::value::{Value, ValueType};
 
 use nom::branch::*;
 use nom::bytes::complete::*;
 use nom::multi::many1;
 use nom::{
-    bytes::complete::escaped, bytes::complete::take, bytes::complete::take_while1,
-    character::complete::alphanumeric1, character::complete::digit1,
-    character::complete::multispace0, character::complete::one_of, character::is_alphanumeric,
-    character::is_digit, combinator::recognize, sequence::delimited, sequence::preceded, IResult,
+    bytes::complete::take, bytes::complete::take_till, bytes::complete::take_while1,
+    character::complete::multispace0, character::is_alphanumeric, character::is_digit,
+    combinator::map, combinator::recognize, sequence::delimited, sequence::preceded, IResult,
+    Offset, Slice,
 };
 use std::collections::HashMap;
 
@@ -60,8 +60,11 @@ lazy_static! {
         keywords.insert("else", TokenType::Else);
         keywords.insert("assert", TokenType::Assert);
         keywords.insert("break", TokenType::Break);
+        keywords.insert("continue", TokenType::Continue);
+        keywords.insert("loop", TokenType::Loop);
         keywords.insert("enum", TokenType::Enum);
         keywords.insert("for", TokenType::For);
+        keywords.insert("in", TokenType::In);
         keywords.insert("while", TokenType::While);
         keywords.insert("fn", TokenType::Fn);
         keywords.insert("or", TokenType::Or);
@@ -74,6 +77,8 @@ lazy_static! {
         keywords.insert("return", TokenType::Return);
         keywords.insert("print", TokenType::Print);
         keywords.insert("import", TokenType::Import);
+        keywords.insert("match", TokenType::Match);
+        keywords.insert("as", TokenType::As);
         keywords
     };
 }
@@ -106,9 +111,12 @@ gen_lex_token!(lex_struct, "struct", TokenType::Struct);
 gen_lex_token!(lex_if, "if", TokenType::If);
 gen_lex_token!(lex_else, "else", TokenType::Else);
 gen_lex_token!(lex_break, "break", TokenType::Break);
+gen_lex_token!(lex_continue, "continue", TokenType::Continue);
+gen_lex_token!(lex_loop, "loop", TokenType::Loop);
 gen_lex_token!(lex_assert, "assert", TokenType::Assert);
 gen_lex_token!(lex_enum, "enum", TokenType::Enum);
 gen_lex_token!(lex_for, "for", TokenType::For);
+gen_lex_token!(lex_in, "in", TokenType::In);
 gen_lex_token!(lex_while, "while", TokenType::While);
 gen_lex_token!(lex_fn, "fn", TokenType::Fn);
 gen_lex_token!(lex_or, "or", TokenType::Or);
@@ -121,6 +129,8 @@ gen_lex_token!(lex_self, "self", TokenType::SelfIdent);
 gen_lex_token!(lex_return, "return", TokenType::Return);
 gen_lex_token!(lex_print, "print", TokenType::Print);
 gen_lex_token!(lex_import, "import", TokenType::Import);
+gen_lex_token!(lex_match, "match", TokenType::Match);
+gen_lex_token!(lex_as, "as", TokenType::As);
 
 // Symbol lexrs
 gen_lex_token!(lex_left_brace, "{", TokenType::LeftBrace);
@@ -131,6 +141,8 @@ gen_lex_token!(lex_right_paren, ")", TokenType::RightParen);
 gen_lex_token!(lex_left_paren, "(", TokenType::LeftParen);
 gen_lex_token!(lex_comma, ",", TokenType::Comma);
 gen_lex_token!(lex_dot, ".", TokenType::Dot);
+gen_lex_token!(lex_dot_dot, "..", TokenType::DotDot);
+gen_lex_token!(lex_dot_dot_equal, "..=", TokenType::DotDotEqual);
 gen_lex_token!(lex_minus, "-", TokenType::Minus);
 gen_lex_token!(lex_plus, "+", TokenType::Plus);
 gen_lex_token!(lex_semi_colon, ";", TokenType::SemiColon);
@@ -139,16 +151,38 @@ gen_lex_token!(lex_path_separator, "::", TokenType::PathSeparator);
 gen_lex_token!(lex_star, "*", TokenType::Star);
 gen_lex_token!(lex_equal, "=", TokenType::Equal);
 gen_lex_token!(lex_slash, "/", TokenType::Slash);
+gen_lex_token!(lex_percent, "%", TokenType::Percent);
 gen_lex_token!(lex_return_type, "->", TokenType::ReturnType);
+gen_lex_token!(lex_fat_arrow, "=>", TokenType::FatArrow);
 gen_lex_token!(lex_single_quote, "'", TokenType::SingleQuote);
 gen_lex_token!(lex_double_quote, "\"", TokenType::DoubleQuote);
 
 // Logical
 gen_lex_token!(lex_bang, "!", TokenType::Bang);
-gen_lex_token!(lex_and_symbol, "&", TokenType::And);
-gen_lex_token!(lex_or_symbol, "|", TokenType::Or);
+gen_lex_token!(lex_and_symbol, "&&", TokenType::And);
+gen_lex_token!(lex_or_symbol, "||", TokenType::Or);
 gen_lex_token!(lex_ternary, "?", TokenType::Ternary);
 
+// Bitwise
+gen_lex_token!(lex_ampersand, "&", TokenType::Ampersand);
+gen_lex_token!(lex_pipe, "|", TokenType::Pipe);
+gen_lex_token!(lex_caret, "^", TokenType::Caret);
+gen_lex_token!(lex_tilde, "~", TokenType::Tilde);
+gen_lex_token!(lex_shift_left, "<<", TokenType::LessLess);
+gen_lex_token!(lex_shift_right, ">>", TokenType::GreaterGreater);
+
+// Compound assignment
+gen_lex_token!(lex_plus_equal, "+=", TokenType::PlusEqual);
+gen_lex_token!(lex_minus_equal, "-=", TokenType::MinusEqual);
+gen_lex_token!(lex_star_equal, "*=", TokenType::StarEqual);
+gen_lex_token!(lex_slash_equal, "/=", TokenType::SlashEqual);
+gen_lex_token!(lex_percent_equal, "%=", TokenType::PercentEqual);
+gen_lex_token!(lex_ampersand_equal, "&=", TokenType::AmpersandEqual);
+gen_lex_token!(lex_pipe_equal, "|=", TokenType::PipeEqual);
+gen_lex_token!(lex_caret_equal, "^=", TokenType::CaretEqual);
+gen_lex_token!(lex_shift_left_equal, "<<=", TokenType::LessLessEqual);
+gen_lex_token!(lex_shift_right_equal, ">>=", TokenType::GreaterGreaterEqual);
+
 // Comparisons
 gen_lex_token!(lex_bang_equal, "!=", TokenType::BangEqual);
 gen_lex_token!(lex_less_than, "<", TokenType::Less);
@@ -158,8 +192,19 @@ gen_lex_token!(lex_greater_eq, ">=", TokenType::GreaterEqual);
 gen_lex_token!(lex_equal_equal, "==", TokenType::EqualEqual);
 
 // Types
+gen_lex_token!(lex_i8_type, "i8", TokenType::Type(TypeAnnotation::I8));
+gen_lex_token!(lex_i16_type, "i16", TokenType::Type(TypeAnnotation::I16));
 gen_lex_token!(lex_i32_type, "i32", TokenType::Type(TypeAnnotation::I32));
 gen_lex_token!(lex_i64_type, "i64", TokenType::Type(TypeAnnotation::I64));
+gen_lex_token!(lex_u8_type, "u8", TokenType::Type(TypeAnnotation::U8));
+gen_lex_token!(lex_u16_type, "u16", TokenType::Type(TypeAnnotation::U16));
+gen_lex_token!(lex_u32_type, "u32", TokenType::Type(TypeAnnotation::U32));
+gen_lex_token!(lex_u64_type, "u64", TokenType::Type(TypeAnnotation::U64));
+gen_lex_token!(
+    lex_usize_type,
+    "usize",
+    TokenType::Type(TypeAnnotation::USize)
+);
 gen_lex_token!(lex_f32_type, "f32", TokenType::Type(TypeAnnotation::F32));
 gen_lex_token!(lex_f64_type, "f64", TokenType::Type(TypeAnnotation::F64));
 gen_lex_token!(lex_char_type, "char", TokenType::Type(TypeAnnotation::Char));
@@ -173,28 +218,113 @@ gen_lex_token!(
 
 fn entry<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
     let (input, result) = alt((
+        lex_comment,
+        lex_string,
         lex_digit,
         lex_type,
         lex_ident,
         lex_keyword,
-        lex_string,
         lex_char,
+        lex_label,
         lex_symbol,
     ))(input)?;
     Ok((input, result))
 }
 
 fn lex_program<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Vec<Token>, LangError> {
-    let (input, mut output) = many1(entry)(input)?;
-    output.push(Token::new2(TokenType::Eof, "EoF"));
+    let (input, output) = many1(lex_tokens)(input)?;
+    let mut output: Vec<Token> = output.into_iter().flatten().collect();
+    // The end of the source is where errors about a missing token are reported
+    let (input, end) = position(input)?;
+    let end = Span::new("EoF", end.offset, end.line, end.column);
+    output.push(Token {
+        token_type: TokenType::Eof,
+        span: SourceSpan::new(end.clone(), end.clone(), end),
+        value: Value::String(TokenType::Eof.to_string()),
+    });
     Ok((input, output))
 }
 
+fn lex_comment<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+    let (input, comment) = alt((lex_line_comment, lex_block_comment))(input)?;
+    Ok((input, comment))
+}
+
+fn comment_token<'a>(
+    token_type: TokenType,
+    begin: Span<&'a str>,
+    comment: Span<&'a str>,
+    end: Span<&'a str>,
+) -> Result<Token<'a>, nom::Err<LangError>> {
+    let value = match Value::from_str(ValueType::String, comment.input) {
+        Ok(v) => v,
+        Err(e) => return Err(nom::Err::Failure::<LangError>(e)),
+    };
+    Ok(Token {
+        token_type,
+        span: SourceSpan::new(begin, comment, end),
+        value,
+    })
+}
+
+fn lex_line_comment<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+    let (input, begin) = preceded(multispace0, position)(input)?;
+    let (input, comment) = recognize(preceded(tag("//"), take_till(|c| c == '\n')))(input)?;
+    let (input, end) = preceded(multispace0, position)(input)?;
+    // Like Rust, `////` and beyond is a regular comment rather than a doc comment
+    let token_type = if comment.input.starts_with("///") && !comment.input.starts_with("////") {
+        TokenType::DocComment
+    } else {
+        TokenType::LineComment
+    };
+    Ok((input, comment_token(token_type, begin, comment, end)?))
+}
+
+/// Block comments nest, `/* /* */ */` is a single comment
+fn lex_block_comment<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+    let (input, begin) = preceded(multispace0, position)(input)?;
+    let (mut rest, _) = tag("/*")(input.clone())?;
+    let mut depth = 1;
+    while depth > 0 {
+        if let Ok((next, _)) = tag::<_, _, LangError>("/*")(rest.clone()) {
+            depth += 1;
+            rest = next;
+        } else if let Ok((next, _)) = tag::<_, _, LangError>("*/")(rest.clone()) {
+            depth -= 1;
+            rest = next;
+        } else if let Ok((next, _)) = take::<_, _, LangError>(1usize)(rest.clone()) {
+            rest = next;
+        } else {
+            return Err(nom::Err::Failure(LangErrorType::new_parser_error(format!(
+                "unterminated block comment starting on line {}",
+                begin.line
+            ))));
+        }
+    }
+    let comment = input.slice(..input.offset(&rest));
+    let (input, end) = preceded(multispace0, position)(rest)?;
+    let is_doc_comment = comment.input.starts_with("/**")
+        && !comment.input.starts_with("/***")
+        && comment.input != "/**/";
+    let token_type = if is_doc_comment {
+        TokenType::DocComment
+    } else {
+        TokenType::BlockComment
+    };
+    Ok((input, comment_token(token_type, begin, comment, end)?))
+}
+
 fn lex_keyword<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
     let (input, token) = alt((
-        lex_let, lex_struct, lex_if, lex_else, lex_break, lex_assert, lex_enum, lex_fn, lex_for,
-        lex_while, lex_or, lex_impl, lex_trait, lex_true, lex_false, lex_self, lex_print,
-        lex_return, lex_and, lex_import,
+        alt((
+            lex_let, lex_struct, lex_if, lex_else, lex_break, lex_assert, lex_enum, lex_fn,
+            lex_for, lex_while, lex_or, lex_impl, lex_trait, lex_true, lex_false, lex_self,
+            lex_print, lex_return, lex_and, lex_import, lex_match,
+        )),
+        lex_as,
+        lex_continue,
+        lex_loop,
+        lex_in,
     ))(input)?;
     Ok((input, token))
 }
@@ -207,24 +337,67 @@ fn lex_symbol<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangErr
         lex_left_paren,
         lex_left_bracket,
         lex_right_bracket,
+        lex_fat_arrow,
+        lex_compound_assignment,
+        lex_shift,
         lex_comparison,
         lex_comma,
-        lex_dot,
+        lex_dots,
         lex_return_type,
-        lex_minus,
-        lex_plus,
+        lex_arithmetic,
         lex_path_separator,
         lex_colon,
         lex_semi_colon,
-        lex_star,
-        lex_slash,
-        lex_or_symbol,
-        lex_and_symbol,
+        lex_logical,
+        lex_bitwise,
         lex_ternary,
     ))(input)?;
     Ok((input, token))
 }
 
+/// The range operators are lexed before the `.` they start with
+fn lex_dots<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+    let (input, token) = alt((lex_dot_dot_equal, lex_dot_dot, lex_dot))(input)?;
+    Ok((input, token))
+}
+
+fn lex_arithmetic<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+    let (input, token) = alt((lex_minus, lex_plus, lex_star, lex_slash, lex_percent))(input)?;
+    Ok((input, token))
+}
+
+/// `&&` and `||` are lexed before the bitwise `&` and `|` they start with
+fn lex_logical<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+    let (input, token) = alt((lex_and_symbol, lex_or_symbol))(input)?;
+    Ok((input, token))
+}
+
+fn lex_bitwise<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+    let (input, token) = alt((lex_ampersand, lex_pipe, lex_caret, lex_tilde))(input)?;
+    Ok((input, token))
+}
+
+fn lex_shift<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+    let (input, token) = alt((lex_shift_left, lex_shift_right))(input)?;
+    Ok((input, token))
+}
+
+fn lex_compound_assignment<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+    let (input, token) = alt((
+        lex_plus_equal,
+        lex_minus_equal,
+        lex_star_equal,
+        lex_slash_equal,
+        lex_percent_equal,
+        lex_ampersand_equal,
+        lex_pipe_equal,
+        lex_caret_equal,
+        lex_shift_left_equal,
+        lex_shift_right_equal,
+    ))(input)?;
+    Ok((input, token))
+}
+
 fn lex_comparison<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
     let (input, token) = alt((
         lex_bang_equal,
@@ -276,59 +449,338 @@ fn lex_ident<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangErro
     ))
 }
 
-fn lex_char_content<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
-    let (input, begin) = preceded(multispace0, position)(input)?;
-    let (input, content) = take(1usize)(input)?;
-    let (input, end) = preceded(multispace0, position)(input)?;
-    if let Some(ch) = content.input.chars().nth(0) {
-        let value = Value::Char(ch);
-        Ok((
-            input,
-            Token {
-                token_type: TokenType::Char,
-                span: SourceSpan::new(begin, content, end),
-                value,
-            },
-        ))
-    } else {
-        Err(nom::Err::<LangError>::Error(LangError::from(
-            LangErrorType::ParserError {
-                reason: "Tried to lex a character but failed".into(),
-            },
-        )))
-    }
+/// A failure to lex at `offset` bytes into `input`, which says where it happened
+fn lex_failure(input: &Span<&str>, offset: usize, reason: String) -> nom::Err<LangError> {
+    let at = input.slice(offset..);
+    nom::Err::Failure(LangErrorType::new_parser_error(format!(
+        "line {}, column {}: {}",
+        at.line,
+        at.column + 1,
+        reason
+    )))
 }
 
+/// Decodes the escape sequence starting at the backslash at `offset` in `input`, returning the
+/// character it stands for and the offset just past it
+fn lex_escape(input: &Span<&str>, offset: usize) -> Result<(char, usize), nom::Err<LangError>> {
+    let sequence = &input.input[offset..];
+    let escaped = match sequence.chars().nth(1) {
+        Some(escaped) => escaped,
+        None => return Err(lex_failure(input, offset, "unterminated escape".into())),
+    };
+    let ch = match escaped {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '0' => '\0',
+        '\\' => '\\',
+        '\'' => '\'',
+        '"' => '"',
+        'u' => {
+            let digits = sequence
+                .strip_prefix("\\u{")
+                .and_then(|rest| rest.find('}').map(|close| &rest[..close]));
+            let ch = digits
+                .filter(|digits| !digits.is_empty() && digits.len() <= 6)
+                .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                .and_then(std::char::from_u32);
+            return match (ch, digits) {
+                (Some(ch), Some(digits)) => Ok((ch, offset + "\\u{}".len() + digits.len())),
+                _ => Err(lex_failure(
+                    input,
+                    offset,
+                    "invalid unicode escape, expected '\\u{' followed by up to 6 hex digits naming a character and '}'".into(),
+                )),
+            };
+        }
+        other => {
+            return Err(lex_failure(
+                input,
+                offset,
+                format!("unknown character escape '\\{}'", other),
+            ))
+        }
+    };
+    Ok((ch, offset + 1 + escaped.len_utf8()))
+}
+
+/// Lexes a character literal, e.g. `'a'` or `'\n'`. A label, the only other thing starting with
+/// a quote, is left to the other lexers
 fn lex_char<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
-    let (input, _) = lex_single_quote(input)?;
-    let (input, ch) = lex_char_content(input)?;
-    let (input, _) = lex_single_quote(input)?;
-    Ok((input, ch))
+    let (input, begin) = preceded(multispace0, position)(input)?;
+    let (input, _) = tag("'")(input)?;
+    let (ch, length) = match input.input.chars().next() {
+        Some('\\') => lex_escape(&input, 0)?,
+        Some('\'') => return Err(lex_failure(&begin, 0, "empty character literal".into())),
+        Some(ch) if ch != '\n' => (ch, ch.len_utf8()),
+        _ => {
+            return Err(nom::Err::<LangError>::Error(LangError::from(
+                LangErrorType::ParserError {
+                    reason: "Tried to lex a character but failed".into(),
+                },
+            )))
+        }
+    };
+    if !input.input[length..].starts_with('\'') {
+        // `'outer` is a label, but `'ab'` is a character literal with too many characters
+        let label_length = input
+            .input
+            .find(|ch| !allowable_ident_char(ch))
+            .unwrap_or(input.input.len());
+        if label_length > 0 && !input.input[label_length..].starts_with('\'') {
+            return Err(nom::Err::<LangError>::Error(LangError::from(
+                LangErrorType::ParserError {
+                    reason: "Expected a closing quote after a character".into(),
+                },
+            )));
+        }
+        return Err(lex_failure(
+            &begin,
+            0,
+            "a character literal holds a single character, strings are written in double quotes"
+                .into(),
+        ));
+    }
+    let content = input.slice(..length);
+    let (input, _) = lex_single_quote(input.slice(length..))?;
+    let (input, end) = preceded(multispace0, position)(input)?;
+    Ok((
+        input,
+        Token {
+            token_type: TokenType::Char,
+            span: SourceSpan::new(begin, content, end),
+            value: Value::Char(ch),
+        },
+    ))
 }
 
-fn lex_string_content<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+/// Lexes the label of a loop, e.g. `'outer`. Tried after characters, so `'a'` stays a character
+fn lex_label<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
     let (input, begin) = preceded(multispace0, position)(input)?;
-    let (input, content) = escaped(alt((alphanumeric1, tag(" "))), '\\', one_of("\"n\\"))(input)?;
+    let (input, label) = preceded(
+        multispace0,
+        recognize(preceded(tag("'"), take_while1(allowable_ident_char))),
+    )(input)?;
     let (input, end) = preceded(multispace0, position)(input)?;
-    let value = match Value::from_str(ValueType::String, content.input) {
+    let value = match Value::from_str(ValueType::String, label.input) {
         Ok(v) => v,
         Err(e) => return Err(nom::Err::Failure::<LangError>(e)),
     };
     Ok((
         input,
         Token {
-            token_type: TokenType::String,
-            span: SourceSpan::new(begin, content, end),
+            token_type: TokenType::Label,
+            span: SourceSpan::new(begin, label, end),
             value,
         },
     ))
 }
 
+/// Decodes the contents of a string up to its closing quote, returning them and their length.
+/// A backslash at the end of a line skips the line break and the indentation after it. `opening`
+/// is where the string starts, which an unterminated string is reported at. The text of a
+/// format string also ends at the `{` of an argument, and has `{{` and `}}` for braces
+fn lex_string_content(
+    opening: &Span<&str>,
+    input: &Span<&str>,
+    format: bool,
+) -> Result<(String, usize), nom::Err<LangError>> {
+    let mut string = String::new();
+    let mut offset = 0;
+    loop {
+        let ch = match input.input[offset..].chars().next() {
+            Some(ch) => ch,
+            None => return Err(lex_failure(opening, 0, "unterminated string".into())),
+        };
+        match ch {
+            '"' => return Ok((string, offset)),
+            '{' | '}' if format && input.input[offset + 1..].starts_with(ch) => {
+                string.push(ch);
+                offset += 2;
+            }
+            '{' if format => return Ok((string, offset)),
+            '}' if format => {
+                return Err(lex_failure(
+                    input,
+                    offset,
+                    "unmatched '}' in a format string, a literal brace is written '}}'".into(),
+                ))
+            }
+            '\\' if input.input[offset + 1..].starts_with(['\n', '\r']) => {
+                let rest = &input.input[offset + 1..];
+                offset += 1 + rest.len() - rest.trim_start().len();
+            }
+            '\\' => {
+                let (escaped, next) = lex_escape(input, offset)?;
+                string.push(escaped);
+                offset = next;
+            }
+            ch => {
+                string.push(ch);
+                offset += ch.len_utf8();
+            }
+        }
+    }
+}
+
+/// Lexes a raw string, e.g. `r"C:\path"` or `r#"a "quoted" word"#`, whose backslashes are kept
+/// as they are. The closing quote must be followed by as many `#`s as the opening one follows
+fn lex_raw_string_content(
+    opening: &Span<&str>,
+    input: &Span<&str>,
+    hashes: usize,
+) -> Result<(String, usize), nom::Err<LangError>> {
+    let closing = format!("\"{}", "#".repeat(hashes));
+    match input.input.find(&closing) {
+        Some(length) => Ok((input.input[..length].to_string(), length)),
+        None => Err(lex_failure(opening, 0, "unterminated raw string".into())),
+    }
+}
+
+/// Lexes a string literal, which may span several lines. Its token's value holds the decoded
+/// contents, while its lexeme is the source between the quotes
 fn lex_string<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
-    let (input, _) = lex_double_quote(input)?;
-    let (input, string) = lex_string_content(input)?;
-    let (input, _) = lex_double_quote(input)?;
-    Ok((input, string))
+    let (input, begin) = preceded(multispace0, position)(input)?;
+    let opening = input.clone();
+    let (input, hashes) = alt((
+        map(tag("\""), |_| None),
+        map(
+            delimited(tag("r"), take_while(|ch| ch == '#'), tag("\"")),
+            |hashes: Span<&str>| Some(hashes.input.len()),
+        ),
+    ))(input)?;
+    let (value, length) = match hashes {
+        Some(hashes) => lex_raw_string_content(&opening, &input, hashes)?,
+        None => lex_string_content(&opening, &input, false)?,
+    };
+    let content = input.slice(..length);
+    let (input, _) = lex_double_quote(input.slice(length..))?;
+    let (input, _) = take(hashes.unwrap_or(0))(input)?;
+    let (input, end) = preceded(multispace0, position)(input)?;
+    Ok((
+        input,
+        Token {
+            token_type: TokenType::String,
+            span: SourceSpan::new(begin, content, end),
+            value: Value::String(value),
+        },
+    ))
+}
+
+/// Lexes the next token, or all the tokens of a format string
+fn lex_tokens<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Vec<Token>, LangError> {
+    alt((lex_format_string, map(entry, |token| vec![token])))(input)
+}
+
+/// A token of a format string, `content` being what it was lexed from
+fn format_token<'a>(token_type: TokenType, content: Span<&'a str>, value: Value) -> Token<'a> {
+    let end = content.slice(content.input.len()..);
+    Token {
+        token_type,
+        span: SourceSpan::new(content.clone(), content, end),
+        value,
+    }
+}
+
+/// Lexes a format string, e.g. `f"x = {x}, y = {y:.2}"`, into a `FormatStart`, its pieces of
+/// text and its arguments, and a `FormatEnd`. An argument is lexed as a `{`, the tokens of its
+/// expression, a `FormatSpec` if it has a `:` followed by one, and a `}`
+fn lex_format_string<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Vec<Token>, LangError> {
+    let (input, _) = multispace0(input)?;
+    let opening = input.clone();
+    let (mut input, start) = tag("f\"")(input)?;
+    let mut tokens = vec![format_token(
+        TokenType::FormatStart,
+        start.clone(),
+        Value::String(start.input.to_string()),
+    )];
+    loop {
+        let (text, length) = lex_string_content(&opening, &input, true)?;
+        if length > 0 {
+            tokens.push(format_token(
+                TokenType::FormatLiteral,
+                input.slice(..length),
+                Value::String(text),
+            ));
+        }
+        input = input.slice(length..);
+        if input.input.starts_with('"') {
+            let quote = input.slice(..1);
+            tokens.push(format_token(
+                TokenType::FormatEnd,
+                quote,
+                Value::String("\"".into()),
+            ));
+            let (input, _) = multispace0(input.slice(1..))?;
+            return Ok((input, tokens));
+        }
+        tokens.push(format_token(
+            TokenType::LeftBrace,
+            input.slice(..1),
+            Value::String("{".into()),
+        ));
+        input = input.slice(1..);
+        // Brackets in the argument, such as the braces of a struct literal or the brackets of a
+        // map, are balanced by the time the brace closing the argument comes. The colons within
+        // them don't start a spec
+        let mut depth = 0;
+        loop {
+            let (rest, _) = multispace0(input)?;
+            input = rest;
+            if depth == 0 && input.input.starts_with('}') {
+                tokens.push(format_token(
+                    TokenType::RightBrace,
+                    input.slice(..1),
+                    Value::String("}".into()),
+                ));
+                input = input.slice(1..);
+                break;
+            }
+            if depth == 0 && input.input.starts_with(':') && !input.input.starts_with("::") {
+                let close = match input.input.find('}') {
+                    Some(close) => close,
+                    None => return Err(lex_failure(&opening, 0, "unterminated string".into())),
+                };
+                let spec = input.slice(1..close);
+                tokens.push(format_token(
+                    TokenType::FormatSpec,
+                    spec.clone(),
+                    Value::String(spec.input.to_string()),
+                ));
+                tokens.push(format_token(
+                    TokenType::RightBrace,
+                    input.slice(close..close + 1),
+                    Value::String("}".into()),
+                ));
+                input = input.slice(close + 1..);
+                break;
+            }
+            let (rest, argument) = match lex_tokens(input.clone()) {
+                Ok(lexed) => lexed,
+                Err(nom::Err::Error(_)) => {
+                    return Err(lex_failure(
+                        &input,
+                        0,
+                        "expected '}' to close a format string argument".into(),
+                    ))
+                }
+                Err(e) => return Err(e),
+            };
+            for token in argument.iter() {
+                match token.token_type {
+                    TokenType::LeftBrace | TokenType::LeftBracket | TokenType::LeftParen => {
+                        depth += 1
+                    }
+                    TokenType::RightBrace | TokenType::RightBracket | TokenType::RightParen => {
+                        depth -= 1
+                    }
+                    _ => {}
+                }
+            }
+            tokens.extend(argument);
+            input = rest;
+        }
+    }
 }
 
 fn lex_array<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
@@ -360,8 +812,15 @@ fn lex_array<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangErro
 
 fn lex_type<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
     let (input, type_annotation) = alt((
+        lex_i8_type,
+        lex_i16_type,
         lex_i32_type,
         lex_i64_type,
+        lex_u8_type,
+        lex_u16_type,
+        lex_u32_type,
+        lex_u64_type,
+        lex_usize_type,
         lex_f32_type,
         lex_f64_type,
         lex_char_type,
@@ -373,45 +832,185 @@ fn lex_type<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError
     Ok((input, type_annotation))
 }
 
-fn lex_digit<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
-    let (input, result) = alt((lex_float, lex_integer))(input)?;
-    Ok((input, result))
+/// The suffixes giving a number literal its type, e.g. the `u8` of `255u8` or the `f32` of `2.0f32`
+const NUMERIC_SUFFIXES: [(&str, NumericType); 11] = [
+    ("i8", NumericType::I8),
+    ("i16", NumericType::I16),
+    ("i32", NumericType::I32),
+    ("i64", NumericType::I64),
+    ("u8", NumericType::U8),
+    ("u16", NumericType::U16),
+    ("u32", NumericType::U32),
+    ("u64", NumericType::U64),
+    ("usize", NumericType::USize),
+    ("f32", NumericType::F32),
+    ("f64", NumericType::F64),
+];
+
+/// The type the suffix of the number literal `lexeme` gives it, if it has one. Float suffixes
+/// are only looked for in decimal literals, where they can't be mistaken for hex digits
+pub fn literal_suffix(lexeme: &str) -> Option<NumericType> {
+    let decimal = !lexeme.starts_with("0x");
+    NUMERIC_SUFFIXES
+        .iter()
+        .find(|(suffix, numeric_type)| {
+            lexeme.ends_with(suffix) && (decimal || numeric_type.is_integer())
+        })
+        .map(|(_, numeric_type)| *numeric_type)
 }
 
-fn lex_float<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
-    let (input, begin) = preceded(multispace0, position)(input)?;
-    let (input, digit) =
-        preceded(multispace0, recognize(delimited(digit1, tag("."), digit1)))(input)?;
-    let (input, end) = preceded(multispace0, position)(input)?;
-    let token_type = TokenType::Float;
-    let value = match Value::from_str(ValueType::Float, digit.input) {
-        Ok(v) => v,
-        Err(e) => return Err(nom::Err::Failure::<LangError>(e)),
+/// The offset just past the digits of base `radix` and `_` separators starting at `offset`
+fn skip_digits(text: &str, offset: usize, radix: u32) -> usize {
+    offset
+        + text[offset..]
+            .find(|c: char| !c.is_digit(radix) && c != '_')
+            .unwrap_or(text.len() - offset)
+}
+
+/// The value of the decimal literal `number` as a float of type `numeric_type`
+fn float_literal(number: &str, numeric_type: NumericType) -> Result<Value, nom::Err<LangError>> {
+    let number = number.replace('_', "");
+    let value = match numeric_type {
+        NumericType::F32 => number.parse::<f32>().map(Value::Float32),
+        _ => number.parse::<f64>().map(Value::Float64),
     };
-    Ok((
-        input,
-        Token {
-            token_type,
-            span: SourceSpan::new(begin, digit, end),
-            value,
-        },
-    ))
+    value.map_err(|e| nom::Err::Failure(e.into()))
 }
 
-fn lex_integer<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
+/// Lexes an integer or float literal. Integers can be written in hex, octal or binary with a
+/// `0x`, `0o` or `0b` prefix, decimal literals can have a fraction and an exponent, and any of
+/// them can have `_` separators between their digits and a suffix giving them a type
+fn lex_digit<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangError> {
     let (input, begin) = preceded(multispace0, position)(input)?;
-    let (input, digit) = preceded(multispace0, digit1)(input)?;
-    let (input, end) = preceded(multispace0, position)(input)?;
-    let token_type = TokenType::Integer;
-    let value = match Value::from_str(ValueType::Integer, digit.input) {
-        Ok(v) => v,
-        Err(e) => return Err(nom::Err::Failure::<LangError>(e)),
+    let text = input.input;
+    if !text.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(nom::Err::<LangError>::Error(LangError::from(
+            LangErrorType::ParserError {
+                reason: "Tried to lex a number but failed".into(),
+            },
+        )));
+    }
+    let radix = match text.get(..2) {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => 10,
+    };
+    let digits_start = if radix == 10 { 0 } else { 2 };
+    let mut offset = skip_digits(text, digits_start, radix);
+    let digits = text[digits_start..offset].replace('_', "");
+    if digits.is_empty() {
+        return Err(lex_failure(
+            &input,
+            0,
+            format!("expected digits after '{}'", &text[..2]),
+        ));
+    }
+    let mut is_float = false;
+    if radix == 10 {
+        // A fraction needs a digit after its dot, leaving `0..10` a range and `1.max()` a call
+        if text[offset..].starts_with('.')
+            && text[offset + 1..].starts_with(|c: char| c.is_ascii_digit())
+        {
+            offset = skip_digits(text, offset + 1, 10);
+            is_float = true;
+        }
+        let exponent = text[offset..]
+            .strip_prefix(['e', 'E'])
+            .map(|rest| rest.strip_prefix(['+', '-']).unwrap_or(rest));
+        if let Some(exponent) = exponent {
+            if exponent.starts_with(|c: char| c.is_ascii_digit()) {
+                offset = skip_digits(text, text.len() - exponent.len(), 10);
+                is_float = true;
+            }
+        }
+    }
+    let number = &text[..offset];
+    let suffix_end = offset
+        + text[offset..]
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(text.len() - offset);
+    let suffix = &text[offset..suffix_end];
+    let suffix = match NUMERIC_SUFFIXES.iter().find(|(name, _)| *name == suffix) {
+        _ if suffix.is_empty() => None,
+        Some((_, numeric_type)) if numeric_type.is_integer() && is_float => {
+            return Err(lex_failure(
+                &input,
+                offset,
+                format!("invalid suffix '{}' for a float literal", suffix),
+            ))
+        }
+        Some((_, numeric_type)) if !numeric_type.is_integer() && radix != 10 => {
+            return Err(lex_failure(
+                &input,
+                offset,
+                format!("invalid suffix '{}' for a base {} literal", suffix, radix),
+            ))
+        }
+        Some((_, numeric_type)) => Some(*numeric_type),
+        None if suffix.starts_with(|c: char| c.is_ascii_digit()) => {
+            return Err(lex_failure(
+                &input,
+                offset,
+                format!("invalid digit for a base {} literal", radix),
+            ))
+        }
+        None => {
+            return Err(lex_failure(
+                &input,
+                offset,
+                format!("invalid suffix '{}' for a number literal", suffix),
+            ))
+        }
+    };
+    let out_of_range = |numeric_type: Option<NumericType>| {
+        let reason = match numeric_type {
+            Some(numeric_type) => format!(
+                "literal {} is out of range for {}",
+                number,
+                TypeAnnotation::from(numeric_type)
+            ),
+            None => format!("literal {} is too large", number),
+        };
+        lex_failure(&input, 0, reason)
     };
+    let value = match suffix {
+        Some(numeric_type) if !numeric_type.is_integer() => float_literal(number, numeric_type)?,
+        _ if is_float => float_literal(number, NumericType::F64)?,
+        suffix => {
+            let integer = u64::from_str_radix(&digits, radix).map_err(|_| out_of_range(suffix))?;
+            let integer = i128::from(integer);
+            match suffix {
+                // Unsuffixed literals are the narrowest of i32, i64 and u64 that fits them
+                None => Value::from_integer(integer, NumericType::I32)
+                    .or_else(|| Value::from_integer(integer, NumericType::I64))
+                    .unwrap_or(Value::UInt64(integer as u64)),
+                Some(suffix) => match Value::from_integer(integer, suffix) {
+                    Some(value) => value,
+                    // A literal one past a signed type's maximum is its minimum once negated, as
+                    // in `-128i8`. It's kept wrapped around and the parser checks it's negated
+                    None if suffix
+                        .integer_range()
+                        .is_some_and(|(min, _)| integer == -min) =>
+                    {
+                        Value::convert_integer(integer, suffix)
+                    }
+                    None => return Err(out_of_range(Some(suffix))),
+                },
+            }
+        }
+    };
+    let token_type = match value.numeric_type() {
+        Some(NumericType::F32 | NumericType::F64) => TokenType::Float,
+        _ => TokenType::Integer,
+    };
+    let content = input.slice(..suffix_end);
+    let (input, end) = preceded(multispace0, position)(input.slice(suffix_end..))?;
     Ok((
         input,
         Token {
             token_type,
-            span: SourceSpan::new(begin, digit, end),
+            span: SourceSpan::new(begin, content, end),
             value,
         },
     ))
@@ -420,6 +1019,8 @@ fn lex_integer<'a>(input: Span<&'a str>) -> IResult<Span<&'a str>, Token, LangEr
 pub struct Scanner<'a> {
     pub source: &'a str,
     pub tokens: Vec<Token<'a>>,
+    /// Comments removed from the token stream by `scan_tokens`, kept for tooling
+    pub comments: Vec<Token<'a>>,
 }
 
 impl<'a> Scanner<'a> {
@@ -427,6 +1028,7 @@ impl<'a> Scanner<'a> {
         Scanner {
             source: script_content,
             tokens: Vec::new(),
+            comments: Vec::new(),
         }
     }
 
@@ -460,12 +1062,39 @@ impl<'a> Scanner<'a> {
         Ok(())
     }
 
-    pub fn scan_tokens(&mut self) -> Result<Vec<Token>, LangError> {
+    /// Returns the doc comments directly preceding `token`, e.g. those documenting a `fn` or
+    /// `struct`, in source order
+    pub fn doc_comments_for(&self, token: &Token) -> Vec<&Token<'a>> {
+        let mut doc_comments = Vec::new();
+        let mut next_offset = token.span.begin.offset;
+        for comment in self.comments.iter().rev() {
+            if comment.span.end.offset > next_offset {
+                continue;
+            }
+            if comment.span.end.offset < next_offset || comment.token_type != TokenType::DocComment
+            {
+                break;
+            }
+            doc_comments.push(comment);
+            next_offset = comment.span.begin.offset;
+        }
+        doc_comments.reverse();
+        doc_comments
+    }
+
+    pub fn scan_tokens(&mut self) -> Result<Vec<Token<'a>>, LangError> {
         let root_span: Span<&str> = Span::new(self.source, 0, 1, 0);
         match lex_program(root_span) {
-            Ok(mut t) => {
-                self.fixup_types(&mut t.1)?;
-                Ok(t.1)
+            Ok((_, tokens)) => {
+                let (comments, mut tokens): (Vec<Token>, Vec<Token>) =
+                    tokens.into_iter().partition(|token| {
+                        token.token_type == TokenType::LineComment
+                            || token.token_type == TokenType::BlockComment
+                            || token.token_type == TokenType::DocComment
+                    });
+                self.comments = comments;
+                self.fixup_types(&mut tokens)?;
+                Ok(tokens)
             }
             Err(e) => Err(e.into()),
         }
@@ -511,14 +1140,32 @@ mod scanner_tests {
     }
 
     gen_lex_token_test!(test_lex_char, lex_char, "'a'", TokenType::Char, true);
-    gen_lex_token_test!(test_lex_float, lex_float, "100.00", TokenType::Float, true);
+    gen_lex_token_test!(test_lex_label, lex_label, "'outer", TokenType::Label, true);
     gen_lex_token_test!(
-        test_lex_integer,
-        lex_integer,
-        "10",
-        TokenType::Integer,
+        test_lex_label_failure,
+        lex_label,
+        "'",
+        TokenType::Label,
+        false
+    );
+    gen_lex_token_test!(test_lex_float, lex_digit, "100.00", TokenType::Float, true);
+    gen_lex_token_test!(test_lex_integer, lex_digit, "10", TokenType::Integer, true);
+    gen_lex_token_test!(
+        test_lex_usize_type,
+        lex_type,
+        "usize",
+        TokenType::Type(TypeAnnotation::USize),
         true
     );
+
+    #[test]
+    fn test_lex_integer_suffix() {
+        let (_, token) = lex_digit(Span::new("255u8", 0, 1, 0)).unwrap();
+        assert_eq!(token.token_type, TokenType::Integer);
+        assert_eq!(token.value, Value::UInt8(255));
+        assert!(lex_digit(Span::new("256u8", 0, 1, 0)).is_err());
+    }
+
     gen_lex_token_test!(
         test_long_lex,
         entry,
@@ -544,8 +1191,17 @@ mod scanner_tests {
         true
     );
     gen_lex_token_test!(test_lex_break, lex_keyword, "break", TokenType::Break, true);
+    gen_lex_token_test!(
+        test_lex_continue,
+        lex_keyword,
+        "continue",
+        TokenType::Continue,
+        true
+    );
+    gen_lex_token_test!(test_lex_loop, lex_keyword, "loop", TokenType::Loop, true);
     gen_lex_token_test!(test_lex_enum, lex_keyword, "enum", TokenType::Enum, true);
     gen_lex_token_test!(test_lex_for, lex_keyword, "for", TokenType::For, true);
+    gen_lex_token_test!(test_lex_in, lex_keyword, "in", TokenType::In, true);
     gen_lex_token_test!(test_lex_while, lex_keyword, "while", TokenType::While, true);
     gen_lex_token_test!(test_lex_fn, lex_keyword, "fn", TokenType::Fn, true);
     gen_lex_token_test!(test_lex_or, lex_keyword, "or", TokenType::Or, true);
@@ -576,6 +1232,8 @@ mod scanner_tests {
         TokenType::Import,
         true
     );
+    gen_lex_token_test!(test_lex_match, lex_keyword, "match", TokenType::Match, true);
+    gen_lex_token_test!(test_lex_as, lex_keyword, "as", TokenType::As, true);
 
     gen_lex_token_test!(
         test_lex_keyword_fail,
@@ -630,6 +1288,14 @@ mod scanner_tests {
     // gen_lex_token_test!(test_lex_comparison, lex_comparison, "", TokenType::, true);
     gen_lex_token_test!(test_lex_comma, lex_comma, ",", TokenType::Comma, true);
     gen_lex_token_test!(test_lex_dot, lex_dot, ".", TokenType::Dot, true);
+    gen_lex_token_test!(test_lex_dot_dot, lex_dots, "..", TokenType::DotDot, true);
+    gen_lex_token_test!(
+        test_lex_dot_dot_equal,
+        lex_dots,
+        "..=",
+        TokenType::DotDotEqual,
+        true
+    );
     gen_lex_token_test!(
         test_lex_return_type,
         lex_return_type,
@@ -637,6 +1303,13 @@ mod scanner_tests {
         TokenType::ReturnType,
         true
     );
+    gen_lex_token_test!(
+        test_lex_fat_arrow,
+        lex_symbol,
+        "=>",
+        TokenType::FatArrow,
+        true
+    );
     gen_lex_token_test!(test_lex_minus, lex_minus, "-", TokenType::Minus, true);
     gen_lex_token_test!(test_lex_plus, lex_plus, "+", TokenType::Plus, true);
     gen_lex_token_test!(
@@ -656,12 +1329,38 @@ mod scanner_tests {
     );
     gen_lex_token_test!(test_lex_star, lex_star, "*", TokenType::Star, true);
     gen_lex_token_test!(test_lex_slash, lex_slash, "/", TokenType::Slash, true);
-    gen_lex_token_test!(test_lex_or_symbol, lex_or_symbol, "|", TokenType::Or, true);
+    gen_lex_token_test!(test_lex_or_symbol, lex_symbol, "||", TokenType::Or, true);
+    gen_lex_token_test!(test_lex_and_symbol, lex_symbol, "&&", TokenType::And, true);
+    gen_lex_token_test!(test_lex_percent, lex_symbol, "%", TokenType::Percent, true);
     gen_lex_token_test!(
-        test_lex_and_symbol,
-        lex_and_symbol,
+        test_lex_ampersand,
+        lex_symbol,
         "&",
-        TokenType::And,
+        TokenType::Ampersand,
+        true
+    );
+    gen_lex_token_test!(test_lex_pipe, lex_symbol, "|", TokenType::Pipe, true);
+    gen_lex_token_test!(test_lex_caret, lex_symbol, "^", TokenType::Caret, true);
+    gen_lex_token_test!(test_lex_tilde, lex_symbol, "~", TokenType::Tilde, true);
+    gen_lex_token_test!(
+        test_lex_shift_left,
+        lex_symbol,
+        "<<",
+        TokenType::LessLess,
+        true
+    );
+    gen_lex_token_test!(
+        test_lex_shift_right_equal,
+        lex_symbol,
+        ">>=",
+        TokenType::GreaterGreaterEqual,
+        true
+    );
+    gen_lex_token_test!(
+        test_lex_plus_equal,
+        lex_symbol,
+        "+=",
+        TokenType::PlusEqual,
         true
     );
     gen_lex_token_test!(test_lex_ternary, lex_ternary, "?", TokenType::Ternary, true);
@@ -717,4 +1416,323 @@ mod scanner_tests {
         TokenType::Greater,
         true
     );
+    gen_lex_token_test!(
+        test_lex_line_comment,
+        lex_comment,
+        "// a comment\nlet",
+        TokenType::LineComment,
+        true
+    );
+    gen_lex_token_test!(
+        test_lex_line_doc_comment,
+        lex_comment,
+        "/// documents the next item",
+        TokenType::DocComment,
+        true
+    );
+    gen_lex_token_test!(
+        test_lex_quadruple_slash_comment,
+        lex_comment,
+        "//// not a doc comment",
+        TokenType::LineComment,
+        true
+    );
+    gen_lex_token_test!(
+        test_lex_block_comment,
+        lex_comment,
+        "/* a\nmulti-line comment */",
+        TokenType::BlockComment,
+        true
+    );
+    gen_lex_token_test!(
+        test_lex_nested_block_comment,
+        lex_comment,
+        "/* outer /* inner */ still outer */",
+        TokenType::BlockComment,
+        true
+    );
+    gen_lex_token_test!(
+        test_lex_block_doc_comment,
+        lex_comment,
+        "/** documents the next item */",
+        TokenType::DocComment,
+        true
+    );
+    gen_lex_token_test!(
+        test_lex_empty_block_comment,
+        lex_comment,
+        "/**/",
+        TokenType::BlockComment,
+        true
+    );
+    gen_lex_token_test!(
+        test_lex_unterminated_block_comment,
+        lex_comment,
+        "/* outer /* inner */",
+        TokenType::BlockComment,
+        false
+    );
+    gen_lex_token_test!(
+        test_lex_slash_not_comment,
+        lex_comment,
+        "/ 2",
+        TokenType::Slash,
+        false
+    );
+
+    #[test]
+    fn test_lex_nested_block_comment_span() {
+        let test_string = "/* a /* b */ c */ d";
+        let string = Span::new(test_string, 0, 1, 0);
+        let (rest, token) = lex_comment(string).unwrap();
+        assert_eq!(token.span.content.input, "/* a /* b */ c */");
+        assert_eq!(rest.input, "d");
+    }
+
+    #[test]
+    fn test_scan_tokens_skips_comments() {
+        let source = "// leading\nlet a: i32 = /* inline */ 1; // trailing\n/* last */";
+        let mut scanner = Scanner::new(source);
+        let tokens = scanner.scan_tokens().unwrap();
+        let token_types: Vec<TokenType> = tokens.into_iter().map(|t| t.token_type).collect();
+        assert_eq!(
+            token_types,
+            vec![
+                TokenType::Let,
+                TokenType::Identifier,
+                TokenType::Colon,
+                TokenType::Type(TypeAnnotation::I32),
+                TokenType::Equal,
+                TokenType::Integer,
+                TokenType::SemiColon,
+                TokenType::Eof,
+            ]
+        );
+        assert_eq!(scanner.comments.len(), 4);
+        assert_eq!(scanner.comments[1].span.content.input, "/* inline */");
+        assert_eq!(scanner.comments[1].span.begin.line, 2);
+    }
+
+    #[test]
+    fn test_scan_tokens_range() {
+        let mut scanner = Scanner::new("for i in 0..=10");
+        let tokens = scanner.scan_tokens().unwrap();
+        let token_types: Vec<TokenType> = tokens.into_iter().map(|t| t.token_type).collect();
+        assert_eq!(
+            token_types,
+            vec![
+                TokenType::For,
+                TokenType::Identifier,
+                TokenType::In,
+                TokenType::Integer,
+                TokenType::DotDotEqual,
+                TokenType::Integer,
+                TokenType::Eof,
+            ]
+        );
+    }
+
+    fn lexed_value(source: &str) -> Value {
+        let mut scanner = Scanner::new(source);
+        scanner.scan_tokens().unwrap()[0].value.clone()
+    }
+
+    fn lex_error(source: &str) -> String {
+        let mut scanner = Scanner::new(source);
+        match scanner.scan_tokens() {
+            Err(e) => e.to_string(),
+            Ok(_) => panic!("expected {:?} to fail to lex", source),
+        }
+    }
+
+    #[test]
+    fn test_lex_string_contents() {
+        assert_eq!(
+            lexed_value("\"Hi, world!\""),
+            Value::String("Hi, world!".into())
+        );
+        assert_eq!(
+            lexed_value("\"  padded  \""),
+            Value::String("  padded  ".into())
+        );
+        assert_eq!(lexed_value("\"héllo ☃\""), Value::String("héllo ☃".into()));
+        assert_eq!(
+            lexed_value("\"first\nsecond\""),
+            Value::String("first\nsecond".into())
+        );
+    }
+
+    #[test]
+    fn test_lex_string_escapes() {
+        assert_eq!(
+            lexed_value(r#""\t\r\n\0\\\"\'""#),
+            Value::String("\t\r\n\0\\\"'".into())
+        );
+        assert_eq!(
+            lexed_value(r#""\u{48}\u{1F600}""#),
+            Value::String("H😀".into())
+        );
+        assert_eq!(
+            lexed_value("\"joined \\\n    together\""),
+            Value::String("joined together".into())
+        );
+    }
+
+    #[test]
+    fn test_lex_raw_string() {
+        assert_eq!(
+            lexed_value(r#"r"C:\path\n""#),
+            Value::String(r"C:\path\n".into())
+        );
+        assert_eq!(
+            lexed_value(r###"r#"say "hi""#"###),
+            Value::String(r#"say "hi""#.into())
+        );
+    }
+
+    #[test]
+    fn test_lex_char_escapes() {
+        assert_eq!(lexed_value(r"'\n'"), Value::Char('\n'));
+        assert_eq!(lexed_value(r"'\''"), Value::Char('\''));
+        assert_eq!(lexed_value(r"'\u{e9}'"), Value::Char('é'));
+        assert_eq!(lexed_value("' '"), Value::Char(' '));
+    }
+
+    #[test]
+    fn test_lex_string_errors() {
+        assert!(lex_error(r#"let a = "bad \q";"#)
+            .contains("line 1, column 14: unknown character escape '\\q'"));
+        assert!(lex_error("let a = 1;\nlet b = \"open;")
+            .contains("line 2, column 9: unterminated string"));
+        assert!(lex_error(r#"let a = "\u{110000}";"#)
+            .contains("line 1, column 10: invalid unicode escape"));
+        assert!(lex_error(r#"let a = r#"open";"#).contains("unterminated raw string"));
+        assert!(lex_error(r"let c = '\q';").contains("line 1, column 10: unknown character escape"));
+    }
+
+    #[test]
+    fn test_lex_char_errors() {
+        assert!(lex_error("let c = '';").contains("line 1, column 9: empty character literal"));
+        assert!(lex_error("let c = 'ab';")
+            .contains("line 1, column 9: a character literal holds a single character"));
+        assert!(lex_error(r"let c = '\nx';").contains("line 1, column 9: a character literal"));
+    }
+
+    #[test]
+    fn test_scan_tokens_eof_location() {
+        let mut scanner = Scanner::new(
+            "let a = 1;
+let b =",
+        );
+        let tokens = scanner.scan_tokens().unwrap();
+        let eof = tokens.last().unwrap();
+        assert_eq!(eof.token_type, TokenType::Eof);
+        assert_eq!(eof.span.begin.line, 2);
+        assert_eq!(eof.span.begin.column, 7);
+    }
+
+    #[test]
+    fn test_lex_number_forms() {
+        assert_eq!(lexed_value("0xFF"), Value::Int32(255));
+        assert_eq!(lexed_value("0o17"), Value::Int32(15));
+        assert_eq!(lexed_value("0b1010"), Value::Int32(10));
+        assert_eq!(lexed_value("1_000_000"), Value::Int32(1_000_000));
+        assert_eq!(lexed_value("0xFF_u8"), Value::UInt8(255));
+        assert_eq!(lexed_value("10i64"), Value::Int64(10));
+        assert_eq!(lexed_value("1.5e-3"), Value::Float64(1.5e-3));
+        assert_eq!(lexed_value("2E3"), Value::Float64(2000.0));
+        assert_eq!(lexed_value("2.0f32"), Value::Float32(2.0));
+        assert_eq!(lexed_value("7f64"), Value::Float64(7.0));
+        assert_eq!(lexed_value("4294967296"), Value::Int64(4_294_967_296));
+    }
+
+    #[test]
+    fn test_lex_number_errors() {
+        assert!(
+            lex_error("let a = 0b102;").contains("line 1, column 13: invalid digit for a base 2")
+        );
+        assert!(lex_error("let a = 1.5i32;").contains("invalid suffix 'i32' for a float literal"));
+        assert!(lex_error("let a = 10abc;").contains("line 1, column 11: invalid suffix 'abc'"));
+        assert!(lex_error("let a = 0x;").contains("expected digits after '0x'"));
+        assert!(lex_error("let a = 256u8;").contains("literal 256 is out of range for u8"));
+    }
+
+    #[test]
+    fn test_literal_suffix() {
+        assert_eq!(literal_suffix("10u8"), Some(NumericType::U8));
+        assert_eq!(literal_suffix("2.5f32"), Some(NumericType::F32));
+        assert_eq!(literal_suffix("0x1f64"), None);
+        assert_eq!(literal_suffix("0xFFi64"), Some(NumericType::I64));
+        assert_eq!(literal_suffix("1e64"), None);
+    }
+
+    #[test]
+    fn test_lex_format_string() {
+        let mut scanner = Scanner::new("f\"a {{{x + 1:>4}}} {m[\"k\"]}\"");
+        let tokens = scanner.scan_tokens().unwrap();
+        let token_types: Vec<TokenType> = tokens.iter().map(|t| t.token_type.clone()).collect();
+        assert_eq!(
+            token_types,
+            vec![
+                TokenType::FormatStart,
+                TokenType::FormatLiteral,
+                TokenType::LeftBrace,
+                TokenType::Identifier,
+                TokenType::Plus,
+                TokenType::Integer,
+                TokenType::FormatSpec,
+                TokenType::RightBrace,
+                TokenType::FormatLiteral,
+                TokenType::LeftBrace,
+                TokenType::Identifier,
+                TokenType::LeftBracket,
+                TokenType::String,
+                TokenType::RightBracket,
+                TokenType::RightBrace,
+                TokenType::FormatEnd,
+                TokenType::Eof,
+            ]
+        );
+        assert_eq!(tokens[1].value, Value::String("a {".to_string()));
+        assert_eq!(tokens[6].span.content.input, ">4");
+        assert_eq!(tokens[8].value, Value::String("} ".to_string()));
+        assert!(lex_error("f\"a } b\"").contains("unmatched '}' in a format string"));
+        assert!(lex_error("f\"{x $}\"").contains("expected '}' to close a format string argument"));
+    }
+
+    #[test]
+    fn test_doc_comments_for() {
+        let source = "/// not attached\nlet a: i32 = 1;\n// plain\n/// first\n/** second */\nfn f() -> i32 { return 1; }";
+        let mut scanner = Scanner::new(source);
+        let tokens = scanner.scan_tokens().unwrap();
+        let let_token = &tokens[0];
+        let fn_token = tokens
+            .iter()
+            .find(|t| t.token_type == TokenType::Fn)
+            .unwrap();
+        let let_docs = scanner.doc_comments_for(let_token);
+        assert_eq!(let_docs.len(), 1);
+        assert_eq!(let_docs[0].span.content.input, "/// not attached");
+        let fn_docs: Vec<&str> = scanner
+            .doc_comments_for(fn_token)
+            .iter()
+            .map(|t| t.span.content.input)
+            .collect();
+        assert_eq!(fn_docs, vec!["/// first", "/** second */"]);
+    }
+
+    #[test]
+    fn test_scan_tokens_columns() {
+        let source = "let a: i32 = 1;
+  a = 20;";
+        let mut scanner = Scanner::new(source);
+        let tokens = scanner.scan_tokens().unwrap();
+        let positions: Vec<(u32, u32)> = tokens
+            .iter()
+            .map(|t| (t.span.begin.line, t.span.begin.column))
+            .collect();
+        assert_eq!(positions[1], (1, 4));
+        assert_eq!(positions[7], (2, 2));
+        assert_eq!(positions[9], (2, 6));
+    }
 }
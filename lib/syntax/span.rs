@@ -39,6 +39,26 @@ impl<T: fmt::Display> fmt::Display for Span<T> {
     }
 }
 
+/// The position in source text that a node of the AST was parsed from. Both the line and the
+/// column start counting from 1
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SourceLocation {
+    pub line: u32,
+    pub column: u32,
+}
+
+impl SourceLocation {
+    pub fn new(line: u32, column: u32) -> SourceLocation {
+        SourceLocation { line, column }
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 impl<T> Span<T> {
     pub fn new(input: T, offset: usize, line: u32, column: u32) -> Span<T> {
         Span {
@@ -134,8 +154,12 @@ macro_rules! gen_slice_impl {
                 let bytes = slice.as_bytes();
                 let number_of_lines = bytecount::count(bytes, b'\n') as u32;
                 let next_line = self.line + number_of_lines;
+                let next_column = match bytes.iter().rposition(|byte| *byte == b'\n') {
+                    Some(newline) => (offset - newline - 1) as u32,
+                    None => self.column + offset as u32,
+                };
 
-                Span::new(next_input, next_offset, next_line, 0)
+                Span::new(next_input, next_offset, next_line, next_column)
             }
         }
     };
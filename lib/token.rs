@@ -1,5 +1,7 @@
 use crate::error::*;
 use crate::syntax::parser::TokenIR;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt::{self, Display};
 
 // TODO: Revisit hashing Token
@@ -8,8 +10,15 @@ use std::fmt::{self, Display};
 /// Types we currently support
 /// TODO: Check user types to make sure they're actually defined
 pub enum TypeAnnotation {
+    I8,
+    I16,
     I32,
     I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    USize,
     F32,
     F64,
     String,
@@ -19,11 +28,160 @@ pub enum TypeAnnotation {
     Unit,
     Fn,
     Array(Box<TypeAnnotation>),
+    /// A map from keys of the first type to values of the second, e.g. `Map<String, i32>`
+    Map(Box<TypeAnnotation>, Box<TypeAnnotation>),
+    /// A fixed-size group of values of possibly different types, e.g. `(i64, String)`
+    Tuple(Vec<TypeAnnotation>),
+    /// A range of integers of the given type, e.g. the `Range<i32>` of `0..10`
+    Range(Box<TypeAnnotation>),
+    /// The type of a function value, e.g. `fn(i32) -> bool`
+    Function {
+        params: Vec<TypeAnnotation>,
+        return_type: Box<TypeAnnotation>,
+    },
     SelfIndex,
     User(String),
+    /// A type parameter of a generic struct, function or impl, e.g. the `T` of
+    /// `fn first<T>(a: Array<T>) -> T`
+    TypeParam(String),
+    /// A generic struct given type arguments, e.g. `Stack<i32>`
+    Generic {
+        name: String,
+        type_args: Vec<TypeAnnotation>,
+    },
+}
+
+/// The types of numbers, which the arithmetic operators apply to and `as` casts convert between
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NumericType {
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    USize,
+    F32,
+    F64,
+}
+
+impl NumericType {
+    pub fn is_integer(self) -> bool {
+        !matches!(self, NumericType::F32 | NumericType::F64)
+    }
+
+    pub fn is_signed(self) -> bool {
+        !matches!(
+            self,
+            NumericType::U8
+                | NumericType::U16
+                | NumericType::U32
+                | NumericType::U64
+                | NumericType::USize
+        )
+    }
+
+    /// The width of values of this type. A `usize` is as wide as a `u64`
+    fn bits(self) -> u32 {
+        match self {
+            NumericType::I8 | NumericType::U8 => 8,
+            NumericType::I16 | NumericType::U16 => 16,
+            NumericType::I32 | NumericType::U32 | NumericType::F32 => 32,
+            NumericType::I64 | NumericType::U64 | NumericType::USize | NumericType::F64 => 64,
+        }
+    }
+
+    /// Whether every value of this type is also a value of `other`, so it converts to it
+    /// implicitly. Integers widen to wider integers of the same signedness, and unsigned ones to
+    /// wider signed ones too, e.g. a `u8` to an `i16`. Integers and floats never mix
+    pub fn widens_to(self, other: NumericType) -> bool {
+        if self == other {
+            return true;
+        }
+        match (self.is_integer(), other.is_integer()) {
+            (true, true) => self.bits() < other.bits() && (!self.is_signed() || other.is_signed()),
+            (false, false) => self.bits() < other.bits(),
+            _ => false,
+        }
+    }
+
+    /// The type an arithmetic operator promotes operands of these two types to, the wider of the
+    /// two, or `None` when neither widens to the other
+    pub fn promote(self, other: NumericType) -> Option<NumericType> {
+        if other.widens_to(self) {
+            Some(self)
+        } else if self.widens_to(other) {
+            Some(other)
+        } else {
+            None
+        }
+    }
+
+    /// `amount` as the number of bits to shift a value of this type by with `<<` or `>>`, or
+    /// `None` when it's negative or not less than the type's width, which overflows
+    pub fn shift_amount(self, amount: i128) -> Option<u32> {
+        u32::try_from(amount)
+            .ok()
+            .filter(|amount| *amount < self.bits())
+    }
+
+    /// The smallest and largest values of an integer type
+    pub fn integer_range(self) -> Option<(i128, i128)> {
+        let range = match self {
+            NumericType::I8 => (i8::MIN.into(), i8::MAX.into()),
+            NumericType::I16 => (i16::MIN.into(), i16::MAX.into()),
+            NumericType::I32 => (i32::MIN.into(), i32::MAX.into()),
+            NumericType::I64 => (i64::MIN.into(), i64::MAX.into()),
+            NumericType::U8 => (0, u8::MAX.into()),
+            NumericType::U16 => (0, u16::MAX.into()),
+            NumericType::U32 => (0, u32::MAX.into()),
+            NumericType::U64 => (0, u64::MAX.into()),
+            NumericType::USize => (0, usize::MAX as i128),
+            NumericType::F32 | NumericType::F64 => return None,
+        };
+        Some(range)
+    }
+}
+
+impl From<NumericType> for TypeAnnotation {
+    fn from(numeric_type: NumericType) -> TypeAnnotation {
+        match numeric_type {
+            NumericType::I8 => TypeAnnotation::I8,
+            NumericType::I16 => TypeAnnotation::I16,
+            NumericType::I32 => TypeAnnotation::I32,
+            NumericType::I64 => TypeAnnotation::I64,
+            NumericType::U8 => TypeAnnotation::U8,
+            NumericType::U16 => TypeAnnotation::U16,
+            NumericType::U32 => TypeAnnotation::U32,
+            NumericType::U64 => TypeAnnotation::U64,
+            NumericType::USize => TypeAnnotation::USize,
+            NumericType::F32 => TypeAnnotation::F32,
+            NumericType::F64 => TypeAnnotation::F64,
+        }
+    }
 }
 
 impl TypeAnnotation {
+    /// The numeric type this is, if it's one
+    pub fn numeric_type(&self) -> Option<NumericType> {
+        match self {
+            TypeAnnotation::I8 => Some(NumericType::I8),
+            TypeAnnotation::I16 => Some(NumericType::I16),
+            TypeAnnotation::I32 => Some(NumericType::I32),
+            TypeAnnotation::I64 => Some(NumericType::I64),
+            TypeAnnotation::U8 => Some(NumericType::U8),
+            TypeAnnotation::U16 => Some(NumericType::U16),
+            TypeAnnotation::U32 => Some(NumericType::U32),
+            TypeAnnotation::U64 => Some(NumericType::U64),
+            TypeAnnotation::USize => Some(NumericType::USize),
+            TypeAnnotation::F32 => Some(NumericType::F32),
+            TypeAnnotation::F64 => Some(NumericType::F64),
+            _ => None,
+        }
+    }
+
     pub fn is_array(&self) -> bool {
         if let TypeAnnotation::Array(_) = self {
             return true;
@@ -40,6 +198,124 @@ impl TypeAnnotation {
         ))
     }
 
+    /// Whether values of this type are instances of a struct, generic or not
+    pub fn is_user_type(&self) -> bool {
+        matches!(
+            self,
+            TypeAnnotation::User(_) | TypeAnnotation::Generic { .. }
+        )
+    }
+
+    /// Whether a value of type `value_type` can be used where a value of this type is expected
+    /// while the program runs. Values don't carry the type arguments they were created with, so
+    /// type parameters admit a value of any type and a generic struct admits any of its instances
+    pub fn admits(&self, value_type: &TypeAnnotation) -> bool {
+        match (self, value_type) {
+            (TypeAnnotation::TypeParam(_), _) => true,
+            (TypeAnnotation::Generic { name, .. }, TypeAnnotation::User(value_name)) => {
+                name == value_name
+            }
+            (TypeAnnotation::Array(lhs), TypeAnnotation::Array(rhs)) => lhs.admits(rhs),
+            (TypeAnnotation::Range(lhs), TypeAnnotation::Range(rhs)) => lhs.admits(rhs),
+            (TypeAnnotation::Map(lhs_key, lhs_value), TypeAnnotation::Map(rhs_key, rhs_value)) => {
+                lhs_key.admits(rhs_key) && lhs_value.admits(rhs_value)
+            }
+            (TypeAnnotation::Tuple(lhs), TypeAnnotation::Tuple(rhs)) => {
+                lhs.len() == rhs.len()
+                    && lhs.iter().zip(rhs.iter()).all(|(lhs, rhs)| lhs.admits(rhs))
+            }
+            (
+                TypeAnnotation::Function {
+                    params: lhs_params,
+                    return_type: lhs_return_type,
+                },
+                TypeAnnotation::Function {
+                    params: rhs_params,
+                    return_type: rhs_return_type,
+                },
+            ) => {
+                lhs_params.len() == rhs_params.len()
+                    && lhs_params
+                        .iter()
+                        .zip(rhs_params.iter())
+                        .all(|(lhs, rhs)| lhs.admits(rhs))
+                    && lhs_return_type.admits(rhs_return_type)
+            }
+            _ => self == value_type,
+        }
+    }
+
+    /// Replaces the type parameters in this type with the types they're bound to in `bindings`
+    pub fn substitute(&self, bindings: &HashMap<String, TypeAnnotation>) -> TypeAnnotation {
+        match self {
+            TypeAnnotation::TypeParam(name) => {
+                bindings.get(name).cloned().unwrap_or_else(|| self.clone())
+            }
+            TypeAnnotation::Array(element_type) => {
+                TypeAnnotation::Array(Box::new(element_type.substitute(bindings)))
+            }
+            TypeAnnotation::Range(element_type) => {
+                TypeAnnotation::Range(Box::new(element_type.substitute(bindings)))
+            }
+            TypeAnnotation::Map(key_type, value_type) => TypeAnnotation::Map(
+                Box::new(key_type.substitute(bindings)),
+                Box::new(value_type.substitute(bindings)),
+            ),
+            TypeAnnotation::Tuple(element_types) => TypeAnnotation::Tuple(
+                element_types
+                    .iter()
+                    .map(|element_type| element_type.substitute(bindings))
+                    .collect(),
+            ),
+            TypeAnnotation::Generic { name, type_args } => TypeAnnotation::Generic {
+                name: name.clone(),
+                type_args: type_args
+                    .iter()
+                    .map(|type_arg| type_arg.substitute(bindings))
+                    .collect(),
+            },
+            TypeAnnotation::Function {
+                params,
+                return_type,
+            } => TypeAnnotation::Function {
+                params: params
+                    .iter()
+                    .map(|param| param.substitute(bindings))
+                    .collect(),
+                return_type: Box::new(return_type.substitute(bindings)),
+            },
+            other => other.clone(),
+        }
+    }
+
+    /// Whether this type refers to any of the type parameters in `type_params`
+    pub fn mentions_type_params(&self, type_params: &[String]) -> bool {
+        match self {
+            TypeAnnotation::TypeParam(name) => type_params.contains(name),
+            TypeAnnotation::Array(element_type) => element_type.mentions_type_params(type_params),
+            TypeAnnotation::Map(key_type, value_type) => {
+                key_type.mentions_type_params(type_params)
+                    || value_type.mentions_type_params(type_params)
+            }
+            TypeAnnotation::Tuple(element_types) => element_types
+                .iter()
+                .any(|element_type| element_type.mentions_type_params(type_params)),
+            TypeAnnotation::Generic { type_args, .. } => type_args
+                .iter()
+                .any(|type_arg| type_arg.mentions_type_params(type_params)),
+            TypeAnnotation::Function {
+                params,
+                return_type,
+            } => {
+                params
+                    .iter()
+                    .any(|param| param.mentions_type_params(type_params))
+                    || return_type.mentions_type_params(type_params)
+            }
+            _ => false,
+        }
+    }
+
     pub fn from_token_type(token_type: &TokenType) -> Result<TypeAnnotation, LangError> {
         match token_type {
             TokenType::Type(type_annotation) => Ok(type_annotation.clone()),
@@ -75,6 +351,13 @@ impl Display for TypeAnnotation {
             TypeAnnotation::I32 => write!(f, "i32"),
             TypeAnnotation::SelfIndex => write!(f, "self"),
             TypeAnnotation::I64 => write!(f, "i64"),
+            TypeAnnotation::I8 => write!(f, "i8"),
+            TypeAnnotation::I16 => write!(f, "i16"),
+            TypeAnnotation::U8 => write!(f, "u8"),
+            TypeAnnotation::U16 => write!(f, "u16"),
+            TypeAnnotation::U32 => write!(f, "u32"),
+            TypeAnnotation::U64 => write!(f, "u64"),
+            TypeAnnotation::USize => write!(f, "usize"),
             TypeAnnotation::F32 => write!(f, "f32"),
             TypeAnnotation::F64 => write!(f, "f64"),
             TypeAnnotation::Bool => write!(f, "bool"),
@@ -84,7 +367,42 @@ impl Display for TypeAnnotation {
             TypeAnnotation::String => write!(f, "String"),
             TypeAnnotation::Char => write!(f, "char"),
             TypeAnnotation::Array(array) => write!(f, "Array<{}>", array.to_string()),
+            TypeAnnotation::Map(key, value) => write!(f, "Map<{}, {}>", key, value),
+            TypeAnnotation::Range(element) => write!(f, "Range<{}>", element),
+            TypeAnnotation::Tuple(elements) => write!(
+                f,
+                "({})",
+                elements
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            TypeAnnotation::Function {
+                params,
+                return_type,
+            } => write!(
+                f,
+                "fn({}) -> {}",
+                params
+                    .iter()
+                    .map(|param| param.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                return_type
+            ),
             TypeAnnotation::User(user_type) => write!(f, "{}", user_type.clone()),
+            TypeAnnotation::TypeParam(name) => write!(f, "{}", name),
+            TypeAnnotation::Generic { name, type_args } => write!(
+                f,
+                "{}<{}>",
+                name,
+                type_args
+                    .iter()
+                    .map(|type_arg| type_arg.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -107,6 +425,13 @@ impl PartialEq for TypeAnnotation {
                 TypeAnnotation::I64 => true,
                 _ => false,
             },
+            TypeAnnotation::I8
+            | TypeAnnotation::I16
+            | TypeAnnotation::U8
+            | TypeAnnotation::U16
+            | TypeAnnotation::U32
+            | TypeAnnotation::U64
+            | TypeAnnotation::USize => self.numeric_type() == other.numeric_type(),
             TypeAnnotation::F32 => match other {
                 TypeAnnotation::F32 => true,
                 _ => false,
@@ -134,6 +459,30 @@ impl PartialEq for TypeAnnotation {
                 TypeAnnotation::Array(rhs) => lhs == rhs,
                 _ => false,
             },
+            TypeAnnotation::Map(lhs_key, lhs_value) => match other {
+                TypeAnnotation::Map(rhs_key, rhs_value) => {
+                    lhs_key == rhs_key && lhs_value == rhs_value
+                }
+                _ => false,
+            },
+            TypeAnnotation::Tuple(lhs) => match other {
+                TypeAnnotation::Tuple(rhs) => lhs == rhs,
+                _ => false,
+            },
+            TypeAnnotation::Range(lhs) => match other {
+                TypeAnnotation::Range(rhs) => lhs == rhs,
+                _ => false,
+            },
+            TypeAnnotation::Function {
+                params: lhs_params,
+                return_type: lhs_return_type,
+            } => match other {
+                TypeAnnotation::Function {
+                    params: rhs_params,
+                    return_type: rhs_return_type,
+                } => lhs_params == rhs_params && lhs_return_type == rhs_return_type,
+                _ => false,
+            },
             // TODO: User types don't have default values,
             // we initialize them as unit and then hope for the best
             // Best thing to do is not allow uninitialized structs.
@@ -143,8 +492,24 @@ impl PartialEq for TypeAnnotation {
                 TypeAnnotation::Unit => true,
                 _ => false,
             },
+            TypeAnnotation::TypeParam(lhs) => match other {
+                TypeAnnotation::TypeParam(rhs) => lhs == rhs,
+                _ => false,
+            },
+            TypeAnnotation::Generic {
+                name: lhs_name,
+                type_args: lhs_type_args,
+            } => match other {
+                TypeAnnotation::Generic {
+                    name: rhs_name,
+                    type_args: rhs_type_args,
+                } => lhs_name == rhs_name && lhs_type_args == rhs_type_args,
+                TypeAnnotation::Unit => true,
+                _ => false,
+            },
             TypeAnnotation::Unit => match other {
                 TypeAnnotation::User(_) => true,
+                TypeAnnotation::Generic { .. } => true,
                 TypeAnnotation::Trait => true,
                 TypeAnnotation::Unit => true,
                 _ => false,
@@ -159,6 +524,10 @@ impl Eq for TypeAnnotation {}
 /// All token types we currently extract from source text
 pub enum TokenType {
     Break,
+    Continue,
+    Loop,
+    /// The label of a loop, e.g. `'outer`
+    Label,
     Assert,
     LeftParen,
     RightParen,
@@ -170,15 +539,21 @@ pub enum TokenType {
     Colon,
     Comma,
     Dot,
+    /// The `..` of an exclusive range, e.g. `0..n`
+    DotDot,
+    /// The `..=` of an inclusive range, e.g. `1..=10`
+    DotDotEqual,
     Minus,
     Plus,
     SemiColon,
     Slash,
     Star,
+    Percent,
     Bang,
     BangEqual,
     Equal,
     EqualEqual,
+    FatArrow,
     Greater,
     GreaterEqual,
     Less,
@@ -186,6 +561,15 @@ pub enum TokenType {
     LessEqual,
     Identifier,
     String,
+    /// The `f"` opening a format string, whose pieces follow it up to a `FormatEnd`. Its
+    /// arguments are lexed as the tokens of their expressions between braces
+    FormatStart,
+    /// A piece of text of a format string, with its escapes decoded
+    FormatLiteral,
+    /// The spec after the `:` of a format string argument, e.g. the `.2` of `{x:.2}`
+    FormatSpec,
+    /// The `"` closing a format string
+    FormatEnd,
     Float,
     Integer,
     And,
@@ -198,7 +582,10 @@ pub enum TokenType {
     Import,
     Fn,
     For,
+    /// The `in` of a `for` loop over an iterable, e.g. `for item in items`
+    In,
     If,
+    Match,
     Unit,
     Ternary,
     Or,
@@ -209,9 +596,34 @@ pub enum TokenType {
     True,
     Let,
     While,
+    /// The `as` of a cast, e.g. `count as f64`
+    As,
+    /// Bitwise and, `&`. The logical and is `&&`
+    Ampersand,
+    /// Bitwise or, `|`. The logical or is `||`
+    Pipe,
+    Caret,
+    /// Bitwise not, `~`
+    Tilde,
+    LessLess,
+    GreaterGreater,
+    PlusEqual,
+    MinusEqual,
+    StarEqual,
+    SlashEqual,
+    PercentEqual,
+    AmpersandEqual,
+    PipeEqual,
+    CaretEqual,
+    LessLessEqual,
+    GreaterGreaterEqual,
     PathSeparator,
     Type(TypeAnnotation),
     SelfIdent,
+    LineComment,
+    BlockComment,
+    /// A `///` or `/** */` comment
+    DocComment,
     Eof,
 }
 
@@ -225,12 +637,55 @@ impl TokenType {
             ))),
         }
     }
+
+    /// The built-in trait a user type implements to overload this binary operator, along with
+    /// the name of the trait's method, e.g. `Add` and `add` for `+`
+    pub fn operator_trait(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            TokenType::Plus => Some(("Add", "add")),
+            TokenType::Minus => Some(("Sub", "sub")),
+            TokenType::Star => Some(("Mul", "mul")),
+            TokenType::Slash => Some(("Div", "div")),
+            TokenType::Percent => Some(("Rem", "rem")),
+            TokenType::Ampersand => Some(("BitAnd", "bitand")),
+            TokenType::Pipe => Some(("BitOr", "bitor")),
+            TokenType::Caret => Some(("BitXor", "bitxor")),
+            TokenType::LessLess => Some(("Shl", "shl")),
+            TokenType::GreaterGreater => Some(("Shr", "shr")),
+            TokenType::EqualEqual | TokenType::BangEqual => Some(("Eq", "eq")),
+            TokenType::Greater
+            | TokenType::GreaterEqual
+            | TokenType::Less
+            | TokenType::LessEqual => Some(("Ord", "cmp")),
+            _ => None,
+        }
+    }
+
+    /// The binary operator a compound assignment applies before assigning, e.g. `+` for `+=`
+    pub fn compound_operator(&self) -> Option<TokenType> {
+        match self {
+            TokenType::PlusEqual => Some(TokenType::Plus),
+            TokenType::MinusEqual => Some(TokenType::Minus),
+            TokenType::StarEqual => Some(TokenType::Star),
+            TokenType::SlashEqual => Some(TokenType::Slash),
+            TokenType::PercentEqual => Some(TokenType::Percent),
+            TokenType::AmpersandEqual => Some(TokenType::Ampersand),
+            TokenType::PipeEqual => Some(TokenType::Pipe),
+            TokenType::CaretEqual => Some(TokenType::Caret),
+            TokenType::LessLessEqual => Some(TokenType::LessLess),
+            TokenType::GreaterGreaterEqual => Some(TokenType::GreaterGreater),
+            _ => None,
+        }
+    }
 }
 
 impl Display for TokenType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match &self {
             TokenType::Break => write!(f, "break"),
+            TokenType::Continue => write!(f, "continue"),
+            TokenType::Loop => write!(f, "loop"),
+            TokenType::Label => write!(f, "label"),
             TokenType::Assert => write!(f, "assert"),
             TokenType::Enum => write!(f, "enum"),
             TokenType::LeftParen => write!(f, "("),
@@ -241,17 +696,21 @@ impl Display for TokenType {
             TokenType::RightBracket => write!(f, "]"),
             TokenType::Comma => write!(f, ","),
             TokenType::Dot => write!(f, "."),
+            TokenType::DotDot => write!(f, ".."),
+            TokenType::DotDotEqual => write!(f, "..="),
             TokenType::Minus => write!(f, "-"),
             TokenType::Plus => write!(f, "+"),
             TokenType::Colon => write!(f, ":"),
             TokenType::SemiColon => write!(f, ";"),
             TokenType::Slash => write!(f, "/"),
             TokenType::Star => write!(f, "*"),
+            TokenType::Percent => write!(f, "%"),
             TokenType::Bang => write!(f, "!"),
             TokenType::BangEqual => write!(f, "!="),
             TokenType::ReturnType => write!(f, "->"),
             TokenType::Equal => write!(f, "="),
             TokenType::EqualEqual => write!(f, "=="),
+            TokenType::FatArrow => write!(f, "=>"),
             TokenType::Greater => write!(f, ">"),
             TokenType::GreaterEqual => write!(f, ">="),
             TokenType::Char => write!(f, "char"),
@@ -261,6 +720,10 @@ impl Display for TokenType {
             TokenType::LessEqual => write!(f, "<="),
             TokenType::Identifier => write!(f, "identifier"),
             TokenType::String => write!(f, "string"),
+            TokenType::FormatStart => write!(f, "f\""),
+            TokenType::FormatLiteral => write!(f, "format string text"),
+            TokenType::FormatSpec => write!(f, "format spec"),
+            TokenType::FormatEnd => write!(f, "\""),
             TokenType::Float => write!(f, "float"),
             TokenType::Integer => write!(f, "integer"),
             TokenType::And => write!(f, "and"),
@@ -270,7 +733,9 @@ impl Display for TokenType {
             TokenType::PathSeparator => write!(f, "::"),
             TokenType::Fn => write!(f, "fn"),
             TokenType::For => write!(f, "for"),
+            TokenType::In => write!(f, "in"),
             TokenType::If => write!(f, "if"),
+            TokenType::Match => write!(f, "match"),
             TokenType::Unit => write!(f, "()"),
             TokenType::Or => write!(f, "or"),
             TokenType::Import => write!(f, "import"),
@@ -282,8 +747,28 @@ impl Display for TokenType {
             TokenType::True => write!(f, "true"),
             TokenType::Let => write!(f, "let"),
             TokenType::While => write!(f, "while"),
+            TokenType::As => write!(f, "as"),
+            TokenType::Ampersand => write!(f, "&"),
+            TokenType::Pipe => write!(f, "|"),
+            TokenType::Caret => write!(f, "^"),
+            TokenType::Tilde => write!(f, "~"),
+            TokenType::LessLess => write!(f, "<<"),
+            TokenType::GreaterGreater => write!(f, ">>"),
+            TokenType::PlusEqual => write!(f, "+="),
+            TokenType::MinusEqual => write!(f, "-="),
+            TokenType::StarEqual => write!(f, "*="),
+            TokenType::SlashEqual => write!(f, "/="),
+            TokenType::PercentEqual => write!(f, "%="),
+            TokenType::AmpersandEqual => write!(f, "&="),
+            TokenType::PipeEqual => write!(f, "|="),
+            TokenType::CaretEqual => write!(f, "^="),
+            TokenType::LessLessEqual => write!(f, "<<="),
+            TokenType::GreaterGreaterEqual => write!(f, ">>="),
             TokenType::SelfIdent => write!(f, "self"),
             TokenType::Type(type_annotation) => write!(f, "{}", type_annotation.to_string()),
+            TokenType::LineComment => write!(f, "line comment"),
+            TokenType::BlockComment => write!(f, "block comment"),
+            TokenType::DocComment => write!(f, "doc comment"),
             TokenType::Eof => write!(f, "EoF"),
         }
     }
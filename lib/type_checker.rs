@@ -1,7 +1,146 @@
+use crate::ast::expr::*;
+use crate::ast::stmt::*;
 use crate::error::*;
+use crate::syntax::span::SourceLocation;
 use crate::token::*;
+use crate::value::{MapMethod, Value};
+use crate::visitor::*;
 
-pub struct TypeChecker;
+use std::collections::{HashMap, HashSet};
+
+/// The parameters and return type of a function, method or enum variant constructor
+#[derive(Clone, Debug)]
+struct Signature {
+    name: String,
+    /// Type parameters inferred from the arguments of each call. A method of a generic impl has
+    /// the impl's type parameters first, which are taken from the type of the object instead
+    type_params: Vec<String>,
+    params: Vec<VariableData>,
+    return_type: Option<TypeAnnotation>,
+}
+
+impl Signature {
+    fn from_function(function_stmt: &FunctionStmt) -> Signature {
+        Signature {
+            name: function_stmt.name.clone(),
+            type_params: function_stmt.type_params.clone(),
+            params: function_stmt.params.clone(),
+            return_type: function_stmt.return_type.to_type_annotation().ok(),
+        }
+    }
+
+    /// The signature of a call to `name`, a value of the function type `params` -> `return_type`
+    fn from_function_type(
+        name: String,
+        params: &[TypeAnnotation],
+        return_type: &TypeAnnotation,
+    ) -> Signature {
+        Signature {
+            name,
+            type_params: Vec::new(),
+            params: params
+                .iter()
+                .enumerate()
+                .map(|(index, param)| VariableData::new(index.to_string(), param.clone()))
+                .collect(),
+            return_type: Some(return_type.clone()),
+        }
+    }
+
+    /// The signature of `method` for a map from `key_type` to `value_type`
+    fn from_map_method(
+        method: MapMethod,
+        key_type: &TypeAnnotation,
+        value_type: &TypeAnnotation,
+    ) -> Signature {
+        let key = || VariableData::new("key".to_string(), key_type.clone());
+        let (params, return_type) = match method {
+            MapMethod::Insert => (
+                vec![
+                    key(),
+                    VariableData::new("value".to_string(), value_type.clone()),
+                ],
+                TypeAnnotation::Unit,
+            ),
+            MapMethod::Remove | MapMethod::Contains => (vec![key()], TypeAnnotation::Bool),
+            MapMethod::Len => (vec![], TypeAnnotation::I64),
+            MapMethod::Keys => (vec![], TypeAnnotation::Array(Box::new(key_type.clone()))),
+            MapMethod::Values => (vec![], TypeAnnotation::Array(Box::new(value_type.clone()))),
+        };
+        Signature {
+            name: format!("Map::{}", method.name()),
+            type_params: Vec::new(),
+            params,
+            return_type: Some(return_type),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Symbol {
+    /// A variable, parameter or pattern binding. The type is `None` when it couldn't be inferred
+    Variable(Option<TypeAnnotation>),
+    Function(Signature),
+}
+
+/// A loop around the statement being checked
+#[derive(Clone, Debug)]
+struct Loop {
+    label: Option<String>,
+    /// Whether it's a `loop`, the only kind of loop which can be broken out of with a value
+    is_loop_expr: bool,
+    /// Types of the values it's broken out of with, unit for a `break` without one
+    break_types: Vec<Option<TypeAnnotation>>,
+}
+
+/// Infers the type of every expression in a program and checks operators, calls, field accesses,
+/// returns and trait implementations before the program is executed. Every error found is
+/// reported at once, each with the location in source it was found at.
+///
+/// Expressions whose type can't be known ahead of time, such as variables declared in an
+/// earlier REPL line, are skipped and left to the checks done by the interpreter
+pub struct TypeChecker {
+    scopes: Vec<HashMap<String, Symbol>>,
+    /// Maps each struct's name to its fields
+    structs: HashMap<String, Vec<StructField>>,
+    /// Maps the name of each generic struct to its type parameters
+    struct_type_params: HashMap<String, Vec<String>>,
+    /// Maps each type's name to the methods implemented for it, including trait methods
+    methods: HashMap<String, HashMap<String, Signature>>,
+    enums: HashMap<String, Vec<EnumItem>>,
+    /// Maps the name of each generic enum to its type parameters
+    enum_type_params: HashMap<String, Vec<String>>,
+    traits: HashMap<String, Vec<TraitFunctionStmt>>,
+    /// Maps the name of each generic trait to its type parameters, besides `Self`
+    trait_type_params: HashMap<String, Vec<String>>,
+    /// Maps each type's name to the traits implemented for it
+    trait_impls: HashMap<String, HashSet<String>>,
+    /// Return types of the functions being checked, the innermost function is last
+    return_types: Vec<Option<TypeAnnotation>>,
+    /// Loops around the statement being checked, innermost last. A function body starts outside
+    /// of any loop
+    loops: Vec<Loop>,
+    errors: Vec<String>,
+}
+
+impl Default for TypeChecker {
+    fn default() -> TypeChecker {
+        TypeChecker {
+            scopes: vec![HashMap::new()],
+            structs: HashMap::new(),
+            struct_type_params: HashMap::new(),
+            methods: HashMap::new(),
+            enums: HashMap::new(),
+            enum_type_params: HashMap::new(),
+            traits: HashMap::new(),
+            trait_type_params: HashMap::new(),
+            trait_impls: HashMap::new(),
+            return_types: Vec::new(),
+            loops: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
 
 impl TypeChecker {
     /// Checks if `lhs` and `rhs` are of the same type by comparing their type annotations.
@@ -30,15 +169,13 @@ impl TypeChecker {
     ) -> bool {
         let lhs_type_annotation = lhs.get_type_annotation();
         let rhs_type_annotation = rhs.get_type_annotation();
+        if let (Some(lhs), Some(rhs)) = (
+            lhs_type_annotation.numeric_type(),
+            rhs_type_annotation.numeric_type(),
+        ) {
+            return lhs != rhs && rhs.widens_to(lhs);
+        }
         match lhs_type_annotation {
-            TypeAnnotation::I64 => match rhs_type_annotation {
-                TypeAnnotation::I32 => true,
-                _ => false,
-            },
-            TypeAnnotation::F64 => match rhs_type_annotation {
-                TypeAnnotation::F32 => true,
-                _ => false,
-            },
             TypeAnnotation::Array(lhs_element_type) => match rhs_type_annotation {
                 TypeAnnotation::Array(rhs_element_type) => {
                     let a = lhs_element_type;
@@ -46,7 +183,2264 @@ impl TypeChecker {
                 }
                 _ => false,
             },
+            TypeAnnotation::Map(lhs_key_type, lhs_value_type) => match rhs_type_annotation {
+                TypeAnnotation::Map(rhs_key_type, rhs_value_type) => {
+                    TypeChecker::is_assignable(lhs_key_type, rhs_key_type)
+                        && TypeChecker::is_assignable(lhs_value_type, rhs_value_type)
+                }
+                _ => false,
+            },
+            TypeAnnotation::Tuple(lhs_element_types) => match rhs_type_annotation {
+                TypeAnnotation::Tuple(rhs_element_types) => {
+                    lhs_element_types.len() == rhs_element_types.len()
+                        && lhs_element_types
+                            .iter()
+                            .zip(rhs_element_types.iter())
+                            .all(|(lhs, rhs)| TypeChecker::is_assignable(lhs, rhs))
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Describes why a variable declared without a type annotation can't take its type from a
+    /// value of type `type_annotation`. `()` and arrays and maps of it, such as the types of `[]`
+    /// and `[:]`, don't say what the variable is meant to hold, nor does a generic type without its
+    /// type arguments, such as the type of `None`
+    pub fn inference_error(name: &str, type_annotation: &TypeAnnotation) -> Option<String> {
+        fn is_ambiguous(type_annotation: &TypeAnnotation) -> bool {
+            match type_annotation {
+                TypeAnnotation::Unit => true,
+                TypeAnnotation::Generic { type_args, .. } => {
+                    type_args.is_empty() || type_args.iter().any(is_ambiguous)
+                }
+                TypeAnnotation::Array(element_type) => is_ambiguous(element_type),
+                TypeAnnotation::Map(key_type, value_type) => {
+                    is_ambiguous(key_type) || is_ambiguous(value_type)
+                }
+                TypeAnnotation::Tuple(element_types) => element_types.iter().any(is_ambiguous),
+                _ => false,
+            }
+        }
+        if is_ambiguous(type_annotation) {
+            let value_type = match type_annotation {
+                TypeAnnotation::Generic { name, type_args } if type_args.is_empty() => name.clone(),
+                _ => type_annotation.to_string(),
+            };
+            return Some(format!(
+                "can't infer the type of '{}' from a value of type {}, add a type annotation",
+                name, value_type
+            ));
+        }
+        None
+    }
+
+    /// Checks `stmts`, returning a type error listing every error found
+    pub fn check(&mut self, stmts: &[Stmt]) -> Result<(), LangError> {
+        for stmt in stmts {
+            self.declare_types(stmt);
+        }
+        self.check_stmts(stmts)?;
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        Err(LangErrorType::new_type_error(self.errors.join("\n")))
+    }
+
+    /// Whether a value of type `value` can be stored where a value of type `target` is expected
+    fn is_assignable(target: &TypeAnnotation, value: &TypeAnnotation) -> bool {
+        target == value || TypeChecker::can_convert_implicitly(target, value)
+    }
+
+    /// Whether values of type `lhs` and `rhs` can be operands of the same binary operator
+    fn is_compatible(lhs: &TypeAnnotation, rhs: &TypeAnnotation) -> bool {
+        TypeChecker::is_assignable(lhs, rhs) || TypeChecker::is_assignable(rhs, lhs)
+    }
+
+    fn is_numeric(type_annotation: &TypeAnnotation) -> bool {
+        type_annotation.numeric_type().is_some()
+    }
+
+    fn is_integer(type_annotation: &TypeAnnotation) -> bool {
+        type_annotation
+            .numeric_type()
+            .is_some_and(NumericType::is_integer)
+    }
+
+    /// Whether `type_annotation` is a type built into the language that has no fields or methods
+    fn is_primitive(type_annotation: &TypeAnnotation) -> bool {
+        match type_annotation {
+            TypeAnnotation::String
+            | TypeAnnotation::Char
+            | TypeAnnotation::Bool
+            | TypeAnnotation::Array(_)
+            | TypeAnnotation::Map(..)
+            | TypeAnnotation::Tuple(_) => true,
+            other => TypeChecker::is_numeric(other),
+        }
+    }
+
+    /// Whether values of type `type_annotation` can be the keys of a map. Floats aren't, since
+    /// they don't compare equal to themselves reliably, and neither are tuples holding them
+    fn is_hashable(type_annotation: &TypeAnnotation) -> bool {
+        match type_annotation {
+            TypeAnnotation::Tuple(element_types) => {
+                element_types.iter().all(TypeChecker::is_hashable)
+            }
+            integer if TypeChecker::is_integer(integer) => true,
+            other => matches!(
+                other,
+                TypeAnnotation::String
+                    | TypeAnnotation::Char
+                    | TypeAnnotation::Bool
+                    | TypeAnnotation::User(_)
+                    | TypeAnnotation::Generic { .. }
+                    | TypeAnnotation::TypeParam(_)
+            ),
+        }
+    }
+
+    fn check_map_key_type(&mut self, key_type: &TypeAnnotation, location: SourceLocation) {
+        if !TypeChecker::is_hashable(key_type) {
+            self.error(
+                location,
+                format!("values of type {} can't be used as map keys", key_type),
+            );
+        }
+    }
+
+    fn error(&mut self, location: SourceLocation, message: String) {
+        self.errors.push(format!("{}: {}", location, message));
+    }
+
+    /// Checks the body of a loop labelled `label`, returning the loop as it was left
+    fn check_loop_body(
+        &mut self,
+        label: &Option<String>,
+        is_loop_expr: bool,
+        body: &[Stmt],
+    ) -> Result<Loop, LangError> {
+        self.loops.push(Loop {
+            label: label.clone(),
+            is_loop_expr,
+            break_types: Vec::new(),
+        });
+        self.begin_scope();
+        let result = self.check_stmts(body);
+        self.end_scope();
+        let checked = self.loops.pop();
+        result?;
+        checked.ok_or_else(|| LangErrorType::new_iie_error("loop went missing".to_string()))
+    }
+
+    /// Records the structs, enums, traits and impls declared by `stmt`, so that they can be used
+    /// before the point they're declared at
+    fn declare_types(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Struct(struct_stmt) => {
+                self.structs
+                    .insert(struct_stmt.name.clone(), struct_stmt.fields.clone());
+                if !struct_stmt.type_params.is_empty() {
+                    self.struct_type_params
+                        .insert(struct_stmt.name.clone(), struct_stmt.type_params.clone());
+                }
+            }
+            Stmt::Enum(enum_stmt) => {
+                self.enums
+                    .insert(enum_stmt.name.clone(), enum_stmt.item_list.clone());
+                if !enum_stmt.type_params.is_empty() {
+                    self.enum_type_params
+                        .insert(enum_stmt.name.clone(), enum_stmt.type_params.clone());
+                }
+            }
+            Stmt::Trait(trait_stmt) => {
+                let trait_functions = trait_stmt
+                    .trait_fn_declarations
+                    .iter()
+                    .filter_map(|fn_decl| match fn_decl {
+                        Stmt::TraitFunction(trait_function) => Some(*trait_function.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                self.traits.insert(trait_stmt.name.clone(), trait_functions);
+                if !trait_stmt.type_params.is_empty() {
+                    self.trait_type_params
+                        .insert(trait_stmt.name.clone(), trait_stmt.type_params.clone());
+                }
+            }
+            Stmt::Impl(impl_stmt) => self.declare_methods(
+                &impl_stmt.name,
+                &impl_stmt.type_params,
+                &impl_stmt.fn_declarations,
+            ),
+            Stmt::ImplTrait(impl_trait_stmt) => {
+                self.trait_impls
+                    .entry(impl_trait_stmt.impl_name.clone())
+                    .or_default()
+                    .insert(impl_trait_stmt.trait_name.clone());
+                self.declare_methods(
+                    &impl_trait_stmt.impl_name,
+                    &impl_trait_stmt.type_params,
+                    &impl_trait_stmt.fn_declarations,
+                )
+            }
+            Stmt::Block(block) => {
+                for stmt in block.statements.iter() {
+                    self.declare_types(stmt);
+                }
+            }
+            Stmt::Function(function_stmt) => {
+                for stmt in function_stmt.body.iter() {
+                    self.declare_types(stmt);
+                }
+            }
+            Stmt::If(if_stmt) => {
+                self.declare_types(&if_stmt.then_branch);
+                if let Some(ref else_branch) = if_stmt.else_branch {
+                    self.declare_types(else_branch);
+                }
+            }
+            Stmt::While(while_stmt) => self.declare_types(&while_stmt.body),
+            Stmt::ForIn(for_in) => {
+                for stmt in for_in.body.iter() {
+                    self.declare_types(stmt);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn declare_methods(
+        &mut self,
+        type_name: &str,
+        type_params: &[String],
+        fn_declarations: &[Stmt],
+    ) {
+        let methods = self.methods.entry(type_name.to_string()).or_default();
+        for fn_decl in fn_declarations.iter() {
+            if let Stmt::Function(function_stmt) = fn_decl {
+                let mut signature = Signature::from_function(function_stmt);
+                signature
+                    .type_params
+                    .splice(0..0, type_params.iter().cloned());
+                methods.insert(function_stmt.name.clone(), signature);
+            }
+        }
+    }
+
+    /// The type of `self` in the methods of an impl for `type_name`
+    fn impl_type(type_name: &str, type_params: &[String]) -> TypeAnnotation {
+        if type_params.is_empty() {
+            return TypeAnnotation::User(type_name.to_string());
+        }
+        TypeAnnotation::Generic {
+            name: type_name.to_string(),
+            type_args: type_params
+                .iter()
+                .map(|type_param| TypeAnnotation::TypeParam(type_param.clone()))
+                .collect(),
+        }
+    }
+
+    /// The method `method_name` of the trait `trait_name`, when `object_type` is a user type that
+    /// implements it
+    fn trait_method(
+        &self,
+        object_type: &TypeAnnotation,
+        trait_name: &str,
+        method_name: &str,
+    ) -> Option<Signature> {
+        let type_name = TypeChecker::struct_name(object_type)?;
+        if !self
+            .trait_impls
+            .get(type_name)
+            .is_some_and(|traits| traits.contains(trait_name))
+        {
+            return None;
+        }
+        self.methods.get(type_name)?.get(method_name).cloned()
+    }
+
+    /// Binds the type parameters of the impl a method is in to the type arguments of the object
+    /// the method is called on
+    fn method_bindings(
+        signature: &Signature,
+        object_type: &Option<TypeAnnotation>,
+    ) -> HashMap<String, TypeAnnotation> {
+        let mut bindings = HashMap::new();
+        if let Some(TypeAnnotation::Generic { type_args, .. }) = object_type {
+            for (type_param, type_arg) in signature.type_params.iter().zip(type_args.iter()) {
+                bindings.insert(type_param.clone(), type_arg.clone());
+            }
+        }
+        bindings
+    }
+
+    /// The type of applying the binary `operator` to operands of types `left` and `right`,
+    /// reporting operands it can't be applied to
+    fn binary_type(
+        &mut self,
+        operator: &TokenType,
+        location: SourceLocation,
+        left: Option<TypeAnnotation>,
+        right: Option<TypeAnnotation>,
+    ) -> Option<TypeAnnotation> {
+        if let Some(result_type) = self.overloaded_operator_type(operator, location, &left, &right)
+        {
+            return result_type;
+        }
+        let is_arithmetic = matches!(
+            operator,
+            TokenType::Plus
+                | TokenType::Minus
+                | TokenType::Star
+                | TokenType::Slash
+                | TokenType::Percent
+        );
+        let is_bitwise = matches!(
+            operator,
+            TokenType::Ampersand | TokenType::Pipe | TokenType::Caret
+        );
+        let is_shift = matches!(operator, TokenType::LessLess | TokenType::GreaterGreater);
+        if let (Some(left), Some(right)) = (&left, &right) {
+            let is_valid = if is_arithmetic {
+                (TypeChecker::is_numeric(left)
+                    && TypeChecker::is_numeric(right)
+                    && TypeChecker::is_compatible(left, right))
+                    || (*operator == TokenType::Plus
+                        && *left == TypeAnnotation::String
+                        && *right == TypeAnnotation::String)
+            } else if is_bitwise {
+                TypeChecker::is_integer(left)
+                    && TypeChecker::is_integer(right)
+                    && TypeChecker::is_compatible(left, right)
+            } else if is_shift {
+                // The shift amount may be an integer of any type
+                TypeChecker::is_integer(left) && TypeChecker::is_integer(right)
+            } else {
+                // Integers of any types compare by value
+                TypeChecker::is_compatible(left, right)
+                    || (TypeChecker::is_integer(left) && TypeChecker::is_integer(right))
+            };
+            if !is_valid {
+                self.error(
+                    location,
+                    format!(
+                        "'{}' can't be applied to values of type {} and {}",
+                        operator, left, right
+                    ),
+                );
+            }
+        }
+        if is_arithmetic || is_bitwise {
+            // Mixing widths promotes to the wider one, e.g. an i32 and an i64 give an i64
+            if let (Some(left), Some(right)) = (&left, &right) {
+                if TypeChecker::can_convert_implicitly(right, left) {
+                    return Some(right.clone());
+                }
+            }
+            return left;
+        }
+        if is_shift {
+            return left;
+        }
+        Some(TypeAnnotation::Bool)
+    }
+
+    /// The type of a binary operation when its left operand is of a user type implementing the
+    /// built-in trait that overloads its operator, which is called with both operands
+    fn overloaded_operator_type(
+        &mut self,
+        operator: &TokenType,
+        location: SourceLocation,
+        left: &Option<TypeAnnotation>,
+        right: &Option<TypeAnnotation>,
+    ) -> Option<Option<TypeAnnotation>> {
+        let (trait_name, method_name) = operator.operator_trait()?;
+        let signature = self.trait_method(left.as_ref()?, trait_name, method_name)?;
+        let bindings = TypeChecker::method_bindings(&signature, left);
+        let return_type = self.check_call(
+            &signature,
+            &[left.clone(), right.clone()],
+            bindings,
+            location,
+        );
+        // `Eq` and `Ord` turn the result of their method into the bool the operator evaluates to
+        match trait_name {
+            "Eq" | "Ord" => Some(Some(TypeAnnotation::Bool)),
+            _ => Some(return_type),
+        }
+    }
+
+    /// The type of the items a `for` loop over a value of `iterable_type` binds: the elements of
+    /// an array, the keys of a map, the characters of a string, the integers of a range or the
+    /// payload of the `Option` a user type implementing `Iterator` returns from `next`
+    fn item_type(&self, iterable_type: &TypeAnnotation) -> Option<TypeAnnotation> {
+        match iterable_type {
+            TypeAnnotation::Array(element_type) | TypeAnnotation::Range(element_type) => {
+                Some(*element_type.clone())
+            }
+            TypeAnnotation::Map(key_type, _) => Some(*key_type.clone()),
+            TypeAnnotation::String => Some(TypeAnnotation::Char),
+            _ => {
+                let signature = self.trait_method(iterable_type, "Iterator", "next")?;
+                let bindings =
+                    TypeChecker::method_bindings(&signature, &Some(iterable_type.clone()));
+                match signature.return_type?.substitute(&bindings) {
+                    TypeAnnotation::Generic {
+                        name,
+                        mut type_args,
+                    } if name == "Option" && type_args.len() == 1 => Some(type_args.remove(0)),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// The type parameters of the generic struct or enum `name`
+    fn type_params_of(&self, name: &str) -> Option<&Vec<String>> {
+        self.struct_type_params
+            .get(name)
+            .or_else(|| self.enum_type_params.get(name))
+    }
+
+    /// Checks that the generic structs and enums named in `type_annotation` are given as many
+    /// type arguments as they have type parameters
+    fn check_type_arguments(&mut self, type_annotation: &TypeAnnotation, location: SourceLocation) {
+        match type_annotation {
+            TypeAnnotation::User(name) => {
+                if let Some(type_params) = self.type_params_of(name) {
+                    let message = format!(
+                        "'{}' takes {} type argument(s) but none were given",
+                        name,
+                        type_params.len()
+                    );
+                    self.error(location, message);
+                }
+            }
+            TypeAnnotation::Generic { name, type_args } => {
+                let expected = self.type_params_of(name).map(Vec::len);
+                match expected {
+                    Some(expected) if expected == type_args.len() => {}
+                    Some(expected) => self.error(
+                        location,
+                        format!(
+                            "'{}' takes {} type argument(s) but {} were given",
+                            name,
+                            expected,
+                            type_args.len()
+                        ),
+                    ),
+                    None if self.structs.contains_key(name) || self.enums.contains_key(name) => {
+                        self.error(
+                            location,
+                            format!("'{}' is not generic but was given type arguments", name),
+                        )
+                    }
+                    None => {}
+                }
+                for type_arg in type_args.iter() {
+                    self.check_type_arguments(type_arg, location);
+                }
+            }
+            TypeAnnotation::Array(element_type) => {
+                self.check_type_arguments(element_type, location)
+            }
+            TypeAnnotation::Map(key_type, value_type) => {
+                self.check_map_key_type(key_type, location);
+                self.check_type_arguments(key_type, location);
+                self.check_type_arguments(value_type, location);
+            }
+            TypeAnnotation::Tuple(element_types) => {
+                for element_type in element_types.iter() {
+                    self.check_type_arguments(element_type, location);
+                }
+            }
+            TypeAnnotation::Function {
+                params,
+                return_type,
+            } => {
+                for param in params.iter() {
+                    self.check_type_arguments(param, location);
+                }
+                self.check_type_arguments(return_type, location);
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether a value of type `arg` can be passed as a parameter of type `param`, binding the
+    /// type parameters in `type_params` that `param` refers to along the way. A type parameter
+    /// that's already bound only accepts values of the type it's bound to
+    fn unify(
+        param: &TypeAnnotation,
+        arg: &TypeAnnotation,
+        type_params: &[String],
+        bindings: &mut HashMap<String, TypeAnnotation>,
+    ) -> bool {
+        match (param, arg) {
+            (TypeAnnotation::TypeParam(name), _) if type_params.contains(name) => {
+                match bindings.get(name) {
+                    Some(bound) => bound == arg,
+                    None => {
+                        bindings.insert(name.clone(), arg.clone());
+                        true
+                    }
+                }
+            }
+            (TypeAnnotation::Array(param), TypeAnnotation::Array(arg)) => {
+                TypeChecker::unify(param, arg, type_params, bindings)
+            }
+            (
+                TypeAnnotation::Map(param_key, param_value),
+                TypeAnnotation::Map(arg_key, arg_value),
+            ) => {
+                TypeChecker::unify(param_key, arg_key, type_params, bindings)
+                    && TypeChecker::unify(param_value, arg_value, type_params, bindings)
+            }
+            (TypeAnnotation::Tuple(param_elements), TypeAnnotation::Tuple(arg_elements)) => {
+                param_elements.len() == arg_elements.len()
+                    && param_elements
+                        .iter()
+                        .zip(arg_elements.iter())
+                        .all(|(param, arg)| TypeChecker::unify(param, arg, type_params, bindings))
+            }
+            (
+                TypeAnnotation::Generic {
+                    name: param_name,
+                    type_args: param_type_args,
+                },
+                TypeAnnotation::Generic {
+                    name: arg_name,
+                    type_args: arg_type_args,
+                },
+            ) => {
+                param_name == arg_name
+                    && param_type_args.len() == arg_type_args.len()
+                    && param_type_args
+                        .iter()
+                        .zip(arg_type_args.iter())
+                        .all(|(param, arg)| TypeChecker::unify(param, arg, type_params, bindings))
+            }
+            (
+                TypeAnnotation::Function {
+                    params: param_params,
+                    return_type: param_return_type,
+                },
+                TypeAnnotation::Function {
+                    params: arg_params,
+                    return_type: arg_return_type,
+                },
+            ) => {
+                param_params.len() == arg_params.len()
+                    && param_params
+                        .iter()
+                        .zip(arg_params.iter())
+                        .all(|(param, arg)| TypeChecker::unify(param, arg, type_params, bindings))
+                    && TypeChecker::unify(param_return_type, arg_return_type, type_params, bindings)
+            }
+            _ => param == arg,
+        }
+    }
+
+    /// Binds the type parameters of the struct `type_name` to the type arguments of
+    /// `object_type`, a value of that struct
+    fn struct_bindings(
+        &self,
+        type_name: &str,
+        object_type: &TypeAnnotation,
+    ) -> HashMap<String, TypeAnnotation> {
+        let mut bindings = HashMap::new();
+        if let (Some(type_params), TypeAnnotation::Generic { type_args, .. }) =
+            (self.struct_type_params.get(type_name), object_type)
+        {
+            for (type_param, type_arg) in type_params.iter().zip(type_args.iter()) {
+                bindings.insert(type_param.clone(), type_arg.clone());
+            }
+        }
+        bindings
+    }
+
+    /// The name of the struct `type_annotation` is an instance of
+    fn struct_name(type_annotation: &TypeAnnotation) -> Option<&String> {
+        match type_annotation {
+            TypeAnnotation::User(name) | TypeAnnotation::Generic { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn define(&mut self, name: &str, symbol: Symbol) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), symbol);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Checks the statements of a block in the current scope. Functions are declared before any
+    /// statement is checked so that they can be called before their declaration
+    fn check_stmts(&mut self, stmts: &[Stmt]) -> Result<(), LangError> {
+        for stmt in stmts {
+            if let Stmt::Function(function_stmt) = stmt {
+                self.define(
+                    &function_stmt.name,
+                    Symbol::Function(Signature::from_function(function_stmt)),
+                );
+            }
+        }
+        for stmt in stmts {
+            self.visit_stmt_mut(stmt)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the body of `function_stmt`. Methods are given the type of the struct they're
+    /// implemented for as `self_type`
+    fn check_function(
+        &mut self,
+        function_stmt: &FunctionStmt,
+        self_type: Option<TypeAnnotation>,
+    ) -> Result<(), LangError> {
+        self.begin_scope();
+        if let Some(self_type) = self_type {
+            self.define("self", Symbol::Variable(Some(self_type)));
+        }
+        for param in function_stmt.params.iter() {
+            self.check_type_arguments(&param.type_annotation, function_stmt.location);
+        }
+        if let Ok(return_type) = function_stmt.return_type.to_type_annotation() {
+            self.check_type_arguments(&return_type, function_stmt.location);
+        }
+        for param in function_stmt.params.iter() {
+            self.define(
+                &param.identifier,
+                Symbol::Variable(Some(param.type_annotation.clone())),
+            );
+        }
+        self.return_types
+            .push(function_stmt.return_type.to_type_annotation().ok());
+        let enclosing_loops = std::mem::take(&mut self.loops);
+        let result = self.check_stmts(&function_stmt.body);
+        self.loops = enclosing_loops;
+        match function_stmt.return_type.to_type_annotation() {
+            Ok(return_type)
+                if return_type != TypeAnnotation::Unit
+                    && !TypeChecker::always_returns(&function_stmt.body) =>
+            {
+                self.error(
+                    function_stmt.location,
+                    format!(
+                        "function {} can end without returning a value of type {}",
+                        function_stmt.name, return_type
+                    ),
+                );
+            }
+            _ => {}
+        }
+        self.return_types.pop();
+        self.end_scope();
+        result
+    }
+
+    /// Checks the number and types of the arguments given to a call of `signature`, returning the
+    /// type of the value the call evaluates to. `bindings` holds the type parameters already known
+    /// from the object a method is called on
+    fn check_call(
+        &mut self,
+        signature: &Signature,
+        arg_types: &[Option<TypeAnnotation>],
+        mut bindings: HashMap<String, TypeAnnotation>,
+        location: SourceLocation,
+    ) -> Option<TypeAnnotation> {
+        if signature.params.len() != arg_types.len() {
+            self.error(
+                location,
+                format!(
+                    "'{}' takes {} argument(s) but {} were given",
+                    signature.name,
+                    signature.params.len(),
+                    arg_types.len()
+                ),
+            );
+        } else {
+            for (param, arg_type) in signature.params.iter().zip(arg_types.iter()) {
+                if let Some(arg_type) = arg_type {
+                    if !TypeChecker::unify(
+                        &param.type_annotation,
+                        arg_type,
+                        &signature.type_params,
+                        &mut bindings,
+                    ) {
+                        let param_type = param.type_annotation.substitute(&bindings);
+                        self.error(
+                            location,
+                            format!(
+                                "parameter '{}' of '{}' expects a value of type {}, found {}",
+                                param.identifier, signature.name, param_type, arg_type
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+        // A return type left with type parameters that no argument determined isn't known
+        let return_type = signature.return_type.as_ref()?.substitute(&bindings);
+        if return_type.mentions_type_params(&signature.type_params) {
+            return None;
+        }
+        Some(return_type)
+    }
+
+    /// Whether running `stmts` can't reach their end, because every path through them returns or
+    /// loops forever
+    fn always_returns(stmts: &[Stmt]) -> bool {
+        stmts.iter().any(TypeChecker::stmt_returns)
+    }
+
+    fn stmt_returns(stmt: &Stmt) -> bool {
+        match stmt {
+            Stmt::Return(_) => true,
+            Stmt::Block(block) => TypeChecker::always_returns(&block.statements),
+            Stmt::If(if_stmt) => {
+                TypeChecker::stmt_returns(&if_stmt.then_branch)
+                    && if_stmt
+                        .else_branch
+                        .as_ref()
+                        .is_some_and(TypeChecker::stmt_returns)
+            }
+            Stmt::Expression(expression_stmt) => {
+                TypeChecker::expr_returns(&expression_stmt.expression)
+            }
+            Stmt::Var(var_stmt) => var_stmt
+                .initializer
+                .as_ref()
+                .is_some_and(TypeChecker::expr_returns),
+            Stmt::While(while_stmt) => {
+                let forever = matches!(
+                    while_stmt.condition,
+                    Expr::Literal(ref literal) if literal.value.value == Value::Boolean(true)
+                );
+                forever && !TypeChecker::breaks_out(&while_stmt.body, &while_stmt.label, false)
+            }
+            _ => false,
+        }
+    }
+
+    fn expr_returns(expr: &Expr) -> bool {
+        match expr {
+            Expr::Grouping(grouping) => TypeChecker::expr_returns(&grouping.expression),
+            Expr::Block(block) => TypeChecker::block_returns(block),
+            Expr::If(if_expr) => {
+                TypeChecker::block_returns(&if_expr.then_branch)
+                    && if_expr
+                        .else_branch
+                        .as_ref()
+                        .is_some_and(TypeChecker::expr_returns)
+            }
+            Expr::Match(match_expr) => {
+                !match_expr.arms.is_empty()
+                    && match_expr
+                        .arms
+                        .iter()
+                        .all(|arm| TypeChecker::stmt_returns(&arm.body))
+            }
+            Expr::Loop(loop_expr) => !loop_expr
+                .body
+                .iter()
+                .any(|stmt| TypeChecker::breaks_out(stmt, &loop_expr.label, false)),
+            _ => false,
+        }
+    }
+
+    fn block_returns(block: &BlockExpr) -> bool {
+        TypeChecker::always_returns(&block.statements)
+            || block.value.as_ref().is_some_and(TypeChecker::expr_returns)
+    }
+
+    /// Whether `stmt` has a `break` leaving the loop labelled `label` it's found in. `nested` is
+    /// whether `stmt` is inside another loop within that one, which an unlabelled `break` leaves
+    fn breaks_out(stmt: &Stmt, label: &Option<String>, nested: bool) -> bool {
+        let any_breaks_out = |stmts: &[Stmt], nested: bool| {
+            stmts
+                .iter()
+                .any(|stmt| TypeChecker::breaks_out(stmt, label, nested))
+        };
+        match stmt {
+            Stmt::Break(break_stmt) => match break_stmt.label {
+                Some(ref break_label) => label.as_ref() == Some(break_label),
+                None => !nested,
+            },
+            Stmt::Block(block) => any_breaks_out(&block.statements, nested),
+            Stmt::If(if_stmt) => {
+                TypeChecker::breaks_out(&if_stmt.then_branch, label, nested)
+                    || if_stmt.else_branch.as_ref().is_some_and(|else_branch| {
+                        TypeChecker::breaks_out(else_branch, label, nested)
+                    })
+            }
+            Stmt::While(while_stmt) => TypeChecker::breaks_out(&while_stmt.body, label, true),
+            Stmt::ForIn(for_in_stmt) => any_breaks_out(&for_in_stmt.body, true),
+            Stmt::Expression(expression_stmt) => {
+                TypeChecker::expr_breaks_out(&expression_stmt.expression, label, nested)
+            }
+            Stmt::Var(var_stmt) => var_stmt.initializer.as_ref().is_some_and(|initializer| {
+                TypeChecker::expr_breaks_out(initializer, label, nested)
+            }),
+            _ => false,
+        }
+    }
+
+    fn expr_breaks_out(expr: &Expr, label: &Option<String>, nested: bool) -> bool {
+        let block_breaks_out = |block: &BlockExpr| {
+            block
+                .statements
+                .iter()
+                .any(|stmt| TypeChecker::breaks_out(stmt, label, nested))
+                || block
+                    .value
+                    .as_ref()
+                    .is_some_and(|value| TypeChecker::expr_breaks_out(value, label, nested))
+        };
+        match expr {
+            Expr::Grouping(grouping) => {
+                TypeChecker::expr_breaks_out(&grouping.expression, label, nested)
+            }
+            Expr::Block(block) => block_breaks_out(block),
+            Expr::If(if_expr) => {
+                block_breaks_out(&if_expr.then_branch)
+                    || if_expr.else_branch.as_ref().is_some_and(|else_branch| {
+                        TypeChecker::expr_breaks_out(else_branch, label, nested)
+                    })
+            }
+            Expr::Match(match_expr) => match_expr
+                .arms
+                .iter()
+                .any(|arm| TypeChecker::breaks_out(&arm.body, label, nested)),
+            Expr::Loop(loop_expr) => loop_expr
+                .body
+                .iter()
+                .any(|stmt| TypeChecker::breaks_out(stmt, label, true)),
             _ => false,
         }
     }
+
+    /// The name of the enum of the variant `expr` makes, either a variant without fields such as
+    /// `None` or a call of a variant's constructor such as `Err(1)`
+    fn variant_enum(expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::EnumPath(enum_path) => Some(enum_path.name.clone()),
+            Expr::Call(call) => match call.callee {
+                Expr::EnumPath(ref enum_path) => Some(enum_path.name.clone()),
+                _ => None,
+            },
+            Expr::Grouping(grouping) => TypeChecker::variant_enum(&grouping.expression),
+            _ => None,
+        }
+    }
+
+    /// Finds the variant named by `enum_path`, reporting an error when its enum is known but has no
+    /// such variant
+    fn enum_variant(&mut self, enum_path: &EnumPathExpr) -> Option<EnumItem> {
+        let variants = self.enums.get(&enum_path.name)?;
+        if let [variant] = enum_path.path_items.as_slice() {
+            if let Some(item) = variants.iter().find(|item| item.identifier == *variant) {
+                return Some(item.clone());
+            }
+            let message = format!("enum '{}' has no variant '{}'", enum_path.name, variant);
+            self.error(enum_path.location, message);
+        }
+        None
+    }
+
+    /// The type of the field or method `name` of a value of type `object_type`
+    fn member_type(
+        &mut self,
+        object_type: &Option<TypeAnnotation>,
+        name: &str,
+        location: SourceLocation,
+    ) -> Option<TypeAnnotation> {
+        match object_type {
+            Some(object_type @ (TypeAnnotation::User(_) | TypeAnnotation::Generic { .. })) => {
+                let type_name = TypeChecker::struct_name(object_type)?;
+                let field = self
+                    .structs
+                    .get(type_name)
+                    .and_then(|fields| fields.iter().find(|field| field.identifier == name));
+                if let Some(field) = field {
+                    let bindings = self.struct_bindings(type_name, object_type);
+                    return Some(field.type_annotation.substitute(&bindings));
+                }
+                let is_method = self
+                    .methods
+                    .get(type_name)
+                    .is_some_and(|methods| methods.contains_key(name));
+                if is_method {
+                    return Some(TypeAnnotation::Fn);
+                }
+                if self.structs.contains_key(type_name) {
+                    self.error(
+                        location,
+                        format!("struct '{}' has no field or method '{}'", type_name, name),
+                    );
+                }
+                None
+            }
+            Some(TypeAnnotation::Map(..)) if MapMethod::from_name(name).is_some() => {
+                Some(TypeAnnotation::Fn)
+            }
+            Some(tuple_type @ TypeAnnotation::Tuple(element_types)) => {
+                let element_type = name
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| element_types.get(index));
+                if element_type.is_none() {
+                    self.error(
+                        location,
+                        format!("tuple of type {} has no field '{}'", tuple_type, name),
+                    );
+                }
+                element_type.cloned()
+            }
+            Some(type_annotation) if TypeChecker::is_primitive(type_annotation) => {
+                self.error(
+                    location,
+                    format!(
+                        "tried to access '{}' on a value of type {}",
+                        name, type_annotation
+                    ),
+                );
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// The element type of the array variable `name`, or the value type of the map variable
+    /// `name`, after checking the type of `index`
+    fn element_type(
+        &mut self,
+        name: &str,
+        index: &Expr,
+        location: SourceLocation,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        if let Some(Symbol::Variable(Some(TypeAnnotation::Map(key_type, value_type)))) =
+            self.lookup(name).cloned()
+        {
+            if let Some(index_type) = self.visit_expr_mut(index)? {
+                if !TypeChecker::is_assignable(&key_type, &index_type) {
+                    self.error(
+                        location,
+                        format!(
+                            "tried to index '{}', which has keys of type {}, with a value of type {}",
+                            name, key_type, index_type
+                        ),
+                    );
+                }
+            }
+            return Ok(Some(*value_type));
+        }
+        self.check_array_index(index, location)?;
+        Ok(self.array_element_type(name, location))
+    }
+
+    /// The element type of the array variable `name`
+    fn array_element_type(
+        &mut self,
+        name: &str,
+        location: SourceLocation,
+    ) -> Option<TypeAnnotation> {
+        match self.lookup(name) {
+            Some(Symbol::Variable(Some(TypeAnnotation::Array(element_type)))) => {
+                Some(*element_type.clone())
+            }
+            Some(Symbol::Variable(Some(type_annotation))) => {
+                let type_annotation = type_annotation.clone();
+                self.error(
+                    location,
+                    format!(
+                        "tried to index '{}', which is a value of type {}",
+                        name, type_annotation
+                    ),
+                );
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn check_array_index(
+        &mut self,
+        index: &Expr,
+        location: SourceLocation,
+    ) -> Result<(), LangError> {
+        if let Some(index_type) = self.visit_expr_mut(index)? {
+            match index_type {
+                integer if TypeChecker::is_integer(&integer) => {}
+                TypeAnnotation::F64 | TypeAnnotation::Bool => {}
+                other => self.error(
+                    location,
+                    format!("tried to index an array with a value of type {}", other),
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Defines the identifiers bound by `pattern` when it's matched against a value of type
+    /// `value_type`
+    fn define_pattern(&mut self, pattern: &Pattern, value_type: Option<TypeAnnotation>) {
+        match pattern {
+            Pattern::Binding(name) => self.define(name, Symbol::Variable(value_type)),
+            Pattern::EnumVariant(variant_pattern) => {
+                let type_params = self
+                    .enum_type_params
+                    .get(&variant_pattern.enum_name)
+                    .cloned()
+                    .unwrap_or_default();
+                // The type arguments of the value matched against give the field types of a
+                // generic enum
+                let mut bindings = HashMap::new();
+                if let Some(TypeAnnotation::Generic { name, type_args }) = &value_type {
+                    if *name == variant_pattern.enum_name {
+                        for (type_param, type_arg) in type_params.iter().zip(type_args.iter()) {
+                            bindings.insert(type_param.clone(), type_arg.clone());
+                        }
+                    }
+                }
+                let field_types: Vec<_> = self
+                    .enums
+                    .get(&variant_pattern.enum_name)
+                    .and_then(|variants| {
+                        variants
+                            .iter()
+                            .find(|item| item.identifier == variant_pattern.variant)
+                    })
+                    .map(|item| item.fields.clone())
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|field_type| field_type.substitute(&bindings))
+                    .map(|field_type| {
+                        Some(field_type)
+                            .filter(|field_type| !field_type.mentions_type_params(&type_params))
+                    })
+                    .collect();
+                for (index, field) in variant_pattern.fields.iter().enumerate() {
+                    self.define_pattern(field, field_types.get(index).cloned().flatten());
+                }
+            }
+            Pattern::Wildcard | Pattern::Literal(_) => {}
+        }
+    }
+
+    /// Checks the trait function declarations `impl_trait_stmt` implements against the trait
+    fn check_impl_trait(&mut self, impl_trait_stmt: &ImplTraitStmt) {
+        let trait_functions = match self.traits.get(&impl_trait_stmt.trait_name) {
+            Some(trait_functions) => trait_functions.clone(),
+            None => {
+                self.error(
+                    impl_trait_stmt.location,
+                    format!(
+                        "'{}' implements the undeclared trait '{}'",
+                        impl_trait_stmt.impl_name, impl_trait_stmt.trait_name
+                    ),
+                );
+                return;
+            }
+        };
+        let type_params = self
+            .trait_type_params
+            .get(&impl_trait_stmt.trait_name)
+            .cloned()
+            .unwrap_or_default();
+        if type_params.len() != impl_trait_stmt.trait_type_args.len() {
+            self.error(
+                impl_trait_stmt.location,
+                format!(
+                    "trait '{}' takes {} type argument(s) but '{}' gives it {}",
+                    impl_trait_stmt.trait_name,
+                    type_params.len(),
+                    impl_trait_stmt.impl_name,
+                    impl_trait_stmt.trait_type_args.len()
+                ),
+            );
+            return;
+        }
+        let bindings = impl_trait_stmt.trait_bindings(&type_params);
+        for trait_function in trait_functions.iter() {
+            let function_stmt =
+                impl_trait_stmt
+                    .fn_declarations
+                    .iter()
+                    .find_map(|fn_decl| match fn_decl {
+                        Stmt::Function(function_stmt)
+                            if function_stmt.name == trait_function.name =>
+                        {
+                            Some(function_stmt)
+                        }
+                        _ => None,
+                    });
+            let function_stmt = match function_stmt {
+                Some(function_stmt) => function_stmt,
+                None => {
+                    self.error(
+                        impl_trait_stmt.location,
+                        format!(
+                            "'{}' is missing '{}' of trait '{}'",
+                            impl_trait_stmt.impl_name,
+                            trait_function.name,
+                            impl_trait_stmt.trait_name
+                        ),
+                    );
+                    continue;
+                }
+            };
+            if let Ok(return_type) = function_stmt.return_type.to_type_annotation() {
+                let trait_return_type = trait_function.return_type.substitute(&bindings);
+                if return_type != trait_return_type {
+                    self.error(
+                        function_stmt.location,
+                        format!(
+                            "'{}' returns {} but trait '{}' declares it to return {}",
+                            function_stmt.name,
+                            return_type,
+                            impl_trait_stmt.trait_name,
+                            trait_return_type
+                        ),
+                    );
+                }
+            }
+            if function_stmt.params.len() != trait_function.params.len() {
+                self.error(
+                    function_stmt.location,
+                    format!(
+                        "'{}' takes {} argument(s) but trait '{}' declares it to take {}",
+                        function_stmt.name,
+                        function_stmt.params.len(),
+                        impl_trait_stmt.trait_name,
+                        trait_function.params.len()
+                    ),
+                );
+                continue;
+            }
+            for (param, trait_param) in function_stmt
+                .params
+                .iter()
+                .zip(trait_function.params.iter())
+            {
+                let trait_param_type = trait_param.type_annotation.substitute(&bindings);
+                if param.type_annotation != trait_param_type {
+                    self.error(
+                        function_stmt.location,
+                        format!(
+                            "parameter '{}' of '{}' is of type {} but trait '{}' declares it as {}",
+                            param.identifier,
+                            function_stmt.name,
+                            param.type_annotation,
+                            impl_trait_stmt.trait_name,
+                            trait_param_type
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl VisitorMut<Option<TypeAnnotation>> for TypeChecker {
+    fn visit_expr_mut(&mut self, expr: &Expr) -> Result<Option<TypeAnnotation>, LangError> {
+        visit_expr_mut(self, expr)
+    }
+    fn visit_stmt_mut(&mut self, stmt: &Stmt) -> Result<Option<TypeAnnotation>, LangError> {
+        visit_stmt_mut(self, stmt)
+    }
+
+    fn visit_assign(&mut self, assign: &AssignExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let value_type = self.visit_expr_mut(&assign.expr)?;
+        if let (Some(Symbol::Variable(Some(target_type))), Some(value_type)) =
+            (self.lookup(&assign.name), value_type)
+        {
+            if !TypeChecker::is_assignable(target_type, &value_type) {
+                let message = format!(
+                    "tried to assign a value of type {} to '{}', which is of type {}",
+                    value_type, assign.name, target_type
+                );
+                self.error(assign.location, message);
+            }
+        }
+        Ok(None)
+    }
+    fn visit_binary(&mut self, binary: &BinaryExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let left = self.visit_expr_mut(&binary.left)?;
+        let right = self.visit_expr_mut(&binary.right)?;
+        Ok(self.binary_type(&binary.operator, binary.location, left, right))
+    }
+    fn visit_call(&mut self, call: &CallExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let mut arg_types = Vec::new();
+        for arg in call.arguments.iter() {
+            arg_types.push(self.visit_expr_mut(arg)?);
+        }
+        match &call.callee {
+            Expr::Variable(variable) => match self.lookup(&variable.name).cloned() {
+                Some(Symbol::Function(signature)) => {
+                    return Ok(self.check_call(
+                        &signature,
+                        &arg_types,
+                        HashMap::new(),
+                        call.location,
+                    ));
+                }
+                // `Name()` is a struct literal giving every field its default value
+                None if self.structs.contains_key(&variable.name) => {
+                    if !call.arguments.is_empty() {
+                        self.error(
+                            call.location,
+                            format!(
+                                "struct constructor {} takes no arguments, passed {}",
+                                variable.name,
+                                call.arguments.len()
+                            ),
+                        );
+                    }
+                    let fields = &self.structs[&variable.name];
+                    return self.visit_struct_literal(&StructLiteralExpr {
+                        name: variable.name.clone(),
+                        fields: StructStmt::constructor_fields(fields),
+                        location: call.location,
+                    });
+                }
+                _ => {}
+            },
+            Expr::Get(get) => {
+                let object_type = self.visit_expr_mut(&get.object)?;
+                if let Some(TypeAnnotation::Map(ref key_type, ref value_type)) = object_type {
+                    if let Some(method) = MapMethod::from_name(&get.name) {
+                        let signature = Signature::from_map_method(method, key_type, value_type);
+                        return Ok(self.check_call(
+                            &signature,
+                            &arg_types,
+                            HashMap::new(),
+                            call.location,
+                        ));
+                    }
+                }
+                let type_name = object_type.as_ref().and_then(TypeChecker::struct_name);
+                let signature = type_name
+                    .and_then(|type_name| self.methods.get(type_name))
+                    .and_then(|methods| methods.get(&get.name))
+                    .cloned();
+                if let Some(signature) = signature {
+                    let bindings = TypeChecker::method_bindings(&signature, &object_type);
+                    // The interpreter passes the object a method is called on as its first
+                    // argument when that parameter is of a user type
+                    let takes_receiver = signature
+                        .params
+                        .first()
+                        .is_some_and(|param| param.type_annotation.is_user_type());
+                    if takes_receiver {
+                        if let Expr::Variable(_) = get.object {
+                            arg_types.insert(0, object_type);
+                        }
+                    }
+                    return Ok(self.check_call(&signature, &arg_types, bindings, call.location));
+                }
+                self.member_type(&object_type, &get.name, get.location);
+                return Ok(None);
+            }
+            Expr::EnumPath(enum_path) => {
+                if let Some(item) = self.enum_variant(enum_path) {
+                    let type_params = self
+                        .enum_type_params
+                        .get(&enum_path.name)
+                        .cloned()
+                        .unwrap_or_default();
+                    let signature = Signature {
+                        name: format!("{}::{}", enum_path.name, item.identifier),
+                        return_type: Some(TypeChecker::impl_type(&enum_path.name, &type_params)),
+                        type_params,
+                        params: item
+                            .fields
+                            .iter()
+                            .enumerate()
+                            .map(|(index, field)| {
+                                VariableData::new(index.to_string(), field.clone())
+                            })
+                            .collect(),
+                    };
+                    return Ok(self.check_call(
+                        &signature,
+                        &arg_types,
+                        HashMap::new(),
+                        call.location,
+                    ));
+                }
+                return Ok(None);
+            }
+            _ => {}
+        }
+        match self.visit_expr_mut(&call.callee)? {
+            Some(TypeAnnotation::Function {
+                params,
+                return_type,
+            }) => {
+                let name = match &call.callee {
+                    Expr::Variable(variable) => variable.name.clone(),
+                    _ => "function".to_string(),
+                };
+                let signature = Signature::from_function_type(name, &params, &return_type);
+                Ok(self.check_call(&signature, &arg_types, HashMap::new(), call.location))
+            }
+            Some(callee_type) if TypeChecker::is_primitive(&callee_type) => {
+                self.error(
+                    call.location,
+                    format!("tried to call a value of type {}", callee_type),
+                );
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+    fn visit_get(&mut self, get: &GetExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let object_type = self.visit_expr_mut(&get.object)?;
+        Ok(self.member_type(&object_type, &get.name, get.location))
+    }
+    fn visit_enum_path(
+        &mut self,
+        enum_path: &EnumPathExpr,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        match self.enum_variant(enum_path) {
+            Some(ref item) if !item.fields.is_empty() => Ok(Some(TypeAnnotation::Fn)),
+            // The type arguments of a variant of a generic enum without fields, such as `None`,
+            // aren't known from the variant alone
+            Some(_) if self.enum_type_params.contains_key(&enum_path.name) => Ok(None),
+            Some(_) => Ok(Some(TypeAnnotation::User(enum_path.name.clone()))),
+            None => Ok(None),
+        }
+    }
+    fn visit_match(&mut self, match_expr: &MatchExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let scrutinee_type = self.visit_expr_mut(&match_expr.scrutinee)?;
+        let mut arm_types = Vec::new();
+        for arm in match_expr.arms.iter() {
+            self.begin_scope();
+            self.define_pattern(&arm.pattern, scrutinee_type.clone());
+            let arm_type = match &arm.body {
+                Stmt::Expression(expr) => self.visit_expr_mut(&expr.expression)?,
+                body => {
+                    self.visit_stmt_mut(body)?;
+                    None
+                }
+            };
+            self.end_scope();
+            arm_types.push(arm_type);
+        }
+        let mut known_types = arm_types.iter().flatten();
+        if let Some(first) = known_types.next() {
+            if let Some(other) = known_types.find(|arm_type| *arm_type != first) {
+                self.error(
+                    match_expr.location,
+                    format!(
+                        "'match' arms have incompatible types {} and {}",
+                        first, other
+                    ),
+                );
+                return Ok(None);
+            }
+        }
+        // The type of a match is only known when every arm evaluates to the same type
+        match arm_types.split_first() {
+            Some((first, rest)) if rest.iter().all(|arm_type| arm_type == first) => {
+                Ok(first.clone())
+            }
+            _ => Ok(None),
+        }
+    }
+    fn visit_grouping(
+        &mut self,
+        grouping: &GroupingExpr,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        self.visit_expr_mut(&grouping.expression)
+    }
+    fn visit_literal(
+        &mut self,
+        literal: &LiteralExpr,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        Ok(Some(literal.value.value_type.clone()))
+    }
+    fn visit_logical(
+        &mut self,
+        logical: &LogicalExpr,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        // Logical operators evaluate to one of their operands
+        let left = self.visit_expr_mut(&logical.left)?;
+        let right = self.visit_expr_mut(&logical.right)?;
+        if left == right {
+            return Ok(left);
+        }
+        Ok(None)
+    }
+    fn visit_set(&mut self, set: &SetExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let object_type = self.visit_expr_mut(&set.object)?;
+        let field_type = match &object_type {
+            Some(object_type @ (TypeAnnotation::User(_) | TypeAnnotation::Generic { .. })) => {
+                let type_name = TypeChecker::struct_name(object_type).cloned();
+                let fields = type_name
+                    .as_ref()
+                    .and_then(|type_name| self.structs.get(type_name));
+                match (type_name.as_ref(), fields) {
+                    (Some(type_name), Some(fields)) => {
+                        match fields.iter().find(|field| field.identifier == set.name) {
+                            Some(field) => {
+                                let bindings = self.struct_bindings(type_name, object_type);
+                                Some(field.type_annotation.substitute(&bindings))
+                            }
+                            None => {
+                                let message =
+                                    format!("struct '{}' has no field '{}'", type_name, set.name);
+                                self.error(set.location, message);
+                                None
+                            }
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            Some(type_annotation) if TypeChecker::is_primitive(type_annotation) => {
+                let message = format!(
+                    "tried to set '{}' on a value of type {}",
+                    set.name, type_annotation
+                );
+                self.error(set.location, message);
+                None
+            }
+            _ => None,
+        };
+        let value_type = self.visit_expr_mut(&set.value)?;
+        let value_type = match set.operator {
+            Some(ref operator) => {
+                self.binary_type(operator, set.location, field_type.clone(), value_type)
+            }
+            None => value_type,
+        };
+        if let (Some(field_type), Some(value_type)) = (field_type, value_type) {
+            if !TypeChecker::is_assignable(&field_type, &value_type) {
+                self.error(
+                    set.location,
+                    format!(
+                        "tried to assign a value of type {} to field '{}', which is of type {}",
+                        value_type, set.name, field_type
+                    ),
+                );
+            }
+        }
+        Ok(None)
+    }
+    fn visit_unary(&mut self, unary: &UnaryExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let right = self.visit_expr_mut(&unary.right)?;
+        match unary.operator {
+            TokenType::Minus => {
+                if let Some(ref right) = right {
+                    let is_signed = right.numeric_type().is_some_and(NumericType::is_signed);
+                    if !is_signed {
+                        self.error(
+                            unary.location,
+                            format!("tried to negate a value of type {}", right),
+                        );
+                    }
+                }
+                Ok(right)
+            }
+            TokenType::Tilde => {
+                if let Some(ref right) = right {
+                    if !TypeChecker::is_integer(right) {
+                        self.error(
+                            unary.location,
+                            format!("tried to flip the bits of a value of type {}", right),
+                        );
+                    }
+                }
+                Ok(right)
+            }
+            _ => Ok(Some(TypeAnnotation::Bool)),
+        }
+    }
+    fn visit_array(&mut self, array: &ArrayExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let mut element_type = None;
+        for (index, element) in array.elements.iter().enumerate() {
+            let next_type = self.visit_expr_mut(element)?;
+            if let Some(TypeAnnotation::Array(ref array_element_type)) = array.type_annotation {
+                if let Some(next_type) = next_type {
+                    if !TypeChecker::is_assignable(array_element_type, &next_type) {
+                        let message = format!(
+                            "tried to add an element of type {} to an array of type {}",
+                            next_type,
+                            TypeAnnotation::Array(array_element_type.clone())
+                        );
+                        self.error(array.location, message);
+                    }
+                }
+            } else if index == 0 {
+                element_type = next_type;
+            } else if let (Some(element_type), Some(next_type)) = (&element_type, next_type) {
+                if *element_type != next_type {
+                    let message = format!(
+                        "array elements must all be of type {}, found {}",
+                        element_type, next_type
+                    );
+                    self.error(array.location, message);
+                }
+            }
+        }
+        if let Some(ref type_annotation) = array.type_annotation {
+            return Ok(Some(type_annotation.clone()));
+        }
+        if array.elements.is_empty() {
+            return Ok(Some(TypeAnnotation::Array(Box::new(TypeAnnotation::Unit))));
+        }
+        Ok(element_type.map(|element_type| TypeAnnotation::Array(Box::new(element_type))))
+    }
+    fn visit_map(&mut self, map: &MapExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let mut entry_types = None;
+        for (index, (key, value)) in map.entries.iter().enumerate() {
+            let key_type = self.visit_expr_mut(key)?;
+            let value_type = self.visit_expr_mut(value)?;
+            let (key_type, value_type) = match (key_type, value_type) {
+                (Some(key_type), Some(value_type)) => (key_type, value_type),
+                _ => continue,
+            };
+            if let Some(TypeAnnotation::Map(ref map_key_type, ref map_value_type)) =
+                map.type_annotation
+            {
+                if !TypeChecker::is_assignable(map_key_type, &key_type)
+                    || !TypeChecker::is_assignable(map_value_type, &value_type)
+                {
+                    let message = format!(
+                        "tried to add an entry of types {}: {} to a map of type {}",
+                        key_type,
+                        value_type,
+                        TypeAnnotation::Map(map_key_type.clone(), map_value_type.clone())
+                    );
+                    self.error(map.location, message);
+                }
+            } else if index == 0 {
+                self.check_map_key_type(&key_type, map.location);
+                entry_types = Some((key_type, value_type));
+            } else if let Some((ref first_key_type, ref first_value_type)) = entry_types {
+                if *first_key_type != key_type || *first_value_type != value_type {
+                    let message = format!(
+                        "map entries must all be of types {}: {}, found {}: {}",
+                        first_key_type, first_value_type, key_type, value_type
+                    );
+                    self.error(map.location, message);
+                }
+            }
+        }
+        if let Some(ref type_annotation) = map.type_annotation {
+            return Ok(Some(type_annotation.clone()));
+        }
+        if map.entries.is_empty() {
+            return Ok(Some(TypeAnnotation::Map(
+                Box::new(TypeAnnotation::Unit),
+                Box::new(TypeAnnotation::Unit),
+            )));
+        }
+        Ok(entry_types.map(|(key_type, value_type)| {
+            TypeAnnotation::Map(Box::new(key_type), Box::new(value_type))
+        }))
+    }
+    fn visit_tuple(&mut self, tuple: &TupleExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let mut element_types = Vec::new();
+        for element in tuple.elements.iter() {
+            element_types.push(self.visit_expr_mut(element)?);
+        }
+        // Left for the interpreter when the type of any of the elements isn't known
+        Ok(element_types
+            .into_iter()
+            .collect::<Option<Vec<TypeAnnotation>>>()
+            .map(TypeAnnotation::Tuple))
+    }
+    fn visit_struct_literal(
+        &mut self,
+        struct_literal: &StructLiteralExpr,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        let mut value_types = Vec::new();
+        for (name, value) in struct_literal.fields.iter() {
+            value_types.push((name, self.visit_expr_mut(value)?));
+        }
+        let fields = match self.structs.get(&struct_literal.name) {
+            Some(fields) => fields.clone(),
+            None => return Ok(None),
+        };
+        let type_params = self
+            .struct_type_params
+            .get(&struct_literal.name)
+            .cloned()
+            .unwrap_or_default();
+        let mut bindings = HashMap::new();
+        for (name, value_type) in value_types {
+            let field = match fields.iter().find(|field| field.identifier == *name) {
+                Some(field) => field,
+                None => {
+                    let message =
+                        format!("struct '{}' has no field '{}'", struct_literal.name, name);
+                    self.error(struct_literal.location, message);
+                    continue;
+                }
+            };
+            let value_type = match value_type {
+                Some(value_type) => value_type,
+                None => continue,
+            };
+            // The values of fields of generic types give the type arguments of the instance
+            let matches = if field.type_annotation.mentions_type_params(&type_params) {
+                TypeChecker::unify(
+                    &field.type_annotation,
+                    &value_type,
+                    &type_params,
+                    &mut bindings,
+                )
+            } else {
+                TypeChecker::is_assignable(&field.type_annotation, &value_type)
+            };
+            if !matches {
+                let field_type = field.type_annotation.substitute(&bindings);
+                self.error(
+                    struct_literal.location,
+                    format!(
+                        "field '{}' of '{}' expects a value of type {}, found {}",
+                        name, struct_literal.name, field_type, value_type
+                    ),
+                );
+            }
+        }
+        for field in fields.iter() {
+            let is_given = struct_literal
+                .fields
+                .iter()
+                .any(|(name, _)| *name == field.identifier);
+            if !is_given && field.default.is_none() {
+                self.error(
+                    struct_literal.location,
+                    format!(
+                        "missing field '{}' in a literal of struct '{}'",
+                        field.identifier, struct_literal.name
+                    ),
+                );
+            }
+        }
+        let struct_type =
+            TypeChecker::impl_type(&struct_literal.name, &type_params).substitute(&bindings);
+        // Type arguments no field determined are given by the annotation of the variable instead
+        if struct_type.mentions_type_params(&type_params) {
+            return Ok(None);
+        }
+        Ok(Some(struct_type))
+    }
+    fn visit_block_expr(&mut self, block: &BlockExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        self.begin_scope();
+        let result = self
+            .check_stmts(&block.statements)
+            .and_then(|_| match block.value {
+                Some(ref value) => self.visit_expr_mut(value),
+                // A block left through a return or break never evaluates to anything, so it fits
+                // wherever a value is expected
+                None => match block.statements.last() {
+                    Some(Stmt::Return(_)) | Some(Stmt::Break(_)) | Some(Stmt::Continue(_)) => {
+                        Ok(None)
+                    }
+                    _ => Ok(Some(TypeAnnotation::Unit)),
+                },
+            });
+        self.end_scope();
+        result
+    }
+    fn visit_loop(&mut self, loop_expr: &LoopExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let checked = self.check_loop_body(&loop_expr.label, true, &loop_expr.body)?;
+        // A loop without a break never ends, so like a return it fits wherever a value is expected
+        let mut break_types = checked.break_types.into_iter().flatten();
+        let loop_type = match break_types.next() {
+            Some(loop_type) => loop_type,
+            None => return Ok(None),
+        };
+        for break_type in break_types {
+            if break_type != loop_type {
+                self.error(
+                    loop_expr.location,
+                    format!(
+                        "'loop' is broken out of with values of incompatible types {} and {}",
+                        loop_type, break_type
+                    ),
+                );
+                return Ok(None);
+            }
+        }
+        Ok(Some(loop_type))
+    }
+    fn visit_range(&mut self, range: &RangeExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let start = self.visit_expr_mut(&range.start)?;
+        let end = self.visit_expr_mut(&range.end)?;
+        let (start, end) = match (start, end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Ok(None),
+        };
+        // Like arithmetic, bounds of different widths promote to the wider one
+        if TypeChecker::is_integer(&start)
+            && TypeChecker::is_integer(&end)
+            && TypeChecker::is_compatible(&start, &end)
+        {
+            let element_type = if TypeChecker::can_convert_implicitly(&end, &start) {
+                end
+            } else {
+                start
+            };
+            return Ok(Some(TypeAnnotation::Range(Box::new(element_type))));
+        }
+        self.error(
+            range.location,
+            format!(
+                "the bounds of a range must be integers of compatible types, found {} and {}",
+                start, end
+            ),
+        );
+        Ok(None)
+    }
+    fn visit_format_string(
+        &mut self,
+        format_string: &FormatStringExpr,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        for piece in &format_string.pieces {
+            let argument = match piece {
+                FormatPiece::Argument(argument) => argument,
+                FormatPiece::Text(_) => continue,
+            };
+            let value_type = self.visit_expr_mut(&argument.value)?;
+            match value_type {
+                Some(ref value_type)
+                    if argument.spec.needs_integer() && !TypeChecker::is_integer(value_type) =>
+                {
+                    self.error(
+                        argument.location,
+                        format!(
+                            "the {} format needs an integer, found {}",
+                            argument.spec.kind, value_type
+                        ),
+                    );
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(TypeAnnotation::String))
+    }
+    fn visit_if_expr(&mut self, if_expr: &IfExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        self.visit_expr_mut(&if_expr.condition)?;
+        let then_type = self.visit_block_expr(&if_expr.then_branch)?;
+        let else_type = match if_expr.else_branch {
+            Some(ref else_branch) => self.visit_expr_mut(else_branch)?,
+            None => Some(TypeAnnotation::Unit),
+        };
+        match (then_type, else_type) {
+            (Some(then_type), Some(_))
+                if if_expr.else_branch.is_none() && then_type != TypeAnnotation::Unit =>
+            {
+                self.error(
+                    if_expr.location,
+                    format!(
+                        "'if' without an 'else' evaluates to (), found {}",
+                        then_type
+                    ),
+                );
+                Ok(None)
+            }
+            (Some(then_type), Some(else_type)) if then_type != else_type => {
+                self.error(
+                    if_expr.location,
+                    format!(
+                        "'if' and 'else' have incompatible types {} and {}",
+                        then_type, else_type
+                    ),
+                );
+                Ok(None)
+            }
+            (Some(branch_type), _) | (None, Some(branch_type)) => Ok(Some(branch_type)),
+            (None, None) => Ok(None),
+        }
+    }
+    fn visit_cast(&mut self, cast: &CastExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let source = self.visit_expr_mut(&cast.expression)?;
+        if !TypeChecker::is_numeric(&cast.target) {
+            self.error(
+                cast.location,
+                format!("can only cast to a numeric type, found {}", cast.target),
+            );
+            return Ok(None);
+        }
+        if let Some(source) = source {
+            let is_castable = TypeChecker::is_numeric(&source)
+                || matches!(source, TypeAnnotation::Bool | TypeAnnotation::Char);
+            if !is_castable {
+                self.error(
+                    cast.location,
+                    format!("can't cast a value of type {} to {}", source, cast.target),
+                );
+            }
+        }
+        Ok(Some(cast.target.clone()))
+    }
+    fn visit_try(&mut self, try_expr: &TryExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        let (name, type_args) = match self.visit_expr_mut(&try_expr.expression)? {
+            Some(TypeAnnotation::Generic { name, type_args })
+                if name == "Option" || name == "Result" =>
+            {
+                (name, type_args)
+            }
+            Some(other) => {
+                self.error(
+                    try_expr.location,
+                    format!(
+                        "the '?' operator can only be applied to an Option or a Result, found {}",
+                        other
+                    ),
+                );
+                return Ok(None);
+            }
+            // A variant whose type arguments aren't all known, such as `Err(1)`, still tells
+            // which enum it's of
+            None => match TypeChecker::variant_enum(&try_expr.expression) {
+                Some(name) if name == "Option" || name == "Result" => (name, Vec::new()),
+                _ => return Ok(None),
+            },
+        };
+        // A `None` or `Err` is returned as it is, so a `Result` can only be returned early from a
+        // function returning a `Result` with the same error type
+        if let Some(return_type) = self.return_types.last().cloned().flatten() {
+            let compatible = match return_type {
+                TypeAnnotation::Generic {
+                    name: ref return_name,
+                    type_args: ref return_type_args,
+                } => {
+                    *return_name == name
+                        && (name == "Option"
+                            || type_args.is_empty()
+                            || return_type_args.get(1) == type_args.get(1))
+                }
+                _ => false,
+            };
+            if !compatible {
+                let value_type = if type_args.is_empty() {
+                    name
+                } else {
+                    TypeAnnotation::Generic {
+                        name,
+                        type_args: type_args.clone(),
+                    }
+                    .to_string()
+                };
+                self.error(
+                    try_expr.location,
+                    format!(
+                        "the '?' operator can't return a value of type {} from a function which returns {}",
+                        value_type, return_type
+                    ),
+                );
+            }
+        }
+        Ok(type_args.first().cloned())
+    }
+    fn visit_index(&mut self, index: &IndexExpr) -> Result<Option<TypeAnnotation>, LangError> {
+        if let Some(Symbol::Variable(object_type)) = self.lookup(&index.from) {
+            let object_type = object_type.clone();
+            let signature = object_type
+                .as_ref()
+                .and_then(|object_type| self.trait_method(object_type, "Index", "index"));
+            if let Some(signature) = signature {
+                let index_type = self.visit_expr_mut(&index.index)?;
+                let bindings = TypeChecker::method_bindings(&signature, &object_type);
+                return Ok(self.check_call(
+                    &signature,
+                    &[object_type, index_type],
+                    bindings,
+                    index.location,
+                ));
+            }
+        }
+        self.element_type(&index.from, &index.index, index.location)
+    }
+    fn visit_set_array_element(
+        &mut self,
+        set_array_element: &SetArrayElementExpr,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        let element_type = self.element_type(
+            &set_array_element.name,
+            &set_array_element.index,
+            set_array_element.location,
+        )?;
+        let value_type = self.visit_expr_mut(&set_array_element.value)?;
+        let value_type = match set_array_element.operator {
+            Some(ref operator) => self.binary_type(
+                operator,
+                set_array_element.location,
+                element_type.clone(),
+                value_type,
+            ),
+            None => value_type,
+        };
+        if let (Some(element_type), Some(value_type)) = (element_type, value_type) {
+            if !TypeChecker::is_assignable(&element_type, &value_type) {
+                self.error(
+                    set_array_element.location,
+                    format!(
+                        "tried to assign a value of type {} to an element of '{}', which holds values of type {}",
+                        value_type, set_array_element.name, element_type
+                    ),
+                );
+            }
+        }
+        Ok(None)
+    }
+    fn visit_variable(
+        &mut self,
+        variable: &VariableExpr,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        match self.lookup(&variable.name) {
+            Some(Symbol::Variable(type_annotation)) => Ok(type_annotation.clone()),
+            Some(Symbol::Function(signature)) => {
+                Ok(signature
+                    .return_type
+                    .as_ref()
+                    .map(|return_type| TypeAnnotation::Function {
+                        params: signature
+                            .params
+                            .iter()
+                            .map(|param| param.type_annotation.clone())
+                            .collect(),
+                        return_type: Box::new(return_type.clone()),
+                    }))
+            }
+            // Types are only named by struct constructors and enum paths, which check them
+            None if self.structs.contains_key(&variable.name)
+                || self.enums.contains_key(&variable.name)
+                || self.traits.contains_key(&variable.name) =>
+            {
+                Ok(None)
+            }
+            None => {
+                self.error(
+                    variable.location,
+                    format!("undefined variable '{}'", variable.name),
+                );
+                Ok(None)
+            }
+        }
+    }
+    fn visit_self_ident(
+        &mut self,
+        self_ident: &SelfIdentExpr,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        match self.lookup(&self_ident.keyword) {
+            Some(Symbol::Variable(type_annotation)) => Ok(type_annotation.clone()),
+            _ => Ok(None),
+        }
+    }
+
+    // stmt
+    fn visit_break(&mut self, break_stmt: &BreakStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        let value_type = match break_stmt.value {
+            Some(ref value) => self.visit_expr_mut(value)?,
+            None => Some(TypeAnnotation::Unit),
+        };
+        let target = match break_stmt.label {
+            Some(ref label) => self
+                .loops
+                .iter_mut()
+                .rev()
+                .find(|enclosing| enclosing.label.as_ref() == Some(label)),
+            None => self.loops.last_mut(),
+        };
+        // A break outside of any loop is reported by the resolver
+        let target = match target {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+        if target.is_loop_expr {
+            target.break_types.push(value_type);
+        } else if break_stmt.value.is_some() {
+            self.error(
+                break_stmt.location,
+                "can only break with a value out of a 'loop'".to_string(),
+            );
+        }
+        Ok(None)
+    }
+    fn visit_continue(&mut self, _: &ContinueStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        Ok(None)
+    }
+    fn visit_assert(
+        &mut self,
+        assert_stmt: &AssertStmt,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        self.visit_expr_mut(&assert_stmt.condition)?;
+        Ok(None)
+    }
+    fn visit_enum(&mut self, _: &EnumStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        Ok(None)
+    }
+    fn visit_impl(&mut self, impl_stmt: &ImplStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        for fn_decl in impl_stmt.fn_declarations.iter() {
+            if let Stmt::Function(function_stmt) = fn_decl {
+                let self_type = TypeChecker::impl_type(&impl_stmt.name, &impl_stmt.type_params);
+                self.check_function(function_stmt, Some(self_type))?;
+            }
+        }
+        Ok(None)
+    }
+    fn visit_impl_trait(
+        &mut self,
+        impl_trait: &ImplTraitStmt,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        self.check_impl_trait(impl_trait);
+        for fn_decl in impl_trait.fn_declarations.iter() {
+            if let Stmt::Function(function_stmt) = fn_decl {
+                let self_type =
+                    TypeChecker::impl_type(&impl_trait.impl_name, &impl_trait.type_params);
+                self.check_function(function_stmt, Some(self_type))?;
+            }
+        }
+        Ok(None)
+    }
+    fn visit_block(&mut self, block: &BlockStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        self.begin_scope();
+        let result = self.check_stmts(&block.statements);
+        self.end_scope();
+        result?;
+        Ok(None)
+    }
+    fn visit_struct(
+        &mut self,
+        struct_stmt: &StructStmt,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        for field in struct_stmt.fields.iter() {
+            self.check_type_arguments(&field.type_annotation, struct_stmt.location);
+            let default = match field.default {
+                Some(ref default) => default,
+                None => continue,
+            };
+            // A default has to suit every type the type parameters can be bound to
+            if field
+                .type_annotation
+                .mentions_type_params(&struct_stmt.type_params)
+            {
+                self.error(
+                    struct_stmt.location,
+                    format!(
+                        "field '{}' of type {} can't have a default value",
+                        field.identifier, field.type_annotation
+                    ),
+                );
+                continue;
+            }
+            if let Some(default_type) = self.visit_expr_mut(default)? {
+                if !TypeChecker::is_assignable(&field.type_annotation, &default_type) {
+                    self.error(
+                        struct_stmt.location,
+                        format!(
+                            "tried to give field '{}', which is of type {}, a default value of type {}",
+                            field.identifier, field.type_annotation, default_type
+                        ),
+                    );
+                }
+            }
+        }
+        Ok(None)
+    }
+    fn visit_expression(
+        &mut self,
+        expr: &ExpressionStmt,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        self.visit_expr_mut(&expr.expression)?;
+        Ok(None)
+    }
+    fn visit_trait(&mut self, _: &TraitStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        Ok(None)
+    }
+    fn visit_trait_function(
+        &mut self,
+        _: &TraitFunctionStmt,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        Ok(None)
+    }
+    fn visit_function(
+        &mut self,
+        function_stmt: &FunctionStmt,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        self.check_function(function_stmt, None)?;
+        Ok(None)
+    }
+    fn visit_if(&mut self, if_stmt: &IfStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        self.visit_expr_mut(&if_stmt.condition)?;
+        self.visit_stmt_mut(&if_stmt.then_branch)?;
+        if let Some(ref else_branch) = if_stmt.else_branch {
+            self.visit_stmt_mut(else_branch)?;
+        }
+        Ok(None)
+    }
+    fn visit_print(&mut self, print_stmt: &PrintStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        self.visit_expr_mut(&print_stmt.expression)?;
+        Ok(None)
+    }
+    fn visit_return(
+        &mut self,
+        return_stmt: &ReturnStmt,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        let value_type = self.visit_expr_mut(&return_stmt.value)?;
+        if let (Some(Some(return_type)), Some(value_type)) =
+            (self.return_types.last().cloned(), value_type)
+        {
+            if return_type != value_type {
+                self.error(
+                    return_stmt.location,
+                    format!(
+                        "tried to return a value of type {} from a function which returns {}",
+                        value_type, return_type
+                    ),
+                );
+            }
+        }
+        Ok(None)
+    }
+    fn visit_var(&mut self, var_stmt: &VarStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        let value_type = match var_stmt.initializer {
+            Some(ref initializer) => self.visit_expr_mut(initializer)?,
+            None => None,
+        };
+        if let Some(ref type_annotation) = var_stmt.type_annotation {
+            self.check_type_arguments(type_annotation, var_stmt.location);
+            if var_stmt.initializer.is_none() {
+                self.error(
+                    var_stmt.location,
+                    format!(
+                        "'{}' must be initialized, values of type {} have no default",
+                        var_stmt.name, type_annotation
+                    ),
+                );
+            }
+        }
+        let var_type = match (&var_stmt.type_annotation, value_type) {
+            (Some(type_annotation), Some(value_type)) => {
+                if *type_annotation != value_type {
+                    self.error(
+                        var_stmt.location,
+                        format!(
+                            "tried to initialize '{}', which is of type {}, with a value of type {}",
+                            var_stmt.name, type_annotation, value_type
+                        ),
+                    );
+                }
+                Some(type_annotation.clone())
+            }
+            (Some(type_annotation), None) => Some(type_annotation.clone()),
+            (None, Some(value_type)) => {
+                if let Some(message) = TypeChecker::inference_error(&var_stmt.name, &value_type) {
+                    self.error(var_stmt.location, message);
+                }
+                Some(value_type)
+            }
+            (None, None) => {
+                // A variant of a generic enum, such as `None` or `Err(3)`, doesn't say what all of
+                // the enum's type arguments are
+                let variant_enum = var_stmt
+                    .initializer
+                    .as_ref()
+                    .and_then(TypeChecker::variant_enum)
+                    .filter(|name| self.enum_type_params.contains_key(name));
+                if let Some(name) = variant_enum {
+                    let partial_type = TypeAnnotation::Generic {
+                        name,
+                        type_args: Vec::new(),
+                    };
+                    if let Some(message) =
+                        TypeChecker::inference_error(&var_stmt.name, &partial_type)
+                    {
+                        self.error(var_stmt.location, message);
+                    }
+                }
+                // Otherwise left for the interpreter to infer from the value the initializer
+                // evaluates to
+                None
+            }
+        };
+        self.define(&var_stmt.name, Symbol::Variable(var_type));
+        Ok(None)
+    }
+    fn visit_destructure(
+        &mut self,
+        destructure_stmt: &DestructureStmt,
+    ) -> Result<Option<TypeAnnotation>, LangError> {
+        let element_types = match self.visit_expr_mut(&destructure_stmt.initializer)? {
+            Some(TypeAnnotation::Tuple(element_types))
+                if element_types.len() == destructure_stmt.names.len() =>
+            {
+                element_types.into_iter().map(Some).collect()
+            }
+            Some(value_type) => {
+                self.error(
+                    destructure_stmt.location,
+                    format!(
+                        "tried to destructure a value of type {} into {} variables",
+                        value_type,
+                        destructure_stmt.names.len()
+                    ),
+                );
+                vec![None; destructure_stmt.names.len()]
+            }
+            None => vec![None; destructure_stmt.names.len()],
+        };
+        for (name, element_type) in destructure_stmt.names.iter().zip(element_types) {
+            self.define(name, Symbol::Variable(element_type));
+        }
+        Ok(None)
+    }
+    fn visit_while(&mut self, while_stmt: &WhileStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        self.visit_expr_mut(&while_stmt.condition)?;
+        self.check_loop_body(
+            &while_stmt.label,
+            false,
+            std::slice::from_ref(&while_stmt.body),
+        )?;
+        if let Some(ref increment) = while_stmt.increment {
+            self.visit_expr_mut(increment)?;
+        }
+        Ok(None)
+    }
+    fn visit_for_in(&mut self, for_in: &ForInStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        let item_type = match self.visit_expr_mut(&for_in.iterable)? {
+            Some(iterable_type) => {
+                let item_type = self.item_type(&iterable_type);
+                if item_type.is_none() {
+                    self.error(
+                        for_in.location,
+                        format!("values of type {} can't be iterated over", iterable_type),
+                    );
+                }
+                item_type
+            }
+            None => None,
+        };
+        self.begin_scope();
+        self.define(&for_in.variable, Symbol::Variable(item_type));
+        let result = self.check_loop_body(&for_in.label, false, &for_in.body);
+        self.end_scope();
+        result?;
+        Ok(None)
+    }
+    fn visit_import(&mut self, _: &ImportStmt) -> Result<Option<TypeAnnotation>, LangError> {
+        Ok(None)
+    }
 }
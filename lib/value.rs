@@ -1,15 +1,19 @@
+use crate::ast::expr::Expr;
 use crate::ast::stmt::*;
 use crate::env::*;
-use crate::error::*;
+use crate::error::{ControlFlow, *};
+use crate::format::{self, DebugParts, DebugValue};
 use crate::interpreter::*;
 use crate::mem::*;
-use crate::token::{GetTypeAnnotation, TokenType, TypeAnnotation};
+use crate::number::{BinaryOperator, Number};
+use crate::ordered_map::OrderedMap;
+use crate::token::{GetTypeAnnotation, NumericType, TokenType, TypeAnnotation};
 use crate::value_traits::callable::CallableTrait;
 use crate::value_traits::r#struct::StructTrait;
 use std::{
     cmp::Ordering,
     collections::HashMap,
-    convert::TryInto,
+    convert::{TryFrom, TryInto},
     fmt::{self, Debug, Display},
     hash::{Hash, Hasher},
     ops::*,
@@ -21,8 +25,227 @@ type Float64 = f64;
 
 type Float32 = f32;
 
+/// The discriminant and payload types of a single variant of an enum declaration
+#[derive(Clone, Debug)]
+pub struct EnumVariantDecl {
+    pub discriminant: Value,
+    pub fields: Vec<TypeAnnotation>,
+}
+
+/// An enum declaration, mapping each variant's name to its declaration
+#[derive(Clone)]
 pub struct Enum {
-    pub values: HashMap<String, Value>,
+    pub name: String,
+    pub variants: HashMap<String, EnumVariantDecl>,
+}
+
+impl Enum {
+    pub fn new(name: String, variants: HashMap<String, EnumVariantDecl>) -> Enum {
+        Enum { name, variants }
+    }
+
+    /// Returns a value of the enum's type for the variant `variant`, or a constructor for it if
+    /// the variant carries a payload
+    pub fn get_variant(&self, variant: &str) -> Result<TypedValue, LangError> {
+        match self.variants.get(variant) {
+            Some(decl) if decl.fields.is_empty() => Ok(TypedValue::new(
+                Value::EnumVariant(Box::new(EnumVariantValue {
+                    enum_name: self.name.clone(),
+                    variant: variant.to_string(),
+                    discriminant: decl.discriminant.clone(),
+                    fields: vec![],
+                })),
+                TypeAnnotation::User(self.name.clone()),
+            )),
+            Some(decl) => Ok(TypedValue::new(
+                Value::Callable(Box::new(EnumVariantConstructor {
+                    enum_name: self.name.clone(),
+                    variant: variant.to_string(),
+                    decl: decl.clone(),
+                })),
+                TypeAnnotation::Fn,
+            )),
+            None => Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::UndefinedVariable {
+                    reason: format!("enum '{}' has no variant '{}'", self.name, variant),
+                },
+            )),
+        }
+    }
+}
+
+/// A single variant of an enum, e.g. `Color::Red` or `Shape::Circle(1.0)`
+#[derive(Clone, Debug)]
+pub struct EnumVariantValue {
+    pub enum_name: String,
+    pub variant: String,
+    pub discriminant: Value,
+    pub fields: Vec<TypedValue>,
+}
+
+/// Builds values of an enum variant that carries a payload, e.g. `Shape::Circle`
+#[derive(Clone, Debug)]
+pub struct EnumVariantConstructor {
+    enum_name: String,
+    variant: String,
+    decl: EnumVariantDecl,
+}
+
+/// A key of a map. Struct instances in it are compared by their names and the values of their
+/// fields, not by the arena entries holding the fields
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    Value(Value),
+    Struct(String, Vec<MapKey>),
+    Elements(Vec<MapKey>),
+    Variant(String, String, Vec<MapKey>),
+}
+
+impl MapKey {
+    pub fn new(value: &Value, arena: &Arena<TypedValue>) -> Result<MapKey, LangError> {
+        let keys = |values: &mut dyn Iterator<Item = &TypedValue>| {
+            values
+                .map(|value| MapKey::new(&value.value, arena))
+                .collect::<Result<Vec<MapKey>, LangError>>()
+        };
+        let key = match value {
+            Value::Struct(s) => {
+                let mut fields = Vec::new();
+                for (_, index) in s.fields() {
+                    let field: &TypedValue = (&arena[index]).try_into()?;
+                    fields.push(MapKey::new(&field.value, arena)?);
+                }
+                MapKey::Struct(s.callable_trait().get_name(), fields)
+            }
+            Value::Array(elements) | Value::Tuple(elements) => {
+                MapKey::Elements(keys(&mut elements.iter())?)
+            }
+            Value::EnumVariant(v) => MapKey::Variant(
+                v.enum_name.clone(),
+                v.variant.clone(),
+                keys(&mut v.fields.iter())?,
+            ),
+            value => MapKey::Value(value.clone()),
+        };
+        Ok(key)
+    }
+}
+
+/// The entries of a map, which are looked up by the `MapKey` of their keys
+pub type MapValue = OrderedMap<MapKey, TypedValue, TypedValue>;
+
+/// The integers from `start` up to `end`, which is left out of the range unless it's inclusive,
+/// e.g. `0..n` or `1..=10`. Both bounds are integers of the type `numeric_type`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RangeValue {
+    pub start: i128,
+    pub end: i128,
+    pub inclusive: bool,
+    pub numeric_type: NumericType,
+}
+
+impl RangeValue {
+    /// The range between two integers given along with their types, whose integers are of the
+    /// type the two promote to. Returns `None` when neither type widens to the other
+    pub fn new(
+        start: (i128, NumericType),
+        end: (i128, NumericType),
+        inclusive: bool,
+    ) -> Option<RangeValue> {
+        let numeric_type = start.1.promote(end.1)?;
+        Some(RangeValue {
+            start: start.0,
+            end: end.0,
+            inclusive,
+            numeric_type,
+        })
+    }
+
+    /// The first integer past the end of the range
+    pub fn end_exclusive(&self) -> i128 {
+        if self.inclusive {
+            self.end + 1
+        } else {
+            self.end
+        }
+    }
+}
+
+impl Display for RangeValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operator = if self.inclusive { "..=" } else { ".." };
+        write!(f, "{}{}{}", self.start, operator, self.end)
+    }
+}
+
+/// What the `?` operator does with a variant of the built-in `Option` and `Result` enums
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryOutcome {
+    /// `Some` and `Ok`, whose payload the expression evaluates to
+    Unwrap,
+    /// `None` and `Err`, which the enclosing function returns
+    Return,
+}
+
+impl TryOutcome {
+    /// Returns `None` for a variant of any other enum
+    pub fn of(enum_name: &str, variant: &str) -> Option<TryOutcome> {
+        match (enum_name, variant) {
+            ("Option", "Some") | ("Result", "Ok") => Some(TryOutcome::Unwrap),
+            ("Option", "None") | ("Result", "Err") => Some(TryOutcome::Return),
+            _ => None,
+        }
+    }
+}
+
+/// The methods built into maps
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapMethod {
+    /// `insert(key, value)`, adds an entry or replaces the value of an existing key
+    Insert,
+    /// `remove(key)`, whether there was an entry to remove
+    Remove,
+    /// `contains(key)`
+    Contains,
+    /// `len()`, the number of entries as an i64
+    Len,
+    /// `keys()`, an array of the keys in insertion order
+    Keys,
+    /// `values()`, an array of the values in insertion order
+    Values,
+}
+
+impl MapMethod {
+    pub fn from_name(name: &str) -> Option<MapMethod> {
+        match name {
+            "insert" => Some(MapMethod::Insert),
+            "remove" => Some(MapMethod::Remove),
+            "contains" => Some(MapMethod::Contains),
+            "len" => Some(MapMethod::Len),
+            "keys" => Some(MapMethod::Keys),
+            "values" => Some(MapMethod::Values),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MapMethod::Insert => "insert",
+            MapMethod::Remove => "remove",
+            MapMethod::Contains => "contains",
+            MapMethod::Len => "len",
+            MapMethod::Keys => "keys",
+            MapMethod::Values => "values",
+        }
+    }
+
+    pub fn arity(self) -> usize {
+        match self {
+            MapMethod::Insert => 2,
+            MapMethod::Remove | MapMethod::Contains => 1,
+            MapMethod::Len | MapMethod::Keys | MapMethod::Values => 0,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -35,10 +258,18 @@ pub enum Value {
     Struct(Box<dyn StructInstanceTrait>),
     Callable(Box<dyn CallableTrait>),
     Enum(Box<Enum>),
+    EnumVariant(Box<EnumVariantValue>),
     String(String),
     Char(char),
+    Int8(i8),
+    Int16(i16),
     Int32(i32),
     Int64(i64),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    USize(usize),
     Float32(Float32),
     Float64(Float64),
     Trait(Box<TraitValue>),
@@ -46,6 +277,9 @@ pub enum Value {
     Ident(String),
     Boolean(bool),
     Array(Vec<TypedValue>),
+    Map(Box<MapValue>),
+    Tuple(Vec<TypedValue>),
+    Range(Box<RangeValue>),
     SelfIndex(SelfIndex),
     Unit,
 }
@@ -86,6 +320,18 @@ impl<'a> TryInto<&'a dyn CallableTrait> for &'a Value {
     }
 }
 
+impl<'a> TryInto<&'a Enum> for &'a Value {
+    type Error = LangError;
+    fn try_into(self) -> Result<&'a Enum, Self::Error> {
+        match self {
+            Value::Enum(enum_value) => Ok(&**enum_value),
+            _ => Err(LangErrorType::new_iie_error(error_message(
+                &ErrMessage::ExpectValueType("enum".to_string()),
+            ))),
+        }
+    }
+}
+
 impl<'a> TryInto<&'a TraitValue> for &'a Value {
     type Error = LangError;
     fn try_into(self) -> Result<&'a TraitValue, Self::Error> {
@@ -122,21 +368,20 @@ impl Value {
     pub fn from_str(value_type: ValueType, lexeme: &str) -> Result<Value, LangError> {
         let value = match value_type {
             ValueType::String => Value::String(lexeme.to_string()),
+            // The narrowest of i32, i64 and u64 that fits
             ValueType::Integer => {
-                let integer_value = lexeme.to_string().parse::<i64>()?;
-                if integer_value <= std::i32::MAX.into() && integer_value >= std::i32::MIN.into() {
-                    Value::Int32(integer_value as i32)
-                } else {
+                let integer_value = lexeme.to_string().parse::<u64>()?;
+                if let Ok(integer_value) = i32::try_from(integer_value) {
+                    Value::Int32(integer_value)
+                } else if let Ok(integer_value) = i64::try_from(integer_value) {
                     Value::Int64(integer_value)
+                } else {
+                    Value::UInt64(integer_value)
                 }
             }
             ValueType::Float => {
                 let value = lexeme.to_string().parse::<f64>()?;
-                if value as f32 <= std::f32::MAX && value as f32 >= std::f32::MIN {
-                    Value::Float32(value as f32)
-                } else {
-                    Value::Float64(value)
-                }
+                Value::Float64(value)
             }
             ValueType::Boolean => {
                 let value = lexeme.to_string().parse::<bool>()?;
@@ -146,20 +391,29 @@ impl Value {
         Ok(value)
     }
 
-    pub fn default_value(type_annotation: &TypeAnnotation) -> Value {
-        match type_annotation {
-            TypeAnnotation::I32 => Value::Int32(0),
-            TypeAnnotation::I64 => Value::Int64(0),
+    /// The value a variable of type `type_annotation` declared without an initializer starts
+    /// with. Structs, enums, functions and chars have none, such variables must be initialized
+    pub fn default_value(type_annotation: &TypeAnnotation) -> Option<Value> {
+        let value = match type_annotation {
             TypeAnnotation::F32 => Value::Float32(0.0),
             TypeAnnotation::F64 => Value::Float64(0.0),
             TypeAnnotation::Bool => Value::Boolean(false),
             TypeAnnotation::String => Value::String(String::new()),
+            TypeAnnotation::Unit => Value::Unit,
             TypeAnnotation::Array(_) => Value::Array(Vec::new()),
-            // Struct, callable defaults values are deferred until they hit
-            // the interpreter. Ident should never be processed because
-            // ident is only ever used by the scanner
-            _ => Value::Unit,
-        }
+            TypeAnnotation::Map(..) => Value::Map(Box::default()),
+            TypeAnnotation::Tuple(element_types) => Value::Tuple(
+                element_types
+                    .iter()
+                    .map(|element_type| {
+                        let value = Value::default_value(element_type)?;
+                        Some(TypedValue::new(value, element_type.clone()))
+                    })
+                    .collect::<Option<Vec<TypedValue>>>()?,
+            ),
+            other => Value::convert_integer(0, other.numeric_type()?),
+        };
+        Some(value)
     }
 
     pub fn type_to_str(&self) -> &str {
@@ -170,8 +424,16 @@ impl Value {
             Value::Callable(_) => "callable",
             Value::String(_) => "string",
             Value::Enum(_) => "enum",
+            Value::EnumVariant(_) => "enum variant",
             Value::Int32(_) => "i32",
             Value::Int64(_) => "i64",
+            Value::Int8(_) => "i8",
+            Value::Int16(_) => "i16",
+            Value::UInt8(_) => "u8",
+            Value::UInt16(_) => "u16",
+            Value::UInt32(_) => "u32",
+            Value::UInt64(_) => "u64",
+            Value::USize(_) => "usize",
             Value::Float32(_) => "f32",
             Value::Float64(_) => "f64",
             Value::TraitFunction(_) => "trait fn",
@@ -179,217 +441,351 @@ impl Value {
             Value::Ident(_) => "Ident",
             Value::Boolean(_) => "bool",
             Value::Array(_) => "array",
+            Value::Map(_) => "map",
+            Value::Tuple(_) => "tuple",
+            Value::Range(_) => "range",
             Value::Unit => "()",
         }
     }
 
-    pub fn as_array_index(&self) -> Result<usize, LangError> {
-        match self {
-            Value::Int64(i) => Ok(*i as usize),
-            Value::Float64(f) => Ok(*f as usize),
-            Value::Boolean(b) => Ok(*b as usize),
-            _ => Err(LangErrorType::new_runtime_error(
-                RuntimeErrorType::GenericError {
-                    reason: error_message(&ErrMessage::IncorrectIndexType(self.to_string())),
-                },
-            )),
-        }
+    /// The position this indexes in an array of length `len`, which must be in its bounds
+    pub fn as_array_index(&self, len: usize) -> Result<usize, LangError> {
+        let index = match self {
+            integer if integer.integer().is_some() => integer.integer().unwrap_or_default(),
+            Value::Float64(f) => *f as i128,
+            Value::Boolean(b) => i128::from(*b),
+            _ => {
+                return Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::GenericError {
+                        reason: error_message(&ErrMessage::IncorrectIndexType(self.to_string())),
+                    },
+                ))
+            }
+        };
+        usize::try_from(index)
+            .ok()
+            .filter(|index| *index < len)
+            .ok_or_else(|| {
+                LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                    reason: error_message(&ErrMessage::IndexOutOfBounds(self.to_string(), len)),
+                })
+            })
     }
 }
 
+// Consistent with `PartialEq`, numbers of any width are equal to the integers they truncate to
+// and struct instances are only equal to the copies that share their fields
 impl Hash for Value {
-    fn hash<H>(&self, _state: &mut H)
+    fn hash<H>(&self, state: &mut H)
     where
         H: Hasher,
     {
+        match self {
+            Value::Struct(struct_value) => {
+                struct_value.callable_trait().get_name().hash(state);
+                struct_value.struct_trait().field_entries().hash(state);
+            }
+            Value::Enum(enum_value) => enum_value.name.hash(state),
+            Value::EnumVariant(variant) => {
+                variant.enum_name.hash(state);
+                variant.variant.hash(state);
+                for field in variant.fields.iter() {
+                    field.value.hash(state);
+                }
+            }
+            Value::String(string_value) => string_value.hash(state),
+            Value::Char(ch) => ch.hash(state),
+            Value::Int32(int_value) => i64::from(*int_value).hash(state),
+            Value::Int64(int_value) => int_value.hash(state),
+            Value::Int8(int_value) => i64::from(*int_value).hash(state),
+            Value::Int16(int_value) => i64::from(*int_value).hash(state),
+            Value::UInt8(int_value) => i64::from(*int_value).hash(state),
+            Value::UInt16(int_value) => i64::from(*int_value).hash(state),
+            Value::UInt32(int_value) => i64::from(*int_value).hash(state),
+            Value::UInt64(int_value) => (*int_value as i64).hash(state),
+            Value::USize(int_value) => (*int_value as i64).hash(state),
+            Value::Float32(float_value) => (*float_value as i64).hash(state),
+            Value::Float64(float_value) => (*float_value as i64).hash(state),
+            Value::Ident(ident) => ident.hash(state),
+            Value::Boolean(bool_value) => bool_value.hash(state),
+            Value::Array(elements) | Value::Tuple(elements) => {
+                for element in elements.iter() {
+                    element.value.hash(state);
+                }
+            }
+            // Equal maps can hold their entries in any order
+            Value::Map(map) => map.len().hash(state),
+            Value::Range(range) => range.hash(state),
+            Value::Callable(_)
+            | Value::Trait(_)
+            | Value::TraitFunction(_)
+            | Value::SelfIndex(_)
+            | Value::Unit => {}
+        }
     }
 }
 
-// TODO: All operations should be done by implementing traits so we can have
-// operator overloading
-impl Add for Value {
-    type Output = Value;
+impl Value {
+    /// Applies `operator` to two numbers, see `Number::apply`
+    fn apply(&self, other: &Value, operator: BinaryOperator) -> Result<Value, LangError> {
+        let types = (self.type_to_str(), other.type_to_str());
+        Number::apply(operator, self.number(), other.number(), types).map(Value::from)
+    }
 
-    #[inline(always)]
-    fn add(self, other: Value) -> Value {
+    /// The value as a number, if it's one
+    pub fn number(&self) -> Option<Number> {
         match self {
-            Value::Int64(lhs) => match other {
-                Value::Int64(rhs) => Value::Int64(lhs + rhs),
-                Value::Float64(rhs) => Value::Int64(lhs + rhs as i64),
-                _ => Value::Int64(lhs),
-            },
-            Value::Int32(lhs) => match other {
-                Value::Int32(rhs) => Value::Int32(lhs + rhs),
-                Value::Float32(rhs) => Value::Int32(lhs + rhs as i32),
-                _ => Value::Int32(lhs),
-            },
-            Value::Float64(lhs) => match other {
-                Value::Int64(rhs) => Value::Float64(lhs + rhs as f64),
-                Value::Float64(rhs) => Value::Float64(lhs + rhs),
-                _ => Value::Float64(lhs),
-            },
-            Value::String(lhs) => match other {
-                Value::String(rhs) => Value::String(format!("{}{}", lhs, rhs)),
-                _ => Value::String(lhs),
-            },
-            _ => self,
+            Value::Int8(value) => Some(Number::I8(*value)),
+            Value::Int16(value) => Some(Number::I16(*value)),
+            Value::Int32(value) => Some(Number::I32(*value)),
+            Value::Int64(value) => Some(Number::I64(*value)),
+            Value::UInt8(value) => Some(Number::U8(*value)),
+            Value::UInt16(value) => Some(Number::U16(*value)),
+            Value::UInt32(value) => Some(Number::U32(*value)),
+            Value::UInt64(value) => Some(Number::U64(*value)),
+            Value::USize(value) => Some(Number::USize(*value)),
+            Value::Float32(value) => Some(Number::F32(*value)),
+            Value::Float64(value) => Some(Number::F64(*value)),
+            _ => None,
         }
     }
+
+    /// The type of the number this is, if it's one
+    pub fn numeric_type(&self) -> Option<NumericType> {
+        self.number().map(Number::numeric_type)
+    }
+
+    /// The value of an integer of any width, which all fit in an i128
+    pub fn integer(&self) -> Option<i128> {
+        self.number()?.integer()
+    }
+
+    /// How `{:?}` writes the value, see `format::debug_string`
+    pub fn debug_string(&self, arena: &Arena<TypedValue>) -> Result<String, LangError> {
+        format::debug_string(&InArena { value: self, arena })
+    }
+
+    /// `value` as an integer of type `numeric_type`, or `None` when it's out of the type's range
+    pub fn from_integer(value: i128, numeric_type: NumericType) -> Option<Value> {
+        Number::from_integer(value, numeric_type).map(Value::from)
+    }
+
+    /// Converts an integer to `numeric_type` the way `as` does, truncating it to narrower integers
+    pub fn convert_integer(value: i128, numeric_type: NumericType) -> Value {
+        Number::convert_integer(value, numeric_type).into()
+    }
+
+    /// Converts a number, bool or char to the numeric type `target` the way `as` does
+    pub fn cast(&self, target: NumericType) -> Result<Value, LangError> {
+        let number = match self {
+            Value::Boolean(value) => Some(Number::convert_integer(i128::from(*value), target)),
+            Value::Char(value) => Some(Number::convert_integer(
+                i128::from(u32::from(*value)),
+                target,
+            )),
+            other => other.number().map(|number| number.cast(target)),
+        };
+        number.map(Value::from).ok_or_else(|| {
+            LangErrorType::new_runtime_error(RuntimeErrorType::InvalidTypeAssignmentError {
+                reason: format!(
+                    "cannot cast a value of type {} to a number",
+                    self.type_to_str()
+                ),
+            })
+        })
+    }
 }
 
-impl<'a> Add for &'a Value {
-    type Output = Value;
+impl From<Number> for Value {
+    fn from(number: Number) -> Value {
+        match number {
+            Number::I8(value) => Value::Int8(value),
+            Number::I16(value) => Value::Int16(value),
+            Number::I32(value) => Value::Int32(value),
+            Number::I64(value) => Value::Int64(value),
+            Number::U8(value) => Value::UInt8(value),
+            Number::U16(value) => Value::UInt16(value),
+            Number::U32(value) => Value::UInt32(value),
+            Number::U64(value) => Value::UInt64(value),
+            Number::USize(value) => Value::USize(value),
+            Number::F32(value) => Value::Float32(value),
+            Number::F64(value) => Value::Float64(value),
+        }
+    }
+}
 
-    #[inline(always)]
-    fn add(self, other: &'a Value) -> Value {
-        match self {
-            Value::Int64(lhs) => match other {
-                Value::Int64(rhs) => Value::Int64(lhs + rhs),
-                Value::Float64(rhs) => Value::Int64(lhs + *rhs as i64),
-                _ => Value::Int64(*lhs),
-            },
-            Value::Int32(lhs) => match other {
-                Value::Int32(rhs) => Value::Int32(lhs + rhs),
-                Value::Float32(rhs) => Value::Int32(lhs + *rhs as i32),
-                _ => Value::Int32(*lhs),
-            },
-            Value::Float64(lhs) => match other {
-                Value::Int64(rhs) => Value::Float64(lhs + *rhs as f64),
-                Value::Float64(rhs) => Value::Float64(lhs + rhs),
-                _ => Value::Float64(*lhs),
-            },
-            Value::String(lhs) => match other {
-                Value::String(rhs) => Value::String(format!("{}{}", lhs, rhs)),
-                _ => Value::String(lhs.clone()),
-            },
-            _ => self.clone(),
+/// A value along with the arena holding the fields of the struct instances within it
+struct InArena<'a> {
+    value: &'a Value,
+    arena: &'a Arena<TypedValue>,
+}
+
+impl<'a> InArena<'a> {
+    fn within(&self, value: &'a Value) -> InArena<'a> {
+        InArena {
+            value,
+            arena: self.arena,
         }
     }
 }
 
-impl Sub for Value {
-    type Output = Value;
+impl<'a> DebugValue for InArena<'a> {
+    /// The field entries of an instance
+    type Identity = Vec<ArenaEntryIndex>;
 
-    #[inline(always)]
-    fn sub(self, other: Value) -> Value {
-        match self {
-            Value::Int64(lhs) => match other {
-                Value::Int64(rhs) => Value::Int64(lhs - rhs),
-                Value::Float64(rhs) => Value::Int64(lhs - rhs as i64),
-                _ => Value::Int64(lhs),
-            },
-            Value::Float64(lhs) => match other {
-                Value::Int64(rhs) => Value::Float64(lhs - rhs as f64),
-                Value::Float64(rhs) => Value::Float64(lhs - rhs),
-                _ => Value::Float64(lhs),
+    fn debug_parts(&self) -> Result<DebugParts<Self, Self::Identity>, LangError> {
+        let values = |values: &'a [TypedValue]| -> Vec<Self> {
+            values
+                .iter()
+                .map(|value| self.within(&value.value))
+                .collect()
+        };
+        let value: &'a Value = self.value;
+        let parts = match value {
+            Value::String(s) => DebugParts::Leaf(format!("{:?}", s)),
+            Value::Char(c) => DebugParts::Leaf(format!("{:?}", c)),
+            Value::Float32(f) => DebugParts::Leaf(format!("{:?}", f)),
+            Value::Float64(f) => DebugParts::Leaf(format!("{:?}", f)),
+            Value::Unit => DebugParts::Leaf("()".to_string()),
+            Value::Array(elements) => DebugParts::Array(values(elements)),
+            Value::Tuple(elements) => DebugParts::Tuple(values(elements)),
+            Value::Map(map) => DebugParts::Map(
+                map.iter()
+                    .map(|(key, value)| (self.within(&key.value), self.within(&value.value)))
+                    .collect(),
+            ),
+            Value::EnumVariant(v) => DebugParts::Variant {
+                enum_name: v.enum_name.clone(),
+                variant: v.variant.clone(),
+                fields: values(&v.fields),
             },
-            _ => self,
-        }
+            Value::Struct(s) => {
+                let mut fields = Vec::new();
+                for (field, index) in s.fields() {
+                    let value: &TypedValue = (&self.arena[index]).try_into()?;
+                    fields.push((field, self.within(&value.value)));
+                }
+                DebugParts::Instance {
+                    name: s.callable_trait().get_name(),
+                    identity: s.field_entries(),
+                    fields,
+                }
+            }
+            other => DebugParts::Leaf(other.to_string()),
+        };
+        Ok(parts)
     }
 }
 
-impl<'a> Sub for &'a Value {
-    type Output = Value;
+impl<'a> Add for &'a Value {
+    type Output = Result<Value, LangError>;
 
     #[inline(always)]
-    fn sub(self, other: &'a Value) -> Value {
-        match self {
-            Value::Int64(lhs) => match other {
-                Value::Int64(rhs) => Value::Int64(lhs - rhs),
-                Value::Float64(rhs) => Value::Int64(lhs - *rhs as i64),
-                _ => Value::Int64(*lhs),
-            },
-            Value::Float64(lhs) => match other {
-                Value::Int64(rhs) => Value::Float64(lhs - *rhs as f64),
-                Value::Float64(rhs) => Value::Float64(lhs - rhs),
-                _ => Value::Float64(*lhs),
-            },
-            _ => self.clone(),
+    fn add(self, other: &'a Value) -> Result<Value, LangError> {
+        if let (Value::String(lhs), Value::String(rhs)) = (self, other) {
+            return Ok(Value::String(format!("{}{}", lhs, rhs)));
         }
+        self.apply(other, BinaryOperator::Add)
     }
 }
 
-impl Mul for Value {
-    type Output = Value;
+impl<'a> Sub for &'a Value {
+    type Output = Result<Value, LangError>;
 
     #[inline(always)]
-    fn mul(self, other: Value) -> Value {
-        match self {
-            Value::Int64(lhs) => match other {
-                Value::Int64(rhs) => Value::Int64(lhs * rhs),
-                Value::Float64(rhs) => Value::Int64(lhs * rhs as i64),
-                _ => Value::Int64(lhs),
-            },
-            Value::Float64(lhs) => match other {
-                Value::Int64(rhs) => Value::Float64(lhs * rhs as f64),
-                Value::Float64(rhs) => Value::Float64(lhs * rhs),
-                _ => Value::Float64(lhs),
-            },
-            _ => self,
-        }
+    fn sub(self, other: &'a Value) -> Result<Value, LangError> {
+        self.apply(other, BinaryOperator::Subtract)
     }
 }
 
 impl<'a> Mul for &'a Value {
-    type Output = Value;
+    type Output = Result<Value, LangError>;
 
     #[inline(always)]
-    fn mul(self, other: &'a Value) -> Value {
-        match self {
-            Value::Int64(lhs) => match other {
-                Value::Int64(rhs) => Value::Int64(lhs * rhs),
-                Value::Float64(rhs) => Value::Int64(lhs * (*rhs) as i64),
-                _ => Value::Int64(*lhs),
-            },
-            Value::Float64(lhs) => match other {
-                Value::Int64(rhs) => Value::Float64(lhs * (*rhs as f64)),
-                Value::Float64(rhs) => Value::Float64(lhs * rhs),
-                _ => Value::Float64(*lhs),
-            },
-            _ => self.clone(),
-        }
+    fn mul(self, other: &'a Value) -> Result<Value, LangError> {
+        self.apply(other, BinaryOperator::Multiply)
     }
 }
 
-impl Div for Value {
-    type Output = Value;
+impl<'a> Div for &'a Value {
+    type Output = Result<Value, LangError>;
 
     #[inline(always)]
-    fn div(self, other: Value) -> Value {
-        match self {
-            Value::Int64(lhs) => match other {
-                Value::Int64(rhs) => Value::Int64(lhs / rhs),
-                Value::Float64(rhs) => Value::Int64(lhs / rhs as i64),
-                _ => Value::Int64(lhs),
-            },
-            Value::Float64(lhs) => match other {
-                Value::Int64(rhs) => Value::Float64(lhs / rhs as f64),
-                Value::Float64(rhs) => Value::Float64(lhs / rhs),
-                _ => Value::Float64(lhs),
-            },
-            _ => self,
-        }
+    fn div(self, other: &'a Value) -> Result<Value, LangError> {
+        self.apply(other, BinaryOperator::Divide)
     }
 }
 
-impl<'a> Div for &'a Value {
-    type Output = Value;
+impl<'a> Rem for &'a Value {
+    type Output = Result<Value, LangError>;
 
     #[inline(always)]
-    fn div(self, other: &'a Value) -> Value {
-        match self {
-            Value::Int64(lhs) => match other {
-                Value::Int64(rhs) => Value::Int64(lhs / rhs),
-                Value::Float64(rhs) => Value::Int64(lhs / *rhs as i64),
-                _ => Value::Int64(*lhs),
-            },
-            Value::Float64(lhs) => match other {
-                Value::Int64(rhs) => Value::Float64(lhs / *rhs as f64),
-                Value::Float64(rhs) => Value::Float64(lhs / rhs),
-                _ => Value::Float64(*lhs),
-            },
-            _ => self.clone(),
-        }
+    fn rem(self, other: &'a Value) -> Result<Value, LangError> {
+        self.apply(other, BinaryOperator::Remainder)
+    }
+}
+
+impl<'a> BitAnd for &'a Value {
+    type Output = Result<Value, LangError>;
+
+    #[inline(always)]
+    fn bitand(self, other: &'a Value) -> Result<Value, LangError> {
+        self.apply(other, BinaryOperator::BitAnd)
+    }
+}
+
+impl<'a> BitOr for &'a Value {
+    type Output = Result<Value, LangError>;
+
+    #[inline(always)]
+    fn bitor(self, other: &'a Value) -> Result<Value, LangError> {
+        self.apply(other, BinaryOperator::BitOr)
+    }
+}
+
+impl<'a> BitXor for &'a Value {
+    type Output = Result<Value, LangError>;
+
+    #[inline(always)]
+    fn bitxor(self, other: &'a Value) -> Result<Value, LangError> {
+        self.apply(other, BinaryOperator::BitXor)
+    }
+}
+
+impl<'a> Shl for &'a Value {
+    type Output = Result<Value, LangError>;
+
+    #[inline(always)]
+    fn shl(self, other: &'a Value) -> Result<Value, LangError> {
+        self.apply(other, BinaryOperator::ShiftLeft)
+    }
+}
+
+impl<'a> Shr for &'a Value {
+    type Output = Result<Value, LangError>;
+
+    #[inline(always)]
+    fn shr(self, other: &'a Value) -> Result<Value, LangError> {
+        self.apply(other, BinaryOperator::ShiftRight)
+    }
+}
+
+/// Bitwise not, the `~` operator
+impl Not for &Value {
+    type Output = Result<Value, LangError>;
+
+    #[inline(always)]
+    fn not(self) -> Result<Value, LangError> {
+        Number::bit_not(self.number(), self.type_to_str()).map(Value::from)
+    }
+}
+
+impl Neg for &Value {
+    type Output = Result<Value, LangError>;
+
+    #[inline(always)]
+    fn neg(self) -> Result<Value, LangError> {
+        Number::negate(self.number(), self.type_to_str()).map(Value::from)
     }
 }
 
@@ -398,21 +794,44 @@ impl Eq for Value {}
 // Value equivalence
 impl PartialEq for Value {
     fn eq(&self, other: &Value) -> bool {
+        // Integers of any width are compared by value
+        if let (Some(lhs), Some(rhs)) = (self.integer(), other.integer()) {
+            return lhs == rhs;
+        }
         match self {
             Value::Char(lhs) => match other {
                 Value::Char(rhs) => lhs == rhs,
                 _ => false,
             },
-            Value::Struct(_) => false,
+            // Copies of an instance share its fields
+            Value::Struct(lhs) => match other {
+                Value::Struct(rhs) => {
+                    lhs.callable_trait().get_name() == rhs.callable_trait().get_name()
+                        && lhs.struct_trait().field_entries() == rhs.struct_trait().field_entries()
+                }
+                _ => false,
+            },
             Value::SelfIndex(_) => false,
             Value::Callable(_) => false,
-            Value::Enum(_) => unimplemented!(),
+            Value::Enum(lhs) => match other {
+                Value::Enum(rhs) => lhs.name == rhs.name,
+                _ => false,
+            },
+            Value::EnumVariant(lhs) => match other {
+                Value::EnumVariant(rhs) => {
+                    lhs.enum_name == rhs.enum_name
+                        && lhs.variant == rhs.variant
+                        && lhs.fields == rhs.fields
+                }
+                _ => false,
+            },
             Value::String(lhs) => match other {
                 Value::String(rhs) => lhs == rhs,
                 _ => false,
             },
             Value::Int32(lhs) => match other {
                 Value::Int32(rhs) => lhs == rhs,
+                Value::Int64(rhs) => i64::from(*lhs) == *rhs,
                 Value::Float32(rhs) => *lhs == *rhs as i32,
                 _ => false,
             },
@@ -422,13 +841,22 @@ impl PartialEq for Value {
                 Value::Int64(rhs) => lhs == rhs,
                 _ => false,
             },
+            Value::Int8(_)
+            | Value::Int16(_)
+            | Value::UInt8(_)
+            | Value::UInt16(_)
+            | Value::UInt32(_)
+            | Value::UInt64(_)
+            | Value::USize(_) => false,
             Value::Float32(lhs) => match other {
                 Value::Float32(rhs) => lhs == rhs,
+                Value::Float64(rhs) => f64::from(*lhs) == *rhs,
                 Value::Int32(rhs) => *lhs == *rhs as f32,
                 _ => false,
             },
             Value::Float64(lhs) => match other {
                 Value::Int64(rhs) => *lhs == *rhs as f64,
+                Value::Float32(rhs) => *lhs == f64::from(*rhs),
                 Value::Float64(rhs) => lhs == rhs,
                 _ => false,
             },
@@ -446,6 +874,24 @@ impl PartialEq for Value {
                 Value::Array(rhs) => lhs == rhs,
                 _ => false,
             },
+            Value::Tuple(lhs) => match other {
+                Value::Tuple(rhs) => lhs == rhs,
+                _ => false,
+            },
+            Value::Range(lhs) => match other {
+                Value::Range(rhs) => lhs == rhs,
+                _ => false,
+            },
+            Value::Map(lhs) => match other {
+                Value::Map(rhs) => {
+                    lhs.len() == rhs.len()
+                        && lhs.lookup_iter().all(|(key, value)| {
+                            rhs.get(key)
+                                .is_some_and(|rhs_value| rhs_value.value == value.value)
+                        })
+                }
+                _ => false,
+            },
             Value::Unit => match other {
                 Value::Unit => true,
                 _ => false,
@@ -464,6 +910,15 @@ pub trait StructInstanceTrait: CallableTrait + StructTrait + Debug {
     fn box_clone(&self) -> Box<dyn StructInstanceTrait>;
     // For constructors
     fn callable_trait(&self) -> &dyn CallableTrait;
+    /// The default values of the fields that declare one, in declaration order
+    fn defaults(&self) -> &[(String, Expr)];
+    /// Builds an instance of the struct out of `values`, which has to hold a value for every
+    /// field and no others
+    fn instantiate(
+        &self,
+        arena: &mut Arena<TypedValue>,
+        values: HashMap<String, TypedValue>,
+    ) -> Result<TypedValue, LangError>;
     fn struct_trait(&self) -> &dyn StructTrait;
     fn set_instance_name(&mut self, name: String);
     fn get_instance_name(&self) -> String;
@@ -489,6 +944,47 @@ impl StructInstanceTrait for StructValue {
     fn callable_trait(&self) -> &dyn CallableTrait {
         self
     }
+
+    fn defaults(&self) -> &[(String, Expr)] {
+        &self.defaults
+    }
+
+    fn instantiate(
+        &self,
+        arena: &mut Arena<TypedValue>,
+        mut values: HashMap<String, TypedValue>,
+    ) -> Result<TypedValue, LangError> {
+        let mut names: Vec<&String> = values.keys().collect();
+        names.sort();
+        if let Some(name) = names
+            .into_iter()
+            .find(|name| !self.field_names.contains(name))
+        {
+            return Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::UndefinedVariable {
+                    reason: format!("struct '{}' has no field '{}'", self.struct_name, name),
+                },
+            ));
+        }
+        let mut new_instance = self.clone();
+        let mut names = self.field_names.clone();
+        names.sort();
+        for name in names {
+            let value = values.remove(&name).ok_or_else(|| {
+                LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                    reason: format!(
+                        "missing field '{}' in a literal of struct '{}'",
+                        name, self.struct_name
+                    ),
+                })
+            })?;
+            new_instance.fields.insert(name, arena.insert(value));
+        }
+        Ok(TypedValue::new(
+            Value::Struct(Box::new(new_instance)),
+            TypeAnnotation::User(self.struct_name.clone()),
+        ))
+    }
 }
 
 impl Clone for Box<dyn StructInstanceTrait> {
@@ -499,6 +995,9 @@ impl Clone for Box<dyn StructInstanceTrait> {
 
 impl Ord for Value {
     fn cmp(&self, other: &Value) -> Ordering {
+        if let (Some(lhs), Some(rhs)) = (self.integer(), other.integer()) {
+            return lhs.cmp(&rhs);
+        }
         match self {
             Value::Char(lhs) => match other {
                 Value::Char(rhs) => lhs.cmp(rhs),
@@ -508,18 +1007,37 @@ impl Ord for Value {
             Value::SelfIndex(_) => Ordering::Less,
             Value::Callable(_) => Ordering::Less,
             Value::Enum(_) => Ordering::Less,
+            // Variants of the same enum are ordered by their discriminants, then their payloads
+            Value::EnumVariant(lhs) => match other {
+                Value::EnumVariant(rhs) if lhs.enum_name == rhs.enum_name => {
+                    lhs.discriminant.cmp(&rhs.discriminant).then_with(|| {
+                        let lhs_fields = lhs.fields.iter().map(|field| &field.value);
+                        lhs_fields.cmp(rhs.fields.iter().map(|field| &field.value))
+                    })
+                }
+                _ => Ordering::Less,
+            },
             Value::String(lhs) => match other {
                 Value::String(rhs) => lhs.cmp(&rhs),
                 _ => Ordering::Less,
             },
             Value::Int32(lhs) => match other {
                 Value::Int32(rhs) => lhs.cmp(&rhs),
+                Value::Int64(rhs) => i64::from(*lhs).cmp(rhs),
                 _ => Ordering::Less,
             },
             Value::Int64(lhs) => match other {
                 Value::Int64(rhs) => lhs.cmp(&rhs),
+                Value::Int32(rhs) => lhs.cmp(&i64::from(*rhs)),
                 _ => Ordering::Less,
             },
+            Value::Int8(_)
+            | Value::Int16(_)
+            | Value::UInt8(_)
+            | Value::UInt16(_)
+            | Value::UInt32(_)
+            | Value::UInt64(_)
+            | Value::USize(_) => Ordering::Less,
             Value::Float32(lhs) => match other {
                 Value::Float32(rhs) => {
                     if lhs == rhs {
@@ -530,9 +1048,11 @@ impl Ord for Value {
                         Ordering::Greater
                     }
                 }
+                Value::Float64(_) => Value::Float64(f64::from(*lhs)).cmp(other),
                 _ => Ordering::Less,
             },
             Value::Float64(lhs) => match other {
+                Value::Float32(rhs) => self.cmp(&Value::Float64(f64::from(*rhs))),
                 Value::Float64(rhs) => {
                     if lhs == rhs {
                         Ordering::Equal
@@ -567,6 +1087,19 @@ impl Ord for Value {
                 }
                 _ => Ordering::Less,
             },
+            Value::Map(_) => Ordering::Less,
+            // Tuples compare element by element, like Rust's
+            Value::Tuple(lhs) => match other {
+                Value::Tuple(rhs) => lhs
+                    .iter()
+                    .map(|element| &element.value)
+                    .cmp(rhs.iter().map(|element| &element.value)),
+                _ => Ordering::Less,
+            },
+            Value::Range(lhs) => match other {
+                Value::Range(rhs) => (lhs.start, lhs.end).cmp(&(rhs.start, rhs.end)),
+                _ => Ordering::Less,
+            },
             Value::Unit => match other {
                 Value::Unit => Ordering::Equal,
                 _ => Ordering::Less,
@@ -581,11 +1114,19 @@ impl Clone for Value {
             Value::Struct(s) => Value::Struct(s.clone()),
             Value::Char(ch) => Value::Char(*ch),
             Value::SelfIndex(s) => Value::SelfIndex(s.clone()),
-            Value::Enum(_) => unimplemented!(),
+            Value::Enum(e) => Value::Enum(e.clone()),
+            Value::EnumVariant(v) => Value::EnumVariant(v.clone()),
             Value::Callable(c) => Value::Callable(c.clone()),
             Value::String(lhs) => Value::String(lhs.clone()),
             Value::Int32(lhs) => Value::Int32(*lhs),
             Value::Int64(lhs) => Value::Int64(*lhs),
+            Value::Int8(lhs) => Value::Int8(*lhs),
+            Value::Int16(lhs) => Value::Int16(*lhs),
+            Value::UInt8(lhs) => Value::UInt8(*lhs),
+            Value::UInt16(lhs) => Value::UInt16(*lhs),
+            Value::UInt32(lhs) => Value::UInt32(*lhs),
+            Value::UInt64(lhs) => Value::UInt64(*lhs),
+            Value::USize(lhs) => Value::USize(*lhs),
             Value::Float32(lhs) => Value::Float32(*lhs),
             Value::Float64(lhs) => Value::Float64(*lhs),
             Value::TraitFunction(lhs) => Value::TraitFunction(lhs.clone()),
@@ -593,6 +1134,9 @@ impl Clone for Value {
             Value::Ident(lhs) => Value::Ident(lhs.clone()),
             Value::Boolean(lhs) => Value::Boolean(*lhs),
             Value::Array(lhs) => Value::Array(lhs.clone()),
+            Value::Map(lhs) => Value::Map(lhs.clone()),
+            Value::Tuple(lhs) => Value::Tuple(lhs.clone()),
+            Value::Range(lhs) => Value::Range(lhs.clone()),
             Value::Unit => Value::Unit,
         }
     }
@@ -612,13 +1156,25 @@ impl Debug for Value {
                 "Value::SelfIndex({}, {})",
                 self_index.name, self_index.env_id
             ),
-            Value::Enum(_) => unimplemented!(),
+            Value::Enum(enum_value) => write!(f, "Value::Enum({})", enum_value.name),
+            Value::EnumVariant(variant) => write!(
+                f,
+                "Value::EnumVariant({}::{})",
+                variant.enum_name, variant.variant
+            ),
             Value::Callable(callable_value) => {
                 write!(f, "Value::Callable({})", callable_value.get_name())
             }
             Value::String(string_value) => write!(f, "Value::String({})", string_value),
             Value::Int32(int_value) => write!(f, "Value::Int32({})", int_value),
             Value::Int64(int_value) => write!(f, "Value::Int64({})", int_value),
+            Value::Int8(int_value) => write!(f, "Value::Int8({})", int_value),
+            Value::Int16(int_value) => write!(f, "Value::Int16({})", int_value),
+            Value::UInt8(int_value) => write!(f, "Value::UInt8({})", int_value),
+            Value::UInt16(int_value) => write!(f, "Value::UInt16({})", int_value),
+            Value::UInt32(int_value) => write!(f, "Value::UInt32({})", int_value),
+            Value::UInt64(int_value) => write!(f, "Value::UInt64({})", int_value),
+            Value::USize(int_value) => write!(f, "Value::USize({})", int_value),
             Value::Float32(float_value) => write!(f, "Value::Float32({})", float_value),
             Value::Float64(float_value) => write!(f, "Value::Float64({})", float_value),
             // TODO: Fix this
@@ -629,6 +1185,11 @@ impl Debug for Value {
             Value::Ident(ident_value) => write!(f, "Value::Ident({})", ident_value),
             Value::Boolean(bool_value) => write!(f, "Value::Boolean({})", bool_value),
             Value::Array(array_value) => write!(f, "Value::Array({:?})", array_value),
+            Value::Map(map_value) => {
+                write!(f, "Value::Map({:?})", map_value.iter().collect::<Vec<_>>())
+            }
+            Value::Tuple(elements) => write!(f, "Value::Tuple({:?})", elements),
+            Value::Range(range) => write!(f, "Value::Range({:?})", range),
             Value::Unit => write!(f, "Value::Unit"),
         }
     }
@@ -673,22 +1234,45 @@ impl TypedValue {
         Ok(())
     }
 
-    pub fn as_array_index(&self) -> Result<usize, LangError> {
+    /// Inserts an entry into the map `self`, returning `Err` if the types of `key` or `value`
+    /// don't match those of the map. `map_key` is the map key of `key`, see `MapKey::new`
+    pub fn insert_checked(
+        &mut self,
+        key: TypedValue,
+        map_key: MapKey,
+        value: TypedValue,
+    ) -> Result<(), LangError> {
+        if let TypeAnnotation::Map(ref key_type, ref value_type) = self.value_type {
+            for (expected, found) in [(key_type, &key), (value_type, &value)] {
+                if !expected.admits(&found.value_type)
+                    && !TypeChecker::can_convert_implicitly(&**expected, found)
+                {
+                    return Err(LangErrorType::new_runtime_error(
+                        RuntimeErrorType::InvalidTypeAssignmentError {
+                            reason: format!(
+                                "tried to insert a value of type {} into a map of type {}",
+                                found.value_type, self.value_type
+                            ),
+                        },
+                    ));
+                }
+            }
+        }
         match self.value {
-            Value::Int64(i) => Ok(i as usize),
-            Value::Int32(i) => Ok(i as usize),
-            Value::Float64(f) => Ok(f as usize),
-            Value::Boolean(b) => Ok(b as usize),
-            _ => Err(LangErrorType::new_runtime_error(
-                RuntimeErrorType::GenericError {
-                    reason: format!(
-                        "Tried to index an array with incorrect type '{}'",
-                        self.value.type_to_str()
-                    ),
-                },
-            )),
+            Value::Map(ref mut map) => {
+                map.insert(key, map_key, value);
+                Ok(())
+            }
+            _ => Err(LangErrorType::new_iie_error(error_message(
+                &ErrMessage::ExpectValueType("map".to_string()),
+            ))),
         }
     }
+
+    /// See `Value::as_array_index`
+    pub fn as_array_index(&self, len: usize) -> Result<usize, LangError> {
+        self.value.as_array_index(len)
+    }
 }
 
 impl GetTypeAnnotation for TypedValue {
@@ -706,21 +1290,38 @@ impl TypedValue {
 #[derive(Clone, Debug)]
 pub struct StructValue {
     struct_name: String,
+    /// The values of an instance's fields, which a declaration has none of
     fields: HashMap<String, ArenaEntryIndex>,
+    /// The names of the fields in declaration order
+    field_names: Vec<String>,
+    /// The default values of the fields that declare one, in declaration order
+    defaults: Vec<(String, Expr)>,
+    /// The values `Name()` gives the fields, see `StructStmt::constructor_fields`
+    constructor_fields: Vec<(String, Expr)>,
     methods: HashMap<String, ArenaEntryIndex>,
     instance_name: String,
 }
 
 impl StructValue {
-    pub fn new(
-        struct_stmt: &StructStmt,
-        fields: HashMap<String, ArenaEntryIndex>,
-        instance_name: String,
-    ) -> StructValue {
+    pub fn new(struct_stmt: &StructStmt, instance_name: String) -> StructValue {
         StructValue {
             struct_name: struct_stmt.name.clone(),
+            defaults: struct_stmt
+                .fields
+                .iter()
+                .filter_map(|field| {
+                    let default = field.default.clone()?;
+                    Some((field.identifier.clone(), default))
+                })
+                .collect(),
+            constructor_fields: StructStmt::constructor_fields(&struct_stmt.fields),
             methods: HashMap::new(),
-            fields,
+            fields: HashMap::new(),
+            field_names: struct_stmt
+                .fields
+                .iter()
+                .map(|field| field.identifier.clone())
+                .collect(),
             instance_name,
         }
     }
@@ -752,22 +1353,241 @@ impl CallableTrait for StructValue {
         vec![]
     }
 
-    // TODO: This should take constructor args
+    /// `Name()` is a struct literal giving every field its default value
+    fn call(
+        &self,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+        interpreter: &Interpreter,
+        args: Vec<ArenaEntryIndex>,
+    ) -> Result<TypedValue, LangError> {
+        if !args.is_empty() {
+            return Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::FnArityError {
+                    reason: format!(
+                        "Struct constructor {} takes no arguments, passed {}",
+                        self.struct_name,
+                        args.len()
+                    ),
+                },
+            ));
+        }
+        interpreter
+            .instantiate_struct(self, &self.constructor_fields, arena, env)
+            .map_err(Interrupt::into_error)
+    }
+
+    fn box_clone(&self) -> Box<dyn CallableTrait> {
+        Box::new((*self).clone())
+    }
+}
+
+/// A method of the map in the arena entry `map`, e.g. `scores.insert`
+#[derive(Clone, Debug)]
+pub struct BoundMapMethod {
+    map: ArenaEntryIndex,
+    method: MapMethod,
+}
+
+impl BoundMapMethod {
+    pub fn new(map: ArenaEntryIndex, method: MapMethod) -> BoundMapMethod {
+        BoundMapMethod { map, method }
+    }
+}
+
+impl CallableTrait for BoundMapMethod {
+    fn get_name(&self) -> String {
+        format!("<fn Map::{}>", self.method.name())
+    }
+
+    fn arity(&self) -> usize {
+        self.method.arity()
+    }
+
+    fn get_return_type(&self) -> Option<TypeAnnotation> {
+        None
+    }
+
+    fn bind(
+        &self,
+        _: &dyn StructInstanceTrait,
+        _: &mut Environment,
+        _: &mut Arena<TypedValue>,
+    ) -> Result<(), LangError> {
+        Err(LangErrorType::new_iie_error(format!(
+            "tried to bind the map method {} to a struct instance",
+            self.get_name()
+        )))
+    }
+
+    fn get_params(&self) -> Vec<VariableData> {
+        vec![]
+    }
+
     fn call(
         &self,
         arena: &mut Arena<TypedValue>,
         _: &mut Environment,
         _: &Interpreter,
-        _: Vec<ArenaEntryIndex>,
+        args: Vec<ArenaEntryIndex>,
     ) -> Result<TypedValue, LangError> {
-        let mut new_instance = self.clone();
-        for field in new_instance.fields.iter_mut() {
-            let field_index = arena.insert(TypedValue::default());
-            *field.1 = field_index;
+        if args.len() != self.arity() {
+            return Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::FnArityError {
+                    reason: format!(
+                        "Function {} requires {} arg(s), passed {}",
+                        self.get_name(),
+                        self.arity(),
+                        args.len()
+                    ),
+                },
+            ));
+        }
+        let mut arg_values = Vec::new();
+        for arg in args {
+            let arg_value_entry = &arena[arg];
+            let arg_value: TypedValue = arg_value_entry.try_into()?;
+            arg_values.push(arg_value);
+        }
+        let map_key = match arg_values.first() {
+            Some(key) => MapKey::new(&key.value, arena)?,
+            None => MapKey::Value(Value::Unit),
+        };
+        let map_entry = &mut arena[self.map];
+        let map_value: &mut TypedValue = map_entry.try_into()?;
+        if let MapMethod::Insert = self.method {
+            let value = arg_values.pop().unwrap_or_default();
+            let key = arg_values.pop().unwrap_or_default();
+            map_value.insert_checked(key, map_key, value)?;
+            return Ok(TypedValue::new(Value::Unit, TypeAnnotation::Unit));
+        }
+        let (key_type, value_type) = match map_value.value_type {
+            TypeAnnotation::Map(ref key_type, ref value_type) => {
+                (*key_type.clone(), *value_type.clone())
+            }
+            _ => (TypeAnnotation::Unit, TypeAnnotation::Unit),
+        };
+        let map = match map_value.value {
+            Value::Map(ref mut map) => map,
+            _ => {
+                return Err(LangErrorType::new_iie_error(error_message(
+                    &ErrMessage::ExpectValueType("map".to_string()),
+                )))
+            }
+        };
+        let result = match self.method {
+            MapMethod::Remove => TypedValue::new(
+                Value::Boolean(map.remove(&map_key).is_some()),
+                TypeAnnotation::Bool,
+            ),
+            MapMethod::Contains => TypedValue::new(
+                Value::Boolean(map.contains_key(&map_key)),
+                TypeAnnotation::Bool,
+            ),
+            MapMethod::Len => TypedValue::new(Value::Int64(map.len() as i64), TypeAnnotation::I64),
+            MapMethod::Keys => TypedValue::new(
+                Value::Array(map.iter().map(|(key, _)| key.clone()).collect()),
+                TypeAnnotation::Array(Box::new(key_type)),
+            ),
+            MapMethod::Values => TypedValue::new(
+                Value::Array(map.iter().map(|(_, value)| value.clone()).collect()),
+                TypeAnnotation::Array(Box::new(value_type)),
+            ),
+            MapMethod::Insert => unreachable!(),
+        };
+        Ok(result)
+    }
+
+    fn box_clone(&self) -> Box<dyn CallableTrait> {
+        Box::new((*self).clone())
+    }
+
+    fn trace(&self, tracer: &mut Tracer) {
+        tracer.value(self.map);
+    }
+}
+
+impl CallableTrait for EnumVariantConstructor {
+    fn get_name(&self) -> String {
+        format!("<fn {}::{}>", self.enum_name, self.variant)
+    }
+
+    fn arity(&self) -> usize {
+        self.decl.fields.len()
+    }
+
+    fn get_return_type(&self) -> Option<TypeAnnotation> {
+        Some(TypeAnnotation::User(self.enum_name.clone()))
+    }
+
+    fn bind(
+        &self,
+        _: &dyn StructInstanceTrait,
+        _: &mut Environment,
+        _: &mut Arena<TypedValue>,
+    ) -> Result<(), LangError> {
+        Err(LangErrorType::new_iie_error(format!(
+            "tried to bind the enum variant constructor {} to a struct instance",
+            self.get_name()
+        )))
+    }
+
+    fn get_params(&self) -> Vec<VariableData> {
+        self.decl
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field_type)| VariableData::new(i.to_string(), field_type.clone()))
+            .collect()
+    }
+
+    fn call(
+        &self,
+        arena: &mut Arena<TypedValue>,
+        _: &mut Environment,
+        _: &Interpreter,
+        args: Vec<ArenaEntryIndex>,
+    ) -> Result<TypedValue, LangError> {
+        if args.len() != self.arity() {
+            return Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::FnArityError {
+                    reason: format!(
+                        "Enum variant {}::{} requires {} value(s), passed {}",
+                        self.enum_name,
+                        self.variant,
+                        self.arity(),
+                        args.len()
+                    ),
+                },
+            ));
+        }
+        let mut fields = Vec::new();
+        for (field_type, arg) in self.decl.fields.iter().zip(args) {
+            let arg_value_entry = &arena[arg];
+            let arg_value: TypedValue = arg_value_entry.try_into()?;
+            if !field_type.admits(&arg_value.value_type) {
+                return Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::InvalidFunctionArgumentType {
+                        reason: format!(
+                            "Tried to construct {}::{} with a value of type {:?} where type {:?} is expected",
+                            self.enum_name,
+                            self.variant,
+                            arg_value.value_type.to_string(),
+                            field_type.to_string()
+                        ),
+                    },
+                ));
+            }
+            fields.push(arg_value);
         }
         Ok(TypedValue::new(
-            Value::Struct(Box::new(new_instance)),
-            TypeAnnotation::User(CallableTrait::get_name(self)),
+            Value::EnumVariant(Box::new(EnumVariantValue {
+                enum_name: self.enum_name.clone(),
+                variant: self.variant.clone(),
+                discriminant: self.decl.discriminant.clone(),
+                fields,
+            })),
+            TypeAnnotation::User(self.enum_name.clone()),
         ))
     }
 
@@ -787,7 +1607,7 @@ impl StructTrait for StructValue {
 
     #[inline(always)]
     fn field_exists(&self, name: &str) -> bool {
-        self.fields.contains_key(name)
+        self.field_names.iter().any(|field| field == name)
     }
 
     fn get_field(&self, name: &str) -> Result<ArenaEntryIndex, LangError> {
@@ -828,21 +1648,92 @@ impl StructTrait for StructValue {
             |value| Ok(*value),
         )
     }
+
+    fn field_entries(&self) -> Vec<ArenaEntryIndex> {
+        let mut fields: Vec<(&String, &ArenaEntryIndex)> = self.fields.iter().collect();
+        fields.sort_by(|lhs, rhs| lhs.0.cmp(rhs.0));
+        fields.into_iter().map(|(_, index)| *index).collect()
+    }
+
+    fn fields(&self) -> Vec<(String, ArenaEntryIndex)> {
+        self.field_names
+            .iter()
+            .filter_map(|name| Some((name.clone(), *self.fields.get(name)?)))
+            .collect()
+    }
+
+    fn trace_entries(&self, tracer: &mut Tracer) {
+        for index in self.fields.values().chain(self.methods.values()) {
+            tracer.value(*index);
+        }
+    }
+}
+
+impl Trace for TypedValue {
+    fn trace(&self, tracer: &mut Tracer) {
+        match &self.value {
+            Value::Struct(struct_value) => struct_value.struct_trait().trace_entries(tracer),
+            Value::Callable(callable) => callable.trace(tracer),
+            Value::SelfIndex(self_index) => tracer.scope(self_index.env_id),
+            Value::EnumVariant(variant) => {
+                for field in variant.fields.iter() {
+                    field.trace(tracer);
+                }
+            }
+            Value::Trait(trait_value) => {
+                for fn_declaration in trait_value.fn_declarations.values() {
+                    fn_declaration.trace(tracer);
+                }
+            }
+            Value::Array(elements) | Value::Tuple(elements) => {
+                for element in elements.iter() {
+                    element.trace(tracer);
+                }
+            }
+            Value::Map(map) => {
+                for (key, value) in map.iter() {
+                    key.trace(tracer);
+                    value.trace(tracer);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 impl Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Value::Char(c) => return write!(f, "{}", c),
-            Value::Struct(s) => return write!(f, "{}", s.struct_trait().get_name()),
+            Value::Struct(s) => return write!(f, "{}", s.callable_trait().get_name()),
             Value::SelfIndex(s) => return write!(f, "{}, {}", s.name, s.env_id),
-            Value::Enum(_) => unimplemented!(),
+            Value::Enum(e) => return write!(f, "enum {}", e.name),
+            Value::EnumVariant(v) => {
+                write!(f, "{}::{}", v.enum_name, v.variant)?;
+                if !v.fields.is_empty() {
+                    let formatted_fields: String = v
+                        .fields
+                        .iter()
+                        .map(|field| field.value.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    write!(f, "({})", formatted_fields)?;
+                }
+                return Ok(());
+            }
             Value::Callable(c) => return write!(f, "{}", c.get_name()),
             Value::String(v) => {
                 return write!(f, "{}", v);
             }
             Value::Int32(i) => return write!(f, "{}", i),
             Value::Int64(i) => return write!(f, "{}", i),
+            Value::Int8(i) => return write!(f, "{}", i),
+            Value::Int16(i) => return write!(f, "{}", i),
+            Value::UInt8(i) => return write!(f, "{}", i),
+            Value::UInt16(i) => return write!(f, "{}", i),
+            Value::UInt32(i) => return write!(f, "{}", i),
+            Value::UInt64(i) => return write!(f, "{}", i),
+            Value::USize(i) => return write!(f, "{}", i),
             Value::Float32(fl) => return write!(f, "{}", fl),
             Value::Float64(fl) => return write!(f, "{}", fl),
             // TODO: Fix this
@@ -858,6 +1749,26 @@ impl Display for Value {
                     .join(" ");
                 return write!(f, "{}", formatted_string);
             }
+            Value::Map(map) => {
+                if map.is_empty() {
+                    return write!(f, "[:]");
+                }
+                let formatted_entries: String = map
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key.value, value.value))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                return write!(f, "[{}]", formatted_entries);
+            }
+            Value::Tuple(elements) => {
+                let formatted_elements: String = elements
+                    .iter()
+                    .map(|element| element.value.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                return write!(f, "({})", formatted_elements);
+            }
+            Value::Range(range) => return write!(f, "{}", range),
             Value::Unit => return write!(f, "unit"),
         };
     }
@@ -913,7 +1824,7 @@ impl CallableTrait for Callable {
             }),
             TypeAnnotation::SelfIndex,
         );
-        env.define_and_insert(self.closure, arena, "self", value);
+        env.define_and_insert(self.closure, arena, "self", value)?;
         Ok(())
     }
 
@@ -921,6 +1832,10 @@ impl CallableTrait for Callable {
         self.function.params.clone()
     }
 
+    fn trace(&self, tracer: &mut Tracer) {
+        tracer.scope(self.closure);
+    }
+
     fn get_return_type(&self) -> Option<TypeAnnotation> {
         match self.function.return_type {
             TokenType::Type(ref type_name) => match type_name {
@@ -937,8 +1852,6 @@ impl CallableTrait for Callable {
         interpreter: &Interpreter,
         args: Vec<ArenaEntryIndex>,
     ) -> Result<TypedValue, LangError> {
-        let mut env_id = env.entry_from(self.closure);
-
         if args.len() != self.arity() {
             return Err(LangErrorType::new_runtime_error(
                 RuntimeErrorType::FnArityError {
@@ -951,6 +1864,7 @@ impl CallableTrait for Callable {
                 },
             ));
         }
+        let mut env_id = env.entry_from(self.closure)?;
         let mut evaluated_args = Vec::new();
         for arg in args {
             let arg_value_entry = &arena[arg];
@@ -958,7 +1872,7 @@ impl CallableTrait for Callable {
             evaluated_args.push((arg, arg_value))
         }
         for it in self.function.params.iter().zip(evaluated_args.iter()) {
-            if it.0.type_annotation != (it.1).1.value_type {
+            if !it.0.type_annotation.admits(&(it.1).1.value_type) {
                 return Err(LangErrorType::new_runtime_error(
                     RuntimeErrorType::InvalidFunctionArgumentType {
                         reason: format!(
@@ -969,23 +1883,19 @@ impl CallableTrait for Callable {
                     },
                 ));
             }
-            env.define(env_id, &it.0.identifier, (it.1).0)
+            env.define(env_id, &it.0.identifier, (it.1).0)?;
         }
         let mut return_value = TypedValue::default();
-        if let Err(value_from_block) =
-            interpreter.execute_block(&self.function.body, &mut env_id, arena, env)
-        {
-            if let LangErrorType::ControlFlow { subtype } = value_from_block.context.get_context() {
-                if let ControlFlow::Return { index } = subtype {
-                    let entry = &arena[*index];
-                    let value: TypedValue = entry.try_into()?;
-                    return_value = value;
-                }
+        match interpreter.execute_block(&self.function.body, &mut env_id, arena, env) {
+            Ok(_) => {}
+            Err(Interrupt::ControlFlow(ControlFlow::Return { index })) => {
+                return_value = (&arena[index]).try_into()?;
             }
+            Err(interrupt) => return Err(interrupt.into_error()),
         }
         debug!("return from execute_block {:?}", return_value);
         if let Some(function_return_type) = self.get_return_type() {
-            if function_return_type != return_value.value_type {
+            if !function_return_type.admits(&return_value.value_type) {
                 return Err(LangErrorType::new_runtime_error(
                     RuntimeErrorType::InvalidFunctionReturnType {
                         reason: format!(
@@ -27,6 +27,8 @@ pub trait CallableTrait {
     ) -> Result<(), LangError>;
     fn get_params(&self) -> Vec<VariableData>;
     fn box_clone(&self) -> Box<dyn CallableTrait>;
+    /// Hands the environment entry the callable closes over, if any, to `tracer`
+    fn trace(&self, _: &mut Tracer) {}
 }
 
 impl Clone for Box<dyn CallableTrait> {
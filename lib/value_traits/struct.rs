@@ -8,4 +8,11 @@ pub trait StructTrait {
     fn field_exists(&self, name: &str) -> bool;
     fn define_method(&mut self, name: &str, value_index: ArenaEntryIndex) -> Result<(), LangError>;
     fn get_method(&self, name: &str) -> Result<ArenaEntryIndex, LangError>;
+    /// The arena indices of the struct's fields, ordered by field name. Copies of an instance
+    /// share its fields, so these identify the instance
+    fn field_entries(&self) -> Vec<ArenaEntryIndex>;
+    /// The names and arena indices of the struct's fields, in declaration order
+    fn fields(&self) -> Vec<(String, ArenaEntryIndex)>;
+    /// Hands the arena indices of the struct's fields and methods to `tracer`
+    fn trace_entries(&self, tracer: &mut Tracer);
 }
@@ -1,7 +1,7 @@
 use crate::ast::expr::*;
 use crate::ast::stmt::*;
 use crate::env::*;
-use crate::error::LangError;
+use crate::error::{Interrupt, LangError};
 use crate::mem::*;
 use crate::value::TypedValue;
 
@@ -20,12 +20,23 @@ pub trait VisitorMut<T>: Sized {
     fn visit_call(&mut self, call: &CallExpr) -> Result<T, LangError>;
     fn visit_get(&mut self, get: &GetExpr) -> Result<T, LangError>;
     fn visit_enum_path(&mut self, enum_path: &EnumPathExpr) -> Result<T, LangError>;
+    fn visit_match(&mut self, match_expr: &MatchExpr) -> Result<T, LangError>;
     fn visit_grouping(&mut self, grouping: &GroupingExpr) -> Result<T, LangError>;
     fn visit_literal(&mut self, literal: &LiteralExpr) -> Result<T, LangError>;
     fn visit_logical(&mut self, logical: &LogicalExpr) -> Result<T, LangError>;
     fn visit_set(&mut self, set: &SetExpr) -> Result<T, LangError>;
     fn visit_unary(&mut self, unary: &UnaryExpr) -> Result<T, LangError>;
     fn visit_array(&mut self, array: &ArrayExpr) -> Result<T, LangError>;
+    fn visit_map(&mut self, map: &MapExpr) -> Result<T, LangError>;
+    fn visit_tuple(&mut self, tuple: &TupleExpr) -> Result<T, LangError>;
+    fn visit_struct_literal(&mut self, struct_literal: &StructLiteralExpr) -> Result<T, LangError>;
+    fn visit_try(&mut self, try_expr: &TryExpr) -> Result<T, LangError>;
+    fn visit_cast(&mut self, cast: &CastExpr) -> Result<T, LangError>;
+    fn visit_block_expr(&mut self, block: &BlockExpr) -> Result<T, LangError>;
+    fn visit_if_expr(&mut self, if_expr: &IfExpr) -> Result<T, LangError>;
+    fn visit_loop(&mut self, loop_expr: &LoopExpr) -> Result<T, LangError>;
+    fn visit_range(&mut self, range: &RangeExpr) -> Result<T, LangError>;
+    fn visit_format_string(&mut self, format_string: &FormatStringExpr) -> Result<T, LangError>;
     fn visit_index(&mut self, index: &IndexExpr) -> Result<T, LangError>;
     fn visit_set_array_element(
         &mut self,
@@ -34,7 +45,8 @@ pub trait VisitorMut<T>: Sized {
     fn visit_variable(&mut self, variable: &VariableExpr) -> Result<T, LangError>;
     fn visit_self_ident(&mut self, self_ident: &SelfIdentExpr) -> Result<T, LangError>;
 
-    fn visit_break(&mut self) -> Result<T, LangError>;
+    fn visit_break(&mut self, break_stmt: &BreakStmt) -> Result<T, LangError>;
+    fn visit_continue(&mut self, continue_stmt: &ContinueStmt) -> Result<T, LangError>;
     fn visit_assert(&mut self, assert_stmt: &AssertStmt) -> Result<T, LangError>;
     fn visit_enum(&mut self, enum_stmt: &EnumStmt) -> Result<T, LangError>;
     fn visit_impl(&mut self, impl_stmt: &ImplStmt) -> Result<T, LangError>;
@@ -49,7 +61,9 @@ pub trait VisitorMut<T>: Sized {
     fn visit_print(&mut self, block: &PrintStmt) -> Result<T, LangError>;
     fn visit_return(&mut self, block: &ReturnStmt) -> Result<T, LangError>;
     fn visit_var(&mut self, block: &VarStmt) -> Result<T, LangError>;
+    fn visit_destructure(&mut self, destructure: &DestructureStmt) -> Result<T, LangError>;
     fn visit_while(&mut self, block: &WhileStmt) -> Result<T, LangError>;
+    fn visit_for_in(&mut self, for_in: &ForInStmt) -> Result<T, LangError>;
     fn visit_import(&mut self, import_stmt: &ImportStmt) -> Result<T, LangError>;
 }
 
@@ -60,12 +74,23 @@ pub fn visit_expr_mut<T, V: VisitorMut<T>>(visitor: &mut V, expr: &Expr) -> Resu
         Expr::Call(ref call_expr) => Ok(visitor.visit_call(&*call_expr)?),
         Expr::Get(ref get_expr) => Ok(visitor.visit_get(&*get_expr)?),
         Expr::EnumPath(ref enum_path_expr) => Ok(visitor.visit_enum_path(&*enum_path_expr)?),
+        Expr::Match(ref match_expr) => Ok(visitor.visit_match(&*match_expr)?),
         Expr::Grouping(ref grouping_expr) => Ok(visitor.visit_grouping(&*grouping_expr)?),
         Expr::Literal(ref literal_expr) => Ok(visitor.visit_literal(&*literal_expr)?),
         Expr::Logical(ref logical_expr) => Ok(visitor.visit_logical(&*logical_expr)?),
         Expr::Set(ref set_expr) => Ok(visitor.visit_set(&*set_expr)?),
         Expr::Unary(ref set_expr) => Ok(visitor.visit_unary(&*set_expr)?),
         Expr::Array(ref set_expr) => Ok(visitor.visit_array(&*set_expr)?),
+        Expr::Map(ref map_expr) => visitor.visit_map(map_expr),
+        Expr::Tuple(ref tuple_expr) => visitor.visit_tuple(tuple_expr),
+        Expr::StructLiteral(ref struct_literal) => visitor.visit_struct_literal(struct_literal),
+        Expr::Try(ref try_expr) => visitor.visit_try(try_expr),
+        Expr::Cast(ref cast) => visitor.visit_cast(cast),
+        Expr::Block(ref block) => visitor.visit_block_expr(block),
+        Expr::If(ref if_expr) => visitor.visit_if_expr(if_expr),
+        Expr::Loop(ref loop_expr) => visitor.visit_loop(loop_expr),
+        Expr::Range(ref range) => visitor.visit_range(range),
+        Expr::FormatString(ref format_string) => visitor.visit_format_string(format_string),
         Expr::Index(ref set_expr) => Ok(visitor.visit_index(&*set_expr)?),
         Expr::SetArrayElement(ref set_expr) => Ok(visitor.visit_set_array_element(&*set_expr)?),
         Expr::Variable(ref set_expr) => Ok(visitor.visit_variable(&*set_expr)?),
@@ -75,7 +100,8 @@ pub fn visit_expr_mut<T, V: VisitorMut<T>>(visitor: &mut V, expr: &Expr) -> Resu
 
 pub fn visit_stmt_mut<T, V: VisitorMut<T>>(visitor: &mut V, stmt: &Stmt) -> Result<T, LangError> {
     match stmt {
-        Stmt::Break => Ok(visitor.visit_break()?),
+        Stmt::Break(ref break_stmt) => visitor.visit_break(break_stmt),
+        Stmt::Continue(ref continue_stmt) => visitor.visit_continue(continue_stmt),
         Stmt::Assert(ref assert_stmt) => Ok(visitor.visit_assert(&*assert_stmt)?),
         Stmt::Enum(ref enum_stmt) => Ok(visitor.visit_enum(&*enum_stmt)?),
         Stmt::Impl(ref enum_stmt) => Ok(visitor.visit_impl(&*enum_stmt)?),
@@ -90,7 +116,9 @@ pub fn visit_stmt_mut<T, V: VisitorMut<T>>(visitor: &mut V, stmt: &Stmt) -> Resu
         Stmt::Print(ref enum_stmt) => Ok(visitor.visit_print(&*enum_stmt)?),
         Stmt::Return(ref enum_stmt) => Ok(visitor.visit_return(&*enum_stmt)?),
         Stmt::Var(ref enum_stmt) => Ok(visitor.visit_var(&*enum_stmt)?),
+        Stmt::Destructure(ref destructure_stmt) => visitor.visit_destructure(destructure_stmt),
         Stmt::While(ref enum_stmt) => Ok(visitor.visit_while(&*enum_stmt)?),
+        Stmt::ForIn(ref for_in) => visitor.visit_for_in(for_in),
         Stmt::Import(ref import_stmt) => Ok(visitor.visit_import(&*import_stmt)?),
     }
 }
@@ -103,7 +131,7 @@ pub trait Visitor<T>: Sized {
         expr: &Expr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError> {
+    ) -> Result<T, Interrupt> {
         Ok(visit_expr(self, expr, arena, env)?)
     }
     fn visit_stmt(
@@ -111,7 +139,7 @@ pub trait Visitor<T>: Sized {
         stmt: &Stmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError> {
+    ) -> Result<T, Interrupt> {
         Ok(visit_stmt(self, stmt, arena, env)?)
     }
 
@@ -120,189 +148,273 @@ pub trait Visitor<T>: Sized {
         assign: &AssignExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_binary(
         &self,
         binary: &BinaryExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_call(
         &self,
         call: &CallExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_get(
         &self,
         get: &GetExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_enum_path(
         &self,
         enum_path: &EnumPathExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
+    fn visit_match(
+        &self,
+        match_expr: &MatchExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
     fn visit_grouping(
         &self,
         grouping: &GroupingExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_literal(
         &self,
         literal: &LiteralExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_logical(
         &self,
         logical: &LogicalExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_set(
         &self,
         set: &SetExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_unary(
         &self,
         unary: &UnaryExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_array(
         &self,
         array: &ArrayExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
+    fn visit_map(
+        &self,
+        map: &MapExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_tuple(
+        &self,
+        tuple: &TupleExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_struct_literal(
+        &self,
+        struct_literal: &StructLiteralExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_try(
+        &self,
+        try_expr: &TryExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_cast(
+        &self,
+        cast: &CastExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_block_expr(
+        &self,
+        block: &BlockExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_if_expr(
+        &self,
+        if_expr: &IfExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_loop(
+        &self,
+        loop_expr: &LoopExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_range(
+        &self,
+        range: &RangeExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_format_string(
+        &self,
+        format_string: &FormatStringExpr,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
     fn visit_index(
         &self,
         index: &IndexExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_set_array_element(
         &self,
         set_array_element: &SetArrayElementExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_variable(
         &self,
         variable: &VariableExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_self_ident(
         &self,
         self_ident: &SelfIdentExpr,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
 
-    fn visit_break(&self) -> Result<T, LangError>;
+    fn visit_break(
+        &self,
+        break_stmt: &BreakStmt,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
+    fn visit_continue(&self, continue_stmt: &ContinueStmt) -> Result<T, Interrupt>;
     fn visit_assert(
         &self,
         condition: &AssertStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_enum(
         &self,
         enum_stmt: &EnumStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_impl(
         &self,
         impl_stmt: &ImplStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_impl_trait(
         &self,
         impl_trait: &ImplTraitStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_block(
         &self,
         block: &BlockStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_struct(
         &self,
         block: &StructStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_expression(
         &self,
         block: &ExpressionStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_trait(
         &self,
         block: &TraitStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_trait_function(
         &self,
         block: &TraitFunctionStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_function(
         &self,
         block: &FunctionStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_if(
         &self,
         block: &IfStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_print(
         &self,
         block: &PrintStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_return(
         &self,
         block: &ReturnStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
     fn visit_var(
         &self,
         block: &VarStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
+    fn visit_destructure(
+        &self,
+        destructure: &DestructureStmt,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
     fn visit_while(
         &self,
         block: &WhileStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
+    fn visit_for_in(
+        &self,
+        for_in: &ForInStmt,
+        arena: &mut Arena<TypedValue>,
+        env: &mut Environment,
+    ) -> Result<T, Interrupt>;
     fn visit_import(
         &self,
         import_stmt: &ImportStmt,
         arena: &mut Arena<TypedValue>,
         env: &mut Environment,
-    ) -> Result<T, LangError>;
+    ) -> Result<T, Interrupt>;
 }
 
 pub fn visit_expr<T, V: Visitor<T>>(
@@ -310,7 +422,7 @@ pub fn visit_expr<T, V: Visitor<T>>(
     expr: &Expr,
     arena: &mut Arena<TypedValue>,
     env: &mut Environment,
-) -> Result<T, LangError> {
+) -> Result<T, Interrupt> {
     match expr {
         Expr::Assign(ref assign_expr) => {
             Ok(visitor.visit_assign(&*assign_expr, &mut *arena, &mut *env)?)
@@ -323,6 +435,9 @@ pub fn visit_expr<T, V: Visitor<T>>(
         Expr::EnumPath(ref enum_path_expr) => {
             Ok(visitor.visit_enum_path(&*enum_path_expr, &mut *arena, &mut *env)?)
         }
+        Expr::Match(ref match_expr) => {
+            Ok(visitor.visit_match(&*match_expr, &mut *arena, &mut *env)?)
+        }
         Expr::Grouping(ref grouping_expr) => {
             Ok(visitor.visit_grouping(&*grouping_expr, &mut *arena, &mut *env)?)
         }
@@ -335,6 +450,20 @@ pub fn visit_expr<T, V: Visitor<T>>(
         Expr::Set(ref set_expr) => Ok(visitor.visit_set(&*set_expr, &mut *arena, &mut *env)?),
         Expr::Unary(ref set_expr) => Ok(visitor.visit_unary(&*set_expr, &mut *arena, &mut *env)?),
         Expr::Array(ref set_expr) => Ok(visitor.visit_array(&*set_expr, &mut *arena, &mut *env)?),
+        Expr::Map(ref map_expr) => visitor.visit_map(map_expr, arena, env),
+        Expr::Tuple(ref tuple_expr) => visitor.visit_tuple(tuple_expr, arena, env),
+        Expr::StructLiteral(ref struct_literal) => {
+            visitor.visit_struct_literal(struct_literal, arena, env)
+        }
+        Expr::Try(ref try_expr) => visitor.visit_try(try_expr, arena, env),
+        Expr::Cast(ref cast) => visitor.visit_cast(cast, arena, env),
+        Expr::Block(ref block) => visitor.visit_block_expr(block, arena, env),
+        Expr::If(ref if_expr) => visitor.visit_if_expr(if_expr, arena, env),
+        Expr::Loop(ref loop_expr) => visitor.visit_loop(loop_expr, arena, env),
+        Expr::Range(ref range) => visitor.visit_range(range, arena, env),
+        Expr::FormatString(ref format_string) => {
+            visitor.visit_format_string(format_string, arena, env)
+        }
         Expr::Index(ref set_expr) => Ok(visitor.visit_index(&*set_expr, &mut *arena, &mut *env)?),
         Expr::SetArrayElement(ref set_expr) => {
             Ok(visitor.visit_set_array_element(&*set_expr, &mut *arena, &mut *env)?)
@@ -353,9 +482,10 @@ pub fn visit_stmt<T, V: Visitor<T>>(
     stmt: &Stmt,
     arena: &mut Arena<TypedValue>,
     env: &mut Environment,
-) -> Result<T, LangError> {
+) -> Result<T, Interrupt> {
     match stmt {
-        Stmt::Break => Ok(visitor.visit_break()?),
+        Stmt::Break(ref break_stmt) => visitor.visit_break(break_stmt, arena, env),
+        Stmt::Continue(ref continue_stmt) => visitor.visit_continue(continue_stmt),
         Stmt::Assert(ref assert_stmt) => Ok(visitor.visit_assert(&*assert_stmt, arena, env)?),
         Stmt::Enum(ref enum_stmt) => Ok(visitor.visit_enum(&*enum_stmt, &mut *arena, &mut *env)?),
         Stmt::Impl(ref enum_stmt) => Ok(visitor.visit_impl(&*enum_stmt, &mut *arena, &mut *env)?),
@@ -388,9 +518,13 @@ pub fn visit_stmt<T, V: Visitor<T>>(
             Ok(visitor.visit_return(&*enum_stmt, &mut *arena, &mut *env)?)
         }
         Stmt::Var(ref enum_stmt) => Ok(visitor.visit_var(&*enum_stmt, &mut *arena, &mut *env)?),
+        Stmt::Destructure(ref destructure_stmt) => {
+            visitor.visit_destructure(destructure_stmt, arena, env)
+        }
         Stmt::While(ref enum_stmt) => {
             Ok(visitor.visit_while(&*enum_stmt, &mut *arena, &mut *env)?)
         }
+        Stmt::ForIn(ref for_in) => visitor.visit_for_in(for_in, arena, env),
         Stmt::Import(ref import_stmt) => {
             Ok(visitor.visit_import(&*import_stmt, &mut *arena, &mut *env)?)
         }
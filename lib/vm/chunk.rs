@@ -0,0 +1,222 @@
+use crate::error::*;
+use crate::format::FormatSpec;
+use crate::token::NumericType;
+use crate::vm::value::*;
+use std::fmt::{self, Display};
+
+/// A single VM instruction. Operands index into the chunk's constant, pattern or format pools, the
+/// globals, or the current frame's local slots and upvalues. Jump offsets are relative to the
+/// instruction following the jump
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpCode {
+    Constant(u16),
+    Unit,
+    True,
+    False,
+    Pop,
+    /// Pushes a copy of the operand's number of values on top of the stack
+    Duplicate(u16),
+    DefineGlobal(u16),
+    GetGlobal(u16),
+    SetGlobal(u16),
+    /// Pops the top of the stack into a local slot
+    DefineLocal(u16),
+    GetLocal(u16),
+    SetLocal(u16),
+    GetUpvalue(u16),
+    SetUpvalue(u16),
+    /// Closes the upvalues pointing at the local slots from the operand upwards
+    CloseUpvalues(u16),
+    GetField(u16),
+    SetField(u16),
+    GetIndex,
+    SetIndex,
+    Array(u16),
+    /// Builds a map out of the operand's number of key and value pairs on top of the stack
+    Map(u16),
+    /// Builds a tuple out of the operand's number of values on top of the stack
+    Tuple(u16),
+    /// Replaces the tuple on top of the stack with its element at the operand
+    TupleField(u16),
+    /// Replaces the tuple on top of the stack with its elements, the first on top. The operand
+    /// is the number of elements expected
+    Unpack(u16),
+    GetVariant(u16),
+    /// Builds a range out of the two integers on top of the stack, including the upper one when
+    /// the operand is `true`
+    Range(bool),
+    /// Replaces the array, map, string or range on top of the stack with an iterator over its
+    /// items, which are the keys of a map. A struct instance is left in place, its `next` method
+    /// is what iterates over it
+    Iterate,
+    /// Replaces the iterator on top of the stack with `Option::Some` of its next item, or `None`
+    /// once it has run out
+    IterNext,
+    /// Replaces the struct instance on top of the stack with what its `fmt` method returns when
+    /// it implements `Display`, leaving any other value in place
+    ToDisplay,
+    /// Replaces the value on top of the stack with a string of it written by the operand's spec
+    Format(u16),
+    /// Joins the operand's number of strings on top of the stack into one
+    Concat(u16),
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+    Negate,
+    /// Converts the number, bool or char on top of the stack to the operand's numeric type
+    Cast(NumericType),
+    Not,
+    /// Flips the bits of the integer on top of the stack
+    BitNot,
+    Jump(u16),
+    /// Pops the condition and jumps if it's falsy
+    JumpIfFalse(u16),
+    /// Jumps if the top of the stack is falsy, leaving it in place
+    JumpIfFalseKeep(u16),
+    /// Jumps if the top of the stack is truthy, leaving it in place
+    JumpIfTrueKeep(u16),
+    /// Replaces the `Some` or `Ok` on top of the stack with its payload, or jumps if it's a
+    /// `None` or `Err`, leaving it in place
+    Unwrap(u16),
+    Loop(u16),
+    Call(u8),
+    Closure(u16),
+    Return,
+    Print,
+    Assert,
+    Struct(u16),
+    /// Builds an instance of the struct below the operand's number of field values on top of the
+    /// stack, which are in the order the struct declares its fields in
+    Construct(u16),
+    /// Adds the closure on top of the stack as a method of the struct below it
+    Method(u16),
+    /// Pops a value and tests it against a pattern. Pushes the values it binds followed by `true`
+    /// when it matches, or just `false`
+    Match(u16),
+    NoMatch,
+}
+
+/// A compiled sequence of instructions along with the constants, patterns and format specs they
+/// refer to
+#[derive(Debug, Default)]
+pub struct Chunk {
+    pub code: Vec<OpCode>,
+    pub constants: Vec<Value>,
+    pub patterns: Vec<MatchPattern>,
+    pub formats: Vec<FormatSpec>,
+}
+
+impl Chunk {
+    /// Appends `op`, returning its offset
+    pub fn write(&mut self, op: OpCode) -> usize {
+        self.code.push(op);
+        self.code.len() - 1
+    }
+
+    /// Adds `value` to the constant pool, reusing the slot of an identical string or number
+    pub fn add_constant(&mut self, value: Value) -> Result<u16, LangError> {
+        let existing = self
+            .constants
+            .iter()
+            .position(|constant| constant.is_same_constant(&value));
+        let index = match existing {
+            Some(index) => index,
+            None => {
+                self.constants.push(value);
+                self.constants.len() - 1
+            }
+        };
+        Chunk::operand(index, "constants")
+    }
+
+    pub fn add_pattern(&mut self, pattern: MatchPattern) -> Result<u16, LangError> {
+        self.patterns.push(pattern);
+        Chunk::operand(self.patterns.len() - 1, "patterns")
+    }
+
+    pub fn add_format(&mut self, spec: FormatSpec) -> Result<u16, LangError> {
+        self.formats.push(spec);
+        Chunk::operand(self.formats.len() - 1, "format specs")
+    }
+
+    /// Checks that `index` fits in an instruction operand
+    pub fn operand(index: usize, what: &str) -> Result<u16, LangError> {
+        if index > u16::MAX as usize {
+            return Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::GenericError {
+                    reason: format!(
+                        "too many {} in one function, the limit is {}",
+                        what,
+                        u16::MAX
+                    ),
+                },
+            ));
+        }
+        Ok(index as u16)
+    }
+}
+
+impl Display for Chunk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (offset, op) in self.code.iter().enumerate() {
+            write!(f, "{:04} {:?}", offset, op)?;
+            match op {
+                OpCode::Constant(index)
+                | OpCode::GetField(index)
+                | OpCode::SetField(index)
+                | OpCode::GetVariant(index)
+                | OpCode::Closure(index)
+                | OpCode::Struct(index)
+                | OpCode::Method(index) => {
+                    write!(f, "\t{}", self.constants[*index as usize])?;
+                }
+                OpCode::Jump(jump)
+                | OpCode::JumpIfFalse(jump)
+                | OpCode::JumpIfFalseKeep(jump)
+                | OpCode::JumpIfTrueKeep(jump)
+                | OpCode::Unwrap(jump) => {
+                    write!(f, "\t-> {:04}", offset + 1 + *jump as usize)?;
+                }
+                OpCode::Loop(jump) => write!(f, "\t-> {:04}", offset + 1 - *jump as usize)?,
+                OpCode::Format(index) => write!(f, "\t{:?}", self.formats[*index as usize])?,
+                _ => {}
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::vm::chunk::*;
+
+    #[test]
+    fn opcodes_are_compact() {
+        assert!(std::mem::size_of::<OpCode>() <= 4);
+    }
+
+    #[test]
+    fn add_constant_reuses_identical_constants() {
+        let mut chunk = Chunk::default();
+        let first = chunk.add_constant(Value::string("a")).unwrap();
+        let number = chunk.add_constant(Value::Int32(1)).unwrap();
+        assert_eq!(chunk.add_constant(Value::string("a")).unwrap(), first);
+        assert_eq!(chunk.add_constant(Value::Int32(1)).unwrap(), number);
+        assert_ne!(chunk.add_constant(Value::Int64(1)).unwrap(), number);
+        assert_eq!(chunk.constants.len(), 3);
+    }
+}
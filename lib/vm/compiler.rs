@@ -0,0 +1,1142 @@
+extern crate log;
+
+use crate::ast::expr::*;
+use crate::ast::stmt::*;
+use crate::error::*;
+use crate::format::FormatKind;
+use crate::token::{TokenType, TypeAnnotation};
+use crate::value;
+use crate::visitor::*;
+use crate::vm::chunk::*;
+use crate::vm::value::*;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A compiled script, along with the names of the globals it refers to, indexed by the operands
+/// of the global instructions
+#[derive(Debug)]
+pub struct Program {
+    pub script: Rc<Function>,
+    pub globals: Vec<String>,
+}
+
+#[derive(Debug)]
+struct Local {
+    name: String,
+    depth: usize,
+    /// Whether a closure refers to the local, its upvalue has to be closed when the scope ends
+    captured: bool,
+}
+
+#[derive(Debug)]
+struct Loop {
+    scope_depth: usize,
+    label: Option<String>,
+    /// Whether it's a `loop`, which leaves the value it's broken out of with on the stack
+    is_loop_expr: bool,
+    /// Offsets of the jumps out of the loop, patched once its end is known
+    breaks: Vec<usize>,
+    /// Offsets of the jumps to the end of the loop's body, from where the next iteration starts
+    continues: Vec<usize>,
+}
+
+/// The function being compiled, the compiler keeps one per level of function nesting
+#[derive(Debug)]
+struct FunctionState {
+    name: String,
+    kind: FunctionKind,
+    arity: usize,
+    chunk: Chunk,
+    locals: Vec<Local>,
+    upvalues: Vec<UpvalueDescriptor>,
+    scope_depth: usize,
+    slot_count: usize,
+    loops: Vec<Loop>,
+}
+
+impl FunctionState {
+    fn new(name: &str, kind: FunctionKind, scope_depth: usize) -> FunctionState {
+        FunctionState {
+            name: name.to_string(),
+            kind,
+            arity: 0,
+            chunk: Chunk::default(),
+            locals: Vec::new(),
+            upvalues: Vec::new(),
+            scope_depth,
+            slot_count: 0,
+            loops: Vec::new(),
+        }
+    }
+}
+
+/// Compiles statements that have already been resolved and type checked into bytecode for the
+/// [`crate::vm::machine::Vm`]. Locals live in slots of their function's frame, variables declared
+/// at the top level of the script are globals
+#[derive(Debug, Default)]
+pub struct Compiler {
+    functions: Vec<FunctionState>,
+    /// The fields of each struct declared so far, which struct literals are compiled against
+    structs: HashMap<String, Vec<StructField>>,
+    globals: HashMap<String, u16>,
+    global_names: Vec<String>,
+}
+
+impl Compiler {
+    pub fn compile(mut self, stmts: &[Stmt]) -> Result<Program, LangError> {
+        self.functions
+            .push(FunctionState::new("script", FunctionKind::Script, 0));
+        // Functions can build instances of structs declared after them
+        for stmt in stmts {
+            if let Stmt::Struct(struct_stmt) = stmt {
+                self.structs
+                    .insert(struct_stmt.name.clone(), struct_stmt.fields.clone());
+            }
+        }
+        for stmt in stmts {
+            self.compile_statement(stmt)?;
+        }
+        let script = self.end_function()?;
+        Ok(Program {
+            script,
+            globals: self.global_names,
+        })
+    }
+
+    fn compile_statement(&mut self, stmt: &Stmt) -> Result<(), LangError> {
+        self.visit_stmt_mut(stmt)
+    }
+
+    fn compile_expr(&mut self, expr: &Expr) -> Result<(), LangError> {
+        self.visit_expr_mut(expr)
+    }
+
+    fn current(&mut self) -> &mut FunctionState {
+        self.functions
+            .last_mut()
+            .expect("the compiler always has a function to compile into")
+    }
+
+    fn emit(&mut self, op: OpCode) -> usize {
+        self.current().chunk.write(op)
+    }
+
+    fn emit_constant(&mut self, value: Value) -> Result<(), LangError> {
+        let index = self.current().chunk.add_constant(value)?;
+        self.emit(OpCode::Constant(index));
+        Ok(())
+    }
+
+    fn name_constant(&mut self, name: &str) -> Result<u16, LangError> {
+        self.current().chunk.add_constant(Value::string(name))
+    }
+
+    /// Emits `op` with a placeholder offset, returning its offset for [`Compiler::patch_jump`]
+    fn emit_jump(&mut self, op: fn(u16) -> OpCode) -> usize {
+        self.emit(op(0))
+    }
+
+    /// Points the jump at `offset` to the next instruction to be emitted
+    fn patch_jump(&mut self, offset: usize) -> Result<(), LangError> {
+        let code = &mut self.current().chunk.code;
+        let jump = Chunk::operand(code.len() - offset - 1, "instructions to jump over")?;
+        code[offset] = match code[offset] {
+            OpCode::Jump(_) => OpCode::Jump(jump),
+            OpCode::JumpIfFalse(_) => OpCode::JumpIfFalse(jump),
+            OpCode::JumpIfFalseKeep(_) => OpCode::JumpIfFalseKeep(jump),
+            OpCode::JumpIfTrueKeep(_) => OpCode::JumpIfTrueKeep(jump),
+            OpCode::Unwrap(_) => OpCode::Unwrap(jump),
+            op => {
+                return Err(LangErrorType::new_iie_error(format!(
+                    "tried to patch {:?}, which isn't a jump",
+                    op
+                )))
+            }
+        };
+        Ok(())
+    }
+
+    fn emit_loop(&mut self, start: usize) -> Result<(), LangError> {
+        let jump = Chunk::operand(
+            self.current().chunk.code.len() + 1 - start,
+            "instructions in a loop",
+        )?;
+        self.emit(OpCode::Loop(jump));
+        Ok(())
+    }
+
+    fn begin_scope(&mut self) {
+        self.current().scope_depth += 1;
+    }
+
+    fn begin_loop(&mut self, label: &Option<String>, is_loop_expr: bool) {
+        let scope_depth = self.current().scope_depth;
+        self.current().loops.push(Loop {
+            scope_depth,
+            label: label.clone(),
+            is_loop_expr,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+        });
+    }
+
+    /// Patches the `continue`s of the innermost loop to jump to the end of its body
+    fn patch_continues(&mut self) -> Result<(), LangError> {
+        let continues = match self.current().loops.last_mut() {
+            Some(innermost) => std::mem::take(&mut innermost.continues),
+            None => Vec::new(),
+        };
+        for continue_jump in continues {
+            self.patch_jump(continue_jump)?;
+        }
+        Ok(())
+    }
+
+    /// Patches the `break`s of the innermost loop to jump past its end
+    fn end_loop(&mut self) -> Result<(), LangError> {
+        if let Some(finished) = self.current().loops.pop() {
+            for break_jump in finished.breaks {
+                self.patch_jump(break_jump)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The position of the loop a `break` or `continue` leaves, the one named `label` or else the
+    /// innermost
+    fn target_loop(&mut self, keyword: &str, label: &Option<String>) -> Result<usize, LangError> {
+        let loops = &self.current().loops;
+        let target = match label {
+            Some(label) => loops
+                .iter()
+                .rposition(|enclosing| enclosing.label.as_ref() == Some(label)),
+            None => loops.len().checked_sub(1),
+        };
+        target.ok_or_else(|| {
+            LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                reason: format!("tried to {} outside of a loop", keyword),
+            })
+        })
+    }
+
+    /// Closes the upvalues of the locals declared in the loop at `target`, then jumps out of it.
+    /// Returns the offset of the jump
+    fn emit_loop_exit(&mut self, target: usize) -> Result<usize, LangError> {
+        let loop_depth = self.current().loops[target].scope_depth;
+        let first = self
+            .current()
+            .locals
+            .iter()
+            .position(|local| local.depth > loop_depth)
+            .unwrap_or_else(|| self.functions[self.functions.len() - 1].locals.len());
+        self.close_upvalues_from(first)?;
+        Ok(self.emit_jump(OpCode::Jump))
+    }
+
+    fn end_scope(&mut self) -> Result<(), LangError> {
+        let function = self.current();
+        function.scope_depth -= 1;
+        let depth = function.scope_depth;
+        let first = function
+            .locals
+            .iter()
+            .position(|local| local.depth > depth)
+            .unwrap_or(function.locals.len());
+        self.close_upvalues_from(first)?;
+        self.current().locals.truncate(first);
+        Ok(())
+    }
+
+    /// Closes the upvalues of the locals from the slot `first` upwards, if any were captured
+    fn close_upvalues_from(&mut self, first: usize) -> Result<(), LangError> {
+        if self.current().locals[first..]
+            .iter()
+            .any(|local| local.captured)
+        {
+            let slot = Chunk::operand(first, "locals")?;
+            self.emit(OpCode::CloseUpvalues(slot));
+        }
+        Ok(())
+    }
+
+    fn declare_local(&mut self, name: &str) -> Result<u16, LangError> {
+        let function = self.current();
+        let depth = function.scope_depth;
+        function.locals.push(Local {
+            name: name.to_string(),
+            depth,
+            captured: false,
+        });
+        function.slot_count = function.slot_count.max(function.locals.len());
+        Chunk::operand(function.locals.len() - 1, "locals")
+    }
+
+    fn global(&mut self, name: &str) -> Result<u16, LangError> {
+        if let Some(index) = self.globals.get(name) {
+            return Ok(*index);
+        }
+        let index = Chunk::operand(self.global_names.len(), "globals")?;
+        self.globals.insert(name.to_string(), index);
+        self.global_names.push(name.to_string());
+        Ok(index)
+    }
+
+    fn resolve_local(&self, function: usize, name: &str) -> Option<u16> {
+        self.functions[function]
+            .locals
+            .iter()
+            .rposition(|local| local.name == name)
+            .map(|slot| slot as u16)
+    }
+
+    /// Finds `name` in the functions enclosing `function`, threading an upvalue through each
+    /// function in between
+    fn resolve_upvalue(&mut self, function: usize, name: &str) -> Result<Option<u16>, LangError> {
+        if function == 0 {
+            return Ok(None);
+        }
+        if let Some(slot) = self.resolve_local(function - 1, name) {
+            self.functions[function - 1].locals[slot as usize].captured = true;
+            return Ok(Some(self.add_upvalue(function, true, slot)?));
+        }
+        if let Some(index) = self.resolve_upvalue(function - 1, name)? {
+            return Ok(Some(self.add_upvalue(function, false, index)?));
+        }
+        Ok(None)
+    }
+
+    fn add_upvalue(
+        &mut self,
+        function: usize,
+        is_local: bool,
+        index: u16,
+    ) -> Result<u16, LangError> {
+        let descriptor = UpvalueDescriptor { is_local, index };
+        let upvalues = &mut self.functions[function].upvalues;
+        if let Some(existing) = upvalues.iter().position(|upvalue| *upvalue == descriptor) {
+            return Ok(existing as u16);
+        }
+        upvalues.push(descriptor);
+        Chunk::operand(upvalues.len() - 1, "captured variables")
+    }
+
+    fn emit_get(&mut self, name: &str) -> Result<(), LangError> {
+        let function = self.functions.len() - 1;
+        let op = if let Some(slot) = self.resolve_local(function, name) {
+            OpCode::GetLocal(slot)
+        } else if let Some(index) = self.resolve_upvalue(function, name)? {
+            OpCode::GetUpvalue(index)
+        } else {
+            OpCode::GetGlobal(self.global(name)?)
+        };
+        self.emit(op);
+        Ok(())
+    }
+
+    fn emit_set(&mut self, name: &str) -> Result<(), LangError> {
+        let function = self.functions.len() - 1;
+        let op = if let Some(slot) = self.resolve_local(function, name) {
+            OpCode::SetLocal(slot)
+        } else if let Some(index) = self.resolve_upvalue(function, name)? {
+            OpCode::SetUpvalue(index)
+        } else {
+            OpCode::SetGlobal(self.global(name)?)
+        };
+        self.emit(op);
+        Ok(())
+    }
+
+    /// Binds the value on top of the stack to `name`, as a global at the top level of the script
+    /// and as a local anywhere else
+    fn emit_define(&mut self, name: &str) -> Result<(), LangError> {
+        if self.current().scope_depth == 0 {
+            let index = self.global(name)?;
+            self.emit(OpCode::DefineGlobal(index));
+        } else {
+            let slot = self.declare_local(name)?;
+            self.emit(OpCode::DefineLocal(slot));
+        }
+        Ok(())
+    }
+
+    fn end_function(&mut self) -> Result<Rc<Function>, LangError> {
+        self.emit(OpCode::Unit);
+        self.emit(OpCode::Return);
+        let function = self.functions.pop().ok_or_else(|| {
+            LangErrorType::new_iie_error("tried to end a function that wasn't started".to_string())
+        })?;
+        debug!(
+            "{}:{} Compiled <fn {}>:\n{}",
+            file!(),
+            line!(),
+            function.name,
+            function.chunk
+        );
+        Ok(Rc::new(Function {
+            name: function.name,
+            kind: function.kind,
+            arity: function.arity,
+            slot_count: function.slot_count,
+            upvalues: function.upvalues,
+            chunk: function.chunk,
+        }))
+    }
+
+    /// Compiles `function` and emits the instruction making a closure out of it
+    fn emit_closure(
+        &mut self,
+        function: &FunctionStmt,
+        kind: FunctionKind,
+    ) -> Result<(), LangError> {
+        self.functions
+            .push(FunctionState::new(&function.name, kind, 1));
+        let mut arity = function.params.len();
+        if let FunctionKind::Method { takes_receiver } = kind {
+            self.declare_local("self")?;
+            if takes_receiver {
+                arity -= 1;
+            }
+        }
+        self.current().arity = arity;
+        for param in function.params.iter() {
+            self.declare_local(&param.identifier)?;
+        }
+        for stmt in function.body.iter() {
+            self.compile_statement(stmt)?;
+        }
+        let function = self.end_function()?;
+        let index = self
+            .current()
+            .chunk
+            .add_constant(Value::Function(function))?;
+        self.emit(OpCode::Closure(index));
+        Ok(())
+    }
+
+    /// Adds `fn_declarations` as methods of the struct `name`
+    fn emit_methods(&mut self, name: &str, fn_declarations: &[Stmt]) -> Result<(), LangError> {
+        self.emit_get(name)?;
+        for fn_declaration in fn_declarations {
+            if let Stmt::Function(function) = fn_declaration {
+                // The object a method is called on is passed as its first argument when that
+                // parameter is of a user type, as the type checker expects
+                let takes_receiver = function
+                    .params
+                    .first()
+                    .is_some_and(|param| param.type_annotation.is_user_type());
+                self.emit_closure(function, FunctionKind::Method { takes_receiver })?;
+                let name = self.name_constant(&function.name)?;
+                self.emit(OpCode::Method(name));
+            }
+        }
+        self.emit(OpCode::Pop);
+        Ok(())
+    }
+
+    /// Converts a literal to a VM value of its annotated type. Integer and float literals are
+    /// scanned as the narrowest type that fits them
+    fn literal_value(literal: &value::TypedValue) -> Result<Value, LangError> {
+        let vm_value = match (&literal.value, &literal.value_type) {
+            (value::Value::Unit, _) => Value::Unit,
+            (value::Value::Boolean(b), _) => Value::Boolean(*b),
+            (value::Value::Char(c), _) => Value::Char(*c),
+            (value::Value::String(s), _) => Value::string(s),
+            (value::Value::Int32(i), TypeAnnotation::I64) => Value::Int64(i64::from(*i)),
+            (value::Value::Int32(i), _) => Value::Int32(*i),
+            (value::Value::Int64(i), _) => Value::Int64(*i),
+            (value::Value::Int8(i), _) => Value::Int8(*i),
+            (value::Value::Int16(i), _) => Value::Int16(*i),
+            (value::Value::UInt8(i), _) => Value::UInt8(*i),
+            (value::Value::UInt16(i), _) => Value::UInt16(*i),
+            (value::Value::UInt32(i), _) => Value::UInt32(*i),
+            (value::Value::UInt64(i), _) => Value::UInt64(*i),
+            (value::Value::USize(i), _) => Value::USize(*i),
+            (value::Value::Float32(f), TypeAnnotation::F32) => Value::Float32(*f),
+            // Going through the shortest representation of the f32 keeps e.g. 1.05 from turning
+            // into 1.0499999523162842
+            (value::Value::Float32(f), _) => Value::Float64(f.to_string().parse::<f64>()?),
+            (value::Value::Float64(f), TypeAnnotation::F32) => Value::Float32(*f as f32),
+            (value::Value::Float64(f), _) => Value::Float64(*f),
+            (other, _) => {
+                return Err(LangErrorType::new_iie_error(format!(
+                    "tried to compile a literal of type {}",
+                    other.type_to_str()
+                )))
+            }
+        };
+        Ok(vm_value)
+    }
+
+    fn emit_literal(&mut self, literal: &value::TypedValue) -> Result<(), LangError> {
+        // Array literals only come from the default value of an array variable, each evaluation
+        // has to create a new array
+        if let value::Value::Array(ref elements) = literal.value {
+            for element in elements.iter() {
+                self.emit_literal(element)?;
+            }
+            let count = Chunk::operand(elements.len(), "array elements")?;
+            self.emit(OpCode::Array(count));
+            return Ok(());
+        }
+        // Same for the empty map a map variable defaults to
+        if let value::Value::Map(_) = literal.value {
+            self.emit(OpCode::Map(0));
+            return Ok(());
+        }
+        // and the defaults of the elements of a tuple variable
+        if let value::Value::Tuple(ref elements) = literal.value {
+            for element in elements.iter() {
+                self.emit_literal(element)?;
+            }
+            let count = Chunk::operand(elements.len(), "tuple elements")?;
+            self.emit(OpCode::Tuple(count));
+            return Ok(());
+        }
+        match Compiler::literal_value(literal)? {
+            Value::Unit => self.emit(OpCode::Unit),
+            Value::Boolean(true) => self.emit(OpCode::True),
+            Value::Boolean(false) => self.emit(OpCode::False),
+            value => return self.emit_constant(value),
+        };
+        Ok(())
+    }
+
+    fn lower_pattern(
+        pattern: &Pattern,
+        bindings: &mut Vec<String>,
+    ) -> Result<MatchPattern, LangError> {
+        let lowered = match pattern {
+            Pattern::Wildcard => MatchPattern::Wildcard,
+            Pattern::Binding(name) => {
+                bindings.push(name.clone());
+                MatchPattern::Binding
+            }
+            Pattern::Literal(literal) => MatchPattern::Literal(Compiler::literal_value(literal)?),
+            Pattern::EnumVariant(variant) => MatchPattern::Variant {
+                enum_name: variant.enum_name.clone(),
+                variant: variant.variant.clone(),
+                fields: variant
+                    .fields
+                    .iter()
+                    .map(|field| Compiler::lower_pattern(field, bindings))
+                    .collect::<Result<Vec<MatchPattern>, LangError>>()?,
+            },
+        };
+        Ok(lowered)
+    }
+
+    /// Enum discriminants are worked out at compile time, so initializers have to be integer
+    /// literals
+    fn discriminant(enum_stmt: &EnumStmt, item: &EnumItem) -> Result<Option<i64>, LangError> {
+        let initializer = match item.initializer {
+            Some(ref initializer) => initializer,
+            None => return Ok(None),
+        };
+        let (literal, negate) = match initializer {
+            Expr::Literal(literal) => (&literal.value, false),
+            Expr::Unary(unary) if unary.operator == TokenType::Minus => match unary.right {
+                Expr::Literal(ref literal) => (&literal.value, true),
+                _ => (&value::TypedValue::default(), false),
+            },
+            _ => (&value::TypedValue::default(), false),
+        };
+        let discriminant = match literal.value {
+            value::Value::Int32(i) => i64::from(i),
+            value::Value::Int64(i) => i,
+            _ => {
+                return Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::InvalidTypeAssignmentError {
+                        reason: format!(
+                            "enum discriminant for '{}::{}' must be an integer literal",
+                            enum_stmt.name, item.identifier
+                        ),
+                    },
+                ))
+            }
+        };
+        Ok(Some(if negate { -discriminant } else { discriminant }))
+    }
+
+    /// The instruction applying the binary `operator` to the two values on top of the stack
+    fn binary_op(operator: &TokenType) -> Result<OpCode, LangError> {
+        let op = match operator {
+            TokenType::Plus => OpCode::Add,
+            TokenType::Minus => OpCode::Subtract,
+            TokenType::Star => OpCode::Multiply,
+            TokenType::Slash => OpCode::Divide,
+            TokenType::Percent => OpCode::Remainder,
+            TokenType::Ampersand => OpCode::BitAnd,
+            TokenType::Pipe => OpCode::BitOr,
+            TokenType::Caret => OpCode::BitXor,
+            TokenType::LessLess => OpCode::ShiftLeft,
+            TokenType::GreaterGreater => OpCode::ShiftRight,
+            TokenType::Greater => OpCode::Greater,
+            TokenType::GreaterEqual => OpCode::GreaterEqual,
+            TokenType::Less => OpCode::Less,
+            TokenType::LessEqual => OpCode::LessEqual,
+            TokenType::BangEqual => OpCode::NotEqual,
+            TokenType::EqualEqual => OpCode::Equal,
+            operator => {
+                return Err(LangErrorType::new_iie_error(format!(
+                    "tried to compile a binary operation with the operator {}",
+                    operator
+                )))
+            }
+        };
+        Ok(op)
+    }
+}
+
+impl VisitorMut<()> for Compiler {
+    fn visit_expr_mut(&mut self, expr: &Expr) -> Result<(), LangError> {
+        visit_expr_mut(self, expr)
+    }
+    fn visit_stmt_mut(&mut self, stmt: &Stmt) -> Result<(), LangError> {
+        visit_stmt_mut(self, stmt)
+    }
+
+    fn visit_assign(&mut self, assign: &AssignExpr) -> Result<(), LangError> {
+        self.compile_expr(&assign.expr)?;
+        self.emit_set(&assign.name)
+    }
+    fn visit_binary(&mut self, binary: &BinaryExpr) -> Result<(), LangError> {
+        self.compile_expr(&binary.left)?;
+        self.compile_expr(&binary.right)?;
+        self.emit(Compiler::binary_op(&binary.operator)?);
+        Ok(())
+    }
+    fn visit_call(&mut self, call: &CallExpr) -> Result<(), LangError> {
+        // `Name()` is a struct literal giving every field its default value
+        if let Expr::Variable(ref variable) = call.callee {
+            if let Some(fields) = self.structs.get(&variable.name) {
+                if call.arguments.is_empty() {
+                    return self.visit_struct_literal(&StructLiteralExpr {
+                        name: variable.name.clone(),
+                        fields: StructStmt::constructor_fields(fields),
+                        location: call.location,
+                    });
+                }
+            }
+        }
+        self.compile_expr(&call.callee)?;
+        for arg in call.arguments.iter() {
+            self.compile_expr(arg)?;
+        }
+        if call.arguments.len() > u8::MAX as usize {
+            return Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::FnArityError {
+                    reason: format!("a call can pass at most {} arguments", u8::MAX),
+                },
+            ));
+        }
+        self.emit(OpCode::Call(call.arguments.len() as u8));
+        Ok(())
+    }
+    fn visit_get(&mut self, get: &GetExpr) -> Result<(), LangError> {
+        self.compile_expr(&get.object)?;
+        // Only tuple fields have numbers for names
+        if let Ok(field) = get.name.parse::<usize>() {
+            let field = Chunk::operand(field, "tuple fields")?;
+            self.emit(OpCode::TupleField(field));
+            return Ok(());
+        }
+        let name = self.name_constant(&get.name)?;
+        self.emit(OpCode::GetField(name));
+        Ok(())
+    }
+    fn visit_enum_path(&mut self, enum_path: &EnumPathExpr) -> Result<(), LangError> {
+        let variant = match enum_path.path_items.as_slice() {
+            [variant] => variant,
+            _ => {
+                return Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::GenericError {
+                        reason: format!(
+                            "expected a path of the form '{}::Variant'",
+                            enum_path.name
+                        ),
+                    },
+                ))
+            }
+        };
+        self.emit_get(&enum_path.name)?;
+        let variant = self.name_constant(variant)?;
+        self.emit(OpCode::GetVariant(variant));
+        Ok(())
+    }
+    fn visit_match(&mut self, match_expr: &MatchExpr) -> Result<(), LangError> {
+        self.compile_expr(&match_expr.scrutinee)?;
+        self.begin_scope();
+        // The scrutinee is kept in a slot no identifier can refer to
+        let scrutinee = self.declare_local("")?;
+        self.emit(OpCode::DefineLocal(scrutinee));
+        let mut end_jumps = Vec::new();
+        for arm in match_expr.arms.iter() {
+            let mut bindings = Vec::new();
+            let pattern = Compiler::lower_pattern(&arm.pattern, &mut bindings)?;
+            let pattern = self.current().chunk.add_pattern(pattern)?;
+            self.emit(OpCode::GetLocal(scrutinee));
+            self.emit(OpCode::Match(pattern));
+            let next_arm = self.emit_jump(OpCode::JumpIfFalse);
+            self.begin_scope();
+            let mut slots = Vec::new();
+            for binding in bindings.iter() {
+                slots.push(self.declare_local(binding)?);
+            }
+            // Bound values are pushed in order, so the last one is on top
+            for slot in slots.into_iter().rev() {
+                self.emit(OpCode::DefineLocal(slot));
+            }
+            match arm.body {
+                Stmt::Expression(ref expression) => self.compile_expr(&expression.expression)?,
+                ref body => {
+                    self.compile_statement(body)?;
+                    self.emit(OpCode::Unit);
+                }
+            }
+            self.end_scope()?;
+            end_jumps.push(self.emit_jump(OpCode::Jump));
+            self.patch_jump(next_arm)?;
+        }
+        self.emit(OpCode::GetLocal(scrutinee));
+        self.emit(OpCode::NoMatch);
+        for end_jump in end_jumps {
+            self.patch_jump(end_jump)?;
+        }
+        self.end_scope()
+    }
+    fn visit_grouping(&mut self, grouping: &GroupingExpr) -> Result<(), LangError> {
+        self.compile_expr(&grouping.expression)
+    }
+    fn visit_literal(&mut self, literal: &LiteralExpr) -> Result<(), LangError> {
+        self.emit_literal(&literal.value)
+    }
+    fn visit_logical(&mut self, logical: &LogicalExpr) -> Result<(), LangError> {
+        self.compile_expr(&logical.left)?;
+        let end = if logical.operator == TokenType::Or {
+            self.emit_jump(OpCode::JumpIfTrueKeep)
+        } else {
+            self.emit_jump(OpCode::JumpIfFalseKeep)
+        };
+        self.emit(OpCode::Pop);
+        self.compile_expr(&logical.right)?;
+        self.patch_jump(end)
+    }
+    fn visit_set(&mut self, set: &SetExpr) -> Result<(), LangError> {
+        self.compile_expr(&set.object)?;
+        let name = self.name_constant(&set.name)?;
+        // A compound assignment reads the field off a copy of the object, which is evaluated once
+        if let Some(ref operator) = set.operator {
+            self.emit(OpCode::Duplicate(1));
+            self.emit(OpCode::GetField(name));
+            self.compile_expr(&set.value)?;
+            self.emit(Compiler::binary_op(operator)?);
+        } else {
+            self.compile_expr(&set.value)?;
+        }
+        self.emit(OpCode::SetField(name));
+        Ok(())
+    }
+    fn visit_unary(&mut self, unary: &UnaryExpr) -> Result<(), LangError> {
+        self.compile_expr(&unary.right)?;
+        match unary.operator {
+            TokenType::Minus => self.emit(OpCode::Negate),
+            TokenType::Bang => self.emit(OpCode::Not),
+            TokenType::Tilde => self.emit(OpCode::BitNot),
+            ref operator => {
+                return Err(LangErrorType::new_iie_error(format!(
+                    "tried to compile a unary operation with the operator {}",
+                    operator
+                )))
+            }
+        };
+        Ok(())
+    }
+    fn visit_array(&mut self, array: &ArrayExpr) -> Result<(), LangError> {
+        for element in array.elements.iter() {
+            self.compile_expr(element)?;
+        }
+        let count = Chunk::operand(array.elements.len(), "array elements")?;
+        self.emit(OpCode::Array(count));
+        Ok(())
+    }
+    fn visit_map(&mut self, map: &MapExpr) -> Result<(), LangError> {
+        for (key, value) in map.entries.iter() {
+            self.compile_expr(key)?;
+            self.compile_expr(value)?;
+        }
+        let count = Chunk::operand(map.entries.len(), "map entries")?;
+        self.emit(OpCode::Map(count));
+        Ok(())
+    }
+    fn visit_struct_literal(
+        &mut self,
+        struct_literal: &StructLiteralExpr,
+    ) -> Result<(), LangError> {
+        let fields = self
+            .structs
+            .get(&struct_literal.name)
+            .cloned()
+            .ok_or_else(|| {
+                LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                    reason: format!("'{}' is not a struct", struct_literal.name),
+                })
+            })?;
+        self.emit_get(&struct_literal.name)?;
+        // Values are evaluated in the order they're written in, into slots no identifier can
+        // refer to, and then pushed in the order the struct declares its fields in
+        self.begin_scope();
+        let mut slots = HashMap::new();
+        for (name, value) in struct_literal.fields.iter() {
+            if !fields.iter().any(|field| field.identifier == *name) {
+                return Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::UndefinedVariable {
+                        reason: format!("struct '{}' has no field '{}'", struct_literal.name, name),
+                    },
+                ));
+            }
+            self.compile_expr(value)?;
+            let slot = self.declare_local("")?;
+            self.emit(OpCode::DefineLocal(slot));
+            slots.insert(name, slot);
+        }
+        for field in fields.iter() {
+            match (slots.get(&field.identifier), &field.default) {
+                (Some(slot), _) => {
+                    self.emit(OpCode::GetLocal(*slot));
+                }
+                // Defaults are evaluated anew for every instance, so instances never share them
+                (None, Some(default)) => self.compile_expr(default)?,
+                (None, None) => {
+                    return Err(LangErrorType::new_runtime_error(
+                        RuntimeErrorType::GenericError {
+                            reason: format!(
+                                "missing field '{}' in a literal of struct '{}'",
+                                field.identifier, struct_literal.name
+                            ),
+                        },
+                    ))
+                }
+            }
+        }
+        let count = Chunk::operand(fields.len(), "struct fields")?;
+        self.emit(OpCode::Construct(count));
+        self.end_scope()
+    }
+    fn visit_try(&mut self, try_expr: &TryExpr) -> Result<(), LangError> {
+        self.compile_expr(&try_expr.expression)?;
+        let failure = self.emit_jump(OpCode::Unwrap);
+        let end = self.emit_jump(OpCode::Jump);
+        // A `None` or `Err` is returned from the enclosing function as it is
+        self.patch_jump(failure)?;
+        self.emit(OpCode::Return);
+        self.patch_jump(end)
+    }
+    fn visit_cast(&mut self, cast: &CastExpr) -> Result<(), LangError> {
+        self.compile_expr(&cast.expression)?;
+        let target = cast.target.numeric_type().ok_or_else(|| {
+            LangErrorType::new_runtime_error(RuntimeErrorType::InvalidTypeAssignmentError {
+                reason: format!("cannot cast to non-numeric type {}", cast.target),
+            })
+        })?;
+        self.emit(OpCode::Cast(target));
+        Ok(())
+    }
+    fn visit_block_expr(&mut self, block: &BlockExpr) -> Result<(), LangError> {
+        self.begin_scope();
+        for stmt in block.statements.iter() {
+            self.compile_statement(stmt)?;
+        }
+        match block.value {
+            Some(ref value) => self.compile_expr(value)?,
+            None => {
+                self.emit(OpCode::Unit);
+            }
+        }
+        self.end_scope()
+    }
+    fn visit_loop(&mut self, loop_expr: &LoopExpr) -> Result<(), LangError> {
+        let start = self.current().chunk.code.len();
+        self.begin_loop(&loop_expr.label, true);
+        self.begin_scope();
+        for stmt in loop_expr.body.iter() {
+            self.compile_statement(stmt)?;
+        }
+        self.end_scope()?;
+        self.patch_continues()?;
+        self.emit_loop(start)?;
+        self.end_loop()
+    }
+    fn visit_range(&mut self, range: &RangeExpr) -> Result<(), LangError> {
+        self.compile_expr(&range.start)?;
+        self.compile_expr(&range.end)?;
+        self.emit(OpCode::Range(range.inclusive));
+        Ok(())
+    }
+    fn visit_format_string(&mut self, format_string: &FormatStringExpr) -> Result<(), LangError> {
+        for piece in format_string.pieces.iter() {
+            match piece {
+                FormatPiece::Text(text) => self.emit_constant(Value::string(text))?,
+                FormatPiece::Argument(argument) => {
+                    self.compile_expr(&argument.value)?;
+                    if argument.spec.kind == FormatKind::Display {
+                        self.emit(OpCode::ToDisplay);
+                    }
+                    let spec = self.current().chunk.add_format(argument.spec.clone())?;
+                    self.emit(OpCode::Format(spec));
+                }
+            }
+        }
+        let count = Chunk::operand(format_string.pieces.len(), "format string pieces")?;
+        self.emit(OpCode::Concat(count));
+        Ok(())
+    }
+    fn visit_if_expr(&mut self, if_expr: &IfExpr) -> Result<(), LangError> {
+        self.compile_expr(&if_expr.condition)?;
+        let else_jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.visit_block_expr(&if_expr.then_branch)?;
+        let end_jump = self.emit_jump(OpCode::Jump);
+        self.patch_jump(else_jump)?;
+        match if_expr.else_branch {
+            Some(ref else_branch) => self.compile_expr(else_branch)?,
+            None => {
+                self.emit(OpCode::Unit);
+            }
+        }
+        self.patch_jump(end_jump)
+    }
+    fn visit_tuple(&mut self, tuple: &TupleExpr) -> Result<(), LangError> {
+        for element in tuple.elements.iter() {
+            self.compile_expr(element)?;
+        }
+        let count = Chunk::operand(tuple.elements.len(), "tuple elements")?;
+        self.emit(OpCode::Tuple(count));
+        Ok(())
+    }
+    fn visit_index(&mut self, index: &IndexExpr) -> Result<(), LangError> {
+        self.emit_get(&index.from)?;
+        self.compile_expr(&index.index)?;
+        self.emit(OpCode::GetIndex);
+        Ok(())
+    }
+    fn visit_set_array_element(
+        &mut self,
+        set_array_element: &SetArrayElementExpr,
+    ) -> Result<(), LangError> {
+        self.emit_get(&set_array_element.name)?;
+        self.compile_expr(&set_array_element.index)?;
+        // A compound assignment reads the element through a copy of the array and the index,
+        // which is evaluated once
+        if let Some(ref operator) = set_array_element.operator {
+            self.emit(OpCode::Duplicate(2));
+            self.emit(OpCode::GetIndex);
+            self.compile_expr(&set_array_element.value)?;
+            self.emit(Compiler::binary_op(operator)?);
+        } else {
+            self.compile_expr(&set_array_element.value)?;
+        }
+        self.emit(OpCode::SetIndex);
+        Ok(())
+    }
+    fn visit_variable(&mut self, variable: &VariableExpr) -> Result<(), LangError> {
+        self.emit_get(&variable.name)
+    }
+    fn visit_self_ident(&mut self, self_ident: &SelfIdentExpr) -> Result<(), LangError> {
+        self.emit_get(&self_ident.keyword)
+    }
+
+    // stmt
+    fn visit_break(&mut self, break_stmt: &BreakStmt) -> Result<(), LangError> {
+        let target = self.target_loop("break", &break_stmt.label)?;
+        // A `loop` evaluates to the value it's broken out of with
+        if self.current().loops[target].is_loop_expr {
+            match break_stmt.value {
+                Some(ref value) => self.compile_expr(value)?,
+                None => {
+                    self.emit(OpCode::Unit);
+                }
+            }
+        }
+        let jump = self.emit_loop_exit(target)?;
+        self.current().loops[target].breaks.push(jump);
+        Ok(())
+    }
+    fn visit_continue(&mut self, continue_stmt: &ContinueStmt) -> Result<(), LangError> {
+        let target = self.target_loop("continue", &continue_stmt.label)?;
+        let jump = self.emit_loop_exit(target)?;
+        self.current().loops[target].continues.push(jump);
+        Ok(())
+    }
+    fn visit_assert(&mut self, assert_stmt: &AssertStmt) -> Result<(), LangError> {
+        self.compile_expr(&assert_stmt.condition)?;
+        self.emit(OpCode::Assert);
+        Ok(())
+    }
+    fn visit_enum(&mut self, enum_stmt: &EnumStmt) -> Result<(), LangError> {
+        let mut variants = Vec::new();
+        // Variants without an initializer take the previous discriminant plus one
+        let mut discriminant: i64 = 0;
+        for item in enum_stmt.item_list.iter() {
+            if let Some(initializer) = Compiler::discriminant(enum_stmt, item)? {
+                discriminant = initializer;
+            }
+            variants.push(VariantDecl {
+                name: Rc::from(item.identifier.as_str()),
+                discriminant,
+                arity: item.fields.len(),
+            });
+            discriminant += 1;
+        }
+        self.emit_constant(Value::Enum(Rc::new(EnumDecl {
+            name: Rc::from(enum_stmt.name.as_str()),
+            variants,
+        })))?;
+        self.emit_define(&enum_stmt.name)
+    }
+    fn visit_impl(&mut self, impl_stmt: &ImplStmt) -> Result<(), LangError> {
+        self.emit_methods(&impl_stmt.name, &impl_stmt.fn_declarations)
+    }
+    fn visit_impl_trait(&mut self, impl_trait: &ImplTraitStmt) -> Result<(), LangError> {
+        self.emit_methods(&impl_trait.impl_name, &impl_trait.fn_declarations)
+    }
+    fn visit_block(&mut self, block: &BlockStmt) -> Result<(), LangError> {
+        self.begin_scope();
+        for stmt in block.statements.iter() {
+            self.compile_statement(stmt)?;
+        }
+        self.end_scope()
+    }
+    fn visit_struct(&mut self, struct_stmt: &StructStmt) -> Result<(), LangError> {
+        self.structs
+            .insert(struct_stmt.name.clone(), struct_stmt.fields.clone());
+        let fields = struct_stmt
+            .fields
+            .iter()
+            .map(|field| field.identifier.clone())
+            .collect();
+        let decl = Value::Struct(Rc::new(StructDecl::new(struct_stmt.name.clone(), fields)));
+        let index = self.current().chunk.add_constant(decl)?;
+        self.emit(OpCode::Struct(index));
+        self.emit_define(&struct_stmt.name)
+    }
+    fn visit_expression(&mut self, expression: &ExpressionStmt) -> Result<(), LangError> {
+        self.compile_expr(&expression.expression)?;
+        self.emit(OpCode::Pop);
+        Ok(())
+    }
+    fn visit_trait(&mut self, trait_stmt: &TraitStmt) -> Result<(), LangError> {
+        self.emit_constant(Value::Trait(Rc::new(TraitDecl {
+            name: trait_stmt.name.clone(),
+        })))?;
+        self.emit_define(&trait_stmt.name)
+    }
+    // Trait functions are only signatures, which the type checker has already checked impls
+    // against
+    fn visit_trait_function(&mut self, _: &TraitFunctionStmt) -> Result<(), LangError> {
+        Ok(())
+    }
+    fn visit_function(&mut self, function_stmt: &FunctionStmt) -> Result<(), LangError> {
+        if self.current().scope_depth == 0 {
+            self.emit_closure(function_stmt, FunctionKind::Function)?;
+            let index = self.global(&function_stmt.name)?;
+            self.emit(OpCode::DefineGlobal(index));
+        } else {
+            // Declared before its body is compiled, so the function can call itself
+            let slot = self.declare_local(&function_stmt.name)?;
+            self.emit_closure(function_stmt, FunctionKind::Function)?;
+            self.emit(OpCode::DefineLocal(slot));
+        }
+        Ok(())
+    }
+    fn visit_if(&mut self, if_stmt: &IfStmt) -> Result<(), LangError> {
+        self.compile_expr(&if_stmt.condition)?;
+        let else_jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.compile_statement(&if_stmt.then_branch)?;
+        match if_stmt.else_branch {
+            Some(ref else_branch) => {
+                let end_jump = self.emit_jump(OpCode::Jump);
+                self.patch_jump(else_jump)?;
+                self.compile_statement(else_branch)?;
+                self.patch_jump(end_jump)
+            }
+            None => self.patch_jump(else_jump),
+        }
+    }
+    fn visit_print(&mut self, print_stmt: &PrintStmt) -> Result<(), LangError> {
+        self.compile_expr(&print_stmt.expression)?;
+        self.emit(OpCode::Print);
+        Ok(())
+    }
+    fn visit_return(&mut self, return_stmt: &ReturnStmt) -> Result<(), LangError> {
+        self.compile_expr(&return_stmt.value)?;
+        self.emit(OpCode::Return);
+        Ok(())
+    }
+    fn visit_var(&mut self, var_stmt: &VarStmt) -> Result<(), LangError> {
+        // A variable can be instantiated without being initialized
+        match var_stmt.initializer {
+            Some(ref initializer) => self.compile_expr(initializer)?,
+            None => {
+                self.emit(OpCode::Unit);
+            }
+        }
+        self.emit_define(&var_stmt.name)
+    }
+    fn visit_destructure(&mut self, destructure_stmt: &DestructureStmt) -> Result<(), LangError> {
+        self.compile_expr(&destructure_stmt.initializer)?;
+        let count = Chunk::operand(destructure_stmt.names.len(), "tuple elements")?;
+        // Unpacking leaves the first element on top, where the first variable defined takes it
+        self.emit(OpCode::Unpack(count));
+        for name in destructure_stmt.names.iter() {
+            self.emit_define(name)?;
+        }
+        Ok(())
+    }
+    fn visit_while(&mut self, while_stmt: &WhileStmt) -> Result<(), LangError> {
+        let start = self.current().chunk.code.len();
+        self.compile_expr(&while_stmt.condition)?;
+        let exit_jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.begin_loop(&while_stmt.label, false);
+        self.compile_statement(&while_stmt.body)?;
+        self.patch_continues()?;
+        if let Some(ref increment) = while_stmt.increment {
+            self.compile_expr(increment)?;
+            self.emit(OpCode::Pop);
+        }
+        self.emit_loop(start)?;
+        self.patch_jump(exit_jump)?;
+        self.end_loop()
+    }
+    fn visit_for_in(&mut self, for_in: &ForInStmt) -> Result<(), LangError> {
+        self.compile_expr(&for_in.iterable)?;
+        self.emit(OpCode::Iterate);
+        self.begin_scope();
+        // Like a match scrutinee, the iterator is kept in a slot no identifier can refer to
+        let iterator = self.declare_local("")?;
+        self.emit(OpCode::DefineLocal(iterator));
+        self.begin_loop(&for_in.label, false);
+        let start = self.current().chunk.code.len();
+        self.emit(OpCode::GetLocal(iterator));
+        self.emit(OpCode::IterNext);
+        let exit_jump = self.emit_jump(OpCode::Unwrap);
+        // Each iteration gets its own variable, so closures capture the item they were made with
+        self.begin_scope();
+        let variable = self.declare_local(&for_in.variable)?;
+        self.emit(OpCode::DefineLocal(variable));
+        for stmt in for_in.body.iter() {
+            self.compile_statement(stmt)?;
+        }
+        self.end_scope()?;
+        self.patch_continues()?;
+        self.emit_loop(start)?;
+        self.patch_jump(exit_jump)?;
+        // Pops the `None` the iterator ran out with
+        self.emit(OpCode::Pop);
+        self.end_loop()?;
+        self.end_scope()
+    }
+    // Imports are resolved into statements ahead of compilation
+    fn visit_import(&mut self, _: &ImportStmt) -> Result<(), LangError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,995 @@
+use crate::error::*;
+use crate::format::{FormatArg, FormatKind, FormatSpec};
+use crate::number::{BinaryOperator, Number};
+use crate::value::{MapMethod, RangeValue, TryOutcome};
+use crate::vm::chunk::*;
+use crate::vm::compiler::Program;
+use crate::vm::value::*;
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+/// The deepest the VM lets calls nest before giving up on a runaway recursion
+const FRAMES_MAX: usize = 1 << 16;
+
+#[derive(Debug)]
+struct CallFrame {
+    closure: Rc<Closure>,
+    ip: usize,
+    /// Where the frame's locals start in [`Vm::slots`]
+    slot_base: usize,
+    /// The height of the operand stack when the frame was called, minus the callee
+    stack_base: usize,
+    /// The operator the frame's method overloads, whose value is made out of the method's result
+    operator: Option<OpCode>,
+}
+
+/// A stack machine running the bytecode produced by [`crate::vm::compiler::Compiler`]. Temporaries
+/// live on the operand stack, while each call gets its own region of local slots
+#[derive(Debug, Default)]
+pub struct Vm {
+    stack: Vec<Value>,
+    slots: Vec<Value>,
+    frames: Vec<CallFrame>,
+    globals: Vec<Option<Value>>,
+    global_names: Vec<String>,
+    /// Upvalues still pointing at a live slot, shared by every closure capturing that slot
+    open_upvalues: Vec<Rc<RefCell<Upvalue>>>,
+}
+
+impl Vm {
+    pub fn new() -> Vm {
+        Vm::default()
+    }
+
+    pub fn run(&mut self, program: Program) -> Result<(), LangError> {
+        self.globals = vec![None; program.globals.len()];
+        self.global_names = program.globals;
+        let script = Rc::new(Closure {
+            function: program.script,
+            upvalues: vec![],
+        });
+        self.slots.resize(script.function.slot_count, Value::Unit);
+        let frame = CallFrame {
+            closure: script,
+            ip: 0,
+            slot_base: 0,
+            stack_base: 0,
+            operator: None,
+        };
+        let result = self.execute(frame);
+        self.stack.clear();
+        self.slots.clear();
+        self.frames.clear();
+        self.open_upvalues.clear();
+        result
+    }
+
+    fn execute(&mut self, mut frame: CallFrame) -> Result<(), LangError> {
+        loop {
+            let op = frame.closure.function.chunk.code[frame.ip];
+            frame.ip += 1;
+            if let Some(method) = self.overloading_method(op) {
+                let callee = self.call_operator(op, method)?;
+                self.frames.push(std::mem::replace(&mut frame, callee));
+                continue;
+            }
+            match op {
+                OpCode::Constant(index) => {
+                    let constant = frame.closure.function.chunk.constants[index as usize].clone();
+                    self.stack.push(constant);
+                }
+                OpCode::Unit => self.stack.push(Value::Unit),
+                OpCode::True => self.stack.push(Value::Boolean(true)),
+                OpCode::False => self.stack.push(Value::Boolean(false)),
+                OpCode::Pop => {
+                    self.pop()?;
+                }
+                OpCode::Duplicate(count) => {
+                    let start = self
+                        .stack
+                        .len()
+                        .checked_sub(count as usize)
+                        .ok_or_else(|| {
+                            LangErrorType::new_iie_error(
+                                "tried to duplicate more values than are on the stack".to_string(),
+                            )
+                        })?;
+                    self.stack.extend_from_within(start..);
+                }
+                OpCode::DefineGlobal(index) => {
+                    self.globals[index as usize] = Some(self.pop()?);
+                }
+                OpCode::GetGlobal(index) => {
+                    let value = match self.globals[index as usize] {
+                        Some(ref value) => value.clone(),
+                        None => return Err(self.undefined_global(index)),
+                    };
+                    self.stack.push(value);
+                }
+                OpCode::SetGlobal(index) => {
+                    if self.globals[index as usize].is_none() {
+                        return Err(self.undefined_global(index));
+                    }
+                    self.globals[index as usize] = Some(self.peek()?.clone());
+                }
+                OpCode::DefineLocal(slot) => {
+                    self.slots[frame.slot_base + slot as usize] = self.pop()?;
+                }
+                OpCode::GetLocal(slot) => {
+                    let value = self.slots[frame.slot_base + slot as usize].clone();
+                    self.stack.push(value);
+                }
+                OpCode::SetLocal(slot) => {
+                    self.slots[frame.slot_base + slot as usize] = self.peek()?.clone();
+                }
+                OpCode::GetUpvalue(index) => {
+                    let value = match *frame.closure.upvalues[index as usize].borrow() {
+                        Upvalue::Open(slot) => self.slots[slot].clone(),
+                        Upvalue::Closed(ref value) => value.clone(),
+                    };
+                    self.stack.push(value);
+                }
+                OpCode::SetUpvalue(index) => {
+                    let value = self.peek()?.clone();
+                    let mut upvalue = frame.closure.upvalues[index as usize].borrow_mut();
+                    match *upvalue {
+                        Upvalue::Open(slot) => self.slots[slot] = value,
+                        Upvalue::Closed(_) => *upvalue = Upvalue::Closed(value),
+                    }
+                }
+                OpCode::CloseUpvalues(slot) => self.close_upvalues(frame.slot_base + slot as usize),
+                OpCode::GetField(name) => {
+                    let name = Vm::name(&frame, name)?;
+                    let object = self.pop()?;
+                    let value = Vm::get_field(object, &name)?;
+                    self.stack.push(value);
+                }
+                OpCode::SetField(name) => {
+                    let name = Vm::name(&frame, name)?;
+                    let value = self.pop()?;
+                    let object = self.pop()?;
+                    Vm::set_field(&object, &name, value.clone())?;
+                    self.stack.push(value);
+                }
+                OpCode::GetIndex => {
+                    let index = self.pop()?;
+                    let array = self.pop()?;
+                    let value = match array {
+                        Value::Map(ref map) => match map.borrow().get(&MapKey::new(&index)) {
+                            Some(value) => value.clone(),
+                            None => {
+                                return Err(LangErrorType::new_runtime_error(
+                                    RuntimeErrorType::GenericError {
+                                        reason: error_message(&ErrMessage::MissingKey(
+                                            index.debug_string()?,
+                                        )),
+                                    },
+                                ))
+                            }
+                        },
+                        _ => Vm::with_element(&array, &index, |element| element.clone())?,
+                    };
+                    self.stack.push(value);
+                }
+                OpCode::SetIndex => {
+                    let value = self.pop()?;
+                    let index = self.pop()?;
+                    let array = self.pop()?;
+                    let assigned = value.clone();
+                    match array {
+                        // Assigning to a key of a map inserts it
+                        Value::Map(ref map) => {
+                            let key = MapKey::stored(&index);
+                            map.borrow_mut().insert(index, key, assigned)
+                        }
+                        _ => Vm::with_element(&array, &index, move |element| *element = assigned)?,
+                    }
+                    self.stack.push(value);
+                }
+                OpCode::Array(count) => {
+                    let elements = self.stack.split_off(self.stack.len() - count as usize);
+                    self.stack
+                        .push(Value::Array(Rc::new(RefCell::new(elements))));
+                }
+                OpCode::Map(count) => {
+                    let mut map = MapValue::default();
+                    let entries = self.stack.split_off(self.stack.len() - 2 * count as usize);
+                    let mut entries = entries.into_iter();
+                    while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+                        let lookup_key = MapKey::stored(&key);
+                        map.insert(key, lookup_key, value);
+                    }
+                    self.stack.push(Value::Map(Rc::new(RefCell::new(map))));
+                }
+                OpCode::Tuple(count) => {
+                    let elements = self.stack.split_off(self.stack.len() - count as usize);
+                    self.stack.push(Value::Tuple(Rc::from(elements)));
+                }
+                OpCode::TupleField(field) => {
+                    let element = match self.pop()? {
+                        Value::Tuple(ref elements) => elements.get(field as usize).cloned(),
+                        _ => None,
+                    };
+                    let element = element.ok_or_else(|| {
+                        LangErrorType::new_runtime_error(RuntimeErrorType::UndefinedVariable {
+                            reason: format!("tuple has no field '{}'", field),
+                        })
+                    })?;
+                    self.stack.push(element);
+                }
+                OpCode::Unpack(count) => match self.pop()? {
+                    Value::Tuple(ref elements) if elements.len() == count as usize => {
+                        self.stack.extend(elements.iter().rev().cloned());
+                    }
+                    other => {
+                        return Err(LangErrorType::new_runtime_error(
+                            RuntimeErrorType::InvalidTypeAssignmentError {
+                                reason: format!(
+                                    "Tried to destructure a value of type {} into {} variables",
+                                    other.type_name(),
+                                    count
+                                ),
+                            },
+                        ))
+                    }
+                },
+                OpCode::GetVariant(name) => {
+                    let name = Vm::name(&frame, name)?;
+                    let value = match self.pop()? {
+                        Value::Enum(decl) => decl.get_variant(&name).ok_or_else(|| {
+                            LangErrorType::new_runtime_error(RuntimeErrorType::UndefinedVariable {
+                                reason: format!("{}::{}", decl.name, name),
+                            })
+                        })?,
+                        value => {
+                            return Err(LangErrorType::new_runtime_error(
+                                RuntimeErrorType::GenericError {
+                                    reason: format!("'{}' is not an enum", value),
+                                },
+                            ))
+                        }
+                    };
+                    self.stack.push(value);
+                }
+                OpCode::Range(inclusive) => {
+                    let end = self.pop()?;
+                    let start = self.pop()?;
+                    let bound = |value: &Value| value.as_integer().zip(value.numeric_type());
+                    let range = match (bound(&start), bound(&end)) {
+                        (Some(start), Some(end)) => RangeValue::new(start, end, inclusive),
+                        _ => None,
+                    };
+                    let range = range.ok_or_else(|| {
+                        LangErrorType::new_runtime_error(
+                            RuntimeErrorType::InvalidTypeAssignmentError {
+                                reason: format!(
+                                    "the bounds of a range must be integers of compatible types, found {} and {}",
+                                    start.type_name(),
+                                    end.type_name()
+                                ),
+                            },
+                        )
+                    })?;
+                    self.stack.push(Value::Range(Rc::new(range)));
+                }
+                OpCode::Iterate => {
+                    let items = match self.pop()? {
+                        Value::Array(elements) => {
+                            Items::Elements(elements.borrow().clone().into_iter())
+                        }
+                        Value::Map(map) => Items::Elements(
+                            map.borrow()
+                                .iter()
+                                .map(|(key, _)| key.clone())
+                                .collect::<Vec<Value>>()
+                                .into_iter(),
+                        ),
+                        Value::String(string) => Items::Elements(
+                            string
+                                .chars()
+                                .map(Value::Char)
+                                .collect::<Vec<Value>>()
+                                .into_iter(),
+                        ),
+                        Value::Range(range) => Items::Range {
+                            next: range.start,
+                            end: range.end_exclusive(),
+                            numeric_type: range.numeric_type,
+                        },
+                        instance @ Value::Instance(_) => {
+                            self.stack.push(instance);
+                            continue;
+                        }
+                        value => {
+                            return Err(LangErrorType::new_runtime_error(
+                                RuntimeErrorType::GenericError {
+                                    reason: format!(
+                                        "values of type {} can't be iterated over",
+                                        value.type_name()
+                                    ),
+                                },
+                            ))
+                        }
+                    };
+                    self.stack
+                        .push(Value::Iterator(Rc::new(RefCell::new(items))));
+                }
+                OpCode::IterNext => {
+                    let item = match self.pop()? {
+                        Value::Iterator(items) => items.borrow_mut().next_item(),
+                        _ => {
+                            return Err(LangErrorType::new_runtime_error(
+                                RuntimeErrorType::GenericError {
+                                    reason: "a struct iterated over must implement Iterator"
+                                        .to_string(),
+                                },
+                            ))
+                        }
+                    };
+                    let (variant, discriminant) = match item {
+                        Some(_) => ("Some", 1),
+                        None => ("None", 0),
+                    };
+                    self.stack.push(Value::EnumVariant(Rc::new(EnumVariant {
+                        enum_name: Rc::from("Option"),
+                        variant: Rc::from(variant),
+                        discriminant,
+                        fields: item.into_iter().collect(),
+                    })));
+                }
+                // An instance implementing `Display` was replaced by its `fmt` method already
+                OpCode::ToDisplay => {}
+                OpCode::Format(index) => {
+                    let spec = &frame.closure.function.chunk.formats[index as usize];
+                    let value = self.pop()?;
+                    let formatted =
+                        spec.format(Vm::format_arg(&value, spec)?).ok_or_else(|| {
+                            LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                                reason: format!("the {} format needs an integer", spec.kind),
+                            })
+                        })?;
+                    self.stack.push(Value::string(&formatted));
+                }
+                OpCode::Concat(count) => {
+                    let pieces = self.stack.split_off(self.stack.len() - count as usize);
+                    let text: String = pieces.iter().map(|piece| piece.to_string()).collect();
+                    self.stack.push(Value::string(&text));
+                }
+                OpCode::Equal => {
+                    let rhs = self.pop()?;
+                    let lhs = self.pop()?;
+                    self.stack.push(Value::Boolean(lhs.equals(&rhs)));
+                }
+                OpCode::NotEqual => {
+                    let rhs = self.pop()?;
+                    let lhs = self.pop()?;
+                    self.stack.push(Value::Boolean(!lhs.equals(&rhs)));
+                }
+                OpCode::Greater => self.comparison(|ordering| ordering == Ordering::Greater)?,
+                OpCode::GreaterEqual => self.comparison(|ordering| ordering != Ordering::Less)?,
+                OpCode::Less => self.comparison(|ordering| ordering == Ordering::Less)?,
+                OpCode::LessEqual => self.comparison(|ordering| ordering != Ordering::Greater)?,
+                OpCode::Add
+                | OpCode::Subtract
+                | OpCode::Multiply
+                | OpCode::Divide
+                | OpCode::Remainder
+                | OpCode::BitAnd
+                | OpCode::BitOr
+                | OpCode::BitXor
+                | OpCode::ShiftLeft
+                | OpCode::ShiftRight => {
+                    let rhs = self.pop()?;
+                    let lhs = self.pop()?;
+                    self.stack.push(Vm::binary(op, lhs, rhs)?);
+                }
+                OpCode::Negate => {
+                    let value = self.pop()?;
+                    let negated = Number::negate(value.number(), value.type_name())?;
+                    self.stack.push(negated.into());
+                }
+                OpCode::Cast(target) => {
+                    let value = self.pop()?;
+                    let result = value.cast(target).ok_or_else(|| {
+                        LangErrorType::new_runtime_error(
+                            RuntimeErrorType::InvalidTypeAssignmentError {
+                                reason: format!(
+                                    "cannot cast a value of type {} to a number",
+                                    value.type_name()
+                                ),
+                            },
+                        )
+                    })?;
+                    self.stack.push(result);
+                }
+                OpCode::Not => {
+                    let value = self.pop()?;
+                    self.stack.push(Value::Boolean(!value.is_truthy()));
+                }
+                OpCode::BitNot => {
+                    let value = self.pop()?;
+                    let result = Number::bit_not(value.number(), value.type_name())?;
+                    self.stack.push(result.into());
+                }
+                OpCode::Jump(jump) => frame.ip += jump as usize,
+                OpCode::JumpIfFalse(jump) => {
+                    if !self.pop()?.is_truthy() {
+                        frame.ip += jump as usize;
+                    }
+                }
+                OpCode::JumpIfFalseKeep(jump) => {
+                    if !self.peek()?.is_truthy() {
+                        frame.ip += jump as usize;
+                    }
+                }
+                OpCode::JumpIfTrueKeep(jump) => {
+                    if self.peek()?.is_truthy() {
+                        frame.ip += jump as usize;
+                    }
+                }
+                OpCode::Unwrap(jump) => {
+                    let outcome = match self.peek()? {
+                        Value::EnumVariant(variant) => {
+                            TryOutcome::of(&variant.enum_name, &variant.variant)
+                                .map(|outcome| (outcome, variant.fields.first().cloned()))
+                        }
+                        _ => None,
+                    };
+                    match outcome {
+                        Some((TryOutcome::Unwrap, Some(payload))) => {
+                            self.pop()?;
+                            self.stack.push(payload);
+                        }
+                        Some((TryOutcome::Return, _)) => frame.ip += jump as usize,
+                        _ => {
+                            return Err(LangErrorType::new_runtime_error(
+                                RuntimeErrorType::GenericError {
+                                    reason: format!(
+                                        "the '?' operator can only be applied to an Option or a Result, found {}",
+                                        self.peek()?.type_name()
+                                    ),
+                                },
+                            ))
+                        }
+                    }
+                }
+                OpCode::Loop(jump) => frame.ip -= jump as usize,
+                OpCode::Call(argc) => {
+                    if let Some(callee) = self.call(argc as usize)? {
+                        self.frames.push(std::mem::replace(&mut frame, callee));
+                    }
+                }
+                OpCode::Closure(index) => {
+                    let function = match frame.closure.function.chunk.constants[index as usize] {
+                        Value::Function(ref function) => function.clone(),
+                        ref value => {
+                            return Err(LangErrorType::new_iie_error(format!(
+                                "tried to make a closure out of {:?}",
+                                value
+                            )))
+                        }
+                    };
+                    let upvalues = function
+                        .upvalues
+                        .iter()
+                        .map(|descriptor| {
+                            if descriptor.is_local {
+                                self.capture_upvalue(frame.slot_base + descriptor.index as usize)
+                            } else {
+                                frame.closure.upvalues[descriptor.index as usize].clone()
+                            }
+                        })
+                        .collect();
+                    self.stack
+                        .push(Value::Closure(Rc::new(Closure { function, upvalues })));
+                }
+                OpCode::Return => {
+                    let result = Vm::operator_result(frame.operator, self.pop()?);
+                    self.stack.truncate(frame.stack_base);
+                    self.close_upvalues(frame.slot_base);
+                    self.slots.truncate(frame.slot_base);
+                    frame = match self.frames.pop() {
+                        Some(caller) => caller,
+                        None => return Ok(()),
+                    };
+                    self.stack.push(result);
+                }
+                OpCode::Print => println!("{}", self.pop()?),
+                OpCode::Assert => {
+                    if !self.pop()?.is_truthy() {
+                        println!("assert failed");
+                        return Err(LangErrorType::new_runtime_error(
+                            RuntimeErrorType::AssertionError {
+                                reason: "the asserted condition was false".to_string(),
+                            },
+                        ));
+                    }
+                }
+                OpCode::Construct(count) => {
+                    let fields = self.stack.split_off(self.stack.len() - count as usize);
+                    let decl = match self.pop()? {
+                        Value::Struct(decl) if decl.fields.len() == fields.len() => decl,
+                        value => {
+                            return Err(LangErrorType::new_iie_error(format!(
+                                "tried to build an instance with {} fields out of {:?}",
+                                fields.len(),
+                                value
+                            )))
+                        }
+                    };
+                    self.stack
+                        .push(Value::Instance(Rc::new(RefCell::new(Instance {
+                            decl,
+                            fields,
+                        }))));
+                }
+                OpCode::Struct(index) => {
+                    // Every evaluation of a struct declaration gets its own set of methods
+                    let decl = match frame.closure.function.chunk.constants[index as usize] {
+                        Value::Struct(ref template) => {
+                            StructDecl::new(template.name.clone(), template.fields.clone())
+                        }
+                        ref value => {
+                            return Err(LangErrorType::new_iie_error(format!(
+                                "tried to declare a struct out of {:?}",
+                                value
+                            )))
+                        }
+                    };
+                    self.stack.push(Value::Struct(Rc::new(decl)));
+                }
+                OpCode::Method(name) => {
+                    let name = Vm::name(&frame, name)?;
+                    let method = match self.pop()? {
+                        Value::Closure(closure) => closure,
+                        value => {
+                            return Err(LangErrorType::new_iie_error(format!(
+                                "tried to define {:?} as a method",
+                                value
+                            )))
+                        }
+                    };
+                    match self.peek()? {
+                        Value::Struct(decl) => {
+                            decl.methods.borrow_mut().insert(name.to_string(), method);
+                        }
+                        value => {
+                            return Err(LangErrorType::new_runtime_error(
+                                RuntimeErrorType::GenericError {
+                                    reason: format!("cannot implement methods for '{}'", value),
+                                },
+                            ))
+                        }
+                    }
+                }
+                OpCode::Match(index) => {
+                    let value = self.pop()?;
+                    let mut bindings = Vec::new();
+                    let pattern = &frame.closure.function.chunk.patterns[index as usize];
+                    if pattern.matches(&value, &mut bindings) {
+                        self.stack.append(&mut bindings);
+                        self.stack.push(Value::Boolean(true));
+                    } else {
+                        self.stack.push(Value::Boolean(false));
+                    }
+                }
+                OpCode::NoMatch => {
+                    let value = self.pop()?;
+                    return Err(LangErrorType::new_runtime_error(
+                        RuntimeErrorType::GenericError {
+                            reason: format!("no match arm matched the value '{}'", value),
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Result<Value, LangError> {
+        self.stack.pop().ok_or_else(|| {
+            LangErrorType::new_iie_error("tried to pop from an empty stack".to_string())
+        })
+    }
+
+    fn peek(&self) -> Result<&Value, LangError> {
+        self.stack.last().ok_or_else(|| {
+            LangErrorType::new_iie_error("tried to peek at an empty stack".to_string())
+        })
+    }
+
+    fn name(frame: &CallFrame, index: u16) -> Result<Rc<str>, LangError> {
+        match frame.closure.function.chunk.constants[index as usize] {
+            Value::String(ref name) => Ok(name.clone()),
+            ref value => Err(LangErrorType::new_iie_error(format!(
+                "expected a name constant, found {:?}",
+                value
+            ))),
+        }
+    }
+
+    fn undefined_global(&self, index: u16) -> LangError {
+        LangErrorType::new_runtime_error(RuntimeErrorType::UndefinedVariable {
+            reason: self.global_names[index as usize].clone(),
+        })
+    }
+
+    /// Calls the callee sitting below the top `argc` values of the stack. Returns the frame to
+    /// switch to when it's a closure, anything else is called in place
+    fn call(&mut self, argc: usize) -> Result<Option<CallFrame>, LangError> {
+        let callee_index = self.stack.len() - argc - 1;
+        let (closure, receiver) = match self.stack[callee_index].clone() {
+            Value::Closure(closure) => (closure, None),
+            Value::BoundMethod(bound) => (bound.method.clone(), Some(bound.receiver.clone())),
+            Value::BoundMapMethod(bound) => {
+                Vm::check_arity(
+                    &format!("Map::{}", bound.method.name()),
+                    bound.method.arity(),
+                    argc,
+                )?;
+                let args = self.stack.split_off(callee_index + 1);
+                self.stack.truncate(callee_index);
+                let result = Vm::call_map_method(&bound, args);
+                self.stack.push(result);
+                return Ok(None);
+            }
+            Value::Struct(decl) => {
+                self.stack.truncate(callee_index);
+                let fields = vec![Value::Unit; decl.fields.len()];
+                self.stack
+                    .push(Value::Instance(Rc::new(RefCell::new(Instance {
+                        decl,
+                        fields,
+                    }))));
+                return Ok(None);
+            }
+            Value::VariantConstructor(constructor) => {
+                let variant = &constructor.decl.variants[constructor.variant];
+                Vm::check_arity(
+                    &format!("{}::{}", constructor.decl.name, variant.name),
+                    variant.arity,
+                    argc,
+                )?;
+                let fields = self.stack.split_off(callee_index + 1);
+                self.stack.truncate(callee_index);
+                self.stack.push(Value::EnumVariant(Rc::new(EnumVariant {
+                    enum_name: constructor.decl.name.clone(),
+                    variant: variant.name.clone(),
+                    discriminant: variant.discriminant,
+                    fields,
+                })));
+                return Ok(None);
+            }
+            _ => {
+                return Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::CallError {
+                        reason: "Can only call functions and structs".to_string(),
+                    },
+                ))
+            }
+        };
+        if self.frames.len() >= FRAMES_MAX {
+            return Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::CallError {
+                    reason: format!("stack overflow calling '{}'", closure.function.name),
+                },
+            ));
+        }
+        let function = &closure.function;
+        let slot_base = self.slots.len();
+        self.slots
+            .resize(slot_base + function.slot_count, Value::Unit);
+        let mut first_arg = 0;
+        let mut expected = function.arity;
+        if let FunctionKind::Method { takes_receiver } = function.kind {
+            first_arg = 1;
+            match receiver {
+                Some(receiver) => {
+                    self.slots[slot_base] = receiver.clone();
+                    if takes_receiver {
+                        self.slots[slot_base + 1] = receiver;
+                        first_arg = 2;
+                    }
+                }
+                // A method looked up on the struct itself rather than an instance is passed its
+                // receiver as the first argument, when it takes one
+                None if takes_receiver => {
+                    expected += 1;
+                    if argc > 0 {
+                        self.slots[slot_base] = self.stack[callee_index + 1].clone();
+                    }
+                }
+                None => {}
+            }
+        }
+        Vm::check_arity(&function.name, expected, argc)?;
+        for (offset, arg) in self.stack.drain(callee_index + 1..).enumerate() {
+            self.slots[slot_base + first_arg + offset] = arg;
+        }
+        self.stack.truncate(callee_index);
+        Ok(Some(CallFrame {
+            closure,
+            ip: 0,
+            slot_base,
+            stack_base: callee_index,
+            operator: None,
+        }))
+    }
+
+    /// The method overloading `op` when its left operand is an instance of a struct implementing
+    /// the operator's built-in trait, such as `Add` for `+`. `IterNext` calls `next` on an
+    /// instance implementing `Iterator`
+    fn overloading_method(&self, op: OpCode) -> Option<Rc<Closure>> {
+        let name = match op {
+            OpCode::Add => "add",
+            OpCode::Subtract => "sub",
+            OpCode::Multiply => "mul",
+            OpCode::Divide => "div",
+            OpCode::Remainder => "rem",
+            OpCode::BitAnd => "bitand",
+            OpCode::BitOr => "bitor",
+            OpCode::BitXor => "bitxor",
+            OpCode::ShiftLeft => "shl",
+            OpCode::ShiftRight => "shr",
+            OpCode::Equal | OpCode::NotEqual => "eq",
+            OpCode::Greater | OpCode::GreaterEqual | OpCode::Less | OpCode::LessEqual => "cmp",
+            OpCode::GetIndex => "index",
+            OpCode::IterNext => "next",
+            OpCode::ToDisplay => "fmt",
+            _ => return None,
+        };
+        let receiver_index = self.stack.len().checked_sub(Vm::operand_count(op))?;
+        match self.stack.get(receiver_index)? {
+            Value::Instance(instance) => instance.borrow().decl.methods.borrow().get(name).cloned(),
+            _ => None,
+        }
+    }
+
+    /// The number of operands on top of the stack an overloadable `op` works on
+    fn operand_count(op: OpCode) -> usize {
+        match op {
+            OpCode::IterNext | OpCode::ToDisplay => 1,
+            _ => 2,
+        }
+    }
+
+    /// Calls `method` on the operands of `op` on top of the stack, returning its frame
+    fn call_operator(&mut self, op: OpCode, method: Rc<Closure>) -> Result<CallFrame, LangError> {
+        let receiver_index = self.stack.len() - Vm::operand_count(op);
+        let receiver = self.stack[receiver_index].clone();
+        self.stack[receiver_index] = Value::BoundMethod(Rc::new(BoundMethod { receiver, method }));
+        let mut callee = self.call(Vm::operand_count(op) - 1)?.ok_or_else(|| {
+            LangErrorType::new_iie_error(format!("the method overloading {:?} isn't a closure", op))
+        })?;
+        callee.operator = Some(op);
+        Ok(callee)
+    }
+
+    /// Makes the value of `operator` out of the result of the method overloading it. `eq` answers
+    /// `==` and `cmp` orders the operands by returning a negative number, zero or a positive number
+    fn operator_result(operator: Option<OpCode>, result: Value) -> Value {
+        match (operator, &result) {
+            (Some(OpCode::NotEqual), Value::Boolean(equal)) => Value::Boolean(!equal),
+            (Some(OpCode::Greater), Value::Int32(ordering)) => Value::Boolean(*ordering > 0),
+            (Some(OpCode::GreaterEqual), Value::Int32(ordering)) => Value::Boolean(*ordering >= 0),
+            (Some(OpCode::Less), Value::Int32(ordering)) => Value::Boolean(*ordering < 0),
+            (Some(OpCode::LessEqual), Value::Int32(ordering)) => Value::Boolean(*ordering <= 0),
+            _ => result,
+        }
+    }
+
+    /// `value` as `spec` formats it
+    fn format_arg(value: &Value, spec: &FormatSpec) -> Result<FormatArg, LangError> {
+        if let Some(number) = value.number() {
+            return Ok(number.format_arg(spec.kind));
+        }
+        let text = match spec.kind {
+            FormatKind::Debug => value.debug_string()?,
+            _ => value.to_string(),
+        };
+        Ok(FormatArg::Text(text))
+    }
+
+    fn check_arity(name: &str, expected: usize, passed: usize) -> Result<(), LangError> {
+        if expected != passed {
+            return Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::FnArityError {
+                    reason: format!(
+                        "Function {} requires {} arg(s), passed {}",
+                        name, expected, passed
+                    ),
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    fn capture_upvalue(&mut self, slot: usize) -> Rc<RefCell<Upvalue>> {
+        let existing = self
+            .open_upvalues
+            .iter()
+            .find(|upvalue| matches!(*upvalue.borrow(), Upvalue::Open(open) if open == slot));
+        if let Some(upvalue) = existing {
+            return upvalue.clone();
+        }
+        let upvalue = Rc::new(RefCell::new(Upvalue::Open(slot)));
+        self.open_upvalues.push(upvalue.clone());
+        upvalue
+    }
+
+    /// Moves the values of the slots from `first` upwards into the upvalues capturing them
+    fn close_upvalues(&mut self, first: usize) {
+        let slots = &self.slots;
+        self.open_upvalues.retain(|upvalue| {
+            let slot = match *upvalue.borrow() {
+                Upvalue::Open(slot) if slot >= first => slot,
+                _ => return true,
+            };
+            *upvalue.borrow_mut() = Upvalue::Closed(slots[slot].clone());
+            false
+        });
+    }
+
+    fn get_field(object: Value, name: &str) -> Result<Value, LangError> {
+        match object {
+            Value::Instance(instance) => {
+                let borrowed = instance.borrow();
+                if let Some(index) = borrowed.decl.field_index(name) {
+                    return Ok(borrowed.fields[index].clone());
+                }
+                if let Some(method) = borrowed.decl.methods.borrow().get(name) {
+                    return Ok(Value::BoundMethod(Rc::new(BoundMethod {
+                        receiver: Value::Instance(instance.clone()),
+                        method: method.clone(),
+                    })));
+                }
+                Err(Vm::undefined_field(&borrowed.decl.name, name))
+            }
+            Value::Struct(decl) => match decl.methods.borrow().get(name) {
+                Some(method) => Ok(Value::Closure(method.clone())),
+                None => Err(Vm::undefined_field(&decl.name, name)),
+            },
+            Value::Map(map) => match MapMethod::from_name(name) {
+                Some(method) => Ok(Value::BoundMapMethod(Rc::new(BoundMapMethod {
+                    map,
+                    method,
+                }))),
+                None => Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::UndefinedVariable {
+                        reason: format!("map has no method '{}'", name),
+                    },
+                )),
+            },
+            value => Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::GenericError {
+                    reason: format!("cannot get the field '{}' of '{}'", name, value),
+                },
+            )),
+        }
+    }
+
+    /// Runs the map method `bound` with the arguments `args`, which it's been checked to take
+    fn call_map_method(bound: &BoundMapMethod, mut args: Vec<Value>) -> Value {
+        let mut map = bound.map.borrow_mut();
+        match bound.method {
+            MapMethod::Insert => {
+                let value = args.pop().unwrap_or(Value::Unit);
+                let key = args.pop().unwrap_or(Value::Unit);
+                let lookup_key = MapKey::stored(&key);
+                map.insert(key, lookup_key, value);
+                Value::Unit
+            }
+            MapMethod::Remove => Value::Boolean(map.remove(&MapKey::new(&args[0])).is_some()),
+            MapMethod::Contains => Value::Boolean(map.contains_key(&MapKey::new(&args[0]))),
+            MapMethod::Len => Value::Int64(map.len() as i64),
+            MapMethod::Keys => {
+                let keys = map.iter().map(|(key, _)| key.clone()).collect();
+                Value::Array(Rc::new(RefCell::new(keys)))
+            }
+            MapMethod::Values => {
+                let values = map.iter().map(|(_, value)| value.clone()).collect();
+                Value::Array(Rc::new(RefCell::new(values)))
+            }
+        }
+    }
+
+    fn set_field(object: &Value, name: &str, value: Value) -> Result<(), LangError> {
+        match object {
+            Value::Instance(instance) => {
+                let mut borrowed = instance.borrow_mut();
+                match borrowed.decl.field_index(name) {
+                    Some(index) => {
+                        borrowed.fields[index] = value;
+                        Ok(())
+                    }
+                    None => Err(Vm::undefined_field(&borrowed.decl.name, name)),
+                }
+            }
+            object => Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::GenericError {
+                    reason: format!("cannot set the field '{}' of '{}'", name, object),
+                },
+            )),
+        }
+    }
+
+    fn undefined_field(struct_name: &str, name: &str) -> LangError {
+        LangErrorType::new_runtime_error(RuntimeErrorType::UndefinedVariable {
+            reason: format!("{}.{}", struct_name, name),
+        })
+    }
+
+    fn with_element<T>(
+        array: &Value,
+        index: &Value,
+        f: impl FnOnce(&mut Value) -> T,
+    ) -> Result<T, LangError> {
+        let elements = match array {
+            Value::Array(elements) => elements,
+            value => {
+                return Err(LangErrorType::new_runtime_error(
+                    RuntimeErrorType::GenericError {
+                        reason: format!("cannot index into a value of type {}", value.type_name()),
+                    },
+                ))
+            }
+        };
+        let mut elements = elements.borrow_mut();
+        let len = elements.len();
+        let element = index
+            .as_integer()
+            .filter(|i| *i >= 0)
+            .and_then(|i| elements.get_mut(i as usize))
+            .ok_or_else(|| {
+                LangErrorType::new_runtime_error(RuntimeErrorType::GenericError {
+                    reason: error_message(&ErrMessage::IndexOutOfBounds(index.to_string(), len)),
+                })
+            })?;
+        Ok(f(element))
+    }
+
+    fn comparison(&mut self, test: impl Fn(Ordering) -> bool) -> Result<(), LangError> {
+        let rhs = self.pop()?;
+        let lhs = self.pop()?;
+        match lhs.compare(&rhs) {
+            Some(ordering) => {
+                self.stack.push(Value::Boolean(test(ordering)));
+                Ok(())
+            }
+            None => Err(LangErrorType::new_runtime_error(
+                RuntimeErrorType::GenericError {
+                    reason: format!(
+                        "cannot compare values of type {} and {}",
+                        lhs.type_name(),
+                        rhs.type_name()
+                    ),
+                },
+            )),
+        }
+    }
+
+    /// Applies a binary arithmetic or bitwise operator, see `Number::apply`. `+` also concatenates
+    /// strings
+    fn binary(op: OpCode, lhs: Value, rhs: Value) -> Result<Value, LangError> {
+        let operator = match op {
+            OpCode::Add => BinaryOperator::Add,
+            OpCode::Subtract => BinaryOperator::Subtract,
+            OpCode::Multiply => BinaryOperator::Multiply,
+            OpCode::Divide => BinaryOperator::Divide,
+            OpCode::Remainder => BinaryOperator::Remainder,
+            OpCode::BitAnd => BinaryOperator::BitAnd,
+            OpCode::BitOr => BinaryOperator::BitOr,
+            OpCode::BitXor => BinaryOperator::BitXor,
+            OpCode::ShiftLeft => BinaryOperator::ShiftLeft,
+            _ => BinaryOperator::ShiftRight,
+        };
+        if let (Value::String(l), Value::String(r)) = (&lhs, &rhs) {
+            if operator == BinaryOperator::Add {
+                return Ok(Value::String(Rc::from(format!("{}{}", l, r))));
+            }
+        }
+        let types = (lhs.type_name(), rhs.type_name());
+        Number::apply(operator, lhs.number(), rhs.number(), types).map(Value::from)
+    }
+}
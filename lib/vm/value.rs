@@ -0,0 +1,759 @@
+use crate::error::LangError;
+use crate::format::{self, DebugParts, DebugValue};
+use crate::number::Number;
+use crate::ordered_map::OrderedMap;
+use crate::token::NumericType;
+use crate::value::{MapMethod, RangeValue};
+use crate::vm::chunk::Chunk;
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{self, Debug, Display},
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+/// A value on the VM's stack. Unlike the interpreter's values these are cheap to clone, anything
+/// larger than a number is reference counted
+#[derive(Clone)]
+pub enum Value {
+    Unit,
+    Boolean(bool),
+    Char(char),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    USize(usize),
+    Float32(f32),
+    Float64(f64),
+    String(Rc<str>),
+    Array(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<MapValue>>),
+    /// Tuples can't be changed once they're made, so they're shared without a `RefCell`
+    Tuple(Rc<[Value]>),
+    /// A compiled function, only found in constant pools. Closures are made from it at runtime
+    Function(Rc<Function>),
+    Closure(Rc<Closure>),
+    BoundMethod(Rc<BoundMethod>),
+    BoundMapMethod(Rc<BoundMapMethod>),
+    Struct(Rc<StructDecl>),
+    Instance(Rc<RefCell<Instance>>),
+    Enum(Rc<EnumDecl>),
+    EnumVariant(Rc<EnumVariant>),
+    VariantConstructor(Rc<VariantConstructor>),
+    Trait(Rc<TraitDecl>),
+    Range(Rc<RangeValue>),
+    /// What a `for` loop goes through when it's over an array, a string or a range
+    Iterator(Rc<RefCell<Items>>),
+}
+
+impl Value {
+    pub fn string(s: &str) -> Value {
+        Value::String(Rc::from(s))
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Unit => false,
+            Value::Boolean(b) => *b,
+            _ => true,
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Unit => "()",
+            Value::Boolean(_) => "bool",
+            Value::Char(_) => "char",
+            Value::Int32(_) => "i32",
+            Value::Int64(_) => "i64",
+            Value::Int8(_) => "i8",
+            Value::Int16(_) => "i16",
+            Value::UInt8(_) => "u8",
+            Value::UInt16(_) => "u16",
+            Value::UInt32(_) => "u32",
+            Value::UInt64(_) => "u64",
+            Value::USize(_) => "usize",
+            Value::Float32(_) => "f32",
+            Value::Float64(_) => "f64",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Map(_) => "map",
+            Value::Tuple(_) => "tuple",
+            Value::Function(_)
+            | Value::Closure(_)
+            | Value::BoundMethod(_)
+            | Value::BoundMapMethod(_) => "fn",
+            Value::Struct(_) => "struct",
+            Value::Instance(_) => "struct instance",
+            Value::Enum(_) => "enum",
+            Value::EnumVariant(_) => "enum variant",
+            Value::VariantConstructor(_) => "enum variant constructor",
+            Value::Trait(_) => "trait",
+            Value::Range(_) => "range",
+            Value::Iterator(_) => "iterator",
+        }
+    }
+
+    /// Whether a constant pool slot holding `self` can be reused for `other`
+    pub fn is_same_constant(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::String(lhs), Value::String(rhs)) => lhs == rhs,
+            (Value::Char(lhs), Value::Char(rhs)) => lhs == rhs,
+            (Value::Int32(lhs), Value::Int32(rhs)) => lhs == rhs,
+            (Value::Int64(lhs), Value::Int64(rhs)) => lhs == rhs,
+            (Value::Int8(lhs), Value::Int8(rhs)) => lhs == rhs,
+            (Value::Int16(lhs), Value::Int16(rhs)) => lhs == rhs,
+            (Value::UInt8(lhs), Value::UInt8(rhs)) => lhs == rhs,
+            (Value::UInt16(lhs), Value::UInt16(rhs)) => lhs == rhs,
+            (Value::UInt32(lhs), Value::UInt32(rhs)) => lhs == rhs,
+            (Value::UInt64(lhs), Value::UInt64(rhs)) => lhs == rhs,
+            (Value::USize(lhs), Value::USize(rhs)) => lhs == rhs,
+            (Value::Float32(lhs), Value::Float32(rhs)) => lhs.to_bits() == rhs.to_bits(),
+            (Value::Float64(lhs), Value::Float64(rhs)) => lhs.to_bits() == rhs.to_bits(),
+            _ => false,
+        }
+    }
+
+    /// The value as a number, if it's one
+    pub fn number(&self) -> Option<Number> {
+        match self {
+            Value::Int8(i) => Some(Number::I8(*i)),
+            Value::Int16(i) => Some(Number::I16(*i)),
+            Value::Int32(i) => Some(Number::I32(*i)),
+            Value::Int64(i) => Some(Number::I64(*i)),
+            Value::UInt8(i) => Some(Number::U8(*i)),
+            Value::UInt16(i) => Some(Number::U16(*i)),
+            Value::UInt32(i) => Some(Number::U32(*i)),
+            Value::UInt64(i) => Some(Number::U64(*i)),
+            Value::USize(i) => Some(Number::USize(*i)),
+            Value::Float32(f) => Some(Number::F32(*f)),
+            Value::Float64(f) => Some(Number::F64(*f)),
+            _ => None,
+        }
+    }
+
+    /// The type of the number this is, if it's one
+    pub fn numeric_type(&self) -> Option<NumericType> {
+        self.number().map(Number::numeric_type)
+    }
+
+    /// The value of an integer of any width, which all fit in an i128
+    pub fn as_integer(&self) -> Option<i128> {
+        self.number()?.integer()
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        let number = self.number()?;
+        number
+            .float()
+            .or_else(|| number.integer().map(|i| i as f64))
+    }
+
+    /// How `{:?}` writes the value, see `format::debug_string`
+    pub fn debug_string(&self) -> Result<String, LangError> {
+        format::debug_string(self)
+    }
+
+    /// `value` as an integer of type `numeric_type`, or `None` when it's out of the type's range
+    pub fn from_integer(value: i128, numeric_type: NumericType) -> Option<Value> {
+        Number::from_integer(value, numeric_type).map(Value::from)
+    }
+
+    /// Converts an integer to `numeric_type` the way `as` does, truncating it to narrower integers
+    pub fn convert_integer(value: i128, numeric_type: NumericType) -> Value {
+        Number::convert_integer(value, numeric_type).into()
+    }
+
+    /// Converts a number, bool or char to the numeric type `target` the way `as` does, or `None`
+    /// for any other value
+    pub fn cast(&self, target: NumericType) -> Option<Value> {
+        let number = match self {
+            Value::Boolean(b) => Number::convert_integer(i128::from(*b), target),
+            Value::Char(c) => Number::convert_integer(i128::from(u32::from(*c)), target),
+            other => other.number()?.cast(target),
+        };
+        Some(number.into())
+    }
+
+    /// Value equality. Numbers of different widths are compared by value, structs and functions
+    /// are only equal to themselves
+    pub fn equals(&self, other: &Value) -> bool {
+        self.equals_within(other, false)
+    }
+
+    /// Like `equals`, but struct instances of the same struct are also equal when their fields
+    /// are, the way the keys of a map are compared
+    fn key_equals(&self, other: &Value) -> bool {
+        self.equals_within(other, true)
+    }
+
+    fn equals_within(&self, other: &Value, instances_by_value: bool) -> bool {
+        let equals = |lhs: &Value, rhs: &Value| lhs.equals_within(rhs, instances_by_value);
+        match (self, other) {
+            (Value::Unit, Value::Unit) => true,
+            (Value::Boolean(lhs), Value::Boolean(rhs)) => lhs == rhs,
+            (Value::Char(lhs), Value::Char(rhs)) => lhs == rhs,
+            (Value::String(lhs), Value::String(rhs)) => lhs == rhs,
+            (Value::Array(lhs), Value::Array(rhs)) => {
+                let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+                lhs.len() == rhs.len() && lhs.iter().zip(rhs.iter()).all(|(l, r)| equals(l, r))
+            }
+            (Value::Map(lhs), Value::Map(rhs)) => {
+                let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+                lhs.len() == rhs.len()
+                    && lhs
+                        .lookup_iter()
+                        .all(|(key, value)| rhs.get(key).is_some_and(|r| equals(value, r)))
+            }
+            (Value::Tuple(lhs), Value::Tuple(rhs)) => {
+                lhs.len() == rhs.len() && lhs.iter().zip(rhs.iter()).all(|(l, r)| equals(l, r))
+            }
+            (Value::EnumVariant(lhs), Value::EnumVariant(rhs)) => {
+                lhs.enum_name == rhs.enum_name
+                    && lhs.variant == rhs.variant
+                    && lhs.fields.len() == rhs.fields.len()
+                    && lhs
+                        .fields
+                        .iter()
+                        .zip(rhs.fields.iter())
+                        .all(|(l, r)| equals(l, r))
+            }
+            (Value::Enum(lhs), Value::Enum(rhs)) => lhs.name == rhs.name,
+            (Value::Closure(lhs), Value::Closure(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (Value::Struct(lhs), Value::Struct(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (Value::Instance(lhs), Value::Instance(rhs)) if instances_by_value => {
+                let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+                lhs.decl.name == rhs.decl.name
+                    && lhs.fields.len() == rhs.fields.len()
+                    && lhs
+                        .fields
+                        .iter()
+                        .zip(rhs.fields.iter())
+                        .all(|(l, r)| equals(l, r))
+            }
+            (Value::Instance(lhs), Value::Instance(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (Value::Trait(lhs), Value::Trait(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (Value::Range(lhs), Value::Range(rhs)) => lhs == rhs,
+            (Value::Iterator(lhs), Value::Iterator(rhs)) => Rc::ptr_eq(lhs, rhs),
+            (lhs, rhs) => match (lhs.as_integer(), rhs.as_integer()) {
+                (Some(lhs), Some(rhs)) => lhs == rhs,
+                _ => match (lhs.as_f64(), rhs.as_f64()) {
+                    (Some(lhs), Some(rhs)) => lhs == rhs,
+                    _ => false,
+                },
+            },
+        }
+    }
+
+    /// Feeds `state` what identifies the value to `key_equals`. Numbers that compare equal hash
+    /// the same whatever their width, and instances hash by their fields
+    fn hash_into<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Value::Boolean(b) => b.hash(state),
+            Value::Char(c) => c.hash(state),
+            Value::String(s) => s.hash(state),
+            Value::Array(elements) => {
+                for element in elements.borrow().iter() {
+                    element.hash_into(state);
+                }
+            }
+            // Equal maps can hold their entries in any order
+            Value::Map(map) => map.borrow().len().hash(state),
+            Value::Tuple(elements) => {
+                for element in elements.iter() {
+                    element.hash_into(state);
+                }
+            }
+            Value::EnumVariant(variant) => {
+                variant.enum_name.hash(state);
+                variant.variant.hash(state);
+                for field in variant.fields.iter() {
+                    field.hash_into(state);
+                }
+            }
+            Value::Enum(decl) => decl.name.hash(state),
+            Value::Instance(instance) => {
+                let instance = instance.borrow();
+                instance.decl.name.hash(state);
+                for field in instance.fields.iter() {
+                    field.hash_into(state);
+                }
+            }
+            Value::Struct(decl) => Rc::as_ptr(decl).hash(state),
+            Value::Range(range) => range.hash(state),
+            value => match (value.as_integer(), value.as_f64()) {
+                (Some(i), _) => i.hash(state),
+                (None, Some(f)) if f.fract() == 0.0 => (f as i128).hash(state),
+                (None, Some(f)) => f.to_bits().hash(state),
+                (None, None) => {}
+            },
+        }
+    }
+
+    /// Orders values of the same kind, `None` when they can't be ordered. Variants of the same
+    /// enum are ordered by their discriminants, then their payloads
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Unit, Value::Unit) => Some(Ordering::Equal),
+            (Value::Boolean(lhs), Value::Boolean(rhs)) => Some(lhs.cmp(rhs)),
+            (Value::Char(lhs), Value::Char(rhs)) => Some(lhs.cmp(rhs)),
+            (Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+            (Value::EnumVariant(lhs), Value::EnumVariant(rhs))
+                if lhs.enum_name == rhs.enum_name =>
+            {
+                let mut ordering = lhs.discriminant.cmp(&rhs.discriminant);
+                for (l, r) in lhs.fields.iter().zip(rhs.fields.iter()) {
+                    if ordering != Ordering::Equal {
+                        break;
+                    }
+                    ordering = l.compare(r)?;
+                }
+                Some(ordering)
+            }
+            (Value::Tuple(lhs), Value::Tuple(rhs)) => {
+                for (l, r) in lhs.iter().zip(rhs.iter()) {
+                    match l.compare(r)? {
+                        Ordering::Equal => {}
+                        ordering => return Some(ordering),
+                    }
+                }
+                Some(lhs.len().cmp(&rhs.len()))
+            }
+            (lhs, rhs) => match (lhs.as_integer(), rhs.as_integer()) {
+                (Some(lhs), Some(rhs)) => Some(lhs.cmp(&rhs)),
+                _ => lhs.as_f64()?.partial_cmp(&rhs.as_f64()?),
+            },
+        }
+    }
+}
+
+impl From<Number> for Value {
+    fn from(number: Number) -> Value {
+        match number {
+            Number::I8(i) => Value::Int8(i),
+            Number::I16(i) => Value::Int16(i),
+            Number::I32(i) => Value::Int32(i),
+            Number::I64(i) => Value::Int64(i),
+            Number::U8(i) => Value::UInt8(i),
+            Number::U16(i) => Value::UInt16(i),
+            Number::U32(i) => Value::UInt32(i),
+            Number::U64(i) => Value::UInt64(i),
+            Number::USize(i) => Value::USize(i),
+            Number::F32(f) => Value::Float32(f),
+            Number::F64(f) => Value::Float64(f),
+        }
+    }
+}
+
+impl DebugValue for Value {
+    type Identity = *const RefCell<Instance>;
+
+    fn debug_parts(&self) -> Result<DebugParts<Value, Self::Identity>, LangError> {
+        let parts = match self {
+            Value::String(s) => DebugParts::Leaf(format!("{:?}", s)),
+            Value::Char(c) => DebugParts::Leaf(format!("{:?}", c)),
+            Value::Float32(f) => DebugParts::Leaf(format!("{:?}", f)),
+            Value::Float64(f) => DebugParts::Leaf(format!("{:?}", f)),
+            Value::Unit => DebugParts::Leaf("()".to_string()),
+            Value::Array(elements) => DebugParts::Array(elements.borrow().clone()),
+            Value::Tuple(elements) => DebugParts::Tuple(elements.to_vec()),
+            Value::Map(map) => DebugParts::Map(map.borrow().iter().cloned().collect()),
+            Value::EnumVariant(variant) => DebugParts::Variant {
+                enum_name: variant.enum_name.to_string(),
+                variant: variant.variant.to_string(),
+                fields: variant.fields.clone(),
+            },
+            Value::Instance(instance) => {
+                let identity = Rc::as_ptr(instance);
+                let instance = instance.borrow();
+                DebugParts::Instance {
+                    name: instance.decl.name.clone(),
+                    identity,
+                    fields: instance
+                        .decl
+                        .fields
+                        .iter()
+                        .cloned()
+                        .zip(instance.fields.iter().cloned())
+                        .collect(),
+                }
+            }
+            _ => DebugParts::Leaf(self.to_string()),
+        };
+        Ok(parts)
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Unit => write!(f, "unit"),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::Char(c) => write!(f, "{}", c),
+            Value::Int32(i) => write!(f, "{}", i),
+            Value::Int64(i) => write!(f, "{}", i),
+            Value::Int8(i) => write!(f, "{}", i),
+            Value::Int16(i) => write!(f, "{}", i),
+            Value::UInt8(i) => write!(f, "{}", i),
+            Value::UInt16(i) => write!(f, "{}", i),
+            Value::UInt32(i) => write!(f, "{}", i),
+            Value::UInt64(i) => write!(f, "{}", i),
+            Value::USize(i) => write!(f, "{}", i),
+            Value::Float32(fl) => write!(f, "{}", fl),
+            Value::Float64(fl) => write!(f, "{}", fl),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Array(elements) => {
+                let formatted_elements: Vec<String> = elements
+                    .borrow()
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect();
+                write!(f, "{}", formatted_elements.join(" "))
+            }
+            Value::Map(map) => {
+                let map = map.borrow();
+                if map.is_empty() {
+                    return write!(f, "[:]");
+                }
+                let formatted_entries: Vec<String> = map
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect();
+                write!(f, "[{}]", formatted_entries.join(", "))
+            }
+            Value::Tuple(elements) => {
+                let formatted_elements: Vec<String> =
+                    elements.iter().map(|element| element.to_string()).collect();
+                write!(f, "({})", formatted_elements.join(", "))
+            }
+            Value::Function(function) => write!(f, "<fn {}>", function.name),
+            Value::Closure(closure) => write!(f, "<fn {}>", closure.function.name),
+            Value::BoundMethod(bound) => write!(f, "<fn {}>", bound.method.function.name),
+            Value::BoundMapMethod(bound) => write!(f, "<fn Map::{}>", bound.method.name()),
+            Value::Struct(decl) => write!(f, "{}", decl.name),
+            Value::Instance(instance) => write!(f, "{}", instance.borrow().decl.name),
+            Value::Enum(decl) => write!(f, "enum {}", decl.name),
+            Value::EnumVariant(variant) => {
+                write!(f, "{}::{}", variant.enum_name, variant.variant)?;
+                if !variant.fields.is_empty() {
+                    let formatted_fields: Vec<String> = variant
+                        .fields
+                        .iter()
+                        .map(|field| field.to_string())
+                        .collect();
+                    write!(f, "({})", formatted_fields.join(", "))?;
+                }
+                Ok(())
+            }
+            Value::VariantConstructor(constructor) => write!(
+                f,
+                "<fn {}::{}>",
+                constructor.decl.name, constructor.decl.variants[constructor.variant].name
+            ),
+            Value::Trait(decl) => write!(f, "trait {}", decl.name),
+            Value::Range(range) => write!(f, "{}", range),
+            Value::Iterator(_) => write!(f, "<iterator>"),
+        }
+    }
+}
+
+// Values can refer to themselves through closures and struct fields, so they're never printed
+// recursively
+impl Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Array(_) | Value::Map(_) | Value::Instance(_) => {
+                write!(f, "Value::{}", self.type_name())
+            }
+            _ => write!(f, "Value::{}({})", self.type_name(), self),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FunctionKind {
+    Script,
+    Function,
+    /// Methods keep the struct instance they're called on in their first slot, and also take it
+    /// as their first argument when that parameter is of a user type
+    Method {
+        takes_receiver: bool,
+    },
+}
+
+/// Where a closure finds one of the variables it captures when it's created: a local slot of
+/// the enclosing function, or one of the enclosing function's own upvalues
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UpvalueDescriptor {
+    pub is_local: bool,
+    pub index: u16,
+}
+
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    pub kind: FunctionKind,
+    /// The number of arguments a call passes, not counting the receiver of a method
+    pub arity: usize,
+    /// The number of local slots a call needs, including its parameters
+    pub slot_count: usize,
+    pub upvalues: Vec<UpvalueDescriptor>,
+    pub chunk: Chunk,
+}
+
+/// A captured variable. It points at a local slot while the slot's scope is alive, and holds
+/// the variable itself once the scope ends
+#[derive(Debug)]
+pub enum Upvalue {
+    Open(usize),
+    Closed(Value),
+}
+
+pub struct Closure {
+    pub function: Rc<Function>,
+    pub upvalues: Vec<Rc<RefCell<Upvalue>>>,
+}
+
+impl Debug for Closure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<fn {}>", self.function.name)
+    }
+}
+
+/// A method looked up on a struct instance, calling it passes the instance along
+#[derive(Debug)]
+pub struct BoundMethod {
+    pub receiver: Value,
+    pub method: Rc<Closure>,
+}
+
+/// The items a `for` loop has yet to go through
+#[derive(Debug)]
+pub enum Items {
+    /// The elements of an array as they were when the loop started, or the chars of a string
+    Elements(std::vec::IntoIter<Value>),
+    Range {
+        next: i128,
+        end: i128,
+        numeric_type: NumericType,
+    },
+}
+
+impl Items {
+    pub fn next_item(&mut self) -> Option<Value> {
+        match self {
+            Items::Elements(elements) => elements.next(),
+            Items::Range {
+                next,
+                end,
+                numeric_type,
+            } => {
+                if *next >= *end {
+                    return None;
+                }
+                let item = Value::convert_integer(*next, *numeric_type);
+                *next += 1;
+                Some(item)
+            }
+        }
+    }
+}
+
+/// A key of a map, hashed and compared the way `==` compares values except that struct
+/// instances are compared by their fields
+#[derive(Clone, Debug)]
+pub struct MapKey(Value);
+
+impl MapKey {
+    pub fn new(key: &Value) -> MapKey {
+        MapKey(key.clone())
+    }
+
+    /// The key an entry is stored under. It copies the instances and arrays in `key`, so changing
+    /// them later doesn't move the entry
+    pub fn stored(key: &Value) -> MapKey {
+        MapKey(MapKey::copy(key))
+    }
+
+    fn copy(value: &Value) -> Value {
+        match value {
+            Value::Instance(instance) => {
+                let instance = instance.borrow();
+                Value::Instance(Rc::new(RefCell::new(Instance {
+                    decl: Rc::clone(&instance.decl),
+                    fields: instance.fields.iter().map(MapKey::copy).collect(),
+                })))
+            }
+            Value::Array(elements) => Value::Array(Rc::new(RefCell::new(
+                elements.borrow().iter().map(MapKey::copy).collect(),
+            ))),
+            Value::Tuple(elements) => Value::Tuple(elements.iter().map(MapKey::copy).collect()),
+            Value::EnumVariant(variant) => Value::EnumVariant(Rc::new(EnumVariant {
+                enum_name: Rc::clone(&variant.enum_name),
+                variant: Rc::clone(&variant.variant),
+                discriminant: variant.discriminant,
+                fields: variant.fields.iter().map(MapKey::copy).collect(),
+            })),
+            value => value.clone(),
+        }
+    }
+}
+
+impl PartialEq for MapKey {
+    fn eq(&self, other: &MapKey) -> bool {
+        self.0.key_equals(&other.0)
+    }
+}
+
+impl Eq for MapKey {}
+
+impl Hash for MapKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash_into(state);
+    }
+}
+
+/// The entries of a map, which are looked up by the `MapKey` of their keys
+pub type MapValue = OrderedMap<MapKey, Value, Value>;
+
+#[derive(Debug)]
+pub struct BoundMapMethod {
+    pub map: Rc<RefCell<MapValue>>,
+    pub method: MapMethod,
+}
+
+#[derive(Debug)]
+pub struct StructDecl {
+    pub name: String,
+    pub fields: Vec<String>,
+    pub methods: RefCell<HashMap<String, Rc<Closure>>>,
+}
+
+impl StructDecl {
+    pub fn new(name: String, fields: Vec<String>) -> StructDecl {
+        StructDecl {
+            name,
+            fields,
+            methods: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|field| field == name)
+    }
+}
+
+#[derive(Debug)]
+pub struct Instance {
+    pub decl: Rc<StructDecl>,
+    /// Field values, in the order the struct declares them
+    pub fields: Vec<Value>,
+}
+
+#[derive(Debug)]
+pub struct VariantDecl {
+    pub name: Rc<str>,
+    pub discriminant: i64,
+    pub arity: usize,
+}
+
+#[derive(Debug)]
+pub struct EnumDecl {
+    pub name: Rc<str>,
+    pub variants: Vec<VariantDecl>,
+}
+
+impl EnumDecl {
+    /// Returns the variant `name`, or a constructor for it if it carries a payload
+    pub fn get_variant(self: &Rc<EnumDecl>, name: &str) -> Option<Value> {
+        let index = self
+            .variants
+            .iter()
+            .position(|variant| &*variant.name == name)?;
+        let variant = &self.variants[index];
+        if variant.arity == 0 {
+            Some(Value::EnumVariant(Rc::new(EnumVariant {
+                enum_name: self.name.clone(),
+                variant: variant.name.clone(),
+                discriminant: variant.discriminant,
+                fields: vec![],
+            })))
+        } else {
+            Some(Value::VariantConstructor(Rc::new(VariantConstructor {
+                decl: self.clone(),
+                variant: index,
+            })))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct EnumVariant {
+    pub enum_name: Rc<str>,
+    pub variant: Rc<str>,
+    pub discriminant: i64,
+    pub fields: Vec<Value>,
+}
+
+#[derive(Debug)]
+pub struct VariantConstructor {
+    pub decl: Rc<EnumDecl>,
+    pub variant: usize,
+}
+
+#[derive(Debug)]
+pub struct TraitDecl {
+    pub name: String,
+}
+
+/// A match arm's pattern, lowered from [`crate::ast::expr::Pattern`] so literals are VM values
+#[derive(Debug)]
+pub enum MatchPattern {
+    Wildcard,
+    Binding,
+    Literal(Value),
+    Variant {
+        enum_name: String,
+        variant: String,
+        fields: Vec<MatchPattern>,
+    },
+}
+
+impl MatchPattern {
+    /// Tests `value`, pushing the values bound by the pattern onto `bindings` in the order the
+    /// pattern declares them
+    pub fn matches(&self, value: &Value, bindings: &mut Vec<Value>) -> bool {
+        match self {
+            MatchPattern::Wildcard => true,
+            MatchPattern::Binding => {
+                bindings.push(value.clone());
+                true
+            }
+            MatchPattern::Literal(literal) => value.equals(literal),
+            MatchPattern::Variant {
+                enum_name,
+                variant,
+                fields,
+            } => match value {
+                Value::EnumVariant(value)
+                    if *value.enum_name == **enum_name
+                        && *value.variant == **variant
+                        && value.fields.len() == fields.len() =>
+                {
+                    fields
+                        .iter()
+                        .zip(value.fields.iter())
+                        .all(|(pattern, field)| pattern.matches(field, bindings))
+                }
+                _ => false,
+            },
+        }
+    }
+}
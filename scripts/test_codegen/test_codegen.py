@@ -51,6 +51,15 @@ class LangTestSources(object):
         let b: char = 'd';
         assert(a <= b);
         """,
+        "char empty literal failure": """
+        let a: char = '';
+        """,
+        "char literal of several characters failure": """
+        let a: char = 'ab';
+        """,
+        "expression missing at the end of the source failure": """
+        let a: i32 = 1 +
+        """,
         # i64
         "i64 equal": """
         let a: i64 = 100;
@@ -304,6 +313,230 @@ class LangTestSources(object):
         let instance: TestStruct = TestStruct();
         instance.hello();
         """,
+        # Comments
+        "Comments": """
+        // A line comment
+        /// A doc comment
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b; // trailing comment
+        }
+        /* A block comment
+           spanning lines /* with a nested comment */
+        */
+        /** A block doc comment */
+        let sum: i32 = add(1, /* inline */ 2);
+        assert(sum == 3);
+        """,
+        "Unterminated block comment failure": """
+        /* never /* closed */
+        let a: i32 = 1;
+        """,
+        # Enums
+        "Enum declaration": """
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+        """,
+        "Enum variant comparison": """
+        enum Color {
+            Red,
+            Green,
+            Blue
+        }
+        let color: Color = Color::Red;
+        assert(color == Color::Red);
+        assert(color != Color::Green);
+        color = Color::Blue;
+        assert(color == Color::Blue);
+        print color;
+        """,
+        "Enum variant with initializer": """
+        enum Status {
+            Ok = 200,
+            NotFound = 404,
+            Teapot
+        }
+        assert(Status::Ok < Status::NotFound);
+        assert(Status::NotFound < Status::Teapot);
+        """,
+        "Enum as function argument and return type": """
+        enum Color {
+            Red,
+            Blue
+        }
+        fn is_red(color: Color) -> bool {
+            return color == Color::Red;
+        }
+        fn favourite() -> Color {
+            return Color::Blue;
+        }
+        assert(is_red(Color::Red));
+        assert(!is_red(favourite()));
+        """,
+        "Enum undefined variant failure": """
+        enum Color {
+            Red
+        }
+        let color: Color = Color::Purple;
+        """,
+        "Enum duplicate variant failure": """
+        enum Color {
+            Red,
+            Red
+        }
+        """,
+        "Enum type mismatch failure": """
+        enum Color {
+            Red
+        }
+        let color: Color = 1;
+        """,
+        "Enum variant with values": """
+        enum Shape {
+            Circle(f64),
+            Rect(i32, i32),
+            Empty
+        }
+        let rect: Shape = Shape::Rect(2, 3);
+        assert(rect == Shape::Rect(2, 3));
+        assert(rect != Shape::Rect(3, 2));
+        assert(rect != Shape::Empty);
+        print rect;
+        """,
+        "Enum variant with values wrong type failure": """
+        enum Shape {
+            Circle(f64)
+        }
+        let circle: Shape = Shape::Circle(\\"round\\");
+        """,
+        "Enum variant with values arity failure": """
+        enum Shape {
+            Rect(i32, i32)
+        }
+        let rect: Shape = Shape::Rect(1);
+        """,
+        # Match
+        "Match expression on enum variants": """
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32),
+            Empty
+        }
+        fn size(shape: Shape) -> i32 {
+            return match shape {
+                Shape::Circle(r) => r + r,
+                Shape::Rect(w, h) => w + h,
+                Shape::Empty => 0,
+            };
+        }
+        assert(size(Shape::Circle(2)) == 4);
+        assert(size(Shape::Rect(2, 5)) == 7);
+        assert(size(Shape::Empty) == 0);
+        """,
+        "Match statement with block arms": """
+        enum Color {
+            Red,
+            Green,
+            Blue
+        }
+        let color: Color = Color::Green;
+        let seen: i32 = 0;
+        match color {
+            Color::Red => {
+                seen = 1;
+            }
+            Color::Green => {
+                seen = 2;
+            }
+            Color::Blue => {
+                seen = 3;
+            }
+        }
+        assert(seen == 2);
+        """,
+        "Match wildcard and literal patterns": """
+        let n: i32 = 3;
+        let name: String = match n {
+            1 => \\"one\\",
+            3 => \\"three\\",
+            _ => \\"many\\",
+        };
+        assert(name == \\"three\\");
+        let flag: bool = false;
+        let as_int: i32 = match flag {
+            true => 1,
+            false => 0,
+        };
+        assert(as_int == 0);
+        """,
+        "Match nested and binding patterns": """
+        enum Inner {
+            A,
+            B(i32)
+        }
+        enum Outer {
+            Wrap(Inner),
+            Nothing
+        }
+        let value: Outer = Outer::Wrap(Inner::B(7));
+        let result: i32 = match value {
+            Outer::Wrap(Inner::B(x)) => x,
+            Outer::Wrap(_) => 1,
+            other => 0,
+        };
+        assert(result == 7);
+        """,
+        "Match missing variant failure": """
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        match shape {
+            Shape::Circle(r) => {
+                print r;
+            }
+        }
+        """,
+        "Match refutable field pattern failure": """
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        let area: i32 = match shape {
+            Shape::Circle(r) => r,
+            Shape::Rect(1, h) => h,
+        };
+        """,
+        "Match literal without wildcard failure": """
+        let n: i32 = 3;
+        let name: String = match n {
+            1 => \\"one\\",
+            3 => \\"three\\",
+        };
+        """,
+        "Match undefined variant failure": """
+        enum Color {
+            Red
+        }
+        let color: Color = Color::Red;
+        let n: i32 = match color {
+            Color::Red => 1,
+            Color::Purple => 2,
+        };
+        """,
+        "Match pattern arity failure": """
+        enum Shape {
+            Circle(i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        let n: i32 = match shape {
+            Shape::Circle(a, b) => a,
+        };
+        """,
         # Control Flow
         "For loop": """
         for (let i: i32 = 0; i < 10; i = i + 1) {
@@ -375,65 +608,2006 @@ class LangTestSources(object):
         }
         assert(false == test());
         """,
-    })
-
-    def generate_files(self, path):
-        output_dir = Path(path)
-        if not output_dir.exists():
-            print("Creating directory '{}'".format(output_dir))
-            os.mkdir(output_dir)
-        for key, value in self.VARIABLE_DECLARATIONS.items():
-            source_file_path = output_dir / \
-                (slugify(key, separator="_") + ".lang")
-            with open(source_file_path, 'w') as source_file:
-                source_file.write(self.FILE_GENERATION_COMMENT + '\n')
-                source_file.write(value)
-                print("Wrote file '{}'".format(source_file_path))
-
-    def print_lang_benches(self):
-        """Prints benches for running at the top-most interface of the interpreter"""
-        print(self.FILE_GENERATION_COMMENT)
-        print("""
-        #[macro_use]
-        extern crate criterion;
-        extern crate lang;
-
-        use criterion::{black_box, Criterion};
-        use self::lang::lang::Lang;
-        """)
-        slugged_keys = []
-        for key, value in self.VARIABLE_DECLARATIONS.items():
-            key_slug = slugify(key, separator="_")
-            slugged_keys.append(key_slug)
-            print("fn {}(c: &mut Criterion) {{".format(
-                key_slug))
-            print("c.bench_function(\"{}\", |b| {{".format(key))
-            print("b.iter(|| Lang::new(black_box(Some(\"{}\"))))".format(value))
-            print("});}")
-        criterion_string = ", ".join(slugged_keys)
-        print("criterion_group!(lang_benches, {});".format(criterion_string))
-        print("criterion_main!(lang_benches);")
-
-    def print_tests(self):
-        source = """
-        #[cfg(test)]
-        mod tests {
-        extern crate lang;
-        use self::lang::lang::Lang;
-        """
-        print(self.FILE_GENERATION_COMMENT)
-        print(source)
-        for key, value in self.VARIABLE_DECLARATIONS.items():
-            should_fail = key.endswith("failure")
-            print("#[test]")
-            print("fn {}() {{".format(slugify(key, separator="_")))
-            print("let mut lang = Lang::new(Some(\"{}\"));".format(value))
-            print("let result = lang.run();")
-            print(
-                "if let Err(ref error) = result { println!(\"{}\", error); }")
-            print("assert_eq!(result.is_ok(), {}) }}".format(
-                "false" if should_fail else "true"))
-        print("}")
+        # Traits
+        "Trait implementation": """
+        struct Point {
+            x: i32,
+        }
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+            fn double(a: i32) -> i32 {
+                return a + a;
+            }
+        }
+        let point: Point = Point();
+        let doubled: i32 = point.double(3);
+        assert(doubled == 6);
+        """,
+        # Type checking
+        "Type check binary operator failure": """
+        let a: i32 = 1 + true;
+        """,
+        "Type check unexecuted call argument failure": """
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b;
+        }
+        fn never_called() -> i32 {
+            return add(1, 2.0);
+        }
+        """,
+        "Type check unexecuted call arity failure": """
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b;
+        }
+        fn never_called() -> i32 {
+            return add(1);
+        }
+        """,
+        "Type check unexecuted return failure": """
+        fn never_called() -> i32 {
+            return \\"not a number\\";
+        }
+        """,
+        "Type check unexecuted field access failure": """
+        struct Point {
+            x: i32,
+        }
+        fn never_called(point: Point) -> i32 {
+            return point.y;
+        }
+        """,
+        "Type check unexecuted branch failure": """
+        let a: i32 = 0;
+        if (false) {
+            a = \\"unreachable\\";
+        }
+        """,
+        "Type check trait impl return type failure": """
+        struct Point {}
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+            fn double(a: i32) -> bool {
+                return true;
+            }
+        }
+        """,
+        "Type check trait impl missing function failure": """
+        struct Point {}
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+        }
+        """,
+        "Let type inference": """
+        struct Point {
+            x: i32,
+        }
+        fn double(a: i32) -> i32 {
+            return a + a;
+        }
+        let a = 1;
+        let b = 2.5;
+        let s = \\"hi\\";
+        let c = 'c';
+        let t = true;
+        let d = double(a);
+        let p = Point();
+        p.x = 3;
+        let arr = [1, 2, 3];
+        a = 5;
+        assert(a == 5);
+        assert(b == 2.5);
+        assert(s == \\"hi\\");
+        assert(c == 'c');
+        assert(t);
+        assert(d == 2);
+        assert(p.x == 3);
+        assert(arr[2] == 3);
+        let q: Point = p;
+        let copy: Array<i32> = arr;
+        """,
+        "Let type inference reassignment failure": """
+        let a = 1;
+        a = \\"not a number\\";
+        """,
+        "Let type inference empty array failure": """
+        let a = [];
+        """,
+        "Let without type or initializer failure": """
+        let a;
+        """,
+        "Closure returned from a function": """
+        fn make_adder(n: i32) -> fn(i32) -> i32 {
+            fn add(x: i32) -> i32 {
+                return x + n;
+            }
+            return add;
+        }
+        fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+            return f(x);
+        }
+        let add5 = make_adder(5);
+        let add10: fn(i32) -> i32 = make_adder(10);
+        assert(add5(3) == 8);
+        assert(add10(3) == 13);
+        assert(apply(add5, 1) == 6);
+        assert(make_adder(1)(1) == 2);
+        """,
+        "Closure outlives its block": """
+        fn zero() -> i32 {
+            return 0;
+        }
+        let f: fn() -> i32 = zero;
+        {
+            let hidden: i32 = 7;
+            fn reveal() -> i32 {
+                return hidden;
+            }
+            f = reveal;
+        }
+        assert(f() == 7);
+        """,
+        "Closure sees the binding in scope at its declaration": """
+        let a: i32 = 1;
+        {
+            fn show() -> i32 {
+                return a;
+            }
+            assert(show() == 1);
+            let a: i32 = 2;
+            assert(show() == 1);
+            assert(a == 2);
+        }
+        """,
+        "Shadowed variables in nested blocks": """
+        let a: i32 = 1;
+        let b: i32 = 0;
+        {
+            b = 7;
+            let a: i32 = 2;
+            let b: i32 = 9;
+            {
+                let a: i32 = 3;
+                assert(a == 3);
+                b = 10;
+            }
+            assert(a == 2);
+            assert(b == 10);
+        }
+        assert(a == 1);
+        assert(b == 7);
+        """,
+        "Redeclaring a variable in the same scope makes a new one": """
+        fn twice_plus_one(x: i32) -> i32 {
+            let x: i32 = x * 2;
+            let x: i32 = x + 1;
+            return x;
+        }
+        assert(twice_plus_one(3) == 7);
+        {
+            let a: i32 = 1;
+            fn show() -> i32 {
+                return a;
+            }
+            let a: String = \\"a\\";
+            assert(show() == 1);
+            assert(a == \\"a\\");
+        }
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 3) {
+            let step: i32 = i;
+            let step: i32 = step + 10;
+            total = total + step;
+            i = i + 1;
+        }
+        assert(total == 33);
+        """,
+        "Undefined variable failure": """
+        let a: i32 = 1;
+        print a;
+        print missing;
+        """,
+        "Undefined function failure": """
+        fn defined() -> i32 {
+            return missing(1);
+        }
+        """,
+        "Closures created in a long loop": """
+        fn make_adder(n: i32) -> fn(i32) -> i32 {
+            fn add(x: i32) -> i32 {
+                return x + n;
+            }
+            return add;
+        }
+        let first = make_adder(100);
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 500) {
+            let adder = make_adder(i);
+            total = total + adder(1);
+            i = i + 1;
+        }
+        assert(total == 125250);
+        assert(first(1) == 101);
+        """,
+        "Function type mismatch failure": """
+        fn zero() -> i32 {
+            return 0;
+        }
+        let f: fn(i32) -> bool = zero;
+        """,
+        "Function type argument failure": """
+        fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+            return f(x);
+        }
+        fn is_positive(x: i32) -> bool {
+            return x > 0;
+        }
+        apply(is_positive, 1);
+        """,
+        "Function type call argument failure": """
+        fn apply(f: fn(i32) -> i32) -> i32 {
+            return f(true);
+        }
+        """,
+        "Empty arrays of function values": """
+        fn one() -> i32 {
+            return 1;
+        }
+        fn two() -> i32 {
+            return 2;
+        }
+        let handlers: Array<fn() -> i32> = [];
+        let sum: i32 = 0;
+        for handler in handlers {
+            sum += handler();
+        }
+        assert(sum == 0);
+        handlers = [one, two];
+        for handler in handlers {
+            sum += handler();
+        }
+        assert(sum == 3);
+        """,
+        "Garbage collection in a long loop": """
+        struct Counter {
+            count: i32,
+        }
+        fn bump(c: Counter) -> i32 {
+            c.count = c.count + 1;
+            return c.count;
+        }
+        fn double(n: i32) -> i32 {
+            return n + n;
+        }
+        let counter: Counter = Counter();
+        counter.count = 0;
+        let names: Array<String> = [\\"a\\", \\"b\\", \\"c\\"];
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 1000) {
+            let doubled: i32 = double(i);
+            total = total + doubled;
+            bump(counter);
+            i = i + 1;
+        }
+        assert(total == 999000);
+        assert(counter.count == 1000);
+        assert(names[2] == \\"c\\");
+        """,
+        "Generic struct with a generic impl": """
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Wrapper<T> {
+            fn get(self: Wrapper<T>) -> T {
+                return self.value;
+            }
+            fn set(self: Wrapper<T>, value: T) -> () {
+                self.value = value;
+            }
+        }
+        let number: Wrapper<i32> = Wrapper { value: 0 };
+        number.set(5);
+        assert(number.get() == 5);
+        let name: Wrapper<String> = Wrapper { value: \\"\\" };
+        name.set(\\"lang\\");
+        assert(name.get() == \\"lang\\");
+        """,
+        "Generic functions": """
+        fn first<T>(a: Array<T>) -> T {
+            return a[0];
+        }
+        fn identity<T>(value: T) -> T {
+            return value;
+        }
+        let i: i32 = first([1, 2, 3]);
+        assert(i == 1);
+        let s: String = identity(\\"a\\");
+        assert(s == \\"a\\");
+        assert(identity(first([true])));
+        """,
+        "Generic function argument failure": """
+        fn pick<T>(a: T, b: T) -> T {
+            return a;
+        }
+        pick(1, \\"a\\");
+        """,
+        "Generic function return type failure": """
+        fn first<T>(a: Array<T>) -> T {
+            return a[0];
+        }
+        let s: String = first([1, 2]);
+        """,
+        "Function falling off its end failure": """
+        fn sign(x: i32) -> i32 {
+            if (x > 0) {
+                return 1;
+            }
+        }
+        let s: i32 = sign(-5);
+        """,
+        "Function leaving a loop without returning failure": """
+        fn first_even(a: Array<i32>) -> i32 {
+            while (true) {
+                for x in a {
+                    if (x % 2 == 0) {
+                        return x;
+                    }
+                }
+                break;
+            }
+        }
+        """,
+        "Functions returning on every path": """
+        enum Sign {
+            Negative,
+            Zero,
+            Positive
+        }
+        fn sign(x: i32) -> Sign {
+            if (x > 0) {
+                return Sign::Positive;
+            } else if (x < 0) {
+                return Sign::Negative;
+            } else {
+                return Sign::Zero;
+            }
+        }
+        fn value(s: Sign) -> i32 {
+            match s {
+                Sign::Negative => {
+                    return -1;
+                }
+                Sign::Zero => {
+                    return 0;
+                }
+                Sign::Positive => {
+                    return 1;
+                }
+            }
+        }
+        fn first_over(a: Array<i32>, limit: i32) -> i32 {
+            loop {
+                for x in a {
+                    if (x > limit) {
+                        return x;
+                    }
+                    break;
+                }
+            }
+        }
+        fn countdown(n: i32) -> i32 {
+            while (true) {
+                if (n == 0) {
+                    return n;
+                }
+                n -= 1;
+            }
+        }
+        assert(value(sign(-5)) == -1);
+        assert(value(sign(0)) == 0);
+        assert(first_over([7, 2], 5) == 7);
+        assert(countdown(3) == 0);
+        """,
+        "Generic struct type argument count failure": """
+        struct Wrapper<T> {
+            value: T,
+        }
+        let w: Wrapper<i32, bool> = Wrapper { value: 1 };
+        """,
+        "Generic struct field type failure": """
+        struct Wrapper<T> {
+            value: T,
+        }
+        let w: Wrapper<i32> = Wrapper { value: 1 };
+        w.value = true;
+        """,
+        "Generic type parameter used as a concrete type failure": """
+        fn to_int<T>(a: T) -> i32 {
+            return a;
+        }
+        """,
+        "Map literals, indexing and methods": """
+        let scores: Map<String, i32> = [\\"alice\\": 3, \\"bob\\": 5];
+        assert(scores[\\"bob\\"] == 5);
+        scores[\\"carol\\"] = 7;
+        scores.insert(\\"alice\\", 4);
+        assert(scores[\\"alice\\"] == 4);
+        assert(scores.len() == 3);
+        assert(scores.contains(\\"bob\\"));
+        assert(scores.remove(\\"bob\\"));
+        assert(!scores.remove(\\"bob\\"));
+        assert(!scores.contains(\\"bob\\"));
+        let empty: Map<i64, bool> = [:];
+        assert(empty.len() == 0);
+        let copy: Map<String, i32> = [\\"carol\\": 7, \\"alice\\": 4];
+        assert(scores == copy);
+        """,
+        "Map keys of each hashable type": """
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        enum Color { Red, Green }
+        let ids = [1: \\"one\\", 2: \\"two\\"];
+        assert(ids[2] == \\"two\\");
+        let letters = ['a': 1, 'b': 2];
+        assert(letters['b'] == 2);
+        let flags = [true: \\"yes\\", false: \\"no\\"];
+        assert(flags[1 == 1] == \\"yes\\");
+        let p = Point { x: 1, y: 2 };
+        let q = Point { x: 2, y: 1 };
+        let names: Map<Point, String> = [p: \\"p\\"];
+        names[q] = \\"q\\";
+        let r = p;
+        assert(names[r] == \\"p\\");
+        assert(names.len() == 2);
+        let colors = [Color::Red: 1];
+        colors[Color::Green] = 2;
+        assert(colors[Color::Green] == 2);
+        """,
+        "Map iteration": """
+        let scores = [\\"a\\": 1, \\"b\\": 2, \\"c\\": 3];
+        scores.remove(\\"b\\");
+        let keys = scores.keys();
+        let values = scores.values();
+        let total = 0;
+        let i = 0;
+        while (i < scores.len()) {
+            assert(scores[keys[i]] == values[i]);
+            total = total + values[i];
+            i = i + 1;
+        }
+        assert(total == 4);
+        assert(keys[1] == \\"c\\");
+        """,
+        "Map float key failure": """
+        let m = [1.5: 2];
+        """,
+        "Map entry type failure": """
+        let m = [\\"a\\": 1, \\"b\\": true];
+        """,
+        "Map index key type failure": """
+        let m = [\\"a\\": 1];
+        print m[1];
+        """,
+        "Map insert value type failure": """
+        let m = [\\"a\\": 1];
+        m.insert(\\"b\\", \\"c\\");
+        """,
+        "Map missing key failure": """
+        let m = [\\"a\\": 1];
+        print m[\\"b\\"];
+        """,
+        "Map struct keys compare by their fields": """
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        struct Segment {
+            from: Point,
+            to: Point
+        }
+        let m: Map<Point, i32> = [Point { x: 1, y: 2 }: 5];
+        assert(m[Point { x: 1, y: 2 }] == 5);
+        assert(m.contains(Point { x: 1, y: 2 }));
+        assert(!m.contains(Point { x: 2, y: 1 }));
+        m[Point { x: 1, y: 2 }] = 6;
+        assert(m.len() == 1);
+        assert(m[Point { x: 1, y: 2 }] == 6);
+        let p = Point { x: 3, y: 4 };
+        m[p] = 7;
+        p.x = 1;
+        assert(m[Point { x: 3, y: 4 }] == 7);
+        let lengths: Map<Segment, i32> = [Segment { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }: 5];
+        assert(lengths[Segment { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }] == 5);
+        let pairs: Map<(Point, i32), bool> = [(Point { x: 1, y: 1 }, 1): true];
+        assert(pairs[(Point { x: 1, y: 1 }, 1)]);
+        assert(m.remove(Point { x: 1, y: 2 }));
+        assert(m.len() == 1);
+        """,
+        "Array negative index failure": """
+        let a = [1, 2];
+        let i = -1;
+        print a[i];
+        """,
+        "Array negative index assignment failure": """
+        let a = [1, 2];
+        let i = -1;
+        a[i] = 3;
+        """,
+        "Struct formats as its name": """
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        let p = Point { x: 1, y: 2 };
+        assert(f\\"{p}\\" == \\"Point\\");
+        assert(f\\"{p:?}\\" == \\"Point { x: 1, y: 2 }\\");
+        """,
+        "Map missing struct key failure": """
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        let m: Map<Point, i32> = [Point { x: 1, y: 2 }: 5];
+        print m[Point { x: 2, y: 1 }];
+        """,
+        "Map empty literal without a type failure": """
+        let m = [:];
+        """,
+        "Tuple literals and fields": """
+        fn min_max(a: i32, b: i32) -> (i32, i32) {
+            if (a < b) {
+                return (a, b);
+            }
+            return (b, a);
+        }
+        let bounds = min_max(7, 3);
+        assert(bounds.0 == 3);
+        assert(bounds.1 == 7);
+        let nested = ((1, 2), \\"three\\");
+        assert(nested.0.1 == 2);
+        assert(nested.1 == \\"three\\");
+        assert((1, \\"a\\") == (1, \\"a\\"));
+        assert((1, \\"a\\") != (1, \\"b\\"));
+        let empty: (i32, bool);
+        assert(empty == (0, false));
+        """,
+        "Tuple destructuring": """
+        fn pair(name: String) -> (String, i32) {
+            return (name, 2);
+        }
+        let (name, count) = pair(\\"apples\\");
+        assert(name == \\"apples\\");
+        assert(count == 2);
+        fn swap(p: (i32, String)) -> (String, i32) {
+            let (number, text) = p;
+            return (text, number);
+        }
+        let swapped = swap((1, \\"one\\"));
+        assert(swapped.0 == \\"one\\");
+        assert(swapped.1 == 1);
+        """,
+        "Tuple map keys": """
+        let grid = [(0, 0): \\"origin\\", (1, 2): \\"point\\"];
+        assert(grid[(1, 2)] == \\"point\\");
+        """,
+        "Tuple field out of range failure": """
+        let t = (1, 2);
+        print t.2;
+        """,
+        "Tuple destructure arity failure": """
+        let (a, b, c) = (1, 2);
+        """,
+        "Tuple return type failure": """
+        fn f() -> (i32, String) {
+            return (\\"a\\", 1);
+        }
+        """,
+        "Tuple field assignment failure": """
+        let t = (1, 2);
+        t.0 = 3;
+        """,
+        "Tuple literals widen to the declared type": """
+        fn f() -> (i64, String) {
+            return (1, \\"a\\");
+        }
+        fn g() -> (i64, (f64, u32)) {
+            (2, (1.5, 3u8))
+        }
+        let t: (i64, String) = (1, \\"a\\");
+        assert(f() == t);
+        assert(g().1.1 == 3u32);
+        let (big, text) = f();
+        let wide: i64 = big + 5000000000;
+        assert(wide == 5000000001);
+        """,
+        "Empty arrays of tuples": """
+        let pairs: Array<(i32, i32)> = [];
+        let names: Array<(String, Array<i32>)> = [];
+        let count: i32 = 0;
+        for pair in pairs {
+            count += 1;
+        }
+        for name in names {
+            count += 1;
+        }
+        assert(count == 0);
+        pairs = [(1, 2), (3, 4)];
+        assert(pairs[1].0 == 3);
+        """,
+        "Array literal element type failure": """
+        let a: Array<i64> = [\\"x\\"];
+        """,
+        "Option and Result constructors": """
+        let some: Option<i32> = Some(3);
+        let none: Option<i32> = None;
+        assert(some == Option::Some(3));
+        assert(some != none);
+        let ok: Result<i32, String> = Ok(1);
+        let err: Result<i32, String> = Err(\\"failed\\");
+        assert(ok != err);
+        let found = match some {
+            Some(n) => n + 1,
+            None => 0,
+        };
+        assert(found == 4);
+        let message = match err {
+            Ok(_) => \\"fine\\",
+            Err(reason) => reason,
+        };
+        assert(message == \\"failed\\");
+        """,
+        "Uninitialized enum variable failure": """
+        let c: Option<i32>;
+        """,
+        "Uninitialized function variable failure": """
+        let f: fn(i32) -> i32;
+        """,
+        "Uninitialized char variable failure": """
+        let c: char;
+        """,
+        "Uninitialized struct variable failure": """
+        struct Point {
+            x: i32
+        }
+        let p: Point;
+        """,
+        "Option propagation": """
+        fn first_even(a: i32, b: i32) -> Option<i32> {
+            if (a == 2) {
+                return Some(a);
+            }
+            if (b == 4) {
+                return Some(b);
+            }
+            return None;
+        }
+        fn plus_one(a: i32, b: i32) -> Option<i32> {
+            let n = first_even(a, b)?;
+            return Some(n + 1);
+        }
+        assert(plus_one(2, 1) == Some(3));
+        assert(plus_one(1, 1) == None);
+        """,
+        "Result propagation": """
+        fn parse(text: String) -> Result<i32, String> {
+            if (text == \\"one\\") {
+                return Ok(1);
+            }
+            return Err(\\"not a number\\");
+        }
+        fn sum(a: String, b: String) -> Result<i32, String> {
+            return Ok(parse(a)? + parse(b)?);
+        }
+        assert(sum(\\"one\\", \\"one\\") == Ok(2));
+        assert(sum(\\"one\\", \\"two\\") == Err(\\"not a number\\"));
+        """,
+        "Try outside a function failure": """
+        let a: Option<i32> = Some(1);
+        let b = a?;
+        """,
+        "Try on a non-option failure": """
+        fn f(n: i32) -> Option<i32> {
+            return Some(n?);
+        }
+        """,
+        "Try in a function not returning an option failure": """
+        fn f(a: Option<i32>) -> i32 {
+            return a?;
+        }
+        """,
+        "Try with a different error type failure": """
+        fn f(a: Result<i32, String>) -> Result<i32, bool> {
+            return Ok(a?);
+        }
+        """,
+        "Option payload type failure": """
+        let a: Option<i32> = Some(\\"x\\");
+        """,
+        "Try on a variant of a different enum failure": """
+        fn first() -> Option<i32> {
+            let value: i32 = Err(1)?;
+            return Some(value);
+        }
+        """,
+        "Infer a variable from None failure": """
+        let a = None;
+        """,
+        "Infer a variable from Err failure": """
+        let a = Err(3);
+        """,
+        "Generic variants with annotations": """
+        let a: Option<String> = None;
+        a = Some(\\"x\\");
+        let b: Result<i32, i32> = Err(3);
+        let c = Some(1);
+        assert(a == Some(\\"x\\"));
+        assert(b == Err(3));
+        assert(c == Some(1));
+        """,
+        "Struct literals": """
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        fn origin() -> Point {
+            return Point { x: 0, y: 0 };
+        }
+        let x = 3;
+        let p = Point { y: 4, x: x };
+        assert(p.x == 3);
+        assert(p.y == 4);
+        let o: Point = origin();
+        assert(o.x == 0);
+        o.x = 1;
+        assert(o.x == 1);
+        """,
+        "Struct literal field defaults": """
+        struct Config {
+            name: String,
+            retries: i32 = 3,
+            verbose: bool = false,
+        }
+        let config = Config { name: \\"default\\" };
+        assert(config.retries == 3);
+        assert(config.verbose == false);
+        let custom = Config { verbose: true, name: \\"custom\\", retries: 5 };
+        assert(custom.retries == 5);
+        assert(custom.verbose);
+        """,
+        "Generic struct literal": """
+        struct Pair<T> {
+            first: T,
+            second: T,
+        }
+        let pair: Pair<String> = Pair { first: \\"a\\", second: \\"b\\" };
+        assert(pair.first == \\"a\\");
+        assert(pair.second == \\"b\\");
+        """,
+        "Struct literal empty collection fields": """
+        fn empty() -> Inventory {
+            return Inventory { items: [], counts: [:] };
+        }
+        struct Inventory {
+            items: Array<String>,
+            counts: Map<String, i32>,
+        }
+        let inventory = Inventory { items: [], counts: [:] };
+        inventory.items = [\\"apple\\"];
+        let items = inventory.items;
+        assert(items[0] == \\"apple\\");
+        let counts = empty().counts;
+        counts[\\"apple\\"] = 2;
+        assert(counts[\\"apple\\"] == 2);
+        """,
+        "Generic struct literal empty array field": """
+        struct Stack<T> {
+            items: Array<T>,
+        }
+        let stack: Stack<i32> = Stack { items: [] };
+        stack.items = [1, 2];
+        let items = stack.items;
+        assert(items[1] == 2);
+        """,
+        "Struct literal missing field failure": """
+        struct Point {
+            x: i32,
+            y: i32 = 0,
+        }
+        let p = Point { y: 1 };
+        """,
+        "Struct literal unknown field failure": """
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: 1, z: 2 };
+        """,
+        "Struct literal field type failure": """
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: true };
+        """,
+        "Struct literal duplicate field failure": """
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: 1, x: 2 };
+        """,
+        "Struct field default type failure": """
+        struct Point {
+            x: i32 = \\"zero\\",
+        }
+        """,
+        "Struct constructor call field defaults": """
+        struct Point {
+            x: i32 = 7,
+            y: i32,
+            label: String,
+        }
+        let p = Point();
+        assert(p.x == 7);
+        assert(p.y == 0);
+        assert(p.label == \\"\\");
+        let q = Point();
+        q.x = 1;
+        assert(p.x == 7);
+        """,
+        "Struct constructor call missing field failure": """
+        struct Inner {
+            x: i32,
+        }
+        struct Outer {
+            inner: Inner,
+        }
+        let o = Outer();
+        """,
+        "Struct constructor call arguments failure": """
+        struct Point {
+            x: i32,
+        }
+        let p = Point(1);
+        """,
+        # Operator overloading
+        "Overloaded arithmetic operators": """
+        struct Vec2 {
+            x: i32,
+            y: i32,
+        }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x, y: self.y + other.y };
+            }
+        }
+        impl Sub for Vec2 {
+            fn sub(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x + 1, y: self.y + other.y + 1 };
+            }
+        }
+        let a = Vec2 { x: 1, y: 2 };
+        let b = Vec2 { x: 3, y: 4 };
+        let c: Vec2 = a + b;
+        assert(c.x == 4);
+        assert(c.y == 6);
+        let d: Vec2 = a + b + c;
+        assert(d.x == 8);
+        let e: Vec2 = a - b;
+        assert(e.y == 7);
+        """,
+        "Overloaded comparison operators": """
+        struct Version {
+            major: i32,
+            minor: i32,
+        }
+        impl Eq for Version {
+            fn eq(self: Version, other: Version) -> bool {
+                return self.major == other.major and self.minor == other.minor;
+            }
+        }
+        impl Ord for Version {
+            fn cmp(self: Version, other: Version) -> i32 {
+                if (self.major != other.major) {
+                    if (self.major < other.major) {
+                        return -1;
+                    }
+                    return 1;
+                }
+                if (self.minor < other.minor) {
+                    return -1;
+                }
+                if (self.minor > other.minor) {
+                    return 1;
+                }
+                return 0;
+            }
+        }
+        let old = Version { major: 1, minor: 4 };
+        let new = Version { major: 2, minor: 0 };
+        assert(old == Version { major: 1, minor: 4 });
+        assert(old != new);
+        assert(old < new);
+        assert(old <= old);
+        assert(new > old);
+        assert(new >= old);
+        assert(false == (new < old));
+        """,
+        "Overloaded index operator": """
+        struct Polynomial {
+            degree: i32,
+            coefficients: Array<i32>,
+        }
+        impl Index<i32, i32> for Polynomial {
+            fn index(self: Polynomial, power: i32) -> i32 {
+                if (power > self.degree) {
+                    return 0;
+                }
+                let coefficients = self.coefficients;
+                return coefficients[power];
+            }
+        }
+        let p = Polynomial { degree: 2, coefficients: [3, 0, 2] };
+        let constant: i32 = p[0];
+        assert(constant == 3);
+        assert(p[2] == 2);
+        assert(p[7] == 0);
+        """,
+        "Overloaded operators on generic structs": """
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Eq for Wrapper<T> {
+            fn eq(self: Wrapper<T>, other: Wrapper<T>) -> bool {
+                return self.value == other.value;
+            }
+        }
+        impl<T> Index<i32, T> for Wrapper<T> {
+            fn index(self: Wrapper<T>, index: i32) -> T {
+                return self.value;
+            }
+        }
+        let a: Wrapper<String> = Wrapper { value: \\"a\\" };
+        let b: Wrapper<String> = Wrapper { value: \\"a\\" };
+        assert(a == b);
+        let value: String = a[0];
+        assert(value == \\"a\\");
+        """,
+        "Type check operator without trait impl failure": """
+        struct Vec2 {
+            x: i32,
+        }
+        let a = Vec2 { x: 1 };
+        let b: Vec2 = a + a;
+        """,
+        "Type check overloaded operator operand failure": """
+        struct Vec2 {
+            x: i32,
+        }
+        impl Mul for Vec2 {
+            fn mul(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x };
+            }
+        }
+        let a = Vec2 { x: 1 };
+        let b: Vec2 = a * 2;
+        """,
+        "Type check overloaded operator result failure": """
+        struct Vec2 {
+            x: i32,
+        }
+        impl Div for Vec2 {
+            fn div(self: Vec2, other: Vec2) -> Vec2 {
+                return self;
+            }
+        }
+        let a = Vec2 { x: 1 };
+        let b: i32 = a / a;
+        """,
+        "Type check overloaded index result failure": """
+        struct Grid {
+            x: i32,
+        }
+        impl Index<i32, bool> for Grid {
+            fn index(self: Grid, index: i32) -> bool {
+                return true;
+            }
+        }
+        let g = Grid { x: 1 };
+        let a: i32 = g[0];
+        """,
+        "Type check trait impl self type failure": """
+        struct Vec2 {
+            x: i32,
+        }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: i32) -> Vec2 {
+                return self;
+            }
+        }
+        """,
+        "Self in impls": """
+        trait Double {
+            fn double(self: Self) -> Self;
+        }
+        struct Point {
+            x: i32,
+        }
+        impl Double for Point {
+            fn double(self: Self) -> Self {
+                let doubled: Self = Point { x: self.x * 2 };
+                return doubled;
+            }
+        }
+        impl Point {
+            fn plus(self: Self, other: Self) -> Self {
+                return Point { x: self.x + other.x };
+            }
+        }
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Wrapper<T> {
+            fn same(self: Self) -> Self {
+                return self;
+            }
+        }
+        let point = Point { x: 3 };
+        let doubled = point.double();
+        assert(doubled.x == 6);
+        let sum = doubled.plus(Point { x: 1 });
+        assert(sum.x == 7);
+        let wrapper: Wrapper<i32> = Wrapper { value: 4 };
+        let same = wrapper.same();
+        assert(same.value == 4);
+        """,
+        "Self in impls type failure": """
+        struct Point {
+            x: i32,
+        }
+        struct Other {
+            x: i32,
+        }
+        impl Point {
+            fn other(self: Self) -> Self {
+                return Other { x: self.x };
+            }
+        }
+        """,
+        "Type check trait type argument count failure": """
+        struct Grid {
+            x: i32,
+        }
+        impl Index<i32> for Grid {
+            fn index(self: Grid, index: i32) -> i32 {
+                return index;
+            }
+        }
+        """,
+        "Integer arithmetic": """
+        let q: i32 = 7 / 2;
+        assert(q == 3);
+        let r: i32 = 7 - 10;
+        assert(r == -3);
+        let p: i32 = 6 * 7;
+        assert(p == 42);
+        let m: i64 = 4294967296 / 2;
+        assert(m == 2147483648);
+        """,
+        "Float arithmetic": """
+        let a: f32 = 1.5;
+        let b: f32 = a * 2.0 as f32 - 0.5 as f32;
+        assert(b == 2.5);
+        let c: f64 = 9.0;
+        let d: f64 = c / 4.0;
+        assert(d == 2.25);
+        """,
+        "Numeric promotion": """
+        let a: i32 = 2147483647;
+        let b: i64 = 1;
+        let c: i64 = a + b;
+        assert(c == 2147483648);
+        let x: f32 = 1.5;
+        let y: f64 = 2.25;
+        let z: f64 = x + y;
+        assert(z == 3.75);
+        """,
+        "Numeric casts": """
+        let x: f64 = 7 as f64;
+        assert(x == 7.0);
+        let t: i32 = 3.9 as i32;
+        assert(t == 3);
+        let n: i32 = -3.9 as i32;
+        assert(n == -3);
+        let big: i64 = 4294967297;
+        assert(big as i32 == 1);
+        assert(3000000000.0 as i32 == 2147483647);
+        assert('a' as i32 == 97);
+        assert(true as i64 == 1);
+        let half: f32 = 1 as f32 / 2 as f32;
+        assert(half == 0.5);
+        """,
+        "Integer overflow failure": """
+        let a: i32 = 2147483647;
+        let b = a + 1;
+        """,
+        "Long overflow failure": """
+        let a: i64 = 9223372036854775807;
+        let b = a * 2;
+        """,
+        "Negation overflow failure": """
+        let a: i32 = -2147483647 - 1;
+        let b = -a;
+        """,
+        "Division by zero failure": """
+        let a: i32 = 1;
+        let b = a / 0;
+        """,
+        "Type check mixed int and float failure": """
+        let a: i32 = 1;
+        let b: f64 = 2.0;
+        let c = a + b;
+        """,
+        "Type check promoted result failure": """
+        let a: i32 = 1;
+        let b: i64 = 2;
+        let c: i32 = a + b;
+        """,
+        "Type check cast to non-numeric failure": """
+        let a = 1 as bool;
+        """,
+        "Type check cast from string failure": """
+        let a = \\"1\\" as i32;
+        """,
+        "Integer widths": """
+        let a: u8 = 255;
+        let b: i8 = -128;
+        let c: i16 = -32768;
+        let d: u16 = 65535;
+        let e: u32 = 4294967295;
+        let f: u64 = 18446744073709551615;
+        let g: usize = 7;
+        assert(a == 255);
+        assert(b == -128);
+        assert(c == -32768);
+        assert(d == 65535);
+        assert(e == 4294967295);
+        assert(f == 18446744073709551615u64);
+        assert(g == 7);
+        """,
+        "Integer literal suffixes": """
+        let a = 255u8;
+        let b = 100i8 + 27i8;
+        let c: u64 = 1u64 + 2u8;
+        assert(a == 255);
+        assert(b == 127);
+        assert(c == 3);
+        let d: i16 = 200u8 + 1i16;
+        assert(d == 201);
+        """,
+        "Unsigned arithmetic": """
+        let count: u32 = 10;
+        let step: u32 = 3;
+        assert(count - step == 7u32);
+        assert(count * step == 30u32);
+        assert(count / step == 3u32);
+        let bytes: u8 = 200u8;
+        let wide: u16 = bytes + 100u16;
+        assert(wide == 300);
+        """,
+        "Integer width casts": """
+        assert(300 as u8 == 44);
+        assert(-1 as u8 == 255);
+        assert(255u8 as i8 == -1);
+        assert(-1i8 as u64 == 18446744073709551615u64);
+        assert(70000 as i16 == 4464);
+        assert(-5.7 as u8 == 0);
+        assert(300.0 as u8 == 255);
+        assert('A' as u8 == 65);
+        """,
+        "Array index with unsigned types": """
+        let values = [10, 20, 30];
+        let i: usize = 2;
+        let j: u8 = 1;
+        assert(values[i] == 30);
+        assert(values[j] == 20);
+        values[0usize] = 5;
+        assert(values[0] == 5);
+        """,
+        "Unsigned map keys": """
+        let counts: Map<u8, i32> = [:];
+        counts[1u8] = 3;
+        assert(counts[1u8] == 3);
+        """,
+        "Unsigned overflow failure": """
+        let a: u8 = 255;
+        let b = a + 1u8;
+        """,
+        "Unsigned underflow failure": """
+        let a: u32 = 0;
+        let b = a - 1u32;
+        """,
+        "Small integer overflow failure": """
+        let a: i8 = 100;
+        let b = a * 2i8;
+        """,
+        "Literal out of range failure": """
+        let a: u8 = 256;
+        """,
+        "Suffixed literal out of range failure": """
+        let a = 128i8;
+        """,
+        "Type check signed and unsigned mix failure": """
+        let a: i32 = 1;
+        let b: u32 = 2;
+        let c = a + b;
+        """,
+        "Type check narrowing assignment failure": """
+        let a: u16 = 1;
+        let b: u8 = a;
+        """,
+        "Type check unsigned negation failure": """
+        let a: u8 = 1;
+        let b = -a;
+        """,
+        "Remainder": """
+        assert(17 % 5 == 2);
+        assert(-7 % 3 == -1);
+        assert(7.5 % 2.0 == 1.5);
+        let a: u8 = 200;
+        assert(a % 7u8 == 4);
+        """,
+        "Remainder by zero failure": """
+        let a = 5;
+        let b = 0;
+        let c = a % b;
+        """,
+        "Bitwise operators": """
+        assert((6 & 3) == 2);
+        assert((6 | 3) == 7);
+        assert((6 ^ 3) == 5);
+        assert(~0 == -1);
+        let a: u8 = 5;
+        assert(~a == 250u8);
+        let b: u8 = 12;
+        let c: i64 = 10;
+        assert((b | c) == 14);
+        """,
+        "Bitwise operators on floats failure": """
+        let a = 1.0 & 2.0;
+        """,
+        "Bitwise not on floats failure": """
+        let a = ~1.5;
+        """,
+        "Shift operators": """
+        assert(1 << 4 == 16);
+        assert(-16 >> 2 == -4);
+        let a: u8 = 3;
+        assert(a << 7 == 128u8);
+        let b: u64 = 18446744073709551615;
+        assert(b >> 60u8 == 15u64);
+        let c: i64 = 1;
+        assert(c << 40u8 == 1099511627776);
+        """,
+        "Shift overflow failure": """
+        let a = 32;
+        let b = 1 << a;
+        """,
+        "Bitwise operator precedence": """
+        assert(1 + 2 << 1 == 6);
+        assert(6 & 3 == 2);
+        assert(1 | 2 ^ 3 & 4 == 3);
+        assert(2 * 3 % 4 == 2);
+        assert(true && false || true);
+        """,
+        "Compound assignment": """
+        let i = 0;
+        i += 5;
+        i -= 1;
+        i *= 3;
+        i /= 2;
+        i %= 4;
+        assert(i == 2);
+        i <<= 3;
+        i |= 1;
+        i ^= 3;
+        i &= 30;
+        i >>= 1;
+        assert(i == 9);
+        let s = \\"a\\";
+        s += \\"b\\";
+        assert(s == \\"ab\\");
+        """,
+        "Compound assignment to fields and elements": """
+        struct Counter { count: i32 }
+        let counter = Counter { count: 1 };
+        counter.count += 10;
+        counter.count <<= 1;
+        assert(counter.count == 22);
+        let values = [1, 2, 3];
+        values[1] += 40;
+        values[2] *= values[1];
+        assert(values[1] == 42);
+        assert(values[2] == 126);
+        """,
+        "Compound assignment evaluates its target once": """
+        struct Counter { count: i32 }
+        struct Calls { count: i32 }
+        let calls = Calls { count: 0 };
+        let counter = Counter { count: 1 };
+        fn target(calls: Calls, counter: Counter) -> Counter {
+            calls.count += 1;
+            return counter;
+        }
+        fn next(calls: Calls) -> i32 {
+            calls.count += 1;
+            return calls.count - 1;
+        }
+        target(calls, counter).count += 5;
+        assert(counter.count == 6);
+        assert(calls.count == 1);
+        let values = [10, 20, 30];
+        values[next(calls)] *= 2;
+        assert(values[1] == 40);
+        assert(calls.count == 2);
+        let totals: Map<String, i32> = [\\"a\\": 1];
+        totals[\\"a\\"] += 2;
+        assert(totals[\\"a\\"] == 3);
+        """,
+        "Compound assignment to a field of an overloaded type": """
+        struct Vec2 { x: i32 }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x };
+            }
+        }
+        struct Body { position: Vec2 }
+        let body = Body { position: Vec2 { x: 1 } };
+        body.position += Vec2 { x: 2 };
+        let position = body.position;
+        assert(position.x == 3);
+        """,
+        "Compound assignment element type mismatch failure": """
+        let values = [1, 2];
+        values[0] += 1.5;
+        """,
+        "Compound assignment type mismatch failure": """
+        let a = 1;
+        a += \\"x\\";
+        """,
+        "Compound assignment invalid target failure": """
+        let a = 1;
+        a + 1 += 2;
+        """,
+        "Overloaded remainder and bitwise operators": """
+        struct Bits { value: i32 }
+        impl Rem for Bits {
+            fn rem(self: Bits, other: Bits) -> Bits {
+                return Bits { value: self.value % other.value };
+            }
+        }
+        impl BitOr for Bits {
+            fn bitor(self: Bits, other: Bits) -> Bits {
+                return Bits { value: self.value | other.value };
+            }
+        }
+        let a = Bits { value: 12 };
+        let b = Bits { value: 5 };
+        assert((a % b).value == 2);
+        a |= b;
+        assert(a.value == 13);
+        """,
+        "Nested generic type arguments": """
+        let a: Option<Option<i32>> = Some(Some(1));
+        match a {
+            Some(Some(value)) => { assert(value == 1); }
+            _ => { assert(false); }
+        }
+        """,
+        "If expression": """
+        let a: bool = true;
+        let x: i32 = if (a) { 1 } else { 2 };
+        assert(x == 1);
+        let y: i32 = if (!a) { 1 } else { 2 };
+        assert(y == 2);
+        let z: i32 = if (x > y) { x } else if (x == y) { 0 } else { y * 10 };
+        assert(z == 20);
+        """,
+        "If expression branches with statements": """
+        let n: i32 = 4;
+        let description: String = if (n % 2 == 0) {
+            let half: i32 = n / 2;
+            assert(half == 2);
+            \\"even\\"
+        } else {
+            \\"odd\\"
+        };
+        assert(description == \\"even\\");
+        """,
+        "Block expression": """
+        let a: i32 = 2;
+        let b: i32 = {
+            let a: i32 = 10;
+            a * 3
+        };
+        assert(a == 2);
+        assert(b == 30);
+        let c: i32 = { a } + { b };
+        assert(c == 32);
+        """,
+        "If statement with block branches": """
+        let counter: i32 = 0;
+        if (counter == 0) {
+            counter = 1;
+        } else {
+            counter = 2;
+        }
+        if (counter == 1) {
+            counter += 10;
+        }
+        assert(counter == 11);
+        """,
+        "Function trailing expression return": """
+        fn square(n: i32) -> i32 {
+            n * n
+        }
+        fn sign(n: i32) -> i32 {
+            if (n > 0) {
+                1
+            } else if (n < 0) {
+                -1
+            } else {
+                0
+            }
+        }
+        fn describe(n: i32) -> String {
+            let prefix: String = \\"n is \\";
+            match sign(n) {
+                1 => prefix + \\"positive\\",
+                0 => prefix + \\"zero\\",
+                _ => prefix + \\"negative\\",
+            }
+        }
+        assert(square(5) == 25);
+        assert(sign(-4) == -1);
+        assert(sign(0) == 0);
+        assert(describe(3) == \\"n is positive\\");
+        """,
+        "Function early return before trailing expression": """
+        fn clamp(n: i32) -> i32 {
+            if (n > 10) {
+                return 10;
+            }
+            n
+        }
+        fn pick(first: bool) -> i32 {
+            if (first) {
+                return 1;
+            } else {
+                return 2;
+            }
+        }
+        assert(clamp(42) == 10);
+        assert(clamp(3) == 3);
+        assert(pick(false) == 2);
+        """,
+        "If expression incompatible branch types failure": """
+        let a: bool = true;
+        let x: i32 = if (a) { 1 } else { \\"two\\" };
+        """,
+        "If expression without else failure": """
+        let a: bool = true;
+        let x: i32 = if (a) { 1 };
+        """,
+        "Match arms of incompatible types failure": """
+        enum Shape { Circle, Square }
+        let shape = Shape::Circle;
+        let sides: i32 = match shape {
+            Shape::Circle => \\"none\\",
+            Shape::Square => 4
+        };
+        """,
+        "Return from inside a while loop": """
+        fn first_multiple(of: i32, above: i32) -> i32 {
+            let candidate: i32 = above;
+            while (true) {
+                if (candidate % of == 0) {
+                    return candidate;
+                }
+                candidate += 1;
+            }
+            return -1;
+        }
+        assert(first_multiple(7, 20) == 21);
+        fn nested(limit: i32) -> i32 {
+            for (let i: i32 = 0; i < limit; i += 1) {
+                for (let j: i32 = 0; j < limit; j += 1) {
+                    if (i * j == 6) return i * 10 + j;
+                }
+            }
+            return 0;
+        }
+        assert(nested(5) == 23);
+        """,
+        "Continue in while and for loops": """
+        let i: i32 = 0;
+        let odd_sum: i32 = 0;
+        while (i < 10) {
+            i += 1;
+            if (i % 2 == 0) {
+                continue;
+            }
+            odd_sum += i;
+        }
+        assert(odd_sum == 25);
+        let even_sum: i32 = 0;
+        for (let j: i32 = 0; j < 10; j += 1) {
+            if (j % 2 == 1) {
+                continue;
+            }
+            even_sum += j;
+        }
+        assert(even_sum == 20);
+        """,
+        "Labeled break and continue": """
+        let pairs: i32 = 0;
+        'outer: for (let i: i32 = 0; i < 5; i += 1) {
+            for (let j: i32 = 0; j < 5; j += 1) {
+                if (j > i) {
+                    continue 'outer;
+                }
+                if (i == 3) {
+                    break 'outer;
+                }
+                pairs += 1;
+            }
+        }
+        assert(pairs == 6);
+        let count: i32 = 0;
+        'rows: while (true) {
+            while (true) {
+                count += 1;
+                if (count == 3) {
+                    break 'rows;
+                }
+            }
+        }
+        assert(count == 3);
+        """,
+        "Loop with break value": """
+        let n: i32 = 0;
+        let square: i32 = loop {
+            n += 1;
+            if (n * n > 50) {
+                break n * n;
+            }
+        };
+        assert(square == 64);
+        fn find(target: i32) -> i32 {
+            let attempts: i32 = 0;
+            'search: loop {
+                let guess: i32 = 0;
+                loop {
+                    attempts += 1;
+                    if (guess == target) {
+                        break 'search attempts;
+                    }
+                    if (guess > target) {
+                        break;
+                    }
+                    guess += 1;
+                }
+            }
+        }
+        assert(find(4) == 5);
+        """,
+        "Assertion inside a function failure": """
+        fn check(n: i32) -> () {
+            assert(n > 0);
+        }
+        check(-1);
+        """,
+        "Break outside of a loop failure": """
+        let a: i32 = 1;
+        break;
+        """,
+        "Continue outside of a loop failure": """
+        fn skip() -> () {
+            continue;
+        }
+        while (true) {
+            skip();
+            break;
+        }
+        """,
+        "Break to an unknown label failure": """
+        while (true) {
+            break 'missing;
+        }
+        """,
+        "Break with a value out of a while failure": """
+        while (true) {
+            break 1;
+        }
+        """,
+        "Loop breaks with incompatible values failure": """
+        let flag: bool = true;
+        let a: i32 = loop {
+            if (flag) {
+                break 1;
+            }
+            break \\"one\\";
+        };
+        """,
+        # For-in loops
+        "For in over an array": """
+        let xs = [1, 2, 3, 4];
+        let sum: i32 = 0;
+        for x in xs {
+            sum += x;
+        }
+        assert(sum == 10);
+        """,
+        "For in over a string": """
+        let count: i32 = 0;
+        let last: char = 'a';
+        for c in \\"hello\\" {
+            count += 1;
+            last = c;
+        }
+        assert(count == 5);
+        assert(last == 'o');
+        """,
+        "For in over a map": """
+        let stock: Map<String, i32> = [\\"apples\\": 3, \\"pears\\": 5];
+        let total: i32 = 0;
+        let names = \\"\\";
+        for name in stock {
+            names += name;
+            total += stock[name];
+            stock[name] = 0;
+        }
+        assert(names == \\"applespears\\");
+        assert(total == 8);
+        assert(stock[\\"pears\\"] == 0);
+        """,
+        "For in over a map key type failure": """
+        let stock: Map<String, i32> = [\\"apples\\": 3];
+        for name in stock {
+            let count: i32 = name;
+        }
+        """,
+        "For in over ranges": """
+        let sum: i32 = 0;
+        for i in 0..5 {
+            sum += i;
+        }
+        assert(sum == 10);
+        let inclusive: i32 = 0;
+        for i in 1..=5 {
+            inclusive += i;
+        }
+        assert(inclusive == 15);
+        let empty: i32 = 0;
+        for i in 5..5 {
+            empty += 1;
+        }
+        assert(empty == 0);
+        """,
+        "Range values": """
+        let r: Range<i64> = 2..4i64;
+        let total: i64 = 0;
+        for i in r {
+            total += i;
+        }
+        assert(total == 5);
+        for i in r {
+            total += i;
+        }
+        assert(total == 10);
+        assert(r == (2..4i64));
+        print r;
+        """,
+        "For in over an iterator": """
+        struct Countdown {
+            from: i32,
+        }
+        impl Iterator<i32> for Countdown {
+            fn next(self: Countdown) -> Option<i32> {
+                if (self.from == 0) {
+                    return Option::None;
+                }
+                self.from -= 1;
+                return Option::Some(self.from + 1);
+            }
+        }
+        let seen: i32 = 0;
+        let sum: i32 = 0;
+        for n in Countdown { from: 3 } {
+            seen += 1;
+            sum += n;
+        }
+        assert(seen == 3);
+        assert(sum == 6);
+        """,
+        "For in break and continue": """
+        let sum: i32 = 0;
+        for i in 0..100 {
+            if (i % 2 == 0) {
+                continue;
+            }
+            if (i > 9) {
+                break;
+            }
+            sum += i;
+        }
+        assert(sum == 25);
+        """,
+        "Labeled for in": """
+        let pairs: i32 = 0;
+        'outer: for i in 0..5 {
+            for j in [0, 1, 2, 3, 4] {
+                if (j > i) {
+                    continue 'outer;
+                }
+                if (i == 3) {
+                    break 'outer;
+                }
+                pairs += 1;
+            }
+        }
+        assert(pairs == 6);
+        """,
+        "For in variable is scoped to its body": """
+        let x: i32 = 10;
+        for x in 0..3 {
+            assert(x < 3);
+        }
+        assert(x == 10);
+        """,
+        "Iterate over a non-iterable failure": """
+        for x in 5 {
+            print x;
+        }
+        """,
+        "Range with float bounds failure": """
+        let r = 0.5..2.5;
+        """,
+        "For in variable type failure": """
+        for c in \\"abc\\" {
+            let i: i32 = c;
+        }
+        """,
+        # String literals
+        "String literal punctuation and unicode": """
+        let a = \\"Hi, world! ¿Qué tal? ☃\\";
+        let count: i32 = 0;
+        for c in a {
+            count += 1;
+        }
+        assert(count == 22);
+        assert(a != \\"Hi world\\");
+        assert(\\"  padded \\" != \\"padded\\");
+        """,
+        "String literal escapes": """
+        let count: i32 = 0;
+        for c in \\"\\\\t\\\\r\\\\n\\\\0\\\\\\\\\\\\\\"\\\\'\\" {
+            count += 1;
+        }
+        assert(count == 7);
+        assert(\\"\\\\u{48}i\\" == \\"Hi\\");
+        assert('\\\\u{41}' == 'A');
+        assert('\\\\'' != '\\\\\\"');
+        let tab: char = 'a';
+        for c in \\"a\\\\tb\\" {
+            if (c != 'a' and c != 'b') {
+                tab = c;
+            }
+        }
+        assert(tab == '\\\\t');
+        """,
+        "Raw and multi-line strings": """
+        let raw = r\\"a\\\\n\\";
+        let count: i32 = 0;
+        for c in raw {
+            count += 1;
+        }
+        assert(count == 3);
+        assert(r#\\"say \\"hi\\"\\"# == \\"say \\\\\\"hi\\\\\\"\\");
+        let lines = \\"one
+two\\";
+        assert(lines == \\"one\\\\ntwo\\");
+        let joined = \\"one \\\\
+                      two\\";
+        assert(joined == \\"one two\\");
+        """,
+        "String invalid escape failure": """
+        let a = \\"\\\\q\\";
+        """,
+        "String unterminated failure": """
+        let a = \\"abc;
+        """,
+        # Numeric literal forms
+        "Hex octal and binary literals": """
+        let a: u8 = 0xFF;
+        assert(a == 255u8);
+        assert(0xdead_beefu32 == 3735928559u32);
+        assert(0o777 == 511);
+        assert(0b1010_1010 == 170);
+        assert(0b1000_0000u8 == 128u8);
+        """,
+        "Digit separators and exponents": """
+        let big: i64 = 1_000_000_000_000;
+        assert(big == 1000000000000i64);
+        assert(1.5e-3 == 0.0015);
+        assert(2E3 == 2000.0);
+        assert(1_0.2_5 == 10.25);
+        """,
+        "Float suffixes": """
+        let a = 2.0f32;
+        let b: f32 = a;
+        let c = 5f64;
+        let d: f64 = c;
+        let e = 1e2f32;
+        assert(e == 100.0f32);
+        """,
+        "Negative literals take their suffix type": """
+        let a = -128i8;
+        let b: i8 = a;
+        let c: i8 = -0x80;
+        assert(b == c);
+        let d = -2.5e1f32;
+        let e: f32 = d;
+        assert(e == -25.0f32);
+        let f = -9223372036854775808;
+        let g: i64 = f;
+        """,
+        "Signed literal past its maximum failure": """
+        let a = 128i8;
+        """,
+        "Invalid digit for radix failure": """
+        let a = 0b102;
+        """,
+        "Integer suffix on float failure": """
+        let a = 1.5i32;
+        """,
+        "Format string interpolation": """
+        let x: i32 = 3;
+        let name: String = \\"lang\\";
+        assert(f\\"x = {x}, name = {name}\\" == \\"x = 3, name = lang\\");
+        assert(f\\"{x + 1} {x * x}\\" == \\"4 9\\");
+        assert(f\\"{{x}} {{{x}}}\\" == \\"{x} {3}\\");
+        assert(f\\"{f\\"{x}{x}\\"}!\\" == \\"33!\\");
+        assert(f\\"\\" == \\"\\");
+        """,
+        "Format string width precision and alignment": """
+        let x: i32 = 42;
+        let y: f64 = 3.14159;
+        assert(f\\"{y:.2}\\" == \\"3.14\\");
+        assert(f\\"[{x:6}]\\" == \\"[    42]\\");
+        assert(f\\"[{x:<6}]\\" == \\"[42    ]\\");
+        assert(f\\"[{x:^6}]\\" == \\"[  42  ]\\");
+        assert(f\\"[{x:*^7}]\\" == \\"[**42***]\\");
+        assert(f\\"[{x:+}] [{x:06}] [{-x:06}]\\" == \\"[+42] [000042] [-00042]\\");
+        assert(f\\"[{y:8.3}]\\" == \\"[   3.142]\\");
+        assert(f\\"[{\\"ab\\":5}] [{\\"abc\\":.2}] [{\\"ab\\":>4}]\\" == \\"[ab   ] [ab] [  ab]\\");
+        """,
+        "Format string radix": """
+        let x: i32 = 255;
+        assert(f\\"{x:x} {x:X} {x:#x}\\" == \\"ff FF 0xff\\");
+        assert(f\\"{x:b} {8:o} {5:#06b}\\" == \\"11111111 10 0b0101\\");
+        assert(f\\"{-1i8:x} {-2:X}\\" == \\"ff FFFFFFFE\\");
+        """,
+        "Format string debug": """
+        struct Point {
+            x: i32,
+            y: f64,
+            label: String,
+        }
+        enum Shape { Circle(f64), Empty }
+        let p: Point = Point { x: 1, y: 2.0, label: \\"origin\\" };
+        assert(f\\"{p:?}\\" == \\"Point { x: 1, y: 2.0, label: \\\\\\"origin\\\\\\" }\\");
+        assert(f\\"{p}\\" == \\"Point\\");
+        assert(f\\"{[1, 2, 3]:?} {[1, 2, 3]}\\" == \\"[1, 2, 3] 1 2 3\\");
+        assert(f\\"{(1, \\"a\\"):?} {(1, \\"a\\")}\\" == \\"(1, \\\\\\"a\\\\\\") (1, a)\\");
+        assert(f\\"{Shape::Circle(1.5):?} {Shape::Empty:?}\\" == \\"Shape::Circle(1.5) Shape::Empty\\");
+        assert(f\\"{'c':?} {'c'} {1.0:?} {1.0} {true:?}\\" == \\"'c' c 1.0 1 true\\");
+        assert(f\\"{[1: \\"a\\"]:?}\\" == \\"[1: \\\\\\"a\\\\\\"]\\");
+        """,
+        "Format string display trait": """
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        impl Display for Point {
+            fn fmt(self: Point) -> String {
+                return f\\"({self.x}, {self.y})\\";
+            }
+        }
+        let p: Point = Point { x: 1, y: 2 };
+        assert(f\\"p = {p}\\" == \\"p = (1, 2)\\");
+        assert(f\\"[{p:>8}]\\" == \\"[  (1, 2)]\\");
+        assert(f\\"{p:?}\\" == \\"Point { x: 1, y: 2 }\\");
+        """,
+        "Format string radix of a float failure": """
+        let y: f64 = 1.5;
+        print f\\"{y:x}\\";
+        """,
+        "Format string unknown spec failure": """
+        print f\\"{1:q}\\";
+        """,
+        "Format string unmatched brace failure": """
+        print f\\"a } b\\";
+        """,
+    })
+
+    def generate_files(self, path):
+        output_dir = Path(path)
+        if not output_dir.exists():
+            print("Creating directory '{}'".format(output_dir))
+            os.mkdir(output_dir)
+        for key, value in self.VARIABLE_DECLARATIONS.items():
+            source_file_path = output_dir / \
+                (slugify(key, separator="_") + ".lang")
+            with open(source_file_path, 'w') as source_file:
+                source_file.write(self.FILE_GENERATION_COMMENT + '\n')
+                source_file.write(value)
+                print("Wrote file '{}'".format(source_file_path))
+
+    def print_lang_benches(self):
+        """Prints benches for running at the top-most interface of the interpreter"""
+        print(self.FILE_GENERATION_COMMENT)
+        print("""
+        #[macro_use]
+        extern crate criterion;
+        extern crate lang;
+
+        use criterion::{black_box, Criterion};
+        use self::lang::lang::Lang;
+        """)
+        slugged_keys = []
+        for key, value in self.VARIABLE_DECLARATIONS.items():
+            key_slug = slugify(key, separator="_")
+            slugged_keys.append(key_slug)
+            print("fn {}(c: &mut Criterion) {{".format(
+                key_slug))
+            print("c.bench_function(\"{}\", |b| {{".format(key))
+            print("b.iter(|| Lang::new(black_box(Some(\"{}\"))))".format(value))
+            print("});}")
+        criterion_string = ", ".join(slugged_keys)
+        print("criterion_group!(lang_benches, {});".format(criterion_string))
+        print("criterion_main!(lang_benches);")
+
+    def print_tests(self):
+        print(self.FILE_GENERATION_COMMENT)
+        # Every test runs on each backend, they have to agree on what passes
+        for backend in ["Interpreter", "Vm"]:
+            source = """
+            #[cfg(test)]
+            mod {} {{
+            extern crate lang;
+            use self::lang::lang::{{Backend, Lang}};
+            """.format(backend.lower())
+            print(source)
+            for key, value in self.VARIABLE_DECLARATIONS.items():
+                should_fail = key.endswith("failure")
+                print("#[test]")
+                print("fn {}() {{".format(slugify(key, separator="_")))
+                print("let mut lang = Lang::new(Some(\"{}\"));".format(value))
+                print("lang.set_backend(Backend::{});".format(backend))
+                print("let result = lang.run();")
+                print(
+                    "if let Err(ref error) = result { println!(\"{}\", error); }")
+                print("assert_eq!(result.is_ok(), {}) }}".format(
+                    "false" if should_fail else "true"))
+            print("}")
 
 
 def main(argv):
@@ -22,7 +22,11 @@ fn main() -> Result<(), LangError> {
                 } else if arg_matches.is_present("print_statements") {
                     result = Lang::new(Some(&content)).print_statements();
                 } else {
-                    result = Lang::new(Some(&content)).run();
+                    let mut lang = Lang::new(Some(&content));
+                    if arg_matches.is_present("vm") {
+                        lang.set_backend(Backend::Vm);
+                    }
+                    result = lang.run();
                 }
                 match result {
                     Ok(_) => (),
@@ -1,71 +1,10170 @@
 // This file is auto-generated. Please do not edit it manually.
 
 #[cfg(test)]
-mod tests {
+mod interpreter {
     extern crate lang;
-    use self::lang::lang::Lang;
+    use self::lang::lang::{Backend, Lang};
 
+    #[test]
+    fn array_index_with_unsigned_types() {
+        let mut lang = Lang::new(Some(
+            "
+        let values = [10, 20, 30];
+        let i: usize = 2;
+        let j: u8 = 1;
+        assert(values[i] == 30);
+        assert(values[j] == 20);
+        values[0usize] = 5;
+        assert(values[0] == 5);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_literal_element_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Array<i64> = [\"x\"];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn array_negative_index_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = [1, 2];
+        let i = -1;
+        a[i] = 3;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn array_negative_index_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = [1, 2];
+        let i = -1;
+        print a[i];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
     #[test]
     fn array_i64_variable_declaration() {
         let mut lang = Lang::new(Some("let i: Array<i64>;"));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_i64_variable_declaration_and_assignment() {
+        let mut lang = Lang::new(Some(
+            "
+        let i: Array<i64> = [0, 1, 2];
+        assert(i[0] == 0);
+        assert(i[1] == 1);
+        assert(i[2] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_i64_variable_declaration_empty() {
+        let mut lang = Lang::new(Some("let i: Array<i64> = [];"));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_i64_variable_re_assignment() {
+        let mut lang = Lang::new(Some(
+            "
+        let i: Array<i64> = [];
+        i = [0, 1, 2];
+        assert(i[0] == 0);
+        assert(i[1] == 1);
+        assert(i[2] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_i64_variable_re_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "let i: Array<i64> = [];
+            i = [0.00, 1.00, 2.00];
+        assert(i[0] == 0.00);
+        assert(i[1] == 1.00);
+        assert(i[2] == 2.00);
+            ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn assertion() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(100 == 100);
+        assert(true == true);
+        assert(1.05 == 1.05);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn assertion_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(0 == 100);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn assertion_inside_a_function_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn check(n: i32) -> () {
+            assert(n > 0);
+        }
+        check(-1);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn bitwise_not_on_floats_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = ~1.5;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn bitwise_operator_precedence() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(1 + 2 << 1 == 6);
+        assert(6 & 3 == 2);
+        assert(1 | 2 ^ 3 & 4 == 3);
+        assert(2 * 3 % 4 == 2);
+        assert(true && false || true);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn bitwise_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        assert((6 & 3) == 2);
+        assert((6 | 3) == 7);
+        assert((6 ^ 3) == 5);
+        assert(~0 == -1);
+        let a: u8 = 5;
+        assert(~a == 250u8);
+        let b: u8 = 12;
+        let c: i64 = 10;
+        assert((b | c) == 14);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn bitwise_operators_on_floats_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1.0 & 2.0;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn block_expression() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 2;
+        let b: i32 = {
+            let a: i32 = 10;
+            a * 3
+        };
+        assert(a == 2);
+        assert(b == 30);
+        let c: i32 = { a } + { b };
+        assert(c == 32);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn break_outside_of_a_loop_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        break;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn break_to_an_unknown_label_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        while (true) {
+            break 'missing;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn break_with_a_value_out_of_a_while_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        while (true) {
+            break 1;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn closure_outlives_its_block() {
+        let mut lang = Lang::new(Some(
+            "
+        fn zero() -> i32 {
+            return 0;
+        }
+        let f: fn() -> i32 = zero;
+        {
+            let hidden: i32 = 7;
+            fn reveal() -> i32 {
+                return hidden;
+            }
+            f = reveal;
+        }
+        assert(f() == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn closure_returned_from_a_function() {
+        let mut lang = Lang::new(Some(
+            "
+        fn make_adder(n: i32) -> fn(i32) -> i32 {
+            fn add(x: i32) -> i32 {
+                return x + n;
+            }
+            return add;
+        }
+        fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+            return f(x);
+        }
+        let add5 = make_adder(5);
+        let add10: fn(i32) -> i32 = make_adder(10);
+        assert(add5(3) == 8);
+        assert(add10(3) == 13);
+        assert(apply(add5, 1) == 6);
+        assert(make_adder(1)(1) == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn closure_sees_the_binding_in_scope_at_its_declaration() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        {
+            fn show() -> i32 {
+                return a;
+            }
+            assert(show() == 1);
+            let a: i32 = 2;
+            assert(show() == 1);
+            assert(a == 2);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn closures_created_in_a_long_loop() {
+        let mut lang = Lang::new(Some(
+            "
+        fn make_adder(n: i32) -> fn(i32) -> i32 {
+            fn add(x: i32) -> i32 {
+                return x + n;
+            }
+            return add;
+        }
+        let first = make_adder(100);
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 500) {
+            let adder = make_adder(i);
+            total = total + adder(1);
+            i = i + 1;
+        }
+        assert(total == 125250);
+        assert(first(1) == 101);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn comments() {
+        let mut lang = Lang::new(Some(
+            "
+        // A line comment
+        /// A doc comment
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b; // trailing comment
+        }
+        /* A block comment
+           spanning lines /* with a nested comment */
+        */
+        /** A block doc comment */
+        let sum: i32 = add(1, /* inline */ 2);
+        assert(sum == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment() {
+        let mut lang = Lang::new(Some(
+            "
+        let i = 0;
+        i += 5;
+        i -= 1;
+        i *= 3;
+        i /= 2;
+        i %= 4;
+        assert(i == 2);
+        i <<= 3;
+        i |= 1;
+        i ^= 3;
+        i &= 30;
+        i >>= 1;
+        assert(i == 9);
+        let s = \"a\";
+        s += \"b\";
+        assert(s == \"ab\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment_element_type_mismatch_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let values = [1, 2];
+        values[0] += 1.5;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn compound_assignment_evaluates_its_target_once() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Counter { count: i32 }
+        struct Calls { count: i32 }
+        let calls = Calls { count: 0 };
+        let counter = Counter { count: 1 };
+        fn target(calls: Calls, counter: Counter) -> Counter {
+            calls.count += 1;
+            return counter;
+        }
+        fn next(calls: Calls) -> i32 {
+            calls.count += 1;
+            return calls.count - 1;
+        }
+        target(calls, counter).count += 5;
+        assert(counter.count == 6);
+        assert(calls.count == 1);
+        let values = [10, 20, 30];
+        values[next(calls)] *= 2;
+        assert(values[1] == 40);
+        assert(calls.count == 2);
+        let totals: Map<String, i32> = [\"a\": 1];
+        totals[\"a\"] += 2;
+        assert(totals[\"a\"] == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment_invalid_target_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1;
+        a + 1 += 2;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn compound_assignment_to_a_field_of_an_overloaded_type() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 { x: i32 }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x };
+            }
+        }
+        struct Body { position: Vec2 }
+        let body = Body { position: Vec2 { x: 1 } };
+        body.position += Vec2 { x: 2 };
+        let position = body.position;
+        assert(position.x == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment_to_fields_and_elements() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Counter { count: i32 }
+        let counter = Counter { count: 1 };
+        counter.count += 10;
+        counter.count <<= 1;
+        assert(counter.count == 22);
+        let values = [1, 2, 3];
+        values[1] += 40;
+        values[2] *= values[1];
+        assert(values[1] == 42);
+        assert(values[2] == 126);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment_type_mismatch_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1;
+        a += \"x\";
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn continue_in_while_and_for_loops() {
+        let mut lang = Lang::new(Some(
+            "
+        let i: i32 = 0;
+        let odd_sum: i32 = 0;
+        while (i < 10) {
+            i += 1;
+            if (i % 2 == 0) {
+                continue;
+            }
+            odd_sum += i;
+        }
+        assert(odd_sum == 25);
+        let even_sum: i32 = 0;
+        for (let j: i32 = 0; j < 10; j += 1) {
+            if (j % 2 == 1) {
+                continue;
+            }
+            even_sum += j;
+        }
+        assert(even_sum == 20);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn continue_outside_of_a_loop_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn skip() -> () {
+            continue;
+        }
+        while (true) {
+            skip();
+            break;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn digit_separators_and_exponents() {
+        let mut lang = Lang::new(Some(
+            "
+        let big: i64 = 1_000_000_000_000;
+        assert(big == 1000000000000i64);
+        assert(1.5e-3 == 0.0015);
+        assert(2E3 == 2000.0);
+        assert(1_0.2_5 == 10.25);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn division_by_zero_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        let b = a / 0;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn empty_arrays_of_function_values() {
+        let mut lang = Lang::new(Some(
+            "
+        fn one() -> i32 {
+            return 1;
+        }
+        fn two() -> i32 {
+            return 2;
+        }
+        let handlers: Array<fn() -> i32> = [];
+        let sum: i32 = 0;
+        for handler in handlers {
+            sum += handler();
+        }
+        assert(sum == 0);
+        handlers = [one, two];
+        for handler in handlers {
+            sum += handler();
+        }
+        assert(sum == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn empty_arrays_of_tuples() {
+        let mut lang = Lang::new(Some(
+            "
+        let pairs: Array<(i32, i32)> = [];
+        let names: Array<(String, Array<i32>)> = [];
+        let count: i32 = 0;
+        for pair in pairs {
+            count += 1;
+        }
+        for name in names {
+            count += 1;
+        }
+        assert(count == 0);
+        pairs = [(1, 2), (3, 4)];
+        assert(pairs[1].0 == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_as_function_argument_and_return_type() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Blue
+        }
+        fn is_red(color: Color) -> bool {
+            return color == Color::Red;
+        }
+        fn favourite() -> Color {
+            return Color::Blue;
+        }
+        assert(is_red(Color::Red));
+        assert(!is_red(favourite()));
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_declaration() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_duplicate_variant_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Red
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn enum_type_mismatch_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red
+        }
+        let color: Color = 1;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn enum_undefined_variant_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red
+        }
+        let color: Color = Color::Purple;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn enum_variant_comparison() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Green,
+            Blue
+        }
+        let color: Color = Color::Red;
+        assert(color == Color::Red);
+        assert(color != Color::Green);
+        color = Color::Blue;
+        assert(color == Color::Blue);
+        print color;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_variant_with_initializer() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Status {
+            Ok = 200,
+            NotFound = 404,
+            Teapot
+        }
+        assert(Status::Ok < Status::NotFound);
+        assert(Status::NotFound < Status::Teapot);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_variant_with_values() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(f64),
+            Rect(i32, i32),
+            Empty
+        }
+        let rect: Shape = Shape::Rect(2, 3);
+        assert(rect == Shape::Rect(2, 3));
+        assert(rect != Shape::Rect(3, 2));
+        assert(rect != Shape::Empty);
+        print rect;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_variant_with_values_arity_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Rect(i32, i32)
+        }
+        let rect: Shape = Shape::Rect(1);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn enum_variant_with_values_wrong_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(f64)
+        }
+        let circle: Shape = Shape::Circle(\"round\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn float_arithmetic() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: f32 = 1.5;
+        let b: f32 = a * 2.0 as f32 - 0.5 as f32;
+        assert(b == 2.5);
+        let c: f64 = 9.0;
+        let d: f64 = c / 4.0;
+        assert(d == 2.25);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn float_suffixes() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 2.0f32;
+        let b: f32 = a;
+        let c = 5f64;
+        let d: f64 = c;
+        let e = 1e2f32;
+        assert(e == 100.0f32);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_break_and_continue() {
+        let mut lang = Lang::new(Some(
+            "
+        let sum: i32 = 0;
+        for i in 0..100 {
+            if (i % 2 == 0) {
+                continue;
+            }
+            if (i > 9) {
+                break;
+            }
+            sum += i;
+        }
+        assert(sum == 25);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_a_map() {
+        let mut lang = Lang::new(Some(
+            "
+        let stock: Map<String, i32> = [\"apples\": 3, \"pears\": 5];
+        let total: i32 = 0;
+        let names = \"\";
+        for name in stock {
+            names += name;
+            total += stock[name];
+            stock[name] = 0;
+        }
+        assert(names == \"applespears\");
+        assert(total == 8);
+        assert(stock[\"pears\"] == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_a_map_key_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let stock: Map<String, i32> = [\"apples\": 3];
+        for name in stock {
+            let count: i32 = name;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn for_in_over_a_string() {
+        let mut lang = Lang::new(Some(
+            "
+        let count: i32 = 0;
+        let last: char = 'a';
+        for c in \"hello\" {
+            count += 1;
+            last = c;
+        }
+        assert(count == 5);
+        assert(last == 'o');
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_an_array() {
+        let mut lang = Lang::new(Some(
+            "
+        let xs = [1, 2, 3, 4];
+        let sum: i32 = 0;
+        for x in xs {
+            sum += x;
+        }
+        assert(sum == 10);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_an_iterator() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Countdown {
+            from: i32,
+        }
+        impl Iterator<i32> for Countdown {
+            fn next(self: Countdown) -> Option<i32> {
+                if (self.from == 0) {
+                    return Option::None;
+                }
+                self.from -= 1;
+                return Option::Some(self.from + 1);
+            }
+        }
+        let seen: i32 = 0;
+        let sum: i32 = 0;
+        for n in Countdown { from: 3 } {
+            seen += 1;
+            sum += n;
+        }
+        assert(seen == 3);
+        assert(sum == 6);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_ranges() {
+        let mut lang = Lang::new(Some(
+            "
+        let sum: i32 = 0;
+        for i in 0..5 {
+            sum += i;
+        }
+        assert(sum == 10);
+        let inclusive: i32 = 0;
+        for i in 1..=5 {
+            inclusive += i;
+        }
+        assert(inclusive == 15);
+        let empty: i32 = 0;
+        for i in 5..5 {
+            empty += 1;
+        }
+        assert(empty == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_variable_is_scoped_to_its_body() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: i32 = 10;
+        for x in 0..3 {
+            assert(x < 3);
+        }
+        assert(x == 10);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_variable_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        for c in \"abc\" {
+            let i: i32 = c;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn for_loop() {
+        let mut lang = Lang::new(Some(
+            "
+        for (let i: i32 = 0; i < 10; i = i + 1) {
+            print i;
+        }
+        for (let b: bool = false; b == true; b = false) {
+            print b;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_debug() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: f64,
+            label: String,
+        }
+        enum Shape { Circle(f64), Empty }
+        let p: Point = Point { x: 1, y: 2.0, label: \"origin\" };
+        assert(f\"{p:?}\" == \"Point { x: 1, y: 2.0, label: \\\"origin\\\" }\");
+        assert(f\"{p}\" == \"Point\");
+        assert(f\"{[1, 2, 3]:?} {[1, 2, 3]}\" == \"[1, 2, 3] 1 2 3\");
+        assert(f\"{(1, \"a\"):?} {(1, \"a\")}\" == \"(1, \\\"a\\\") (1, a)\");
+        assert(f\"{Shape::Circle(1.5):?} {Shape::Empty:?}\" == \"Shape::Circle(1.5) Shape::Empty\");
+        assert(f\"{'c':?} {'c'} {1.0:?} {1.0} {true:?}\" == \"'c' c 1.0 1 true\");
+        assert(f\"{[1: \"a\"]:?}\" == \"[1: \\\"a\\\"]\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_display_trait() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        impl Display for Point {
+            fn fmt(self: Point) -> String {
+                return f\"({self.x}, {self.y})\";
+            }
+        }
+        let p: Point = Point { x: 1, y: 2 };
+        assert(f\"p = {p}\" == \"p = (1, 2)\");
+        assert(f\"[{p:>8}]\" == \"[  (1, 2)]\");
+        assert(f\"{p:?}\" == \"Point { x: 1, y: 2 }\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_interpolation() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: i32 = 3;
+        let name: String = \"lang\";
+        assert(f\"x = {x}, name = {name}\" == \"x = 3, name = lang\");
+        assert(f\"{x + 1} {x * x}\" == \"4 9\");
+        assert(f\"{{x}} {{{x}}}\" == \"{x} {3}\");
+        assert(f\"{f\"{x}{x}\"}!\" == \"33!\");
+        assert(f\"\" == \"\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_radix() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: i32 = 255;
+        assert(f\"{x:x} {x:X} {x:#x}\" == \"ff FF 0xff\");
+        assert(f\"{x:b} {8:o} {5:#06b}\" == \"11111111 10 0b0101\");
+        assert(f\"{-1i8:x} {-2:X}\" == \"ff FFFFFFFE\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_radix_of_a_float_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let y: f64 = 1.5;
+        print f\"{y:x}\";
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn format_string_unknown_spec_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        print f\"{1:q}\";
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn format_string_unmatched_brace_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        print f\"a } b\";
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn format_string_width_precision_and_alignment() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: i32 = 42;
+        let y: f64 = 3.14159;
+        assert(f\"{y:.2}\" == \"3.14\");
+        assert(f\"[{x:6}]\" == \"[    42]\");
+        assert(f\"[{x:<6}]\" == \"[42    ]\");
+        assert(f\"[{x:^6}]\" == \"[  42  ]\");
+        assert(f\"[{x:*^7}]\" == \"[**42***]\");
+        assert(f\"[{x:+}] [{x:06}] [{-x:06}]\" == \"[+42] [000042] [-00042]\");
+        assert(f\"[{y:8.3}]\" == \"[   3.142]\");
+        assert(f\"[{\"ab\":5}] [{\"abc\":.2}] [{\"ab\":>4}]\" == \"[ab   ] [ab] [  ab]\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_assert_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test(a: i32, b: f64) -> bool {
+            if (!(a > 1000)) {
+                assert(false);
+            }
+            return false;
+        }
+        test(1, 0.0);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_early_return_before_trailing_expression() {
+        let mut lang = Lang::new(Some(
+            "
+        fn clamp(n: i32) -> i32 {
+            if (n > 10) {
+                return 10;
+            }
+            n
+        }
+        fn pick(first: bool) -> i32 {
+            if (first) {
+                return 1;
+            } else {
+                return 2;
+            }
+        }
+        assert(clamp(42) == 10);
+        assert(clamp(3) == 3);
+        assert(pick(false) == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_falling_off_its_end_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn sign(x: i32) -> i32 {
+            if (x > 0) {
+                return 1;
+            }
+        }
+        let s: i32 = sign(-5);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_leaving_a_loop_without_returning_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first_even(a: Array<i32>) -> i32 {
+            while (true) {
+                for x in a {
+                    if (x % 2 == 0) {
+                        return x;
+                    }
+                }
+                break;
+            }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_nested_return() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test() -> bool {
+            {
+                {
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+        assert(false == test());
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_returns_bool() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test() -> bool {
+            return false;
+        }
+        assert(false == test());
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_returns_bool_with_args() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test(a: i32, b: f64) -> bool {
+            return false;
+        }
+        assert(false == test(100, 100.00));
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_trailing_expression_return() {
+        let mut lang = Lang::new(Some(
+            "
+        fn square(n: i32) -> i32 {
+            n * n
+        }
+        fn sign(n: i32) -> i32 {
+            if (n > 0) {
+                1
+            } else if (n < 0) {
+                -1
+            } else {
+                0
+            }
+        }
+        fn describe(n: i32) -> String {
+            let prefix: String = \"n is \";
+            match sign(n) {
+                1 => prefix + \"positive\",
+                0 => prefix + \"zero\",
+                _ => prefix + \"negative\",
+            }
+        }
+        assert(square(5) == 25);
+        assert(sign(-4) == -1);
+        assert(sign(0) == 0);
+        assert(describe(3) == \"n is positive\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_type_argument_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+            return f(x);
+        }
+        fn is_positive(x: i32) -> bool {
+            return x > 0;
+        }
+        apply(is_positive, 1);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_type_call_argument_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn apply(f: fn(i32) -> i32) -> i32 {
+            return f(true);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_type_mismatch_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn zero() -> i32 {
+            return 0;
+        }
+        let f: fn(i32) -> bool = zero;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn functions_returning_on_every_path() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Sign {
+            Negative,
+            Zero,
+            Positive
+        }
+        fn sign(x: i32) -> Sign {
+            if (x > 0) {
+                return Sign::Positive;
+            } else if (x < 0) {
+                return Sign::Negative;
+            } else {
+                return Sign::Zero;
+            }
+        }
+        fn value(s: Sign) -> i32 {
+            match s {
+                Sign::Negative => {
+                    return -1;
+                }
+                Sign::Zero => {
+                    return 0;
+                }
+                Sign::Positive => {
+                    return 1;
+                }
+            }
+        }
+        fn first_over(a: Array<i32>, limit: i32) -> i32 {
+            loop {
+                for x in a {
+                    if (x > limit) {
+                        return x;
+                    }
+                    break;
+                }
+            }
+        }
+        fn countdown(n: i32) -> i32 {
+            while (true) {
+                if (n == 0) {
+                    return n;
+                }
+                n -= 1;
+            }
+        }
+        assert(value(sign(-5)) == -1);
+        assert(value(sign(0)) == 0);
+        assert(first_over([7, 2], 5) == 7);
+        assert(countdown(3) == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn garbage_collection_in_a_long_loop() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Counter {
+            count: i32,
+        }
+        fn bump(c: Counter) -> i32 {
+            c.count = c.count + 1;
+            return c.count;
+        }
+        fn double(n: i32) -> i32 {
+            return n + n;
+        }
+        let counter: Counter = Counter();
+        counter.count = 0;
+        let names: Array<String> = [\"a\", \"b\", \"c\"];
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 1000) {
+            let doubled: i32 = double(i);
+            total = total + doubled;
+            bump(counter);
+            i = i + 1;
+        }
+        assert(total == 999000);
+        assert(counter.count == 1000);
+        assert(names[2] == \"c\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_function_argument_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn pick<T>(a: T, b: T) -> T {
+            return a;
+        }
+        pick(1, \"a\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_function_return_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first<T>(a: Array<T>) -> T {
+            return a[0];
+        }
+        let s: String = first([1, 2]);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_functions() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first<T>(a: Array<T>) -> T {
+            return a[0];
+        }
+        fn identity<T>(value: T) -> T {
+            return value;
+        }
+        let i: i32 = first([1, 2, 3]);
+        assert(i == 1);
+        let s: String = identity(\"a\");
+        assert(s == \"a\");
+        assert(identity(first([true])));
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_struct_field_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Wrapper<T> {
+            value: T,
+        }
+        let w: Wrapper<i32> = Wrapper { value: 1 };
+        w.value = true;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_struct_literal() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Pair<T> {
+            first: T,
+            second: T,
+        }
+        let pair: Pair<String> = Pair { first: \"a\", second: \"b\" };
+        assert(pair.first == \"a\");
+        assert(pair.second == \"b\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_struct_literal_empty_array_field() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Stack<T> {
+            items: Array<T>,
+        }
+        let stack: Stack<i32> = Stack { items: [] };
+        stack.items = [1, 2];
+        let items = stack.items;
+        assert(items[1] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_struct_type_argument_count_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Wrapper<T> {
+            value: T,
+        }
+        let w: Wrapper<i32, bool> = Wrapper { value: 1 };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_struct_with_a_generic_impl() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Wrapper<T> {
+            fn get(self: Wrapper<T>) -> T {
+                return self.value;
+            }
+            fn set(self: Wrapper<T>, value: T) -> () {
+                self.value = value;
+            }
+        }
+        let number: Wrapper<i32> = Wrapper { value: 0 };
+        number.set(5);
+        assert(number.get() == 5);
+        let name: Wrapper<String> = Wrapper { value: \"\" };
+        name.set(\"lang\");
+        assert(name.get() == \"lang\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_type_parameter_used_as_a_concrete_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn to_int<T>(a: T) -> i32 {
+            return a;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_variants_with_annotations() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Option<String> = None;
+        a = Some(\"x\");
+        let b: Result<i32, i32> = Err(3);
+        let c = Some(1);
+        assert(a == Some(\"x\"));
+        assert(b == Err(3));
+        assert(c == Some(1));
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn hex_octal_and_binary_literals() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u8 = 0xFF;
+        assert(a == 255u8);
+        assert(0xdead_beefu32 == 3735928559u32);
+        assert(0o777 == 511);
+        assert(0b1010_1010 == 170);
+        assert(0b1000_0000u8 == 128u8);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn if_expression() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: bool = true;
+        let x: i32 = if (a) { 1 } else { 2 };
+        assert(x == 1);
+        let y: i32 = if (!a) { 1 } else { 2 };
+        assert(y == 2);
+        let z: i32 = if (x > y) { x } else if (x == y) { 0 } else { y * 10 };
+        assert(z == 20);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn if_expression_branches_with_statements() {
+        let mut lang = Lang::new(Some(
+            "
+        let n: i32 = 4;
+        let description: String = if (n % 2 == 0) {
+            let half: i32 = n / 2;
+            assert(half == 2);
+            \"even\"
+        } else {
+            \"odd\"
+        };
+        assert(description == \"even\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn if_expression_incompatible_branch_types_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: bool = true;
+        let x: i32 = if (a) { 1 } else { \"two\" };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn if_expression_without_else_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: bool = true;
+        let x: i32 = if (a) { 1 };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn if_statement_with_block_branches() {
+        let mut lang = Lang::new(Some(
+            "
+        let counter: i32 = 0;
+        if (counter == 0) {
+            counter = 1;
+        } else {
+            counter = 2;
+        }
+        if (counter == 1) {
+            counter += 10;
+        }
+        assert(counter == 11);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn infer_a_variable_from_err_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = Err(3);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn infer_a_variable_from_none_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = None;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn integer_arithmetic() {
+        let mut lang = Lang::new(Some(
+            "
+        let q: i32 = 7 / 2;
+        assert(q == 3);
+        let r: i32 = 7 - 10;
+        assert(r == -3);
+        let p: i32 = 6 * 7;
+        assert(p == 42);
+        let m: i64 = 4294967296 / 2;
+        assert(m == 2147483648);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn integer_literal_suffixes() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 255u8;
+        let b = 100i8 + 27i8;
+        let c: u64 = 1u64 + 2u8;
+        assert(a == 255);
+        assert(b == 127);
+        assert(c == 3);
+        let d: i16 = 200u8 + 1i16;
+        assert(d == 201);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn integer_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 2147483647;
+        let b = a + 1;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn integer_suffix_on_float_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1.5i32;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn integer_width_casts() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(300 as u8 == 44);
+        assert(-1 as u8 == 255);
+        assert(255u8 as i8 == -1);
+        assert(-1i8 as u64 == 18446744073709551615u64);
+        assert(70000 as i16 == 4464);
+        assert(-5.7 as u8 == 0);
+        assert(300.0 as u8 == 255);
+        assert('A' as u8 == 65);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn integer_widths() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u8 = 255;
+        let b: i8 = -128;
+        let c: i16 = -32768;
+        let d: u16 = 65535;
+        let e: u32 = 4294967295;
+        let f: u64 = 18446744073709551615;
+        let g: usize = 7;
+        assert(a == 255);
+        assert(b == -128);
+        assert(c == -32768);
+        assert(d == 65535);
+        assert(e == 4294967295);
+        assert(f == 18446744073709551615u64);
+        assert(g == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn invalid_digit_for_radix_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 0b102;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn iterate_over_a_non_iterable_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        for x in 5 {
+            print x;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn labeled_break_and_continue() {
+        let mut lang = Lang::new(Some(
+            "
+        let pairs: i32 = 0;
+        'outer: for (let i: i32 = 0; i < 5; i += 1) {
+            for (let j: i32 = 0; j < 5; j += 1) {
+                if (j > i) {
+                    continue 'outer;
+                }
+                if (i == 3) {
+                    break 'outer;
+                }
+                pairs += 1;
+            }
+        }
+        assert(pairs == 6);
+        let count: i32 = 0;
+        'rows: while (true) {
+            while (true) {
+                count += 1;
+                if (count == 3) {
+                    break 'rows;
+                }
+            }
+        }
+        assert(count == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn labeled_for_in() {
+        let mut lang = Lang::new(Some(
+            "
+        let pairs: i32 = 0;
+        'outer: for i in 0..5 {
+            for j in [0, 1, 2, 3, 4] {
+                if (j > i) {
+                    continue 'outer;
+                }
+                if (i == 3) {
+                    break 'outer;
+                }
+                pairs += 1;
+            }
+        }
+        assert(pairs == 6);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn let_type_inference() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        fn double(a: i32) -> i32 {
+            return a + a;
+        }
+        let a = 1;
+        let b = 2.5;
+        let s = \"hi\";
+        let c = 'c';
+        let t = true;
+        let d = double(a);
+        let p = Point();
+        p.x = 3;
+        let arr = [1, 2, 3];
+        a = 5;
+        assert(a == 5);
+        assert(b == 2.5);
+        assert(s == \"hi\");
+        assert(c == 'c');
+        assert(t);
+        assert(d == 2);
+        assert(p.x == 3);
+        assert(arr[2] == 3);
+        let q: Point = p;
+        let copy: Array<i32> = arr;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn let_type_inference_empty_array_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = [];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn let_type_inference_reassignment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1;
+        a = \"not a number\";
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn let_without_type_or_initializer_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn literal_out_of_range_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u8 = 256;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn long_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i64 = 9223372036854775807;
+        let b = a * 2;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn loop_breaks_with_incompatible_values_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let flag: bool = true;
+        let a: i32 = loop {
+            if (flag) {
+                break 1;
+            }
+            break \"one\";
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn loop_with_break_value() {
+        let mut lang = Lang::new(Some(
+            "
+        let n: i32 = 0;
+        let square: i32 = loop {
+            n += 1;
+            if (n * n > 50) {
+                break n * n;
+            }
+        };
+        assert(square == 64);
+        fn find(target: i32) -> i32 {
+            let attempts: i32 = 0;
+            'search: loop {
+                let guess: i32 = 0;
+                loop {
+                    attempts += 1;
+                    if (guess == target) {
+                        break 'search attempts;
+                    }
+                    if (guess > target) {
+                        break;
+                    }
+                    guess += 1;
+                }
+            }
+        }
+        assert(find(4) == 5);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn map_empty_literal_without_a_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [:];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_entry_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [\"a\": 1, \"b\": true];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_float_key_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [1.5: 2];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_index_key_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [\"a\": 1];
+        print m[1];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_insert_value_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [\"a\": 1];
+        m.insert(\"b\", \"c\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_iteration() {
+        let mut lang = Lang::new(Some(
+            "
+        let scores = [\"a\": 1, \"b\": 2, \"c\": 3];
+        scores.remove(\"b\");
+        let keys = scores.keys();
+        let values = scores.values();
+        let total = 0;
+        let i = 0;
+        while (i < scores.len()) {
+            assert(scores[keys[i]] == values[i]);
+            total = total + values[i];
+            i = i + 1;
+        }
+        assert(total == 4);
+        assert(keys[1] == \"c\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn map_keys_of_each_hashable_type() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        enum Color { Red, Green }
+        let ids = [1: \"one\", 2: \"two\"];
+        assert(ids[2] == \"two\");
+        let letters = ['a': 1, 'b': 2];
+        assert(letters['b'] == 2);
+        let flags = [true: \"yes\", false: \"no\"];
+        assert(flags[1 == 1] == \"yes\");
+        let p = Point { x: 1, y: 2 };
+        let q = Point { x: 2, y: 1 };
+        let names: Map<Point, String> = [p: \"p\"];
+        names[q] = \"q\";
+        let r = p;
+        assert(names[r] == \"p\");
+        assert(names.len() == 2);
+        let colors = [Color::Red: 1];
+        colors[Color::Green] = 2;
+        assert(colors[Color::Green] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn map_literals_indexing_and_methods() {
+        let mut lang = Lang::new(Some(
+            "
+        let scores: Map<String, i32> = [\"alice\": 3, \"bob\": 5];
+        assert(scores[\"bob\"] == 5);
+        scores[\"carol\"] = 7;
+        scores.insert(\"alice\", 4);
+        assert(scores[\"alice\"] == 4);
+        assert(scores.len() == 3);
+        assert(scores.contains(\"bob\"));
+        assert(scores.remove(\"bob\"));
+        assert(!scores.remove(\"bob\"));
+        assert(!scores.contains(\"bob\"));
+        let empty: Map<i64, bool> = [:];
+        assert(empty.len() == 0);
+        let copy: Map<String, i32> = [\"carol\": 7, \"alice\": 4];
+        assert(scores == copy);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn map_missing_key_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [\"a\": 1];
+        print m[\"b\"];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_missing_struct_key_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        let m: Map<Point, i32> = [Point { x: 1, y: 2 }: 5];
+        print m[Point { x: 2, y: 1 }];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_struct_keys_compare_by_their_fields() {
+        let mut lang = Lang::new(Some("
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        struct Segment {
+            from: Point,
+            to: Point
+        }
+        let m: Map<Point, i32> = [Point { x: 1, y: 2 }: 5];
+        assert(m[Point { x: 1, y: 2 }] == 5);
+        assert(m.contains(Point { x: 1, y: 2 }));
+        assert(!m.contains(Point { x: 2, y: 1 }));
+        m[Point { x: 1, y: 2 }] = 6;
+        assert(m.len() == 1);
+        assert(m[Point { x: 1, y: 2 }] == 6);
+        let p = Point { x: 3, y: 4 };
+        m[p] = 7;
+        p.x = 1;
+        assert(m[Point { x: 3, y: 4 }] == 7);
+        let lengths: Map<Segment, i32> = [Segment { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }: 5];
+        assert(lengths[Segment { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }] == 5);
+        let pairs: Map<(Point, i32), bool> = [(Point { x: 1, y: 1 }, 1): true];
+        assert(pairs[(Point { x: 1, y: 1 }, 1)]);
+        assert(m.remove(Point { x: 1, y: 2 }));
+        assert(m.len() == 1);
+        "));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn match_arms_of_incompatible_types_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape { Circle, Square }
+        let shape = Shape::Circle;
+        let sides: i32 = match shape {
+            Shape::Circle => \"none\",
+            Shape::Square => 4
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_expression_on_enum_variants() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32),
+            Empty
+        }
+        fn size(shape: Shape) -> i32 {
+            return match shape {
+                Shape::Circle(r) => r + r,
+                Shape::Rect(w, h) => w + h,
+                Shape::Empty => 0,
+            };
+        }
+        assert(size(Shape::Circle(2)) == 4);
+        assert(size(Shape::Rect(2, 5)) == 7);
+        assert(size(Shape::Empty) == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn match_literal_without_wildcard_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let n: i32 = 3;
+        let name: String = match n {
+            1 => \"one\",
+            3 => \"three\",
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_missing_variant_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        match shape {
+            Shape::Circle(r) => {
+                print r;
+            }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_nested_and_binding_patterns() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Inner {
+            A,
+            B(i32)
+        }
+        enum Outer {
+            Wrap(Inner),
+            Nothing
+        }
+        let value: Outer = Outer::Wrap(Inner::B(7));
+        let result: i32 = match value {
+            Outer::Wrap(Inner::B(x)) => x,
+            Outer::Wrap(_) => 1,
+            other => 0,
+        };
+        assert(result == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn match_pattern_arity_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        let n: i32 = match shape {
+            Shape::Circle(a, b) => a,
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_refutable_field_pattern_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        let area: i32 = match shape {
+            Shape::Circle(r) => r,
+            Shape::Rect(1, h) => h,
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_statement_with_block_arms() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Green,
+            Blue
+        }
+        let color: Color = Color::Green;
+        let seen: i32 = 0;
+        match color {
+            Color::Red => {
+                seen = 1;
+            }
+            Color::Green => {
+                seen = 2;
+            }
+            Color::Blue => {
+                seen = 3;
+            }
+        }
+        assert(seen == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn match_undefined_variant_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red
+        }
+        let color: Color = Color::Red;
+        let n: i32 = match color {
+            Color::Red => 1,
+            Color::Purple => 2,
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_wildcard_and_literal_patterns() {
+        let mut lang = Lang::new(Some(
+            "
+        let n: i32 = 3;
+        let name: String = match n {
+            1 => \"one\",
+            3 => \"three\",
+            _ => \"many\",
+        };
+        assert(name == \"three\");
+        let flag: bool = false;
+        let as_int: i32 = match flag {
+            true => 1,
+            false => 0,
+        };
+        assert(as_int == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn negation_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = -2147483647 - 1;
+        let b = -a;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn negative_literals_take_their_suffix_type() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = -128i8;
+        let b: i8 = a;
+        let c: i8 = -0x80;
+        assert(b == c);
+        let d = -2.5e1f32;
+        let e: f32 = d;
+        assert(e == -25.0f32);
+        let f = -9223372036854775808;
+        let g: i64 = f;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn nested_generic_type_arguments() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Option<Option<i32>> = Some(Some(1));
+        match a {
+            Some(Some(value)) => { assert(value == 1); }
+            _ => { assert(false); }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn numeric_casts() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: f64 = 7 as f64;
+        assert(x == 7.0);
+        let t: i32 = 3.9 as i32;
+        assert(t == 3);
+        let n: i32 = -3.9 as i32;
+        assert(n == -3);
+        let big: i64 = 4294967297;
+        assert(big as i32 == 1);
+        assert(3000000000.0 as i32 == 2147483647);
+        assert('a' as i32 == 97);
+        assert(true as i64 == 1);
+        let half: f32 = 1 as f32 / 2 as f32;
+        assert(half == 0.5);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn numeric_promotion() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 2147483647;
+        let b: i64 = 1;
+        let c: i64 = a + b;
+        assert(c == 2147483648);
+        let x: f32 = 1.5;
+        let y: f64 = 2.25;
+        let z: f64 = x + y;
+        assert(z == 3.75);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn option_and_result_constructors() {
+        let mut lang = Lang::new(Some(
+            "
+        let some: Option<i32> = Some(3);
+        let none: Option<i32> = None;
+        assert(some == Option::Some(3));
+        assert(some != none);
+        let ok: Result<i32, String> = Ok(1);
+        let err: Result<i32, String> = Err(\"failed\");
+        assert(ok != err);
+        let found = match some {
+            Some(n) => n + 1,
+            None => 0,
+        };
+        assert(found == 4);
+        let message = match err {
+            Ok(_) => \"fine\",
+            Err(reason) => reason,
+        };
+        assert(message == \"failed\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn option_payload_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Option<i32> = Some(\"x\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn option_propagation() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first_even(a: i32, b: i32) -> Option<i32> {
+            if (a == 2) {
+                return Some(a);
+            }
+            if (b == 4) {
+                return Some(b);
+            }
+            return None;
+        }
+        fn plus_one(a: i32, b: i32) -> Option<i32> {
+            let n = first_even(a, b)?;
+            return Some(n + 1);
+        }
+        assert(plus_one(2, 1) == Some(3));
+        assert(plus_one(1, 1) == None);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_arithmetic_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 {
+            x: i32,
+            y: i32,
+        }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x, y: self.y + other.y };
+            }
+        }
+        impl Sub for Vec2 {
+            fn sub(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x + 1, y: self.y + other.y + 1 };
+            }
+        }
+        let a = Vec2 { x: 1, y: 2 };
+        let b = Vec2 { x: 3, y: 4 };
+        let c: Vec2 = a + b;
+        assert(c.x == 4);
+        assert(c.y == 6);
+        let d: Vec2 = a + b + c;
+        assert(d.x == 8);
+        let e: Vec2 = a - b;
+        assert(e.y == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_comparison_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Version {
+            major: i32,
+            minor: i32,
+        }
+        impl Eq for Version {
+            fn eq(self: Version, other: Version) -> bool {
+                return self.major == other.major and self.minor == other.minor;
+            }
+        }
+        impl Ord for Version {
+            fn cmp(self: Version, other: Version) -> i32 {
+                if (self.major != other.major) {
+                    if (self.major < other.major) {
+                        return -1;
+                    }
+                    return 1;
+                }
+                if (self.minor < other.minor) {
+                    return -1;
+                }
+                if (self.minor > other.minor) {
+                    return 1;
+                }
+                return 0;
+            }
+        }
+        let old = Version { major: 1, minor: 4 };
+        let new = Version { major: 2, minor: 0 };
+        assert(old == Version { major: 1, minor: 4 });
+        assert(old != new);
+        assert(old < new);
+        assert(old <= old);
+        assert(new > old);
+        assert(new >= old);
+        assert(false == (new < old));
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_index_operator() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Polynomial {
+            degree: i32,
+            coefficients: Array<i32>,
+        }
+        impl Index<i32, i32> for Polynomial {
+            fn index(self: Polynomial, power: i32) -> i32 {
+                if (power > self.degree) {
+                    return 0;
+                }
+                let coefficients = self.coefficients;
+                return coefficients[power];
+            }
+        }
+        let p = Polynomial { degree: 2, coefficients: [3, 0, 2] };
+        let constant: i32 = p[0];
+        assert(constant == 3);
+        assert(p[2] == 2);
+        assert(p[7] == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_operators_on_generic_structs() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Eq for Wrapper<T> {
+            fn eq(self: Wrapper<T>, other: Wrapper<T>) -> bool {
+                return self.value == other.value;
+            }
+        }
+        impl<T> Index<i32, T> for Wrapper<T> {
+            fn index(self: Wrapper<T>, index: i32) -> T {
+                return self.value;
+            }
+        }
+        let a: Wrapper<String> = Wrapper { value: \"a\" };
+        let b: Wrapper<String> = Wrapper { value: \"a\" };
+        assert(a == b);
+        let value: String = a[0];
+        assert(value == \"a\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_remainder_and_bitwise_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Bits { value: i32 }
+        impl Rem for Bits {
+            fn rem(self: Bits, other: Bits) -> Bits {
+                return Bits { value: self.value % other.value };
+            }
+        }
+        impl BitOr for Bits {
+            fn bitor(self: Bits, other: Bits) -> Bits {
+                return Bits { value: self.value | other.value };
+            }
+        }
+        let a = Bits { value: 12 };
+        let b = Bits { value: 5 };
+        assert((a % b).value == 2);
+        a |= b;
+        assert(a.value == 13);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn range_values() {
+        let mut lang = Lang::new(Some(
+            "
+        let r: Range<i64> = 2..4i64;
+        let total: i64 = 0;
+        for i in r {
+            total += i;
+        }
+        assert(total == 5);
+        for i in r {
+            total += i;
+        }
+        assert(total == 10);
+        assert(r == (2..4i64));
+        print r;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn range_with_float_bounds_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let r = 0.5..2.5;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn raw_and_multi_line_strings() {
+        let mut lang = Lang::new(Some(
+            "
+        let raw = r\"a\\n\";
+        let count: i32 = 0;
+        for c in raw {
+            count += 1;
+        }
+        assert(count == 3);
+        assert(r#\"say \"hi\"\"# == \"say \\\"hi\\\"\");
+        let lines = \"one
+two\";
+        assert(lines == \"one\\ntwo\");
+        let joined = \"one \\
+                      two\";
+        assert(joined == \"one two\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn redeclaring_a_variable_in_the_same_scope_makes_a_new_one() {
+        let mut lang = Lang::new(Some(
+            "
+        fn twice_plus_one(x: i32) -> i32 {
+            let x: i32 = x * 2;
+            let x: i32 = x + 1;
+            return x;
+        }
+        assert(twice_plus_one(3) == 7);
+        {
+            let a: i32 = 1;
+            fn show() -> i32 {
+                return a;
+            }
+            let a: String = \"a\";
+            assert(show() == 1);
+            assert(a == \"a\");
+        }
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 3) {
+            let step: i32 = i;
+            let step: i32 = step + 10;
+            total = total + step;
+            i = i + 1;
+        }
+        assert(total == 33);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn remainder() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(17 % 5 == 2);
+        assert(-7 % 3 == -1);
+        assert(7.5 % 2.0 == 1.5);
+        let a: u8 = 200;
+        assert(a % 7u8 == 4);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn remainder_by_zero_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 5;
+        let b = 0;
+        let c = a % b;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn result_propagation() {
+        let mut lang = Lang::new(Some(
+            "
+        fn parse(text: String) -> Result<i32, String> {
+            if (text == \"one\") {
+                return Ok(1);
+            }
+            return Err(\"not a number\");
+        }
+        fn sum(a: String, b: String) -> Result<i32, String> {
+            return Ok(parse(a)? + parse(b)?);
+        }
+        assert(sum(\"one\", \"one\") == Ok(2));
+        assert(sum(\"one\", \"two\") == Err(\"not a number\"));
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn return_from_block() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test() -> i32 {
+            {
+                return 100;
+            }
+        }
+        let value: i32 = test();
+        assert(value == 100);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn return_from_inside_a_while_loop() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first_multiple(of: i32, above: i32) -> i32 {
+            let candidate: i32 = above;
+            while (true) {
+                if (candidate % of == 0) {
+                    return candidate;
+                }
+                candidate += 1;
+            }
+            return -1;
+        }
+        assert(first_multiple(7, 20) == 21);
+        fn nested(limit: i32) -> i32 {
+            for (let i: i32 = 0; i < limit; i += 1) {
+                for (let j: i32 = 0; j < limit; j += 1) {
+                    if (i * j == 6) return i * 10 + j;
+                }
+            }
+            return 0;
+        }
+        assert(nested(5) == 23);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn self_in_impls() {
+        let mut lang = Lang::new(Some(
+            "
+        trait Double {
+            fn double(self: Self) -> Self;
+        }
+        struct Point {
+            x: i32,
+        }
+        impl Double for Point {
+            fn double(self: Self) -> Self {
+                let doubled: Self = Point { x: self.x * 2 };
+                return doubled;
+            }
+        }
+        impl Point {
+            fn plus(self: Self, other: Self) -> Self {
+                return Point { x: self.x + other.x };
+            }
+        }
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Wrapper<T> {
+            fn same(self: Self) -> Self {
+                return self;
+            }
+        }
+        let point = Point { x: 3 };
+        let doubled = point.double();
+        assert(doubled.x == 6);
+        let sum = doubled.plus(Point { x: 1 });
+        assert(sum.x == 7);
+        let wrapper: Wrapper<i32> = Wrapper { value: 4 };
+        let same = wrapper.same();
+        assert(same.value == 4);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn self_in_impls_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        struct Other {
+            x: i32,
+        }
+        impl Point {
+            fn other(self: Self) -> Self {
+                return Other { x: self.x };
+            }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn shadowed_variables_in_nested_blocks() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        let b: i32 = 0;
+        {
+            b = 7;
+            let a: i32 = 2;
+            let b: i32 = 9;
+            {
+                let a: i32 = 3;
+                assert(a == 3);
+                b = 10;
+            }
+            assert(a == 2);
+            assert(b == 10);
+        }
+        assert(a == 1);
+        assert(b == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn shift_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(1 << 4 == 16);
+        assert(-16 >> 2 == -4);
+        let a: u8 = 3;
+        assert(a << 7 == 128u8);
+        let b: u64 = 18446744073709551615;
+        assert(b >> 60u8 == 15u64);
+        let c: i64 = 1;
+        assert(c << 40u8 == 1099511627776);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn shift_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 32;
+        let b = 1 << a;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn signed_literal_past_its_maximum_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 128i8;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn small_integer_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i8 = 100;
+        let b = a * 2i8;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn string_invalid_escape_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = \"\\q\";
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn string_literal_escapes() {
+        let mut lang = Lang::new(Some(
+            "
+        let count: i32 = 0;
+        for c in \"\\t\\r\\n\\0\\\\\\\"\\'\" {
+            count += 1;
+        }
+        assert(count == 7);
+        assert(\"\\u{48}i\" == \"Hi\");
+        assert('\\u{41}' == 'A');
+        assert('\\'' != '\\\"');
+        let tab: char = 'a';
+        for c in \"a\\tb\" {
+            if (c != 'a' and c != 'b') {
+                tab = c;
+            }
+        }
+        assert(tab == '\\t');
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn string_literal_punctuation_and_unicode() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = \"Hi, world! ¿Qué tal? ☃\";
+        let count: i32 = 0;
+        for c in a {
+            count += 1;
+        }
+        assert(count == 22);
+        assert(a != \"Hi world\");
+        assert(\"  padded \" != \"padded\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn string_unterminated_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = \"abc;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_constructor_call_arguments_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        let p = Point(1);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_constructor_call_field_defaults() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32 = 7,
+            y: i32,
+            label: String,
+        }
+        let p = Point();
+        assert(p.x == 7);
+        assert(p.y == 0);
+        assert(p.label == \"\");
+        let q = Point();
+        q.x = 1;
+        assert(p.x == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_constructor_call_missing_field_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Inner {
+            x: i32,
+        }
+        struct Outer {
+            inner: Inner,
+        }
+        let o = Outer();
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_declaration() {
+        let mut lang = Lang::new(Some("struct TestStruct {}"));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_declaration_failure() {
+        let mut lang = Lang::new(Some("struct TestStruct {{}"));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_declaration_with_fields() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            field0: i64,
+            field1: f64,
+            field2: bool
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_field_default_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32 = \"zero\",
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_formats_as_its_name() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        let p = Point { x: 1, y: 2 };
+        assert(f\"{p}\" == \"Point\");
+        assert(f\"{p:?}\" == \"Point { x: 1, y: 2 }\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_literal_duplicate_field_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: 1, x: 2 };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_literal_empty_collection_fields() {
+        let mut lang = Lang::new(Some(
+            "
+        fn empty() -> Inventory {
+            return Inventory { items: [], counts: [:] };
+        }
+        struct Inventory {
+            items: Array<String>,
+            counts: Map<String, i32>,
+        }
+        let inventory = Inventory { items: [], counts: [:] };
+        inventory.items = [\"apple\"];
+        let items = inventory.items;
+        assert(items[0] == \"apple\");
+        let counts = empty().counts;
+        counts[\"apple\"] = 2;
+        assert(counts[\"apple\"] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_literal_field_defaults() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Config {
+            name: String,
+            retries: i32 = 3,
+            verbose: bool = false,
+        }
+        let config = Config { name: \"default\" };
+        assert(config.retries == 3);
+        assert(config.verbose == false);
+        let custom = Config { verbose: true, name: \"custom\", retries: 5 };
+        assert(custom.retries == 5);
+        assert(custom.verbose);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_literal_field_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: true };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_literal_missing_field_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32 = 0,
+        }
+        let p = Point { y: 1 };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_literal_unknown_field_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: 1, z: 2 };
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_literals() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        fn origin() -> Point {
+            return Point { x: 0, y: 0 };
+        }
+        let x = 3;
+        let p = Point { y: 4, x: x };
+        assert(p.x == 3);
+        assert(p.y == 4);
+        let o: Point = origin();
+        assert(o.x == 0);
+        o.x = 1;
+        assert(o.x == 1);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_field_access_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {}
+
+        let instance: TestStruct = TestStruct();
+        instance.field_doesnt_exist;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_with_fields_instatiation() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            field0: i64,
+            field1: f64,
+            field2: bool
+        }
+
+        let instance: TestStruct = TestStruct();
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_fields_instatiation_and_field_assignment() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            field0: i64,
+            field1: f64,
+            field2: bool
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.field0 = 0;
+        instance.field1 = 1.00;
+        instance.field2 = false;
+        assert(instance.field0 == 0);
+        assert(instance.field1 == 1.00);
+        assert(instance.field2 == false);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_impl() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+        }
+
+        impl TestStruct {
+            fn hello() -> () {
+                print \"Hello world\";
+            }
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.hello();
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_impl_using_mutable_self() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            i: i32,
+        }
+
+        impl TestStruct {
+            fn hello(self: TestStruct, other: TestStruct) -> () {
+                self.i = self.i + other.i;
+            }
+        }
+
+        let instance: TestStruct = TestStruct();
+        let other: TestStruct = TestStruct();
+        other.i = 100;
+        instance.i = 100;
+        instance.hello(other);
+        assert(instance.i == 200);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_impl_using_self() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            i: i32,
+        }
+
+        impl TestStruct {
+            fn hello() -> () {
+                print self.i;
+            }
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.i = 100;
+        assert(instance.i == 100);
+        instance.hello();
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_method_call_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+        }
+
+        impl TestStruct {
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.hello();
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn suffixed_literal_out_of_range_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 128i8;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn trait_implementation() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+            fn double(a: i32) -> i32 {
+                return a + a;
+            }
+        }
+        let point: Point = Point();
+        let doubled: i32 = point.double(3);
+        assert(doubled == 6);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn try_in_a_function_not_returning_an_option_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f(a: Option<i32>) -> i32 {
+            return a?;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn try_on_a_non_option_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f(n: i32) -> Option<i32> {
+            return Some(n?);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn try_on_a_variant_of_a_different_enum_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first() -> Option<i32> {
+            let value: i32 = Err(1)?;
+            return Some(value);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn try_outside_a_function_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Option<i32> = Some(1);
+        let b = a?;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn try_with_a_different_error_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f(a: Result<i32, String>) -> Result<i32, bool> {
+            return Ok(a?);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn tuple_destructure_arity_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let (a, b, c) = (1, 2);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn tuple_destructuring() {
+        let mut lang = Lang::new(Some(
+            "
+        fn pair(name: String) -> (String, i32) {
+            return (name, 2);
+        }
+        let (name, count) = pair(\"apples\");
+        assert(name == \"apples\");
+        assert(count == 2);
+        fn swap(p: (i32, String)) -> (String, i32) {
+            let (number, text) = p;
+            return (text, number);
+        }
+        let swapped = swap((1, \"one\"));
+        assert(swapped.0 == \"one\");
+        assert(swapped.1 == 1);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn tuple_field_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let t = (1, 2);
+        t.0 = 3;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn tuple_field_out_of_range_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let t = (1, 2);
+        print t.2;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn tuple_literals_and_fields() {
+        let mut lang = Lang::new(Some(
+            "
+        fn min_max(a: i32, b: i32) -> (i32, i32) {
+            if (a < b) {
+                return (a, b);
+            }
+            return (b, a);
+        }
+        let bounds = min_max(7, 3);
+        assert(bounds.0 == 3);
+        assert(bounds.1 == 7);
+        let nested = ((1, 2), \"three\");
+        assert(nested.0.1 == 2);
+        assert(nested.1 == \"three\");
+        assert((1, \"a\") == (1, \"a\"));
+        assert((1, \"a\") != (1, \"b\"));
+        let empty: (i32, bool);
+        assert(empty == (0, false));
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn tuple_literals_widen_to_the_declared_type() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f() -> (i64, String) {
+            return (1, \"a\");
+        }
+        fn g() -> (i64, (f64, u32)) {
+            (2, (1.5, 3u8))
+        }
+        let t: (i64, String) = (1, \"a\");
+        assert(f() == t);
+        assert(g().1.1 == 3u32);
+        let (big, text) = f();
+        let wide: i64 = big + 5000000000;
+        assert(wide == 5000000001);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn tuple_map_keys() {
+        let mut lang = Lang::new(Some(
+            "
+        let grid = [(0, 0): \"origin\", (1, 2): \"point\"];
+        assert(grid[(1, 2)] == \"point\");
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn tuple_return_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f() -> (i32, String) {
+            return (\"a\", 1);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_binary_operator_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1 + true;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_cast_from_string_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = \"1\" as i32;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_cast_to_non_numeric_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1 as bool;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_mixed_int_and_float_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        let b: f64 = 2.0;
+        let c = a + b;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_narrowing_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u16 = 1;
+        let b: u8 = a;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_operator_without_trait_impl_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 {
+            x: i32,
+        }
+        let a = Vec2 { x: 1 };
+        let b: Vec2 = a + a;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_overloaded_index_result_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Grid {
+            x: i32,
+        }
+        impl Index<i32, bool> for Grid {
+            fn index(self: Grid, index: i32) -> bool {
+                return true;
+            }
+        }
+        let g = Grid { x: 1 };
+        let a: i32 = g[0];
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_overloaded_operator_operand_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 {
+            x: i32,
+        }
+        impl Mul for Vec2 {
+            fn mul(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x };
+            }
+        }
+        let a = Vec2 { x: 1 };
+        let b: Vec2 = a * 2;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_overloaded_operator_result_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 {
+            x: i32,
+        }
+        impl Div for Vec2 {
+            fn div(self: Vec2, other: Vec2) -> Vec2 {
+                return self;
+            }
+        }
+        let a = Vec2 { x: 1 };
+        let b: i32 = a / a;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_promoted_result_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        let b: i64 = 2;
+        let c: i32 = a + b;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_signed_and_unsigned_mix_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        let b: u32 = 2;
+        let c = a + b;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_trait_impl_missing_function_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {}
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_trait_impl_return_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {}
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+            fn double(a: i32) -> bool {
+                return true;
+            }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_trait_impl_self_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 {
+            x: i32,
+        }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: i32) -> Vec2 {
+                return self;
+            }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_trait_type_argument_count_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Grid {
+            x: i32,
+        }
+        impl Index<i32> for Grid {
+            fn index(self: Grid, index: i32) -> i32 {
+                return index;
+            }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_unexecuted_branch_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 0;
+        if (false) {
+            a = \"unreachable\";
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_unexecuted_call_argument_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b;
+        }
+        fn never_called() -> i32 {
+            return add(1, 2.0);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_unexecuted_call_arity_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b;
+        }
+        fn never_called() -> i32 {
+            return add(1);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_unexecuted_field_access_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        fn never_called(point: Point) -> i32 {
+            return point.y;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_unexecuted_return_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn never_called() -> i32 {
+            return \"not a number\";
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_unsigned_negation_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u8 = 1;
+        let b = -a;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn undefined_function_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn defined() -> i32 {
+            return missing(1);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn undefined_variable_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        print a;
+        print missing;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn uninitialized_char_variable_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let c: char;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn uninitialized_enum_variable_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let c: Option<i32>;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn uninitialized_function_variable_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let f: fn(i32) -> i32;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn uninitialized_struct_variable_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32
+        }
+        let p: Point;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn unsigned_arithmetic() {
+        let mut lang = Lang::new(Some(
+            "
+        let count: u32 = 10;
+        let step: u32 = 3;
+        assert(count - step == 7u32);
+        assert(count * step == 30u32);
+        assert(count / step == 3u32);
+        let bytes: u8 = 200u8;
+        let wide: u16 = bytes + 100u16;
+        assert(wide == 300);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn unsigned_map_keys() {
+        let mut lang = Lang::new(Some(
+            "
+        let counts: Map<u8, i32> = [:];
+        counts[1u8] = 3;
+        assert(counts[1u8] == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn unsigned_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u8 = 255;
+        let b = a + 1u8;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn unsigned_underflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u32 = 0;
+        let b = a - 1u32;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn unterminated_block_comment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        /* never /* closed */
+        let a: i32 = 1;
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn while_loop() {
+        let mut lang = Lang::new(Some(
+            "
+        let b: bool = true;
+        let i: i32 = 0;
+        while (b) {
+            i = i + 1;
+            if (i == 10) {
+                b = false;
+            }
+        }
+        assert(b == false);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Array<i32> = [0, 1, 2];
+        let b: Array<i32> = [0, 1, 2];
+        assert(a == b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_not_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Array<i32> = [0, 1, 2];
+        let b: Array<i32> = [0, 1, 4];
+        assert(a != b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn bool_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: bool = false;
+        let b: bool = false;
+        assert(a == b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn bool_not_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: bool = false;
+        let b: bool = true;
+        assert(a != b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn char_empty_literal_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: char = '';
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn char_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: char = 'a';
+        let b: char = 'a';
+        assert(a == b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn char_greater() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i64 = 300;
+        let b: i64 = 100;
+        assert(a > b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn char_greater_or_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: char = 'b';
+        let b: char = 'b';
+        assert(a >= b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn char_less() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: char = 'a';
+        let b: char = 'c';
+        assert(a < b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn char_less_or_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: char = 'd';
+        let b: char = 'd';
+        assert(a <= b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn char_literal_of_several_characters_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: char = 'ab';
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn char_not_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: char = 'a';
+        let b: char = 'b';
+        assert(a != b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn expression_missing_at_the_end_of_the_source_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1 +
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn f64_variable_declaration() {
+        let mut lang = Lang::new(Some("let i: f64;"));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn f64_variable_declaration_and_assignment() {
+        let mut lang = Lang::new(Some("let i: f64 = 0.00;"));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn f64_variable_re_assignment() {
+        let mut lang = Lang::new(Some(
+            "let i: f64 = 0.00;
+            assert(i == 0.00);
+            i = 100.00;
+            assert(i == 100.00);",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn f64_variable_re_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "let i: f64 = 0;
+        i = 100;",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn f64_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: f64 = 100.00;
+        let b: f64 = 100.00;
+        assert(a == b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn f64_greater() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: f64 = 300.00;
+        let b: f64 = 100.00;
+        assert(a > b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn f64_greater_or_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: f64 = 300.00;
+        let b: f64 = 300.00;
+        assert(a >= b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn f64_less() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: f64 = 300.00;
+        let b: f64 = 500.00;
+        assert(a < b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn f64_less_or_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: f64 = 300.00;
+        let b: f64 = 300.00;
+        assert(a <= b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn f64_not_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: f64 = 300.00;
+        let b: f64 = 100.00;
+        assert(a != b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn i64_variable_declaration() {
+        let mut lang = Lang::new(Some("let i: i64;"));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn i64_variable_declaration_and_assignment() {
+        let mut lang = Lang::new(Some(
+            "let i: i64 = 0;
+        assert(i == 0);",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn i64_variable_re_assignment() {
+        let mut lang = Lang::new(Some(
+            "let i: i64 = 0;
+            assert(i == 0);
+            i = 100;
+            assert(i == 100);",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn i64_variable_re_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "let i: i64 = 0;
+        i = 100.00;",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn i64_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i64 = 100;
+        let b: i64 = 100;
+        assert(a == b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn i64_greater() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i64 = 300;
+        let b: i64 = 100;
+        assert(a > b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn i64_greater_or_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i64 = 300;
+        let b: i64 = 300;
+        assert(a >= b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn i64_less() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i64 = 300;
+        let b: i64 = 500;
+        assert(a < b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn i64_less_or_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i64 = 300;
+        let b: i64 = 300;
+        assert(a <= b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn i64_not_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i64 = 300;
+        let b: i64 = 100;
+        assert(a != b);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_vars_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Test {
+            a: i32,
+            b: bool,
+            c: f64,
+        }
+        let instance: Test = Test();
+        instance.a = 100;
+        instance.b = true;
+        instance.c = 10.05;
+        assert(instance.a == 100);
+        assert(instance.b == true);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_vars_not_equal() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Test {
+            a: i32,
+            b: bool,
+            c: f64,
+        }
+        let instance: Test = Test();
+        instance.a = 100;
+        instance.b = true;
+        instance.c = 10.05;
+        assert(instance.a != 101);
+        assert(instance.b != false);
+        ",
+        ));
+        lang.set_backend(Backend::Interpreter);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+}
+
+#[cfg(test)]
+mod vm {
+    extern crate lang;
+    use self::lang::lang::{Backend, Lang};
+
+    #[test]
+    fn array_index_with_unsigned_types() {
+        let mut lang = Lang::new(Some(
+            "
+        let values = [10, 20, 30];
+        let i: usize = 2;
+        let j: u8 = 1;
+        assert(values[i] == 30);
+        assert(values[j] == 20);
+        values[0usize] = 5;
+        assert(values[0] == 5);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_literal_element_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Array<i64> = [\"x\"];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn array_negative_index_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = [1, 2];
+        let i = -1;
+        a[i] = 3;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn array_negative_index_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = [1, 2];
+        let i = -1;
+        print a[i];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn array_i64_variable_declaration() {
+        let mut lang = Lang::new(Some("let i: Array<i64>;"));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_i64_variable_declaration_and_assignment() {
+        let mut lang = Lang::new(Some(
+            "
+        let i: Array<i64> = [0, 1, 2];
+        assert(i[0] == 0);
+        assert(i[1] == 1);
+        assert(i[2] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_i64_variable_declaration_empty() {
+        let mut lang = Lang::new(Some("let i: Array<i64> = [];"));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_i64_variable_re_assignment() {
+        let mut lang = Lang::new(Some(
+            "
+        let i: Array<i64> = [];
+        i = [0, 1, 2];
+        assert(i[0] == 0);
+        assert(i[1] == 1);
+        assert(i[2] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn array_i64_variable_re_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "let i: Array<i64> = [];
+            i = [0.00, 1.00, 2.00];
+        assert(i[0] == 0.00);
+        assert(i[1] == 1.00);
+        assert(i[2] == 2.00);
+            ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn assertion() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(100 == 100);
+        assert(true == true);
+        assert(1.05 == 1.05);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn assertion_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(0 == 100);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn assertion_inside_a_function_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn check(n: i32) -> () {
+            assert(n > 0);
+        }
+        check(-1);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn bitwise_not_on_floats_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = ~1.5;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn bitwise_operator_precedence() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(1 + 2 << 1 == 6);
+        assert(6 & 3 == 2);
+        assert(1 | 2 ^ 3 & 4 == 3);
+        assert(2 * 3 % 4 == 2);
+        assert(true && false || true);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn bitwise_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        assert((6 & 3) == 2);
+        assert((6 | 3) == 7);
+        assert((6 ^ 3) == 5);
+        assert(~0 == -1);
+        let a: u8 = 5;
+        assert(~a == 250u8);
+        let b: u8 = 12;
+        let c: i64 = 10;
+        assert((b | c) == 14);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn bitwise_operators_on_floats_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1.0 & 2.0;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn block_expression() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 2;
+        let b: i32 = {
+            let a: i32 = 10;
+            a * 3
+        };
+        assert(a == 2);
+        assert(b == 30);
+        let c: i32 = { a } + { b };
+        assert(c == 32);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn break_outside_of_a_loop_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        break;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn break_to_an_unknown_label_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        while (true) {
+            break 'missing;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn break_with_a_value_out_of_a_while_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        while (true) {
+            break 1;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn closure_outlives_its_block() {
+        let mut lang = Lang::new(Some(
+            "
+        fn zero() -> i32 {
+            return 0;
+        }
+        let f: fn() -> i32 = zero;
+        {
+            let hidden: i32 = 7;
+            fn reveal() -> i32 {
+                return hidden;
+            }
+            f = reveal;
+        }
+        assert(f() == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn closure_returned_from_a_function() {
+        let mut lang = Lang::new(Some(
+            "
+        fn make_adder(n: i32) -> fn(i32) -> i32 {
+            fn add(x: i32) -> i32 {
+                return x + n;
+            }
+            return add;
+        }
+        fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+            return f(x);
+        }
+        let add5 = make_adder(5);
+        let add10: fn(i32) -> i32 = make_adder(10);
+        assert(add5(3) == 8);
+        assert(add10(3) == 13);
+        assert(apply(add5, 1) == 6);
+        assert(make_adder(1)(1) == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn closure_sees_the_binding_in_scope_at_its_declaration() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        {
+            fn show() -> i32 {
+                return a;
+            }
+            assert(show() == 1);
+            let a: i32 = 2;
+            assert(show() == 1);
+            assert(a == 2);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn closures_created_in_a_long_loop() {
+        let mut lang = Lang::new(Some(
+            "
+        fn make_adder(n: i32) -> fn(i32) -> i32 {
+            fn add(x: i32) -> i32 {
+                return x + n;
+            }
+            return add;
+        }
+        let first = make_adder(100);
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 500) {
+            let adder = make_adder(i);
+            total = total + adder(1);
+            i = i + 1;
+        }
+        assert(total == 125250);
+        assert(first(1) == 101);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn comments() {
+        let mut lang = Lang::new(Some(
+            "
+        // A line comment
+        /// A doc comment
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b; // trailing comment
+        }
+        /* A block comment
+           spanning lines /* with a nested comment */
+        */
+        /** A block doc comment */
+        let sum: i32 = add(1, /* inline */ 2);
+        assert(sum == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment() {
+        let mut lang = Lang::new(Some(
+            "
+        let i = 0;
+        i += 5;
+        i -= 1;
+        i *= 3;
+        i /= 2;
+        i %= 4;
+        assert(i == 2);
+        i <<= 3;
+        i |= 1;
+        i ^= 3;
+        i &= 30;
+        i >>= 1;
+        assert(i == 9);
+        let s = \"a\";
+        s += \"b\";
+        assert(s == \"ab\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment_element_type_mismatch_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let values = [1, 2];
+        values[0] += 1.5;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn compound_assignment_evaluates_its_target_once() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Counter { count: i32 }
+        struct Calls { count: i32 }
+        let calls = Calls { count: 0 };
+        let counter = Counter { count: 1 };
+        fn target(calls: Calls, counter: Counter) -> Counter {
+            calls.count += 1;
+            return counter;
+        }
+        fn next(calls: Calls) -> i32 {
+            calls.count += 1;
+            return calls.count - 1;
+        }
+        target(calls, counter).count += 5;
+        assert(counter.count == 6);
+        assert(calls.count == 1);
+        let values = [10, 20, 30];
+        values[next(calls)] *= 2;
+        assert(values[1] == 40);
+        assert(calls.count == 2);
+        let totals: Map<String, i32> = [\"a\": 1];
+        totals[\"a\"] += 2;
+        assert(totals[\"a\"] == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment_invalid_target_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1;
+        a + 1 += 2;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn compound_assignment_to_a_field_of_an_overloaded_type() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 { x: i32 }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x };
+            }
+        }
+        struct Body { position: Vec2 }
+        let body = Body { position: Vec2 { x: 1 } };
+        body.position += Vec2 { x: 2 };
+        let position = body.position;
+        assert(position.x == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment_to_fields_and_elements() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Counter { count: i32 }
+        let counter = Counter { count: 1 };
+        counter.count += 10;
+        counter.count <<= 1;
+        assert(counter.count == 22);
+        let values = [1, 2, 3];
+        values[1] += 40;
+        values[2] *= values[1];
+        assert(values[1] == 42);
+        assert(values[2] == 126);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn compound_assignment_type_mismatch_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1;
+        a += \"x\";
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn continue_in_while_and_for_loops() {
+        let mut lang = Lang::new(Some(
+            "
+        let i: i32 = 0;
+        let odd_sum: i32 = 0;
+        while (i < 10) {
+            i += 1;
+            if (i % 2 == 0) {
+                continue;
+            }
+            odd_sum += i;
+        }
+        assert(odd_sum == 25);
+        let even_sum: i32 = 0;
+        for (let j: i32 = 0; j < 10; j += 1) {
+            if (j % 2 == 1) {
+                continue;
+            }
+            even_sum += j;
+        }
+        assert(even_sum == 20);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn continue_outside_of_a_loop_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn skip() -> () {
+            continue;
+        }
+        while (true) {
+            skip();
+            break;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn digit_separators_and_exponents() {
+        let mut lang = Lang::new(Some(
+            "
+        let big: i64 = 1_000_000_000_000;
+        assert(big == 1000000000000i64);
+        assert(1.5e-3 == 0.0015);
+        assert(2E3 == 2000.0);
+        assert(1_0.2_5 == 10.25);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn division_by_zero_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        let b = a / 0;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn empty_arrays_of_function_values() {
+        let mut lang = Lang::new(Some(
+            "
+        fn one() -> i32 {
+            return 1;
+        }
+        fn two() -> i32 {
+            return 2;
+        }
+        let handlers: Array<fn() -> i32> = [];
+        let sum: i32 = 0;
+        for handler in handlers {
+            sum += handler();
+        }
+        assert(sum == 0);
+        handlers = [one, two];
+        for handler in handlers {
+            sum += handler();
+        }
+        assert(sum == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn empty_arrays_of_tuples() {
+        let mut lang = Lang::new(Some(
+            "
+        let pairs: Array<(i32, i32)> = [];
+        let names: Array<(String, Array<i32>)> = [];
+        let count: i32 = 0;
+        for pair in pairs {
+            count += 1;
+        }
+        for name in names {
+            count += 1;
+        }
+        assert(count == 0);
+        pairs = [(1, 2), (3, 4)];
+        assert(pairs[1].0 == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_as_function_argument_and_return_type() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Blue
+        }
+        fn is_red(color: Color) -> bool {
+            return color == Color::Red;
+        }
+        fn favourite() -> Color {
+            return Color::Blue;
+        }
+        assert(is_red(Color::Red));
+        assert(!is_red(favourite()));
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_declaration() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_duplicate_variant_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Red
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn enum_type_mismatch_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red
+        }
+        let color: Color = 1;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn enum_undefined_variant_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red
+        }
+        let color: Color = Color::Purple;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn enum_variant_comparison() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Green,
+            Blue
+        }
+        let color: Color = Color::Red;
+        assert(color == Color::Red);
+        assert(color != Color::Green);
+        color = Color::Blue;
+        assert(color == Color::Blue);
+        print color;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_variant_with_initializer() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Status {
+            Ok = 200,
+            NotFound = 404,
+            Teapot
+        }
+        assert(Status::Ok < Status::NotFound);
+        assert(Status::NotFound < Status::Teapot);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_variant_with_values() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(f64),
+            Rect(i32, i32),
+            Empty
+        }
+        let rect: Shape = Shape::Rect(2, 3);
+        assert(rect == Shape::Rect(2, 3));
+        assert(rect != Shape::Rect(3, 2));
+        assert(rect != Shape::Empty);
+        print rect;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn enum_variant_with_values_arity_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Rect(i32, i32)
+        }
+        let rect: Shape = Shape::Rect(1);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn enum_variant_with_values_wrong_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(f64)
+        }
+        let circle: Shape = Shape::Circle(\"round\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn float_arithmetic() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: f32 = 1.5;
+        let b: f32 = a * 2.0 as f32 - 0.5 as f32;
+        assert(b == 2.5);
+        let c: f64 = 9.0;
+        let d: f64 = c / 4.0;
+        assert(d == 2.25);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn float_suffixes() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 2.0f32;
+        let b: f32 = a;
+        let c = 5f64;
+        let d: f64 = c;
+        let e = 1e2f32;
+        assert(e == 100.0f32);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_break_and_continue() {
+        let mut lang = Lang::new(Some(
+            "
+        let sum: i32 = 0;
+        for i in 0..100 {
+            if (i % 2 == 0) {
+                continue;
+            }
+            if (i > 9) {
+                break;
+            }
+            sum += i;
+        }
+        assert(sum == 25);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_a_map() {
+        let mut lang = Lang::new(Some(
+            "
+        let stock: Map<String, i32> = [\"apples\": 3, \"pears\": 5];
+        let total: i32 = 0;
+        let names = \"\";
+        for name in stock {
+            names += name;
+            total += stock[name];
+            stock[name] = 0;
+        }
+        assert(names == \"applespears\");
+        assert(total == 8);
+        assert(stock[\"pears\"] == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_a_map_key_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let stock: Map<String, i32> = [\"apples\": 3];
+        for name in stock {
+            let count: i32 = name;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn for_in_over_a_string() {
+        let mut lang = Lang::new(Some(
+            "
+        let count: i32 = 0;
+        let last: char = 'a';
+        for c in \"hello\" {
+            count += 1;
+            last = c;
+        }
+        assert(count == 5);
+        assert(last == 'o');
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_an_array() {
+        let mut lang = Lang::new(Some(
+            "
+        let xs = [1, 2, 3, 4];
+        let sum: i32 = 0;
+        for x in xs {
+            sum += x;
+        }
+        assert(sum == 10);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_an_iterator() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Countdown {
+            from: i32,
+        }
+        impl Iterator<i32> for Countdown {
+            fn next(self: Countdown) -> Option<i32> {
+                if (self.from == 0) {
+                    return Option::None;
+                }
+                self.from -= 1;
+                return Option::Some(self.from + 1);
+            }
+        }
+        let seen: i32 = 0;
+        let sum: i32 = 0;
+        for n in Countdown { from: 3 } {
+            seen += 1;
+            sum += n;
+        }
+        assert(seen == 3);
+        assert(sum == 6);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_over_ranges() {
+        let mut lang = Lang::new(Some(
+            "
+        let sum: i32 = 0;
+        for i in 0..5 {
+            sum += i;
+        }
+        assert(sum == 10);
+        let inclusive: i32 = 0;
+        for i in 1..=5 {
+            inclusive += i;
+        }
+        assert(inclusive == 15);
+        let empty: i32 = 0;
+        for i in 5..5 {
+            empty += 1;
+        }
+        assert(empty == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_variable_is_scoped_to_its_body() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: i32 = 10;
+        for x in 0..3 {
+            assert(x < 3);
+        }
+        assert(x == 10);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn for_in_variable_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        for c in \"abc\" {
+            let i: i32 = c;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn for_loop() {
+        let mut lang = Lang::new(Some(
+            "
+        for (let i: i32 = 0; i < 10; i = i + 1) {
+            print i;
+        }
+        for (let b: bool = false; b == true; b = false) {
+            print b;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_debug() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: f64,
+            label: String,
+        }
+        enum Shape { Circle(f64), Empty }
+        let p: Point = Point { x: 1, y: 2.0, label: \"origin\" };
+        assert(f\"{p:?}\" == \"Point { x: 1, y: 2.0, label: \\\"origin\\\" }\");
+        assert(f\"{p}\" == \"Point\");
+        assert(f\"{[1, 2, 3]:?} {[1, 2, 3]}\" == \"[1, 2, 3] 1 2 3\");
+        assert(f\"{(1, \"a\"):?} {(1, \"a\")}\" == \"(1, \\\"a\\\") (1, a)\");
+        assert(f\"{Shape::Circle(1.5):?} {Shape::Empty:?}\" == \"Shape::Circle(1.5) Shape::Empty\");
+        assert(f\"{'c':?} {'c'} {1.0:?} {1.0} {true:?}\" == \"'c' c 1.0 1 true\");
+        assert(f\"{[1: \"a\"]:?}\" == \"[1: \\\"a\\\"]\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_display_trait() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        impl Display for Point {
+            fn fmt(self: Point) -> String {
+                return f\"({self.x}, {self.y})\";
+            }
+        }
+        let p: Point = Point { x: 1, y: 2 };
+        assert(f\"p = {p}\" == \"p = (1, 2)\");
+        assert(f\"[{p:>8}]\" == \"[  (1, 2)]\");
+        assert(f\"{p:?}\" == \"Point { x: 1, y: 2 }\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_interpolation() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: i32 = 3;
+        let name: String = \"lang\";
+        assert(f\"x = {x}, name = {name}\" == \"x = 3, name = lang\");
+        assert(f\"{x + 1} {x * x}\" == \"4 9\");
+        assert(f\"{{x}} {{{x}}}\" == \"{x} {3}\");
+        assert(f\"{f\"{x}{x}\"}!\" == \"33!\");
+        assert(f\"\" == \"\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_radix() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: i32 = 255;
+        assert(f\"{x:x} {x:X} {x:#x}\" == \"ff FF 0xff\");
+        assert(f\"{x:b} {8:o} {5:#06b}\" == \"11111111 10 0b0101\");
+        assert(f\"{-1i8:x} {-2:X}\" == \"ff FFFFFFFE\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn format_string_radix_of_a_float_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let y: f64 = 1.5;
+        print f\"{y:x}\";
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn format_string_unknown_spec_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        print f\"{1:q}\";
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn format_string_unmatched_brace_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        print f\"a } b\";
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn format_string_width_precision_and_alignment() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: i32 = 42;
+        let y: f64 = 3.14159;
+        assert(f\"{y:.2}\" == \"3.14\");
+        assert(f\"[{x:6}]\" == \"[    42]\");
+        assert(f\"[{x:<6}]\" == \"[42    ]\");
+        assert(f\"[{x:^6}]\" == \"[  42  ]\");
+        assert(f\"[{x:*^7}]\" == \"[**42***]\");
+        assert(f\"[{x:+}] [{x:06}] [{-x:06}]\" == \"[+42] [000042] [-00042]\");
+        assert(f\"[{y:8.3}]\" == \"[   3.142]\");
+        assert(f\"[{\"ab\":5}] [{\"abc\":.2}] [{\"ab\":>4}]\" == \"[ab   ] [ab] [  ab]\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_assert_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test(a: i32, b: f64) -> bool {
+            if (!(a > 1000)) {
+                assert(false);
+            }
+            return false;
+        }
+        test(1, 0.0);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_early_return_before_trailing_expression() {
+        let mut lang = Lang::new(Some(
+            "
+        fn clamp(n: i32) -> i32 {
+            if (n > 10) {
+                return 10;
+            }
+            n
+        }
+        fn pick(first: bool) -> i32 {
+            if (first) {
+                return 1;
+            } else {
+                return 2;
+            }
+        }
+        assert(clamp(42) == 10);
+        assert(clamp(3) == 3);
+        assert(pick(false) == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_falling_off_its_end_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn sign(x: i32) -> i32 {
+            if (x > 0) {
+                return 1;
+            }
+        }
+        let s: i32 = sign(-5);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_leaving_a_loop_without_returning_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first_even(a: Array<i32>) -> i32 {
+            while (true) {
+                for x in a {
+                    if (x % 2 == 0) {
+                        return x;
+                    }
+                }
+                break;
+            }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_nested_return() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test() -> bool {
+            {
+                {
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+        assert(false == test());
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_returns_bool() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test() -> bool {
+            return false;
+        }
+        assert(false == test());
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_returns_bool_with_args() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test(a: i32, b: f64) -> bool {
+            return false;
+        }
+        assert(false == test(100, 100.00));
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_trailing_expression_return() {
+        let mut lang = Lang::new(Some(
+            "
+        fn square(n: i32) -> i32 {
+            n * n
+        }
+        fn sign(n: i32) -> i32 {
+            if (n > 0) {
+                1
+            } else if (n < 0) {
+                -1
+            } else {
+                0
+            }
+        }
+        fn describe(n: i32) -> String {
+            let prefix: String = \"n is \";
+            match sign(n) {
+                1 => prefix + \"positive\",
+                0 => prefix + \"zero\",
+                _ => prefix + \"negative\",
+            }
+        }
+        assert(square(5) == 25);
+        assert(sign(-4) == -1);
+        assert(sign(0) == 0);
+        assert(describe(3) == \"n is positive\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn function_type_argument_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+            return f(x);
+        }
+        fn is_positive(x: i32) -> bool {
+            return x > 0;
+        }
+        apply(is_positive, 1);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_type_call_argument_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn apply(f: fn(i32) -> i32) -> i32 {
+            return f(true);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn function_type_mismatch_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn zero() -> i32 {
+            return 0;
+        }
+        let f: fn(i32) -> bool = zero;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn functions_returning_on_every_path() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Sign {
+            Negative,
+            Zero,
+            Positive
+        }
+        fn sign(x: i32) -> Sign {
+            if (x > 0) {
+                return Sign::Positive;
+            } else if (x < 0) {
+                return Sign::Negative;
+            } else {
+                return Sign::Zero;
+            }
+        }
+        fn value(s: Sign) -> i32 {
+            match s {
+                Sign::Negative => {
+                    return -1;
+                }
+                Sign::Zero => {
+                    return 0;
+                }
+                Sign::Positive => {
+                    return 1;
+                }
+            }
+        }
+        fn first_over(a: Array<i32>, limit: i32) -> i32 {
+            loop {
+                for x in a {
+                    if (x > limit) {
+                        return x;
+                    }
+                    break;
+                }
+            }
+        }
+        fn countdown(n: i32) -> i32 {
+            while (true) {
+                if (n == 0) {
+                    return n;
+                }
+                n -= 1;
+            }
+        }
+        assert(value(sign(-5)) == -1);
+        assert(value(sign(0)) == 0);
+        assert(first_over([7, 2], 5) == 7);
+        assert(countdown(3) == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn garbage_collection_in_a_long_loop() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Counter {
+            count: i32,
+        }
+        fn bump(c: Counter) -> i32 {
+            c.count = c.count + 1;
+            return c.count;
+        }
+        fn double(n: i32) -> i32 {
+            return n + n;
+        }
+        let counter: Counter = Counter();
+        counter.count = 0;
+        let names: Array<String> = [\"a\", \"b\", \"c\"];
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 1000) {
+            let doubled: i32 = double(i);
+            total = total + doubled;
+            bump(counter);
+            i = i + 1;
+        }
+        assert(total == 999000);
+        assert(counter.count == 1000);
+        assert(names[2] == \"c\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_function_argument_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn pick<T>(a: T, b: T) -> T {
+            return a;
+        }
+        pick(1, \"a\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_function_return_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first<T>(a: Array<T>) -> T {
+            return a[0];
+        }
+        let s: String = first([1, 2]);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_functions() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first<T>(a: Array<T>) -> T {
+            return a[0];
+        }
+        fn identity<T>(value: T) -> T {
+            return value;
+        }
+        let i: i32 = first([1, 2, 3]);
+        assert(i == 1);
+        let s: String = identity(\"a\");
+        assert(s == \"a\");
+        assert(identity(first([true])));
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_struct_field_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Wrapper<T> {
+            value: T,
+        }
+        let w: Wrapper<i32> = Wrapper { value: 1 };
+        w.value = true;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_struct_literal() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Pair<T> {
+            first: T,
+            second: T,
+        }
+        let pair: Pair<String> = Pair { first: \"a\", second: \"b\" };
+        assert(pair.first == \"a\");
+        assert(pair.second == \"b\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_struct_literal_empty_array_field() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Stack<T> {
+            items: Array<T>,
+        }
+        let stack: Stack<i32> = Stack { items: [] };
+        stack.items = [1, 2];
+        let items = stack.items;
+        assert(items[1] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_struct_type_argument_count_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Wrapper<T> {
+            value: T,
+        }
+        let w: Wrapper<i32, bool> = Wrapper { value: 1 };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_struct_with_a_generic_impl() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Wrapper<T> {
+            fn get(self: Wrapper<T>) -> T {
+                return self.value;
+            }
+            fn set(self: Wrapper<T>, value: T) -> () {
+                self.value = value;
+            }
+        }
+        let number: Wrapper<i32> = Wrapper { value: 0 };
+        number.set(5);
+        assert(number.get() == 5);
+        let name: Wrapper<String> = Wrapper { value: \"\" };
+        name.set(\"lang\");
+        assert(name.get() == \"lang\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn generic_type_parameter_used_as_a_concrete_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn to_int<T>(a: T) -> i32 {
+            return a;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn generic_variants_with_annotations() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Option<String> = None;
+        a = Some(\"x\");
+        let b: Result<i32, i32> = Err(3);
+        let c = Some(1);
+        assert(a == Some(\"x\"));
+        assert(b == Err(3));
+        assert(c == Some(1));
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn hex_octal_and_binary_literals() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u8 = 0xFF;
+        assert(a == 255u8);
+        assert(0xdead_beefu32 == 3735928559u32);
+        assert(0o777 == 511);
+        assert(0b1010_1010 == 170);
+        assert(0b1000_0000u8 == 128u8);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn if_expression() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: bool = true;
+        let x: i32 = if (a) { 1 } else { 2 };
+        assert(x == 1);
+        let y: i32 = if (!a) { 1 } else { 2 };
+        assert(y == 2);
+        let z: i32 = if (x > y) { x } else if (x == y) { 0 } else { y * 10 };
+        assert(z == 20);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn if_expression_branches_with_statements() {
+        let mut lang = Lang::new(Some(
+            "
+        let n: i32 = 4;
+        let description: String = if (n % 2 == 0) {
+            let half: i32 = n / 2;
+            assert(half == 2);
+            \"even\"
+        } else {
+            \"odd\"
+        };
+        assert(description == \"even\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn if_expression_incompatible_branch_types_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: bool = true;
+        let x: i32 = if (a) { 1 } else { \"two\" };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn if_expression_without_else_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: bool = true;
+        let x: i32 = if (a) { 1 };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn if_statement_with_block_branches() {
+        let mut lang = Lang::new(Some(
+            "
+        let counter: i32 = 0;
+        if (counter == 0) {
+            counter = 1;
+        } else {
+            counter = 2;
+        }
+        if (counter == 1) {
+            counter += 10;
+        }
+        assert(counter == 11);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn infer_a_variable_from_err_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = Err(3);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn infer_a_variable_from_none_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = None;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn integer_arithmetic() {
+        let mut lang = Lang::new(Some(
+            "
+        let q: i32 = 7 / 2;
+        assert(q == 3);
+        let r: i32 = 7 - 10;
+        assert(r == -3);
+        let p: i32 = 6 * 7;
+        assert(p == 42);
+        let m: i64 = 4294967296 / 2;
+        assert(m == 2147483648);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn integer_literal_suffixes() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 255u8;
+        let b = 100i8 + 27i8;
+        let c: u64 = 1u64 + 2u8;
+        assert(a == 255);
+        assert(b == 127);
+        assert(c == 3);
+        let d: i16 = 200u8 + 1i16;
+        assert(d == 201);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn integer_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 2147483647;
+        let b = a + 1;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn integer_suffix_on_float_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1.5i32;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn integer_width_casts() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(300 as u8 == 44);
+        assert(-1 as u8 == 255);
+        assert(255u8 as i8 == -1);
+        assert(-1i8 as u64 == 18446744073709551615u64);
+        assert(70000 as i16 == 4464);
+        assert(-5.7 as u8 == 0);
+        assert(300.0 as u8 == 255);
+        assert('A' as u8 == 65);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn integer_widths() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u8 = 255;
+        let b: i8 = -128;
+        let c: i16 = -32768;
+        let d: u16 = 65535;
+        let e: u32 = 4294967295;
+        let f: u64 = 18446744073709551615;
+        let g: usize = 7;
+        assert(a == 255);
+        assert(b == -128);
+        assert(c == -32768);
+        assert(d == 65535);
+        assert(e == 4294967295);
+        assert(f == 18446744073709551615u64);
+        assert(g == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn invalid_digit_for_radix_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 0b102;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn iterate_over_a_non_iterable_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        for x in 5 {
+            print x;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn labeled_break_and_continue() {
+        let mut lang = Lang::new(Some(
+            "
+        let pairs: i32 = 0;
+        'outer: for (let i: i32 = 0; i < 5; i += 1) {
+            for (let j: i32 = 0; j < 5; j += 1) {
+                if (j > i) {
+                    continue 'outer;
+                }
+                if (i == 3) {
+                    break 'outer;
+                }
+                pairs += 1;
+            }
+        }
+        assert(pairs == 6);
+        let count: i32 = 0;
+        'rows: while (true) {
+            while (true) {
+                count += 1;
+                if (count == 3) {
+                    break 'rows;
+                }
+            }
+        }
+        assert(count == 3);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn labeled_for_in() {
+        let mut lang = Lang::new(Some(
+            "
+        let pairs: i32 = 0;
+        'outer: for i in 0..5 {
+            for j in [0, 1, 2, 3, 4] {
+                if (j > i) {
+                    continue 'outer;
+                }
+                if (i == 3) {
+                    break 'outer;
+                }
+                pairs += 1;
+            }
+        }
+        assert(pairs == 6);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn let_type_inference() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        fn double(a: i32) -> i32 {
+            return a + a;
+        }
+        let a = 1;
+        let b = 2.5;
+        let s = \"hi\";
+        let c = 'c';
+        let t = true;
+        let d = double(a);
+        let p = Point();
+        p.x = 3;
+        let arr = [1, 2, 3];
+        a = 5;
+        assert(a == 5);
+        assert(b == 2.5);
+        assert(s == \"hi\");
+        assert(c == 'c');
+        assert(t);
+        assert(d == 2);
+        assert(p.x == 3);
+        assert(arr[2] == 3);
+        let q: Point = p;
+        let copy: Array<i32> = arr;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn let_type_inference_empty_array_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = [];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn let_type_inference_reassignment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1;
+        a = \"not a number\";
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn let_without_type_or_initializer_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn literal_out_of_range_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u8 = 256;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn long_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i64 = 9223372036854775807;
+        let b = a * 2;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn loop_breaks_with_incompatible_values_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let flag: bool = true;
+        let a: i32 = loop {
+            if (flag) {
+                break 1;
+            }
+            break \"one\";
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn loop_with_break_value() {
+        let mut lang = Lang::new(Some(
+            "
+        let n: i32 = 0;
+        let square: i32 = loop {
+            n += 1;
+            if (n * n > 50) {
+                break n * n;
+            }
+        };
+        assert(square == 64);
+        fn find(target: i32) -> i32 {
+            let attempts: i32 = 0;
+            'search: loop {
+                let guess: i32 = 0;
+                loop {
+                    attempts += 1;
+                    if (guess == target) {
+                        break 'search attempts;
+                    }
+                    if (guess > target) {
+                        break;
+                    }
+                    guess += 1;
+                }
+            }
+        }
+        assert(find(4) == 5);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn map_empty_literal_without_a_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [:];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_entry_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [\"a\": 1, \"b\": true];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_float_key_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [1.5: 2];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_index_key_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [\"a\": 1];
+        print m[1];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_insert_value_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [\"a\": 1];
+        m.insert(\"b\", \"c\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_iteration() {
+        let mut lang = Lang::new(Some(
+            "
+        let scores = [\"a\": 1, \"b\": 2, \"c\": 3];
+        scores.remove(\"b\");
+        let keys = scores.keys();
+        let values = scores.values();
+        let total = 0;
+        let i = 0;
+        while (i < scores.len()) {
+            assert(scores[keys[i]] == values[i]);
+            total = total + values[i];
+            i = i + 1;
+        }
+        assert(total == 4);
+        assert(keys[1] == \"c\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn map_keys_of_each_hashable_type() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        enum Color { Red, Green }
+        let ids = [1: \"one\", 2: \"two\"];
+        assert(ids[2] == \"two\");
+        let letters = ['a': 1, 'b': 2];
+        assert(letters['b'] == 2);
+        let flags = [true: \"yes\", false: \"no\"];
+        assert(flags[1 == 1] == \"yes\");
+        let p = Point { x: 1, y: 2 };
+        let q = Point { x: 2, y: 1 };
+        let names: Map<Point, String> = [p: \"p\"];
+        names[q] = \"q\";
+        let r = p;
+        assert(names[r] == \"p\");
+        assert(names.len() == 2);
+        let colors = [Color::Red: 1];
+        colors[Color::Green] = 2;
+        assert(colors[Color::Green] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn map_literals_indexing_and_methods() {
+        let mut lang = Lang::new(Some(
+            "
+        let scores: Map<String, i32> = [\"alice\": 3, \"bob\": 5];
+        assert(scores[\"bob\"] == 5);
+        scores[\"carol\"] = 7;
+        scores.insert(\"alice\", 4);
+        assert(scores[\"alice\"] == 4);
+        assert(scores.len() == 3);
+        assert(scores.contains(\"bob\"));
+        assert(scores.remove(\"bob\"));
+        assert(!scores.remove(\"bob\"));
+        assert(!scores.contains(\"bob\"));
+        let empty: Map<i64, bool> = [:];
+        assert(empty.len() == 0);
+        let copy: Map<String, i32> = [\"carol\": 7, \"alice\": 4];
+        assert(scores == copy);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn map_missing_key_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let m = [\"a\": 1];
+        print m[\"b\"];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_missing_struct_key_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        let m: Map<Point, i32> = [Point { x: 1, y: 2 }: 5];
+        print m[Point { x: 2, y: 1 }];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn map_struct_keys_compare_by_their_fields() {
+        let mut lang = Lang::new(Some("
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        struct Segment {
+            from: Point,
+            to: Point
+        }
+        let m: Map<Point, i32> = [Point { x: 1, y: 2 }: 5];
+        assert(m[Point { x: 1, y: 2 }] == 5);
+        assert(m.contains(Point { x: 1, y: 2 }));
+        assert(!m.contains(Point { x: 2, y: 1 }));
+        m[Point { x: 1, y: 2 }] = 6;
+        assert(m.len() == 1);
+        assert(m[Point { x: 1, y: 2 }] == 6);
+        let p = Point { x: 3, y: 4 };
+        m[p] = 7;
+        p.x = 1;
+        assert(m[Point { x: 3, y: 4 }] == 7);
+        let lengths: Map<Segment, i32> = [Segment { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }: 5];
+        assert(lengths[Segment { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }] == 5);
+        let pairs: Map<(Point, i32), bool> = [(Point { x: 1, y: 1 }, 1): true];
+        assert(pairs[(Point { x: 1, y: 1 }, 1)]);
+        assert(m.remove(Point { x: 1, y: 2 }));
+        assert(m.len() == 1);
+        "));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn match_arms_of_incompatible_types_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape { Circle, Square }
+        let shape = Shape::Circle;
+        let sides: i32 = match shape {
+            Shape::Circle => \"none\",
+            Shape::Square => 4
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_expression_on_enum_variants() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32),
+            Empty
+        }
+        fn size(shape: Shape) -> i32 {
+            return match shape {
+                Shape::Circle(r) => r + r,
+                Shape::Rect(w, h) => w + h,
+                Shape::Empty => 0,
+            };
+        }
+        assert(size(Shape::Circle(2)) == 4);
+        assert(size(Shape::Rect(2, 5)) == 7);
+        assert(size(Shape::Empty) == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn match_literal_without_wildcard_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let n: i32 = 3;
+        let name: String = match n {
+            1 => \"one\",
+            3 => \"three\",
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_missing_variant_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        match shape {
+            Shape::Circle(r) => {
+                print r;
+            }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_nested_and_binding_patterns() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Inner {
+            A,
+            B(i32)
+        }
+        enum Outer {
+            Wrap(Inner),
+            Nothing
+        }
+        let value: Outer = Outer::Wrap(Inner::B(7));
+        let result: i32 = match value {
+            Outer::Wrap(Inner::B(x)) => x,
+            Outer::Wrap(_) => 1,
+            other => 0,
+        };
+        assert(result == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn match_pattern_arity_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        let n: i32 = match shape {
+            Shape::Circle(a, b) => a,
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_refutable_field_pattern_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Shape {
+            Circle(i32),
+            Rect(i32, i32)
+        }
+        let shape: Shape = Shape::Circle(1);
+        let area: i32 = match shape {
+            Shape::Circle(r) => r,
+            Shape::Rect(1, h) => h,
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_statement_with_block_arms() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red,
+            Green,
+            Blue
+        }
+        let color: Color = Color::Green;
+        let seen: i32 = 0;
+        match color {
+            Color::Red => {
+                seen = 1;
+            }
+            Color::Green => {
+                seen = 2;
+            }
+            Color::Blue => {
+                seen = 3;
+            }
+        }
+        assert(seen == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn match_undefined_variant_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        enum Color {
+            Red
+        }
+        let color: Color = Color::Red;
+        let n: i32 = match color {
+            Color::Red => 1,
+            Color::Purple => 2,
+        };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn match_wildcard_and_literal_patterns() {
+        let mut lang = Lang::new(Some(
+            "
+        let n: i32 = 3;
+        let name: String = match n {
+            1 => \"one\",
+            3 => \"three\",
+            _ => \"many\",
+        };
+        assert(name == \"three\");
+        let flag: bool = false;
+        let as_int: i32 = match flag {
+            true => 1,
+            false => 0,
+        };
+        assert(as_int == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn negation_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = -2147483647 - 1;
+        let b = -a;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn negative_literals_take_their_suffix_type() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = -128i8;
+        let b: i8 = a;
+        let c: i8 = -0x80;
+        assert(b == c);
+        let d = -2.5e1f32;
+        let e: f32 = d;
+        assert(e == -25.0f32);
+        let f = -9223372036854775808;
+        let g: i64 = f;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn nested_generic_type_arguments() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Option<Option<i32>> = Some(Some(1));
+        match a {
+            Some(Some(value)) => { assert(value == 1); }
+            _ => { assert(false); }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn numeric_casts() {
+        let mut lang = Lang::new(Some(
+            "
+        let x: f64 = 7 as f64;
+        assert(x == 7.0);
+        let t: i32 = 3.9 as i32;
+        assert(t == 3);
+        let n: i32 = -3.9 as i32;
+        assert(n == -3);
+        let big: i64 = 4294967297;
+        assert(big as i32 == 1);
+        assert(3000000000.0 as i32 == 2147483647);
+        assert('a' as i32 == 97);
+        assert(true as i64 == 1);
+        let half: f32 = 1 as f32 / 2 as f32;
+        assert(half == 0.5);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn numeric_promotion() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 2147483647;
+        let b: i64 = 1;
+        let c: i64 = a + b;
+        assert(c == 2147483648);
+        let x: f32 = 1.5;
+        let y: f64 = 2.25;
+        let z: f64 = x + y;
+        assert(z == 3.75);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn option_and_result_constructors() {
+        let mut lang = Lang::new(Some(
+            "
+        let some: Option<i32> = Some(3);
+        let none: Option<i32> = None;
+        assert(some == Option::Some(3));
+        assert(some != none);
+        let ok: Result<i32, String> = Ok(1);
+        let err: Result<i32, String> = Err(\"failed\");
+        assert(ok != err);
+        let found = match some {
+            Some(n) => n + 1,
+            None => 0,
+        };
+        assert(found == 4);
+        let message = match err {
+            Ok(_) => \"fine\",
+            Err(reason) => reason,
+        };
+        assert(message == \"failed\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn option_payload_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Option<i32> = Some(\"x\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn option_propagation() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first_even(a: i32, b: i32) -> Option<i32> {
+            if (a == 2) {
+                return Some(a);
+            }
+            if (b == 4) {
+                return Some(b);
+            }
+            return None;
+        }
+        fn plus_one(a: i32, b: i32) -> Option<i32> {
+            let n = first_even(a, b)?;
+            return Some(n + 1);
+        }
+        assert(plus_one(2, 1) == Some(3));
+        assert(plus_one(1, 1) == None);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_arithmetic_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 {
+            x: i32,
+            y: i32,
+        }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x, y: self.y + other.y };
+            }
+        }
+        impl Sub for Vec2 {
+            fn sub(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x + other.x + 1, y: self.y + other.y + 1 };
+            }
+        }
+        let a = Vec2 { x: 1, y: 2 };
+        let b = Vec2 { x: 3, y: 4 };
+        let c: Vec2 = a + b;
+        assert(c.x == 4);
+        assert(c.y == 6);
+        let d: Vec2 = a + b + c;
+        assert(d.x == 8);
+        let e: Vec2 = a - b;
+        assert(e.y == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_comparison_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Version {
+            major: i32,
+            minor: i32,
+        }
+        impl Eq for Version {
+            fn eq(self: Version, other: Version) -> bool {
+                return self.major == other.major and self.minor == other.minor;
+            }
+        }
+        impl Ord for Version {
+            fn cmp(self: Version, other: Version) -> i32 {
+                if (self.major != other.major) {
+                    if (self.major < other.major) {
+                        return -1;
+                    }
+                    return 1;
+                }
+                if (self.minor < other.minor) {
+                    return -1;
+                }
+                if (self.minor > other.minor) {
+                    return 1;
+                }
+                return 0;
+            }
+        }
+        let old = Version { major: 1, minor: 4 };
+        let new = Version { major: 2, minor: 0 };
+        assert(old == Version { major: 1, minor: 4 });
+        assert(old != new);
+        assert(old < new);
+        assert(old <= old);
+        assert(new > old);
+        assert(new >= old);
+        assert(false == (new < old));
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_index_operator() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Polynomial {
+            degree: i32,
+            coefficients: Array<i32>,
+        }
+        impl Index<i32, i32> for Polynomial {
+            fn index(self: Polynomial, power: i32) -> i32 {
+                if (power > self.degree) {
+                    return 0;
+                }
+                let coefficients = self.coefficients;
+                return coefficients[power];
+            }
+        }
+        let p = Polynomial { degree: 2, coefficients: [3, 0, 2] };
+        let constant: i32 = p[0];
+        assert(constant == 3);
+        assert(p[2] == 2);
+        assert(p[7] == 0);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_operators_on_generic_structs() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Eq for Wrapper<T> {
+            fn eq(self: Wrapper<T>, other: Wrapper<T>) -> bool {
+                return self.value == other.value;
+            }
+        }
+        impl<T> Index<i32, T> for Wrapper<T> {
+            fn index(self: Wrapper<T>, index: i32) -> T {
+                return self.value;
+            }
+        }
+        let a: Wrapper<String> = Wrapper { value: \"a\" };
+        let b: Wrapper<String> = Wrapper { value: \"a\" };
+        assert(a == b);
+        let value: String = a[0];
+        assert(value == \"a\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn overloaded_remainder_and_bitwise_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Bits { value: i32 }
+        impl Rem for Bits {
+            fn rem(self: Bits, other: Bits) -> Bits {
+                return Bits { value: self.value % other.value };
+            }
+        }
+        impl BitOr for Bits {
+            fn bitor(self: Bits, other: Bits) -> Bits {
+                return Bits { value: self.value | other.value };
+            }
+        }
+        let a = Bits { value: 12 };
+        let b = Bits { value: 5 };
+        assert((a % b).value == 2);
+        a |= b;
+        assert(a.value == 13);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn range_values() {
+        let mut lang = Lang::new(Some(
+            "
+        let r: Range<i64> = 2..4i64;
+        let total: i64 = 0;
+        for i in r {
+            total += i;
+        }
+        assert(total == 5);
+        for i in r {
+            total += i;
+        }
+        assert(total == 10);
+        assert(r == (2..4i64));
+        print r;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn range_with_float_bounds_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let r = 0.5..2.5;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn raw_and_multi_line_strings() {
+        let mut lang = Lang::new(Some(
+            "
+        let raw = r\"a\\n\";
+        let count: i32 = 0;
+        for c in raw {
+            count += 1;
+        }
+        assert(count == 3);
+        assert(r#\"say \"hi\"\"# == \"say \\\"hi\\\"\");
+        let lines = \"one
+two\";
+        assert(lines == \"one\\ntwo\");
+        let joined = \"one \\
+                      two\";
+        assert(joined == \"one two\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn redeclaring_a_variable_in_the_same_scope_makes_a_new_one() {
+        let mut lang = Lang::new(Some(
+            "
+        fn twice_plus_one(x: i32) -> i32 {
+            let x: i32 = x * 2;
+            let x: i32 = x + 1;
+            return x;
+        }
+        assert(twice_plus_one(3) == 7);
+        {
+            let a: i32 = 1;
+            fn show() -> i32 {
+                return a;
+            }
+            let a: String = \"a\";
+            assert(show() == 1);
+            assert(a == \"a\");
+        }
+        let i: i32 = 0;
+        let total: i32 = 0;
+        while (i < 3) {
+            let step: i32 = i;
+            let step: i32 = step + 10;
+            total = total + step;
+            i = i + 1;
+        }
+        assert(total == 33);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn remainder() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(17 % 5 == 2);
+        assert(-7 % 3 == -1);
+        assert(7.5 % 2.0 == 1.5);
+        let a: u8 = 200;
+        assert(a % 7u8 == 4);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn remainder_by_zero_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 5;
+        let b = 0;
+        let c = a % b;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn result_propagation() {
+        let mut lang = Lang::new(Some(
+            "
+        fn parse(text: String) -> Result<i32, String> {
+            if (text == \"one\") {
+                return Ok(1);
+            }
+            return Err(\"not a number\");
+        }
+        fn sum(a: String, b: String) -> Result<i32, String> {
+            return Ok(parse(a)? + parse(b)?);
+        }
+        assert(sum(\"one\", \"one\") == Ok(2));
+        assert(sum(\"one\", \"two\") == Err(\"not a number\"));
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn return_from_block() {
+        let mut lang = Lang::new(Some(
+            "
+        fn test() -> i32 {
+            {
+                return 100;
+            }
+        }
+        let value: i32 = test();
+        assert(value == 100);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn return_from_inside_a_while_loop() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first_multiple(of: i32, above: i32) -> i32 {
+            let candidate: i32 = above;
+            while (true) {
+                if (candidate % of == 0) {
+                    return candidate;
+                }
+                candidate += 1;
+            }
+            return -1;
+        }
+        assert(first_multiple(7, 20) == 21);
+        fn nested(limit: i32) -> i32 {
+            for (let i: i32 = 0; i < limit; i += 1) {
+                for (let j: i32 = 0; j < limit; j += 1) {
+                    if (i * j == 6) return i * 10 + j;
+                }
+            }
+            return 0;
+        }
+        assert(nested(5) == 23);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn self_in_impls() {
+        let mut lang = Lang::new(Some(
+            "
+        trait Double {
+            fn double(self: Self) -> Self;
+        }
+        struct Point {
+            x: i32,
+        }
+        impl Double for Point {
+            fn double(self: Self) -> Self {
+                let doubled: Self = Point { x: self.x * 2 };
+                return doubled;
+            }
+        }
+        impl Point {
+            fn plus(self: Self, other: Self) -> Self {
+                return Point { x: self.x + other.x };
+            }
+        }
+        struct Wrapper<T> {
+            value: T,
+        }
+        impl<T> Wrapper<T> {
+            fn same(self: Self) -> Self {
+                return self;
+            }
+        }
+        let point = Point { x: 3 };
+        let doubled = point.double();
+        assert(doubled.x == 6);
+        let sum = doubled.plus(Point { x: 1 });
+        assert(sum.x == 7);
+        let wrapper: Wrapper<i32> = Wrapper { value: 4 };
+        let same = wrapper.same();
+        assert(same.value == 4);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn self_in_impls_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        struct Other {
+            x: i32,
+        }
+        impl Point {
+            fn other(self: Self) -> Self {
+                return Other { x: self.x };
+            }
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn shadowed_variables_in_nested_blocks() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        let b: i32 = 0;
+        {
+            b = 7;
+            let a: i32 = 2;
+            let b: i32 = 9;
+            {
+                let a: i32 = 3;
+                assert(a == 3);
+                b = 10;
+            }
+            assert(a == 2);
+            assert(b == 10);
+        }
+        assert(a == 1);
+        assert(b == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn shift_operators() {
+        let mut lang = Lang::new(Some(
+            "
+        assert(1 << 4 == 16);
+        assert(-16 >> 2 == -4);
+        let a: u8 = 3;
+        assert(a << 7 == 128u8);
+        let b: u64 = 18446744073709551615;
+        assert(b >> 60u8 == 15u64);
+        let c: i64 = 1;
+        assert(c << 40u8 == 1099511627776);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn shift_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 32;
+        let b = 1 << a;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn signed_literal_past_its_maximum_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 128i8;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn small_integer_overflow_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i8 = 100;
+        let b = a * 2i8;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn string_invalid_escape_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = \"\\q\";
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn string_literal_escapes() {
+        let mut lang = Lang::new(Some(
+            "
+        let count: i32 = 0;
+        for c in \"\\t\\r\\n\\0\\\\\\\"\\'\" {
+            count += 1;
+        }
+        assert(count == 7);
+        assert(\"\\u{48}i\" == \"Hi\");
+        assert('\\u{41}' == 'A');
+        assert('\\'' != '\\\"');
+        let tab: char = 'a';
+        for c in \"a\\tb\" {
+            if (c != 'a' and c != 'b') {
+                tab = c;
+            }
+        }
+        assert(tab == '\\t');
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn string_literal_punctuation_and_unicode() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = \"Hi, world! ¿Qué tal? ☃\";
+        let count: i32 = 0;
+        for c in a {
+            count += 1;
+        }
+        assert(count == 22);
+        assert(a != \"Hi world\");
+        assert(\"  padded \" != \"padded\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn string_unterminated_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = \"abc;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_constructor_call_arguments_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        let p = Point(1);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_constructor_call_field_defaults() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32 = 7,
+            y: i32,
+            label: String,
+        }
+        let p = Point();
+        assert(p.x == 7);
+        assert(p.y == 0);
+        assert(p.label == \"\");
+        let q = Point();
+        q.x = 1;
+        assert(p.x == 7);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_constructor_call_missing_field_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Inner {
+            x: i32,
+        }
+        struct Outer {
+            inner: Inner,
+        }
+        let o = Outer();
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_declaration() {
+        let mut lang = Lang::new(Some("struct TestStruct {}"));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_declaration_failure() {
+        let mut lang = Lang::new(Some("struct TestStruct {{}"));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_declaration_with_fields() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            field0: i64,
+            field1: f64,
+            field2: bool
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_field_default_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32 = \"zero\",
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_formats_as_its_name() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32
+        }
+        let p = Point { x: 1, y: 2 };
+        assert(f\"{p}\" == \"Point\");
+        assert(f\"{p:?}\" == \"Point { x: 1, y: 2 }\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_literal_duplicate_field_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: 1, x: 2 };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_literal_empty_collection_fields() {
+        let mut lang = Lang::new(Some(
+            "
+        fn empty() -> Inventory {
+            return Inventory { items: [], counts: [:] };
+        }
+        struct Inventory {
+            items: Array<String>,
+            counts: Map<String, i32>,
+        }
+        let inventory = Inventory { items: [], counts: [:] };
+        inventory.items = [\"apple\"];
+        let items = inventory.items;
+        assert(items[0] == \"apple\");
+        let counts = empty().counts;
+        counts[\"apple\"] = 2;
+        assert(counts[\"apple\"] == 2);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_literal_field_defaults() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Config {
+            name: String,
+            retries: i32 = 3,
+            verbose: bool = false,
+        }
+        let config = Config { name: \"default\" };
+        assert(config.retries == 3);
+        assert(config.verbose == false);
+        let custom = Config { verbose: true, name: \"custom\", retries: 5 };
+        assert(custom.retries == 5);
+        assert(custom.verbose);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_literal_field_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: true };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_literal_missing_field_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32 = 0,
+        }
+        let p = Point { y: 1 };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_literal_unknown_field_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        let p = Point { x: 1, z: 2 };
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_literals() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+        fn origin() -> Point {
+            return Point { x: 0, y: 0 };
+        }
+        let x = 3;
+        let p = Point { y: 4, x: x };
+        assert(p.x == 3);
+        assert(p.y == 4);
+        let o: Point = origin();
+        assert(o.x == 0);
+        o.x = 1;
+        assert(o.x == 1);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_field_access_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {}
+
+        let instance: TestStruct = TestStruct();
+        instance.field_doesnt_exist;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn struct_with_fields_instatiation() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            field0: i64,
+            field1: f64,
+            field2: bool
+        }
+
+        let instance: TestStruct = TestStruct();
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_fields_instatiation_and_field_assignment() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            field0: i64,
+            field1: f64,
+            field2: bool
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.field0 = 0;
+        instance.field1 = 1.00;
+        instance.field2 = false;
+        assert(instance.field0 == 0);
+        assert(instance.field1 == 1.00);
+        assert(instance.field2 == false);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_impl() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+        }
+
+        impl TestStruct {
+            fn hello() -> () {
+                print \"Hello world\";
+            }
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.hello();
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_impl_using_mutable_self() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            i: i32,
+        }
+
+        impl TestStruct {
+            fn hello(self: TestStruct, other: TestStruct) -> () {
+                self.i = self.i + other.i;
+            }
+        }
+
+        let instance: TestStruct = TestStruct();
+        let other: TestStruct = TestStruct();
+        other.i = 100;
+        instance.i = 100;
+        instance.hello(other);
+        assert(instance.i == 200);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_impl_using_self() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+            i: i32,
+        }
+
+        impl TestStruct {
+            fn hello() -> () {
+                print self.i;
+            }
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.i = 100;
+        assert(instance.i == 100);
+        instance.hello();
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn struct_with_method_call_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct TestStruct {
+        }
+
+        impl TestStruct {
+        }
+
+        let instance: TestStruct = TestStruct();
+        instance.hello();
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn suffixed_literal_out_of_range_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 128i8;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn trait_implementation() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Point {
+            x: i32,
+        }
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+            fn double(a: i32) -> i32 {
+                return a + a;
+            }
+        }
+        let point: Point = Point();
+        let doubled: i32 = point.double(3);
+        assert(doubled == 6);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn try_in_a_function_not_returning_an_option_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f(a: Option<i32>) -> i32 {
+            return a?;
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn try_on_a_non_option_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f(n: i32) -> Option<i32> {
+            return Some(n?);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn try_on_a_variant_of_a_different_enum_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn first() -> Option<i32> {
+            let value: i32 = Err(1)?;
+            return Some(value);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn try_outside_a_function_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: Option<i32> = Some(1);
+        let b = a?;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn try_with_a_different_error_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f(a: Result<i32, String>) -> Result<i32, bool> {
+            return Ok(a?);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn tuple_destructure_arity_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let (a, b, c) = (1, 2);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn tuple_destructuring() {
+        let mut lang = Lang::new(Some(
+            "
+        fn pair(name: String) -> (String, i32) {
+            return (name, 2);
+        }
+        let (name, count) = pair(\"apples\");
+        assert(name == \"apples\");
+        assert(count == 2);
+        fn swap(p: (i32, String)) -> (String, i32) {
+            let (number, text) = p;
+            return (text, number);
+        }
+        let swapped = swap((1, \"one\"));
+        assert(swapped.0 == \"one\");
+        assert(swapped.1 == 1);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn tuple_field_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let t = (1, 2);
+        t.0 = 3;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn tuple_field_out_of_range_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let t = (1, 2);
+        print t.2;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn tuple_literals_and_fields() {
+        let mut lang = Lang::new(Some(
+            "
+        fn min_max(a: i32, b: i32) -> (i32, i32) {
+            if (a < b) {
+                return (a, b);
+            }
+            return (b, a);
+        }
+        let bounds = min_max(7, 3);
+        assert(bounds.0 == 3);
+        assert(bounds.1 == 7);
+        let nested = ((1, 2), \"three\");
+        assert(nested.0.1 == 2);
+        assert(nested.1 == \"three\");
+        assert((1, \"a\") == (1, \"a\"));
+        assert((1, \"a\") != (1, \"b\"));
+        let empty: (i32, bool);
+        assert(empty == (0, false));
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn tuple_literals_widen_to_the_declared_type() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f() -> (i64, String) {
+            return (1, \"a\");
+        }
+        fn g() -> (i64, (f64, u32)) {
+            (2, (1.5, 3u8))
+        }
+        let t: (i64, String) = (1, \"a\");
+        assert(f() == t);
+        assert(g().1.1 == 3u32);
+        let (big, text) = f();
+        let wide: i64 = big + 5000000000;
+        assert(wide == 5000000001);
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn tuple_map_keys() {
+        let mut lang = Lang::new(Some(
+            "
+        let grid = [(0, 0): \"origin\", (1, 2): \"point\"];
+        assert(grid[(1, 2)] == \"point\");
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), true)
+    }
+    #[test]
+    fn tuple_return_type_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn f() -> (i32, String) {
+            return (\"a\", 1);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_binary_operator_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1 + true;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_cast_from_string_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = \"1\" as i32;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_cast_to_non_numeric_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a = 1 as bool;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_mixed_int_and_float_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        let b: f64 = 2.0;
+        let c = a + b;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_narrowing_assignment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: u16 = 1;
+        let b: u8 = a;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_operator_without_trait_impl_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 {
+            x: i32,
+        }
+        let a = Vec2 { x: 1 };
+        let b: Vec2 = a + a;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_overloaded_index_result_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Grid {
+            x: i32,
+        }
+        impl Index<i32, bool> for Grid {
+            fn index(self: Grid, index: i32) -> bool {
+                return true;
+            }
+        }
+        let g = Grid { x: 1 };
+        let a: i32 = g[0];
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_overloaded_operator_operand_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 {
+            x: i32,
+        }
+        impl Mul for Vec2 {
+            fn mul(self: Vec2, other: Vec2) -> Vec2 {
+                return Vec2 { x: self.x };
+            }
+        }
+        let a = Vec2 { x: 1 };
+        let b: Vec2 = a * 2;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn type_check_overloaded_operator_result_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        struct Vec2 {
+            x: i32,
+        }
+        impl Div for Vec2 {
+            fn div(self: Vec2, other: Vec2) -> Vec2 {
+                return self;
+            }
+        }
+        let a = Vec2 { x: 1 };
+        let b: i32 = a / a;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn array_i64_variable_declaration_and_assignment() {
+    fn type_check_promoted_result_failure() {
         let mut lang = Lang::new(Some(
             "
-        let i: Array<i64> = [0, 1, 2];
-        assert(i[0] == 0);
-        assert(i[1] == 1);
-        assert(i[2] == 2);
+        let a: i32 = 1;
+        let b: i64 = 2;
+        let c: i32 = a + b;
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn array_i64_variable_declaration_empty() {
-        let mut lang = Lang::new(Some("let i: Array<i64> = [];"));
+    fn type_check_signed_and_unsigned_mix_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        let b: u32 = 2;
+        let c = a + b;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn array_i64_variable_re_assignment() {
+    fn type_check_trait_impl_missing_function_failure() {
         let mut lang = Lang::new(Some(
             "
-        let i: Array<i64> = [];
-        i = [0, 1, 2];
-        assert(i[0] == 0);
-        assert(i[1] == 1);
-        assert(i[2] == 2);
+        struct Point {}
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+        }
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn array_i64_variable_re_assignment_failure() {
+    fn type_check_trait_impl_return_type_failure() {
         let mut lang = Lang::new(Some(
-            "let i: Array<i64> = [];
-            i = [0.00, 1.00, 2.00];
-        assert(i[0] == 0.00);
-        assert(i[1] == 1.00);
-        assert(i[2] == 2.00);
-            ",
+            "
+        struct Point {}
+        trait Double {
+            fn double(a: i32) -> i32;
+        }
+        impl Double for Point {
+            fn double(a: i32) -> bool {
+                return true;
+            }
+        }
+        ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -73,27 +10172,41 @@ mod tests {
         assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn assertion() {
+    fn type_check_trait_impl_self_type_failure() {
         let mut lang = Lang::new(Some(
             "
-        assert(100 == 100);
-        assert(true == true);
-        assert(1.05 == 1.05);
+        struct Vec2 {
+            x: i32,
+        }
+        impl Add for Vec2 {
+            fn add(self: Vec2, other: i32) -> Vec2 {
+                return self;
+            }
+        }
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn assertion_failure() {
+    fn type_check_trait_type_argument_count_failure() {
         let mut lang = Lang::new(Some(
             "
-        assert(0 == 100);
+        struct Grid {
+            x: i32,
+        }
+        impl Index<i32> for Grid {
+            fn index(self: Grid, index: i32) -> i32 {
+                return index;
+            }
+        }
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -101,36 +10214,35 @@ mod tests {
         assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn for_loop() {
+    fn type_check_unexecuted_branch_failure() {
         let mut lang = Lang::new(Some(
             "
-        for (let i: i32 = 0; i < 10; i = i + 1) {
-            print i;
-        }
-        for (let b: bool = false; b == true; b = false) {
-            print b;
+        let a: i32 = 0;
+        if (false) {
+            a = \"unreachable\";
         }
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn function_assert_failure() {
+    fn type_check_unexecuted_call_argument_failure() {
         let mut lang = Lang::new(Some(
             "
-        fn test(a: i32, b: f64) -> bool {
-            if (!(a > 1000)) {
-                assert(false);
-            }
-            return false;
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b;
+        }
+        fn never_called() -> i32 {
+            return add(1, 2.0);
         }
-        test(1, 0.0);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -138,90 +10250,100 @@ mod tests {
         assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn function_nested_return() {
+    fn type_check_unexecuted_call_arity_failure() {
         let mut lang = Lang::new(Some(
             "
-        fn test() -> bool {
-            {
-                {
-                    {
-                        return false;
-                    }
-                }
-            }
+        fn add(a: i32, b: i32) -> i32 {
+            return a + b;
+        }
+        fn never_called() -> i32 {
+            return add(1);
         }
-        assert(false == test());
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn function_returns_bool() {
+    fn type_check_unexecuted_field_access_failure() {
         let mut lang = Lang::new(Some(
             "
-        fn test() -> bool {
-            return false;
+        struct Point {
+            x: i32,
+        }
+        fn never_called(point: Point) -> i32 {
+            return point.y;
         }
-        assert(false == test());
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn function_returns_bool_with_args() {
+    fn type_check_unexecuted_return_failure() {
         let mut lang = Lang::new(Some(
             "
-        fn test(a: i32, b: f64) -> bool {
-            return false;
+        fn never_called() -> i32 {
+            return \"not a number\";
         }
-        assert(false == test(100, 100.00));
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn return_from_block() {
+    fn type_check_unsigned_negation_failure() {
         let mut lang = Lang::new(Some(
             "
-        fn test() -> i32 {
-            {
-                return 100;
-            }
-        }
-        let value: i32 = test();
-        assert(value == 100);
+        let a: u8 = 1;
+        let b = -a;
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn struct_declaration() {
-        let mut lang = Lang::new(Some("struct TestStruct {}"));
+    fn undefined_function_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        fn defined() -> i32 {
+            return missing(1);
+        }
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn struct_declaration_failure() {
-        let mut lang = Lang::new(Some("struct TestStruct {{}"));
+    fn undefined_variable_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1;
+        print a;
+        print missing;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -229,32 +10351,27 @@ mod tests {
         assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn struct_declaration_with_fields() {
+    fn uninitialized_char_variable_failure() {
         let mut lang = Lang::new(Some(
             "
-        struct TestStruct {
-            field0: i64,
-            field1: f64,
-            field2: bool
-        }
+        let c: char;
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn struct_with_field_access_failure() {
+    fn uninitialized_enum_variable_failure() {
         let mut lang = Lang::new(Some(
             "
-        struct TestStruct {}
-
-        let instance: TestStruct = TestStruct();
-        instance.field_doesnt_exist;
+        let c: Option<i32>;
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -262,66 +10379,51 @@ mod tests {
         assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn struct_with_fields_instatiation() {
+    fn uninitialized_function_variable_failure() {
         let mut lang = Lang::new(Some(
             "
-        struct TestStruct {
-            field0: i64,
-            field1: f64,
-            field2: bool
-        }
-
-        let instance: TestStruct = TestStruct();
+        let f: fn(i32) -> i32;
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn struct_with_fields_instatiation_and_field_assignment() {
+    fn uninitialized_struct_variable_failure() {
         let mut lang = Lang::new(Some(
             "
-        struct TestStruct {
-            field0: i64,
-            field1: f64,
-            field2: bool
+        struct Point {
+            x: i32
         }
-
-        let instance: TestStruct = TestStruct();
-        instance.field0 = 0;
-        instance.field1 = 1.00;
-        instance.field2 = false;
-        assert(instance.field0 == 0);
-        assert(instance.field1 == 1.00);
-        assert(instance.field2 == false);
+        let p: Point;
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn struct_with_impl() {
+    fn unsigned_arithmetic() {
         let mut lang = Lang::new(Some(
             "
-        struct TestStruct {
-        }
-
-        impl TestStruct {
-            fn hello() -> () {
-                print \"Hello world\";
-            }
-        }
-
-        let instance: TestStruct = TestStruct();
-        instance.hello();
+        let count: u32 = 10;
+        let step: u32 = 3;
+        assert(count - step == 7u32);
+        assert(count * step == 30u32);
+        assert(count / step == 3u32);
+        let bytes: u8 = 200u8;
+        let wide: u16 = bytes + 100u16;
+        assert(wide == 300);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -329,27 +10431,15 @@ mod tests {
         assert_eq!(result.is_ok(), true)
     }
     #[test]
-    fn struct_with_impl_using_mutable_self() {
+    fn unsigned_map_keys() {
         let mut lang = Lang::new(Some(
             "
-        struct TestStruct {
-            i: i32,
-        }
-
-        impl TestStruct {
-            fn hello(self: TestStruct, other: TestStruct) -> () {
-                self.i = self.i + other.i;
-            }
-        }
-
-        let instance: TestStruct = TestStruct();
-        let other: TestStruct = TestStruct();
-        other.i = 100;
-        instance.i = 100;
-        instance.hello(other);
-        assert(instance.i == 200);
+        let counts: Map<u8, i32> = [:];
+        counts[1u8] = 3;
+        assert(counts[1u8] == 3);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -357,45 +10447,44 @@ mod tests {
         assert_eq!(result.is_ok(), true)
     }
     #[test]
-    fn struct_with_impl_using_self() {
+    fn unsigned_overflow_failure() {
         let mut lang = Lang::new(Some(
             "
-        struct TestStruct {
-            i: i32,
-        }
-
-        impl TestStruct {
-            fn hello() -> () {
-                print self.i;
-            }
-        }
-
-        let instance: TestStruct = TestStruct();
-        instance.i = 100;
-        assert(instance.i == 100);
-        instance.hello();
+        let a: u8 = 255;
+        let b = a + 1u8;
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
         }
-        assert_eq!(result.is_ok(), true)
+        assert_eq!(result.is_ok(), false)
     }
     #[test]
-    fn struct_with_method_call_failure() {
+    fn unsigned_underflow_failure() {
         let mut lang = Lang::new(Some(
             "
-        struct TestStruct {
-        }
-
-        impl TestStruct {
+        let a: u32 = 0;
+        let b = a - 1u32;
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
         }
-
-        let instance: TestStruct = TestStruct();
-        instance.hello();
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
+    fn unterminated_block_comment_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        /* never /* closed */
+        let a: i32 = 1;
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -417,6 +10506,7 @@ mod tests {
         assert(b == false);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -432,6 +10522,7 @@ mod tests {
         assert(a == b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -447,6 +10538,7 @@ mod tests {
         assert(a != b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -462,6 +10554,7 @@ mod tests {
         assert(a == b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -477,6 +10570,7 @@ mod tests {
         assert(a != b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -484,6 +10578,20 @@ mod tests {
         assert_eq!(result.is_ok(), true)
     }
     #[test]
+    fn char_empty_literal_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: char = '';
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
     fn char_equal() {
         let mut lang = Lang::new(Some(
             "
@@ -492,6 +10600,7 @@ mod tests {
         assert(a == b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -507,6 +10616,7 @@ mod tests {
         assert(a > b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -522,6 +10632,7 @@ mod tests {
         assert(a >= b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -537,6 +10648,7 @@ mod tests {
         assert(a < b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -552,6 +10664,7 @@ mod tests {
         assert(a <= b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -559,6 +10672,20 @@ mod tests {
         assert_eq!(result.is_ok(), true)
     }
     #[test]
+    fn char_literal_of_several_characters_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: char = 'ab';
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
     fn char_not_equal() {
         let mut lang = Lang::new(Some(
             "
@@ -567,6 +10694,7 @@ mod tests {
         assert(a != b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -574,8 +10702,23 @@ mod tests {
         assert_eq!(result.is_ok(), true)
     }
     #[test]
+    fn expression_missing_at_the_end_of_the_source_failure() {
+        let mut lang = Lang::new(Some(
+            "
+        let a: i32 = 1 +
+        ",
+        ));
+        lang.set_backend(Backend::Vm);
+        let result = lang.run();
+        if let Err(ref error) = result {
+            println!("{}", error);
+        }
+        assert_eq!(result.is_ok(), false)
+    }
+    #[test]
     fn f64_variable_declaration() {
         let mut lang = Lang::new(Some("let i: f64;"));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -585,6 +10728,7 @@ mod tests {
     #[test]
     fn f64_variable_declaration_and_assignment() {
         let mut lang = Lang::new(Some("let i: f64 = 0.00;"));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -599,6 +10743,7 @@ mod tests {
             i = 100.00;
             assert(i == 100.00);",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -611,6 +10756,7 @@ mod tests {
             "let i: f64 = 0;
         i = 100;",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -626,6 +10772,7 @@ mod tests {
         assert(a == b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -641,6 +10788,7 @@ mod tests {
         assert(a > b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -656,6 +10804,7 @@ mod tests {
         assert(a >= b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -671,6 +10820,7 @@ mod tests {
         assert(a < b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -686,6 +10836,7 @@ mod tests {
         assert(a <= b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -701,6 +10852,7 @@ mod tests {
         assert(a != b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -710,6 +10862,7 @@ mod tests {
     #[test]
     fn i64_variable_declaration() {
         let mut lang = Lang::new(Some("let i: i64;"));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -722,6 +10875,7 @@ mod tests {
             "let i: i64 = 0;
         assert(i == 0);",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -736,6 +10890,7 @@ mod tests {
             i = 100;
             assert(i == 100);",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -748,6 +10903,7 @@ mod tests {
             "let i: i64 = 0;
         i = 100.00;",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -763,6 +10919,7 @@ mod tests {
         assert(a == b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -778,6 +10935,7 @@ mod tests {
         assert(a > b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -793,6 +10951,7 @@ mod tests {
         assert(a >= b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -808,6 +10967,7 @@ mod tests {
         assert(a < b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -823,6 +10983,7 @@ mod tests {
         assert(a <= b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -838,6 +10999,7 @@ mod tests {
         assert(a != b);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -861,6 +11023,7 @@ mod tests {
         assert(instance.b == true);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);
@@ -884,6 +11047,7 @@ mod tests {
         assert(instance.b != false);
         ",
         ));
+        lang.set_backend(Backend::Vm);
         let result = lang.run();
         if let Err(ref error) = result {
             println!("{}", error);